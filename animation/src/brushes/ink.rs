@@ -30,7 +30,10 @@ pub struct InkBrush {
     max_width: f32,
 
     // Distance to scale up at the start of the brush stroke
-    scale_up_distance: f32
+    scale_up_distance: f32,
+
+    /// How the inputs from the drawing device affect the brush stroke
+    dynamics: BrushDynamics
 }
 
 impl InkBrush {
@@ -49,9 +52,35 @@ impl InkBrush {
             blend_mode,
             min_width:          definition.min_width,
             max_width:          definition.max_width,
-            scale_up_distance:  definition.scale_up_distance
+            scale_up_distance:  definition.scale_up_distance,
+            dynamics:           definition.dynamics.clone()
         }
     }
+
+    ///
+    /// Generates the drawing instructions for a single section of a brush stroke
+    ///
+    fn section_drawing(color: Color, upper_curves: Vec<bezier::Curve<Coord2>>, lower_curves: Vec<bezier::Curve<Coord2>>) -> Vec<Draw> {
+        if upper_curves.len() == 0 || lower_curves.len() == 0 {
+            return vec![];
+        }
+
+        let Coord2(start_x, start_y)    = upper_curves[0].start_point();
+        let Coord2(end_x, end_y)        = lower_curves[lower_curves.len()-1].end_point();
+
+        let mut drawing = vec![
+            Draw::FillColor(color),
+            Draw::NewPath,
+            Draw::Move(start_x as f32, start_y as f32)
+        ];
+
+        drawing.extend(upper_curves.iter().map(|curve_section| Draw::from(curve_section)));
+        drawing.push(Draw::Line(end_x as f32, end_y as f32));
+        drawing.extend(lower_curves.iter().rev().map(|curve_section| Draw::from(&curve_section.reverse::<bezier::Curve<_>>())));
+        drawing.push(Draw::Fill);
+
+        drawing
+    }
 }
 
 ///
@@ -61,7 +90,9 @@ impl InkBrush {
 struct InkCoord {
     x: f64,
     y: f64,
-    pressure: f64
+    pressure: f64,
+    opacity: f64,
+    color_mix: f64
 }
 
 impl InkCoord {
@@ -75,12 +106,19 @@ impl InkCoord {
     }
 }
 
-impl<'a> From<&'a RawPoint> for InkCoord {
-    fn from(src: &'a RawPoint) -> Self {
+impl InkCoord {
+    ///
+    /// Creates an ink coordinate from a raw point, using the brush dynamics to work out the width, opacity and colour
+    ///
+    /// The 'pressure' of an ink coordinate is the width that the dynamics generate for the point
+    ///
+    pub fn from_raw_point(src: &RawPoint, inputs: &DynamicsInputs, dynamics: &BrushDynamics) -> Self {
         Self {
-            x: src.position.0 as f64,
-            y: src.position.1 as f64,
-            pressure: (src.pressure as f64)*INK_PRESSURE_SCALE
+            x:          src.position.0 as f64,
+            y:          src.position.1 as f64,
+            pressure:   (dynamics.width_for(inputs) as f64)*INK_PRESSURE_SCALE,
+            opacity:    (dynamics.opacity_for(inputs) as f64)*INK_PRESSURE_SCALE,
+            color_mix:  (dynamics.color_mix_for(inputs) as f64)*INK_PRESSURE_SCALE
         }
     }
 }
@@ -88,9 +126,11 @@ impl<'a> From<&'a RawPoint> for InkCoord {
 impl<'a> From<&'a BrushPoint> for InkCoord {
     fn from(src: &'a BrushPoint) -> Self {
         Self {
-            x: src.position.0 as f64,
-            y: src.position.1 as f64,
-            pressure: (src.width as f64)*INK_PRESSURE_SCALE
+            x:          src.position.0 as f64,
+            y:          src.position.1 as f64,
+            pressure:   (src.width as f64)*INK_PRESSURE_SCALE,
+            opacity:    (src.opacity as f64)*INK_PRESSURE_SCALE,
+            color_mix:  (src.color_mix as f64)*INK_PRESSURE_SCALE
         }
     }
 }
@@ -103,7 +143,9 @@ impl Add<InkCoord> for InkCoord {
         Self {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
-            pressure: self.pressure + rhs.pressure
+            pressure: self.pressure + rhs.pressure,
+            opacity: self.opacity + rhs.opacity,
+            color_mix: self.color_mix + rhs.color_mix
         }
     }
 }
@@ -116,7 +158,9 @@ impl Sub<InkCoord> for InkCoord {
         Self {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
-            pressure: self.pressure - rhs.pressure
+            pressure: self.pressure - rhs.pressure,
+            opacity: self.opacity - rhs.opacity,
+            color_mix: self.color_mix - rhs.color_mix
         }
     }
 }
@@ -129,7 +173,9 @@ impl Mul<f64> for InkCoord {
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
-            pressure: self.pressure * rhs
+            pressure: self.pressure * rhs,
+            opacity: self.opacity * rhs,
+            color_mix: self.color_mix * rhs
         }
     }
 }
@@ -137,16 +183,16 @@ impl Mul<f64> for InkCoord {
 impl Coordinate for InkCoord {
     #[inline]
     fn from_components(components: &[f64]) -> Self {
-        Self { x: components[0], y: components[1], pressure: components[2] }
+        Self { x: components[0], y: components[1], pressure: components[2], opacity: components[3], color_mix: components[4] }
     }
 
     #[inline]
     fn origin() -> Self {
-        Self { x: 0.0, y: 0.0, pressure: 0.0 }
+        Self { x: 0.0, y: 0.0, pressure: 0.0, opacity: 0.0, color_mix: 0.0 }
     }
 
     #[inline]
-    fn len() -> usize { 3 }
+    fn len() -> usize { 5 }
 
     #[inline]
     fn get(&self, index: usize) -> f64 {
@@ -154,7 +200,9 @@ impl Coordinate for InkCoord {
             0 => self.x,
             1 => self.y,
             2 => self.pressure,
-            _ => panic!("InkCoord only has three components")
+            3 => self.opacity,
+            4 => self.color_mix,
+            _ => panic!("InkCoord only has five components")
         }
     }
 
@@ -162,7 +210,9 @@ impl Coordinate for InkCoord {
         Self {
             x: f64::from_biggest_components(p1.x, p2.x),
             y: f64::from_biggest_components(p1.y, p2.y),
-            pressure: f64::from_biggest_components(p1.pressure, p2.pressure)
+            pressure: f64::from_biggest_components(p1.pressure, p2.pressure),
+            opacity: f64::from_biggest_components(p1.opacity, p2.opacity),
+            color_mix: f64::from_biggest_components(p1.color_mix, p2.color_mix)
         }
     }
 
@@ -170,7 +220,9 @@ impl Coordinate for InkCoord {
         Self {
             x: f64::from_smallest_components(p1.x, p2.x),
             y: f64::from_smallest_components(p1.y, p2.y),
-            pressure: f64::from_smallest_components(p1.pressure, p2.pressure)
+            pressure: f64::from_smallest_components(p1.pressure, p2.pressure),
+            opacity: f64::from_smallest_components(p1.opacity, p2.opacity),
+            color_mix: f64::from_smallest_components(p1.color_mix, p2.color_mix)
         }
     }
 
@@ -179,13 +231,15 @@ impl Coordinate for InkCoord {
         let dist_x = target.x-self.x;
         let dist_y = target.y-self.y;
         let dist_p = target.pressure-self.pressure;
+        let dist_o = target.opacity-self.opacity;
+        let dist_c = target.color_mix-self.color_mix;
 
-        f64::sqrt(dist_x*dist_x + dist_y*dist_y + dist_p*dist_p)
+        f64::sqrt(dist_x*dist_x + dist_y*dist_y + dist_p*dist_p + dist_o*dist_o + dist_c*dist_c)
     }
 
     #[inline]
     fn dot(&self, target: &Self) -> f64 {
-        self.x*target.x + self.y*target.y + self.pressure*target.pressure
+        self.x*target.x + self.y*target.y + self.pressure*target.pressure + self.opacity*target.opacity + self.color_mix*target.color_mix
     }
 }
 
@@ -205,11 +259,11 @@ impl InkCurve {
     ///
     pub fn from_brush_points(last_point: &BrushPoint, next_point: &BrushPoint) -> Self {
         Self {
-            start_point:    InkCoord { x: last_point.position.0 as f64, y: last_point.position.1 as f64, pressure: last_point.width as f64 },
-            end_point:      InkCoord { x: next_point.position.0 as f64, y: next_point.position.1 as f64, pressure: next_point.width as f64 },
+            start_point:    InkCoord { x: last_point.position.0 as f64, y: last_point.position.1 as f64, pressure: last_point.width as f64, opacity: last_point.opacity as f64, color_mix: last_point.color_mix as f64 },
+            end_point:      InkCoord { x: next_point.position.0 as f64, y: next_point.position.1 as f64, pressure: next_point.width as f64, opacity: next_point.opacity as f64, color_mix: next_point.color_mix as f64 },
             control_points: (
-                InkCoord { x: next_point.cp1.0 as f64, y: next_point.cp1.1 as f64, pressure: next_point.width as f64 },
-                InkCoord { x: next_point.cp2.0 as f64, y: next_point.cp2.1 as f64, pressure: next_point.width as f64 }
+                InkCoord { x: next_point.cp1.0 as f64, y: next_point.cp1.1 as f64, pressure: next_point.width as f64, opacity: next_point.opacity as f64, color_mix: next_point.color_mix as f64 },
+                InkCoord { x: next_point.cp2.0 as f64, y: next_point.cp2.1 as f64, pressure: next_point.width as f64, opacity: next_point.opacity as f64, color_mix: next_point.color_mix as f64 }
             )
         }
     }
@@ -274,8 +328,9 @@ impl Brush for InkBrush {
             return vec![];
         }
 
-        // Convert points to ink points
-        let ink_points: Vec<_> = points.iter().map(|point| InkCoord::from(point)).collect();
+        // Convert points to ink points (the dynamics decide the width, opacity and colour of each point)
+        let inputs              = self.dynamics.inputs_for_raw_points(points);
+        let ink_points: Vec<_>  = points.iter().zip(inputs.iter()).map(|(point, inputs)| InkCoord::from_raw_point(point, inputs, &self.dynamics)).collect();
 
         // Average points that are very close together so we don't overdo
         // the curve fitting
@@ -360,7 +415,9 @@ impl Brush for InkBrush {
                 position:   (start.x as f32, start.y as f32),
                 cp1:        (0.0, 0.0),
                 cp2:        (0.0, 0.0),
-                width:      (start.pressure/INK_PRESSURE_SCALE) as f32
+                width:      (start.pressure/INK_PRESSURE_SCALE) as f32,
+                opacity:    (start.opacity/INK_PRESSURE_SCALE).max(0.0).min(1.0) as f32,
+                color_mix:  (start.color_mix/INK_PRESSURE_SCALE).max(0.0).min(1.0) as f32
             });
 
             // Convert the remaining curve segments
@@ -372,7 +429,9 @@ impl Brush for InkBrush {
                     position:   (end.x as f32, end.y as f32),
                     cp1:        (cp1.x as f32, cp1.y as f32),
                     cp2:        (cp2.x as f32, cp2.y as f32),
-                    width:      (end.pressure/INK_PRESSURE_SCALE) as f32
+                    width:      (end.pressure/INK_PRESSURE_SCALE) as f32,
+                    opacity:    (end.opacity/INK_PRESSURE_SCALE).max(0.0).min(1.0) as f32,
+                    color_mix:  (end.color_mix/INK_PRESSURE_SCALE).max(0.0).min(1.0) as f32
                 });
             }
         }
@@ -418,6 +477,27 @@ impl Brush for InkBrush {
             .map(|ink_curve| ink_curve.to_offset_curves((self.min_width*size_ratio) as f64, (self.max_width*size_ratio) as f64))
             .unzip();
 
+        // Strokes where the opacity or the colour changes along their length are drawn one section at a time
+        if points.iter().any(|point| point.opacity < 1.0 || point.color_mix > 0.0) {
+            let brush_color = properties.color;
+            let opacity     = properties.opacity;
+
+            let sections    = upper_curves.into_iter()
+                .zip(lower_curves.into_iter())
+                .enumerate()
+                .flat_map(move |(idx, (upper_curves, lower_curves))| {
+                    let (start, end)        = (&points[idx], &points[idx+1]);
+                    let section_opacity     = (start.opacity + end.opacity) / 2.0;
+                    let section_color_mix   = (start.color_mix + end.color_mix) / 2.0;
+                    let section_color       = self.dynamics.mix_color(brush_color, section_color_mix).with_alpha(opacity * section_opacity);
+
+                    Self::section_drawing(section_color, upper_curves, lower_curves)
+                })
+                .chain(iter::once(Draw::FillColor(brush_color.with_alpha(opacity))));    // Restore the fill colour set up by prepare_to_render
+
+            return Box::new(sections);
+        }
+
         // Upper portion
         let Coord2(x, y) = upper_curves[0][0].start_point();
         let preamble = vec![
@@ -463,7 +543,8 @@ impl Brush for InkBrush {
         let definition = BrushDefinition::Ink(InkDefinition {
             min_width:          self.min_width,
             max_width:          self.max_width,
-            scale_up_distance:  self.scale_up_distance
+            scale_up_distance:  self.scale_up_distance,
+            dynamics:           self.dynamics.clone()
         });
 
         let drawing_style = match self.blend_mode {
@@ -0,0 +1,53 @@
+use super::stream_animation_core::*;
+use crate::storage::storage_api::*;
+use crate::traits::*;
+
+use futures::prelude::*;
+
+impl StreamAnimationCore {
+    ///
+    /// Performs an annotation edit on this animation
+    ///
+    pub fn annotation_edit<'a>(&'a mut self, annotation_id: ElementId, annotation_edit: &'a AnnotationEdit) -> impl 'a+Future<Output=()> {
+        async move {
+            use self::AnnotationEdit::*;
+
+            // Annotations can only be edited once they have an ID
+            let annotation_id = match annotation_id.id() {
+                Some(id)    => id,
+                None        => { return; }
+            };
+
+            match annotation_edit {
+                Create(annotation)      => { self.request_one(StorageCommand::WriteAnnotation(annotation_id, annotation.layer_id, annotation.serialize_to_string())).await; }
+                Delete                  => { self.request_one(StorageCommand::DeleteAnnotation(annotation_id)).await; }
+                SetResolved(resolved)   => { self.update_annotation(annotation_id, |annotation| annotation.resolved = *resolved).await; }
+            }
+        }
+    }
+
+    ///
+    /// Reads the annotation with the specified ID
+    ///
+    pub fn read_annotation<'a>(&'a mut self, annotation_id: i64) -> impl 'a+Future<Output=Option<Annotation>> {
+        async move {
+            match self.request_one(StorageCommand::ReadAnnotation(annotation_id)).await {
+                Some(StorageResponse::Annotation(_, _, annotation)) => Annotation::deserialize(&mut annotation.chars()),
+                _                                                   => None
+            }
+        }
+    }
+
+    ///
+    /// Updates an existing annotation (has no effect if the annotation doesn't exist)
+    ///
+    fn update_annotation<'a, UpdateFn>(&'a mut self, annotation_id: i64, update_fn: UpdateFn) -> impl 'a+Future<Output=()>
+    where UpdateFn: 'a+Send+FnOnce(&mut Annotation) {
+        async move {
+            if let Some(mut annotation) = self.read_annotation(annotation_id).await {
+                update_fn(&mut annotation);
+                self.request_one(StorageCommand::WriteAnnotation(annotation_id, annotation.layer_id, annotation.serialize_to_string())).await;
+            }
+        }
+    }
+}
@@ -42,14 +42,31 @@ impl StreamAnimationCore {
             use self::ElementEdit::*;
             use self::ElementUpdate::*;
 
-            let element_ids = element_ids.iter().map(|elem| elem.id()).flatten().collect();
+            let element_ids = element_ids.iter().map(|elem| elem.id()).flatten().collect::<Vec<_>>();
+
+            // Elements on locked layers can't be edited
+            let mut unlocked_ids = vec![];
+            for element_id in element_ids {
+                if !self.element_is_locked(element_id).await {
+                    unlocked_ids.push(element_id);
+                }
+            }
+            let element_ids = unlocked_ids;
 
             match element_edit {
                 AddAttachment(attach_id)            => { self.update_elements(element_ids, |_wrapper| { AddAttachments(vec![*attach_id]) }).await; }
                 RemoveAttachment(attach_id)         => { self.update_elements(element_ids, |_wrapper| { RemoveAttachments(vec![*attach_id]) }).await; }
                 SetPath(new_path)                   => { self.update_elements(element_ids, |mut wrapper| { wrapper.element = wrapper.element.with_path_components(new_path.iter().cloned()); ChangeWrapper(wrapper) }).await; }
                 Order(ordering)                     => { self.order_elements(element_ids, *ordering).await; }
+                SetText(text)                       => { self.update_elements(element_ids, |wrapper| Self::update_text(wrapper, |text_element| text_element.with_text(Arc::new(text.clone())))).await; }
+                SetTextStyle(style)                 => { self.update_elements(element_ids, |wrapper| Self::update_text(wrapper, |text_element| text_element.with_style(Arc::new(style.clone())))).await; }
+                InsertPathNode(index, pos)          => { self.update_elements(element_ids, |wrapper| Self::update_path(wrapper, |path| path.with_node_inserted(*index, *pos))).await; }
+                DeletePathNode(index)               => { self.update_elements(element_ids, |wrapper| Self::update_path(wrapper, |path| path.with_node_deleted(*index))).await; }
+                SetPathNodeType(index, node_type)   => { self.update_elements(element_ids, |wrapper| Self::update_path(wrapper, |path| path.with_node_type(*index, *node_type))).await; }
+                BreakPath(index)                    => { self.update_elements(element_ids, |wrapper| Self::update_path(wrapper, |path| path.with_break_at_node(*index))).await; }
+                JoinPathNodes(first, second)        => { self.update_elements(element_ids, |wrapper| Self::update_path(wrapper, |path| path.with_nodes_joined(*first, *second))).await; }
                 Group(group_id, group_type)         => { self.group_elements(element_ids, *group_id, *group_type).await; }
+                CombinePaths(new_id, operation)     => { self.combine_element_paths(element_ids, *new_id, *operation).await; }
                 
                 Ungroup                             => { 
                     for id in element_ids {
@@ -158,6 +175,38 @@ impl StreamAnimationCore {
         }
     }
 
+    ///
+    /// Updates an element if it's a text element (other types of element are left unchanged)
+    ///
+    fn update_text<UpdateFn: FnOnce(&TextElement) -> TextElement>(wrapper: ElementWrapper, update_text: UpdateFn) -> ElementUpdate {
+        let mut wrapper = wrapper;
+
+        match &wrapper.element {
+            Vector::Text(text_element)  => {
+                wrapper.element = Vector::Text(update_text(text_element));
+                ElementUpdate::ChangeWrapper(wrapper)
+            }
+
+            _                           => ElementUpdate::Other(vec![])
+        }
+    }
+
+    ///
+    /// Updates an element if it's a path element (other types of element are left unchanged)
+    ///
+    fn update_path<UpdateFn: FnOnce(&Path) -> Path>(wrapper: ElementWrapper, update_path: UpdateFn) -> ElementUpdate {
+        let mut wrapper = wrapper;
+
+        match &wrapper.element {
+            Vector::Path(path_element)  => {
+                wrapper.element = Vector::Path(PathElement::new(path_element.id(), update_path(path_element.path()), path_element.brush(), path_element.properties()));
+                ElementUpdate::ChangeWrapper(wrapper)
+            }
+
+            _                           => ElementUpdate::Other(vec![])
+        }
+    }
+
     ///
     /// Given an attachment ID, removes it from the attachments (and attached_to) items for an element
     ///
@@ -223,6 +272,11 @@ impl StreamAnimationCore {
                 }
             }
 
+            // Images are stored separately from the elements that display them
+            for wrapper in elements.iter_mut() {
+                wrapper.element = self.load_images(wrapper.element.clone()).await;
+            }
+
             elements
         }
     }
@@ -0,0 +1,80 @@
+use super::stream_animation_core::*;
+use crate::storage::storage_api::*;
+use crate::traits::*;
+
+use futures::prelude::*;
+use futures::future::{BoxFuture};
+
+use std::sync::*;
+
+impl StreamAnimationCore {
+    ///
+    /// Stores a reference image so it can be placed on keyframes
+    ///
+    pub fn add_image<'a>(&'a mut self, image_id: ElementId, image: &'a Arc<ReferenceImage>) -> impl 'a+Future<Output=()> {
+        async move {
+            // Images can only be added once they have an ID
+            let image_id = match image_id.id() {
+                Some(id)    => id,
+                None        => { return; }
+            };
+
+            let mut serialized = String::new();
+            image.serialize(&mut serialized);
+
+            self.request_one(StorageCommand::WriteImage(image_id, serialized)).await;
+            self.images.insert(image_id, Arc::clone(image));
+        }
+    }
+
+    ///
+    /// Retrieves a stored reference image
+    ///
+    /// Images never change once they've been stored, so each one is only read from storage and decoded once
+    ///
+    pub fn image<'a>(&'a mut self, image_id: ElementId) -> impl 'a+Future<Output=Option<Arc<ReferenceImage>>> {
+        async move {
+            let image_id = image_id.id()?;
+
+            if let Some(image) = self.images.get(&image_id) {
+                return Some(Arc::clone(image));
+            }
+
+            let image = match self.request_one(StorageCommand::ReadImage(image_id)).await {
+                Some(StorageResponse::Image(_, image))  => Arc::new(ReferenceImage::deserialize(&mut image.chars())?),
+                _                                       => { return None; }
+            };
+
+            self.images.insert(image_id, Arc::clone(&image));
+            Some(image)
+        }
+    }
+
+    ///
+    /// Loads the images displayed by an element read from storage (and by any elements that it contains)
+    ///
+    pub fn load_images<'a>(&'a mut self, element: Vector) -> BoxFuture<'a, Vector> {
+        async move {
+            match element {
+                Vector::Image(image_element)    => {
+                    match self.image(image_element.image_id()).await {
+                        Some(image) => Vector::Image(image_element.with_image(image)),
+                        None        => Vector::Image(image_element)
+                    }
+                }
+
+                Vector::Group(group)            => {
+                    let mut grouped_elements = vec![];
+
+                    for grouped_element in group.elements().cloned().collect::<Vec<_>>() {
+                        grouped_elements.push(self.load_images(grouped_element).await);
+                    }
+
+                    Vector::Group(group.with_elements(grouped_elements))
+                }
+
+                other                           => other
+            }
+        }.boxed()
+    }
+}
@@ -5,6 +5,8 @@ use crate::storage::layer_properties::*;
 
 use futures::prelude::*;
 
+use std::sync::*;
+
 impl StreamAnimationCore {
     ///
     /// Performs a layer edit on this animation
@@ -14,12 +16,23 @@ impl StreamAnimationCore {
 
         async move {
             match layer_edit {
-                Paint(when, paint_edit)     => { self.paint_edit(layer_id, *when, paint_edit).await }
-                Path(when, path_edit)       => { self.path_edit(layer_id, *when, path_edit).await }
-                AddKeyFrame(when)           => { self.add_key_frame(layer_id, *when).await }
-                RemoveKeyFrame(when)        => { self.remove_key_frame(layer_id, *when).await }
-                SetName(new_name)           => { self.set_layer_name(layer_id, new_name).await }
-                SetOrdering(ordering)       => { self.set_layer_ordering(layer_id, *ordering).await }
+                Paint(when, paint_edit)                 => { if !self.layer_is_locked(layer_id).await { self.paint_edit(layer_id, *when, paint_edit).await } }
+                Path(when, path_edit)                   => { if !self.layer_is_locked(layer_id).await { self.path_edit(layer_id, *when, path_edit).await } }
+                AddKeyFrame(when)                       => { self.add_key_frame(layer_id, *when).await }
+                RemoveKeyFrame(when)                    => { self.remove_key_frame(layer_id, *when).await }
+                MoveKeyFrame(from, to)                  => { self.move_key_frame(layer_id, *from, *to).await }
+                DuplicateKeyFrame(from, to, new_ids)    => { self.duplicate_key_frame(layer_id, *from, *to, new_ids).await }
+                RetimeKeyFrames(from, to)               => { self.retime_key_frames(layer_id, from.clone(), to.clone()).await }
+                BakeInbetweens(when, every, new_ids)    => { self.bake_inbetweens(layer_id, when.clone(), *every, new_ids).await }
+                SetName(new_name)                       => { self.set_layer_name(layer_id, new_name).await }
+                SetOrdering(ordering)                   => { self.set_layer_ordering(layer_id, *ordering).await }
+                SetVisibility(visible)                  => { self.update_layer_properties(layer_id, move |properties| properties.visible = *visible).await }
+                SetLocked(locked)                       => { self.update_layer_properties(layer_id, move |properties| properties.locked = *locked).await }
+                SetOpacity(opacity)                     => { self.update_layer_properties(layer_id, move |properties| properties.opacity = opacity.max(0.0).min(1.0)).await }
+                SetBlendMode(blend_mode)                => { self.update_layer_properties(layer_id, move |properties| properties.blend_mode = *blend_mode).await }
+                SetInbetweenMode(mode)                  => { self.update_layer_properties(layer_id, move |properties| properties.inbetween_mode = *mode).await }
+                ImportAudio(clip_id)                    => { self.import_layer_audio(layer_id, *clip_id).await }
+                RemoveAudio                             => { self.remove_layer_audio(layer_id).await }
             }
         }
     }
@@ -121,27 +134,119 @@ impl StreamAnimationCore {
     }
 
     ///
-    /// Sets the name of a layer
+    /// Reads the properties for a layer
     ///
-    pub fn set_layer_name<'a>(&'a mut self, layer_id: u64, name: &'a str) -> impl 'a+Future<Output=()> { 
+    pub fn layer_properties<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=LayerProperties> {
         async move {
-            // Read the current properties for this layer
-            let mut properties = match self.request_one(StorageCommand::ReadLayerProperties(layer_id)).await {
+            match self.request_one(StorageCommand::ReadLayerProperties(layer_id)).await {
                 Some(StorageResponse::LayerProperties(_, properties)) => {
                     LayerProperties::deserialize(&mut properties.chars())
                         .unwrap_or_else(|| LayerProperties::default())
                 }
 
                 _ => LayerProperties::default()
+            }
+        }
+    }
+
+    ///
+    /// Returns true if the specified layer is locked against edits
+    ///
+    pub fn layer_is_locked<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=bool> {
+        async move {
+            self.layer_properties(layer_id).await.locked
+        }
+    }
+
+    ///
+    /// Returns true if the specified element is attached to a layer that is locked against edits
+    ///
+    pub fn element_is_locked<'a>(&'a mut self, element_id: i64) -> impl 'a+Future<Output=bool> {
+        async move {
+            let attachments = match self.request_one(StorageCommand::ReadElementAttachments(element_id)).await {
+                Some(StorageResponse::ElementAttachments(_, attachments))   => attachments,
+                _                                                           => vec![]
             };
 
-            // Update the name
-            properties.name = name.to_string();
+            for (layer_id, _when) in attachments {
+                if self.layer_is_locked(layer_id).await {
+                    return true;
+                }
+            }
+
+            false
+        }
+    }
+
+    ///
+    /// Reads the properties for a layer, updates them and writes them back to storage
+    ///
+    pub fn update_layer_properties<'a, UpdateFn>(&'a mut self, layer_id: u64, update_fn: UpdateFn) -> impl 'a+Future<Output=()>
+    where UpdateFn: 'a+Send+FnOnce(&mut LayerProperties) {
+        async move {
+            // Read the current properties for this layer
+            let mut properties = self.layer_properties(layer_id).await;
+
+            // Update them
+            update_fn(&mut properties);
 
             // Save back to the storage
             let mut serialized = String::new();
             properties.serialize(&mut serialized);
             self.request_one(StorageCommand::WriteLayerProperties(layer_id, serialized)).await;
-        } 
+        }
+    }
+
+    ///
+    /// Sets the name of a layer
+    ///
+    pub fn set_layer_name<'a>(&'a mut self, layer_id: u64, name: &'a str) -> impl 'a+Future<Output=()> { 
+        self.update_layer_properties(layer_id, move |properties| properties.name = name.to_string())
+    }
+
+    ///
+    /// Stores an audio clip so it can be attached to layers
+    ///
+    pub fn add_audio_clip<'a>(&'a mut self, clip_id: ElementId, clip: &'a Arc<AudioClip>) -> impl 'a+Future<Output=()> {
+        async move {
+            // Audio clips can only be added once they have an ID
+            let clip_id = match clip_id.id() {
+                Some(id)    => id,
+                None        => { return; }
+            };
+
+            let mut serialized = String::new();
+            clip.serialize(&mut serialized);
+
+            self.request_one(StorageCommand::WriteAudioClip(clip_id, serialized)).await;
+        }
+    }
+
+    ///
+    /// Attaches a stored audio clip to a layer
+    ///
+    pub fn import_layer_audio<'a>(&'a mut self, layer_id: u64, clip_id: ElementId) -> impl 'a+Future<Output=()> {
+        async move {
+            let clip_id = match clip_id.id() {
+                Some(id)    => id,
+                None        => { return; }
+            };
+
+            // The storage won't attach a clip that hasn't been added
+            match self.request_one(StorageCommand::WriteLayerAudio(layer_id, clip_id)).await {
+                Some(StorageResponse::Updated)  => { self.update_layer_properties(layer_id, |properties| properties.has_audio = true).await }
+                _                               => { }
+            }
+        }
+    }
+
+    ///
+    /// Removes the audio clip from a layer
+    ///
+    pub fn remove_layer_audio<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=()> {
+        async move {
+            self.request_one(StorageCommand::DeleteLayerAudio(layer_id)).await;
+            self.update_layer_properties(layer_id, |properties| properties.has_audio = false).await;
+        }
     }
 }
@@ -23,7 +23,7 @@ impl StreamAnimationCore {
             };
 
             match motion_edit {
-                Create                  => {
+                Create                      => {
                     // Create the motion element
                     let motion          = Motion::None;
                     let motion          = MotionElement::new(ElementId::Assigned(motion_id), motion);
@@ -33,11 +33,12 @@ impl StreamAnimationCore {
                     // Write
                     self.request_one(StorageCommand::WriteElement(motion_id, motion.serialize_to_string())).await;
                 }
-                Delete                  => { self.request_one(StorageCommand::DeleteElement(motion_id)).await; }
+                Delete                      => { self.request_one(StorageCommand::DeleteElement(motion_id)).await; }
 
-                SetType(motion_type)    => { self.update_motion(motion_id, |mut motion| { motion.set_type(*motion_type); motion }).await; }
-                SetOrigin(x, y)         => { self.update_motion(motion_id, |mut motion| { motion.set_origin((*x, *y)); motion }).await; }
-                SetPath(time_curve)     => { self.update_motion(motion_id, |mut motion| { motion.set_path(time_curve.clone()); motion }).await; }
+                SetType(motion_type)        => { self.update_motion(motion_id, |mut motion| { motion.set_type(*motion_type); motion }).await; }
+                SetOrigin(x, y)             => { self.update_motion(motion_id, |mut motion| { motion.set_origin((*x, *y)); motion }).await; }
+                SetPath(time_curve)         => { self.update_motion(motion_id, |mut motion| { motion.set_path(time_curve.clone()); motion }).await; }
+                SetEasing(section, easing)  => { self.update_motion(motion_id, |mut motion| { motion.set_easing(*section, *easing); motion }).await; }
             }
         }
     }
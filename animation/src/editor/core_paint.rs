@@ -59,11 +59,43 @@ impl StreamAnimationCore {
                     (element_id, Some(wrapper))
                 }
 
+                CreateBrushStroke(element_id, points)   => {
+                    // Create a brush stroke element from points that have already been processed by a brush
+                    let brush_element   = BrushElement::new(*element_id, Arc::clone(points));
+                    let element         = Vector::BrushStroke(brush_element);
+                    let element_id      = element_id.id().unwrap_or(0);
+                    let mut wrapper     = ElementWrapper::attached_with_element(element, when);
+
+                    wrapper.attachments = vec![self.brush_defn, self.brush_props].into_iter().flatten().collect();
+
+                    (element_id, Some(wrapper))
+                }
 
                 Fill(element_id, point, options)        => {
                     let element_id = element_id.id().unwrap_or(0);
                     (element_id, self.paint_fill(layer_id, when, ElementId::Assigned(element_id), *point, options).await)
                 }
+
+                PlaceImage(element_id, image_id, position, size, opacity) => {
+                    // The image must have been stored before it can be placed. Images don't use the brush, so they're created without any attachments
+                    let image           = self.image(*image_id).await;
+                    let image_element   = ImageElement::new(*element_id, *image_id, *position, *size, *opacity);
+                    let element         = image.map(|image| Vector::Image(image_element.with_image(image)));
+                    let element_id      = element_id.id().unwrap_or(0);
+                    let wrapper         = element.map(|element| ElementWrapper::attached_with_element(element, when));
+
+                    (element_id, wrapper)
+                }
+
+                CreateText(element_id, text, style, position) => {
+                    // Text is drawn in its own colour and font, so it doesn't use the brush either
+                    let text_element    = TextElement::new(*element_id, Arc::new(text.clone()), Arc::new(style.clone()), *position);
+                    let element         = Vector::Text(text_element);
+                    let element_id      = element_id.id().unwrap_or(0);
+                    let wrapper         = ElementWrapper::attached_with_element(element, when);
+
+                    (element_id, Some(wrapper))
+                }
             };
 
             if let Some(wrapper) = wrapper {
@@ -0,0 +1,371 @@
+use super::stream_animation_core::*;
+use crate::storage::storage_api::*;
+use crate::storage::file_properties::*;
+use crate::serializer::*;
+
+use futures::prelude::*;
+
+use std::i64;
+use std::time::{Duration};
+
+impl StreamAnimationCore {
+    ///
+    /// Sends a request to the storage layer without recording it in the undo history
+    ///
+    pub (super) fn request_unrecorded<'a>(&'a mut self, request: Vec<StorageCommand>) -> impl 'a+Future<Output=Option<Vec<StorageResponse>>> {
+        async move {
+            self.storage_requests.publish(request).await;
+            self.storage_responses.next().await
+        }
+    }
+
+    ///
+    /// Sends a single request to the storage layer without recording it in the undo history
+    ///
+    fn request_one_unrecorded<'a>(&'a mut self, request: StorageCommand) -> impl 'a+Future<Output=Option<StorageResponse>> {
+        async move {
+            self.request_unrecorded(vec![request]).await
+                .and_then(|mut result| result.pop())
+        }
+    }
+
+    ///
+    /// Sends a request to the storage layer, recording the commands needed to reverse it in the current undo step
+    ///
+    pub (super) fn request_recording_undo<'a>(&'a mut self, request: Vec<StorageCommand>) -> impl 'a+Future<Output=Option<Vec<StorageResponse>>> {
+        async move {
+            let mut responses = vec![];
+
+            // Commands are sent one at a time so that each inverse is generated from the state just before the command was run
+            for command in request {
+                let inverse = self.inverse_command(&command).await;
+
+                match self.request_unrecorded(vec![command]).await {
+                    Some(response)  => responses.extend(response),
+                    None            => { return None; }
+                }
+
+                if let Some(undo_step) = self.undo_step.as_mut() {
+                    if inverse.len() > 0 {
+                        undo_step.push(inverse);
+                    }
+                }
+            }
+
+            Some(responses)
+        }
+    }
+
+    ///
+    /// Starts recording a new undo step
+    ///
+    pub (super) fn start_undo_step(&mut self) {
+        self.undo_step = Some(vec![]);
+    }
+
+    ///
+    /// Stops recording the current undo step and returns the commands that will reverse it
+    ///
+    fn take_undo_step(&mut self) -> Vec<StorageCommand> {
+        // Each command's inverse needs to run in the opposite order to the original commands
+        self.undo_step.take()
+            .unwrap_or_else(|| vec![])
+            .into_iter()
+            .rev()
+            .flatten()
+            .collect()
+    }
+
+    ///
+    /// Stops recording the current undo step and pushes it on to the undo stack of the current undo history
+    ///
+    /// Any steps waiting to be redone are discarded, unless the step turned out not to make any changes.
+    ///
+    pub (super) fn finish_undo_step<'a>(&'a mut self) -> impl 'a+Future<Output=()> {
+        async move {
+            let undo_step = self.take_undo_step();
+
+            if undo_step.len() > 0 {
+                let mut serialized = String::new();
+                serialize_storage_commands(&mut serialized, undo_step.iter());
+
+                self.request_unrecorded(vec![
+                    StorageCommand::PushUndoStep(self.undo_history, UndoStack::Undo, serialized),
+                    StorageCommand::ClearUndoStack(self.undo_history, UndoStack::Redo)
+                ]).await;
+            }
+        }
+    }
+
+    ///
+    /// Reverses the most recent undo step in the current undo history
+    ///
+    pub fn undo<'a>(&'a mut self) -> impl 'a+Future<Output=()> {
+        async move {
+            self.replay_undo_step(UndoStack::Undo, UndoStack::Redo).await
+        }
+    }
+
+    ///
+    /// Re-applies the most recently undone step in the current undo history
+    ///
+    pub fn redo<'a>(&'a mut self) -> impl 'a+Future<Output=()> {
+        async move {
+            self.replay_undo_step(UndoStack::Redo, UndoStack::Undo).await
+        }
+    }
+
+    ///
+    /// Removes the topmost step from one stack in the current undo history, performs it and pushes the step that reverses it on to the other stack
+    ///
+    fn replay_undo_step<'a>(&'a mut self, from_stack: UndoStack, to_stack: UndoStack) -> impl 'a+Future<Output=()> {
+        async move {
+            // Fetch the step to perform
+            let undo_history    = self.undo_history;
+            let step            = match self.request_one_unrecorded(StorageCommand::PopUndoStep(undo_history, from_stack)).await {
+                Some(StorageResponse::UndoStep(step))   => step,
+                _                                       => { return; }
+            };
+
+            let commands = match deserialize_storage_commands(&mut step.chars()) {
+                Some(commands)  => commands,
+                None            => { return; }
+            };
+
+            // Perform the commands, recording how to reverse them again
+            self.start_undo_step();
+            self.request(commands).await;
+            let reverse_step = self.take_undo_step();
+
+            let mut serialized = String::new();
+            serialize_storage_commands(&mut serialized, reverse_step.iter());
+            self.request_one_unrecorded(StorageCommand::PushUndoStep(undo_history, to_stack, serialized)).await;
+
+            // The cached keyframe no longer reflects what's in storage
+            self.cached_keyframe = None;
+        }
+    }
+
+    ///
+    /// Generates the storage commands that will reverse the effect of a command on the current state of the storage
+    ///
+    fn inverse_command<'a>(&'a mut self, command: &'a StorageCommand) -> impl 'a+Future<Output=Vec<StorageCommand>> {
+        async move {
+            use self::StorageCommand::*;
+
+            match command {
+                WriteAnimationProperties(_)                 => {
+                    match self.request_one_unrecorded(ReadAnimationProperties).await {
+                        Some(StorageResponse::AnimationProperties(properties))  => vec![WriteAnimationProperties(properties)],
+                        _                                                       => {
+                            // The animation was using the default properties
+                            let mut properties = String::new();
+                            FileProperties::default().serialize(&mut properties);
+                            vec![WriteAnimationProperties(properties)]
+                        }
+                    }
+                }
+
+                WriteElement(element_id, _)                 => {
+                    match self.request_one_unrecorded(ReadElement(*element_id)).await {
+                        Some(StorageResponse::Element(_, element))  => vec![WriteElement(*element_id, element)],
+                        _                                           => vec![DeleteElement(*element_id)]
+                    }
+                }
+
+                DeleteElement(element_id)                   => {
+                    match self.request_one_unrecorded(ReadElement(*element_id)).await {
+                        Some(StorageResponse::Element(_, element))  => {
+                            let mut inverse = vec![WriteElement(*element_id, element)];
+                            inverse.extend(self.restore_attachments(*element_id).await);
+                            inverse
+                        }
+                        _                                           => vec![]
+                    }
+                }
+
+                AddLayer(layer_id, _)                       => {
+                    match self.request_one_unrecorded(ReadLayerProperties(*layer_id)).await {
+                        Some(StorageResponse::LayerProperties(_, properties))   => vec![WriteLayerProperties(*layer_id, properties)],
+                        _                                                       => vec![DeleteLayer(*layer_id)]
+                    }
+                }
+
+                WriteLayerProperties(layer_id, _)           => {
+                    match self.request_one_unrecorded(ReadLayerProperties(*layer_id)).await {
+                        Some(StorageResponse::LayerProperties(_, properties))   => vec![WriteLayerProperties(*layer_id, properties)],
+                        _                                                       => vec![]
+                    }
+                }
+
+                DeleteLayer(layer_id)                       => { self.restore_layer(*layer_id).await }
+
+                AddKeyFrame(layer_id, when)                 => {
+                    if self.keyframe_exists(*layer_id, *when).await {
+                        vec![]
+                    } else {
+                        vec![DeleteKeyFrame(*layer_id, *when)]
+                    }
+                }
+
+                DeleteKeyFrame(layer_id, when)              => {
+                    if self.keyframe_exists(*layer_id, *when).await {
+                        self.restore_keyframe(*layer_id, *when).await
+                    } else {
+                        vec![]
+                    }
+                }
+
+                MoveKeyFrame(layer_id, from, to)            => {
+                    if from != to && self.keyframe_exists(*layer_id, *from).await && !self.keyframe_exists(*layer_id, *to).await {
+                        vec![MoveKeyFrame(*layer_id, *to, *from)]
+                    } else {
+                        vec![]
+                    }
+                }
+
+                AttachElementToLayer(_, element_id, _)      |
+                DetachElementFromLayer(element_id)          => {
+                    let mut inverse = vec![DetachElementFromLayer(*element_id)];
+                    inverse.extend(self.restore_attachments(*element_id).await);
+                    inverse
+                }
+
+                WriteLayerAudio(layer_id, _)                |
+                DeleteLayerAudio(layer_id)                  => {
+                    match self.request_one_unrecorded(ReadLayerAudio(*layer_id)).await {
+                        Some(StorageResponse::LayerAudio(_, clip_id))   => vec![WriteLayerAudio(*layer_id, clip_id)],
+                        _                                               => vec![DeleteLayerAudio(*layer_id)]
+                    }
+                }
+
+                // Audio clips never change once they're written, so they're left in storage: undoing the edit that attached
+                // a clip to a layer is enough to remove it, and this keeps the audio data out of the undo history
+                WriteAudioClip(_, _)                        => vec![],
+
+                // Images are kept in storage for the same reason: undoing the edits that placed an image removes it from the keyframes
+                WriteImage(_, _)                            => vec![],
+
+                WriteAnnotation(annotation_id, _, _)        |
+                DeleteAnnotation(annotation_id)             => {
+                    match self.request_one_unrecorded(ReadAnnotation(*annotation_id)).await {
+                        Some(StorageResponse::Annotation(_, layer_id, annotation))  => vec![WriteAnnotation(*annotation_id, layer_id, annotation)],
+                        _                                                           => vec![DeleteAnnotation(*annotation_id)]
+                    }
+                }
+
+                // Cached values are regenerated on demand, so the cache only needs to be cleared again
+                WriteLayerCache(layer_id, when, key, _)     |
+                DeleteLayerCache(layer_id, when, key)       => vec![DeleteLayerCache(*layer_id, *when, key.clone())],
+
+                // Reads, the edit log and the undo stacks themselves are not part of the undo history
+                _                                           => vec![]
+            }
+        }
+    }
+
+    ///
+    /// Returns true if there's a keyframe at exactly the specified time
+    ///
+    pub (super) fn keyframe_exists<'a>(&'a mut self, layer_id: u64, when: Duration) -> impl 'a+Future<Output=bool> {
+        async move {
+            let keyframes = self.request_unrecorded(vec![StorageCommand::ReadKeyFrames(layer_id, when..(when+Duration::from_micros(1)))]).await;
+
+            keyframes.unwrap_or_else(|| vec![])
+                .into_iter()
+                .any(|response| match response {
+                    StorageResponse::KeyFrame(start, _) => start == when,
+                    _                                   => false
+                })
+        }
+    }
+
+    ///
+    /// Generates the commands to re-attach an element to the keyframes it's currently attached to
+    ///
+    fn restore_attachments<'a>(&'a mut self, element_id: i64) -> impl 'a+Future<Output=Vec<StorageCommand>> {
+        async move {
+            match self.request_one_unrecorded(StorageCommand::ReadElementAttachments(element_id)).await {
+                Some(StorageResponse::ElementAttachments(_, attachments)) => {
+                    attachments.into_iter()
+                        .map(|(layer_id, when)| StorageCommand::AttachElementToLayer(layer_id, element_id, when))
+                        .collect()
+                }
+
+                _ => vec![]
+            }
+        }
+    }
+
+    ///
+    /// Generates the commands to recreate an existing keyframe, along with the elements that are attached to it
+    ///
+    fn restore_keyframe<'a>(&'a mut self, layer_id: u64, when: Duration) -> impl 'a+Future<Output=Vec<StorageCommand>> {
+        async move {
+            let mut restore = vec![StorageCommand::AddKeyFrame(layer_id, when)];
+            let elements    = self.request_unrecorded(vec![StorageCommand::ReadElementsForKeyFrame(layer_id, when)]).await;
+
+            restore.extend(elements.unwrap_or_else(|| vec![])
+                .into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::Element(element_id, _) => Some(StorageCommand::AttachElementToLayer(layer_id, element_id, when)),
+                    _                                       => None
+                }));
+
+            restore
+        }
+    }
+
+    ///
+    /// Generates the commands to recreate an existing layer along with all of its keyframes
+    ///
+    fn restore_layer<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=Vec<StorageCommand>> {
+        async move {
+            // Nothing to restore if the layer doesn't exist
+            let properties = match self.request_one_unrecorded(StorageCommand::ReadLayerProperties(layer_id)).await {
+                Some(StorageResponse::LayerProperties(_, properties))   => properties,
+                _                                                       => { return vec![]; }
+            };
+
+            // Find all of the keyframes in the layer
+            let all_time    = Duration::from_micros(0)..Duration::from_micros(i64::max_value() as u64);
+            let keyframes   = self.request_unrecorded(vec![StorageCommand::ReadKeyFrames(layer_id, all_time)]).await;
+            let keyframes   = keyframes.unwrap_or_else(|| vec![])
+                .into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::KeyFrame(start, _) => Some(start),
+                    _                                   => None
+                })
+                .collect::<Vec<_>>();
+
+            // Recreate the layer, then each of its keyframes
+            let mut restore = vec![StorageCommand::AddLayer(layer_id, properties)];
+
+            for when in keyframes {
+                restore.extend(self.restore_keyframe(layer_id, when).await);
+            }
+
+            // Put back any audio that was attached to the layer
+            if let Some(StorageResponse::LayerAudio(_, clip_id)) = self.request_one_unrecorded(StorageCommand::ReadLayerAudio(layer_id)).await {
+                restore.push(StorageCommand::WriteLayerAudio(layer_id, clip_id));
+            }
+
+            // ... and any annotations that were left on it
+            let annotations = self.request_unrecorded(vec![StorageCommand::ReadAnnotations]).await;
+            restore.extend(annotations.unwrap_or_else(|| vec![])
+                .into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::Annotation(annotation_id, annotation_layer_id, annotation) => {
+                        if annotation_layer_id == layer_id {
+                            Some(StorageCommand::WriteAnnotation(annotation_id, layer_id, annotation))
+                        } else {
+                            None
+                        }
+                    }
+                    _                                                                           => None
+                }));
+
+            restore
+        }
+    }
+}
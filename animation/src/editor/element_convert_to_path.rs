@@ -55,6 +55,13 @@ impl StreamAnimationCore {
                         }
                     }
 
+                    // Text has no brush attachments: the path it's converted to is filled with the colour of the text instead
+                    if let Vector::Text(text_element) = &wrapper.element {
+                        let style           = text_element.style();
+                        let properties      = BrushProperties { size: 1.0, opacity: 1.0, color: style.color, fill: BrushFill::Solid };
+                        brush_properties    = BrushPropertiesElement::new(ElementId::Unassigned, properties);
+                    }
+
                     // Convert the element to a path
                     let path        = wrapper.element.to_path(&*vector_properties, PathConversion::RemoveInteriorPoints);
                     let path        = path.unwrap_or(vec![]).into_iter();
@@ -0,0 +1,229 @@
+use super::keyframe_core::*;
+use super::element_wrapper::*;
+use super::stream_animation_core::*;
+use super::pending_storage_change::*;
+use crate::storage::storage_api::*;
+use crate::traits::*;
+
+use flo_curves::bezier::path::*;
+use futures::prelude::*;
+
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// Combines the paths for a set of elements (each entry is the list of subpaths for a single element) using a path operation
+///
+fn combine_paths(element_paths: Vec<Vec<Path>>, operation: PathOperation) -> Vec<Path> {
+    let mut element_paths   = element_paths.into_iter();
+    let first               = element_paths.next().unwrap_or_else(|| vec![]);
+    let remaining           = element_paths.filter(|paths| paths.len() > 0).collect::<Vec<_>>();
+
+    match operation {
+        PathOperation::Union        => {
+            let all_paths = Some(first).into_iter().filter(|paths| paths.len() > 0).chain(remaining).collect::<Vec<_>>();
+
+            if all_paths.len() > 0 {
+                path_add_chain::<_, Path>(&all_paths, 0.01)
+            } else {
+                vec![]
+            }
+        }
+
+        PathOperation::Subtract     => {
+            if first.len() == 0 || remaining.len() == 0 {
+                first
+            } else {
+                let remaining = path_add_chain::<_, Path>(&remaining, 0.01);
+                path_sub::<_, _, _, Path>(&first, &remaining, 0.01)
+            }
+        }
+
+        PathOperation::Intersect    => {
+            remaining.iter().fold(first, |so_far, paths| {
+                if so_far.len() == 0 {
+                    so_far
+                } else {
+                    path_intersect::<_, _, _, Path>(&so_far, paths, 0.01)
+                }
+            })
+        }
+
+        PathOperation::Exclude      => {
+            remaining.iter().fold(first, |so_far, paths| {
+                if so_far.len() == 0 {
+                    paths.clone()
+                } else {
+                    // Remove the intersection of the two sets of paths from their union
+                    let union           = path_add::<_, _, _, Path>(&so_far, paths, 0.01);
+                    let intersection    = path_intersect::<_, _, _, Path>(&so_far, paths, 0.01);
+
+                    if intersection.len() == 0 {
+                        union
+                    } else {
+                        path_sub::<_, _, _, Path>(&union, &intersection, 0.01)
+                    }
+                }
+            })
+        }
+    }
+}
+
+impl StreamAnimationCore {
+    ///
+    /// Finds the brush definition and properties to use for the path generated from an element, along with the
+    /// attachments that supply them
+    ///
+    fn brush_for_element_path(frame: &KeyFrameCore, wrapper: &ElementWrapper) -> (Arc<BrushDefinitionElement>, Arc<BrushPropertiesElement>, Vec<ElementId>) {
+        match &wrapper.element {
+            // Paths already have a brush
+            Vector::Path(path_element)  => (path_element.brush(), path_element.properties(), vec![]),
+
+            // Text is filled with its colour
+            Vector::Text(text_element)  => {
+                let properties = BrushProperties { size: 1.0, opacity: 1.0, color: text_element.style().color, fill: BrushFill::Solid };
+                (Arc::new(BrushDefinitionElement::default()), Arc::new(BrushPropertiesElement::new(ElementId::Unassigned, properties)), vec![])
+            }
+
+            // Other elements use the brush attached to them
+            _                           => {
+                let mut brush_definition    = Arc::new(BrushDefinitionElement::default());
+                let mut brush_properties    = Arc::new(BrushPropertiesElement::default());
+                let mut brush_attachments   = vec![];
+
+                for attachment_id in wrapper.attachments.iter() {
+                    match frame.elements.get(attachment_id).map(|attachment| &attachment.element) {
+                        Some(Vector::BrushDefinition(brush_defn))   => { brush_definition = Arc::new(brush_defn.clone()); brush_attachments.push(*attachment_id); },
+                        Some(Vector::BrushProperties(brush_props))  => { brush_properties = Arc::new(brush_props.clone()); brush_attachments.push(*attachment_id); },
+                        _                                           => { }
+                    }
+                }
+
+                (brush_definition, brush_properties, brush_attachments)
+            }
+        }
+    }
+
+    ///
+    /// Replaces a set of elements with a path element formed by combining their paths with a path operation
+    ///
+    pub fn combine_element_paths<'a>(&'a mut self, element_ids: Vec<i64>, combined_id: ElementId, operation: PathOperation) -> impl 'a+Future<Output=()> {
+        async move {
+            // Nothing to do if there are no elements to combine
+            if element_ids.len() == 0 {
+                return;
+            }
+
+            // Fetch the frame for the first element
+            let frame = match self.edit_keyframe_for_element(element_ids[0]).await {
+                Some(frame) => frame,
+                None        => { return; }
+            };
+
+            // Assign an ID to the new element if none is supplied
+            let mut combined_id = combined_id;
+            if combined_id.is_unassigned() {
+                combined_id = self.assign_element_id(combined_id).await;
+            }
+
+            let combined_id = match combined_id.id() {
+                Some(id)    => id,
+                None        => { return; }
+            };
+
+            let updates = frame.future(move |frame| {
+                async move {
+                    let mut updates = PendingStorageChange::new();
+
+                    // Fetch the elements that are in this frame
+                    let wrappers    = element_ids.iter()
+                        .flat_map(|element_id| frame.elements.get(&ElementId::Assigned(*element_id)).cloned())
+                        .collect::<Vec<_>>();
+
+                    if wrappers.len() == 0 {
+                        return updates;
+                    }
+
+                    // Generate the combined path (transformations attached to the elements are applied to the paths)
+                    let element_paths = wrappers.iter()
+                        .map(|wrapper| {
+                            let properties = frame.apply_properties_for_element(&wrapper.element, Arc::new(VectorProperties::default()), frame.start);
+
+                            wrapper.element.to_path(&*properties, PathConversion::RemoveInteriorPoints)
+                                .unwrap_or_else(|| vec![])
+                                .into_iter()
+                                .flat_map(|path| path.to_subpaths())
+                                .collect::<Vec<_>>()
+                        })
+                        .collect::<Vec<_>>();
+
+                    let combined_path = combine_paths(element_paths, operation);
+                    let combined_path = Path::from_paths(&combined_path);
+
+                    // Leave the elements alone if there's nothing left after the operation
+                    if combined_path.elements().count() == 0 {
+                        return updates;
+                    }
+
+                    // The new element replaces the first element
+                    let first_element   = wrappers[0].clone();
+                    let mut start_time  = first_element.start_time;
+                    let mut order_after = first_element.order_after;
+                    let parent          = first_element.parent;
+
+                    let (brush_definition, brush_properties, brush_attachments) = Self::brush_for_element_path(&*frame, &first_element);
+
+                    // Remove the original elements from the frame
+                    for wrapper in wrappers.iter() {
+                        let element_id = wrapper.element.id();
+
+                        // If this is the element the new element was going to be ordered after, move the new element behind it
+                        if order_after == Some(element_id) {
+                            order_after = wrapper.order_after;
+                        }
+
+                        // The new element starts at the earliest time of all of the elements
+                        start_time = Duration::min(start_time, wrapper.start_time);
+
+                        // Unlink the element and remove it from anything it's attached to
+                        updates.extend(frame.unlink_element(element_id));
+
+                        for attachment_id in wrapper.attachments.iter() {
+                            if let (Some(attachment), Some(attachment_id)) = (frame.elements.get_mut(attachment_id), attachment_id.id()) {
+                                attachment.attached_to.retain(|attached_to| *attached_to != element_id);
+                                updates.push_element(attachment_id, attachment.clone());
+                            }
+                        }
+
+                        // Delete the element
+                        frame.elements.remove(&element_id);
+                        if let Some(element_id) = element_id.id() {
+                            updates.push(StorageCommand::DeleteElement(element_id));
+                        }
+                    }
+
+                    // Create the combined element
+                    let combined        = PathElement::new(ElementId::Assigned(combined_id), combined_path, brush_definition, brush_properties);
+                    let combined        = ElementWrapper::attached_with_element(Vector::Path(combined), start_time);
+
+                    // Add the new element to the frame in place of the original elements
+                    updates.push_element(combined_id, combined.clone());
+                    updates.push(StorageCommand::AttachElementToLayer(frame.layer_id, combined_id, start_time));
+                    frame.elements.insert(ElementId::Assigned(combined_id), combined);
+
+                    // Keep the brush attached to the first element (transformations have been applied to the path so are not kept)
+                    if brush_attachments.len() > 0 {
+                        updates.extend(frame.add_attachment(ElementId::Assigned(combined_id), &brush_attachments));
+                    }
+
+                    updates.extend(frame.order_after(ElementId::Assigned(combined_id), parent, order_after));
+
+                    updates
+                }.boxed()
+            }).await.unwrap();
+
+            // Send the updates to storage
+            self.request(updates).await;
+        }
+    }
+}
@@ -41,7 +41,7 @@ pub (super) struct KeyFrameCore {
 ///
 /// Resolves an element from a partially resolved list of elements
 ///
-fn resolve_element<'a, Resolver>(unresolved: &mut HashMap<ElementId, Option<Resolver>>, resolved: &'a mut HashMap<ElementId, ElementWrapper>, element_id: ElementId) -> Option<ElementWrapper> 
+pub (crate) fn resolve_element<'a, Resolver>(unresolved: &mut HashMap<ElementId, Option<Resolver>>, resolved: &'a mut HashMap<ElementId, ElementWrapper>, element_id: ElementId) -> Option<ElementWrapper> 
 where Resolver: ResolveElements<ElementWrapper> {
     if let Some(resolved_element) = resolved.get(&element_id) {
         // Already resolved
@@ -127,6 +127,11 @@ impl KeyFrameCore {
                     // Already resolved this element so there's nothing more to do
                 }
             }
+
+            // Images are stored separately from the elements that display them
+            for wrapper in resolved.values_mut() {
+                wrapper.element = core.load_images(wrapper.element.clone()).await;
+            }
             
             // The initial element is the first element we can find with no parent and not ordered after any element
             // There may be more than one of these: we pick the first in the order that the elements are found
@@ -804,4 +809,137 @@ impl KeyFrameCore {
 
         updates
     }
+
+    ///
+    /// Returns the new start time for an element in this keyframe if the keyframe is moved so that it starts at `new_start`
+    ///
+    /// Elements that appear at the start of the keyframe will still be visible from the new start time if it's later than
+    /// the old one, so these don't need to be changed. Returns None if the element can keep its existing start time.
+    ///
+    fn retimed_start(&self, start_time: Duration, new_start: Duration) -> Option<Duration> {
+        if start_time > self.start || start_time > new_start {
+            // Keep the element at the same offset from the start of the keyframe
+            let offset = start_time.checked_sub(self.start).unwrap_or(Duration::from_millis(0));
+            Some(new_start + offset)
+        } else {
+            // Element appears at (or before) the start of the keyframe at both times
+            None
+        }
+    }
+
+    ///
+    /// Generates a copy of an element wrapper with its timing updated for when this keyframe starts at `new_start`, or None
+    /// if the element does not need to be changed
+    ///
+    fn retimed_wrapper(&self, wrapper: &ElementWrapper, new_start: Duration) -> Option<ElementWrapper> {
+        let mut retimed = None;
+
+        // Elements drawn part-way through the keyframe stay at the same time relative to the start of the keyframe
+        if let Some(new_start_time) = self.retimed_start(wrapper.start_time, new_start) {
+            let mut new_wrapper         = wrapper.clone();
+            new_wrapper.start_time      = new_start_time;
+            retimed                     = Some(new_wrapper);
+        }
+
+        // Motions are described in absolute time, so they need to move with the keyframe
+        if let Vector::Motion(motion) = &wrapper.element {
+            let offset_millis           = (to_millis(new_start) - to_millis(self.start)) as f32;
+            let new_motion              = MotionElement::new(motion.id(), motion.motion().with_time_offset(offset_millis));
+
+            let mut new_wrapper         = retimed.unwrap_or_else(|| wrapper.clone());
+            new_wrapper.element         = Vector::Motion(new_motion);
+            retimed                     = Some(new_wrapper);
+        }
+
+        retimed
+    }
+
+    ///
+    /// Generates the element updates needed after this keyframe has been moved to start at a new time
+    ///
+    /// The keyframe itself and the attachments to it are moved by the `MoveKeyFrame` storage command: this only needs to
+    /// rewrite the elements whose timing depends on when the keyframe starts.
+    ///
+    pub fn move_elements_to(&self, new_start: Duration) -> PendingStorageChange {
+        let mut updates = PendingStorageChange::new();
+
+        for (element_id, wrapper) in self.elements.iter() {
+            if let (Some(element_id), Some(retimed)) = (element_id.id(), self.retimed_wrapper(wrapper, new_start)) {
+                updates.push_element(element_id, retimed);
+            }
+        }
+
+        updates
+    }
+
+    ///
+    /// Generates the storage commands that copy the elements of this keyframe into a keyframe starting at `new_start`
+    ///
+    /// `new_ids` supplies the ID of the copy of each element: elements without an entry are not copied. The keyframe at
+    /// `new_start` must already exist.
+    ///
+    pub fn duplicate_elements_to(&self, new_start: Duration, new_ids: &HashMap<ElementId, ElementId>) -> PendingStorageChange {
+        let mut updates = PendingStorageChange::new();
+        let map_id      = |element_id: &ElementId| *new_ids.get(element_id).unwrap_or(element_id);
+
+        // Copy the elements in order of their IDs so that the attachments are generated in a consistent order
+        let mut element_ids = self.elements.keys().cloned().collect::<Vec<_>>();
+        element_ids.sort_by_key(|element_id| element_id.id());
+
+        for element_id in element_ids {
+            let wrapper     = &self.elements[&element_id];
+            let new_id      = match new_ids.get(&element_id).and_then(|new_id| new_id.id()) {
+                Some(new_id)    => new_id,
+                None            => { continue; }
+            };
+
+            // Retime the element, then update every reference to another element to point at the copies
+            let mut copy            = self.retimed_wrapper(wrapper, new_start).unwrap_or_else(|| wrapper.clone());
+            copy.element            = Self::remap_element_ids(&copy.element, new_ids);
+            copy.attachments        = copy.attachments.iter().map(map_id).collect();
+            copy.attached_to        = copy.attached_to.iter().map(map_id).collect();
+            copy.parent             = copy.parent.as_ref().map(map_id);
+            copy.order_before       = copy.order_before.as_ref().map(map_id);
+            copy.order_after        = copy.order_after.as_ref().map(map_id);
+
+            updates.push_element(new_id, copy);
+            updates.push(StorageCommand::AttachElementToLayer(self.layer_id, new_id, new_start));
+        }
+
+        updates
+    }
+
+    ///
+    /// Changes the IDs of an element and the elements it contains according to a map of old IDs to new IDs
+    ///
+    fn remap_element_ids(element: &Vector, new_ids: &HashMap<ElementId, ElementId>) -> Vector {
+        let map_id = |element_id: ElementId| *new_ids.get(&element_id).unwrap_or(&element_id);
+
+        let mut element = match element {
+            Vector::Path(path)      => {
+                // Paths refer to their brush elements
+                let mut brush       = (*path.brush()).clone();
+                let mut properties  = (*path.properties()).clone();
+                brush.set_id(map_id(brush.id()));
+                properties.set_id(map_id(properties.id()));
+
+                Vector::Path(PathElement::new(path.id(), path.path().clone(), Arc::new(brush), Arc::new(properties)))
+            }
+
+            Vector::Group(group)    => {
+                // Groups contain their child elements
+                let mut new_group   = group.with_elements(group.elements().map(|child| Self::remap_element_ids(child, new_ids)));
+                if let Some(hint_path) = group.hint_path() {
+                    new_group.set_hint_path(hint_path);
+                }
+
+                Vector::Group(new_group)
+            }
+
+            other                   => other.clone()
+        };
+
+        element.set_id(map_id(element.id()));
+        element
+    }
 }
@@ -0,0 +1,589 @@
+use super::keyframe_core::*;
+use super::element_wrapper::*;
+use crate::traits::*;
+
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::{HashMap, HashSet};
+
+///
+/// The width, opacity and colour mix at a point on a brush stroke
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct PointStyle {
+    width:      f64,
+    opacity:    f64,
+    color_mix:  f64
+}
+
+///
+/// A section of a curve used when generating in-betweens
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct CurveSection {
+    /// The first control point of this section
+    cp1: (f64, f64),
+
+    /// The second control point of this section
+    cp2: (f64, f64),
+
+    /// Where this section ends
+    end: (f64, f64),
+
+    /// The style of the curve at the end of this section (brush strokes only)
+    style: PointStyle
+}
+
+///
+/// A curve in a form where it can be interpolated with another curve
+///
+#[derive(Clone, PartialEq, Debug)]
+struct InbetweenCurve {
+    /// The start point of the curve
+    start: (f64, f64),
+
+    /// The style of the curve at the start point
+    start_style: PointStyle,
+
+    /// The bezier sections that make up the curve
+    sections: Vec<CurveSection>,
+
+    /// True if this curve is closed (for paths)
+    closed: bool
+}
+
+#[inline]
+fn lerp(from: f64, to: f64, ratio: f64) -> f64 {
+    from + (to-from)*ratio
+}
+
+#[inline]
+fn lerp_point(from: (f64, f64), to: (f64, f64), ratio: f64) -> (f64, f64) {
+    (lerp(from.0, to.0, ratio), lerp(from.1, to.1, ratio))
+}
+
+impl PointStyle {
+    ///
+    /// The style used for the points of paths (which have no width)
+    ///
+    fn path() -> PointStyle {
+        PointStyle { width: 0.0, opacity: 1.0, color_mix: 0.0 }
+    }
+
+    ///
+    /// The style of a brush point
+    ///
+    fn from_brush_point(point: &BrushPoint) -> PointStyle {
+        PointStyle { width: point.width as f64, opacity: point.opacity as f64, color_mix: point.color_mix as f64 }
+    }
+
+    ///
+    /// Creates a style part-way between this style and another
+    ///
+    fn lerp(&self, to: &PointStyle, ratio: f64) -> PointStyle {
+        PointStyle {
+            width:      lerp(self.width, to.width, ratio),
+            opacity:    lerp(self.opacity, to.opacity, ratio),
+            color_mix:  lerp(self.color_mix, to.color_mix, ratio)
+        }
+    }
+
+    ///
+    /// Creates a brush point with this style
+    ///
+    fn brush_point(&self, position: (f32, f32), cp1: (f32, f32), cp2: (f32, f32)) -> BrushPoint {
+        BrushPoint { position, cp1, cp2, width: self.width as f32, opacity: self.opacity as f32, color_mix: self.color_mix as f32 }
+    }
+}
+
+#[inline]
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (a.0-b.0, a.1-b.1);
+    (dx*dx + dy*dy).sqrt()
+}
+
+impl InbetweenCurve {
+    ///
+    /// Creates a curve from the points in a brush stroke
+    ///
+    fn from_brush_points(points: &Vec<BrushPoint>) -> Option<InbetweenCurve> {
+        let as_f64  = |(x, y): (f32, f32)| (x as f64, y as f64);
+        let first   = points.first()?;
+
+        Some(InbetweenCurve {
+            start:          as_f64(first.position),
+            start_style:    PointStyle::from_brush_point(first),
+            sections:       points.iter().skip(1)
+                .map(|point| CurveSection { cp1: as_f64(point.cp1), cp2: as_f64(point.cp2), end: as_f64(point.position), style: PointStyle::from_brush_point(point) })
+                .collect(),
+            closed:         false
+        })
+    }
+
+    ///
+    /// Creates a set of curves from the subpaths of a path
+    ///
+    fn from_path(path: &Path) -> Vec<InbetweenCurve> {
+        let mut curves  = vec![];
+        let mut current = None;
+
+        for component in path.elements() {
+            match component {
+                PathComponent::Move(point) => {
+                    if let Some(curve) = current.take() { curves.push(curve); }
+                    current = Some(InbetweenCurve { start: point.position, start_style: PointStyle::path(), sections: vec![], closed: false });
+                }
+
+                PathComponent::Line(point) => {
+                    if let Some(curve) = current.as_mut() { curve.line_to(point.position); }
+                }
+
+                PathComponent::Bezier(end, cp1, cp2) => {
+                    if let Some(curve) = current.as_mut() { curve.sections.push(CurveSection { cp1: cp1.position, cp2: cp2.position, end: end.position, style: PointStyle::path() }); }
+                }
+
+                PathComponent::Close => {
+                    if let Some(mut curve) = current.take() {
+                        // Closing the path draws a line back to the start
+                        let start = curve.start;
+                        if curve.end_point() != start {
+                            curve.line_to(start);
+                        }
+
+                        curve.closed = true;
+                        curves.push(curve);
+                    }
+                }
+            }
+        }
+
+        if let Some(curve) = current.take() { curves.push(curve); }
+
+        curves
+    }
+
+    ///
+    /// Converts a set of curves back into a path
+    ///
+    fn to_path(curves: &Vec<InbetweenCurve>) -> Path {
+        let point = |(x, y): (f64, f64)| PathPoint { position: (x, y) };
+
+        Path::from_elements(curves.iter()
+            .flat_map(|curve| {
+                let mut components = vec![PathComponent::Move(point(curve.start))];
+                components.extend(curve.sections.iter().map(|section| PathComponent::Bezier(point(section.end), point(section.cp1), point(section.cp2))));
+
+                if curve.closed {
+                    components.push(PathComponent::Close);
+                }
+
+                components
+            }))
+    }
+
+    ///
+    /// Converts this curve back into the points of a brush stroke
+    ///
+    fn to_brush_points(&self) -> Vec<BrushPoint> {
+        let as_f32 = |(x, y): (f64, f64)| (x as f32, y as f32);
+
+        let mut points = vec![self.start_style.brush_point(as_f32(self.start), as_f32(self.start), as_f32(self.start))];
+        points.extend(self.sections.iter().map(|section| section.style.brush_point(as_f32(section.end), as_f32(section.cp1), as_f32(section.cp2))));
+
+        points
+    }
+
+    ///
+    /// The point where this curve ends
+    ///
+    fn end_point(&self) -> (f64, f64) {
+        self.sections.last().map(|section| section.end).unwrap_or(self.start)
+    }
+
+    ///
+    /// Adds a straight line to the end of this curve
+    ///
+    fn line_to(&mut self, end: (f64, f64)) {
+        let start = self.end_point();
+
+        self.sections.push(CurveSection {
+            cp1:    lerp_point(start, end, 1.0/3.0),
+            cp2:    lerp_point(start, end, 2.0/3.0),
+            end:    end,
+            style:  self.sections.last().map(|section| section.style).unwrap_or(self.start_style)
+        });
+    }
+
+    ///
+    /// The point and style at the start of the specified section
+    ///
+    fn section_start(&self, section_idx: usize) -> ((f64, f64), PointStyle) {
+        if section_idx == 0 {
+            (self.start, self.start_style)
+        } else {
+            (self.sections[section_idx-1].end, self.sections[section_idx-1].style)
+        }
+    }
+
+    ///
+    /// The approximate centre of this curve
+    ///
+    fn centre(&self) -> (f64, f64) {
+        let count       = (self.sections.len() + 1) as f64;
+        let (sx, sy)    = self.sections.iter().fold(self.start, |(x, y), section| (x + section.end.0, y + section.end.1));
+
+        (sx / count, sy / count)
+    }
+
+    ///
+    /// Splits the longest section of this curve in half (so the curve looks the same but has an extra section)
+    ///
+    fn split_longest_section(&mut self) {
+        if self.sections.len() == 0 {
+            // A single point is turned into a section with no length
+            let start = self.start;
+            self.sections.push(CurveSection { cp1: start, cp2: start, end: start, style: self.start_style });
+            return;
+        }
+
+        // Measure the sections by the length of their control polygons
+        let lengths         = (0..self.sections.len())
+            .map(|section_idx| {
+                let (start, _)  = self.section_start(section_idx);
+                let section     = &self.sections[section_idx];
+
+                distance(start, section.cp1) + distance(section.cp1, section.cp2) + distance(section.cp2, section.end)
+            });
+        let (longest, _)    = lengths.enumerate()
+            .fold((0, -1.0), |(longest, longest_length), (section_idx, length)| if length > longest_length { (section_idx, length) } else { (longest, longest_length) });
+
+        // Subdivide the section at its midpoint
+        let (start, start_style)    = self.section_start(longest);
+        let section                 = self.sections[longest];
+
+        let p01     = lerp_point(start, section.cp1, 0.5);
+        let p12     = lerp_point(section.cp1, section.cp2, 0.5);
+        let p23     = lerp_point(section.cp2, section.end, 0.5);
+        let p012    = lerp_point(p01, p12, 0.5);
+        let p123    = lerp_point(p12, p23, 0.5);
+        let mid     = lerp_point(p012, p123, 0.5);
+
+        let first   = CurveSection { cp1: p01, cp2: p012, end: mid, style: start_style.lerp(&section.style, 0.5) };
+        let second  = CurveSection { cp1: p123, cp2: p23, end: section.end, style: section.style };
+
+        self.sections[longest] = first;
+        self.sections.insert(longest+1, second);
+    }
+
+    ///
+    /// Creates a curve that is part-way between this curve and another one (0.0 is this curve, 1.0 is the target curve)
+    ///
+    fn interpolate(&self, target: &InbetweenCurve, ratio: f64) -> InbetweenCurve {
+        // Both curves need the same number of sections
+        let mut from    = self.clone();
+        let mut to      = target.clone();
+
+        while from.sections.len() < to.sections.len() { from.split_longest_section(); }
+        while to.sections.len() < from.sections.len() { to.split_longest_section(); }
+
+        InbetweenCurve {
+            start:          lerp_point(from.start, to.start, ratio),
+            start_style:    from.start_style.lerp(&to.start_style, ratio),
+            sections:       from.sections.iter().zip(to.sections.iter())
+                .map(|(from, to)| CurveSection {
+                    cp1:    lerp_point(from.cp1, to.cp1, ratio),
+                    cp2:    lerp_point(from.cp2, to.cp2, ratio),
+                    end:    lerp_point(from.end, to.end, ratio),
+                    style:  from.style.lerp(&to.style, ratio)
+                })
+                .collect(),
+            closed:         if ratio < 0.5 { from.closed } else { to.closed }
+        }
+    }
+}
+
+///
+/// Returns the curves making up an element, if it's a type of element that can be in-betweened
+///
+fn element_curves(element: &Vector) -> Option<Vec<InbetweenCurve>> {
+    match element {
+        Vector::BrushStroke(brush_stroke)   => InbetweenCurve::from_brush_points(&*brush_stroke.points()).map(|curve| vec![curve]),
+        Vector::Path(path)                  => Some(InbetweenCurve::from_path(path.path())).filter(|curves| curves.len() > 0),
+        _                                   => None
+    }
+}
+
+///
+/// Returns the approximate centre of a set of curves
+///
+fn curves_centre(curves: &Vec<InbetweenCurve>) -> (f64, f64) {
+    let count       = curves.len().max(1) as f64;
+    let (sx, sy)    = curves.iter().map(|curve| curve.centre()).fold((0.0, 0.0), |(sx, sy), (x, y)| (sx+x, sy+y));
+
+    (sx / count, sy / count)
+}
+
+///
+/// Creates an element that's part-way between two elements, if they can be in-betweened (the result has the ID of the first element)
+///
+pub (super) fn interpolate_element(from: &Vector, to: &Vector, ratio: f64) -> Option<Vector> {
+    let from_curves = element_curves(from)?;
+    let to_curves   = element_curves(to)?;
+
+    match (from, to) {
+        (Vector::BrushStroke(from_stroke), Vector::BrushStroke(_)) => {
+            let curve = from_curves[0].interpolate(&to_curves[0], ratio);
+
+            Some(Vector::BrushStroke(BrushElement::new(from_stroke.id(), Arc::new(curve.to_brush_points()))))
+        }
+
+        (Vector::Path(from_path), Vector::Path(_)) => {
+            // Paths can only be in-betweened if they have the same number of subpaths
+            if from_curves.len() != to_curves.len() {
+                return None;
+            }
+
+            let curves = from_curves.iter().zip(to_curves.iter())
+                .map(|(from, to)| from.interpolate(to, ratio))
+                .collect();
+
+            Some(Vector::Path(PathElement::new(from_path.id(), InbetweenCurve::to_path(&curves), from_path.brush(), from_path.properties())))
+        }
+
+        _ => None
+    }
+}
+
+///
+/// Finds the pairs of elements that should be in-betweened, returning the indexes of the matched elements
+///
+pub (super) fn match_elements(from: &Vec<Vector>, to: &Vec<Vector>, mode: InbetweenMode) -> Vec<(usize, usize)> {
+    // Only elements that are made up of curves can be in-betweened
+    let from_curves = from.iter().enumerate().filter_map(|(idx, element)| element_curves(element).map(|curves| (idx, VectorType::from(element), curves))).collect::<Vec<_>>();
+    let to_curves   = to.iter().enumerate().filter_map(|(idx, element)| element_curves(element).map(|curves| (idx, VectorType::from(element), curves))).collect::<Vec<_>>();
+
+    match mode {
+        InbetweenMode::Off              => vec![],
+
+        InbetweenMode::MatchByOrder     => {
+            // The first curve on the first keyframe is matched with the first curve on the second and so on
+            from_curves.iter().zip(to_curves.iter())
+                .filter(|((_, from_type, _), (_, to_type, _))| from_type == to_type)
+                .map(|((from_idx, _, _), (to_idx, _, _))| (*from_idx, *to_idx))
+                .collect()
+        }
+
+        InbetweenMode::MatchByProximity => {
+            // Measure the distance between every pair of elements of the same type
+            let mut pairs = vec![];
+            for (from_idx, from_type, from_curves) in from_curves.iter() {
+                let from_centre = curves_centre(from_curves);
+
+                for (to_idx, to_type, to_curves) in to_curves.iter() {
+                    if from_type == to_type {
+                        pairs.push((*from_idx, *to_idx, distance(from_centre, curves_centre(to_curves))));
+                    }
+                }
+            }
+
+            // Match the closest pairs first
+            pairs.sort_by(|(_, _, dist_a), (_, _, dist_b)| dist_a.partial_cmp(dist_b).unwrap_or(std::cmp::Ordering::Equal));
+
+            let mut used_from   = HashSet::new();
+            let mut used_to     = HashSet::new();
+            let mut matches     = vec![];
+
+            for (from_idx, to_idx, _) in pairs {
+                if !used_from.contains(&from_idx) && !used_to.contains(&to_idx) {
+                    used_from.insert(from_idx);
+                    used_to.insert(to_idx);
+                    matches.push((from_idx, to_idx));
+                }
+            }
+
+            matches.sort();
+            matches
+        }
+    }
+}
+
+impl KeyFrameCore {
+    ///
+    /// Generates a keyframe containing the drawing part-way between this keyframe and the following one
+    ///
+    /// The result starts at `when`. Elements that can be matched are interpolated and keep their IDs and attachments
+    /// from this keyframe. Elements that can't be matched are shown from this keyframe for the first half of the
+    /// time between the keyframes, and from the next keyframe for the second half.
+    ///
+    pub fn inbetween(&self, next: &KeyFrameCore, when: Duration, mode: InbetweenMode) -> KeyFrameCore {
+        // Work out how far between the two keyframes we are
+        let length          = next.start.checked_sub(self.start).unwrap_or(Duration::from_millis(0)).as_micros() as f64;
+        let elapsed         = when.checked_sub(self.start).unwrap_or(Duration::from_millis(0)).as_micros() as f64;
+        let ratio           = if length > 0.0 { (elapsed / length).min(1.0) } else { 0.0 };
+
+        // Match up the elements in the two keyframes
+        let from_elements   = self.vector_elements(when).collect::<Vec<_>>();
+        let to_elements     = next.vector_elements(next.start).collect::<Vec<_>>();
+        let interpolated    = match_elements(&from_elements, &to_elements, mode).into_iter()
+            .filter_map(|(from_idx, to_idx)| interpolate_element(&from_elements[from_idx], &to_elements[to_idx], ratio).map(|element| (from_idx, to_idx, element)))
+            .collect::<Vec<_>>();
+
+        let matched_to      = interpolated.iter().map(|(_, to_idx, _)| *to_idx).collect::<HashSet<_>>();
+        let mut interpolated = interpolated.into_iter().map(|(from_idx, _, element)| (from_idx, element)).collect::<HashMap<_, _>>();
+
+        // Build the new list of elements
+        let mut elements    = self.elements.clone();
+        let mut order       = vec![];
+
+        for (from_idx, element) in from_elements.iter().enumerate() {
+            let element_id = element.id();
+
+            if let Some(interpolated) = interpolated.remove(&from_idx) {
+                // Matched elements are replaced with the interpolated version
+                if let Some(wrapper) = elements.get_mut(&element_id) {
+                    wrapper.element = interpolated;
+                }
+
+                order.push(element_id);
+            } else if ratio < 0.5 {
+                // Unmatched elements from this keyframe disappear halfway through
+                order.push(element_id);
+            }
+        }
+
+        if ratio >= 0.5 {
+            // Unmatched elements from the next keyframe appear halfway through, in front of everything else
+            for (to_idx, element) in to_elements.iter().enumerate() {
+                if matched_to.contains(&to_idx) { continue; }
+
+                let element_id = element.id();
+                if let Some(wrapper) = next.elements.get(&element_id) {
+                    let mut wrapper     = wrapper.clone();
+                    wrapper.start_time  = when;
+                    wrapper.parent      = None;
+
+                    for attachment_id in wrapper.attachments.iter() {
+                        if let (false, Some(attachment)) = (elements.contains_key(attachment_id), next.elements.get(attachment_id)) {
+                            elements.insert(*attachment_id, attachment.clone());
+                        }
+                    }
+
+                    elements.insert(element_id, wrapper);
+                    order.push(element_id);
+                }
+            }
+        }
+
+        // Link the elements together in their new order
+        for (idx, element_id) in order.iter().enumerate() {
+            if let Some(wrapper) = elements.get_mut(element_id) {
+                wrapper.order_after     = if idx > 0 { Some(order[idx-1]) } else { None };
+                wrapper.order_before    = order.get(idx+1).cloned();
+            }
+        }
+
+        // Remove anything that's no longer used by the elements that are displayed
+        let used = Self::used_elements(&elements, &order);
+        elements.retain(|element_id, _| used.contains(element_id));
+        elements.values_mut().for_each(|wrapper: &mut ElementWrapper| wrapper.attached_to.retain(|element_id| used.contains(element_id)));
+
+        KeyFrameCore {
+            layer_id:           self.layer_id,
+            elements:           elements,
+            initial_element:    order.first().cloned(),
+            last_element:       order.last().cloned(),
+            start:              when,
+            end:                next.start,
+            active_brush:       None
+        }
+    }
+
+    ///
+    /// Finds the elements that are needed to render a list of elements (the elements themselves, their attachments and the elements in any groups)
+    ///
+    fn used_elements(elements: &HashMap<ElementId, ElementWrapper>, order: &Vec<ElementId>) -> HashSet<ElementId> {
+        let mut used        = HashSet::new();
+        let mut to_visit    = order.clone();
+
+        while let Some(element_id) = to_visit.pop() {
+            if used.contains(&element_id) { continue; }
+            used.insert(element_id);
+
+            if let Some(wrapper) = elements.get(&element_id) {
+                to_visit.extend(wrapper.attachments.iter().cloned());
+            }
+
+            // Grouped elements refer to the group as their parent
+            to_visit.extend(elements.iter()
+                .filter(|(_, wrapper)| wrapper.parent == Some(element_id))
+                .map(|(child_id, _)| *child_id));
+        }
+
+        used
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn brush_stroke(id: i64, points: Vec<(f32, f32)>) -> Vector {
+        let points = points.into_iter()
+            .map(|point| BrushPoint { position: point, cp1: point, cp2: point, width: 1.0, opacity: 1.0, color_mix: 0.0 })
+            .collect();
+
+        Vector::BrushStroke(BrushElement::new(ElementId::Assigned(id), Arc::new(points)))
+    }
+
+    fn points(element: &Vector) -> Vec<(f32, f32)> {
+        match element {
+            Vector::BrushStroke(brush_stroke)   => brush_stroke.points().iter().map(|point| point.position).collect(),
+            _                                   => vec![]
+        }
+    }
+
+    #[test]
+    fn interpolate_brush_strokes_halfway() {
+        let from            = brush_stroke(1, vec![(0.0, 0.0), (10.0, 0.0)]);
+        let to              = brush_stroke(2, vec![(0.0, 10.0), (10.0, 10.0)]);
+        let interpolated    = interpolate_element(&from, &to, 0.5).unwrap();
+
+        assert!(interpolated.id() == ElementId::Assigned(1));
+        assert!(points(&interpolated) == vec![(0.0, 5.0), (10.0, 5.0)]);
+    }
+
+    #[test]
+    fn interpolate_brush_strokes_with_different_lengths() {
+        let from            = brush_stroke(1, vec![(0.0, 0.0), (10.0, 0.0)]);
+        let to              = brush_stroke(2, vec![(0.0, 10.0), (10.0, 10.0), (20.0, 10.0)]);
+        let interpolated    = interpolate_element(&from, &to, 1.0).unwrap();
+
+        assert!(points(&interpolated) == vec![(0.0, 10.0), (10.0, 10.0), (20.0, 10.0)]);
+    }
+
+    #[test]
+    fn cannot_interpolate_stroke_with_path() {
+        let from            = brush_stroke(1, vec![(0.0, 0.0), (10.0, 0.0)]);
+        let to              = Vector::Path(PathElement::new(ElementId::Assigned(2), Path::from_elements(vec![PathComponent::Move(PathPoint::new(0.0, 0.0)), PathComponent::Line(PathPoint::new(10.0, 0.0))]),
+            Arc::new(BrushDefinitionElement::new(ElementId::Assigned(3), BrushDefinition::Simple, BrushDrawingStyle::Draw)),
+            Arc::new(BrushPropertiesElement::new(ElementId::Assigned(4), BrushProperties::new()))));
+
+        assert!(interpolate_element(&from, &to, 0.5).is_none());
+    }
+
+    #[test]
+    fn match_by_order() {
+        let from    = vec![brush_stroke(1, vec![(0.0, 0.0)]), brush_stroke(2, vec![(100.0, 100.0)])];
+        let to      = vec![brush_stroke(3, vec![(100.0, 100.0)]), brush_stroke(4, vec![(0.0, 0.0)])];
+
+        assert!(match_elements(&from, &to, InbetweenMode::MatchByOrder) == vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn match_by_proximity() {
+        let from    = vec![brush_stroke(1, vec![(0.0, 0.0)]), brush_stroke(2, vec![(100.0, 100.0)])];
+        let to      = vec![brush_stroke(3, vec![(100.0, 100.0)]), brush_stroke(4, vec![(0.0, 0.0)])];
+
+        assert!(match_elements(&from, &to, InbetweenMode::MatchByProximity) == vec![(0, 1), (1, 0)]);
+    }
+}
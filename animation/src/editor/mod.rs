@@ -8,15 +8,20 @@ mod core_path;
 mod core_paint;
 mod core_layer;
 mod core_motion;
+mod core_annotation;
+mod core_image;
 mod core_element;
-mod keyframe_core;
+mod core_undo;
+pub (crate) mod keyframe_core;
 mod keyframe_raycast;
+mod keyframe_inbetween;
 mod pending_storage_change;
 mod paint_fill;
-mod element_wrapper;
+pub (crate) mod element_wrapper;
 mod element_collide;
 mod element_transform;
 mod element_convert_to_path;
+mod element_path_operation;
 mod stream_layer;
 mod stream_frame;
 mod stream_layer_cache;
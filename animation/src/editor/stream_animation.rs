@@ -18,6 +18,7 @@ use futures::stream::{BoxStream};
 use std::sync::*;
 use std::ops::{Range};
 use std::time::{Duration};
+use std::collections::{HashMap};
 
 ///
 /// Animation that sends its updates to a storage stream
@@ -50,11 +51,15 @@ impl StreamAnimation {
             storage_responses:  storage_responses,
             storage_requests:   requests,
             next_element_id:    None,
+            element_id_range:   None,
             cached_keyframe:    None,
             brush_defn:         None,
             brush_props:        None,
             path_brush_defn:    None,
-            path_brush_props:   None
+            path_brush_props:   None,
+            undo_step:          None,
+            undo_history:       0,
+            images:             HashMap::new()
         };
         let core            = Arc::new(Desync::new(core));
 
@@ -89,6 +94,36 @@ impl StreamAnimation {
         request_core_sync(Arc::clone(&self.core), &self.idle_sync_requests, request.into_iter().collect())
     }
 
+    ///
+    /// Reads an image from the storage layer
+    ///
+    fn read_stored_image(&self, image_id: ElementId) -> Option<Arc<ReferenceImage>> {
+        let image_id = image_id.id()?;
+
+        self.request_sync(vec![StorageCommand::ReadImage(image_id)])?
+            .into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::Image(_, image)    => ReferenceImage::deserialize(&mut image.chars()).map(Arc::new),
+                _                                   => None
+            })
+            .nth(0)
+    }
+
+    ///
+    /// Reads an audio clip from the storage layer
+    ///
+    fn read_stored_audio_clip(&self, clip_id: ElementId) -> Option<Arc<AudioClip>> {
+        let clip_id = clip_id.id()?;
+
+        self.request_sync(vec![StorageCommand::ReadAudioClip(clip_id)])?
+            .into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::AudioClip(_, clip) => AudioClip::deserialize(&mut clip.chars()).map(Arc::new),
+                _                                   => None
+            })
+            .nth(0)
+    }
+
     ///
     /// Waits for any pending edits on this animation to complete
     ///
@@ -128,6 +163,20 @@ impl StreamAnimation {
 
         properties
     }
+
+    ///
+    /// Retrieves the number of steps in the undo and redo stacks for an undo history
+    ///
+    fn undo_stack_depth(&self, undo_history: u64) -> (usize, usize) {
+        self.wait_for_edits();
+
+        let mut response = self.request_sync(vec![StorageCommand::ReadUndoStackDepth(undo_history)]).unwrap_or_else(|| vec![]);
+
+        match response.pop() {
+            Some(StorageResponse::UndoStackDepth(undo, redo))   => (undo, redo),
+            _                                                   => (0, 0)
+        }
+    }
 }
 
 impl Animation for StreamAnimation {
@@ -267,8 +316,12 @@ impl Animation for StreamAnimation {
                             while let Some(response) = response.pop() {
                                 // Ignore everything that's not an edit (we have no way to do error handling here)
                                 if let StorageResponse::Edit(_num, serialized_edit) = response {
-                                    // Store edits that deserialize successfully on the fetched list
-                                    if let Some(edit) = AnimationEdit::deserialize(&mut serialized_edit.chars()) {
+                                    // Store edits that deserialize successfully on the fetched list (images and audio clips are only logged by ID, so are read from storage)
+                                    let edit = AnimationEdit::deserialize_from_edit_log(&mut serialized_edit.chars(),
+                                        |image_id| self.read_stored_image(image_id),
+                                        |clip_id| self.read_stored_audio_clip(clip_id));
+
+                                    if let Some(edit) = edit {
                                         fetched.push(edit)
                                     }
                                 }
@@ -290,6 +343,22 @@ impl Animation for StreamAnimation {
     fn motion<'a>(&'a self) -> &'a dyn AnimationMotion {
         &*self
     }
+
+    ///
+    /// Retrieves the review annotations that have been left on this animation, along with their IDs
+    ///
+    fn get_annotations(&self) -> Vec<(ElementId, Annotation)> {
+        self.wait_for_edits();
+
+        let annotations = self.request_sync(vec![StorageCommand::ReadAnnotations]).unwrap_or_else(|| vec![]);
+
+        annotations.into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::Annotation(annotation_id, _, annotation)   => Annotation::deserialize(&mut annotation.chars()).map(|annotation| (ElementId::Assigned(annotation_id), annotation)),
+                _                                                           => None
+            })
+            .collect()
+    }
 }
 
 impl EditableAnimation for StreamAnimation {
@@ -314,6 +383,17 @@ impl EditableAnimation for StreamAnimation {
         request.sync(|result| result.take()).unwrap()
     }
 
+    ///
+    /// Restricts the element IDs assigned by this animation to the specified range
+    ///
+    fn set_element_id_range(&self, range: Range<i64>) {
+        // Wait for any pending edits so that they're assigned IDs before the range changes
+        self.wait_for_edits();
+
+        let _ = self.core.future(move |core| core.set_element_id_range(range).boxed());
+        self.core.sync(|_| { });
+    }
+
     ///
     /// Retrieves a sink that can be used to send edits for this animation
     ///
@@ -364,6 +444,18 @@ impl EditableAnimation for StreamAnimation {
         self.idle_sync_requests.desync(move |reqs| { reqs.push(sync_request) });
     }
 
+    ///
+    /// Performs a set of edits on this animation without adding them to the undo history
+    ///
+    fn perform_edits_without_undo(&self, edits: Vec<AnimationEdit>) {
+        // These go straight to the core, so wait for any edits that were published earlier so they're performed in order
+        self.wait_for_edits();
+
+        let edits = Arc::new(edits);
+        let _     = self.core.future(move |core| core.perform_edits_without_undo(edits).boxed());
+        self.core.sync(|_| { });
+    }
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
@@ -372,6 +464,20 @@ impl EditableAnimation for StreamAnimation {
             core.cached_keyframe = None;
         });
     }
+
+    ///
+    /// Returns true if there are edits in the specified undo history that can be undone
+    ///
+    fn can_undo_in(&self, undo_history: u64) -> bool {
+        self.undo_stack_depth(undo_history).0 > 0
+    }
+
+    ///
+    /// Returns true if there are undone edits in the specified undo history that can be redone
+    ///
+    fn can_redo_in(&self, undo_history: u64) -> bool {
+        self.undo_stack_depth(undo_history).1 > 0
+    }
 }
 
 impl AnimationMotion for StreamAnimation {
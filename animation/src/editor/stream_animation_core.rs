@@ -11,7 +11,9 @@ use futures::prelude::*;
 use futures::stream::{BoxStream};
 
 use std::sync::*;
+use std::ops::{Range};
 use std::time::{Duration};
+use std::collections::{HashMap, HashSet};
 
 ///
 /// Performs an asynchronous request on a storage layer for this animation
@@ -80,6 +82,9 @@ pub (super) struct StreamAnimationCore {
     /// The next element ID to assign (None if we haven't retrieved the element ID yet)
     pub (super) next_element_id: Option<i64>,
 
+    /// The range that new element IDs are assigned from (None if IDs are assigned after the highest ID in use)
+    pub (super) element_id_range: Option<Range<i64>>,
+
     /// The keyframe that is currently being edited, if there is one
     pub (super) cached_keyframe: Option<Arc<Desync<KeyFrameCore>>>,
 
@@ -93,7 +98,16 @@ pub (super) struct StreamAnimationCore {
     pub (super) path_brush_defn: Option<Arc<BrushDefinitionElement>>,
 
     /// The element that should be used as the properties for the current path (unassigned if there is none)
-    pub (super) path_brush_props: Option<Arc<BrushPropertiesElement>>
+    pub (super) path_brush_props: Option<Arc<BrushPropertiesElement>>,
+
+    /// While an undo step is being recorded, the storage commands that will reverse each of the commands sent to the storage so far
+    pub (super) undo_step: Option<Vec<Vec<StorageCommand>>>,
+
+    /// The undo history that undo steps are recorded in and that undo and redo act on
+    pub (super) undo_history: u64,
+
+    /// The reference images that have been read from storage so far, by ID
+    pub (super) images: HashMap<i64, Arc<ReferenceImage>>
 }
 
 impl StreamAnimationCore {
@@ -102,8 +116,13 @@ impl StreamAnimationCore {
     ///
     pub fn request<'a, Commands: 'a+IntoIterator<Item=StorageCommand>>(&'a mut self, request: Commands) -> impl 'a+Future<Output=Option<Vec<StorageResponse>>> {
         async move {
-            self.storage_requests.publish(request.into_iter().collect()).await;
-            self.storage_responses.next().await
+            let request = request.into_iter().collect();
+
+            if self.undo_step.is_some() {
+                self.request_recording_undo(request).await
+            } else {
+                self.request_unrecorded(request).await
+            }
         }
     }
 
@@ -122,8 +141,18 @@ impl StreamAnimationCore {
     ///
     pub fn assign_element_id<'a>(&'a mut self, element_id: ElementId) -> impl 'a+Future<Output=ElementId> {
         async move {
-            if let ElementId::Assigned(_) = element_id {
-                // Nothing to do if the element ID is already assigned
+            if let ElementId::Assigned(assigned_id) = element_id {
+                // Make sure that IDs assigned elsewhere (eg, in edits synchronised from another editor) are not assigned again
+                // (IDs outside of our range were assigned by another editor with its own range, so can't be assigned here anyway)
+                let in_range = self.element_id_range.as_ref().map(|range| range.contains(&assigned_id)).unwrap_or(true);
+
+                if let (true, Some(next_element_id)) = (in_range, self.next_element_id.as_mut()) {
+                    if *next_element_id <= assigned_id {
+                        *next_element_id = assigned_id+1;
+                    }
+                }
+
+                // Nothing else to do if the element ID is already assigned
                 element_id
             } else {
                 let next_element_id = if let Some(element_id) = self.next_element_id.as_mut() {
@@ -152,6 +181,46 @@ impl StreamAnimationCore {
         }
     }
 
+    ///
+    /// Restricts the element IDs assigned by this animation to a range
+    ///
+    pub fn set_element_id_range<'a>(&'a mut self, range: Range<i64>) -> impl 'a+Future<Output=()> {
+        async move {
+            if self.element_id_range.as_ref() == Some(&range) {
+                // Carry on from the last ID we assigned
+                return;
+            }
+
+            // Carry on after any IDs that are already in use in the range (other editors may have used higher IDs from their own ranges)
+            let next_id = match self.request_one(StorageCommand::ReadHighestUnusedElementIdInRange(range.clone())).await {
+                Some(StorageResponse::HighestUnusedElementId(next_id))  => next_id,
+                _                                                       => range.start
+            };
+
+            self.next_element_id    = Some(next_id);
+            self.element_id_range   = Some(range);
+        }
+    }
+
+    ///
+    /// Assigns a list of element IDs, adding new IDs to the end until there are at least `count` of them
+    ///
+    fn assign_element_ids<'a>(&'a mut self, element_ids: &'a Vec<ElementId>, count: usize) -> impl 'a+Future<Output=Vec<ElementId>> {
+        async move {
+            let mut assigned = Vec::with_capacity(element_ids.len().max(count));
+
+            for element_id in element_ids.iter() {
+                assigned.push(self.assign_element_id(*element_id).await);
+            }
+
+            while assigned.len() < count {
+                assigned.push(self.assign_element_id(ElementId::Unassigned).await);
+            }
+
+            assigned
+        }
+    }
+
     ///
     /// Updates any edit log entries so they don't use an unassigned element ID
     /// 
@@ -174,9 +243,18 @@ impl StreamAnimationCore {
                 Layer(layer_id, Paint(when, BrushStroke(element, points))) =>
                     Layer(*layer_id, Paint(*when, BrushStroke(self.assign_element_id(*element).await, points.clone()))),
 
+                Layer(layer_id, Paint(when, CreateBrushStroke(element, points))) =>
+                    Layer(*layer_id, Paint(*when, CreateBrushStroke(self.assign_element_id(*element).await, points.clone()))),
+
                 Layer(layer_id, Paint(when, Fill(element, point, options))) =>
                     Layer(*layer_id, Paint(*when, Fill(self.assign_element_id(*element).await, point.clone(), options.clone()))),
 
+                Layer(layer_id, Paint(when, PlaceImage(element, image_id, position, size, opacity))) =>
+                    Layer(*layer_id, Paint(*when, PlaceImage(self.assign_element_id(*element).await, *image_id, *position, *size, *opacity))),
+
+                Layer(layer_id, Paint(when, CreateText(element, text, style, position))) =>
+                    Layer(*layer_id, Paint(*when, CreateText(self.assign_element_id(*element).await, text.clone(), style.clone(), *position))),
+
                 Layer(layer_id, Path(when, PathEdit::CreatePath(element, points))) =>
                     Layer(*layer_id, Path(*when, PathEdit::CreatePath(self.assign_element_id(*element).await, points.clone()))),
 
@@ -189,6 +267,28 @@ impl StreamAnimationCore {
                 Element(elements, Group(group_id, group_type)) =>
                     Element(elements.clone(), Group(self.assign_element_id(*group_id).await, *group_type)),
 
+                Element(elements, CombinePaths(new_id, operation)) =>
+                    Element(elements.clone(), CombinePaths(self.assign_element_id(*new_id).await, *operation)),
+
+                Annotation(annotation_id, AnnotationEdit::Create(annotation)) =>
+                    Annotation(self.assign_element_id(*annotation_id).await, AnnotationEdit::Create(annotation.clone())),
+
+                AddImage(image_id, image) =>
+                    AddImage(self.assign_element_id(*image_id).await, Arc::clone(image)),
+
+                AddAudioClip(clip_id, clip) =>
+                    AddAudioClip(self.assign_element_id(*clip_id).await, Arc::clone(clip)),
+
+                Layer(layer_id, DuplicateKeyFrame(from, to, new_ids)) => {
+                    let num_elements = if new_ids.len() > 0 { 0 } else { self.key_frame_to_duplicate(*layer_id, *from, *to).await.map(|keyframe| keyframe.elements.len()).unwrap_or(0) };
+                    Layer(*layer_id, DuplicateKeyFrame(*from, *to, self.assign_element_ids(new_ids, num_elements).await))
+                }
+
+                Layer(layer_id, BakeInbetweens(when, every, new_ids)) => {
+                    let num_elements = if new_ids.len() > 0 { 0 } else { self.inbetweens_to_bake(*layer_id, when.clone(), *every).await.iter().map(|(_, keyframe)| keyframe.elements.len()).sum() };
+                    Layer(*layer_id, BakeInbetweens(when.clone(), *every, self.assign_element_ids(new_ids, num_elements).await))
+                }
+
                 other => other.clone()
             }
         }
@@ -198,43 +298,95 @@ impl StreamAnimationCore {
     /// Performs a set of edits on the core
     ///
     pub fn perform_edits<'a>(&'a mut self, edits: Arc<Vec<AnimationEdit>>) -> impl 'a+Future<Output=()> {
+        self.perform_edits_recording_undo(edits, true)
+    }
+
+    ///
+    /// Performs a set of edits on the core without adding an undo step for them
+    ///
+    /// Edits that follow a `SelectUndoHistory` are still recorded in the history that it selects
+    ///
+    pub fn perform_edits_without_undo<'a>(&'a mut self, edits: Arc<Vec<AnimationEdit>>) -> impl 'a+Future<Output=()> {
+        self.perform_edits_recording_undo(edits, false)
+    }
+
+    ///
+    /// Performs a set of edits on the core, optionally recording them as a step in the undo history
+    ///
+    fn perform_edits_recording_undo<'a>(&'a mut self, edits: Arc<Vec<AnimationEdit>>, record_undo: bool) -> impl 'a+Future<Output=()> {
         async move {
-            // Assign IDs to the edits
-            let mut mapped_edits    = Vec::with_capacity(edits.len());
-            for edit in edits.iter() {
-                mapped_edits.push(self.assign_element_id_to_edit_log(edit).await);
-            }
-            let edits               = mapped_edits;
-
-            // Send the edits to the edit log by serializing them
-            let edit_log = edits.iter()
-                .map(|edit| {
-                    let mut serialized = String::new();
-                    edit.serialize(&mut serialized);
-                    serialized
-                })
-                .map(|edit| StorageCommand::WriteEdit(edit))
-                .collect::<Vec<_>>();
+            // Each set of edits is undone as a single step, in undo history 0 unless another history is selected
+            self.undo_history = 0;
+            if record_undo { self.start_undo_step(); }
 
-            self.request(edit_log).await;
+            // The edits are written to the edit log once they've all been performed
+            let mut edit_log = vec![];
 
             // Process the edits in the order that they arrive
             for edit in edits.iter() {
                 use self::AnimationEdit::*;
 
+                // Assign IDs to the edit (this happens as the edit is performed, as some edits need the IDs of the elements created by the edits before them)
+                let edit = self.assign_element_id_to_edit_log(edit).await;
+                let edit = &edit;
+
+                edit_log.push(Self::edit_log_command(edit));
+
                 // Edit the elements
                 match edit {
                     Layer(layer_id, layer_edit)             => { self.layer_edit(*layer_id, layer_edit).await; }
                     Element(element_ids, element_edit)      => { self.element_edit(element_ids, element_edit).await; }
                     Motion(motion_id, motion_edit)          => { self.motion_edit(*motion_id, motion_edit).await; }
+                    Annotation(annotation_id, edit)         => { self.annotation_edit(*annotation_id, edit).await; }
+                    AddImage(image_id, image)               => { self.add_image(*image_id, image).await; }
+                    AddAudioClip(clip_id, clip)             => { self.add_audio_clip(*clip_id, clip).await; }
                     SetSize(width, height)                  => { self.set_size(*width, *height).await }
+                    SetFrameLength(length)                  => { self.set_frame_length(*length).await }
+                    SetDuration(duration)                   => { self.set_duration(*duration).await }
                     AddNewLayer(layer_id)                   => { self.add_new_layer(*layer_id).await; }
                     RemoveLayer(layer_id)                   => { self.remove_layer(*layer_id).await; }
+
+                    SelectUndoHistory(undo_history)         => {
+                        self.finish_undo_step().await;
+                        self.undo_history = *undo_history;
+                        self.start_undo_step();
+                    }
+
+                    Undo                                    => {
+                        let recording = self.undo_step.is_some();
+                        self.finish_undo_step().await;
+                        self.undo().await;
+                        if recording { self.start_undo_step(); }
+                    }
+
+                    Redo                                    => {
+                        let recording = self.undo_step.is_some();
+                        self.finish_undo_step().await;
+                        self.redo().await;
+                        if recording { self.start_undo_step(); }
+                    }
                 }
             }
+
+            self.finish_undo_step().await;
+
+            // Write out the edits that were performed
+            if edit_log.len() > 0 {
+                self.request_unrecorded(edit_log).await;
+            }
         }
     }
 
+    ///
+    /// Creates the storage command that writes an edit to the edit log
+    ///
+    fn edit_log_command(edit: &AnimationEdit) -> StorageCommand {
+        let mut serialized = String::new();
+        edit.serialize_for_edit_log(&mut serialized);
+
+        StorageCommand::WriteEdit(serialized)
+    }
+
     ///
     /// Loads the keyframe containing the specified moment
     ///
@@ -244,6 +396,37 @@ impl StreamAnimationCore {
         }
     }
 
+    ///
+    /// Loads the keyframe containing the specified moment, replacing its drawing with the in-between with the following keyframe
+    ///
+    /// If the moment is at the start of a keyframe or there's no following keyframe, this returns the keyframe unaltered.
+    ///
+    pub fn load_inbetween_keyframe<'a>(&'a mut self, layer_id: u64, when: Duration, inbetween_mode: InbetweenMode) -> impl 'a + Future<Output=Option<KeyFrameCore>> {
+        async move {
+            // Find the start and end of the keyframe containing this moment
+            let keyframes   = self.request(vec![StorageCommand::ReadKeyFrames(layer_id, when..(when + Duration::from_micros(1)))]).await.unwrap_or_else(|| vec![]);
+            let keyframe    = keyframes.into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::KeyFrame(start, end)   => Some((start, end)),
+                    _                                       => None
+                })
+                .filter(|(start, end)| *start <= when && *end > when)
+                .nth(0);
+
+            match keyframe {
+                Some((start, end)) if start < when && end != Duration::from_micros(i64::max_value() as u64) => {
+                    // Generate the in-between from the keyframes on either side of this moment
+                    let from    = self.load_keyframe(layer_id, start).await?;
+                    let to      = self.load_keyframe(layer_id, end).await?;
+
+                    Some(from.inbetween(&to, when, inbetween_mode))
+                }
+
+                _ => self.load_keyframe(layer_id, when).await
+            }
+        }
+    }
+
     ///
     /// Updates the cached keyframe to be at the specific time/layer if it's not already
     ///
@@ -290,9 +473,10 @@ impl StreamAnimationCore {
     }
 
     ///
-    /// Sets the size of the animation
+    /// Updates the file properties for this animation
     ///
-    pub fn set_size<'a>(&'a mut self, width: f64, height: f64) -> impl 'a+Future<Output=()> {
+    fn update_file_properties<'a, UpdateFn>(&'a mut self, update_fn: UpdateFn) -> impl 'a+Future<Output=()>
+    where UpdateFn: 'a+Send+FnOnce(&mut FileProperties) {
         async move {
             // Get the current animation properties
             let properties      = self.request_one(StorageCommand::ReadAnimationProperties).await;
@@ -303,16 +487,37 @@ impl StreamAnimationCore {
             };
             let mut properties  = properties.unwrap_or_else(|| FileProperties::default());
 
-            // Update the size
-            properties.size     = (width, height);
+            // Update the properties
+            update_fn(&mut properties);
 
-            // Send the new file size to the storage
+            // Send the new properties to the storage
             let mut new_properties = String::new();
             properties.serialize(&mut new_properties);
             self.request_one(StorageCommand::WriteAnimationProperties(new_properties)).await;
         }
     }
 
+    ///
+    /// Sets the size of the animation
+    ///
+    pub fn set_size<'a>(&'a mut self, width: f64, height: f64) -> impl 'a+Future<Output=()> {
+        self.update_file_properties(move |properties| properties.size = (width, height))
+    }
+
+    ///
+    /// Sets the length of a frame in the animation
+    ///
+    pub fn set_frame_length<'a>(&'a mut self, frame_length: Duration) -> impl 'a+Future<Output=()> {
+        self.update_file_properties(move |properties| properties.frame_length = frame_length)
+    }
+
+    ///
+    /// Sets the total length of the animation
+    ///
+    pub fn set_duration<'a>(&'a mut self, duration: Duration) -> impl 'a+Future<Output=()> {
+        self.update_file_properties(move |properties| properties.duration = duration)
+    }
+
     ///
     /// Adds a key frame to a layer
     ///
@@ -330,4 +535,232 @@ impl StreamAnimationCore {
             self.request_one(StorageCommand::DeleteKeyFrame(layer_id, when)).await;
         } 
     }
+
+    ///
+    /// Moves a key frame to a new time, along with the drawing on it
+    ///
+    /// Nothing happens if there's no keyframe at the original time, or if there's already a keyframe at the new time.
+    ///
+    pub fn move_key_frame<'a>(&'a mut self, layer_id: u64, from: Duration, to: Duration) -> impl 'a+Future<Output=()> {
+        async move {
+            if from == to || !self.keyframe_exists(layer_id, from).await || self.keyframe_exists(layer_id, to).await {
+                return;
+            }
+
+            // Read the keyframe before it moves so we know which elements depend on its start time
+            let keyframe = match self.load_keyframe(layer_id, from).await {
+                Some(keyframe)  => keyframe,
+                None            => { return; }
+            };
+
+            // The storage moves the keyframe and its attachments: only the elements whose timing has changed need to be rewritten
+            if let Some(StorageResponse::Updated) = self.request_one(StorageCommand::MoveKeyFrame(layer_id, from, to)).await {
+                self.request(keyframe.move_elements_to(to)).await;
+            }
+
+            self.cached_keyframe = None;
+        }
+    }
+
+    ///
+    /// Returns the keyframe that will be copied by a `DuplicateKeyFrame` edit
+    ///
+    /// This is `None` if there's no keyframe at the original time, or if there's already a keyframe at the new time.
+    ///
+    fn key_frame_to_duplicate<'a>(&'a mut self, layer_id: u64, from: Duration, to: Duration) -> impl 'a+Future<Output=Option<KeyFrameCore>> {
+        async move {
+            if from == to || !self.keyframe_exists(layer_id, from).await || self.keyframe_exists(layer_id, to).await {
+                return None;
+            }
+
+            self.load_keyframe(layer_id, from).await
+        }
+    }
+
+    ///
+    /// Maps the elements of a keyframe to the new IDs they'll be given when they're copied
+    ///
+    /// The new IDs are taken in order, matching the elements sorted by their existing ID. Elements left over once the
+    /// new IDs run out are not mapped (and so will not be copied).
+    ///
+    fn new_element_ids<'a>(keyframe: &KeyFrameCore, new_ids: &mut impl Iterator<Item=&'a ElementId>) -> HashMap<ElementId, ElementId> {
+        let mut element_ids = keyframe.elements.keys().cloned().collect::<Vec<_>>();
+        element_ids.sort_by_key(|element_id| element_id.id());
+
+        element_ids.into_iter()
+            .zip(new_ids)
+            .map(|(old_id, new_id)| (old_id, *new_id))
+            .collect()
+    }
+
+    ///
+    /// Creates a new key frame containing a copy of the drawing on an existing keyframe
+    ///
+    /// Nothing happens if there's no keyframe at the original time, or if there's already a keyframe at the new time. The
+    /// copied elements are given the IDs from `new_ids`, which are assigned when the edit is written to the log.
+    ///
+    pub fn duplicate_key_frame<'a>(&'a mut self, layer_id: u64, from: Duration, to: Duration, new_ids: &'a Vec<ElementId>) -> impl 'a+Future<Output=()> {
+        async move {
+            let keyframe = match self.key_frame_to_duplicate(layer_id, from, to).await {
+                Some(keyframe)  => keyframe,
+                None            => { return; }
+            };
+
+            // Create the new keyframe and copy the elements into it
+            let new_ids = Self::new_element_ids(&keyframe, &mut new_ids.iter());
+
+            self.request_one(StorageCommand::AddKeyFrame(layer_id, to)).await;
+            self.request(keyframe.duplicate_elements_to(to, &new_ids)).await;
+
+            self.cached_keyframe = None;
+        }
+    }
+
+    ///
+    /// Reads the start times of the keyframes in a layer that start within a time range
+    ///
+    fn key_frames_starting_in<'a>(&'a mut self, layer_id: u64, when: Range<Duration>) -> impl 'a+Future<Output=Vec<Duration>> {
+        async move {
+            let keyframes = self.request(vec![StorageCommand::ReadKeyFrames(layer_id, when.clone())]).await.unwrap_or_else(|| vec![]);
+
+            keyframes.into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::KeyFrame(start, _) => Some(start),
+                    _                                   => None
+                })
+                .filter(|start| when.contains(start))
+                .collect()
+        }
+    }
+
+    ///
+    /// Works out where each of the keyframes that start within one time range will end up when they're spread across another time range
+    ///
+    /// Returns the list of `(original time, new time)` pairs for the keyframes, or `None` if either range is empty or if any keyframe
+    /// would land on top of another keyframe.
+    ///
+    fn key_frame_retimes<'a>(&'a mut self, layer_id: u64, from: Range<Duration>, to: Range<Duration>) -> impl 'a+Future<Output=Option<Vec<(Duration, Duration)>>> {
+        async move {
+            if from.end <= from.start || to.end <= to.start {
+                return None;
+            }
+
+            // Work out where each keyframe ends up (keyframes are stored to the nearest microsecond)
+            let keyframes   = self.key_frames_starting_in(layer_id, from.clone()).await;
+            let micros      = |when: Duration| when.as_micros() as f64;
+            let scale       = (micros(to.end) - micros(to.start)) / (micros(from.end) - micros(from.start));
+            let moves       = keyframes.into_iter()
+                .map(|start| (start, micros(to.start) + (micros(start) - micros(from.start)) * scale))
+                .map(|(start, new_start)| (start, Duration::from_micros(new_start.round() as u64)))
+                .collect::<Vec<_>>();
+
+            // A keyframe collides if it lands on a keyframe that isn't being moved, or if rounding puts it on the same time as a keyframe that is
+            let staying     = self.key_frames_starting_in(layer_id, to.start..(to.end + Duration::from_micros(1))).await
+                .into_iter()
+                .filter(|start| !from.contains(start))
+                .collect::<HashSet<_>>();
+            let mut landed  = HashSet::new();
+            let collides    = moves.iter()
+                .any(|(_start, new_start)| staying.contains(new_start) || !landed.insert(*new_start));
+
+            if collides {
+                None
+            } else {
+                Some(moves)
+            }
+        }
+    }
+
+    ///
+    /// Moves the key frames that start within one time range so they're spread out across another time range
+    ///
+    /// Nothing is moved if either range is empty, or if any of the keyframes would land on top of another keyframe.
+    ///
+    pub fn retime_key_frames<'a>(&'a mut self, layer_id: u64, from: Range<Duration>, to: Range<Duration>) -> impl 'a+Future<Output=()> {
+        async move {
+            let moves = match self.key_frame_retimes(layer_id, from, to).await {
+                Some(moves) => moves,
+                None        => { return; }
+            };
+
+            // Keyframes moving earlier are moved first, starting with the earliest, then the keyframes moving later starting with the latest.
+            // As the keyframes stay in the same order, this ensures that no keyframe is moved on top of one that hasn't moved yet.
+            let earlier     = moves.iter().filter(|(start, new_start)| new_start < start);
+            let later       = moves.iter().filter(|(start, new_start)| new_start > start).rev();
+
+            for (start, new_start) in earlier.chain(later).cloned().collect::<Vec<_>>() {
+                self.move_key_frame(layer_id, start, new_start).await;
+            }
+        }
+    }
+
+    ///
+    /// Generates the keyframes that will be created by a `BakeInbetweens` edit, along with the times they'll be created at
+    ///
+    fn inbetweens_to_bake<'a>(&'a mut self, layer_id: u64, when: Range<Duration>, every: Duration) -> impl 'a+Future<Output=Vec<(Duration, KeyFrameCore)>> {
+        async move {
+            if when.end <= when.start || every == Duration::from_millis(0) {
+                return vec![];
+            }
+
+            // Layers with in-betweening turned off are baked by matching their elements in order
+            let inbetween_mode  = match self.layer_properties(layer_id).await.inbetween_mode {
+                InbetweenMode::Off  => InbetweenMode::MatchByOrder,
+                other               => other
+            };
+
+            // Find the pairs of keyframes that overlap the range
+            let end_of_time     = Duration::from_micros(i64::max_value() as u64);
+            let keyframes       = self.request(vec![StorageCommand::ReadKeyFrames(layer_id, when.clone())]).await.unwrap_or_else(|| vec![]);
+            let keyframes       = keyframes.into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::KeyFrame(start, end)   => Some((start, end)),
+                    _                                       => None
+                })
+                .filter(|(_start, end)| *end != end_of_time)
+                .collect::<Vec<_>>();
+
+            let mut inbetweens  = vec![];
+
+            for (start, end) in keyframes {
+                let from    = match self.load_keyframe(layer_id, start).await { Some(keyframe) => keyframe, None => { continue; } };
+                let to      = match self.load_keyframe(layer_id, end).await { Some(keyframe) => keyframe, None => { continue; } };
+
+                // Frames are counted from the start of the keyframe
+                let mut frame_time = start + every;
+
+                while frame_time < end {
+                    if when.contains(&frame_time) {
+                        inbetweens.push((frame_time, from.inbetween(&to, frame_time, inbetween_mode)));
+                    }
+
+                    frame_time += every;
+                }
+            }
+
+            inbetweens
+        }
+    }
+
+    ///
+    /// Turns the in-betweens within a time range into real keyframes, creating a new keyframe every time the `every` duration elapses
+    ///
+    /// The elements in the new keyframes are given the IDs from `new_ids` in order, which are assigned when the edit is written to the log.
+    ///
+    pub fn bake_inbetweens<'a>(&'a mut self, layer_id: u64, when: Range<Duration>, every: Duration, new_ids: &'a Vec<ElementId>) -> impl 'a+Future<Output=()> {
+        async move {
+            // The in-betweens are all generated from the original keyframes before any new ones are added
+            let inbetweens  = self.inbetweens_to_bake(layer_id, when, every).await;
+            let mut new_ids = new_ids.iter();
+
+            for (frame_time, inbetween) in inbetweens {
+                let new_ids = Self::new_element_ids(&inbetween, &mut new_ids);
+
+                self.request_one(StorageCommand::AddKeyFrame(layer_id, frame_time)).await;
+                self.request(inbetween.duplicate_elements_to(frame_time, &new_ids)).await;
+            }
+
+            self.cached_keyframe = None;
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use super::keyframe_core::*;
 use crate::traits::*;
+use crate::storage::layer_properties::*;
 
 use flo_canvas::*;
 
@@ -14,17 +15,29 @@ pub struct StreamFrame {
     frame_time: Duration,
 
     /// The keyframe that was retrieved for this frame (or none if no keyframe was retrieved)
-    keyframe_core: Option<Arc<KeyFrameCore>>
+    keyframe_core: Option<Arc<KeyFrameCore>>,
+
+    /// False if the layer this frame is from is hidden
+    visible: bool,
+
+    /// The opacity of the layer this frame is from
+    opacity: f64,
+
+    /// The blend mode of the layer this frame is from
+    blend_mode: BlendMode
 }
 
 impl StreamFrame {
     ///
     /// Creates a new stream frame
     ///
-    pub (super) fn new(frame_time: Duration, keyframe_core: Option<KeyFrameCore>) -> StreamFrame {
+    pub (super) fn new(frame_time: Duration, keyframe_core: Option<KeyFrameCore>, layer_properties: &LayerProperties) -> StreamFrame {
         StreamFrame {
             frame_time:     frame_time,
-            keyframe_core:  keyframe_core.map(|core| Arc::new(core))
+            keyframe_core:  keyframe_core.map(|core| Arc::new(core)),
+            visible:        layer_properties.visible,
+            opacity:        layer_properties.opacity,
+            blend_mode:     layer_properties.blend_mode
         }
     }
 
@@ -84,6 +97,11 @@ impl Frame for StreamFrame {
     /// Renders this frame to a particular graphics context
     ///
     fn render_to(&self, gc: &mut dyn GraphicsPrimitives) {
+        // Nothing is drawn for hidden layers
+        if !self.visible {
+            return;
+        }
+
         // Set up the properties
         let mut properties;
         let mut active_attachments  = vec![];
@@ -132,6 +150,20 @@ impl Frame for StreamFrame {
         }
     }
 
+    ///
+    /// The blend mode to use when compositing the canvas layer this frame is rendered to with the layers underneath it
+    ///
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    ///
+    /// The alpha value to use when compositing the canvas layer this frame is rendered to with the layers underneath it
+    ///
+    fn opacity(&self) -> f64 {
+        self.opacity
+    }
+
     ///
     /// Applies all of the properties for the specified element (including those added by attached elements)
     ///
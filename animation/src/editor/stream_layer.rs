@@ -6,6 +6,7 @@ use crate::storage::layer_properties::*;
 use crate::traits::*;
 
 use ::desync::*;
+use flo_canvas::{BlendMode};
 use futures::prelude::*;
 
 use std::sync::*;
@@ -67,13 +68,86 @@ impl Layer for StreamLayer {
         Some(self.properties.name.clone())
     }
 
+    ///
+    /// True if this layer should be rendered
+    ///
+    fn is_visible(&self) -> bool {
+        self.properties.visible
+    }
+
+    ///
+    /// True if this layer is locked (paint and path edits to a locked layer are ignored)
+    ///
+    fn is_locked(&self) -> bool {
+        self.properties.locked
+    }
+
+    ///
+    /// The opacity of this layer (0.0 is fully transparent, 1.0 is fully opaque)
+    ///
+    fn opacity(&self) -> f64 {
+        self.properties.opacity
+    }
+
+    ///
+    /// How this layer is blended with the layers underneath it
+    ///
+    fn blend_mode(&self) -> BlendMode {
+        self.properties.blend_mode
+    }
+
+    ///
+    /// How the frames between the keyframes of this layer are generated
+    ///
+    fn inbetween_mode(&self) -> InbetweenMode {
+        self.properties.inbetween_mode
+    }
+
     ///
     /// The types of edit that are supported by this layer
     ///
     fn supported_edit_types(&self) -> Vec<LayerEditType> {
-        vec![
-            LayerEditType::Vector
-        ]
+        let mut edit_types = vec![
+            LayerEditType::Vector,
+            LayerEditType::Image
+        ];
+
+        if self.properties.has_audio {
+            edit_types.push(LayerEditType::Audio);
+        }
+
+        edit_types
+    }
+
+    ///
+    /// Retrieves the ID of the audio clip attached to this layer, if there is one
+    ///
+    fn audio_clip_id(&self) -> Option<ElementId> {
+        if !self.properties.has_audio {
+            return None;
+        }
+
+        let mut response = self.request_sync(vec![StorageCommand::ReadLayerAudio(self.layer_id)]).unwrap_or_else(|| vec![]);
+
+        match response.pop() {
+            Some(StorageResponse::LayerAudio(_, clip_id))   => Some(ElementId::Assigned(clip_id)),
+            _                                               => None
+        }
+    }
+
+    ///
+    /// Retrieves the audio clip attached to this layer, if there is one
+    ///
+    fn audio_clip(&self) -> Option<Arc<AudioClip>> {
+        // The layer refers to one of the stored audio clips
+        let clip_id      = self.audio_clip_id()?.id()?;
+
+        let mut response = self.request_sync(vec![StorageCommand::ReadAudioClip(clip_id)]).unwrap_or_else(|| vec![]);
+
+        match response.pop() {
+            Some(StorageResponse::AudioClip(_, audio))      => AudioClip::deserialize(&mut audio.chars()).map(|clip| Arc::new(clip)),
+            _                                               => None
+        }
     }
 
     ///
@@ -83,6 +157,7 @@ impl Layer for StreamLayer {
         // Retrieve the keyframe from the core
         let core            = Arc::clone(&self.core);
         let layer_id        = self.layer_id;
+        let inbetween_mode  = self.properties.inbetween_mode;
         let keyframe_core   = Desync::new(None);
 
         // Load into the keyframe_core desync
@@ -90,7 +165,11 @@ impl Layer for StreamLayer {
             async move {
                 *frame = core.future(move |core| {
                     async move {
-                        core.load_keyframe(layer_id, time_index).await
+                        if inbetween_mode != InbetweenMode::Off {
+                            core.load_inbetween_keyframe(layer_id, time_index, inbetween_mode).await
+                        } else {
+                            core.load_keyframe(layer_id, time_index).await
+                        }
                     }.boxed()
                 }).await.unwrap_or(None);
             }.boxed()
@@ -100,7 +179,7 @@ impl Layer for StreamLayer {
         let keyframe_core   = keyframe_core.sync(|frame| frame.take());
 
         // Create a frame with the keyframe core
-        Arc::new(StreamFrame::new(time_index, keyframe_core))
+        Arc::new(StreamFrame::new(time_index, keyframe_core, &self.properties))
     }
 
     ///
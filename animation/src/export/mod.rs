@@ -0,0 +1,7 @@
+//!
+//! Exporting animation frames to other file formats
+//!
+
+mod svg;
+
+pub use self::svg::*;
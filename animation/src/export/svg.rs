@@ -0,0 +1,1051 @@
+use super::super::traits::*;
+
+use flo_canvas::*;
+
+use std::sync::*;
+use std::fmt::Write;
+use std::time::Duration;
+use std::collections::{HashMap};
+
+///
+/// The part of the SVG writer's state that is saved and restored by `PushState` and `PopState`
+///
+#[derive(Clone)]
+struct SvgState {
+    /// How paths are filled
+    fill_style: FillStyle,
+
+    /// The colour stops for the next gradient fill
+    gradient_stops: Vec<(f32, Color)>,
+
+    /// The colour used for strokes
+    stroke_color: Color,
+
+    /// The width of a stroke
+    line_width: f32,
+
+    /// True if the line width is measured in pixels rather than canvas units
+    line_width_pixels: bool,
+
+    /// How lines are joined
+    line_join: LineJoin,
+
+    /// How lines are capped
+    line_cap: LineCap,
+
+    /// The dash pattern for strokes (empty for a solid line)
+    dash_pattern: Vec<f32>,
+
+    /// The offset into the dash pattern
+    dash_offset: f32,
+
+    /// How new elements are blended with what's beneath them
+    blend_mode: BlendMode,
+
+    /// The transformation that's applied to new paths
+    transform: Transform2D,
+
+    /// The transformation that's applied to sprites when they're drawn
+    sprite_transform: Transform2D,
+
+    /// The ID of the clipping path that's currently in effect
+    clip_path: Option<usize>
+}
+
+///
+/// Converts a series of canvas drawing instructions into an SVG document
+///
+/// Drawing instructions are expected to be in canvas coordinates, where y increases upwards: the document
+/// flips these so that the image appears the same way up as it does in the editor.
+///
+pub struct SvgWriter {
+    /// The current drawing state
+    state: SvgState,
+
+    /// States saved by PushState
+    state_stack: Vec<SvgState>,
+
+    /// The path data for the path that's currently being defined
+    path: String,
+
+    /// The definitions section of the document (clipping paths and fill patterns)
+    definitions: String,
+
+    /// The elements that make up the body of the document
+    body: String,
+
+    /// The groups that are currently open (the position in the body where the group started, and the length of the opening tag)
+    open_groups: Vec<(usize, usize)>,
+
+    /// The index in `open_groups` of the group for the layer that's currently being written
+    layer_group: Option<usize>,
+
+    /// The contents of the masks generated by erasing paths
+    masks: Vec<String>,
+
+    /// The ID to assign to the next clipping path
+    next_clip_id: usize,
+
+    /// The ID to assign to the next gradient or pattern fill
+    next_fill_id: usize,
+
+    /// The drawing instructions for each sprite that has been declared
+    sprites: HashMap<SpriteId, Vec<Draw>>,
+
+    /// The sprite that drawing instructions are currently being sent to
+    current_sprite: Option<SpriteId>
+}
+
+impl SvgState {
+    ///
+    /// Creates the default drawing state
+    ///
+    fn new() -> SvgState {
+        SvgState {
+            fill_style:         FillStyle::Solid(Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            gradient_stops:     vec![],
+            stroke_color:       Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            line_width:         1.0,
+            line_width_pixels:  false,
+            line_join:          LineJoin::Round,
+            line_cap:           LineCap::Butt,
+            dash_pattern:       vec![],
+            dash_offset:        0.0,
+            blend_mode:         BlendMode::SourceOver,
+            transform:          Transform2D::identity(),
+            sprite_transform:   Transform2D::identity(),
+            clip_path:          None
+        }
+    }
+}
+
+impl SvgWriter {
+    ///
+    /// Creates a new SVG writer with an empty document
+    ///
+    pub fn new() -> SvgWriter {
+        SvgWriter {
+            state:          SvgState::new(),
+            state_stack:    vec![],
+            path:           String::new(),
+            definitions:    String::new(),
+            body:           String::new(),
+            open_groups:    vec![],
+            layer_group:    None,
+            masks:          vec![],
+            next_clip_id:   0,
+            next_fill_id:   0,
+            sprites:        HashMap::new(),
+            current_sprite: None
+        }
+    }
+
+    ///
+    /// Formats a number for use in an SVG attribute
+    ///
+    fn number(value: f32) -> String {
+        let rounded = (value * 1000.0).round() / 1000.0;
+
+        if rounded == 0.0 {
+            // Avoid writing '-0'
+            "0".to_string()
+        } else {
+            format!("{}", rounded)
+        }
+    }
+
+    ///
+    /// Formats a colour as an SVG colour and opacity value
+    ///
+    fn color(color: &Color) -> (String, String) {
+        let (r, g, b, a)    = color.to_rgba_components();
+        let component       = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+
+        (format!("#{:02x}{:02x}{:02x}", component(r), component(g), component(b)), Self::number(a.max(0.0).min(1.0)))
+    }
+
+    ///
+    /// Formats a transform as an SVG transform attribute value
+    ///
+    fn transform(transform: &Transform2D) -> String {
+        let Transform2D(matrix) = transform;
+
+        format!("matrix({} {} {} {} {} {})",
+            Self::number(matrix[0][0]), Self::number(matrix[1][0]),
+            Self::number(matrix[0][1]), Self::number(matrix[1][1]),
+            Self::number(matrix[0][2]), Self::number(matrix[1][2]))
+    }
+
+    ///
+    /// Returns the CSS name for a blend mode
+    ///
+    fn blend_mode(blend_mode: BlendMode) -> Option<&'static str> {
+        use self::BlendMode::*;
+
+        match blend_mode {
+            Multiply    => Some("multiply"),
+            Screen      => Some("screen"),
+            Darken      => Some("darken"),
+            Lighten     => Some("lighten"),
+
+            // Erasing is written as a mask (see `erase()`). The other compositing modes have no direct equivalent in SVG, so are drawn as normal
+            _           => None
+        }
+    }
+
+    ///
+    /// Adds a command to the current path
+    ///
+    fn path_command(&mut self, command: char, points: &[(f32, f32)]) {
+        if !self.path.is_empty() {
+            self.path.push(' ');
+        }
+
+        self.path.push(command);
+
+        for (x, y) in points.iter() {
+            write!(self.path, " {} {}", Self::number(*x), Self::number(*y)).ok();
+        }
+    }
+
+    ///
+    /// Writes out the attributes common to all path elements
+    ///
+    fn common_attributes(&self) -> String {
+        let mut attributes = String::new();
+
+        if self.state.transform != Transform2D::identity() {
+            write!(attributes, " transform=\"{}\"", Self::transform(&self.state.transform)).ok();
+        }
+
+        if let Some(blend_mode) = Self::blend_mode(self.state.blend_mode) {
+            write!(attributes, " style=\"mix-blend-mode:{}\"", blend_mode).ok();
+        }
+
+        attributes
+    }
+
+    ///
+    /// True if paths are currently erasing what's beneath them instead of being drawn
+    ///
+    fn is_erasing(&self) -> bool {
+        self.state.blend_mode == BlendMode::DestinationOut
+    }
+
+    ///
+    /// Formats the colour to paint a path with: erasing paths are written to a mask, so they're painted black with the same opacity
+    ///
+    fn paint_color(&self, color: &Color) -> (String, String) {
+        if self.is_erasing() {
+            let (_, _, _, a) = color.to_rgba_components();
+            Self::color(&Color::Rgba(0.0, 0.0, 0.0, a))
+        } else {
+            Self::color(color)
+        }
+    }
+
+    ///
+    /// Writes a path element to the body of the document
+    ///
+    fn write_path(&mut self, attributes: String) {
+        if self.path.is_empty() {
+            return;
+        }
+
+        if self.is_erasing() {
+            self.erase(attributes);
+            return;
+        }
+
+        // Clipped paths are wrapped in a group so the clipping path is not affected by the path's own transform
+        if let Some(clip_id) = self.state.clip_path {
+            write!(self.body, "<g clip-path=\"url(#clip{})\">", clip_id).ok();
+        }
+
+        write!(self.body, "<path d=\"{}\"{}{}/>", self.path, attributes, self.common_attributes()).ok();
+
+        if self.state.clip_path.is_some() {
+            self.body.push_str("</g>");
+        }
+
+        self.body.push('\n');
+    }
+
+    ///
+    /// Erases the current path from everything that's been drawn on the current layer so far
+    ///
+    /// SVG has no way to erase, so the existing content of the layer is wrapped in a group with a mask: the mask is white
+    /// everywhere except where the path is drawn.
+    ///
+    fn erase(&mut self, attributes: String) {
+        // Everything in the current layer up to the start of the element that's being drawn is erased
+        let (layer_start, first_group)  = match self.layer_group {
+            Some(layer_group)   => { let (start_pos, tag_len) = self.open_groups[layer_group]; (start_pos + tag_len, layer_group + 1) },
+            None                => (0, 0)
+        };
+        let erase_end                   = self.open_groups.get(first_group).map(|(start_pos, _)| *start_pos).unwrap_or(self.body.len());
+
+        if erase_end <= layer_start {
+            // Nothing to erase
+            return;
+        }
+
+        // The path is drawn in the mask (which uses the same coordinates as the content it's applied to)
+        let mask_id         = self.masks.len();
+        let mut mask_path   = String::new();
+
+        if let Some(clip_id) = self.state.clip_path {
+            write!(mask_path, "<g clip-path=\"url(#clip{})\">", clip_id).ok();
+        }
+        write!(mask_path, "<path d=\"{}\"{}{}/>", self.path, attributes, self.common_attributes()).ok();
+        if self.state.clip_path.is_some() {
+            mask_path.push_str("</g>");
+        }
+
+        self.masks.push(mask_path);
+
+        // Wrap the erased content in a group that applies the mask
+        let open_tag    = format!("<g mask=\"url(#mask{})\">\n", mask_id);
+        let close_tag   = "</g>\n";
+
+        self.body.insert_str(erase_end, close_tag);
+        self.body.insert_str(layer_start, &open_tag);
+
+        // Groups that were opened after the erased content have moved
+        for (start_pos, _) in self.open_groups.iter_mut() {
+            if *start_pos >= erase_end {
+                *start_pos += open_tag.len() + close_tag.len();
+            }
+        }
+    }
+
+    ///
+    /// Writes the stops for a gradient definition
+    ///
+    fn gradient_stops(stops: &Vec<(f32, Color)>) -> String {
+        let mut stops = stops.clone();
+        stops.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        stops.into_iter()
+            .map(|(pos, color)| {
+                let (color, opacity) = Self::color(&color);
+                format!("<stop offset=\"{}\" stop-color=\"{}\" stop-opacity=\"{}\"/>", Self::number(pos), color, opacity)
+            })
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    ///
+    /// Adds a definition for the current gradient or texture fill, returning its ID
+    ///
+    fn define_fill(&mut self) -> Option<usize> {
+        let fill_id = self.next_fill_id;
+
+        match &self.state.fill_style {
+            FillStyle::Solid(_)                                     => { return None; }
+
+            FillStyle::LinearGradient(stops, (x1, y1), (x2, y2))    => {
+                write!(self.definitions, "<linearGradient id=\"fill{}\" gradientUnits=\"userSpaceOnUse\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">{}</linearGradient>\n",
+                    fill_id, Self::number(*x1), Self::number(*y1), Self::number(*x2), Self::number(*y2), Self::gradient_stops(stops)).ok();
+            }
+
+            FillStyle::RadialGradient(stops, (x, y), radius)        => {
+                write!(self.definitions, "<radialGradient id=\"fill{}\" gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" r=\"{}\">{}</radialGradient>\n",
+                    fill_id, Self::number(*x), Self::number(*y), Self::number(*radius), Self::gradient_stops(stops)).ok();
+            }
+
+            FillStyle::Texture(pattern, fg, bg, size)               => {
+                let (fg, fg_opacity)    = Self::color(fg);
+                let (bg, bg_opacity)    = Self::color(bg);
+                let half                = size / 2.0;
+
+                // The foreground shapes match the definitions in TexturePattern::is_foreground
+                let foreground          = match pattern {
+                    TexturePattern::Stripes => format!("<path d=\"M 0 0 L {h} 0 L 0 {h} Z M {s} 0 L {s} {h} L {h} {s} L 0 {s} Z\"", h=Self::number(half), s=Self::number(*size)),
+                    TexturePattern::Checks  => format!("<path d=\"M 0 0 H {h} V {h} H 0 Z M {h} {h} H {s} V {s} H {h} Z\"", h=Self::number(half), s=Self::number(*size)),
+                    TexturePattern::Dots    => format!("<circle cx=\"{h}\" cy=\"{h}\" r=\"{r}\"", h=Self::number(half), r=Self::number(size / 4.0))
+                };
+
+                write!(self.definitions, "<pattern id=\"fill{}\" patternUnits=\"userSpaceOnUse\" width=\"{s}\" height=\"{s}\"><rect width=\"{s}\" height=\"{s}\" fill=\"{}\" fill-opacity=\"{}\"/>{} fill=\"{}\" fill-opacity=\"{}\"/></pattern>\n",
+                    fill_id, bg, bg_opacity, foreground, fg, fg_opacity, s=Self::number(*size)).ok();
+            }
+        }
+
+        self.next_fill_id += 1;
+        Some(fill_id)
+    }
+
+    ///
+    /// Fills the current path
+    ///
+    fn fill(&mut self) {
+        if self.path.is_empty() {
+            return;
+        }
+
+        // Erasing paths are drawn into a mask, so they're always filled with a solid colour
+        let fill_id     = if self.is_erasing() { None } else { self.define_fill() };
+        let attributes  = match fill_id {
+            Some(fill_id)   => format!(" fill=\"url(#fill{})\"", fill_id),
+
+            None            => {
+                let (color, opacity) = self.paint_color(&self.state.fill_style.color_at((0.0, 0.0)));
+                let mut attributes   = format!(" fill=\"{}\"", color);
+
+                if opacity != "1" {
+                    write!(attributes, " fill-opacity=\"{}\"", opacity).ok();
+                }
+
+                attributes
+            }
+        };
+
+        self.write_path(attributes);
+    }
+
+    ///
+    /// Strokes the current path
+    ///
+    fn stroke(&mut self) {
+        let (color, opacity) = self.paint_color(&self.state.stroke_color);
+        let mut attributes   = format!(" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"", color, Self::number(self.state.line_width));
+
+        if opacity != "1" {
+            write!(attributes, " stroke-opacity=\"{}\"", opacity).ok();
+        }
+
+        let line_join = match self.state.line_join {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel"
+        };
+        let line_cap = match self.state.line_cap {
+            LineCap::Butt   => "butt",
+            LineCap::Round  => "round",
+            LineCap::Square => "square"
+        };
+        write!(attributes, " stroke-linejoin=\"{}\" stroke-linecap=\"{}\"", line_join, line_cap).ok();
+
+        if !self.state.dash_pattern.is_empty() {
+            let dash_pattern = self.state.dash_pattern.iter().map(|len| Self::number(*len)).collect::<Vec<_>>().join(" ");
+            write!(attributes, " stroke-dasharray=\"{}\" stroke-dashoffset=\"{}\"", dash_pattern, Self::number(self.state.dash_offset)).ok();
+        }
+
+        if self.state.line_width_pixels {
+            attributes.push_str(" vector-effect=\"non-scaling-stroke\"");
+        }
+
+        self.write_path(attributes);
+    }
+
+    ///
+    /// Sets the clipping path to the current path
+    ///
+    fn clip(&mut self) {
+        let clip_id         = self.next_clip_id;
+        self.next_clip_id   += 1;
+
+        // Clipping paths are defined in document coordinates, so they need to include the current transform
+        let transform = if self.state.transform != Transform2D::identity() {
+            format!(" transform=\"{}\"", Self::transform(&self.state.transform))
+        } else {
+            String::new()
+        };
+
+        // If there's an existing clipping path, the new one is clipped against it
+        let existing_clip = self.state.clip_path
+            .map(|existing_clip| format!(" clip-path=\"url(#clip{})\"", existing_clip))
+            .unwrap_or_else(|| String::new());
+
+        write!(self.definitions, "<clipPath id=\"clip{}\" clipPathUnits=\"userSpaceOnUse\"{}><path d=\"{}\"{}/></clipPath>\n", clip_id, existing_clip, self.path, transform).ok();
+
+        self.state.clip_path = Some(clip_id);
+    }
+
+    ///
+    /// Updates the transformation applied to sprites
+    ///
+    fn sprite_transform(&mut self, transform: SpriteTransform) {
+        match transform {
+            SpriteTransform::Identity   => { self.state.sprite_transform = Transform2D::identity(); }
+            other                       => { self.state.sprite_transform = self.state.sprite_transform * Transform2D::from(other); }
+        }
+    }
+
+    ///
+    /// Writes out the instructions for a sprite using the current sprite transform
+    ///
+    fn draw_sprite(&mut self, sprite_id: SpriteId) {
+        let sprite = match self.sprites.get(&sprite_id) {
+            Some(sprite)    => sprite.clone(),
+            None            => { return; }
+        };
+
+        // Sprites are drawn with the current state, but their own path
+        let state   = self.state.clone();
+        let path    = self.path.clone();
+
+        self.state.transform = self.state.transform * self.state.sprite_transform;
+        self.draw_all(sprite);
+
+        self.state  = state;
+        self.path   = path;
+    }
+
+    ///
+    /// Processes a single drawing instruction
+    ///
+    pub fn draw(&mut self, drawing: Draw) {
+        use self::Draw::*;
+
+        // Instructions sent to a sprite are stored until the sprite is drawn
+        if let Some(sprite_id) = self.current_sprite {
+            match drawing {
+                Sprite(_) | Layer(_) | ClearCanvas  => { }
+                ClearSprite                         => { self.sprites.insert(sprite_id, vec![]); return; }
+                other                               => { self.sprites.entry(sprite_id).or_insert_with(|| vec![]).push(other); return; }
+            }
+        }
+
+        match drawing {
+            NewPath                             => { self.path = String::new(); }
+            Move(x, y)                          => { self.path_command('M', &[(x, y)]); }
+            Line(x, y)                          => { self.path_command('L', &[(x, y)]); }
+            BezierCurve(end, cp1, cp2)          => { self.path_command('C', &[cp1, cp2, end]); }
+            ClosePath                           => { self.path_command('Z', &[]); }
+            Fill                                => { self.fill(); }
+            Stroke                              => { self.stroke(); }
+
+            LineWidth(width)                    => { self.state.line_width = width; self.state.line_width_pixels = false; }
+            LineWidthPixels(width)              => { self.state.line_width = width; self.state.line_width_pixels = true; }
+            LineJoin(join)                      => { self.state.line_join = join; }
+            LineCap(cap)                        => { self.state.line_cap = cap; }
+            NewDashPattern                      => { self.state.dash_pattern = vec![]; }
+            DashLength(length)                  => { self.state.dash_pattern.push(length); }
+            DashOffset(offset)                  => { self.state.dash_offset = offset; }
+            FillColor(color)                    => { self.state.fill_style = FillStyle::Solid(color); }
+            NewGradient                         => { self.state.gradient_stops = vec![]; }
+            GradientStop(pos, color)            => { self.state.gradient_stops.push((pos, color)); }
+            FillLinearGradient(start, end)      => { self.state.fill_style = FillStyle::LinearGradient(self.state.gradient_stops.clone(), start, end); }
+            FillRadialGradient(center, radius)  => { self.state.fill_style = FillStyle::RadialGradient(self.state.gradient_stops.clone(), center, radius); }
+            FillTexture(pattern, fg, bg, size)  => { self.state.fill_style = FillStyle::Texture(pattern, fg, bg, size); }
+            StrokeColor(color)                  => { self.state.stroke_color = color; }
+            BlendMode(blend_mode)               => { self.state.blend_mode = blend_mode; }
+
+            IdentityTransform                   => { self.state.transform = Transform2D::identity(); }
+            MultiplyTransform(transform)        => { self.state.transform = self.state.transform * transform; }
+
+            Clip                                => { self.clip(); }
+            Unclip                              => { self.state.clip_path = None; }
+
+            PushState                           => { self.state_stack.push(self.state.clone()); }
+            PopState                            => { if let Some(state) = self.state_stack.pop() { self.state = state; } }
+
+            // The document itself defines the coordinate scheme, so the instructions that set up the canvas are ignored
+            CanvasHeight(_)                     |
+            CenterRegion(_, _)                  => { }
+
+            // Sprites are written out with the sprite transform each time they're drawn
+            Sprite(sprite_id)                   => { self.current_sprite = Some(sprite_id); }
+            Layer(_)                            |
+            ClearCanvas                         => { self.current_sprite = None; }
+            SpriteTransform(transform)          => { self.sprite_transform(transform); }
+            DrawSprite(sprite_id)               => { self.draw_sprite(sprite_id); }
+
+            // Layers and stored buffers are not used by vector elements
+            Store                               |
+            Restore                             |
+            FreeStoredBuffer                    |
+            LayerBlend(_, _)                    |
+            LayerAlpha(_, _)                    |
+            ClearLayer                          |
+            ClearSprite                         => { }
+        }
+    }
+
+    ///
+    /// Processes a series of drawing instructions
+    ///
+    pub fn draw_all<DrawIter: IntoIterator<Item=Draw>>(&mut self, drawing: DrawIter) {
+        drawing.into_iter().for_each(|draw| self.draw(draw));
+    }
+
+    ///
+    /// Starts a new group in the document. Groups that end up with no content are left out of the document
+    ///
+    pub fn begin_group(&mut self, id: Option<String>) {
+        let tag         = match id {
+            Some(id)    => format!("<g id=\"{}\">\n", id),
+            None        => "<g>\n".to_string()
+        };
+
+        self.open_group(tag);
+    }
+
+    ///
+    /// Starts a new group for an animation layer, which is composited using the layer's opacity and blend mode
+    ///
+    pub fn begin_layer_group(&mut self, layer_id: u64, opacity: f64, blend_mode: BlendMode) {
+        let mut style = String::new();
+
+        if opacity < 1.0 {
+            write!(style, "opacity:{};", Self::number(opacity.max(0.0) as f32)).ok();
+        }
+
+        if let Some(blend_mode) = Self::blend_mode(blend_mode) {
+            write!(style, "mix-blend-mode:{};", blend_mode).ok();
+        }
+
+        let tag         = if style.is_empty() {
+            format!("<g id=\"layer-{}\">\n", layer_id)
+        } else {
+            format!("<g id=\"layer-{}\" style=\"{}\">\n", layer_id, style)
+        };
+
+        self.layer_group = Some(self.open_groups.len());
+        self.open_group(tag);
+    }
+
+    ///
+    /// Writes the opening tag of a group and records where it started
+    ///
+    fn open_group(&mut self, tag: String) {
+        let start_pos   = self.body.len();
+
+        self.body.push_str(&tag);
+        self.open_groups.push((start_pos, tag.len()));
+    }
+
+    ///
+    /// Finishes the most recent group started by `begin_group`
+    ///
+    pub fn end_group(&mut self) {
+        if let Some((start_pos, tag_len)) = self.open_groups.pop() {
+            if self.layer_group == Some(self.open_groups.len()) {
+                self.layer_group = None;
+            }
+
+            if self.body.len() == start_pos + tag_len {
+                // Group is empty
+                self.body.truncate(start_pos);
+            } else {
+                self.body.push_str("</g>\n");
+            }
+        }
+    }
+
+    ///
+    /// Writes the elements from a frame to this document
+    ///
+    pub fn write_frame(&mut self, frame: &dyn Frame) {
+        // Declare the sprites used to draw the images in the frame
+        let mut sprites = vec![];
+        frame.render_sprites(&mut sprites);
+        self.draw_all(sprites);
+        self.current_sprite = None;
+
+        let when        = frame.time_index();
+        let elements    = match frame.vector_elements() {
+            Some(elements)  => elements,
+            None            => { return; }
+        };
+
+        for element in elements {
+            // Render the element using the properties from its attachments
+            let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+            let mut drawing = vec![];
+
+            properties.prepare_to_render(&mut drawing);
+            properties.render(&mut drawing, element.clone(), when);
+
+            // Each element is written as its own group
+            self.begin_group(element.id().id().map(|id| format!("element-{}", id)));
+            self.draw_all(drawing);
+            self.end_group();
+        }
+    }
+
+    ///
+    /// Generates the SVG document for everything that has been drawn so far
+    ///
+    pub fn to_document(&self, size: (f64, f64)) -> String {
+        let (width, height) = (Self::number(size.0 as f32), Self::number(size.1 as f32));
+        let mut document    = String::new();
+
+        document.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n");
+        write!(document, "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n", width, height, width, height).ok();
+
+        if !self.definitions.is_empty() || !self.masks.is_empty() {
+            document.push_str("<defs>\n");
+            document.push_str(&self.definitions);
+
+            // Masks cover the whole document, and are white (ie, show the content) except where paths were erased
+            for (mask_id, mask) in self.masks.iter().enumerate() {
+                write!(document, "<mask id=\"mask{}\" maskUnits=\"userSpaceOnUse\" x=\"0\" y=\"0\" width=\"{}\" height=\"{}\"><rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>{}</mask>\n",
+                    mask_id, width, height, width, height, mask).ok();
+            }
+
+            document.push_str("</defs>\n");
+        }
+
+        // Canvas coordinates have y increasing upwards, but SVG coordinates have y increasing downwards
+        write!(document, "<g transform=\"matrix(1 0 0 -1 0 {})\">\n", height).ok();
+        document.push_str(&self.body);
+        document.push_str("</g>\n");
+        document.push_str("</svg>\n");
+
+        document
+    }
+}
+
+///
+/// Generates an SVG document containing the elements in a single frame
+///
+pub fn frame_to_svg(frame: &dyn Frame, size: (f64, f64)) -> String {
+    let mut writer = SvgWriter::new();
+    writer.write_frame(frame);
+    writer.to_document(size)
+}
+
+///
+/// Generates an SVG document containing every layer of an animation at a particular time
+///
+pub fn animation_frame_to_svg(animation: &dyn Animation, when: Duration) -> String {
+    let mut writer = SvgWriter::new();
+
+    for layer_id in animation.get_layer_ids() {
+        if let Some(layer) = animation.get_layer_with_id(layer_id) {
+            // Hidden layers are left out of the document
+            if !layer.is_visible() {
+                continue;
+            }
+
+            let frame = layer.get_frame_at_time(when);
+
+            writer.begin_layer_group(layer_id, layer.opacity(), layer.blend_mode());
+            writer.write_frame(&*frame);
+            writer.end_group();
+        }
+    }
+
+    writer.to_document(animation.size())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::super::editor::*;
+    use super::super::super::storage::*;
+
+    use futures::prelude::*;
+
+    #[test]
+    fn empty_document() {
+        let writer      = SvgWriter::new();
+        let document    = writer.to_document((1920.0, 1080.0));
+
+        assert!(document.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n"));
+        assert!(document.contains("<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"1920\" height=\"1080\" viewBox=\"0 0 1920 1080\">"));
+        assert!(document.contains("<g transform=\"matrix(1 0 0 -1 0 1080)\">"));
+        assert!(document.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn fill_path() {
+        let mut writer = SvgWriter::new();
+
+        writer.draw_all(vec![
+            Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            Draw::NewPath,
+            Draw::Move(10.0, 20.0),
+            Draw::Line(30.0, 40.0),
+            Draw::BezierCurve((50.0, 60.0), (1.0, 2.0), (3.0, 4.0)),
+            Draw::ClosePath,
+            Draw::Fill
+        ]);
+
+        let document = writer.to_document((100.0, 100.0));
+        assert!(document.contains("<path d=\"M 10 20 L 30 40 C 1 2 3 4 50 60 Z\" fill=\"#ff0000\"/>"));
+    }
+
+    #[test]
+    fn fill_path_with_linear_gradient() {
+        let mut writer = SvgWriter::new();
+
+        writer.draw_all(vec![
+            Draw::NewGradient,
+            Draw::GradientStop(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0)),
+            Draw::GradientStop(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            Draw::FillLinearGradient((0.0, 0.0), (10.0, 0.0)),
+            Draw::NewPath,
+            Draw::Move(0.0, 0.0),
+            Draw::Line(10.0, 10.0),
+            Draw::ClosePath,
+            Draw::Fill
+        ]);
+
+        let document = writer.to_document((100.0, 100.0));
+        assert!(document.contains("<linearGradient id=\"fill0\" gradientUnits=\"userSpaceOnUse\" x1=\"0\" y1=\"0\" x2=\"10\" y2=\"0\"><stop offset=\"0\" stop-color=\"#ff0000\" stop-opacity=\"1\"/><stop offset=\"1\" stop-color=\"#0000ff\" stop-opacity=\"1\"/></linearGradient>"));
+        assert!(document.contains("<path d=\"M 0 0 L 10 10 Z\" fill=\"url(#fill0)\"/>"));
+    }
+
+    #[test]
+    fn fill_path_with_texture() {
+        let mut writer = SvgWriter::new();
+
+        writer.draw_all(vec![
+            Draw::FillTexture(TexturePattern::Dots, Color::Rgba(1.0, 1.0, 1.0, 1.0), Color::Rgba(0.0, 0.0, 0.0, 1.0), 8.0),
+            Draw::NewPath,
+            Draw::Move(0.0, 0.0),
+            Draw::Line(10.0, 10.0),
+            Draw::ClosePath,
+            Draw::Fill,
+            Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            Draw::Fill
+        ]);
+
+        let document = writer.to_document((100.0, 100.0));
+        assert!(document.contains("<pattern id=\"fill0\" patternUnits=\"userSpaceOnUse\" width=\"8\" height=\"8\"><rect width=\"8\" height=\"8\" fill=\"#000000\" fill-opacity=\"1\"/><circle cx=\"4\" cy=\"4\" r=\"2\" fill=\"#ffffff\" fill-opacity=\"1\"/></pattern>"));
+        assert!(document.contains("<path d=\"M 0 0 L 10 10 Z\" fill=\"url(#fill0)\"/>"));
+        assert!(document.contains("<path d=\"M 0 0 L 10 10 Z\" fill=\"#ff0000\"/>"));
+    }
+
+    #[test]
+    fn stroke_path_with_opacity() {
+        let mut writer = SvgWriter::new();
+
+        writer.draw_all(vec![
+            Draw::StrokeColor(Color::Rgba(0.0, 0.0, 1.0, 0.5)),
+            Draw::LineWidth(2.5),
+            Draw::NewPath,
+            Draw::Move(0.0, 0.0),
+            Draw::Line(10.0, 10.0),
+            Draw::Stroke
+        ]);
+
+        let document = writer.to_document((100.0, 100.0));
+        assert!(document.contains("<path d=\"M 0 0 L 10 10\" fill=\"none\" stroke=\"#0000ff\" stroke-width=\"2.5\" stroke-opacity=\"0.5\""));
+    }
+
+    #[test]
+    fn transform_path() {
+        let mut writer = SvgWriter::new();
+
+        writer.draw_all(vec![
+            Draw::PushState,
+            Draw::MultiplyTransform(Transform2D::translate(5.0, 6.0)),
+            Draw::NewPath,
+            Draw::Move(0.0, 0.0),
+            Draw::Line(10.0, 10.0),
+            Draw::Fill,
+            Draw::PopState,
+            Draw::Fill
+        ]);
+
+        let document = writer.to_document((100.0, 100.0));
+        assert!(document.contains("<path d=\"M 0 0 L 10 10\" fill=\"#000000\" transform=\"matrix(1 0 0 1 5 6)\"/>"));
+        assert!(document.contains("<path d=\"M 0 0 L 10 10\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn draw_sprite() {
+        let mut writer = SvgWriter::new();
+
+        writer.draw_all(vec![
+            Draw::Sprite(SpriteId(1)),
+            Draw::ClearSprite,
+            Draw::NewPath,
+            Draw::Move(0.0, 0.0),
+            Draw::Line(10.0, 10.0),
+            Draw::Fill,
+            Draw::Layer(0),
+
+            Draw::SpriteTransform(SpriteTransform::Translate(5.0, 6.0)),
+            Draw::DrawSprite(SpriteId(1)),
+            Draw::SpriteTransform(SpriteTransform::Identity),
+            Draw::DrawSprite(SpriteId(1))
+        ]);
+
+        // The sprite is only written when it's drawn
+        let document = writer.to_document((100.0, 100.0));
+        assert!(document.matches("<path ").count() == 2);
+        assert!(document.contains("<path d=\"M 0 0 L 10 10\" fill=\"#000000\" transform=\"matrix(1 0 0 1 5 6)\"/>"));
+        assert!(document.contains("<path d=\"M 0 0 L 10 10\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn erase_path() {
+        let mut writer = SvgWriter::new();
+
+        writer.begin_layer_group(1, 1.0, BlendMode::SourceOver);
+
+        writer.begin_group(Some("drawn".to_string()));
+        writer.draw_all(vec![
+            Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            Draw::NewPath,
+            Draw::Move(0.0, 0.0),
+            Draw::Line(10.0, 10.0),
+            Draw::ClosePath,
+            Draw::Fill
+        ]);
+        writer.end_group();
+
+        writer.begin_group(Some("erased".to_string()));
+        writer.draw_all(vec![
+            Draw::BlendMode(BlendMode::DestinationOut),
+            Draw::FillColor(Color::Rgba(1.0, 1.0, 1.0, 0.5)),
+            Draw::NewPath,
+            Draw::Move(5.0, 5.0),
+            Draw::Line(10.0, 0.0),
+            Draw::ClosePath,
+            Draw::Fill
+        ]);
+        writer.end_group();
+
+        writer.end_group();
+
+        // The erased path is drawn in black in a mask that's applied to the content drawn before it
+        let document = writer.to_document((100.0, 100.0));
+        assert!(document.contains("<mask id=\"mask0\" maskUnits=\"userSpaceOnUse\" x=\"0\" y=\"0\" width=\"100\" height=\"100\"><rect x=\"0\" y=\"0\" width=\"100\" height=\"100\" fill=\"#ffffff\"/><path d=\"M 5 5 L 10 0 Z\" fill=\"#000000\" fill-opacity=\"0.5\"/></mask>"));
+        assert!(document.contains("<g id=\"layer-1\">\n<g mask=\"url(#mask0)\">\n<g id=\"drawn\">\n<path d=\"M 0 0 L 10 10 Z\" fill=\"#ff0000\"/>\n</g>\n</g>\n</g>\n"));
+        assert!(!document.contains("erased"));
+    }
+
+    #[test]
+    fn erasing_nothing_adds_no_mask() {
+        let mut writer = SvgWriter::new();
+
+        writer.draw_all(vec![
+            Draw::BlendMode(BlendMode::DestinationOut),
+            Draw::NewPath,
+            Draw::Move(5.0, 5.0),
+            Draw::Line(10.0, 0.0),
+            Draw::ClosePath,
+            Draw::Fill
+        ]);
+
+        let document = writer.to_document((100.0, 100.0));
+        assert!(!document.contains("mask"));
+        assert!(!document.contains("<path"));
+    }
+
+    #[test]
+    fn empty_groups_are_removed() {
+        let mut writer = SvgWriter::new();
+
+        writer.begin_group(Some("empty".to_string()));
+        writer.end_group();
+
+        let document = writer.to_document((100.0, 100.0));
+        assert!(!document.contains("empty"));
+    }
+
+    #[test]
+    fn export_brush_stroke() {
+        let in_memory_store = InMemoryStorage::new();
+        let anim            = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        anim.perform_edits(vec![
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                    ElementId::Unassigned,
+                    BrushDefinition::Ink(InkDefinition::default()),
+                    BrushDrawingStyle::Draw
+                )
+            )),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0)),
+                RawPoint::from((40.0, 30.0))
+            ]))))
+        ]);
+
+        let document = animation_frame_to_svg(&anim, Duration::from_millis(0));
+
+        assert!(document.contains("<g id=\"layer-2\">"));
+        assert!(document.contains("<g id=\"element-100\">"));
+        assert!(document.contains("<path d=\"M "));
+    }
+
+    #[test]
+    fn export_erased_brush_stroke() {
+        let in_memory_store = InMemoryStorage::new();
+        let anim            = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        anim.perform_edits(vec![
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                    ElementId::Unassigned,
+                    BrushDefinition::Ink(InkDefinition::default()),
+                    BrushDrawingStyle::Draw
+                )
+            )),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0)),
+                RawPoint::from((40.0, 30.0))
+            ])))),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                    ElementId::Unassigned,
+                    BrushDefinition::Ink(InkDefinition::default()),
+                    BrushDrawingStyle::Erase
+                )
+            )),
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(101), Arc::new(vec![
+                RawPoint::from((15.0, 0.0)),
+                RawPoint::from((15.0, 10.0)),
+                RawPoint::from((15.0, 20.0))
+            ]))))
+        ]);
+
+        let document = animation_frame_to_svg(&anim, Duration::from_millis(0));
+
+        // The erased stroke is drawn as a mask over the stroke beneath it, rather than being painted
+        assert!(document.contains("<mask id=\"mask0\""));
+        assert!(document.contains("<g id=\"layer-2\">\n<g mask=\"url(#mask0)\">\n<g id=\"element-100\">"));
+        assert!(!document.contains("element-101"));
+    }
+
+    #[test]
+    fn export_layer_properties() {
+        let in_memory_store = InMemoryStorage::new();
+        let anim            = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        let mut edits = vec![];
+        for layer_id in 2..4 {
+            edits.extend(vec![
+                AnimationEdit::AddNewLayer(layer_id),
+                AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                        ElementId::Unassigned,
+                        BrushDefinition::Ink(InkDefinition::default()),
+                        BrushDrawingStyle::Draw
+                    )
+                )),
+                AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(layer_id as i64 * 100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0)),
+                    RawPoint::from((40.0, 30.0))
+                ]))))
+            ]);
+        }
+
+        edits.extend(vec![
+            AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.5)),
+            AnimationEdit::Layer(2, LayerEdit::SetBlendMode(BlendMode::Multiply)),
+            AnimationEdit::Layer(3, LayerEdit::SetVisibility(false))
+        ]);
+        anim.perform_edits(edits);
+
+        let document = animation_frame_to_svg(&anim, Duration::from_millis(0));
+
+        assert!(document.contains("<g id=\"layer-2\" style=\"opacity:0.5;mix-blend-mode:multiply;\">"));
+        assert!(!document.contains("layer-3"));
+        assert!(!document.contains("element-300"));
+    }
+}
@@ -0,0 +1,594 @@
+use crate::traits::{InbetweenMode};
+use crate::storage::file_properties::*;
+
+use flo_canvas::{BlendMode};
+
+use std::str::{FromStr};
+use std::time::{Duration};
+
+/// The version of the interchange format that is written by this version of FlowBetween
+pub const INTERCHANGE_FORMAT_VERSION: u64 = 6;
+
+/// The keyword on the first line of an interchange document
+const HEADER_KEYWORD: &str = "flo-animation";
+
+///
+/// Errors that can occur while reading an interchange document
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum InterchangeError {
+    /// The document does not start with a FlowBetween animation header
+    MissingHeader,
+
+    /// The document was written using a version of the format that this version of FlowBetween can't read
+    UnsupportedVersion(u64),
+
+    /// The line with the specified line number could not be parsed
+    CannotParseLine(usize, String),
+
+    /// The element with the specified ID could not be deserialized
+    CannotParseElement(i64),
+
+    /// The reference image with the specified ID could not be deserialized
+    CannotParseImage(i64),
+
+    /// The audio clip attached to the layer with the specified ID could not be deserialized
+    CannotParseAudio(u64),
+
+    /// The annotation with the specified ID could not be deserialized
+    CannotParseAnnotation(i64)
+}
+
+///
+/// Represents the contents of a keyframe in an interchange document
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyFrameDocument {
+    /// When this keyframe starts
+    pub when: Duration,
+
+    /// The elements attached to this keyframe, in the same serialized form that's used by the storage layer
+    pub elements: Vec<(i64, String)>
+}
+
+///
+/// Represents the contents of a layer in an interchange document
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayerDocument {
+    /// The ID of this layer
+    pub layer_id: u64,
+
+    /// The name of this layer
+    pub name: String,
+
+    /// The ordering of this layer relative to the other layers
+    pub ordering: i64,
+
+    /// True if this layer is rendered
+    pub visible: bool,
+
+    /// True if this layer is locked against paint edits
+    pub locked: bool,
+
+    /// The opacity of this layer
+    pub opacity: f64,
+
+    /// How this layer is blended with the layers underneath it
+    pub blend_mode: BlendMode,
+
+    /// How the frames between the keyframes of this layer are generated
+    pub inbetween_mode: InbetweenMode,
+
+    /// The audio clip attached to this layer, in the same serialized form that's used by the storage layer
+    pub audio: Option<String>,
+
+    /// The keyframes in this layer
+    pub keyframes: Vec<KeyFrameDocument>
+}
+
+///
+/// Represents an entire animation in the interchange format
+///
+/// The text form of a document has one item per line, so changes to an animation produce small diffs. Each line
+/// starts with a keyword describing what it contains:
+///
+/// ```text
+/// ; FlowBetween animation
+/// flo-animation 6
+/// size 1920 1080
+/// frame-length 33333
+/// duration 120000000
+/// image 50 <serialized image>
+/// layer 1 0 Background
+/// layer-display 1 visible unlocked 1 source-over
+/// inbetween 1 proximity
+/// audio 1 <serialized audio clip>
+/// keyframe 1 0
+/// element 1 0 100 <serialized element>
+///
+/// annotation 200 <serialized annotation>
+/// ```
+///
+/// Times are in microseconds. Images, elements and annotations are stored in the same format used by the storage layer.
+/// Elements are identified by the layer ID and the time of the keyframe that they belong to, and annotations
+/// record the layer and frame they're attached to themselves. Blank lines and lines starting with ';' are ignored.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnimationDocument {
+    /// The size of the canvas
+    pub size: (f64, f64),
+
+    /// The length of a frame
+    pub frame_length: Duration,
+
+    /// The length of the animation
+    pub duration: Duration,
+
+    /// The reference images displayed by the elements in this animation, in the same serialized form that's used by the storage layer
+    pub images: Vec<(i64, String)>,
+
+    /// The layers in this animation, in the order that they should be displayed
+    pub layers: Vec<LayerDocument>,
+
+    /// The review annotations left on this animation, in the same serialized form that's used by the storage layer
+    pub annotations: Vec<(i64, String)>
+}
+
+///
+/// Escapes a string so it can be written as part of a single line
+///
+fn escape_line(text: &str) -> String {
+    let mut result = String::new();
+
+    for chr in text.chars() {
+        match chr {
+            '\\'    => result.push_str("\\\\"),
+            '\n'    => result.push_str("\\n"),
+            '\r'    => result.push_str("\\r"),
+            other   => result.push(other)
+        }
+    }
+
+    result
+}
+
+///
+/// Reverses the effect of `escape_line`
+///
+fn unescape_line(text: &str) -> String {
+    let mut result  = String::new();
+    let mut chars   = text.chars();
+
+    while let Some(chr) = chars.next() {
+        if chr == '\\' {
+            match chars.next() {
+                Some('n')   => result.push('\n'),
+                Some('r')   => result.push('\r'),
+                Some(other) => result.push(other),
+                None        => result.push('\\')
+            }
+        } else {
+            result.push(chr);
+        }
+    }
+
+    result
+}
+
+///
+/// Parses the next word of a line as a value
+///
+fn next_value<'a, Value: FromStr, Words: Iterator<Item=&'a str>>(words: &mut Words) -> Option<Value> {
+    words.next().and_then(|word| word.parse().ok())
+}
+
+/// The names used for blend modes in the text form of a document
+const BLEND_MODE_NAMES: [(BlendMode, &str); 12] = [
+    (BlendMode::SourceOver,         "source-over"),
+    (BlendMode::SourceIn,           "source-in"),
+    (BlendMode::SourceOut,          "source-out"),
+    (BlendMode::DestinationOver,    "destination-over"),
+    (BlendMode::DestinationIn,      "destination-in"),
+    (BlendMode::DestinationOut,     "destination-out"),
+    (BlendMode::SourceAtop,         "source-atop"),
+    (BlendMode::DestinationAtop,    "destination-atop"),
+    (BlendMode::Multiply,           "multiply"),
+    (BlendMode::Screen,             "screen"),
+    (BlendMode::Darken,             "darken"),
+    (BlendMode::Lighten,            "lighten")
+];
+
+///
+/// Returns the name of a blend mode
+///
+fn blend_mode_name(blend_mode: BlendMode) -> &'static str {
+    BLEND_MODE_NAMES.iter()
+        .filter(|(mode, _)| *mode == blend_mode)
+        .map(|(_, name)| *name)
+        .nth(0)
+        .unwrap_or("source-over")
+}
+
+/// The names used for in-betweening modes in the text form of a document
+const INBETWEEN_MODE_NAMES: [(InbetweenMode, &str); 3] = [
+    (InbetweenMode::Off,                "off"),
+    (InbetweenMode::MatchByOrder,       "order"),
+    (InbetweenMode::MatchByProximity,   "proximity")
+];
+
+///
+/// Returns the name of an in-betweening mode
+///
+fn inbetween_mode_name(inbetween_mode: InbetweenMode) -> &'static str {
+    INBETWEEN_MODE_NAMES.iter()
+        .filter(|(mode, _)| *mode == inbetween_mode)
+        .map(|(_, name)| *name)
+        .nth(0)
+        .unwrap_or("off")
+}
+
+///
+/// Returns the in-betweening mode with the specified name
+///
+fn inbetween_mode_with_name(name: &str) -> Option<InbetweenMode> {
+    INBETWEEN_MODE_NAMES.iter()
+        .filter(|(_, mode_name)| *mode_name == name)
+        .map(|(mode, _)| *mode)
+        .nth(0)
+}
+
+///
+/// Returns the blend mode with the specified name
+///
+fn blend_mode_with_name(name: &str) -> Option<BlendMode> {
+    BLEND_MODE_NAMES.iter()
+        .filter(|(_, mode_name)| *mode_name == name)
+        .map(|(mode, _)| *mode)
+        .nth(0)
+}
+
+impl AnimationDocument {
+    ///
+    /// Creates the text form of this document
+    ///
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        // Header
+        text.push_str("; FlowBetween animation\n");
+        text.push_str(&format!("{} {}\n", HEADER_KEYWORD, INTERCHANGE_FORMAT_VERSION));
+
+        // Animation properties
+        text.push_str(&format!("size {} {}\n", self.size.0, self.size.1));
+        text.push_str(&format!("frame-length {}\n", self.frame_length.as_micros()));
+        text.push_str(&format!("duration {}\n", self.duration.as_micros()));
+
+        // Images are written before the elements that display them
+        for (image_id, image) in self.images.iter() {
+            text.push_str(&format!("image {} {}\n", image_id, image));
+        }
+
+        // Layers, followed by their keyframes and elements
+        for layer in self.layers.iter() {
+            text.push_str("\n");
+            text.push_str(&format!("layer {} {} {}\n", layer.layer_id, layer.ordering, escape_line(&layer.name)));
+            text.push_str(&format!("layer-display {} {} {} {} {}\n",
+                layer.layer_id,
+                if layer.visible { "visible" } else { "hidden" },
+                if layer.locked { "locked" } else { "unlocked" },
+                layer.opacity,
+                blend_mode_name(layer.blend_mode)));
+
+            if layer.inbetween_mode != InbetweenMode::Off {
+                text.push_str(&format!("inbetween {} {}\n", layer.layer_id, inbetween_mode_name(layer.inbetween_mode)));
+            }
+
+            if let Some(audio) = layer.audio.as_ref() {
+                text.push_str(&format!("audio {} {}\n", layer.layer_id, audio));
+            }
+
+            for keyframe in layer.keyframes.iter() {
+                let when = keyframe.when.as_micros();
+
+                text.push_str(&format!("keyframe {} {}\n", layer.layer_id, when));
+
+                for (element_id, element) in keyframe.elements.iter() {
+                    text.push_str(&format!("element {} {} {} {}\n", layer.layer_id, when, element_id, element));
+                }
+            }
+        }
+
+        // Annotations
+        if self.annotations.len() > 0 {
+            text.push_str("\n");
+        }
+
+        for (annotation_id, annotation) in self.annotations.iter() {
+            text.push_str(&format!("annotation {} {}\n", annotation_id, annotation));
+        }
+
+        text
+    }
+
+    ///
+    /// Reads a document from its text form
+    ///
+    pub fn from_text(text: &str) -> Result<AnimationDocument, InterchangeError> {
+        // Properties that aren't in the document are left at their default values
+        let properties      = FileProperties::default();
+        let mut document    = AnimationDocument {
+            size:           properties.size,
+            frame_length:   properties.frame_length,
+            duration:       properties.duration,
+            images:         vec![],
+            layers:         vec![],
+            annotations:    vec![]
+        };
+
+        let mut read_header = false;
+
+        for (line_index, line) in text.lines().enumerate() {
+            let line_number = line_index + 1;
+            let line        = line.trim_end_matches('\r');
+
+            // Blank lines and comments are ignored
+            if line.trim().is_empty() || line.starts_with(';') {
+                continue;
+            }
+
+            let cannot_parse    = || InterchangeError::CannotParseLine(line_number, line.to_string());
+            let mut words       = line.splitn(2, ' ');
+            let keyword         = words.next().unwrap_or("");
+            let remaining       = words.next().unwrap_or("");
+
+            // The first line must be the header
+            if !read_header {
+                if keyword != HEADER_KEYWORD {
+                    return Err(InterchangeError::MissingHeader);
+                }
+
+                let version: u64 = remaining.trim().parse().map_err(|_| cannot_parse())?;
+                if version > INTERCHANGE_FORMAT_VERSION {
+                    return Err(InterchangeError::UnsupportedVersion(version));
+                }
+
+                read_header = true;
+                continue;
+            }
+
+            match keyword {
+                "size"          => {
+                    let mut values  = remaining.split(' ');
+                    let width       = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let height      = next_value(&mut values).ok_or_else(cannot_parse)?;
+
+                    document.size   = (width, height);
+                }
+
+                "frame-length"  => { document.frame_length = Duration::from_micros(remaining.trim().parse().map_err(|_| cannot_parse())?); }
+                "duration"      => { document.duration = Duration::from_micros(remaining.trim().parse().map_err(|_| cannot_parse())?); }
+
+                "image"         => {
+                    let mut values  = remaining.splitn(2, ' ');
+                    let image_id    = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let image       = values.next().ok_or_else(cannot_parse)?;
+
+                    document.images.push((image_id, image.to_string()));
+                }
+
+                "layer"         => {
+                    // The name is the rest of the line, so it can contain spaces
+                    let mut values  = remaining.splitn(3, ' ');
+                    let layer_id    = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let ordering    = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let name        = unescape_line(values.next().unwrap_or(""));
+
+                    document.layers.push(LayerDocument {
+                        layer_id:       layer_id,
+                        name:           name,
+                        ordering:       ordering,
+                        visible:        true,
+                        locked:         false,
+                        opacity:        1.0,
+                        blend_mode:     BlendMode::SourceOver,
+                        inbetween_mode: InbetweenMode::Off,
+                        audio:          None,
+                        keyframes:      vec![]
+                    });
+                }
+
+                "layer-display" => {
+                    let mut values  = remaining.split(' ');
+                    let layer_id    = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let visible     = match values.next() { Some("visible") => true, Some("hidden") => false, _ => { return Err(cannot_parse()); } };
+                    let locked      = match values.next() { Some("locked") => true, Some("unlocked") => false, _ => { return Err(cannot_parse()); } };
+                    let opacity     = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let blend_mode  = values.next().and_then(blend_mode_with_name).ok_or_else(cannot_parse)?;
+                    let layer       = document.layer_mut(layer_id).ok_or_else(cannot_parse)?;
+
+                    layer.visible       = visible;
+                    layer.locked        = locked;
+                    layer.opacity       = opacity;
+                    layer.blend_mode    = blend_mode;
+                }
+
+                "inbetween"     => {
+                    let mut values      = remaining.split(' ');
+                    let layer_id        = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let inbetween_mode  = values.next().and_then(inbetween_mode_with_name).ok_or_else(cannot_parse)?;
+                    let layer           = document.layer_mut(layer_id).ok_or_else(cannot_parse)?;
+
+                    layer.inbetween_mode = inbetween_mode;
+                }
+
+                "audio"         => {
+                    let mut values  = remaining.splitn(2, ' ');
+                    let layer_id    = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let audio       = values.next().ok_or_else(cannot_parse)?;
+                    let layer       = document.layer_mut(layer_id).ok_or_else(cannot_parse)?;
+
+                    layer.audio     = Some(audio.to_string());
+                }
+
+                "keyframe"      => {
+                    let mut values  = remaining.split(' ');
+                    let layer_id    = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let when        = Duration::from_micros(next_value(&mut values).ok_or_else(cannot_parse)?);
+                    let layer       = document.layer_mut(layer_id).ok_or_else(cannot_parse)?;
+
+                    layer.keyframes.push(KeyFrameDocument {
+                        when:       when,
+                        elements:   vec![]
+                    });
+                }
+
+                "element"       => {
+                    let mut values  = remaining.splitn(4, ' ');
+                    let layer_id    = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let when        = Duration::from_micros(next_value(&mut values).ok_or_else(cannot_parse)?);
+                    let element_id  = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let element     = values.next().ok_or_else(cannot_parse)?;
+
+                    let keyframe    = document.layer_mut(layer_id)
+                        .and_then(|layer| layer.keyframes.iter_mut().filter(|keyframe| keyframe.when == when).nth(0))
+                        .ok_or_else(cannot_parse)?;
+
+                    keyframe.elements.push((element_id, element.to_string()));
+                }
+
+                "annotation"    => {
+                    let mut values      = remaining.splitn(2, ' ');
+                    let annotation_id   = next_value(&mut values).ok_or_else(cannot_parse)?;
+                    let annotation      = values.next().ok_or_else(cannot_parse)?;
+
+                    document.annotations.push((annotation_id, annotation.to_string()));
+                }
+
+                _               => { return Err(cannot_parse()); }
+            }
+        }
+
+        if read_header {
+            Ok(document)
+        } else {
+            Err(InterchangeError::MissingHeader)
+        }
+    }
+
+    ///
+    /// Finds the layer with the specified ID in this document
+    ///
+    fn layer_mut(&mut self, layer_id: u64) -> Option<&mut LayerDocument> {
+        self.layers.iter_mut()
+            .filter(|layer| layer.layer_id == layer_id)
+            .nth(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_document() -> AnimationDocument {
+        AnimationDocument {
+            size:           (1024.0, 768.5),
+            frame_length:   Duration::from_micros(41_667),
+            duration:       Duration::from_secs(10),
+            images:         vec![(50, "EEEE".to_string())],
+            layers:         vec![
+                LayerDocument {
+                    layer_id:       2,
+                    name:           "Back\\ground\nlayer".to_string(),
+                    ordering:       0,
+                    visible:        true,
+                    locked:         false,
+                    opacity:        1.0,
+                    blend_mode:     BlendMode::SourceOver,
+                    inbetween_mode: InbetweenMode::MatchByProximity,
+                    audio:          None,
+                    keyframes:      vec![
+                        KeyFrameDocument { when: Duration::from_millis(0), elements: vec![(100, "AAAA".to_string()), (101, "BBBB".to_string())] },
+                        KeyFrameDocument { when: Duration::from_millis(500), elements: vec![] }
+                    ]
+                },
+                LayerDocument {
+                    layer_id:       1,
+                    name:           "".to_string(),
+                    ordering:       1,
+                    visible:        false,
+                    locked:         true,
+                    opacity:        0.5,
+                    blend_mode:     BlendMode::Multiply,
+                    inbetween_mode: InbetweenMode::Off,
+                    audio:          Some("CCCC".to_string()),
+                    keyframes:      vec![]
+                }
+            ],
+            annotations:    vec![(200, "DDDD".to_string())]
+        }
+    }
+
+    #[test]
+    fn read_document_from_text() {
+        let document    = test_document();
+        let text        = document.to_text();
+
+        assert!(AnimationDocument::from_text(&text) == Ok(document));
+    }
+
+    #[test]
+    fn one_line_per_element() {
+        let text = test_document().to_text();
+
+        assert!(text.lines().filter(|line| line.starts_with("element ")).count() == 2);
+        assert!(text.lines().any(|line| line == "element 2 0 100 AAAA"));
+    }
+
+    #[test]
+    fn one_line_per_image() {
+        let text = test_document().to_text();
+
+        assert!(text.lines().any(|line| line == "image 50 EEEE"));
+    }
+
+    #[test]
+    fn one_line_per_annotation() {
+        let text = test_document().to_text();
+
+        assert!(text.lines().any(|line| line == "annotation 200 DDDD"));
+    }
+
+    #[test]
+    fn ignore_comments_and_blank_lines() {
+        let document = AnimationDocument::from_text("; Comment\n\nflo-animation 1\n; Another comment\n\nsize 100 200\n");
+
+        assert!(document.map(|document| document.size) == Ok((100.0, 200.0)));
+    }
+
+    #[test]
+    fn reject_missing_header() {
+        assert!(AnimationDocument::from_text("size 100 200\n") == Err(InterchangeError::MissingHeader));
+        assert!(AnimationDocument::from_text("") == Err(InterchangeError::MissingHeader));
+    }
+
+    #[test]
+    fn reject_future_version() {
+        assert!(AnimationDocument::from_text("flo-animation 7\n") == Err(InterchangeError::UnsupportedVersion(7)));
+    }
+
+    #[test]
+    fn reject_element_without_keyframe() {
+        assert!(AnimationDocument::from_text("flo-animation 1\nlayer 1 0 Layer\nelement 1 0 100 AAAA\n") == Err(InterchangeError::CannotParseLine(3, "element 1 0 100 AAAA".to_string())));
+    }
+
+    #[test]
+    fn version_1_layers_use_default_display_properties() {
+        let document = AnimationDocument::from_text("flo-animation 1\nlayer 1 0 Layer\n").unwrap();
+
+        assert!(document.layers[0].visible == true);
+        assert!(document.layers[0].locked == false);
+        assert!(document.layers[0].opacity == 1.0);
+        assert!(document.layers[0].blend_mode == BlendMode::SourceOver);
+    }
+}
@@ -0,0 +1,200 @@
+use super::document::*;
+use crate::storage::storage_api::*;
+use crate::storage::file_properties::*;
+use crate::storage::layer_properties::*;
+use crate::traits::*;
+
+use flo_stream::*;
+
+use futures::prelude::*;
+use futures::stream::{BoxStream};
+
+use std::i64;
+use std::time::{Duration};
+use std::collections::{BTreeSet};
+
+///
+/// Returns the ID of the image displayed by a serialized element, if it's an image element
+///
+fn image_id_for_element(element_id: i64, element: &str) -> Option<i64> {
+    let mut element = element.chars();
+
+    if element.next() != Some('i') {
+        return None;
+    }
+
+    ImageElement::deserialize(ElementId::Assigned(element_id), &mut element)
+        .and_then(|image| image.image_id().id())
+}
+
+///
+/// Sends requests to a storage layer and reads the responses
+///
+struct StorageReader {
+    /// Publisher where the requests are sent
+    requests: Publisher<Vec<StorageCommand>>,
+
+    /// Stream where the responses arrive
+    responses: BoxStream<'static, Vec<StorageResponse>>
+}
+
+impl StorageReader {
+    ///
+    /// Sends a request to the storage layer and waits for the response
+    ///
+    fn request<'a>(&'a mut self, request: Vec<StorageCommand>) -> impl 'a+Future<Output=Vec<StorageResponse>> {
+        async move {
+            self.requests.publish(request).await;
+            self.responses.next().await.unwrap_or_else(|| vec![])
+        }
+    }
+}
+
+///
+/// Reads the contents of an animation from a storage layer into an interchange document
+///
+/// The storage layer is connected in the same way as for `create_animation_editor()`: the function is passed the stream
+/// of storage commands and should return a stream with one response for each command.
+///
+pub fn read_animation_document<ConnectStream>(connect_stream: ConnectStream) -> impl Future<Output=AnimationDocument>
+where ConnectStream: FnOnce(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>> {
+    let mut requests    = Publisher::new(1);
+    let commands        = requests.subscribe().boxed();
+    let responses       = connect_stream(commands);
+    let mut storage     = StorageReader { requests: requests, responses: responses };
+
+    async move {
+        // Read the animation properties
+        let properties = storage.request(vec![StorageCommand::ReadAnimationProperties]).await
+            .into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::AnimationProperties(properties)    => FileProperties::deserialize(&mut properties.chars()),
+                _                                                   => None
+            })
+            .nth(0)
+            .unwrap_or_else(|| FileProperties::default());
+
+        // Read the layers, in order
+        let mut layers = storage.request(vec![StorageCommand::ReadLayers]).await
+            .into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::LayerProperties(layer_id, properties)  => Some((layer_id, LayerProperties::deserialize(&mut properties.chars()).unwrap_or_else(|| LayerProperties::default()))),
+                _                                                       => None
+            })
+            .collect::<Vec<_>>();
+        layers.sort_by_key(|(layer_id, properties)| (properties.ordering, *layer_id));
+
+        // Read the keyframes and elements for each layer
+        let all_time            = Duration::from_micros(0)..Duration::from_micros(i64::max_value() as u64);
+        let mut layer_documents = vec![];
+        let mut image_ids       = BTreeSet::new();
+
+        for (layer_id, properties) in layers {
+            let keyframe_times = storage.request(vec![StorageCommand::ReadKeyFrames(layer_id, all_time.clone())]).await
+                .into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::KeyFrame(start, _) => Some(start),
+                    _                                   => None
+                })
+                .collect::<Vec<_>>();
+
+            let mut keyframes = vec![];
+
+            for when in keyframe_times {
+                let mut elements = storage.request(vec![StorageCommand::ReadElementsForKeyFrame(layer_id, when)]).await
+                    .into_iter()
+                    .filter_map(|response| match response {
+                        StorageResponse::Element(element_id, element)   => Some((element_id, element)),
+                        _                                               => None
+                    })
+                    .collect::<Vec<_>>();
+                elements.sort_by_key(|(element_id, _)| *element_id);
+
+                image_ids.extend(elements.iter().filter_map(|(element_id, element)| image_id_for_element(*element_id, element)));
+
+                keyframes.push(KeyFrameDocument {
+                    when:       when,
+                    elements:   elements
+                });
+            }
+
+            // Layers refer to a stored audio clip, which is written out with the layer
+            let clip_id = if properties.has_audio {
+                storage.request(vec![StorageCommand::ReadLayerAudio(layer_id)]).await
+                    .into_iter()
+                    .filter_map(|response| match response {
+                        StorageResponse::LayerAudio(_, clip_id) => Some(clip_id),
+                        _                                       => None
+                    })
+                    .nth(0)
+            } else {
+                None
+            };
+
+            let audio = if let Some(clip_id) = clip_id {
+                storage.request(vec![StorageCommand::ReadAudioClip(clip_id)]).await
+                    .into_iter()
+                    .filter_map(|response| match response {
+                        StorageResponse::AudioClip(_, audio)    => Some(audio),
+                        _                                       => None
+                    })
+                    .nth(0)
+            } else {
+                None
+            };
+
+            layer_documents.push(LayerDocument {
+                layer_id:       layer_id,
+                name:           properties.name,
+                ordering:       properties.ordering,
+                visible:        properties.visible,
+                locked:         properties.locked,
+                opacity:        properties.opacity,
+                blend_mode:     properties.blend_mode,
+                inbetween_mode: properties.inbetween_mode,
+                audio:          audio,
+                keyframes:      keyframes
+            });
+        }
+
+        // Read the images used by the elements (several elements can display the same image, but it's only written once)
+        let mut images = vec![];
+
+        for image_id in image_ids {
+            images.extend(storage.request(vec![StorageCommand::ReadImage(image_id)]).await
+                .into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::Image(image_id, image) => Some((image_id, image)),
+                    _                                       => None
+                }));
+        }
+
+        // Read the annotations
+        let mut annotations = storage.request(vec![StorageCommand::ReadAnnotations]).await
+            .into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::Annotation(annotation_id, _, annotation)   => Some((annotation_id, annotation)),
+                _                                                           => None
+            })
+            .collect::<Vec<_>>();
+        annotations.sort_by_key(|(annotation_id, _)| *annotation_id);
+
+        AnimationDocument {
+            size:           properties.size,
+            frame_length:   properties.frame_length,
+            duration:       properties.duration,
+            images:         images,
+            layers:         layer_documents,
+            annotations:    annotations
+        }
+    }
+}
+
+///
+/// Exports the animation in a storage layer as the text form of an interchange document
+///
+pub fn export_animation<ConnectStream>(connect_stream: ConnectStream) -> impl Future<Output=String>
+where ConnectStream: FnOnce(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>> {
+    read_animation_document(connect_stream)
+        .map(|document| document.to_text())
+}
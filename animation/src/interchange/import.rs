@@ -0,0 +1,365 @@
+use super::document::*;
+use crate::editor::element_wrapper::*;
+use crate::editor::keyframe_core::*;
+use crate::traits::*;
+
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::{HashMap, HashSet};
+
+///
+/// Converts a list of transformations into the element transforms that will recreate them
+///
+fn element_transforms(transformations: &[Transformation]) -> Vec<ElementTransform> {
+    transformations.iter()
+        .flat_map(|transformation| transformation.to_element_transforms())
+        .collect()
+}
+
+///
+/// Generates the edits that recreate the contents of an interchange document
+///
+struct ImportEdits {
+    /// The edits generated so far
+    edits: Vec<AnimationEdit>,
+
+    /// Edits that should be performed after all of the others (these create elements with new IDs, so they need to be made
+    /// once all of the elements with known IDs exist)
+    final_edits: Vec<AnimationEdit>,
+
+    /// The elements that have been created so far
+    created: HashSet<ElementId>,
+
+    /// The brush definition and properties that will be attached to new brush strokes
+    paint_brush: (Option<ElementId>, Option<ElementId>),
+
+    /// The brush definition and properties that will be used for new paths
+    path_brush: (Option<ElementId>, Option<ElementId>)
+}
+
+impl ImportEdits {
+    ///
+    /// Creates a new edit generator
+    ///
+    fn new() -> ImportEdits {
+        ImportEdits {
+            edits:          vec![],
+            final_edits:    vec![],
+            created:        HashSet::new(),
+            paint_brush:    (None, None),
+            path_brush:     (None, None)
+        }
+    }
+
+    ///
+    /// Generates the edits for the elements in a keyframe
+    ///
+    fn keyframe(&mut self, layer_id: u64, keyframe: &KeyFrameDocument) -> Result<(), InterchangeError> {
+        // Deserialize the elements
+        let mut element_ids = vec![];
+        let mut unresolved  = HashMap::new();
+
+        for (element_id, serialized) in keyframe.elements.iter() {
+            let wrapper = ElementWrapper::deserialize(ElementId::Assigned(*element_id), &mut serialized.chars())
+                .ok_or(InterchangeError::CannotParseElement(*element_id))?;
+
+            unresolved.insert(ElementId::Assigned(*element_id), Some(wrapper));
+            element_ids.push(ElementId::Assigned(*element_id));
+        }
+
+        // Resolve them using the other elements in the same keyframe
+        let mut elements = HashMap::new();
+        for element_id in element_ids.iter() {
+            resolve_element(&mut unresolved, &mut elements, *element_id);
+        }
+
+        // Brushes used by paths are created alongside the paths themselves
+        let path_brushes = elements.values()
+            .flat_map(|wrapper| match &wrapper.element {
+                Vector::Path(path)  => vec![path.brush().id(), path.properties().id()],
+                _                   => vec![]
+            })
+            .collect::<HashSet<_>>();
+
+        // Recreate the brushes and motions: these are attached to other elements
+        for element_id in element_ids.iter() {
+            let wrapper = &elements[element_id];
+            let when    = Duration::max(wrapper.start_time, keyframe.when);
+
+            if self.created.contains(element_id) || path_brushes.contains(element_id) || !wrapper.unattached {
+                continue;
+            }
+
+            match &wrapper.element {
+                Vector::BrushDefinition(defn)   => {
+                    self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(*element_id, defn.definition().clone(), defn.drawing_style()))));
+                    self.paint_brush.0 = Some(*element_id);
+                }
+
+                Vector::BrushProperties(props)  => {
+                    self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(*element_id, props.brush_properties().clone()))));
+                    self.paint_brush.1 = Some(*element_id);
+                }
+
+                Vector::Motion(motion)          => {
+                    let motion = motion.motion();
+
+                    self.edits.push(AnimationEdit::Motion(*element_id, MotionEdit::Create));
+                    self.edits.push(AnimationEdit::Motion(*element_id, MotionEdit::SetType(motion.motion_type())));
+
+                    if let (Some(origin), Some(path)) = (motion.origin(), motion.path()) {
+                        self.edits.push(AnimationEdit::Motion(*element_id, MotionEdit::SetOrigin(origin.0, origin.1)));
+                        self.edits.push(AnimationEdit::Motion(*element_id, MotionEdit::SetPath(path.clone())));
+                    }
+                }
+
+                // Transformations are recreated alongside the elements they're attached to
+                _                               => { continue; }
+            }
+
+            self.created.insert(*element_id);
+        }
+
+        // Recreate the elements in the keyframe, in order. The first element is the one that has no parent and is not ordered after anything
+        let initial_element = element_ids.iter()
+            .filter(|element_id| {
+                let wrapper = &elements[*element_id];
+                wrapper.parent.is_none() && wrapper.order_after.is_none() && !wrapper.unattached
+            })
+            .nth(0)
+            .cloned();
+
+        let mut visited         = HashSet::new();
+        let mut next_element    = initial_element;
+
+        while let Some(element_id) = next_element {
+            // Stop if the order of the elements contains a loop
+            if visited.contains(&element_id) { break; }
+            visited.insert(element_id);
+
+            self.element(layer_id, keyframe.when, element_id, &elements);
+
+            next_element = elements.get(&element_id).and_then(|wrapper| wrapper.order_before);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Generates the edits to recreate an element (which is added as the topmost element of the keyframe)
+    ///
+    fn element(&mut self, layer_id: u64, keyframe_time: Duration, element_id: ElementId, elements: &HashMap<ElementId, ElementWrapper>) {
+        // Each element is only created once
+        if self.created.contains(&element_id) {
+            return;
+        }
+
+        let wrapper = match elements.get(&element_id) {
+            Some(wrapper)   => wrapper,
+            None            => { return; }
+        };
+        let when    = Duration::max(wrapper.start_time, keyframe_time);
+
+        // Create the element, and work out which attachments it will have once it's created
+        let initial_attachments = match &wrapper.element {
+            Vector::BrushStroke(brush_stroke)   => {
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::CreateBrushStroke(element_id, brush_stroke.points()))));
+
+                vec![self.paint_brush.0, self.paint_brush.1].into_iter().flatten().collect()
+            }
+
+            Vector::Path(path)                  => {
+                let brush       = path.brush();
+                let properties  = path.properties();
+
+                if self.path_brush.0 != Some(brush.id()) {
+                    self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::SelectBrush(brush.id(), brush.definition().clone(), brush.drawing_style()))));
+                    self.path_brush.0 = Some(brush.id());
+                    self.created.insert(brush.id());
+                }
+
+                if self.path_brush.1 != Some(properties.id()) {
+                    self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::BrushProperties(properties.id(), properties.brush_properties().clone()))));
+                    self.path_brush.1 = Some(properties.id());
+                    self.created.insert(properties.id());
+                }
+
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::CreatePath(element_id, Arc::new(path.path().elements().collect())))));
+
+                vec![]
+            }
+
+            Vector::Image(image)                => {
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::PlaceImage(element_id, image.image_id(), image.position(), image.size(), image.opacity()))));
+
+                vec![]
+            }
+
+            Vector::Text(text)                  => {
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::CreateText(element_id, (*text.text()).clone(), (*text.style()).clone(), text.position()))));
+
+                vec![]
+            }
+
+            Vector::Group(group)                => {
+                // The grouped elements are created first, then combined into the group
+                let grouped_ids = group.elements().map(|element| element.id()).collect::<Vec<_>>();
+
+                for grouped_id in grouped_ids.iter() {
+                    self.element(layer_id, keyframe_time, *grouped_id, elements);
+                }
+
+                self.edits.push(AnimationEdit::Element(grouped_ids.clone(), ElementEdit::Group(element_id, group.group_type())));
+
+                // Groups other than normal groups take the attachments of their first element
+                if group.group_type() != GroupType::Normal {
+                    grouped_ids.get(0)
+                        .and_then(|first_id| elements.get(first_id))
+                        .map(|first_element| first_element.attachments.clone())
+                        .unwrap_or_else(|| vec![])
+                } else {
+                    vec![]
+                }
+            }
+
+            // Other elements are only used as attachments
+            _                                   => { return; }
+        };
+
+        self.created.insert(element_id);
+
+        // Update the attachments to match the ones the element is supposed to have
+        let mut attachments = vec![];
+
+        for attachment_id in wrapper.attachments.iter() {
+            match elements.get(attachment_id).map(|attachment| &attachment.element) {
+                Some(Vector::Transformation((_, transformations))) => {
+                    // Transformations are created with new element IDs, so they need to be added after everything else
+                    self.final_edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::Transform(element_transforms(transformations))));
+                }
+
+                _ => {
+                    if self.created.contains(attachment_id) {
+                        attachments.push(*attachment_id);
+                    }
+                }
+            }
+        }
+
+        for removed_id in initial_attachments.iter().filter(|attachment_id| !attachments.contains(*attachment_id)) {
+            self.edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::RemoveAttachment(*removed_id)));
+        }
+
+        for added_id in attachments.iter().filter(|attachment_id| !initial_attachments.contains(*attachment_id)) {
+            self.edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::AddAttachment(*added_id)));
+        }
+    }
+}
+
+impl AnimationDocument {
+    ///
+    /// Generates the edits that will recreate this document in an empty animation
+    ///
+    /// Element and annotation IDs are preserved, except for the IDs of transformations which are assigned when the edits are performed
+    ///
+    pub fn to_edits(&self) -> Result<Vec<AnimationEdit>, InterchangeError> {
+        let mut import = ImportEdits::new();
+
+        // Animation properties
+        import.edits.push(AnimationEdit::SetSize(self.size.0, self.size.1));
+        import.edits.push(AnimationEdit::SetFrameLength(self.frame_length));
+        import.edits.push(AnimationEdit::SetDuration(self.duration));
+
+        // Add the images used by the elements (these keep their IDs)
+        for (image_id, image) in self.images.iter() {
+            let image = ReferenceImage::deserialize(&mut image.chars()).ok_or(InterchangeError::CannotParseImage(*image_id))?;
+
+            import.edits.push(AnimationEdit::AddImage(ElementId::Assigned(*image_id), Arc::new(image)));
+        }
+
+        // Create the layers
+        let mut layers = self.layers.iter().collect::<Vec<_>>();
+        layers.sort_by_key(|layer| layer.ordering);
+
+        // Audio clips need IDs that aren't used by any of the elements, images or annotations in the document
+        let mut next_clip_id = self.layers.iter()
+            .flat_map(|layer| layer.keyframes.iter())
+            .flat_map(|keyframe| keyframe.elements.iter())
+            .chain(self.annotations.iter())
+            .chain(self.images.iter())
+            .map(|(element_id, _)| *element_id + 1)
+            .max()
+            .unwrap_or(0);
+
+        for layer in layers.iter() {
+            import.edits.push(AnimationEdit::AddNewLayer(layer.layer_id));
+            import.edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::SetName(layer.name.clone())));
+            import.edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::SetVisibility(layer.visible)));
+            import.edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::SetOpacity(layer.opacity)));
+            import.edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::SetBlendMode(layer.blend_mode)));
+            import.edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::SetInbetweenMode(layer.inbetween_mode)));
+
+            if let Some(audio) = layer.audio.as_ref() {
+                let clip    = AudioClip::deserialize(&mut audio.chars()).ok_or(InterchangeError::CannotParseAudio(layer.layer_id))?;
+                let clip_id = ElementId::Assigned(next_clip_id);
+                next_clip_id += 1;
+
+                import.edits.push(AnimationEdit::AddAudioClip(clip_id, Arc::new(clip)));
+                import.edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::ImportAudio(clip_id)));
+            }
+        }
+
+        // Put the layers in order by ordering each layer behind the one above it (layers that all share the same ordering are left in the order they were created)
+        if layers.iter().any(|layer| layer.ordering != layers[0].ordering) {
+            for layer_idx in (0..(layers.len()-1)).rev() {
+                import.edits.push(AnimationEdit::Layer(layers[layer_idx].layer_id, LayerEdit::SetOrdering(layers[layer_idx+1].layer_id)));
+            }
+        }
+
+        // Create all the keyframes before adding any elements to them
+        for layer in layers.iter() {
+            for keyframe in layer.keyframes.iter() {
+                import.edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::AddKeyFrame(keyframe.when)));
+            }
+        }
+
+        // Recreate the elements in each keyframe
+        for layer in layers.iter() {
+            for keyframe in layer.keyframes.iter() {
+                import.keyframe(layer.layer_id, keyframe)?;
+            }
+        }
+
+        // Recreate the annotations (these keep their IDs and their resolved state)
+        for (annotation_id, annotation) in self.annotations.iter() {
+            let annotation = Annotation::deserialize(&mut annotation.chars()).ok_or(InterchangeError::CannotParseAnnotation(*annotation_id))?;
+
+            import.edits.push(AnimationEdit::Annotation(ElementId::Assigned(*annotation_id), AnnotationEdit::Create(annotation)));
+        }
+
+        // Locked layers are locked once everything has been drawn on them
+        for layer in layers.iter().filter(|layer| layer.locked) {
+            import.final_edits.push(AnimationEdit::Layer(layer.layer_id, LayerEdit::SetLocked(true)));
+        }
+
+        // Final set of edits
+        let mut edits = import.edits;
+        edits.extend(import.final_edits);
+
+        Ok(edits)
+    }
+}
+
+///
+/// Recreates the animation described by the text form of an interchange document
+///
+/// The animation should be empty: the document is imported as a single set of edits, so it can be undone in one step.
+///
+pub fn import_animation(document: &str, animation: &dyn EditableAnimation) -> Result<(), InterchangeError> {
+    let document    = AnimationDocument::from_text(document)?;
+    let edits       = document.to_edits()?;
+
+    animation.perform_edits(edits);
+
+    Ok(())
+}
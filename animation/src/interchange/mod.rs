@@ -0,0 +1,15 @@
+//!
+//! A versioned text format for exchanging whole animations
+//!
+//! An interchange document contains everything that's held by the storage layer for an animation, with one
+//! item per line so that changes produce readable diffs. Documents are read from a storage layer by
+//! `export_animation()` and recreated by `import_animation()`, which generates edits for an `EditableAnimation`.
+//!
+
+mod document;
+mod export;
+mod import;
+
+pub use self::document::*;
+pub use self::export::*;
+pub use self::import::*;
@@ -19,6 +19,8 @@ pub mod raycast;
 pub mod serializer;
 pub mod storage;
 pub mod editor;
+pub mod export;
+pub mod interchange;
 
 pub use self::traits::*;
 pub use self::onion_skin::*;
@@ -29,6 +29,11 @@ pub fn onion_skin_for_layer(layer: Arc<dyn Layer>, when: Duration) -> CacheProce
 
             // Generate the onion skin path for this frame
             for element in elements {
+                // Reference images would cover up the onion skin, so they're left out
+                if VectorType::from(&element) == VectorType::Image {
+                    continue;
+                }
+
                 // Fetch the attachment IDs
                 let element_attachments = frame.attached_elements(element.id()).into_iter().map(|(id, _type)| id).collect::<Vec<_>>();
 
@@ -44,10 +44,14 @@ impl RaycastEdge {
             Vector::Transformation(_transform)  => { Box::new(iter::empty()) }
             Vector::Error                       => { Box::new(iter::empty()) }
 
+            // Images are references to draw over, so they don't form the edges of filled areas
+            Vector::Image(_image)               => { Box::new(iter::empty()) }
+
             Vector::Transformed(transform)      => { Self::from_transformed(transform, properties) }
             Vector::BrushStroke(brush_stroke)   => { Self::from_brush_stroke(brush_stroke, properties) }
             Vector::Path(path)                  => { Box::new(Self::from_path_element(path)) }
-            Vector::Group(group_element)        => { Box::new(Self::from_group(group_element, properties)) }
+            Vector::Text(text)                  => { Self::from_text(text, properties) }
+            Vector::Group(group_element)        => { Self::from_group(group_element, properties) }
         }
     }
 
@@ -68,15 +72,39 @@ impl RaycastEdge {
     ///
     /// Retrieves the edges corresponding to a group element
     ///
-    pub fn from_group<'a>(group: &'a GroupElement, properties: Arc<VectorProperties>) -> impl 'a+Iterator<Item=Self> {
+    pub fn from_group<'a>(group: &'a GroupElement, properties: Arc<VectorProperties>) -> Box<dyn 'a+Iterator<Item=Self>> {
         let element_id = group.id();
 
-        group.elements()
-            .flat_map(move |element| Self::from_vector(element, properties.clone()))
-            .map(move |mut element| {
-                element.element_id = element_id;
-                element
-            })
+        match group.group_type() {
+            GroupType::Normal | GroupType::Added    => {
+                // The edges are the edges of all of the elements in the group
+                Box::new(group.elements()
+                    .flat_map(move |element| Self::from_vector(element, properties.clone()))
+                    .map(move |mut element| {
+                        element.element_id = element_id;
+                        element
+                    }))
+            }
+
+            GroupType::Subtracted | GroupType::Masked | GroupType::InvertedMask => {
+                // Parts of the elements are hidden by these group types, so the edges are taken from the combined path
+                let paths = group.to_path(&*properties, PathConversion::Fastest).unwrap_or_else(|| vec![]);
+
+                Box::new(paths.into_iter()
+                    .flat_map(move |path| Self::from_path(element_id, &path, RaycastEdgeKind::Solid).collect::<Vec<_>>()))
+            }
+        }
+    }
+
+    ///
+    /// Retrieves the edges corresponding to a text element (the outlines of its glyphs)
+    ///
+    pub fn from_text<'a>(text: &'a TextElement, properties: Arc<VectorProperties>) -> Box<dyn 'a+Iterator<Item=Self>> {
+        let element_id  = text.id();
+        let outlines    = text.to_path(&*properties, PathConversion::Fastest).unwrap_or_else(|| vec![]);
+
+        Box::new(outlines.into_iter()
+            .flat_map(move |path| Self::from_path(element_id, &path, RaycastEdgeKind::Solid).collect::<Vec<_>>()))
     }
 
     ///
@@ -0,0 +1,98 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl Annotation {
+    ///
+    /// Generates a serialized version of this annotation on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // v0
+        data.write_small_u64(0);
+
+        data.write_small_u64(self.layer_id);
+        data.write_duration(self.when);
+        data.write_str(&self.author);
+        data.write_u64(self.timestamp);
+
+        match self.position {
+            Some((x, y))    => { data.write_chr('P'); data.write_f32(x); data.write_f32(y); }
+            None            => { data.write_chr('-'); }
+        }
+
+        data.write_str(&self.text);
+        data.write_chr(if self.resolved { 'R' } else { 'U' });
+    }
+
+    ///
+    /// Generates a serialized version of this annotation as a string
+    ///
+    pub fn serialize_to_string(&self) -> String {
+        let mut result = String::new();
+        self.serialize(&mut result);
+        result
+    }
+
+    ///
+    /// Deserializes an annotation from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<Annotation> {
+        match data.next_small_u64() {
+            0 => {
+                let layer_id    = data.next_small_u64();
+                let when        = data.next_duration();
+                let author      = data.next_string();
+                let timestamp   = data.next_u64();
+
+                let position    = match data.next_chr() {
+                    'P' => Some((data.next_f32(), data.next_f32())),
+                    '-' => None,
+                    _   => { return None; }
+                };
+
+                let text        = data.next_string();
+
+                let resolved    = match data.next_chr() {
+                    'R' => true,
+                    'U' => false,
+                    _   => { return None; }
+                };
+
+                Some(Annotation {
+                    layer_id:   layer_id,
+                    when:       when,
+                    author:     author,
+                    timestamp:  timestamp,
+                    position:   position,
+                    text:       text,
+                    resolved:   resolved
+                })
+            }
+
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration};
+
+    #[test]
+    fn annotation_with_position() {
+        let annotation  = Annotation::new(2, Duration::from_millis(420), "Director", 1_600_000_000, Some((100.0, 200.0)), "Make the arm swing wider");
+        let encoded     = annotation.serialize_to_string();
+
+        assert!(Annotation::deserialize(&mut encoded.chars()) == Some(annotation));
+    }
+
+    #[test]
+    fn resolved_annotation_without_position() {
+        let mut annotation  = Annotation::new(0, Duration::from_millis(0), "", 0, None, "Timing is off\non this frame");
+        annotation.resolved = true;
+        let encoded         = annotation.serialize_to_string();
+
+        assert!(Annotation::deserialize(&mut encoded.chars()) == Some(annotation));
+    }
+}
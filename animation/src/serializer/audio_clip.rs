@@ -0,0 +1,79 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl AudioClip {
+    ///
+    /// Generates a serialized version of this audio clip on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // v0
+        data.write_small_u64(0);
+
+        data.write_u32(self.sample_rate());
+        data.write_small_u64(self.channels() as u64);
+        data.write_usize(self.samples().len());
+
+        let bytes = self.samples().iter()
+            .flat_map(|sample| sample.to_le_bytes().to_vec())
+            .collect::<Vec<_>>();
+        data.write_bytes(&bytes);
+    }
+
+    ///
+    /// Deserializes an audio clip from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<AudioClip> {
+        match data.next_small_u64() {
+            0 => {
+                let sample_rate = data.next_u32();
+                let channels    = data.next_small_u64() as u16;
+                let num_samples = data.next_usize();
+                let num_bytes   = num_samples.checked_mul(2)?;
+
+                let bytes       = data.next_bytes(num_bytes);
+                let samples     = bytes.chunks_exact(2)
+                    .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+                    .collect();
+
+                Some(AudioClip::new(sample_rate, channels, samples))
+            }
+
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn audio_clip() {
+        let clip        = AudioClip::new(44100, 2, vec![0, 1, -1, 32767, -32768, 1234, -4321]);
+        let mut encoded = String::new();
+        clip.serialize(&mut encoded);
+
+        assert!(AudioClip::deserialize(&mut encoded.chars()) == Some(clip));
+    }
+
+    #[test]
+    fn empty_audio_clip() {
+        let clip        = AudioClip::new(8000, 1, vec![]);
+        let mut encoded = String::new();
+        clip.serialize(&mut encoded);
+
+        assert!(AudioClip::deserialize(&mut encoded.chars()) == Some(clip));
+    }
+
+    #[test]
+    fn audio_clip_with_too_many_samples() {
+        let mut encoded = String::new();
+        encoded.write_small_u64(0);
+        encoded.write_u32(8000);
+        encoded.write_small_u64(1);
+        encoded.write_usize(usize::max_value());
+
+        assert!(AudioClip::deserialize(&mut encoded.chars()) == None);
+    }
+}
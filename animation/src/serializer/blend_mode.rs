@@ -0,0 +1,69 @@
+use super::source::*;
+use super::target::*;
+
+use flo_canvas::*;
+
+///
+/// Generates a serialized version of a blend mode on the specified data target
+///
+pub fn serialize_blend_mode<Tgt: AnimationDataTarget>(blend_mode: &BlendMode, data: &mut Tgt) {
+    use self::BlendMode::*;
+
+    match blend_mode {
+        SourceOver      => { data.write_chr('S'); }
+        SourceIn        => { data.write_chr('i'); }
+        SourceOut       => { data.write_chr('o'); }
+        DestinationOver => { data.write_chr('D'); }
+        DestinationIn   => { data.write_chr('I'); }
+        DestinationOut  => { data.write_chr('O'); }
+        SourceAtop      => { data.write_chr('a'); }
+        DestinationAtop => { data.write_chr('A'); }
+
+        Multiply        => { data.write_chr('m'); }
+        Screen          => { data.write_chr('s'); }
+        Darken          => { data.write_chr('d'); }
+        Lighten         => { data.write_chr('l'); }
+    }
+}
+
+///
+/// Deserializes a blend mode from a data source
+///
+pub fn deserialize_blend_mode<Src: AnimationDataSource>(data: &mut Src) -> Option<BlendMode> {
+    use self::BlendMode::*;
+
+    match data.next_chr() {
+        'S' => Some(SourceOver),
+        'i' => Some(SourceIn),
+        'o' => Some(SourceOut),
+        'D' => Some(DestinationOver),
+        'I' => Some(DestinationIn),
+        'O' => Some(DestinationOut),
+        'a' => Some(SourceAtop),
+        'A' => Some(DestinationAtop),
+
+        'm' => Some(Multiply),
+        's' => Some(Screen),
+        'd' => Some(Darken),
+        'l' => Some(Lighten),
+
+        _   => None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_blend_modes() {
+        use self::BlendMode::*;
+
+        for blend_mode in vec![SourceOver, SourceIn, SourceOut, DestinationOver, DestinationIn, DestinationOut, SourceAtop, DestinationAtop, Multiply, Screen, Darken, Lighten] {
+            let mut encoded = String::new();
+            serialize_blend_mode(&blend_mode, &mut encoded);
+
+            assert!(deserialize_blend_mode(&mut encoded.chars()) == Some(blend_mode));
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::color::*;
 use super::source::*;
 use super::target::*;
 use super::super::traits::*;
@@ -32,11 +33,12 @@ impl InkDefinition {
     /// Generates a serialized version of this brush definition on the specified data target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        data.write_small_u64(0);        // v0 definition
+        data.write_small_u64(1);        // v1 definition
 
         data.write_f32(self.min_width);
         data.write_f32(self.max_width);
         data.write_f32(self.scale_up_distance);
+        self.dynamics.serialize(data);
     }
 
     ///
@@ -48,28 +50,147 @@ impl InkDefinition {
                 Some(InkDefinition {
                     min_width:          data.next_f32(),
                     max_width:          data.next_f32(),
-                    scale_up_distance:  data.next_f32()
+                    scale_up_distance:  data.next_f32(),
+                    dynamics:           BrushDynamics::default()
                 })
             }
+            1 => {
+                let min_width           = data.next_f32();
+                let max_width           = data.next_f32();
+                let scale_up_distance   = data.next_f32();
+                let dynamics            = BrushDynamics::deserialize(data)?;
+
+                Some(InkDefinition {
+                    min_width, max_width, scale_up_distance, dynamics
+                })
+            }
+            _ => { None }
+        }
+    }
+}
+
+impl BrushDynamics {
+    ///
+    /// Generates a serialized version of these brush dynamics on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        data.write_small_u64(0);        // v0 dynamics
+
+        for responses in [&self.width, &self.opacity, &self.color].iter() {
+            data.write_usize(responses.len());
+            responses.iter().for_each(|response| response.serialize(data));
+        }
+
+        serialize_color(&self.secondary_color, data);
+        data.write_f32(self.full_speed);
+    }
+
+    ///
+    /// Deserializes brush dynamics from the specified data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<BrushDynamics> {
+        match data.next_small_u64() {
+            0 => {
+                let mut responses = vec![];
+
+                for _ in 0..3 {
+                    let num_responses   = data.next_usize();
+                    let mut property    = vec![];
+
+                    for _ in 0..num_responses {
+                        property.push(DynamicsResponse::deserialize(data)?);
+                    }
+
+                    responses.push(property);
+                }
+
+                let color       = responses.pop()?;
+                let opacity     = responses.pop()?;
+                let width       = responses.pop()?;
+
+                let secondary_color = deserialize_color(data)?;
+                let full_speed      = data.next_f32();
+
+                Some(BrushDynamics {
+                    width, opacity, color, secondary_color, full_speed
+                })
+            }
+
             _ => { None }
         }
     }
 }
 
+impl DynamicsResponse {
+    ///
+    /// Generates a serialized version of this dynamics response on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        match self.input {
+            DynamicsInput::Pressure => data.write_chr('P'),
+            DynamicsInput::Tilt     => data.write_chr('T'),
+            DynamicsInput::Speed    => data.write_chr('S')
+        }
+
+        data.write_usize(self.curve.points.len());
+        for (input, output) in self.curve.points.iter() {
+            data.write_f32(*input);
+            data.write_f32(*output);
+        }
+    }
+
+    ///
+    /// Deserializes a dynamics response from the specified data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<DynamicsResponse> {
+        let input = match data.next_chr() {
+            'P' => DynamicsInput::Pressure,
+            'T' => DynamicsInput::Tilt,
+            'S' => DynamicsInput::Speed,
+            _   => { return None; }
+        };
+
+        let num_points  = data.next_usize();
+        let mut points  = vec![];
+        for _ in 0..num_points {
+            points.push((data.next_f32(), data.next_f32()));
+        }
+
+        Some(DynamicsResponse::new(input, ResponseCurve::from_points(points)))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use flo_canvas::*;
 
     #[test]
     fn brush_ink_defn_1() {
         let mut encoded = String::new();
-        BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0 }).serialize(&mut encoded);
+        BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0, dynamics: BrushDynamics::default() }).serialize(&mut encoded);
 
-        assert!(BrushDefinition::deserialize(&mut encoded.chars()) == Some(BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0 })));
+        assert!(BrushDefinition::deserialize(&mut encoded.chars()) == Some(BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0, dynamics: BrushDynamics::default() })));
     }
 
     #[test]
     fn brush_ink_defn_2() {
-        assert!(BrushDefinition::deserialize(&mut "IAAAAg/AAAAAABAAAQAB".chars()) == Some(BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0 })));
+        assert!(BrushDefinition::deserialize(&mut "IAAAAg/AAAAAABAAAQAB".chars()) == Some(BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0, dynamics: BrushDynamics::default() })));
+    }
+
+    #[test]
+    fn brush_ink_defn_with_dynamics() {
+        let dynamics = BrushDynamics {
+            width:              vec![DynamicsResponse::new(DynamicsInput::Pressure, ResponseCurve::soft())],
+            opacity:            vec![DynamicsResponse::new(DynamicsInput::Speed, ResponseCurve::inverted()), DynamicsResponse::new(DynamicsInput::Tilt, ResponseCurve::linear())],
+            color:              vec![DynamicsResponse::new(DynamicsInput::Tilt, ResponseCurve::firm())],
+            secondary_color:    Color::Rgba(0.1, 0.2, 0.3, 1.0),
+            full_speed:         12.0
+        };
+
+        let mut encoded = String::new();
+        BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0, dynamics: dynamics.clone() }).serialize(&mut encoded);
+
+        assert!(BrushDefinition::deserialize(&mut encoded.chars()) == Some(BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0, dynamics: dynamics })));
     }
 }
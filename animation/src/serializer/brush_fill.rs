@@ -0,0 +1,108 @@
+use super::color::*;
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+use flo_canvas::*;
+
+impl BrushFill {
+    ///
+    /// Generates a serialized version of this brush fill on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::BrushFill::*;
+
+        match self {
+            Solid                                   => { data.write_chr('S'); }
+            LinearGradient(color, start, end)       => { data.write_chr('L'); serialize_color(color, data); data.write_f32(start.0); data.write_f32(start.1); data.write_f32(end.0); data.write_f32(end.1); }
+            RadialGradient(color, center, radius)   => { data.write_chr('R'); serialize_color(color, data); data.write_f32(center.0); data.write_f32(center.1); data.write_f32(*radius); }
+            Texture(pattern, background, size)      => {
+                data.write_chr('T');
+                data.write_chr(match pattern {
+                    TexturePattern::Stripes => 's',
+                    TexturePattern::Checks  => 'c',
+                    TexturePattern::Dots    => 'd'
+                });
+                serialize_color(background, data);
+                data.write_f32(*size);
+            }
+        }
+    }
+
+    ///
+    /// Deserializes a brush fill from a source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<BrushFill> {
+        match data.next_chr() {
+            'S' => Some(BrushFill::Solid),
+
+            'L' => {
+                let color   = deserialize_color(data)?;
+                let start   = (data.next_f32(), data.next_f32());
+                let end     = (data.next_f32(), data.next_f32());
+
+                Some(BrushFill::LinearGradient(color, start, end))
+            }
+
+            'R' => {
+                let color   = deserialize_color(data)?;
+                let center  = (data.next_f32(), data.next_f32());
+                let radius  = data.next_f32();
+
+                Some(BrushFill::RadialGradient(color, center, radius))
+            }
+
+            'T' => {
+                let pattern = match data.next_chr() {
+                    's' => TexturePattern::Stripes,
+                    'c' => TexturePattern::Checks,
+                    'd' => TexturePattern::Dots,
+                    _   => { return None; }
+                };
+                let background  = deserialize_color(data)?;
+                let size        = data.next_f32();
+
+                Some(BrushFill::Texture(pattern, background, size))
+            }
+
+            _   => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solid() {
+        let mut encoded = String::new();
+        BrushFill::Solid.serialize(&mut encoded);
+
+        assert!(BrushFill::deserialize(&mut encoded.chars()) == Some(BrushFill::Solid));
+    }
+
+    #[test]
+    fn linear_gradient() {
+        let mut encoded = String::new();
+        BrushFill::LinearGradient(Color::Rgba(0.1, 0.2, 0.3, 0.4), (0.0, 0.5), (1.0, 0.25)).serialize(&mut encoded);
+
+        assert!(BrushFill::deserialize(&mut encoded.chars()) == Some(BrushFill::LinearGradient(Color::Rgba(0.1, 0.2, 0.3, 0.4), (0.0, 0.5), (1.0, 0.25))));
+    }
+
+    #[test]
+    fn radial_gradient() {
+        let mut encoded = String::new();
+        BrushFill::RadialGradient(Color::Rgba(0.1, 0.2, 0.3, 0.4), (0.5, 0.5), 0.75).serialize(&mut encoded);
+
+        assert!(BrushFill::deserialize(&mut encoded.chars()) == Some(BrushFill::RadialGradient(Color::Rgba(0.1, 0.2, 0.3, 0.4), (0.5, 0.5), 0.75)));
+    }
+
+    #[test]
+    fn texture() {
+        let mut encoded = String::new();
+        BrushFill::Texture(TexturePattern::Checks, Color::Rgba(0.1, 0.2, 0.3, 0.4), 12.0).serialize(&mut encoded);
+
+        assert!(BrushFill::deserialize(&mut encoded.chars()) == Some(BrushFill::Texture(TexturePattern::Checks, Color::Rgba(0.1, 0.2, 0.3, 0.4), 12.0)));
+    }
+}
@@ -8,30 +8,44 @@ impl BrushProperties {
     /// Generates a serialized version of these brush properties on the specified data target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        // v0
-        data.write_small_u64(0);
+        // v1
+        data.write_small_u64(1);
 
         data.write_f32(self.size);
         data.write_f32(self.opacity);
         serialize_color(&self.color, data);
+        self.fill.serialize(data);
     }
 
     ///
     /// Deserializes brush properties from a stream
     ///
     pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<BrushProperties> {
-        if data.next_small_u64() == 0 {
-            let size    = data.next_f32();
-            let opacity = data.next_f32();
-            let color   = deserialize_color(data);
-
-            color.map(|color| {
-                BrushProperties {
-                    size, opacity, color
-                }
-            })
-        } else {
-            None
+        match data.next_small_u64() {
+            0 => {
+                let size    = data.next_f32();
+                let opacity = data.next_f32();
+                let color   = deserialize_color(data);
+
+                color.map(|color| {
+                    BrushProperties {
+                        size, opacity, color, fill: BrushFill::Solid
+                    }
+                })
+            }
+
+            1 => {
+                let size    = data.next_f32();
+                let opacity = data.next_f32();
+                let color   = deserialize_color(data)?;
+                let fill    = BrushFill::deserialize(data)?;
+
+                Some(BrushProperties {
+                    size, opacity, color, fill
+                })
+            }
+
+            _ => None
         }
     }
 }
@@ -44,13 +58,31 @@ mod test {
     #[test]
     fn brush_properties_1() {
         let mut encoded = String::new();
-        BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0) }.serialize(&mut encoded);
+        BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0), fill: BrushFill::Solid }.serialize(&mut encoded);
 
-        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0) }));
+        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0), fill: BrushFill::Solid }));
     }
 
     #[test]
     fn brush_properties_2() {
-        assert!(BrushProperties::deserialize(&mut "AAAAoBBAAAg/AhzMTmZamZ//P".chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0) }));
+        assert!(BrushProperties::deserialize(&mut "AAAAoBBAAAg/AhzMTmZamZ//P".chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0), fill: BrushFill::Solid }));
+    }
+
+    #[test]
+    fn brush_properties_with_gradient_fill() {
+        let properties  = BrushProperties { size: 5.0, opacity: 0.5, color: Color::Rgba(0.1, 0.2, 0.3, 1.0), fill: BrushFill::LinearGradient(Color::Rgba(0.4, 0.5, 0.6, 1.0), (0.0, 0.0), (1.0, 1.0)) };
+        let mut encoded = String::new();
+        properties.serialize(&mut encoded);
+
+        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(properties));
+    }
+
+    #[test]
+    fn brush_properties_with_texture_fill() {
+        let properties  = BrushProperties { size: 5.0, opacity: 0.5, color: Color::Rgba(0.1, 0.2, 0.3, 1.0), fill: BrushFill::Texture(TexturePattern::Dots, Color::Rgba(1.0, 1.0, 1.0, 1.0), 16.0) };
+        let mut encoded = String::new();
+        properties.serialize(&mut encoded);
+
+        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(properties));
     }
 }
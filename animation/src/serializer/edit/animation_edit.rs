@@ -2,6 +2,8 @@ use super::super::source::*;
 use super::super::target::*;
 use super::super::super::traits::*;
 
+use std::sync::*;
+
 impl AnimationEdit {
     ///
     /// Generates a serialized version of this edit on the specified data target
@@ -13,9 +15,48 @@ impl AnimationEdit {
             Layer(layer_id, edit)       => { data.write_chr('L'); data.write_small_u64(*layer_id); edit.serialize(data); },
             Element(elements, edit)     => { data.write_chr('E'); data.write_usize(elements.len()); elements.iter().for_each(|elem| elem.serialize(data)); edit.serialize(data); },
             Motion(element, edit)       => { data.write_chr('M'); element.serialize(data); edit.serialize(data); },
+            Annotation(element, edit)   => { data.write_chr('N'); element.serialize(data); edit.serialize(data); },
+            AddImage(element, image)    => { data.write_chr('I'); element.serialize(data); image.serialize(data); },
+            AddAudioClip(element, clip) => { data.write_chr('A'); element.serialize(data); clip.serialize(data); },
             SetSize(width, height)      => { data.write_chr('S'); data.write_f64(*width); data.write_f64(*height); },
+            SetFrameLength(length)      => { data.write_chr('f'); data.write_duration(*length); },
+            SetDuration(duration)       => { data.write_chr('d'); data.write_duration(*duration); },
             AddNewLayer(layer_id)       => { data.write_chr('+'); data.write_small_u64(*layer_id); },
             RemoveLayer(layer_id)       => { data.write_chr('-'); data.write_small_u64(*layer_id); }
+            Undo                        => { data.write_chr('U'); }
+            Redo                        => { data.write_chr('R'); }
+            SelectUndoHistory(history)  => { data.write_chr('u'); data.write_small_u64(*history); }
+        }
+    }
+
+    ///
+    /// Generates the serialized version of this edit that's written to the edit log
+    ///
+    /// Images and audio clips are stored separately by the storage layer, so only their IDs are logged for the edits that add them
+    ///
+    pub fn serialize_for_edit_log<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::AnimationEdit::*;
+
+        match self {
+            AddImage(image_id, _)       => { data.write_chr('i'); image_id.serialize(data); },
+            AddAudioClip(clip_id, _)    => { data.write_chr('a'); clip_id.serialize(data); },
+            other                       => { other.serialize(data); }
+        }
+    }
+
+    ///
+    /// Deserializes an edit written by `serialize_for_edit_log()`
+    ///
+    /// The images and audio clips for the edits that add them are retrieved by ID using the supplied functions
+    ///
+    pub fn deserialize_from_edit_log<Src, LoadImageFn, LoadClipFn>(data: &mut Src, load_image: LoadImageFn, load_audio_clip: LoadClipFn) -> Option<AnimationEdit>
+    where   Src:            AnimationDataSource,
+            LoadImageFn:    FnOnce(ElementId) -> Option<Arc<ReferenceImage>>,
+            LoadClipFn:     FnOnce(ElementId) -> Option<Arc<AudioClip>> {
+        match data.next_chr() {
+            'i'     => { ElementId::deserialize(data).and_then(|image_id| load_image(image_id).map(move |image| AnimationEdit::AddImage(image_id, image))) }
+            'a'     => { ElementId::deserialize(data).and_then(|clip_id| load_audio_clip(clip_id).map(move |clip| AnimationEdit::AddAudioClip(clip_id, clip))) }
+            other   => { Self::deserialize_with_code(other, data) }
         }
     }
 
@@ -23,12 +64,28 @@ impl AnimationEdit {
     /// Deserializes an animation edit
     ///
     pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<AnimationEdit> {
-        match data.next_chr() {
+        let code = data.next_chr();
+        Self::deserialize_with_code(code, data)
+    }
+
+    ///
+    /// Deserializes the remainder of an animation edit once its type code has been read
+    ///
+    fn deserialize_with_code<Src: AnimationDataSource>(code: char, data: &mut Src) -> Option<AnimationEdit> {
+        match code {
             'L' => { let layer_id = data.next_small_u64(); LayerEdit::deserialize(data).map(move |edit| AnimationEdit::Layer(layer_id, edit)) }
             'M' => { ElementId::deserialize(data).and_then(|elem| MotionEdit::deserialize(data).map(move |edit| AnimationEdit::Motion(elem, edit))) }
+            'N' => { ElementId::deserialize(data).and_then(|elem| AnnotationEdit::deserialize(data).map(move |edit| AnimationEdit::Annotation(elem, edit))) }
+            'I' => { ElementId::deserialize(data).and_then(|elem| ReferenceImage::deserialize(data).map(move |image| AnimationEdit::AddImage(elem, Arc::new(image)))) }
+            'A' => { ElementId::deserialize(data).and_then(|elem| AudioClip::deserialize(data).map(move |clip| AnimationEdit::AddAudioClip(elem, Arc::new(clip)))) }
             'S' => { Some(AnimationEdit::SetSize(data.next_f64(), data.next_f64())) }
+            'f' => { Some(AnimationEdit::SetFrameLength(data.next_duration())) }
+            'd' => { Some(AnimationEdit::SetDuration(data.next_duration())) }
             '+' => { Some(AnimationEdit::AddNewLayer(data.next_small_u64())) }
             '-' => { Some(AnimationEdit::RemoveLayer(data.next_small_u64())) }
+            'U' => { Some(AnimationEdit::Undo) }
+            'R' => { Some(AnimationEdit::Redo) }
+            'u' => { Some(AnimationEdit::SelectUndoHistory(data.next_small_u64())) }
 
             'E' => { 
                 let num_elements    = data.next_usize();
@@ -101,6 +158,22 @@ mod test {
         assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::SetSize(1024.0, 768.0)));
     }
 
+    #[test]
+    fn set_frame_length() {
+        let mut encoded = String::new();
+        AnimationEdit::SetFrameLength(Duration::from_micros(41_667)).serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::SetFrameLength(Duration::from_micros(41_667))));
+    }
+
+    #[test]
+    fn set_duration() {
+        let mut encoded = String::new();
+        AnimationEdit::SetDuration(Duration::from_secs(30)).serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::SetDuration(Duration::from_secs(30))));
+    }
+
     #[test]
     fn add_new_layer() {
         let mut encoded = String::new();
@@ -117,6 +190,89 @@ mod test {
         assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::RemoveLayer(42)));
     }
 
+    #[test]
+    fn undo() {
+        let mut encoded = String::new();
+        AnimationEdit::Undo.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::Undo));
+    }
+
+    #[test]
+    fn redo() {
+        let mut encoded = String::new();
+        AnimationEdit::Redo.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::Redo));
+    }
+
+    #[test]
+    fn select_undo_history() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::SelectUndoHistory(3<<32 | 2);
+        edit.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn add_image() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::AddImage(ElementId::Assigned(42), Arc::new(ReferenceImage::from_rgba(2, 1, &[255, 255, 255, 255, 0, 0, 0, 255]).unwrap()));
+        edit.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn log_add_image_by_id() {
+        let mut encoded = String::new();
+        let image       = Arc::new(ReferenceImage::from_rgba(2, 1, &[255, 255, 255, 255, 0, 0, 0, 255]).unwrap());
+        let edit        = AnimationEdit::AddImage(ElementId::Assigned(42), Arc::clone(&image));
+        edit.serialize_for_edit_log(&mut encoded);
+
+        // Only the ID is written to the log
+        let mut full    = String::new();
+        edit.serialize(&mut full);
+        assert!(encoded.len() < full.len());
+
+        let mut loaded  = vec![];
+        let decoded     = AnimationEdit::deserialize_from_edit_log(&mut encoded.chars(), |image_id| { loaded.push(image_id); Some(Arc::clone(&image)) }, |_| None);
+
+        assert!(decoded == Some(edit));
+        assert!(loaded == vec![ElementId::Assigned(42)]);
+    }
+
+    #[test]
+    fn log_add_audio_clip_by_id() {
+        let mut encoded = String::new();
+        let clip        = Arc::new(AudioClip::new(8000, 1, vec![0, 100, -100, 200]));
+        let edit        = AnimationEdit::AddAudioClip(ElementId::Assigned(42), Arc::clone(&clip));
+        edit.serialize_for_edit_log(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == None);
+        assert!(AnimationEdit::deserialize_from_edit_log(&mut encoded.chars(), |_| None, |_| Some(Arc::clone(&clip))) == Some(edit));
+        assert!(AnimationEdit::deserialize_from_edit_log(&mut encoded.chars(), |_| None, |_| None) == None);
+    }
+
+    #[test]
+    fn log_other_edits_in_full() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(1000)));
+        edit.serialize_for_edit_log(&mut encoded);
+
+        assert!(AnimationEdit::deserialize_from_edit_log(&mut encoded.chars(), |_| None, |_| None) == Some(edit));
+    }
+
+    #[test]
+    fn add_audio_clip() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::AddAudioClip(ElementId::Assigned(42), Arc::new(AudioClip::new(8000, 1, vec![0, 100, -100, 200])));
+        edit.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
     #[test]
     fn layer_edit() {
         let mut encoded = String::new();
@@ -143,4 +299,13 @@ mod test {
 
         assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
+
+    #[test]
+    fn annotation_edit() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::Annotation(ElementId::Assigned(42), AnnotationEdit::SetResolved(true));
+        edit.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
 }
@@ -0,0 +1,67 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl AnnotationEdit {
+    ///
+    /// Generates a serialized version of this edit on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::AnnotationEdit::*;
+
+        match self {
+            Create(annotation)      => { data.write_chr('+'); annotation.serialize(data); }
+            Delete                  => { data.write_chr('-'); }
+            SetResolved(true)       => { data.write_chr('R'); }
+            SetResolved(false)      => { data.write_chr('U'); }
+        }
+    }
+
+    ///
+    /// Deserializes an annotation edit from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<AnnotationEdit> {
+        match data.next_chr() {
+            '+'     => Annotation::deserialize(data).map(|annotation| AnnotationEdit::Create(annotation)),
+            '-'     => Some(AnnotationEdit::Delete),
+            'R'     => Some(AnnotationEdit::SetResolved(true)),
+            'U'     => Some(AnnotationEdit::SetResolved(false)),
+
+            _       => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration};
+
+    #[test]
+    fn create() {
+        let edit        = AnnotationEdit::Create(Annotation::new(1, Duration::from_millis(40), "Director", 1234, Some((10.0, 20.0)), "Note"));
+        let mut encoded = String::new();
+        edit.serialize(&mut encoded);
+
+        assert!(AnnotationEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn delete() {
+        let mut encoded = String::new();
+        AnnotationEdit::Delete.serialize(&mut encoded);
+
+        assert!(AnnotationEdit::deserialize(&mut encoded.chars()) == Some(AnnotationEdit::Delete));
+    }
+
+    #[test]
+    fn set_resolved() {
+        let mut encoded = String::new();
+        AnnotationEdit::SetResolved(true).serialize(&mut encoded);
+        AnnotationEdit::SetResolved(false).serialize(&mut encoded);
+
+        let mut chars = encoded.chars();
+        assert!(AnnotationEdit::deserialize(&mut chars) == Some(AnnotationEdit::SetResolved(true)));
+        assert!(AnnotationEdit::deserialize(&mut chars) == Some(AnnotationEdit::SetResolved(false)));
+    }
+}
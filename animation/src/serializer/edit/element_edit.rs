@@ -23,6 +23,14 @@ impl ElementEdit {
             ConvertToPath                   => { data.write_chr('p'); }
             Group(group_id, group_type)     => { data.write_chr('g'); group_id.serialize(data); group_type.serialize(data); }
             Ungroup                         => { data.write_chr('u'); }
+            CombinePaths(new_id, operation) => { data.write_chr('B'); new_id.serialize(data); operation.serialize(data); }
+            SetText(text)                   => { data.write_chr('x'); data.write_str(text); }
+            SetTextStyle(style)             => { data.write_chr('y'); style.serialize(data); }
+            InsertPathNode(index, pos)      => { data.write_chr('i'); data.write_usize(*index); data.write_f32(*pos); }
+            DeletePathNode(index)           => { data.write_chr('d'); data.write_usize(*index); }
+            SetPathNodeType(index, typ)     => { data.write_chr('n'); data.write_usize(*index); typ.serialize(data); }
+            BreakPath(index)                => { data.write_chr('b'); data.write_usize(*index); }
+            JoinPathNodes(first, second)    => { data.write_chr('J'); data.write_usize(*first); data.write_usize(*second); }
 
             SetControlPoints(points, when)  => { 
                 data.write_chr('c');
@@ -101,6 +109,48 @@ impl ElementEdit {
                 Some(ElementEdit::Ungroup)
             }
 
+            'B' => {
+                Some(ElementEdit::CombinePaths(ElementId::deserialize(data)?, PathOperation::deserialize(data)?))
+            }
+
+            'x' => {
+                Some(ElementEdit::SetText(data.next_string()))
+            }
+
+            'y' => {
+                TextStyle::deserialize(data)
+                    .map(|style| ElementEdit::SetTextStyle(style))
+            }
+
+            'i' => {
+                let index   = data.next_usize();
+                let pos     = data.next_f32();
+
+                Some(ElementEdit::InsertPathNode(index, pos))
+            }
+
+            'd' => {
+                Some(ElementEdit::DeletePathNode(data.next_usize()))
+            }
+
+            'n' => {
+                let index   = data.next_usize();
+
+                PathNodeType::deserialize(data)
+                    .map(|node_type| ElementEdit::SetPathNodeType(index, node_type))
+            }
+
+            'b' => {
+                Some(ElementEdit::BreakPath(data.next_usize()))
+            }
+
+            'J' => {
+                let first   = data.next_usize();
+                let second  = data.next_usize();
+
+                Some(ElementEdit::JoinPathNodes(first, second))
+            }
+
             'C' => {
                 // Obsolete version from older versions of FlowBetween
                 let num_points      = data.next_usize();
@@ -234,6 +284,23 @@ mod test {
         assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::ConvertToPath));
     }
 
+    #[test]
+    fn set_text() {
+        let mut encoded = String::new();
+        ElementEdit::SetText("Speech bubble".to_string()).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::SetText("Speech bubble".to_string())));
+    }
+
+    #[test]
+    fn set_text_style() {
+        let style       = TextStyle { font: "Lato Thin".to_string(), size: 12.0, color: flo_canvas::Color::Rgba(1.0, 0.0, 0.0, 1.0), alignment: TextAlignment::Right };
+        let mut encoded = String::new();
+        ElementEdit::SetTextStyle(style.clone()).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::SetTextStyle(style)));
+    }
+
     #[test]
     fn set_control_points() {
         let mut encoded = String::new();
@@ -250,6 +317,48 @@ mod test {
         assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::SetPath(Arc::new(vec![PathComponent::Move(PathPoint::new(1.0, 2.0)), PathComponent::Line(PathPoint::new(2.0, 3.0)), PathComponent::Bezier(PathPoint::new(4.0, 5.0), PathPoint::new(6.0, 7.0), PathPoint::new(8.0, 9.0)), PathComponent::Close]))));
     }
 
+    #[test]
+    fn insert_path_node() {
+        let mut encoded = String::new();
+        ElementEdit::InsertPathNode(3, 0.25).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::InsertPathNode(3, 0.25)));
+    }
+
+    #[test]
+    fn delete_path_node() {
+        let mut encoded = String::new();
+        ElementEdit::DeletePathNode(3).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::DeletePathNode(3)));
+    }
+
+    #[test]
+    fn set_path_node_type() {
+        for node_type in vec![PathNodeType::Smooth, PathNodeType::Corner] {
+            let mut encoded = String::new();
+            ElementEdit::SetPathNodeType(3, node_type).serialize(&mut encoded);
+
+            assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::SetPathNodeType(3, node_type)));
+        }
+    }
+
+    #[test]
+    fn break_path() {
+        let mut encoded = String::new();
+        ElementEdit::BreakPath(3).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::BreakPath(3)));
+    }
+
+    #[test]
+    fn join_path_nodes() {
+        let mut encoded = String::new();
+        ElementEdit::JoinPathNodes(3, 7).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::JoinPathNodes(3, 7)));
+    }
+
     #[test]
     fn group() {
         let mut encoded = String::new();
@@ -266,6 +375,16 @@ mod test {
         assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::Group(ElementId::Unassigned, GroupType::Added)));
     }
 
+    #[test]
+    fn group_subtracted_and_masked() {
+        for group_type in vec![GroupType::Subtracted, GroupType::Masked, GroupType::InvertedMask] {
+            let mut encoded = String::new();
+            ElementEdit::Group(ElementId::Assigned(42), group_type).serialize(&mut encoded);
+
+            assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::Group(ElementId::Assigned(42), group_type)));
+        }
+    }
+
     #[test]
     fn ungroup() {
         let mut encoded = String::new();
@@ -274,6 +393,16 @@ mod test {
         assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::Ungroup));
     }
 
+    #[test]
+    fn combine_paths() {
+        for operation in vec![PathOperation::Union, PathOperation::Subtract, PathOperation::Intersect, PathOperation::Exclude] {
+            let mut encoded = String::new();
+            ElementEdit::CombinePaths(ElementId::Assigned(42), operation).serialize(&mut encoded);
+
+            assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::CombinePaths(ElementId::Assigned(42), operation)));
+        }
+    }
+
     #[test]
     fn transform_set_anchor() {
         let mut encoded = String::new();
@@ -1,5 +1,6 @@
 use super::super::source::*;
 use super::super::target::*;
+use super::super::blend_mode::*;
 use super::super::super::traits::*;
 
 impl LayerEdit {
@@ -10,12 +11,23 @@ impl LayerEdit {
         use self::LayerEdit::*;
 
         match self {
-            Paint(when, edit)       => { data.write_chr('P'); data.write_duration(*when); edit.serialize(data); },
-            Path(when, edit)        => { data.write_chr('p'); data.write_duration(*when); edit.serialize(data); },
-            AddKeyFrame(when)       => { data.write_chr('+'); data.write_duration(*when); },
-            RemoveKeyFrame(when)    => { data.write_chr('-'); data.write_duration(*when); },
-            SetName(name)           => { data.write_chr('N'); data.write_str(name); },
-            SetOrdering(ordering)   => { data.write_chr('O'); data.write_u64(*ordering); }
+            Paint(when, edit)                       => { data.write_chr('P'); data.write_duration(*when); edit.serialize(data); },
+            Path(when, edit)                        => { data.write_chr('p'); data.write_duration(*when); edit.serialize(data); },
+            AddKeyFrame(when)                       => { data.write_chr('+'); data.write_duration(*when); },
+            RemoveKeyFrame(when)                    => { data.write_chr('-'); data.write_duration(*when); },
+            MoveKeyFrame(from, to)                  => { data.write_chr('M'); data.write_duration(*from); data.write_duration(*to); },
+            DuplicateKeyFrame(from, to, new_ids)    => { data.write_chr('D'); data.write_duration(*from); data.write_duration(*to); serialize_element_ids(new_ids, data); },
+            RetimeKeyFrames(from, to)               => { data.write_chr('R'); data.write_duration(from.start); data.write_duration(from.end); data.write_duration(to.start); data.write_duration(to.end); },
+            BakeInbetweens(when, every, new_ids)    => { data.write_chr('K'); data.write_duration(when.start); data.write_duration(when.end); data.write_duration(*every); serialize_element_ids(new_ids, data); },
+            SetName(name)                           => { data.write_chr('N'); data.write_str(name); },
+            SetOrdering(ordering)                   => { data.write_chr('O'); data.write_u64(*ordering); }
+            SetVisibility(visible)                  => { data.write_chr('V'); data.write_chr(if *visible { '+' } else { '-' }); }
+            SetLocked(locked)                       => { data.write_chr('L'); data.write_chr(if *locked { '+' } else { '-' }); }
+            SetOpacity(opacity)                     => { data.write_chr('A'); data.write_f64(*opacity); }
+            SetBlendMode(mode)                      => { data.write_chr('B'); serialize_blend_mode(mode, data); }
+            SetInbetweenMode(mode)                  => { data.write_chr('I'); mode.serialize(data); }
+            ImportAudio(clip_id)                    => { data.write_chr('W'); clip_id.serialize(data); }
+            RemoveAudio                             => { data.write_chr('w'); }
         }
     }
 
@@ -36,14 +48,62 @@ impl LayerEdit {
             }
             '+' => { Some(LayerEdit::AddKeyFrame(data.next_duration())) }
             '-' => { Some(LayerEdit::RemoveKeyFrame(data.next_duration())) }
+            'M' => { let from = data.next_duration(); Some(LayerEdit::MoveKeyFrame(from, data.next_duration())) }
+            'D' => {
+                let from        = data.next_duration();
+                let to          = data.next_duration();
+
+                deserialize_element_ids(data).map(move |new_ids| LayerEdit::DuplicateKeyFrame(from, to, new_ids))
+            }
+            'R' => {
+                let from_start  = data.next_duration();
+                let from_end    = data.next_duration();
+                let to_start    = data.next_duration();
+                let to_end      = data.next_duration();
+
+                Some(LayerEdit::RetimeKeyFrames(from_start..from_end, to_start..to_end))
+            }
+            'K' => {
+                let start       = data.next_duration();
+                let end         = data.next_duration();
+                let every       = data.next_duration();
+
+                deserialize_element_ids(data).map(move |new_ids| LayerEdit::BakeInbetweens(start..end, every, new_ids))
+            }
             'N' => { Some(LayerEdit::SetName(data.next_string())) }
             'O' => { Some(LayerEdit::SetOrdering(data.next_u64())) }
+            'V' => { Some(LayerEdit::SetVisibility(data.next_chr() == '+')) }
+            'L' => { Some(LayerEdit::SetLocked(data.next_chr() == '+')) }
+            'A' => { Some(LayerEdit::SetOpacity(data.next_f64())) }
+            'B' => { deserialize_blend_mode(data).map(|mode| LayerEdit::SetBlendMode(mode)) }
+            'I' => { InbetweenMode::deserialize(data).map(|mode| LayerEdit::SetInbetweenMode(mode)) }
+            'W' => { ElementId::deserialize(data).map(|clip_id| LayerEdit::ImportAudio(clip_id)) }
+            'w' => { Some(LayerEdit::RemoveAudio) }
 
             _   => None
         }
     }
 }
 
+///
+/// Writes out a list of element IDs
+///
+fn serialize_element_ids<Tgt: AnimationDataTarget>(element_ids: &Vec<ElementId>, data: &mut Tgt) {
+    data.write_usize(element_ids.len());
+    element_ids.iter().for_each(|element_id| element_id.serialize(data));
+}
+
+///
+/// Reads a list of element IDs written by `serialize_element_ids`
+///
+fn deserialize_element_ids<Src: AnimationDataSource>(data: &mut Src) -> Option<Vec<ElementId>> {
+    let num_elements = data.next_usize();
+
+    (0..num_elements).into_iter()
+        .map(|_| ElementId::deserialize(data))
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -85,6 +145,42 @@ mod test {
         assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
 
+    #[test]
+    fn move_key_frame() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::MoveKeyFrame(Duration::from_millis(1234), Duration::from_millis(5678));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn duplicate_key_frame() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::DuplicateKeyFrame(Duration::from_millis(1234), Duration::from_millis(5678), vec![ElementId::Assigned(42), ElementId::Assigned(43)]);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn retime_key_frames() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::RetimeKeyFrames(Duration::from_millis(1000)..Duration::from_millis(2000), Duration::from_millis(1500)..Duration::from_millis(3500));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn bake_inbetweens() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::BakeInbetweens(Duration::from_millis(1000)..Duration::from_millis(2000), Duration::from_micros(41667), vec![ElementId::Assigned(42), ElementId::Assigned(43), ElementId::Assigned(44)]);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
     #[test]
     fn set_name() {
         let mut encoded = String::new();
@@ -102,4 +198,67 @@ mod test {
 
         assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
+
+    #[test]
+    fn set_visibility() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetVisibility(false);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_locked() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetLocked(true);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_opacity() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetOpacity(0.25);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_blend_mode() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetBlendMode(flo_canvas::BlendMode::Screen);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_inbetween_mode() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetInbetweenMode(InbetweenMode::MatchByProximity);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn import_audio() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::ImportAudio(ElementId::Assigned(42));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn remove_audio() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::RemoveAudio;
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
 }
@@ -2,16 +2,19 @@ mod path_edit;
 mod layer_edit;
 mod paint_edit;
 mod motion_edit;
+mod annotation_edit;
 mod element_edit;
 mod element_align;
 mod animation_edit;
 mod element_ordering;
 mod element_transform;
+mod path_operation;
 
 pub use self::path_edit::*;
 pub use self::layer_edit::*;
 pub use self::paint_edit::*;
 pub use self::motion_edit::*;
+pub use self::annotation_edit::*;
 pub use self::element_edit::*;
 pub use self::element_align::*;
 pub use self::animation_edit::*;
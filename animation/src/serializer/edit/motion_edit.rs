@@ -15,8 +15,12 @@ impl MotionEdit {
             SetType(MotionType::None)       => { data.write_chr('T'); data.write_chr('-'); }
             SetType(MotionType::Reverse)    => { data.write_chr('T'); data.write_chr('R'); }
             SetType(MotionType::Translate)  => { data.write_chr('T'); data.write_chr('T'); }
+            SetType(MotionType::Rotate)     => { data.write_chr('T'); data.write_chr('A'); }
+            SetType(MotionType::Scale)      => { data.write_chr('T'); data.write_chr('S'); }
+            SetType(MotionType::Fade)       => { data.write_chr('T'); data.write_chr('F'); }
             SetOrigin(x, y)                 => { data.write_chr('O'); data.write_f32(*x); data.write_f32(*y); }
             SetPath(curve)                  => { data.write_chr('P'); curve.serialize(data); }
+            SetEasing(section, easing)      => { data.write_chr('E'); data.write_usize(*section); easing.serialize(data); }
         }
     }
 
@@ -31,11 +35,18 @@ impl MotionEdit {
                 '-' => Some(MotionEdit::SetType(MotionType::None)),
                 'R' => Some(MotionEdit::SetType(MotionType::Reverse)),
                 'T' => Some(MotionEdit::SetType(MotionType::Translate)),
+                'A' => Some(MotionEdit::SetType(MotionType::Rotate)),
+                'S' => Some(MotionEdit::SetType(MotionType::Scale)),
+                'F' => Some(MotionEdit::SetType(MotionType::Fade)),
 
                 _   => None
             },
             'O'     => Some(MotionEdit::SetOrigin(data.next_f32(), data.next_f32())),
-            'P'     => Some(MotionEdit::SetPath(TimeCurve::deserialize(data))),
+            'P'     => TimeCurve::deserialize(data).map(MotionEdit::SetPath),
+            'E'     => {
+                let section = data.next_usize();
+                Easing::deserialize(data).map(|easing| MotionEdit::SetEasing(section, easing))
+            }
 
             _       => None
         }
@@ -87,6 +98,30 @@ mod test {
         assert!(MotionEdit::deserialize(&mut encoded.chars()) == Some(MotionEdit::SetType(MotionType::Translate)));
     }
 
+    #[test]
+    fn set_type_rotate() {
+        let mut encoded = String::new();
+        MotionEdit::SetType(MotionType::Rotate).serialize(&mut encoded);
+
+        assert!(MotionEdit::deserialize(&mut encoded.chars()) == Some(MotionEdit::SetType(MotionType::Rotate)));
+    }
+
+    #[test]
+    fn set_type_scale() {
+        let mut encoded = String::new();
+        MotionEdit::SetType(MotionType::Scale).serialize(&mut encoded);
+
+        assert!(MotionEdit::deserialize(&mut encoded.chars()) == Some(MotionEdit::SetType(MotionType::Scale)));
+    }
+
+    #[test]
+    fn set_type_fade() {
+        let mut encoded = String::new();
+        MotionEdit::SetType(MotionType::Fade).serialize(&mut encoded);
+
+        assert!(MotionEdit::deserialize(&mut encoded.chars()) == Some(MotionEdit::SetType(MotionType::Fade)));
+    }
+
     #[test]
     fn set_origin() {
         let mut encoded = String::new();
@@ -107,4 +142,12 @@ mod test {
             assert!(false);
         }
     }
+
+    #[test]
+    fn set_easing() {
+        let mut encoded = String::new();
+        MotionEdit::SetEasing(3, Easing::CubicBezier(0.25, 0.1, 0.25, 1.0)).serialize(&mut encoded);
+
+        assert!(MotionEdit::deserialize(&mut encoded.chars()) == Some(MotionEdit::SetEasing(3, Easing::CubicBezier(0.25, 0.1, 0.25, 1.0))));
+    }
 }
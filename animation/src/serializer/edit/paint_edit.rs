@@ -36,6 +36,14 @@ impl PaintEdit {
                 }
             },
 
+            CreateBrushStroke(elem, points)                 => {
+                data.write_chr('B');
+                elem.serialize(data);
+
+                // Points are stored in the same way as for a brush stroke element
+                BrushElement::new(*elem, Arc::clone(points)).serialize(data);
+            }
+
             Fill(elem, point, options)                      => {
                 data.write_chr('F');
                 elem.serialize(data);
@@ -51,6 +59,22 @@ impl PaintEdit {
                     option.serialize(data);
                 }
             }
+
+            PlaceImage(elem, image_id, position, size, opacity) => {
+                data.write_chr('I');
+                elem.serialize(data);
+
+                // The image is stored in the same way as for an image element
+                ImageElement::new(*elem, *image_id, *position, *size, *opacity).serialize(data);
+            }
+
+            CreateText(elem, text, style, position)         => {
+                data.write_chr('T');
+                elem.serialize(data);
+
+                // The text is stored in the same way as for a text element
+                TextElement::new(*elem, Arc::new(text.clone()), Arc::new(style.clone()), *position).serialize(data);
+            }
         }
     }
 
@@ -98,6 +122,13 @@ impl PaintEdit {
                     })
             }
 
+            'B' => {
+                let elem_id     = ElementId::deserialize(data)?;
+                let brush       = BrushElement::deserialize(elem_id, data)?;
+
+                Some(PaintEdit::CreateBrushStroke(elem_id, brush.points()))
+            }
+
             'F' => {
                 let elem_id     = ElementId::deserialize(data)?;
                 let position    = (data.next_f64() as f32, data.next_f64() as f32);
@@ -114,6 +145,20 @@ impl PaintEdit {
                 Some(PaintEdit::Fill(elem_id, point, options))
             }
 
+            'I' => {
+                let elem_id     = ElementId::deserialize(data)?;
+                let image       = ImageElement::deserialize(elem_id, data)?;
+
+                Some(PaintEdit::PlaceImage(elem_id, image.image_id(), image.position(), image.size(), image.opacity()))
+            }
+
+            'T' => {
+                let elem_id     = ElementId::deserialize(data)?;
+                let text        = TextElement::deserialize(elem_id, data)?;
+
+                Some(PaintEdit::CreateText(elem_id, (*text.text()).clone(), (*text.style()).clone(), text.position()))
+            }
+
             _   => None
         }
     }
@@ -147,6 +192,18 @@ mod test {
         assert!(PaintEdit::deserialize(&mut encoded.chars()) == Some(PaintEdit::BrushStroke(ElementId::Assigned(42), Arc::new(vec![RawPoint::from((1.0, 2.0)), RawPoint::from((2.0, 3.0)), RawPoint::from((4.0, 5.0))]))));
     }
 
+    #[test]
+    fn create_brush_stroke() {
+        let points      = Arc::new(vec![
+            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0, opacity: 1.0, color_mix: 0.0 },
+            BrushPoint { position: (8.0, 9.0), cp1: (10.0, 11.0), cp2: (12.0, 13.0), width: 14.0, opacity: 1.0, color_mix: 0.0 }
+        ]);
+        let mut encoded = String::new();
+        PaintEdit::CreateBrushStroke(ElementId::Assigned(42), points.clone()).serialize(&mut encoded);
+
+        assert!(PaintEdit::deserialize(&mut encoded.chars()) == Some(PaintEdit::CreateBrushStroke(ElementId::Assigned(42), points)));
+    }
+
     #[test]
     fn fill() {
         let mut encoded = String::new();
@@ -154,4 +211,20 @@ mod test {
 
         assert!(PaintEdit::deserialize(&mut encoded.chars()) == Some(        PaintEdit::Fill(ElementId::Assigned(42), RawPoint::from((1.0, 2.0)), vec![FillOption::Algorithm(FillAlgorithm::Concave), FillOption::Position(FillPosition::Behind)])));
     }
+
+    #[test]
+    fn place_image() {
+        let mut encoded = String::new();
+        PaintEdit::PlaceImage(ElementId::Assigned(42), ElementId::Assigned(40), (1.0, 2.0), (30.0, 40.0), 0.5).serialize(&mut encoded);
+
+        assert!(PaintEdit::deserialize(&mut encoded.chars()) == Some(PaintEdit::PlaceImage(ElementId::Assigned(42), ElementId::Assigned(40), (1.0, 2.0), (30.0, 40.0), 0.5)));
+    }
+
+    #[test]
+    fn create_text() {
+        let mut encoded = String::new();
+        PaintEdit::CreateText(ElementId::Assigned(42), "Title".to_string(), TextStyle::default(), (1.0, 2.0)).serialize(&mut encoded);
+
+        assert!(PaintEdit::deserialize(&mut encoded.chars()) == Some(PaintEdit::CreateText(ElementId::Assigned(42), "Title".to_string(), TextStyle::default(), (1.0, 2.0))));
+    }
 }
@@ -0,0 +1,48 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl PathOperation {
+    ///
+    /// Generates a serialized version of this path operation on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::PathOperation::*;
+
+        match self {
+            Union       => data.write_chr('U'),
+            Subtract    => data.write_chr('S'),
+            Intersect   => data.write_chr('I'),
+            Exclude     => data.write_chr('X')
+        }
+    }
+
+    ///
+    /// Deserializes a path operation from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<PathOperation> {
+        match data.next_chr() {
+            'U'     => Some(PathOperation::Union),
+            'S'     => Some(PathOperation::Subtract),
+            'I'     => Some(PathOperation::Intersect),
+            'X'     => Some(PathOperation::Exclude),
+
+            _       => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_operations() {
+        for operation in vec![PathOperation::Union, PathOperation::Subtract, PathOperation::Intersect, PathOperation::Exclude] {
+            let mut encoded = String::new();
+            operation.serialize(&mut encoded);
+
+            assert!(PathOperation::deserialize(&mut encoded.chars()) == Some(operation));
+        }
+    }
+}
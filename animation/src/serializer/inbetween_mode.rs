@@ -0,0 +1,59 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl InbetweenMode {
+    ///
+    /// Generates a serialized version of this in-betweening mode on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::InbetweenMode::*;
+
+        match self {
+            Off                 => { data.write_chr('-'); }
+            MatchByOrder        => { data.write_chr('O'); }
+            MatchByProximity    => { data.write_chr('P'); }
+        }
+    }
+
+    ///
+    /// Deserializes an in-betweening mode from a source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<InbetweenMode> {
+        match data.next_chr() {
+            '-' => Some(InbetweenMode::Off),
+            'O' => Some(InbetweenMode::MatchByOrder),
+            'P' => Some(InbetweenMode::MatchByProximity),
+            _   => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn off() {
+        let mut encoded = String::new();
+        InbetweenMode::Off.serialize(&mut encoded);
+
+        assert!(InbetweenMode::deserialize(&mut encoded.chars()) == Some(InbetweenMode::Off));
+    }
+
+    #[test]
+    fn match_by_order() {
+        let mut encoded = String::new();
+        InbetweenMode::MatchByOrder.serialize(&mut encoded);
+
+        assert!(InbetweenMode::deserialize(&mut encoded.chars()) == Some(InbetweenMode::MatchByOrder));
+    }
+
+    #[test]
+    fn match_by_proximity() {
+        let mut encoded = String::new();
+        InbetweenMode::MatchByProximity.serialize(&mut encoded);
+
+        assert!(InbetweenMode::deserialize(&mut encoded.chars()) == Some(InbetweenMode::MatchByProximity));
+    }
+}
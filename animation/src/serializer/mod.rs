@@ -13,27 +13,45 @@ mod target;
 
 mod edit;
 mod color;
+mod blend_mode;
 mod vector;
 mod time_path;
 mod cache_type;
 mod element_id;
 mod fill_option;
+mod inbetween_mode;
 mod drawing_style;
 mod path_component;
+mod path_node_type;
+mod audio_clip;
+mod annotation;
+mod reference_image;
+mod text_style;
+mod storage_command;
 mod brush_definition;
 mod brush_properties;
+mod brush_fill;
 
 pub use self::source::*;
 pub use self::target::*;
 
 pub use self::edit::*;
 pub use self::color::*;
+pub use self::blend_mode::*;
 pub use self::vector::*;
 pub use self::time_path::*;
 pub use self::cache_type::*;
 pub use self::element_id::*;
 pub use self::fill_option::*;
+pub use self::inbetween_mode::*;
 pub use self::drawing_style::*;
 pub use self::path_component::*;
+pub use self::path_node_type::*;
+pub use self::audio_clip::*;
+pub use self::annotation::*;
+pub use self::reference_image::*;
+pub use self::text_style::*;
+pub use self::storage_command::*;
 pub use self::brush_definition::*;
 pub use self::brush_properties::*;
+pub use self::brush_fill::*;
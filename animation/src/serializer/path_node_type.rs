@@ -0,0 +1,49 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl PathNodeType {
+    ///
+    /// Generates a serialized version of this node type on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::PathNodeType::*;
+
+        match self {
+            Smooth  => { data.write_chr('S'); }
+            Corner  => { data.write_chr('C'); }
+        }
+    }
+
+    ///
+    /// Deserializes a node type from a source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<PathNodeType> {
+        match data.next_chr() {
+            'S' => Some(PathNodeType::Smooth),
+            'C' => Some(PathNodeType::Corner),
+            _   => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smooth() {
+        let mut encoded = String::new();
+        PathNodeType::Smooth.serialize(&mut encoded);
+
+        assert!(PathNodeType::deserialize(&mut encoded.chars()) == Some(PathNodeType::Smooth));
+    }
+
+    #[test]
+    fn corner() {
+        let mut encoded = String::new();
+        PathNodeType::Corner.serialize(&mut encoded);
+
+        assert!(PathNodeType::deserialize(&mut encoded.chars()) == Some(PathNodeType::Corner));
+    }
+}
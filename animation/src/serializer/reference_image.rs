@@ -0,0 +1,48 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl ReferenceImage {
+    ///
+    /// Generates a serialized version of this image on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // v0
+        data.write_small_u64(0);
+
+        // Images are stored as the PNG data they were read from
+        let png_data = self.png_data();
+        data.write_usize(png_data.len());
+        data.write_bytes(&png_data);
+    }
+
+    ///
+    /// Deserializes an image from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<ReferenceImage> {
+        match data.next_small_u64() {
+            0 => {
+                let num_bytes   = data.next_usize();
+                let png_data    = data.next_bytes(num_bytes);
+
+                ReferenceImage::from_png(&png_data).ok()
+            }
+
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reference_image() {
+        let image       = ReferenceImage::from_rgba(2, 2, &[255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 128]).unwrap();
+        let mut encoded = String::new();
+        image.serialize(&mut encoded);
+
+        assert!(ReferenceImage::deserialize(&mut encoded.chars()) == Some(image));
+    }
+}
@@ -10,7 +10,7 @@ use std::convert::{TryInto};
 ///
 /// Decodes a character to a 6-bit value (ie, from ENCODING_CHAR_SET)
 ///
-fn decode_chr(c: char) -> u8 {
+pub (super) fn decode_chr(c: char) -> u8 {
     if c >= 'A' && c <= 'Z' {
         ((c as u8) - ('A' as u8)) as u8
     } else if c >= 'a' && c <= 'z' {
@@ -26,6 +26,34 @@ fn decode_chr(c: char) -> u8 {
     }
 }
 
+///
+/// Decodes the rest of a usize from a data source, given the first character of its encoding
+///
+pub (super) fn decode_usize<Src: AnimationDataSource>(first_chr: char, data: &mut Src) -> usize {
+    let mut result  = 0usize;
+    let mut shift   = 0;
+    let mut chr     = first_chr;
+
+    loop {
+        // Decode the next character
+        let byte    = decode_chr(chr);
+
+        // Lower 5 bits contain the value
+        let lower   = byte & 0x1f;
+        result      |= (lower as usize) << shift;
+        shift       += 5;
+
+        // If the upper bit is set there are more bytes
+        if (byte & 0x20) == 0 {
+            break;
+        }
+
+        chr = data.next_chr();
+    }
+
+    result
+}
+
 ///
 /// Reader implementation that can read bytes from an animation data source
 ///
@@ -98,25 +126,8 @@ pub trait AnimationDataSource : Sized {
     /// Reads a usize from this source
     ///
     fn next_usize(&mut self) -> usize {
-        let mut result  = 0usize;
-        let mut shift   = 0;
-
-        loop {
-            // Decode the next character
-            let byte    = decode_chr(self.next_chr());
-
-            // Lower 5 bits contain the value
-            let lower   = byte & 0x1f;
-            result      |= (lower as usize) << shift;
-            shift       += 5;
-
-            // If the upper bit is set there are more bytes
-            if (byte & 0x20) == 0 {
-                break;
-            }
-        }
-
-        result
+        let first_chr = self.next_chr();
+        decode_usize(first_chr, self)
     }
 
     ///
@@ -0,0 +1,251 @@
+use super::source::*;
+use super::target::*;
+use super::super::storage::*;
+
+impl UndoStack {
+    ///
+    /// Generates a serialized version of this undo stack identifier on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        match self {
+            UndoStack::Undo => data.write_chr('U'),
+            UndoStack::Redo => data.write_chr('R')
+        }
+    }
+
+    ///
+    /// Deserializes an undo stack identifier from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<UndoStack> {
+        match data.next_chr() {
+            'U' => Some(UndoStack::Undo),
+            'R' => Some(UndoStack::Redo),
+            _   => None
+        }
+    }
+}
+
+impl StorageCommand {
+    ///
+    /// Generates a serialized version of this storage command on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::StorageCommand::*;
+
+        match self {
+            WriteAnimationProperties(props)                     => { data.write_chr('P'); data.write_str(props); }
+            ReadAnimationProperties                             => { data.write_chr('p'); }
+            WriteEdit(edit)                                     => { data.write_chr('E'); data.write_str(edit); }
+            ReadHighestUnusedElementId                          => { data.write_chr('h'); }
+            ReadHighestUnusedElementIdInRange(range)            => { data.write_chr('H'); data.write_i64(range.start); data.write_i64(range.end); }
+            ReadEditLogLength                                   => { data.write_chr('n'); }
+            ReadEdits(range)                                    => { data.write_chr('e'); data.write_usize(range.start); data.write_usize(range.end); }
+            WriteElement(element_id, element)                   => { data.write_chr('X'); data.write_i64(*element_id); data.write_str(element); }
+            ReadElement(element_id)                             => { data.write_chr('x'); data.write_i64(*element_id); }
+            DeleteElement(element_id)                           => { data.write_chr('D'); data.write_i64(*element_id); }
+            AddLayer(layer_id, props)                           => { data.write_chr('L'); data.write_small_u64(*layer_id); data.write_str(props); }
+            DeleteLayer(layer_id)                               => { data.write_chr('d'); data.write_small_u64(*layer_id); }
+            ReadLayers                                          => { data.write_chr('l'); }
+            WriteLayerProperties(layer_id, props)               => { data.write_chr('Y'); data.write_small_u64(*layer_id); data.write_str(props); }
+            ReadLayerProperties(layer_id)                       => { data.write_chr('y'); data.write_small_u64(*layer_id); }
+            AddKeyFrame(layer_id, when)                         => { data.write_chr('K'); data.write_small_u64(*layer_id); data.write_duration(*when); }
+            DeleteKeyFrame(layer_id, when)                      => { data.write_chr('k'); data.write_small_u64(*layer_id); data.write_duration(*when); }
+            MoveKeyFrame(layer_id, from, to)                    => { data.write_chr('M'); data.write_small_u64(*layer_id); data.write_duration(*from); data.write_duration(*to); }
+            ReadKeyFrames(layer_id, when)                       => { data.write_chr('F'); data.write_small_u64(*layer_id); data.write_duration(when.start); data.write_duration(when.end); }
+            AttachElementToLayer(layer_id, element_id, when)    => { data.write_chr('A'); data.write_small_u64(*layer_id); data.write_i64(*element_id); data.write_duration(*when); }
+            DetachElementFromLayer(element_id)                  => { data.write_chr('a'); data.write_i64(*element_id); }
+            ReadElementAttachments(element_id)                  => { data.write_chr('r'); data.write_i64(*element_id); }
+            ReadElementsForKeyFrame(layer_id, when)             => { data.write_chr('f'); data.write_small_u64(*layer_id); data.write_duration(*when); }
+            WriteLayerCache(layer_id, when, key, value)         => { data.write_chr('C'); data.write_small_u64(*layer_id); data.write_duration(*when); data.write_str(key); data.write_str(value); }
+            DeleteLayerCache(layer_id, when, key)               => { data.write_chr('c'); data.write_small_u64(*layer_id); data.write_duration(*when); data.write_str(key); }
+            ReadLayerCache(layer_id, when, key)                 => { data.write_chr('q'); data.write_small_u64(*layer_id); data.write_duration(*when); data.write_str(key); }
+            WriteImage(image_id, image)                         => { data.write_chr('G'); data.write_i64(*image_id); data.write_str(image); }
+            ReadImage(image_id)                                 => { data.write_chr('g'); data.write_i64(*image_id); }
+            WriteAudioClip(clip_id, audio)                      => { data.write_chr('I'); data.write_i64(*clip_id); data.write_str(audio); }
+            ReadAudioClip(clip_id)                              => { data.write_chr('i'); data.write_i64(*clip_id); }
+            WriteLayerAudio(layer_id, clip_id)                  => { data.write_chr('W'); data.write_small_u64(*layer_id); data.write_i64(*clip_id); }
+            ReadLayerAudio(layer_id)                            => { data.write_chr('w'); data.write_small_u64(*layer_id); }
+            DeleteLayerAudio(layer_id)                          => { data.write_chr('b'); data.write_small_u64(*layer_id); }
+            WriteAnnotation(annotation_id, layer_id, text)      => { data.write_chr('N'); data.write_i64(*annotation_id); data.write_small_u64(*layer_id); data.write_str(text); }
+            ReadAnnotation(annotation_id)                       => { data.write_chr('o'); data.write_i64(*annotation_id); }
+            ReadAnnotations                                     => { data.write_chr('O'); }
+            DeleteAnnotation(annotation_id)                     => { data.write_chr('m'); data.write_i64(*annotation_id); }
+            PushUndoStep(history, stack, step)                  => { data.write_chr('U'); data.write_small_u64(*history); stack.serialize(data); data.write_str(step); }
+            PopUndoStep(history, stack)                         => { data.write_chr('u'); data.write_small_u64(*history); stack.serialize(data); }
+            ClearUndoStack(history, stack)                      => { data.write_chr('Z'); data.write_small_u64(*history); stack.serialize(data); }
+            ReadUndoStackDepth(history)                         => { data.write_chr('z'); data.write_small_u64(*history); }
+        }
+    }
+
+    ///
+    /// Deserializes a storage command from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<StorageCommand> {
+        use self::StorageCommand::*;
+
+        match data.next_chr() {
+            'P' => Some(WriteAnimationProperties(data.next_string())),
+            'p' => Some(ReadAnimationProperties),
+            'E' => Some(WriteEdit(data.next_string())),
+            'h' => Some(ReadHighestUnusedElementId),
+            'H' => { let start = data.next_i64(); let end = data.next_i64(); Some(ReadHighestUnusedElementIdInRange(start..end)) }
+            'n' => Some(ReadEditLogLength),
+            'e' => { let start = data.next_usize(); let end = data.next_usize(); Some(ReadEdits(start..end)) }
+            'X' => { let element_id = data.next_i64(); Some(WriteElement(element_id, data.next_string())) }
+            'x' => Some(ReadElement(data.next_i64())),
+            'D' => Some(DeleteElement(data.next_i64())),
+            'L' => { let layer_id = data.next_small_u64(); Some(AddLayer(layer_id, data.next_string())) }
+            'd' => Some(DeleteLayer(data.next_small_u64())),
+            'l' => Some(ReadLayers),
+            'Y' => { let layer_id = data.next_small_u64(); Some(WriteLayerProperties(layer_id, data.next_string())) }
+            'y' => Some(ReadLayerProperties(data.next_small_u64())),
+            'K' => { let layer_id = data.next_small_u64(); Some(AddKeyFrame(layer_id, data.next_duration())) }
+            'k' => { let layer_id = data.next_small_u64(); Some(DeleteKeyFrame(layer_id, data.next_duration())) }
+            'M' => { let layer_id = data.next_small_u64(); let from = data.next_duration(); Some(MoveKeyFrame(layer_id, from, data.next_duration())) }
+            'F' => { let layer_id = data.next_small_u64(); let start = data.next_duration(); let end = data.next_duration(); Some(ReadKeyFrames(layer_id, start..end)) }
+            'A' => { let layer_id = data.next_small_u64(); let element_id = data.next_i64(); Some(AttachElementToLayer(layer_id, element_id, data.next_duration())) }
+            'a' => Some(DetachElementFromLayer(data.next_i64())),
+            'r' => Some(ReadElementAttachments(data.next_i64())),
+            'f' => { let layer_id = data.next_small_u64(); Some(ReadElementsForKeyFrame(layer_id, data.next_duration())) }
+            'C' => { let layer_id = data.next_small_u64(); let when = data.next_duration(); let key = data.next_string(); Some(WriteLayerCache(layer_id, when, key, data.next_string())) }
+            'c' => { let layer_id = data.next_small_u64(); let when = data.next_duration(); Some(DeleteLayerCache(layer_id, when, data.next_string())) }
+            'q' => { let layer_id = data.next_small_u64(); let when = data.next_duration(); Some(ReadLayerCache(layer_id, when, data.next_string())) }
+            'G' => { let image_id = data.next_i64(); Some(WriteImage(image_id, data.next_string())) }
+            'g' => Some(ReadImage(data.next_i64())),
+            'I' => { let clip_id = data.next_i64(); Some(WriteAudioClip(clip_id, data.next_string())) }
+            'i' => Some(ReadAudioClip(data.next_i64())),
+            'W' => { let layer_id = data.next_small_u64(); Some(WriteLayerAudio(layer_id, data.next_i64())) }
+            'w' => Some(ReadLayerAudio(data.next_small_u64())),
+            'b' => Some(DeleteLayerAudio(data.next_small_u64())),
+            'N' => { let annotation_id = data.next_i64(); let layer_id = data.next_small_u64(); Some(WriteAnnotation(annotation_id, layer_id, data.next_string())) }
+            'o' => Some(ReadAnnotation(data.next_i64())),
+            'O' => Some(ReadAnnotations),
+            'm' => Some(DeleteAnnotation(data.next_i64())),
+            'U' => { let history = data.next_small_u64(); UndoStack::deserialize(data).map(|stack| PushUndoStep(history, stack, data.next_string())) }
+            'u' => { let history = data.next_small_u64(); UndoStack::deserialize(data).map(|stack| PopUndoStep(history, stack)) }
+            'Z' => { let history = data.next_small_u64(); UndoStack::deserialize(data).map(|stack| ClearUndoStack(history, stack)) }
+            'z' => Some(ReadUndoStackDepth(data.next_small_u64())),
+
+            _   => None
+        }
+    }
+}
+
+///
+/// Serializes a list of storage commands (such as a step in the undo history)
+///
+pub fn serialize_storage_commands<'a, Tgt: AnimationDataTarget, Commands: IntoIterator<Item=&'a StorageCommand>>(data: &mut Tgt, commands: Commands) {
+    let commands = commands.into_iter().collect::<Vec<_>>();
+
+    data.write_usize(commands.len());
+    commands.into_iter().for_each(|command| command.serialize(data));
+}
+
+///
+/// Deserializes a list of storage commands written by `serialize_storage_commands`
+///
+pub fn deserialize_storage_commands<Src: AnimationDataSource>(data: &mut Src) -> Option<Vec<StorageCommand>> {
+    let num_commands = data.next_usize();
+
+    (0..num_commands).into_iter()
+        .map(|_| StorageCommand::deserialize(data))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::{Duration};
+
+    fn round_trip(command: StorageCommand) {
+        let mut encoded = String::new();
+        command.serialize(&mut encoded);
+
+        assert!(StorageCommand::deserialize(&mut encoded.chars()) == Some(command));
+    }
+
+    #[test]
+    fn write_element() {
+        round_trip(StorageCommand::WriteElement(42, "Test element".to_string()));
+    }
+
+    #[test]
+    fn add_layer() {
+        round_trip(StorageCommand::AddLayer(3, "Layer properties".to_string()));
+    }
+
+    #[test]
+    fn attach_element_to_layer() {
+        round_trip(StorageCommand::AttachElementToLayer(3, 42, Duration::from_millis(1234)));
+    }
+
+    #[test]
+    fn move_key_frame() {
+        round_trip(StorageCommand::MoveKeyFrame(3, Duration::from_millis(1234), Duration::from_millis(5678)));
+    }
+
+    #[test]
+    fn write_layer_cache() {
+        round_trip(StorageCommand::WriteLayerCache(3, Duration::from_millis(1234), "O".to_string(), "Cached value".to_string()));
+    }
+
+    #[test]
+    fn write_image() {
+        round_trip(StorageCommand::WriteImage(42, "Image".to_string()));
+    }
+
+    #[test]
+    fn read_image() {
+        round_trip(StorageCommand::ReadImage(42));
+    }
+
+    #[test]
+    fn write_audio_clip() {
+        round_trip(StorageCommand::WriteAudioClip(42, "Audio clip".to_string()));
+    }
+
+    #[test]
+    fn write_layer_audio() {
+        round_trip(StorageCommand::WriteLayerAudio(3, 42));
+    }
+
+    #[test]
+    fn write_annotation() {
+        round_trip(StorageCommand::WriteAnnotation(42, 3, "Annotation".to_string()));
+    }
+
+    #[test]
+    fn delete_annotation() {
+        round_trip(StorageCommand::DeleteAnnotation(42));
+    }
+
+    #[test]
+    fn read_highest_unused_element_id_in_range() {
+        round_trip(StorageCommand::ReadHighestUnusedElementIdInRange(1<<32..2<<32));
+    }
+
+    #[test]
+    fn push_undo_step() {
+        round_trip(StorageCommand::PushUndoStep(3<<32 | 2, UndoStack::Redo, "Step".to_string()));
+    }
+
+    #[test]
+    fn read_undo_stack_depth() {
+        round_trip(StorageCommand::ReadUndoStackDepth(42));
+    }
+
+    #[test]
+    fn command_list() {
+        let commands    = vec![
+            StorageCommand::DeleteElement(42),
+            StorageCommand::AddKeyFrame(1, Duration::from_millis(0)),
+            StorageCommand::DeleteLayer(2)
+        ];
+
+        let mut encoded = String::new();
+        serialize_storage_commands(&mut encoded, commands.iter());
+
+        assert!(deserialize_storage_commands(&mut encoded.chars()) == Some(commands));
+    }
+}
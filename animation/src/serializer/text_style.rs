@@ -0,0 +1,89 @@
+use super::color::*;
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl TextAlignment {
+    ///
+    /// Generates a serialized version of this alignment on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::TextAlignment::*;
+
+        match self {
+            Left    => data.write_chr('L'),
+            Center  => data.write_chr('C'),
+            Right   => data.write_chr('R')
+        }
+    }
+
+    ///
+    /// Deserializes a text alignment from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<TextAlignment> {
+        match data.next_chr() {
+            'L' => Some(TextAlignment::Left),
+            'C' => Some(TextAlignment::Center),
+            'R' => Some(TextAlignment::Right),
+            _   => None
+        }
+    }
+}
+
+impl TextStyle {
+    ///
+    /// Generates a serialized version of this text style on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // v0
+        data.write_small_u64(0);
+
+        data.write_str(&self.font);
+        data.write_f32(self.size);
+        serialize_color(&self.color, data);
+        self.alignment.serialize(data);
+    }
+
+    ///
+    /// Deserializes a text style from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<TextStyle> {
+        match data.next_small_u64() {
+            0 => {
+                let font        = data.next_string();
+                let size        = data.next_f32();
+                let color       = deserialize_color(data)?;
+                let alignment   = TextAlignment::deserialize(data)?;
+
+                Some(TextStyle { font, size, color, alignment })
+            }
+
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flo_canvas::*;
+
+    #[test]
+    fn text_alignment() {
+        for alignment in vec![TextAlignment::Left, TextAlignment::Center, TextAlignment::Right] {
+            let mut encoded = String::new();
+            alignment.serialize(&mut encoded);
+
+            assert!(TextAlignment::deserialize(&mut encoded.chars()) == Some(alignment));
+        }
+    }
+
+    #[test]
+    fn text_style() {
+        let style       = TextStyle { font: "Lato Bold".to_string(), size: 48.0, color: Color::Rgba(0.25, 0.5, 0.75, 1.0), alignment: TextAlignment::Center };
+        let mut encoded = String::new();
+        style.serialize(&mut encoded);
+
+        assert!(TextStyle::deserialize(&mut encoded.chars()) == Some(style));
+    }
+}
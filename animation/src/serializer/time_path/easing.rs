@@ -0,0 +1,52 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl Easing {
+    ///
+    /// Generates a serialized version of this easing on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::Easing::*;
+
+        match self {
+            Linear                      => { data.write_chr('L'); }
+            EaseIn                      => { data.write_chr('I'); }
+            EaseOut                     => { data.write_chr('O'); }
+            EaseInOut                   => { data.write_chr('B'); }
+            CubicBezier(x1, y1, x2, y2) => { data.write_chr('C'); data.write_f32(*x1); data.write_f32(*y1); data.write_f32(*x2); data.write_f32(*y2); }
+            Step                        => { data.write_chr('S'); }
+        }
+    }
+
+    ///
+    /// Deserializes an easing from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<Easing> {
+        match data.next_chr() {
+            'L' => Some(Easing::Linear),
+            'I' => Some(Easing::EaseIn),
+            'O' => Some(Easing::EaseOut),
+            'B' => Some(Easing::EaseInOut),
+            'C' => Some(Easing::CubicBezier(data.next_f32(), data.next_f32(), data.next_f32(), data.next_f32())),
+            'S' => Some(Easing::Step),
+
+            _   => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_easings() {
+        for easing in vec![Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::CubicBezier(0.1, 0.2, 0.3, 0.4), Easing::Step] {
+            let mut encoded = String::new();
+            easing.serialize(&mut encoded);
+
+            assert!(Easing::deserialize(&mut encoded.chars()) == Some(easing));
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod time_control_point;
 mod time_curve;
 mod time_point;
+mod easing;
 
 pub use self::time_point::*;
 pub use self::time_curve::*;
 pub use self::time_control_point::*;
+pub use self::easing::*;
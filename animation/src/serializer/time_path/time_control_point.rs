@@ -31,7 +31,7 @@ impl TimeControlPoint {
         let past    = TimePoint::deserialize(data);
         let future  = TimePoint::deserialize(data);
 
-        TimeControlPoint { point, past, future }
+        TimeControlPoint::new(past, point, future)
     }
 
     ///
@@ -42,6 +42,6 @@ impl TimeControlPoint {
         let point   = TimePoint::deserialize_next(&past, data);
         let future  = TimePoint::deserialize_next(&point, data);
 
-        (TimeControlPoint { point, past, future }, future)
+        (TimeControlPoint::new(past, point, future), future)
     }
 }
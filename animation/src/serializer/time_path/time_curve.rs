@@ -9,19 +9,35 @@ impl TimeCurve {
     /// Generates a serialized version of this time curve on the specified data target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // Curves with easing are marked with a '~' (which can never be the first character of the point count), so curves without easing are written in the original format
+        let has_easing = self.points.iter().any(|point| point.easing != Easing::Linear);
+
+        if has_easing {
+            data.write_chr('~');
+        }
+
         data.write_usize(self.points.len());
 
         let mut last_point = TimePoint::new(0.0, 0.0, Duration::from_millis(0));
         for point in self.points.iter() {
             last_point = point.serialize_next(&last_point, data);
         }
+
+        if has_easing {
+            for point in self.points.iter() {
+                point.easing.serialize(data);
+            }
+        }
     }
     
     ///
     /// Deserializes a time curve from the specified data source
     ///
-    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> TimeCurve {
-        let num_points      = data.next_usize();
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<TimeCurve> {
+        // Read the point count, which is preceded by a '~' if the curve has easing
+        let first_chr       = data.next_chr();
+        let has_easing      = first_chr == '~';
+        let num_points      = if has_easing { data.next_usize() } else { decode_usize(first_chr, data) };
 
         let mut last_point  = TimePoint::new(0.0, 0.0, Duration::from_millis(0));
         let mut points      = vec![];
@@ -31,6 +47,77 @@ impl TimeCurve {
             last_point = next_point;
         }
 
-        TimeCurve { points }
+        if has_easing {
+            for point in points.iter_mut() {
+                point.easing = Easing::deserialize(data)?;
+            }
+        }
+
+        Some(TimeCurve { points })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn curve_without_easing() {
+        let curve       = TimeCurve::new(TimePoint::new(1.0, 2.0, Duration::from_millis(100)), TimePoint::new(3.0, 4.0, Duration::from_millis(200)));
+
+        let mut encoded = String::new();
+        curve.serialize(&mut encoded);
+
+        assert!(!encoded.starts_with('~'));
+        assert!(TimeCurve::deserialize(&mut encoded.chars()).unwrap().is_close_to(&curve));
+    }
+
+    #[test]
+    fn curve_with_easing() {
+        let mut curve   = TimeCurve::new(TimePoint::new(1.0, 2.0, Duration::from_millis(100)), TimePoint::new(3.0, 4.0, Duration::from_millis(200)));
+        curve           = curve.set_point_at_time(Duration::from_millis(300), (5.0, 6.0));
+        curve.set_easing(0, Easing::EaseIn);
+        curve.set_easing(1, Easing::CubicBezier(0.1, 0.2, 0.3, 0.4));
+
+        let mut encoded = String::new();
+        curve.serialize(&mut encoded);
+
+        let decoded     = TimeCurve::deserialize(&mut encoded.chars()).unwrap();
+
+        assert!(decoded.is_close_to(&curve));
+        assert!(decoded.points[0].easing == Easing::EaseIn);
+        assert!(decoded.points[1].easing == Easing::CubicBezier(0.1, 0.2, 0.3, 0.4));
+        assert!(decoded.points[2].easing == Easing::Linear);
+    }
+
+    #[test]
+    fn curve_with_unknown_easing() {
+        let mut curve   = TimeCurve::new(TimePoint::new(1.0, 2.0, Duration::from_millis(100)), TimePoint::new(3.0, 4.0, Duration::from_millis(200)));
+        curve.set_easing(0, Easing::EaseOut);
+
+        let mut encoded = String::new();
+        curve.serialize(&mut encoded);
+
+        // The easings are written at the end of the curve: replace the 'ease out' with an unknown easing code
+        assert!(encoded.ends_with("OL"));
+        encoded.truncate(encoded.len()-2);
+        encoded.push_str("?L");
+
+        assert!(TimeCurve::deserialize(&mut encoded.chars()).is_none());
+    }
+
+    #[test]
+    fn long_curve_without_easing() {
+        // More than 32 points needs more than one character to encode the length
+        let mut curve   = TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(0.0, 0.0, Duration::from_millis(100)));
+        for point in 2..40 {
+            curve = curve.set_point_at_time(Duration::from_millis(point * 100), (point as f32, 0.0));
+        }
+        assert!(curve.points.len() == 40);
+
+        let mut encoded = String::new();
+        curve.serialize(&mut encoded);
+
+        assert!(TimeCurve::deserialize(&mut encoded.chars()).unwrap().is_close_to(&curve));
     }
 }
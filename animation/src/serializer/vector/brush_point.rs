@@ -11,6 +11,7 @@ impl BrushPoint {
         data.write_f32(self.cp1.0);      data.write_f32(self.cp1.1);
         data.write_f32(self.cp2.0);      data.write_f32(self.cp2.1);
         data.write_f32(self.width);
+        data.write_f32(self.opacity);    data.write_f32(self.color_mix);
     }
 
     ///
@@ -21,6 +22,7 @@ impl BrushPoint {
         data.write_next_f64(last.cp1.0 as f64, self.cp1.0 as f64);           data.write_next_f64(last.cp1.1 as f64, self.cp1.1 as f64);
         data.write_next_f64(last.cp2.0 as f64, self.cp2.0 as f64);           data.write_next_f64(last.cp2.1 as f64, self.cp2.1 as f64);
         data.write_next_f64(last.width as f64, self.width as f64);
+        data.write_next_f64(last.opacity as f64, self.opacity as f64);       data.write_next_f64(last.color_mix as f64, self.color_mix as f64);
 
         self.clone()
     }
@@ -33,9 +35,11 @@ impl BrushPoint {
         let cp1         = (data.next_f32(), data.next_f32());
         let cp2         = (data.next_f32(), data.next_f32());
         let width       = data.next_f32();
+        let opacity     = data.next_f32();
+        let color_mix   = data.next_f32();
 
         BrushPoint { 
-            position, cp1, cp2, width
+            position, cp1, cp2, width, opacity, color_mix
         }
     }
 
@@ -43,6 +47,19 @@ impl BrushPoint {
     /// Deserializes a brush point from a data source
     ///
     pub fn deserialize_next<Src: AnimationDataSource>(last: &BrushPoint, data: &mut Src) -> BrushPoint {
+        let point       = Self::deserialize_next_without_dynamics(last, data);
+        let opacity     = data.next_f64_offset(last.opacity as f64) as f32;
+        let color_mix   = data.next_f64_offset(last.color_mix as f64) as f32;
+
+        BrushPoint {
+            opacity, color_mix, ..point
+        }
+    }
+
+    ///
+    /// Deserializes a brush point that was serialized before brush points had an opacity or a colour mix
+    ///
+    pub fn deserialize_next_without_dynamics<Src: AnimationDataSource>(last: &BrushPoint, data: &mut Src) -> BrushPoint {
         let position    = (data.next_f64_offset(last.position.0 as f64), data.next_f64_offset(last.position.1 as f64));
         let cp1         = (data.next_f64_offset(last.cp1.0 as f64), data.next_f64_offset(last.cp1.1 as f64));
         let cp2         = (data.next_f64_offset(last.cp2.0 as f64), data.next_f64_offset(last.cp2.1 as f64));
@@ -54,7 +71,7 @@ impl BrushPoint {
         let width       = width as f32;
 
         BrushPoint { 
-            position, cp1, cp2, width
+            position, cp1, cp2, width, opacity: 1.0, color_mix: 0.0
         }
     }
 }
@@ -70,7 +87,9 @@ mod test {
             position:   (1.0, 2.0),
             cp1:        (3.0, 4.0),
             cp2:        (5.0, 6.0),
-            width:      7.0
+            width:      7.0,
+            opacity:    0.5,
+            color_mix:  0.25
         };
         element.serialize(&mut encoded);
 
@@ -86,13 +105,17 @@ mod test {
             position:   (1.0, 2.0),
             cp1:        (3.0, 4.0),
             cp2:        (5.0, 6.0),
-            width:      7.0
+            width:      7.0,
+            opacity:    0.5,
+            color_mix:  0.25
         };
         let element     = BrushPoint {
             position:   (8.0, 9.0),
             cp1:        (10.0, 11.0),
             cp2:        (12.0, 13.0),
-            width:      14.0
+            width:      14.0,
+            opacity:    0.75,
+            color_mix:  1.0
         };
         element.serialize_next(&last, &mut encoded);
 
@@ -9,15 +9,17 @@ impl BrushElement {
     /// Generates a serialized version of this brush stroke element on the specified data target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        // Version 0
-        data.write_small_u64(0);
+        // Version 1
+        data.write_small_u64(1);
 
         // Write the points
         let mut last_point = BrushPoint { 
             position:   (0.0, 0.0), 
             cp1:        (0.0, 0.0), 
             cp2:        (0.0, 0.0),
-            width:      0.0
+            width:      0.0,
+            opacity:    0.0,
+            color_mix:  0.0
         };
 
         data.write_usize(self.points().len());
@@ -31,7 +33,7 @@ impl BrushElement {
     ///
     pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<BrushElement> {
         match data.next_small_u64() {
-            0 => {
+            version @ 0..=1 => {
                 let num_points      = data.next_usize();
                 let mut points      = vec![];
 
@@ -39,11 +41,19 @@ impl BrushElement {
                     position:   (0.0, 0.0), 
                     cp1:        (0.0, 0.0), 
                     cp2:        (0.0, 0.0),
-                    width:      0.0
+                    width:      0.0,
+                    opacity:    0.0,
+                    color_mix:  0.0
                 };
 
                 for _ in 0..num_points {
-                    let next_point = BrushPoint::deserialize_next(&last_point, data);
+                    // Version 0 brush strokes have no opacity or colour mix for their points
+                    let next_point = if version == 0 {
+                        BrushPoint::deserialize_next_without_dynamics(&last_point, data)
+                    } else {
+                        BrushPoint::deserialize_next(&last_point, data)
+                    };
+
                     points.push(next_point);
                     last_point = next_point;
                 }
@@ -64,10 +74,10 @@ mod test {
     fn brush_stroke() {
         let mut encoded = String::new();
         let element     = BrushElement::new(ElementId::Assigned(1), Arc::new(vec![
-            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0 },
-            BrushPoint { position: (8.0, 9.0), cp1: (10.0, 11.0), cp2: (12.0, 13.0), width: 14.0 },
-            BrushPoint { position: (15.0, 16.0), cp1: (17.0, 18.0), cp2: (19.0, 6.0), width: 20.0 },
-            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0 }
+            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0, opacity: 1.0, color_mix: 0.0 },
+            BrushPoint { position: (8.0, 9.0), cp1: (10.0, 11.0), cp2: (12.0, 13.0), width: 14.0, opacity: 1.0, color_mix: 0.0 },
+            BrushPoint { position: (15.0, 16.0), cp1: (17.0, 18.0), cp2: (19.0, 6.0), width: 20.0, opacity: 1.0, color_mix: 0.0 },
+            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0, opacity: 1.0, color_mix: 0.0 }
         ]));
         element.serialize(&mut encoded);
 
@@ -76,4 +86,15 @@ mod test {
 
         assert!(decoded.points() == element.points());
     }
+
+    #[test]
+    fn brush_stroke_version_0() {
+        let decoded     = BrushElement::deserialize(ElementId::Assigned(1), &mut "ACAEAAIAAMAAQAAUAAYAAcAAcAAcAAcAAcAAcAAcAAcA".chars());
+        let decoded     = decoded.unwrap();
+
+        assert!(*decoded.points() == vec![
+            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0, opacity: 1.0, color_mix: 0.0 },
+            BrushPoint { position: (8.0, 9.0), cp1: (10.0, 11.0), cp2: (12.0, 13.0), width: 14.0, opacity: 1.0, color_mix: 0.0 }
+        ]);
+    }
 }
\ No newline at end of file
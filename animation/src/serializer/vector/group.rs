@@ -13,8 +13,11 @@ impl GroupType {
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
         use self::GroupType::*;
         match self {
-            Normal      => { data.write_chr('N'); }
-            Added       => { data.write_chr('+'); }
+            Normal          => { data.write_chr('N'); }
+            Added           => { data.write_chr('+'); }
+            Subtracted      => { data.write_chr('-'); }
+            Masked          => { data.write_chr('M'); }
+            InvertedMask    => { data.write_chr('I'); }
         }
     }
 
@@ -25,6 +28,9 @@ impl GroupType {
         match data.next_chr() {
             'N'     => Some(GroupType::Normal),
             '+'     => Some(GroupType::Added),
+            '-'     => Some(GroupType::Subtracted),
+            'M'     => Some(GroupType::Masked),
+            'I'     => Some(GroupType::InvertedMask),
             _       => None
         }
     }
@@ -0,0 +1,65 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl ImageElement {
+    ///
+    /// Generates a serialized version of this image element on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // v0
+        data.write_small_u64(0);
+
+        data.write_f32(self.position().0);
+        data.write_f32(self.position().1);
+        data.write_f32(self.size().0);
+        data.write_f32(self.size().1);
+        data.write_f32(self.opacity());
+
+        // The image itself is stored separately, so only its ID is written here
+        self.image_id().serialize(data);
+    }
+
+    ///
+    /// Deserializes an image element from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<ImageElement> {
+        match data.next_small_u64() {
+            0 => {
+                let position    = (data.next_f32(), data.next_f32());
+                let size        = (data.next_f32(), data.next_f32());
+                let opacity     = data.next_f32();
+                let image_id    = ElementId::deserialize(data)?;
+
+                Some(ImageElement::new(element_id, image_id, position, size, opacity))
+            }
+
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::*;
+
+    #[test]
+    fn image_element() {
+        let image       = ReferenceImage::from_rgba(1, 2, &[10, 20, 30, 255, 40, 50, 60, 255]).unwrap();
+        let element     = ImageElement::new(ElementId::Assigned(1), ElementId::Assigned(2), (10.0, 20.0), (100.0, 200.0), 0.5).with_image(Arc::new(image));
+
+        let mut encoded = String::new();
+        element.serialize(&mut encoded);
+
+        let decoded     = ImageElement::deserialize(ElementId::Assigned(1), &mut encoded.chars()).unwrap();
+
+        assert!(decoded.id() == ElementId::Assigned(1));
+        assert!(decoded.position() == (10.0, 20.0));
+        assert!(decoded.size() == (100.0, 200.0));
+        assert!(decoded.opacity() == 0.5);
+        assert!(decoded.image_id() == ElementId::Assigned(2));
+        assert!(decoded.image().is_none());
+    }
+}
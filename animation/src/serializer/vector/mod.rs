@@ -1,4 +1,6 @@
 mod path;
+mod image;
+mod text;
 mod group;
 mod vector;
 mod motion;
@@ -11,6 +13,8 @@ mod brush_definition;
 mod brush_properties;
 
 pub use self::path::*;
+pub use self::image::*;
+pub use self::text::*;
 pub use self::group::*;
 pub use self::vector::*;
 pub use self::motion::*;
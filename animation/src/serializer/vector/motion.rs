@@ -32,6 +32,9 @@ impl Motion {
             None                    => { data.write_chr('X'); }
             Reverse(motion)         => { data.write_chr('R'); motion.serialize(data); }
             Translate(translation)  => { data.write_chr('T'); translation.serialize(data); }
+            Rotate(rotation)        => { data.write_chr('A'); rotation.serialize(data); }
+            Scale(scale)            => { data.write_chr('S'); scale.serialize(data); }
+            Fade(fade)              => { data.write_chr('F'); fade.serialize(data); }
         }
     }
 
@@ -46,7 +49,16 @@ impl Motion {
                 motion.map(|motion| Motion::Reverse(Arc::new(motion)))
             }
             'T' => {
-                Some(Motion::Translate(TranslateMotion::deserialize(data)))
+                TranslateMotion::deserialize(data).map(Motion::Translate)
+            }
+            'A' => {
+                RotateMotion::deserialize(data).map(Motion::Rotate)
+            }
+            'S' => {
+                ScaleMotion::deserialize(data).map(Motion::Scale)
+            }
+            'F' => {
+                FadeMotion::deserialize(data).map(Motion::Fade)
             }
 
             _ => None
@@ -67,11 +79,74 @@ impl TranslateMotion {
     ///
     /// Deserializes a translate motion from a data source
     ///
-    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> TranslateMotion {
-        TranslateMotion {
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<TranslateMotion> {
+        Some(TranslateMotion {
             origin:     (data.next_f32(), data.next_f32()),
-            translate:  TimeCurve::deserialize(data)
-        }
+            translate:  TimeCurve::deserialize(data)?
+        })
+    }
+}
+
+impl RotateMotion {
+    ///
+    /// Generates a serialized version of this rotation on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        data.write_f32(self.origin.0);
+        data.write_f32(self.origin.1);
+        self.rotate.serialize(data);
+    }
+
+    ///
+    /// Deserializes a rotate motion from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<RotateMotion> {
+        Some(RotateMotion {
+            origin:     (data.next_f32(), data.next_f32()),
+            rotate:     TimeCurve::deserialize(data)?
+        })
+    }
+}
+
+impl ScaleMotion {
+    ///
+    /// Generates a serialized version of this scale motion on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        data.write_f32(self.origin.0);
+        data.write_f32(self.origin.1);
+        self.scale.serialize(data);
+    }
+
+    ///
+    /// Deserializes a scale motion from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<ScaleMotion> {
+        Some(ScaleMotion {
+            origin:     (data.next_f32(), data.next_f32()),
+            scale:      TimeCurve::deserialize(data)?
+        })
+    }
+}
+
+impl FadeMotion {
+    ///
+    /// Generates a serialized version of this fade on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        data.write_f32(self.origin.0);
+        data.write_f32(self.origin.1);
+        self.opacity.serialize(data);
+    }
+
+    ///
+    /// Deserializes a fade motion from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<FadeMotion> {
+        Some(FadeMotion {
+            origin:     (data.next_f32(), data.next_f32()),
+            opacity:    TimeCurve::deserialize(data)?
+        })
     }
 }
 
@@ -103,4 +178,90 @@ mod test {
             assert!(false);
         }
     }
+
+    #[test]
+    fn rotate_motion() {
+        let motion = Motion::Rotate(RotateMotion {
+            origin:     (2.0, 3.0),
+            rotate:     TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(90.0, 0.0, Duration::from_millis(1000)))
+        });
+        let motion = MotionElement::new(ElementId::Assigned(1), motion);
+
+        let mut encoded = String::new();
+        motion.serialize(&mut encoded);
+
+        let decoded     = MotionElement::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        let decoded     = decoded.unwrap();
+
+        if let Motion::Rotate(rotate) = &*decoded.motion() {
+            assert!(rotate.origin == (2.0, 3.0));
+            assert!(rotate.rotate.is_close_to(&TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(90.0, 0.0, Duration::from_millis(1000)))));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn scale_motion() {
+        let motion = Motion::Scale(ScaleMotion {
+            origin:     (4.0, 5.0),
+            scale:      TimeCurve::new(TimePoint::new(1.0, 1.0, Duration::from_millis(0)), TimePoint::new(2.0, 0.5, Duration::from_millis(1000)))
+        });
+        let motion = MotionElement::new(ElementId::Assigned(1), motion);
+
+        let mut encoded = String::new();
+        motion.serialize(&mut encoded);
+
+        let decoded     = MotionElement::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        let decoded     = decoded.unwrap();
+
+        if let Motion::Scale(scale) = &*decoded.motion() {
+            assert!(scale.origin == (4.0, 5.0));
+            assert!(scale.scale.is_close_to(&TimeCurve::new(TimePoint::new(1.0, 1.0, Duration::from_millis(0)), TimePoint::new(2.0, 0.5, Duration::from_millis(1000)))));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn fade_motion() {
+        let motion = Motion::Fade(FadeMotion::fade_between(Duration::from_millis(100), Duration::from_millis(600), 1.0, 0.25));
+        let motion = MotionElement::new(ElementId::Assigned(1), motion);
+
+        let mut encoded = String::new();
+        motion.serialize(&mut encoded);
+
+        let decoded     = MotionElement::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        let decoded     = decoded.unwrap();
+
+        if let Motion::Fade(fade) = &*decoded.motion() {
+            assert!(fade.origin == (0.0, 0.0));
+            assert!(fade.opacity.is_close_to(&TimeCurve::new(TimePoint::new(1.0, 0.0, Duration::from_millis(100)), TimePoint::new(0.25, 0.0, Duration::from_millis(600)))));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn reversed_scale_motion() {
+        let motion = Motion::Scale(ScaleMotion::scale_to(Duration::from_millis(442), (1.0, 2.0), (3.0, 4.0))).reverse();
+        let motion = MotionElement::new(ElementId::Assigned(1), motion);
+
+        let mut encoded = String::new();
+        motion.serialize(&mut encoded);
+
+        let decoded     = MotionElement::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        let decoded     = decoded.unwrap();
+
+        if let Motion::Reverse(reversed) = &*decoded.motion() {
+            if let Motion::Scale(scale) = &**reversed {
+                assert!(scale.origin == (1.0, 2.0));
+                assert!(scale.scale.is_close_to(&ScaleMotion::scale_to(Duration::from_millis(442), (1.0, 2.0), (3.0, 4.0)).scale));
+            } else {
+                assert!(false);
+            }
+        } else {
+            assert!(false);
+        }
+    }
 }
@@ -0,0 +1,58 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+use std::sync::*;
+
+impl TextElement {
+    ///
+    /// Generates a serialized version of this text element on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // v0
+        data.write_small_u64(0);
+
+        data.write_f32(self.position().0);
+        data.write_f32(self.position().1);
+        data.write_str(&*self.text());
+
+        self.style().serialize(data);
+    }
+
+    ///
+    /// Deserializes a text element from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<TextElement> {
+        match data.next_small_u64() {
+            0 => {
+                let position    = (data.next_f32(), data.next_f32());
+                let text        = data.next_string();
+                let style       = TextStyle::deserialize(data)?;
+
+                Some(TextElement::new(element_id, Arc::new(text), Arc::new(style), position))
+            }
+
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn text_element() {
+        let element     = TextElement::new(ElementId::Assigned(1), Arc::new("Hello\nWorld".to_string()), Arc::new(TextStyle::default()), (10.0, 20.0));
+
+        let mut encoded = String::new();
+        element.serialize(&mut encoded);
+
+        let decoded     = TextElement::deserialize(ElementId::Assigned(1), &mut encoded.chars()).unwrap();
+
+        assert!(decoded.id() == ElementId::Assigned(1));
+        assert!(decoded.position() == (10.0, 20.0));
+        assert!(*decoded.text() == "Hello\nWorld".to_string());
+        assert!(*decoded.style() == TextStyle::default());
+    }
+}
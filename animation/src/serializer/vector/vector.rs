@@ -27,6 +27,8 @@ impl Vector {
             BrushProperties(props)          => { data.write_chr('P'); props.serialize(data); }
             BrushStroke(brush)              => { data.write_chr('s'); brush.serialize(data); }
             Path(path)                      => { data.write_chr('p'); path.serialize(data); }
+            Image(image)                    => { data.write_chr('i'); image.serialize(data); }
+            Text(text)                      => { data.write_chr('x'); text.serialize(data); }
             Motion(motion)                  => { data.write_chr('m'); motion.serialize(data); }
             Group(group)                    => { data.write_chr('g'); group.serialize(data); }
             Error                           => { data.write_chr('?'); }
@@ -72,6 +74,14 @@ impl Vector {
                     Some(Vector::Path(path))
                 }))
             }
+            'i' => {
+                ImageElement::deserialize(element_id, data)
+                    .map(|image| box_fn(move |_| Some(Vector::Image(image))))
+            }
+            'x' => {
+                TextElement::deserialize(element_id, data)
+                    .map(|text| box_fn(move |_| Some(Vector::Text(text))))
+            }
             'm' => { 
                 MotionElement::deserialize(element_id, data)
                     .map(|motion| box_fn(move |_| Some(Vector::Motion(motion))))
@@ -8,7 +8,7 @@ use futures::future;
 use std::i64;
 use std::sync::*;
 use std::time::{Duration};
-use std::collections::{HashMap};
+use std::collections::{HashMap, BTreeMap};
 
 ///
 /// Represents a key frame
@@ -46,7 +46,10 @@ struct InMemoryLayerStorage {
     keyframes: Vec<InMemoryKeyFrameStorage>,
 
     /// The cached items for this layer
-    cache: Vec<InMemoryLayerCache>
+    cache: Vec<InMemoryLayerCache>,
+
+    /// The ID of the audio clip attached to this layer
+    audio: Option<i64>
 }
 
 ///
@@ -74,7 +77,19 @@ struct InMemoryStorageCore {
     element_attachments: HashMap<i64, Vec<ElementAttachment>>,
 
     /// The layers
-    layers: HashMap<u64, InMemoryLayerStorage>
+    layers: HashMap<u64, InMemoryLayerStorage>,
+
+    /// The review annotations, along with the layer each one is attached to
+    annotations: BTreeMap<i64, (u64, String)>,
+
+    /// The serialized reference images that can be placed on keyframes
+    images: HashMap<i64, String>,
+
+    /// The serialized audio clips that can be attached to layers
+    audio_clips: HashMap<i64, String>,
+
+    /// The steps that can be undone and redone in each undo history (most recent last)
+    undo_histories: HashMap<u64, (Vec<String>, Vec<String>)>
 }
 
 ///
@@ -96,7 +111,11 @@ impl InMemoryStorage {
             edit_log:               vec![],
             elements:               HashMap::new(),
             layers:                 HashMap::new(),
-            element_attachments:    HashMap::new()
+            element_attachments:    HashMap::new(),
+            annotations:            BTreeMap::new(),
+            images:                 HashMap::new(),
+            audio_clips:            HashMap::new(),
+            undo_histories:         HashMap::new()
         };
 
         // And the storage
@@ -170,6 +189,66 @@ impl InMemoryStorageCore {
         }
     }
 
+    ///
+    /// Moves the keyframe at the time 'from' to the time 'to', keeping all of the elements that are attached to it
+    ///
+    fn move_keyframe(&mut self, layer_id: u64, from: Duration, to: Duration) -> StorageResponse {
+        let layer = match self.layers.get_mut(&layer_id) {
+            Some(layer) => layer,
+            None        => { return StorageResponse::NotFound; }
+        };
+
+        // There must be a keyframe at the original time and the new time must not be in use
+        let from_index = match layer.keyframes.binary_search_by(|frame| frame.when.cmp(&from)) {
+            Ok(index)   => index,
+            Err(_)      => { return StorageResponse::NotFound; }
+        };
+
+        if from != to && layer.keyframes.binary_search_by(|frame| frame.when.cmp(&to)).is_ok() {
+            return StorageResponse::NotReplacingExisting;
+        }
+
+        // Move the keyframe to its new position
+        let mut keyframe    = layer.keyframes.remove(from_index);
+        keyframe.when       = to;
+
+        for (_element_id, when) in keyframe.attached_elements.iter_mut() {
+            *when = to + when.checked_sub(from).unwrap_or(Duration::from_millis(0));
+        }
+
+        // Update where the elements are attached
+        for (element_id, _when) in keyframe.attached_elements.iter() {
+            if let Some(attachments) = self.element_attachments.get_mut(element_id) {
+                attachments.iter_mut()
+                    .filter(|attachment| attachment.layer_id == layer_id && attachment.keyframe_time == from)
+                    .for_each(|attachment| attachment.keyframe_time = to);
+            }
+        }
+
+        let to_index = match layer.keyframes.binary_search_by(|frame| frame.when.cmp(&to)) {
+            Ok(index)   => index,
+            Err(index)  => index
+        };
+        layer.keyframes.insert(to_index, keyframe);
+
+        // Anything cached for the keyframe at its old location is out of date
+        layer.cache.retain(|cache_item| cache_item.when != from);
+
+        StorageResponse::Updated
+    }
+
+    ///
+    /// Retrieves the list of steps for an undo stack
+    ///
+    fn undo_stack(&mut self, history: u64, stack: UndoStack) -> &mut Vec<String> {
+        let (undo_steps, redo_steps) = self.undo_histories.entry(history).or_insert_with(|| (vec![], vec![]));
+
+        match stack {
+            UndoStack::Undo => undo_steps,
+            UndoStack::Redo => redo_steps
+        }
+    }
+
     ///
     /// Runs a series of storage commands on this store
     ///
@@ -197,7 +276,17 @@ impl InMemoryStorageCore {
                 }
 
                 ReadHighestUnusedElementId                          => { 
-                    response.push(StorageResponse::HighestUnusedElementId(self.elements.keys().cloned().max().unwrap_or(-1)+1)); 
+                    // Annotations, images and audio clips share their IDs with the elements
+                    let highest_id = self.elements.keys().chain(self.annotations.keys()).chain(self.images.keys()).chain(self.audio_clips.keys()).cloned().max().unwrap_or(-1);
+                    response.push(StorageResponse::HighestUnusedElementId(highest_id+1)); 
+                }
+
+                ReadHighestUnusedElementIdInRange(range)            => {
+                    let highest_id = self.elements.keys().chain(self.annotations.keys()).chain(self.images.keys()).chain(self.audio_clips.keys())
+                        .filter(|id| range.contains(*id))
+                        .cloned()
+                        .max();
+                    response.push(StorageResponse::HighestUnusedElementId(highest_id.map(|id| id+1).unwrap_or(range.start)));
                 }
 
                 ReadEditLogLength                                   => { 
@@ -237,6 +326,7 @@ impl InMemoryStorageCore {
                 DeleteLayer(layer_id)                               => { 
                     if self.layers.remove(&layer_id).is_some() { 
                         self.detach_all_elements_in_layer(layer_id);
+                        self.annotations.retain(|_, (annotation_layer_id, _)| *annotation_layer_id != layer_id);
                         response.push(StorageResponse::Updated); 
                     } else { 
                         response.push(StorageResponse::NotFound); 
@@ -312,6 +402,10 @@ impl InMemoryStorageCore {
                     }
                 }
 
+                MoveKeyFrame(layer_id, from, to)                    => {
+                    response.push(self.move_keyframe(layer_id, from, to));
+                }
+
                 ReadKeyFrames(layer_id, period)                     => {
                     if let Some(layer) = self.layers.get(&layer_id) {
                         // Search for the initial keyframe
@@ -475,6 +569,106 @@ impl InMemoryStorageCore {
                         response.push(StorageResponse::NotFound);
                     }
                 }
+
+                WriteImage(image_id, image)                         => {
+                    self.images.insert(image_id, image);
+                    response.push(StorageResponse::Updated);
+                }
+
+                ReadImage(image_id)                                 => {
+                    response.push(self.images.get(&image_id)
+                        .map(|image| StorageResponse::Image(image_id, image.clone()))
+                        .unwrap_or(StorageResponse::NotFound));
+                }
+
+                WriteAudioClip(clip_id, audio)                      => {
+                    self.audio_clips.insert(clip_id, audio);
+                    response.push(StorageResponse::Updated);
+                }
+
+                ReadAudioClip(clip_id)                              => {
+                    response.push(self.audio_clips.get(&clip_id)
+                        .map(|audio| StorageResponse::AudioClip(clip_id, audio.clone()))
+                        .unwrap_or(StorageResponse::NotFound));
+                }
+
+                WriteLayerAudio(layer_id, clip_id)                  => {
+                    let clip_exists = self.audio_clips.contains_key(&clip_id);
+
+                    match self.layers.get_mut(&layer_id) {
+                        Some(layer) if clip_exists  => {
+                            layer.audio = Some(clip_id);
+                            response.push(StorageResponse::Updated);
+                        }
+
+                        // Layer or audio clip not present
+                        _                           => response.push(StorageResponse::NotFound)
+                    }
+                }
+
+                ReadLayerAudio(layer_id)                            => {
+                    response.push(self.layers.get(&layer_id)
+                        .and_then(|layer| layer.audio)
+                        .map(|clip_id| StorageResponse::LayerAudio(layer_id, clip_id))
+                        .unwrap_or(StorageResponse::NotFound));
+                }
+
+                DeleteLayerAudio(layer_id)                          => {
+                    if let Some(layer) = self.layers.get_mut(&layer_id) {
+                        layer.audio = None;
+                        response.push(StorageResponse::Updated);
+                    } else {
+                        // Layer not present
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
+                WriteAnnotation(annotation_id, layer_id, text)      => {
+                    self.annotations.insert(annotation_id, (layer_id, text));
+                    response.push(StorageResponse::Updated);
+                }
+
+                ReadAnnotation(annotation_id)                       => {
+                    response.push(self.annotations.get(&annotation_id)
+                        .map(|(layer_id, annotation)| StorageResponse::Annotation(annotation_id, *layer_id, annotation.clone()))
+                        .unwrap_or(StorageResponse::NotFound));
+                }
+
+                ReadAnnotations                                     => {
+                    for (annotation_id, (layer_id, annotation)) in self.annotations.iter() {
+                        response.push(StorageResponse::Annotation(*annotation_id, *layer_id, annotation.clone()));
+                    }
+                }
+
+                DeleteAnnotation(annotation_id)                     => {
+                    if self.annotations.remove(&annotation_id).is_some() {
+                        response.push(StorageResponse::Updated);
+                    } else {
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
+                PushUndoStep(history, stack, step)                  => {
+                    self.undo_stack(history, stack).push(step);
+                    response.push(StorageResponse::Updated);
+                }
+
+                PopUndoStep(history, stack)                         => {
+                    response.push(self.undo_stack(history, stack).pop()
+                        .map(|step| StorageResponse::UndoStep(step))
+                        .unwrap_or(StorageResponse::NotFound));
+                }
+
+                ClearUndoStack(history, stack)                      => {
+                    self.undo_stack(history, stack).clear();
+                    response.push(StorageResponse::Updated);
+                }
+
+                ReadUndoStackDepth(history)                         => {
+                    response.push(self.undo_histories.get(&history)
+                        .map(|(undo_steps, redo_steps)| StorageResponse::UndoStackDepth(undo_steps.len(), redo_steps.len()))
+                        .unwrap_or(StorageResponse::UndoStackDepth(0, 0)));
+                }
             }
         }
 
@@ -490,7 +684,8 @@ impl InMemoryLayerStorage {
         InMemoryLayerStorage {
             properties: properties,
             keyframes:  vec![],
-            cache:      vec![]
+            cache:      vec![],
+            audio:      None
         }
     }
 }
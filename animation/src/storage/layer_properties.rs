@@ -1,24 +1,52 @@
+use super::super::traits::*;
 use super::super::serializer::*;
 
+use flo_canvas::*;
+
 use std::i64;
 
 ///
 /// Storage/serialization structure used to represent the properties of a layer
 ///
+#[derive(Clone)]
 pub struct LayerProperties {
     /// The name of this layer
     pub name: String,
 
     /// The ordering of this layer, relative to other layers
-    pub ordering: i64
+    pub ordering: i64,
+
+    /// True if this layer should be rendered
+    pub visible: bool,
+
+    /// True if this layer should not accept any paint edits
+    pub locked: bool,
+
+    /// The opacity of this layer (0.0 is fully transparent, 1.0 is fully opaque)
+    pub opacity: f64,
+
+    /// How this layer is blended with the layers underneath it
+    pub blend_mode: BlendMode,
+
+    /// True if an audio clip is attached to this layer
+    pub has_audio: bool,
+
+    /// How the frames between the keyframes of this layer are generated
+    pub inbetween_mode: InbetweenMode
 }
 
 
 impl Default for LayerProperties {
     fn default() -> LayerProperties {
         LayerProperties {
-            name:       "".to_string(),
-            ordering:   i64::max_value()
+            name:           "".to_string(),
+            ordering:       i64::max_value(),
+            visible:        true,
+            locked:         false,
+            opacity:        1.0,
+            blend_mode:     BlendMode::SourceOver,
+            has_audio:      false,
+            inbetween_mode: InbetweenMode::Off
         }
     }
 }
@@ -28,11 +56,18 @@ impl LayerProperties {
     /// Serializes these file properties to a target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        // Version 0 of the properties
-        data.write_small_u64(0);
+        // Version 3 of the properties
+        data.write_small_u64(3);
 
         data.write_str(&self.name);
         data.write_i64(self.ordering);
+
+        data.write_chr(if self.visible { 'V' } else { 'H' });
+        data.write_chr(if self.locked { 'L' } else { 'U' });
+        data.write_f64(self.opacity);
+        serialize_blend_mode(&self.blend_mode, data);
+        data.write_chr(if self.has_audio { 'A' } else { '-' });
+        self.inbetween_mode.serialize(data);
     }
 
     ///
@@ -49,7 +84,97 @@ impl LayerProperties {
                 Some(result)
             }
 
+            1 => {
+                result.name         = data.next_string();
+                result.ordering     = data.next_i64();
+
+                result.visible      = data.next_chr() == 'V';
+                result.locked       = data.next_chr() == 'L';
+                result.opacity      = data.next_f64();
+                result.blend_mode   = deserialize_blend_mode(data)?;
+
+                Some(result)
+            }
+
+            2 => {
+                result.name         = data.next_string();
+                result.ordering     = data.next_i64();
+
+                result.visible      = data.next_chr() == 'V';
+                result.locked       = data.next_chr() == 'L';
+                result.opacity      = data.next_f64();
+                result.blend_mode   = deserialize_blend_mode(data)?;
+                result.has_audio    = data.next_chr() == 'A';
+
+                Some(result)
+            }
+
+            3 => {
+                result.name             = data.next_string();
+                result.ordering         = data.next_i64();
+
+                result.visible          = data.next_chr() == 'V';
+                result.locked           = data.next_chr() == 'L';
+                result.opacity          = data.next_f64();
+                result.blend_mode       = deserialize_blend_mode(data)?;
+                result.has_audio        = data.next_chr() == 'A';
+                result.inbetween_mode   = InbetweenMode::deserialize(data)?;
+
+                Some(result)
+            }
+
             _ => None
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn layer_properties() {
+        let mut encoded = String::new();
+        LayerProperties {
+            name:           "Layer".to_string(),
+            ordering:       3,
+            visible:        false,
+            locked:         true,
+            opacity:        0.5,
+            blend_mode:     BlendMode::Multiply,
+            has_audio:      true,
+            inbetween_mode: InbetweenMode::MatchByProximity
+        }.serialize(&mut encoded);
+
+        let properties = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+
+        assert!(properties.name == "Layer".to_string());
+        assert!(properties.ordering == 3);
+        assert!(properties.visible == false);
+        assert!(properties.locked == true);
+        assert!((properties.opacity-0.5).abs() < 0.001);
+        assert!(properties.blend_mode == BlendMode::Multiply);
+        assert!(properties.has_audio == true);
+        assert!(properties.inbetween_mode == InbetweenMode::MatchByProximity);
+    }
+
+    #[test]
+    fn read_version_0() {
+        // Version 0 properties only have a name and an ordering
+        let mut encoded = String::new();
+        encoded.write_small_u64(0);
+        encoded.write_str("Layer");
+        encoded.write_i64(3);
+
+        let properties = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+
+        assert!(properties.name == "Layer".to_string());
+        assert!(properties.ordering == 3);
+        assert!(properties.visible == true);
+        assert!(properties.locked == false);
+        assert!(properties.opacity == 1.0);
+        assert!(properties.blend_mode == BlendMode::SourceOver);
+        assert!(properties.has_audio == false);
+        assert!(properties.inbetween_mode == InbetweenMode::Off);
+    }
+}
@@ -1,6 +1,18 @@
 use std::ops::{Range};
 use std::time::{Duration};
 
+///
+/// Identifies one of the stacks used to store the undo history
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UndoStack {
+    /// Steps that will reverse the edits that have been performed on the animation
+    Undo,
+
+    /// Steps that will re-apply edits that have been undone
+    Redo
+}
+
 ///
 /// Command that is sent to a storage backend
 ///
@@ -18,6 +30,9 @@ pub enum StorageCommand {
     /// Retrieves the highest unused element ID (this ID and any higher are guaranteed to be unassigned)
     ReadHighestUnusedElementId,
 
+    /// Retrieves the ID after the highest element ID in use within a range (the start of the range if none of its IDs are in use)
+    ReadHighestUnusedElementIdInRange(Range<i64>),
+
     /// Reads how many edits are currently in the edit log
     ReadEditLogLength,
 
@@ -54,6 +69,9 @@ pub enum StorageCommand {
     /// Removes a key frame from a layer
     DeleteKeyFrame(u64, Duration),
 
+    /// Moves the key frame at the first time so it starts at the second time, keeping the elements that are attached to it
+    MoveKeyFrame(u64, Duration, Duration),
+
     /// Reads the keyframes that appear in a particular time range for a layer
     ReadKeyFrames(u64, Range<Duration>),
 
@@ -76,7 +94,52 @@ pub enum StorageCommand {
     DeleteLayerCache(u64, Duration, String),
 
     /// Reads from the layer cache (parameters are layer id, cache time and key)
-    ReadLayerCache(u64, Duration, String)
+    ReadLayerCache(u64, Duration, String),
+
+    /// Stores a serialized reference image (images share their IDs with the elements, and are never changed once they're written)
+    WriteImage(i64, String),
+
+    /// Reads the serialized reference image with the specified ID
+    ReadImage(i64),
+
+    /// Stores a serialized audio clip (audio clips share their IDs with the elements, and are never changed once they're written)
+    WriteAudioClip(i64, String),
+
+    /// Reads the serialized audio clip with the specified ID
+    ReadAudioClip(i64),
+
+    /// Attaches a stored audio clip to a layer (parameters are the layer ID and the audio clip ID)
+    WriteLayerAudio(u64, i64),
+
+    /// Reads the ID of the audio clip attached to a layer
+    ReadLayerAudio(u64),
+
+    /// Removes the audio clip attached to a layer
+    DeleteLayerAudio(u64),
+
+    /// Writes a serialized review annotation (parameters are the annotation ID, the layer it's attached to and the annotation)
+    WriteAnnotation(i64, u64, String),
+
+    /// Reads the annotation with the specified ID
+    ReadAnnotation(i64),
+
+    /// Reads all of the annotations in the animation
+    ReadAnnotations,
+
+    /// Removes an annotation from the storage
+    DeleteAnnotation(i64),
+
+    /// Pushes a serialized undo step onto the top of the specified undo stack in an undo history
+    PushUndoStep(u64, UndoStack, String),
+
+    /// Removes the topmost step from the specified undo stack in an undo history and returns it
+    PopUndoStep(u64, UndoStack),
+
+    /// Removes all of the steps from the specified undo stack in an undo history
+    ClearUndoStack(u64, UndoStack),
+
+    /// Reads the number of steps in the undo and redo stacks for an undo history
+    ReadUndoStackDepth(u64)
 }
 
 ///
@@ -123,6 +186,24 @@ pub enum StorageResponse {
     /// Returns the contents of the requested layer cache
     LayerCache(String),
 
+    /// A serialized reference image (parameters are the image ID and the image)
+    Image(i64, String),
+
+    /// A serialized audio clip (parameters are the audio clip ID and the clip)
+    AudioClip(i64, String),
+
+    /// The ID of the audio clip attached to a layer
+    LayerAudio(u64, i64),
+
+    /// A serialized review annotation (parameters are the annotation ID, the layer it's attached to and the annotation)
+    Annotation(i64, u64, String),
+
+    /// A serialized step that was removed from one of the undo stacks
+    UndoStep(String),
+
+    /// The number of steps in the undo and the redo stacks
+    UndoStackDepth(usize, usize),
+
     /// The storage subsystem encountered an error
     Error(StorageError, String)
 }
@@ -0,0 +1,130 @@
+use super::*;
+
+use std::time::Duration;
+
+///
+/// Creates a layer with a single annotation on it, returning the ID of the annotation
+///
+fn add_annotation<Anim: EditableAnimation>(anim: &Anim) -> ElementId {
+    let annotation_id = anim.assign_element_id();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Annotation(annotation_id, AnnotationEdit::Create(Annotation::new(2, Duration::from_millis(442), "Director", 1_600_000_000, Some((100.0, 200.0)), "Arm should swing wider")))
+    ]);
+
+    annotation_id
+}
+
+#[test]
+fn no_annotations_initially() {
+    let anim = create_animation();
+
+    assert!(anim.get_annotations().len() == 0);
+}
+
+#[test]
+fn create_annotation() {
+    let anim            = create_animation();
+    let annotation_id   = add_annotation(&anim);
+    let annotations     = anim.get_annotations();
+
+    assert!(annotations.len() == 1);
+    assert!(annotations[0].0 == annotation_id);
+    assert!(annotations[0].1.layer_id == 2);
+    assert!(annotations[0].1.when == Duration::from_millis(442));
+    assert!(annotations[0].1.author == "Director".to_string());
+    assert!(annotations[0].1.position == Some((100.0, 200.0)));
+    assert!(annotations[0].1.text == "Arm should swing wider".to_string());
+    assert!(!annotations[0].1.resolved);
+}
+
+#[test]
+fn create_annotation_with_unassigned_id() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Annotation(ElementId::Unassigned, AnnotationEdit::Create(Annotation::new(2, Duration::from_millis(0), "Director", 0, None, "Note")))
+    ]);
+
+    let annotations = anim.get_annotations();
+
+    assert!(annotations.len() == 1);
+    assert!(annotations[0].0.is_assigned());
+}
+
+#[test]
+fn resolve_annotation() {
+    let anim            = create_animation();
+    let annotation_id   = add_annotation(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Annotation(annotation_id, AnnotationEdit::SetResolved(true))]);
+
+    let annotations     = anim.get_annotations();
+    assert!(annotations.len() == 1);
+    assert!(annotations[0].1.resolved);
+    assert!(annotations[0].1.text == "Arm should swing wider".to_string());
+}
+
+#[test]
+fn delete_annotation() {
+    let anim            = create_animation();
+    let annotation_id   = add_annotation(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Annotation(annotation_id, AnnotationEdit::Delete)]);
+
+    assert!(anim.get_annotations().len() == 0);
+}
+
+#[test]
+fn removing_layer_removes_annotations() {
+    let anim = create_animation();
+    add_annotation(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::RemoveLayer(2)]);
+
+    assert!(anim.get_annotations().len() == 0);
+}
+
+#[test]
+fn undo_resolve_annotation() {
+    let anim            = create_animation();
+    let annotation_id   = add_annotation(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Annotation(annotation_id, AnnotationEdit::SetResolved(true))]);
+    anim.undo();
+
+    assert!(!anim.get_annotations()[0].1.resolved);
+
+    anim.redo();
+
+    assert!(anim.get_annotations()[0].1.resolved);
+}
+
+#[test]
+fn undo_delete_annotation() {
+    let anim            = create_animation();
+    let annotation_id   = add_annotation(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Annotation(annotation_id, AnnotationEdit::Delete)]);
+    anim.undo();
+
+    let annotations = anim.get_annotations();
+    assert!(annotations.len() == 1);
+    assert!(annotations[0].0 == annotation_id);
+}
+
+#[test]
+fn undo_remove_layer_restores_annotations() {
+    let anim            = create_animation();
+    let annotation_id   = add_annotation(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::RemoveLayer(2)]);
+    anim.undo();
+
+    let annotations = anim.get_annotations();
+    assert!(annotations.len() == 1);
+    assert!(annotations[0].0 == annotation_id);
+    assert!(annotations[0].1.layer_id == 2);
+}
@@ -0,0 +1,139 @@
+use super::*;
+
+use flo_canvas::*;
+
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// Creates an animation with two overlapping rectangles (IDs 100 and 101) grouped into a group with the specified type (ID 200)
+///
+fn group_two_rectangles(group_type: GroupType) -> impl EditableAnimation {
+    let animation = create_animation();
+
+    animation.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        rectangle(ElementId::Assigned(100), 0.0, 0.0, 100.0, 100.0),
+        rectangle(ElementId::Assigned(101), 50.0, -50.0, 150.0, 150.0),
+    ]);
+
+    animation.perform_edits(vec![AnimationEdit::Element(
+        vec![ElementId::Assigned(100), ElementId::Assigned(101)],
+        ElementEdit::Group(ElementId::Assigned(200), group_type))
+    ]);
+
+    animation
+}
+
+///
+/// Retrieves the group with ID 200 from the first frame of an animation along with its path
+///
+fn read_group<Anim: EditableAnimation>(animation: &Anim) -> (GroupElement, Vec<Path>) {
+    let layer       = animation.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let element     = frame.element_with_id(ElementId::Assigned(200)).unwrap();
+    let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+
+    match element {
+        Vector::Group(group)    => {
+            let path = group.to_path(&*properties, PathConversion::Fastest).unwrap();
+            (group, path)
+        }
+
+        _                       => { assert!(false); unimplemented!() }
+    }
+}
+
+///
+/// Renders the first frame of an animation
+///
+fn render_frame<Anim: EditableAnimation>(animation: &Anim) -> Vec<Draw> {
+    let layer                   = animation.get_layer_with_id(1).unwrap();
+    let frame                   = layer.get_frame_at_time(Duration::from_millis(0));
+    let mut drawing: Vec<Draw>  = vec![];
+
+    frame.render_to(&mut drawing);
+
+    drawing
+}
+
+#[test]
+fn subtract_rectangles() {
+    let animation           = group_two_rectangles(GroupType::Subtracted);
+    let (group, path)       = read_group(&animation);
+
+    assert!(group.group_type() == GroupType::Subtracted);
+    assert!(group.elements().map(|elem| elem.id()).collect::<Vec<_>>() == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+
+    // The second rectangle covers the right-hand half of the first one
+    assert!(path.len() == 1);
+
+    let bounds = Rect::from(&path[0]).normalize();
+    assert!((bounds.x1 - 0.0).abs() < 0.1);
+    assert!((bounds.y1 - 0.0).abs() < 0.1);
+    assert!((bounds.x2 - 50.0).abs() < 0.1);
+    assert!((bounds.y2 - 100.0).abs() < 0.1);
+}
+
+#[test]
+fn mask_rectangles() {
+    let animation           = group_two_rectangles(GroupType::Masked);
+    let (group, path)       = read_group(&animation);
+
+    assert!(group.group_type() == GroupType::Masked);
+
+    // Only the part of the second rectangle inside the first one is visible
+    assert!(path.len() == 1);
+
+    let bounds = Rect::from(&path[0]).normalize();
+    assert!((bounds.x1 - 50.0).abs() < 0.1);
+    assert!((bounds.y1 - 0.0).abs() < 0.1);
+    assert!((bounds.x2 - 100.0).abs() < 0.1);
+    assert!((bounds.y2 - 100.0).abs() < 0.1);
+
+    // Masks are rendered by clipping
+    let drawing = render_frame(&animation);
+    assert!(drawing.contains(&Draw::PushState));
+    assert!(drawing.contains(&Draw::Clip));
+    assert!(drawing.contains(&Draw::PopState));
+}
+
+#[test]
+fn inverted_mask_rectangles() {
+    let animation           = group_two_rectangles(GroupType::InvertedMask);
+    let (group, path)       = read_group(&animation);
+
+    assert!(group.group_type() == GroupType::InvertedMask);
+
+    // Only the part of the second rectangle outside the first one is visible
+    assert!(path.len() == 1);
+
+    let bounds = Rect::from(&path[0]).normalize();
+    assert!((bounds.x1 - 50.0).abs() < 0.1);
+    assert!((bounds.y1 - -50.0).abs() < 0.1);
+    assert!((bounds.x2 - 150.0).abs() < 0.1);
+    assert!((bounds.y2 - 150.0).abs() < 0.1);
+
+    // Inverted masks are also rendered by clipping
+    let drawing = render_frame(&animation);
+    assert!(drawing.contains(&Draw::Clip));
+}
+
+#[test]
+fn ungroup_subtracted_group() {
+    let animation = group_two_rectangles(GroupType::Subtracted);
+
+    animation.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::Ungroup)]);
+
+    let layer       = animation.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+    let ids         = elements.iter().map(|element| element.id()).collect::<Vec<_>>();
+
+    assert!(ids == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+}
@@ -121,7 +121,7 @@ fn read_brush_strokes_from_edit_log() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -155,7 +155,7 @@ fn read_brush_strokes_from_edit_log() {
     assert!(match edits[3] {
         AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::
             BrushProperties(ElementId::Assigned(_element_id), ref brush_properties)))
-                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 },
+                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid },
             _ => false
     });
     assert!(match edits[6] {
@@ -181,7 +181,7 @@ fn read_element_delete_from_edit_log() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -219,7 +219,7 @@ fn will_assign_element_ids() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                 RawPoint::from((10.0, 10.0)),
                 RawPoint::from((20.0, 5.0))
@@ -238,3 +238,46 @@ fn will_assign_element_ids() {
     // Element ID should be assigned
     assert!(match &paint_edit[0] { &AnimationEdit::Layer(0, LayerEdit::Paint(_, PaintEdit::BrushStroke(ElementId::Assigned(_), _))) => true, _ => false });
 }
+
+#[test]
+fn element_ids_continue_within_range_after_higher_ids_are_used() {
+    let animation   = create_animation();
+    let brush_edit  = |element_id| AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(element_id, BrushProperties::new())));
+
+    animation.perform_edits(vec![
+        AnimationEdit::AddNewLayer(0),
+        AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+
+    // Assign two IDs from the first range
+    animation.set_element_id_range(1000..2000);
+    animation.perform_edits(vec![brush_edit(ElementId::Unassigned), brush_edit(ElementId::Unassigned)]);
+
+    // Another editor creates an element with a higher ID
+    animation.perform_edits(vec![brush_edit(ElementId::Assigned(3000))]);
+
+    // Switching away from the range and back again should carry on from the IDs that were already used in it
+    animation.set_element_id_range(2000..3000);
+    animation.set_element_id_range(1000..2000);
+    assert!(animation.assign_element_id() == ElementId::Assigned(1002));
+}
+
+#[test]
+fn audio_clips_are_logged_by_id() {
+    let storage     = Arc::new(InMemoryStorage::new());
+    let anim_store  = Arc::clone(&storage);
+    let anim        = create_animation_editor(move |commands| anim_store.get_responses(commands).boxed());
+    let clip        = Arc::new(AudioClip::new(8000, 1, vec![100; 8000]));
+
+    anim.perform_edits(vec![AnimationEdit::AddAudioClip(ElementId::Assigned(50), Arc::clone(&clip))]);
+
+    // The clip data is stored once, outside of the edit log
+    let logged      = executor::block_on(storage.get_responses(stream::iter(vec![vec![StorageCommand::ReadEdits(0..1)]])).next()).unwrap();
+    let logged_len  = match &logged[0] { StorageResponse::Edit(_, edit) => edit.len(), _ => usize::max_value() };
+
+    assert!(logged_len < 16);
+
+    // Reading the edit log loads the clip again
+    let edits: Vec<_> = executor::block_on(anim.read_edit_log(0..1).collect());
+    assert!(edits == vec![AnimationEdit::AddAudioClip(ElementId::Assigned(50), clip)]);
+}
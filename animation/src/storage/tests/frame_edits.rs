@@ -19,7 +19,7 @@ fn fetch_element_by_id() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -69,7 +69,7 @@ fn read_frame_after_edits() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -140,7 +140,7 @@ fn delete_element() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -200,7 +200,7 @@ fn delete_first_element() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -260,7 +260,7 @@ fn delete_last_element() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -320,7 +320,7 @@ fn detach_element() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Assigned(125), BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -366,3 +366,71 @@ fn detach_element() {
         assert!(all_elements == vec![ElementId::Assigned(126), ElementId::Assigned(128)]);
     }
 }
+
+#[test]
+fn place_reference_image() {
+    let anim    = create_animation();
+    let image   = Arc::new(ReferenceImage::from_rgba(2, 1, &[255, 0, 0, 255, 0, 0, 255, 255]).unwrap());
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::AddImage(ElementId::Assigned(99), Arc::clone(&image)),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::PlaceImage(ElementId::Assigned(100), ElementId::Assigned(99), (10.0, 20.0), (200.0, 100.0), 0.5)))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+    let frame = layer.get_frame_at_time(Duration::from_millis(0));
+
+    assert!(layer.supported_edit_types().contains(&LayerEditType::Image));
+
+    match frame.element_with_id(ElementId::Assigned(100)) {
+        Some(Vector::Image(image_element)) => {
+            assert!(image_element.image_id() == ElementId::Assigned(99));
+            assert!(image_element.image() == Some(image));
+            assert!(image_element.position() == (10.0, 20.0));
+            assert!(image_element.size() == (200.0, 100.0));
+            assert!(image_element.opacity() == 0.5);
+        }
+
+        _ => { assert!(false) }
+    }
+
+    // The image is declared as a sprite with one filled region per colour
+    let mut sprites: Vec<Draw> = vec![];
+    frame.render_sprites(&mut sprites);
+
+    let sprite_id = match frame.element_with_id(ElementId::Assigned(100)) {
+        Some(Vector::Image(image_element))  => image_element.sprite_id(),
+        _                                   => SpriteId(0)
+    };
+
+    assert!(sprites[0] == Draw::Sprite(sprite_id));
+    assert!(sprites.iter().filter(|draw| **draw == Draw::Fill).count() == 2);
+    assert!(sprites.contains(&Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 0.5))));
+    assert!(sprites.contains(&Draw::Move(0.0, 1.0)));
+    assert!(sprites.contains(&Draw::Line(2.0, 0.0)));
+
+    // Rendering the frame draws the sprite scaled to the size of the image
+    let mut drawing: Vec<Draw> = vec![];
+    frame.render_to(&mut drawing);
+
+    assert!(drawing.iter().all(|draw| *draw != Draw::Fill));
+    assert!(drawing.contains(&Draw::SpriteTransform(SpriteTransform::Transform2D(Transform2D([[100.0, 0.0, 10.0], [0.0, 100.0, 20.0], [0.0, 0.0, 1.0]])))));
+    assert!(drawing.contains(&Draw::DrawSprite(sprite_id)));
+}
+
+#[test]
+fn place_image_that_was_never_added() {
+    let anim    = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::PlaceImage(ElementId::Assigned(100), ElementId::Assigned(99), (10.0, 20.0), (200.0, 100.0), 0.5)))
+    ]);
+
+    let frame = anim.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(0));
+
+    assert!(frame.element_with_id(ElementId::Assigned(100)).is_none());
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Draws a brush stroke on layer 1 at the specified time
+///
+fn draw_stroke<Anim: EditableAnimation>(anim: &Anim, when: Duration, element_id: i64, points: Vec<(f32, f32)>) {
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::Paint(when, PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(when, PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(points.into_iter().map(|point| RawPoint::from(point)).collect()))))
+    ]);
+}
+
+///
+/// Creates a layer with a horizontal stroke at 0ms and the same stroke moved down at 1000ms
+///
+fn create_two_keyframes<Anim: EditableAnimation>(anim: &Anim) {
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(1000)))
+    ]);
+
+    draw_stroke(anim, Duration::from_millis(0), 100, vec![(10.0, 10.0), (50.0, 10.0), (90.0, 10.0)]);
+    draw_stroke(anim, Duration::from_millis(1000), 200, vec![(10.0, 110.0), (50.0, 110.0), (90.0, 110.0)]);
+}
+
+///
+/// Reads the brush strokes from layer 1 at the specified time
+///
+fn brush_strokes_at<Anim: EditableAnimation>(anim: &Anim, when: Duration) -> Vec<BrushElement> {
+    let layer = anim.get_layer_with_id(1).unwrap();
+    let frame = layer.get_frame_at_time(when);
+
+    frame.vector_elements()
+        .map(|elements| elements.filter_map(|element| match element {
+                Vector::BrushStroke(brush_stroke)   => Some(brush_stroke),
+                _                                   => None
+            }).collect())
+        .unwrap_or_else(|| vec![])
+}
+
+///
+/// The start point of a brush stroke
+///
+fn start_point(brush_stroke: &BrushElement) -> (f32, f32) {
+    brush_stroke.points()[0].position
+}
+
+#[test]
+fn inbetweens_are_off_by_default() {
+    let anim = create_animation();
+    create_two_keyframes(&anim);
+
+    let strokes = brush_strokes_at(&anim, Duration::from_millis(500));
+
+    assert!(anim.get_layer_with_id(1).unwrap().inbetween_mode() == InbetweenMode::Off);
+    assert!(strokes.len() == 1);
+    assert!(strokes[0].id() == ElementId::Assigned(100));
+    assert!(start_point(&strokes[0]) == start_point(&brush_strokes_at(&anim, Duration::from_millis(0))[0]));
+}
+
+#[test]
+fn generate_inbetween_halfway() {
+    let anim = create_animation();
+    create_two_keyframes(&anim);
+    anim.perform_edits(vec![AnimationEdit::Layer(1, LayerEdit::SetInbetweenMode(InbetweenMode::MatchByOrder))]);
+
+    let (from_x, from_y)    = start_point(&brush_strokes_at(&anim, Duration::from_millis(0))[0]);
+    let (to_x, to_y)        = start_point(&brush_strokes_at(&anim, Duration::from_millis(1000))[0]);
+    let strokes             = brush_strokes_at(&anim, Duration::from_millis(500));
+
+    assert!(anim.get_layer_with_id(1).unwrap().inbetween_mode() == InbetweenMode::MatchByOrder);
+    assert!(strokes.len() == 1);
+    assert!(strokes[0].id() == ElementId::Assigned(100));
+
+    let (x, y)              = start_point(&strokes[0]);
+    assert!((x - (from_x+to_x)/2.0).abs() < 0.01);
+    assert!((y - (from_y+to_y)/2.0).abs() < 0.01);
+}
+
+#[test]
+fn keyframes_are_not_inbetweened() {
+    let anim = create_animation();
+    create_two_keyframes(&anim);
+    anim.perform_edits(vec![AnimationEdit::Layer(1, LayerEdit::SetInbetweenMode(InbetweenMode::MatchByProximity))]);
+
+    let strokes = brush_strokes_at(&anim, Duration::from_millis(1000));
+
+    assert!(strokes.len() == 1);
+    assert!(strokes[0].id() == ElementId::Assigned(200));
+}
+
+#[test]
+fn unmatched_strokes_swap_halfway() {
+    let anim = create_animation();
+    create_two_keyframes(&anim);
+    draw_stroke(&anim, Duration::from_millis(0), 101, vec![(10.0, 50.0), (90.0, 50.0)]);
+    anim.perform_edits(vec![AnimationEdit::Layer(1, LayerEdit::SetInbetweenMode(InbetweenMode::MatchByOrder))]);
+
+    assert!(brush_strokes_at(&anim, Duration::from_millis(250)).len() == 2);
+    assert!(brush_strokes_at(&anim, Duration::from_millis(750)).len() == 1);
+}
+
+#[test]
+fn bake_inbetweens() {
+    let anim = create_animation();
+    create_two_keyframes(&anim);
+
+    let (from_x, from_y)    = start_point(&brush_strokes_at(&anim, Duration::from_millis(0))[0]);
+    let (to_x, to_y)        = start_point(&brush_strokes_at(&anim, Duration::from_millis(1000))[0]);
+
+    anim.perform_edits(vec![AnimationEdit::Layer(1, LayerEdit::BakeInbetweens(Duration::from_millis(0)..Duration::from_millis(1000), Duration::from_millis(250), vec![]))]);
+
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    let keyframes   = layer.get_key_frames_during_time(Duration::from_millis(0)..Duration::from_millis(2000)).collect::<Vec<_>>();
+    assert!(keyframes == vec![Duration::from_millis(0), Duration::from_millis(250), Duration::from_millis(500), Duration::from_millis(750), Duration::from_millis(1000)]);
+
+    // The baked keyframe contains a copy of the stroke with a new ID
+    let strokes     = brush_strokes_at(&anim, Duration::from_millis(500));
+    assert!(strokes.len() == 1);
+    assert!(strokes[0].id() != ElementId::Assigned(100));
+    assert!(strokes[0].id() != ElementId::Assigned(200));
+
+    let (x, y)      = start_point(&strokes[0]);
+    assert!((x - (from_x+to_x)/2.0).abs() < 0.01);
+    assert!((y - (from_y+to_y)/2.0).abs() < 0.01);
+
+    // The original keyframes are unchanged
+    assert!(brush_strokes_at(&anim, Duration::from_millis(0))[0].id() == ElementId::Assigned(100));
+    assert!(brush_strokes_at(&anim, Duration::from_millis(1000))[0].id() == ElementId::Assigned(200));
+}
@@ -0,0 +1,182 @@
+use super::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates a layer with a keyframe and three brush strokes with the IDs 100, 101 and 102
+///
+fn draw_three_strokes<Anim: EditableAnimation>(anim: &Anim) {
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new())))
+    ]);
+
+    for element_id in 100..103 {
+        anim.perform_edits(vec![
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(vec![
+                        RawPoint::from((10.0, 10.0)),
+                        RawPoint::from((20.0, 5.0))
+                    ]))))
+        ]);
+    }
+}
+
+///
+/// Reads the IDs of the elements in layer 2 at the specified time
+///
+fn element_ids_at<Anim: EditableAnimation>(anim: &Anim, when: Duration) -> Vec<ElementId> {
+    let layer   = anim.get_layer_with_id(2).unwrap();
+    let frame   = layer.get_frame_at_time(when);
+
+    frame.vector_elements()
+        .map(|elements| elements.map(|elem| elem.id()).collect())
+        .unwrap_or_else(|| vec![])
+}
+
+///
+/// Reads the times of the keyframes in layer 2
+///
+fn key_frames<Anim: EditableAnimation>(anim: &Anim) -> Vec<Duration> {
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    layer.get_key_frames_during_time(Duration::from_millis(0)..Duration::from_millis(10000)).collect()
+}
+
+#[test]
+fn move_keyframe() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(0), Duration::from_millis(1000)))]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(1000)]);
+
+    // The elements keep their offset from the start of the keyframe
+    assert!(element_ids_at(&anim, Duration::from_millis(442)) == vec![]);
+    assert!(element_ids_at(&anim, Duration::from_millis(1442)) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+}
+
+#[test]
+fn move_keyframe_earlier() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(0), Duration::from_millis(1000))),
+        AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(1000), Duration::from_millis(500)))
+    ]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(500)]);
+    assert!(element_ids_at(&anim, Duration::from_millis(942)) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+}
+
+#[test]
+fn move_keyframe_onto_existing_keyframe_is_ignored() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+        AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(0), Duration::from_millis(1000)))
+    ]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(0), Duration::from_millis(1000)]);
+    assert!(element_ids_at(&anim, Duration::from_millis(442)) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+    assert!(element_ids_at(&anim, Duration::from_millis(1442)) == vec![]);
+}
+
+#[test]
+fn duplicate_keyframe() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Layer(2, LayerEdit::DuplicateKeyFrame(Duration::from_millis(0), Duration::from_millis(1000), vec![]))]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(0), Duration::from_millis(1000)]);
+
+    // Original keyframe is unchanged
+    let original    = element_ids_at(&anim, Duration::from_millis(442));
+    assert!(original == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+
+    // Copy has the same number of elements but with new IDs
+    let copy        = element_ids_at(&anim, Duration::from_millis(1442));
+    assert!(copy.len() == 3);
+    assert!(copy.iter().all(|element_id| element_id.is_assigned() && !original.contains(element_id)));
+}
+
+#[test]
+fn retime_keyframes() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(2000))),
+        AnimationEdit::Layer(2, LayerEdit::RetimeKeyFrames(Duration::from_millis(1000)..Duration::from_millis(3000), Duration::from_millis(2000)..Duration::from_millis(6000)))
+    ]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(0), Duration::from_millis(2000), Duration::from_millis(4000)]);
+}
+
+#[test]
+fn retime_keyframes_earlier() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(2000))),
+        AnimationEdit::Layer(2, LayerEdit::RetimeKeyFrames(Duration::from_millis(1000)..Duration::from_millis(3000), Duration::from_millis(500)..Duration::from_millis(2500)))
+    ]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(0), Duration::from_millis(500), Duration::from_millis(1500)]);
+    assert!(element_ids_at(&anim, Duration::from_millis(442)) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+}
+
+#[test]
+fn retime_keyframes_onto_existing_keyframe_is_rejected() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(2000))),
+        AnimationEdit::Layer(2, LayerEdit::RetimeKeyFrames(Duration::from_millis(1000)..Duration::from_millis(3000), Duration::from_millis(0)..Duration::from_millis(2000)))
+    ]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(0), Duration::from_millis(1000), Duration::from_millis(2000)]);
+}
+
+#[test]
+fn retime_keyframes_to_reversed_range_is_rejected() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(2000))),
+        AnimationEdit::Layer(2, LayerEdit::RetimeKeyFrames(Duration::from_millis(1000)..Duration::from_millis(3000), Duration::from_millis(6000)..Duration::from_millis(2000)))
+    ]);
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(0), Duration::from_millis(1000), Duration::from_millis(2000)]);
+}
+
+#[test]
+fn undo_move_keyframe() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(0), Duration::from_millis(1000)))]);
+    anim.undo();
+
+    assert!(key_frames(&anim) == vec![Duration::from_millis(0)]);
+    assert!(element_ids_at(&anim, Duration::from_millis(442)) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+}
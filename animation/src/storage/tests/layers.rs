@@ -334,3 +334,184 @@ fn get_active_brush() {
 
     assert!(active_brush.is_some());
 }
+
+#[test]
+fn default_layer_display_properties() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2)
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(layer.is_visible());
+    assert!(!layer.is_locked());
+    assert!(layer.opacity() == 1.0);
+    assert!(layer.blend_mode() == flo_canvas::BlendMode::SourceOver);
+}
+
+#[test]
+fn set_layer_display_properties() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetName("Overlay".to_string())),
+        AnimationEdit::Layer(2, LayerEdit::SetVisibility(false)),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true)),
+        AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.25)),
+        AnimationEdit::Layer(2, LayerEdit::SetBlendMode(flo_canvas::BlendMode::Screen))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(layer.name() == Some("Overlay".to_string()));
+    assert!(!layer.is_visible());
+    assert!(layer.is_locked());
+    assert!((layer.opacity()-0.25).abs() < 0.001);
+    assert!(layer.blend_mode() == flo_canvas::BlendMode::Screen);
+    assert!(layer.get_frame_at_time(Duration::from_millis(0)).blend_mode() == flo_canvas::BlendMode::Screen);
+    assert!((layer.get_frame_at_time(Duration::from_millis(0)).opacity()-0.25).abs() < 0.001);
+}
+
+#[test]
+fn locked_layer_ignores_paint_edits() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true)),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+    ]);
+
+    let frame = anim.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(0));
+    assert!(frame.element_with_id(ElementId::Assigned(100)).is_none());
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(false)),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(101), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+    ]);
+
+    let frame = anim.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(0));
+    assert!(frame.element_with_id(ElementId::Assigned(101)).is_some());
+}
+
+#[test]
+fn locked_layer_ignores_element_edits() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(102), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 20.0)),
+                PathComponent::Line(PathPoint::new(20.0, 20.0)),
+                PathComponent::Line(PathPoint::new(20.0, 30.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true)),
+        AnimationEdit::Element(vec![ElementId::Assigned(102)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(10.0, 20.0), ElementTransform::MoveTo(100.0, 100.0)])),
+        AnimationEdit::Element(vec![ElementId::Assigned(102)], ElementEdit::Delete)
+    ]);
+
+    // The path should still be there, without a transformation attached to it
+    let frame = anim.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(0));
+
+    assert!(frame.element_with_id(ElementId::Assigned(102)).is_some());
+    assert!(frame.attached_elements(ElementId::Assigned(102)).len() == 0);
+
+    // The element can be edited again once the layer is unlocked
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(false)),
+        AnimationEdit::Element(vec![ElementId::Assigned(102)], ElementEdit::Delete)
+    ]);
+
+    let frame = anim.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(0));
+    assert!(frame.element_with_id(ElementId::Assigned(102)).is_none());
+}
+
+#[test]
+fn layer_opacity_is_clamped() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::Layer(2, LayerEdit::SetOpacity(1.5)),
+        AnimationEdit::Layer(3, LayerEdit::SetOpacity(-0.5))
+    ]);
+
+    assert!(anim.get_layer_with_id(2).unwrap().opacity() == 1.0);
+    assert!(anim.get_layer_with_id(3).unwrap().opacity() == 0.0);
+}
+
+#[test]
+fn import_audio_to_layer() {
+    let anim = create_animation();
+    let clip = Arc::new(AudioClip::new(100, 1, (0..100).map(|index| if index >= 50 { 16384 } else { 0 }).collect()));
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddAudioClip(ElementId::Assigned(50), Arc::clone(&clip)),
+        AnimationEdit::Layer(2, LayerEdit::ImportAudio(ElementId::Assigned(50)))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(layer.supported_edit_types().contains(&LayerEditType::Audio));
+    assert!(layer.audio_clip() == Some(Arc::clone(&clip)));
+
+    let amplitudes = layer.audio_clip().unwrap().frame_amplitudes(Duration::from_millis(250));
+    assert!(amplitudes.len() == 4);
+    assert!(amplitudes[1] == 0.0);
+    assert!((amplitudes[2] - 0.5).abs() < 0.001);
+}
+
+#[test]
+fn remove_audio_from_layer() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddAudioClip(ElementId::Assigned(50), Arc::new(AudioClip::new(8000, 1, vec![0; 800]))),
+        AnimationEdit::Layer(2, LayerEdit::ImportAudio(ElementId::Assigned(50))),
+        AnimationEdit::Layer(2, LayerEdit::RemoveAudio)
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(!layer.supported_edit_types().contains(&LayerEditType::Audio));
+    assert!(layer.audio_clip().is_none());
+}
+
+#[test]
+fn cannot_import_audio_clip_that_was_not_added() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::ImportAudio(ElementId::Assigned(50)))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(!layer.supported_edit_types().contains(&LayerEditType::Audio));
+    assert!(layer.audio_clip().is_none());
+}
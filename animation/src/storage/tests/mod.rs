@@ -3,6 +3,9 @@ use crate::editor::*;
 use crate::storage::*;
 use futures::*;
 
+use std::sync::{Arc};
+use std::time::{Duration};
+
 mod animation_properties;
 mod layers;
 mod edit_log;
@@ -12,7 +15,14 @@ mod path;
 mod caching;
 mod collide_paths;
 mod grouping;
+mod keyframes;
+mod inbetweens;
+mod boolean_groups;
+mod path_operations;
+mod text;
 mod transformation;
+mod undo;
+mod annotations;
 
 ///
 /// Creates an in-memory animaton for the tests
@@ -24,6 +34,20 @@ pub fn create_animation() -> impl EditableAnimation {
     animation
 }
 
+///
+/// Creates the edits to draw a rectangle as a path element on the keyframe at time 0 of layer 1
+///
+pub fn rectangle(element_id: ElementId, x1: f32, y1: f32, x2: f32, y2: f32) -> AnimationEdit {
+    AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0),
+        PathEdit::CreatePath(element_id, Arc::new(vec![
+            PathComponent::Move(PathPoint::new(x1, y1)),
+            PathComponent::Line(PathPoint::new(x2, y1)),
+            PathComponent::Line(PathPoint::new(x2, y2)),
+            PathComponent::Line(PathPoint::new(x1, y2)),
+            PathComponent::Close
+        ]))))
+}
+
 ///
 /// Deserializes some edits and runs them on the animation. The edit string can be generated
 /// by the diagnostics command line tool.
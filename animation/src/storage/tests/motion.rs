@@ -261,3 +261,101 @@ fn delete_motion_element() {
     let attached = anim.motion().get_elements_for_motion(ElementId::Assigned(100));
     assert!(attached == vec![]);
 }
+
+#[test]
+fn rotate_existing_element() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Rotate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(15.0, 7.5)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(442)), TimePoint::new(90.0, 0.0, Duration::from_millis(1442))))),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100)))
+    ]);
+
+    let attached = anim.motion().get_motions_for_element(ElementId::Assigned(50));
+    assert!(attached == vec![ElementId::Assigned(100)]);
+
+    let motion = anim.motion().get_motion(ElementId::Assigned(100));
+    assert!(motion.as_ref().unwrap().motion_type() == MotionType::Rotate);
+
+    if let Some(Motion::Rotate(rotate)) = motion {
+        assert!(rotate.origin == (15.0, 7.5));
+        assert!(rotate.rotate == TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(442)), TimePoint::new(90.0, 0.0, Duration::from_millis(1442))));
+    } else {
+        assert!(false)
+    }
+}
+
+#[test]
+fn fade_existing_element() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Fade)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(1.0, 0.0, Duration::from_millis(442)), TimePoint::new(0.0, 0.0, Duration::from_millis(1442))))),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100)))
+    ]);
+
+    let motion = anim.motion().get_motion(ElementId::Assigned(100));
+    assert!(motion.as_ref().unwrap().motion_type() == MotionType::Fade);
+
+    let motion = motion.unwrap();
+    assert!(motion.transformation(Duration::from_millis(942)).len() == 0);
+    assert!((motion.opacity(Duration::from_millis(442)) - 1.0).abs() < 0.01);
+    assert!((motion.opacity(Duration::from_millis(1442)) - 0.0).abs() < 0.01);
+}
+
+#[test]
+fn set_motion_easing() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(0.0, 0.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 0.0, Duration::from_millis(1000))))),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetEasing(0, Easing::EaseIn))
+    ]);
+
+    let motion = anim.motion().get_motion(ElementId::Assigned(100));
+
+    if let Some(Motion::Translate(translate)) = motion {
+        assert!(translate.translate.points[0].easing == Easing::EaseIn);
+        assert!(translate.translate.point_at_time(250.0).unwrap().0 < 20.0);
+    } else {
+        assert!(false)
+    }
+}
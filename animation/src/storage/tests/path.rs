@@ -469,3 +469,121 @@ fn create_path_and_re_order_to_top_and_bottom() {
         assert!(elements[2].id() == ElementId::Assigned(102));
     }
 }
+
+///
+/// Creates a layer 24 containing a square path element with ID 100
+///
+fn create_square_path() -> impl EditableAnimation {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(300))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(0.0, 0.0)),
+                PathComponent::Line(PathPoint::new(100.0, 0.0)),
+                PathComponent::Line(PathPoint::new(100.0, 100.0)),
+                PathComponent::Line(PathPoint::new(0.0, 100.0)),
+                PathComponent::Close
+            ]))))
+    ]);
+
+    anim
+}
+
+///
+/// Reads the path components for element 100 in layer 24
+///
+fn read_square_path(anim: &impl EditableAnimation) -> Vec<PathComponent> {
+    let layer   = anim.get_layer_with_id(24).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(300));
+
+    if let Some(Vector::Path(path)) = frame.element_with_id(ElementId::Assigned(100)) {
+        path.path().elements().collect()
+    } else {
+        assert!(false, "Element 100 is not a path");
+        vec![]
+    }
+}
+
+#[test]
+fn insert_and_delete_path_node() {
+    let anim = create_square_path();
+
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::InsertPathNode(1, 0.5))]);
+    assert!(read_square_path(&anim) == vec![
+        PathComponent::Move(PathPoint::new(0.0, 0.0)),
+        PathComponent::Line(PathPoint::new(50.0, 0.0)),
+        PathComponent::Line(PathPoint::new(100.0, 0.0)),
+        PathComponent::Line(PathPoint::new(100.0, 100.0)),
+        PathComponent::Line(PathPoint::new(0.0, 100.0)),
+        PathComponent::Close
+    ]);
+
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::DeletePathNode(3))]);
+    assert!(read_square_path(&anim) == vec![
+        PathComponent::Move(PathPoint::new(0.0, 0.0)),
+        PathComponent::Line(PathPoint::new(50.0, 0.0)),
+        PathComponent::Line(PathPoint::new(100.0, 0.0)),
+        PathComponent::Line(PathPoint::new(0.0, 100.0)),
+        PathComponent::Close
+    ]);
+}
+
+#[test]
+fn convert_path_node_to_smooth_and_back() {
+    let anim = create_square_path();
+
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetPathNodeType(2, PathNodeType::Smooth))]);
+    let smooth = read_square_path(&anim);
+
+    match (smooth[2], smooth[3]) {
+        (PathComponent::Bezier(_, _, cp2), PathComponent::Bezier(_, cp1, _)) => {
+            assert!(cp2 != PathPoint::new(100.0, 100.0));
+            assert!(cp1 != PathPoint::new(100.0, 100.0));
+        },
+        _ => assert!(false, "Node was not made smooth")
+    }
+
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetPathNodeType(2, PathNodeType::Corner))]);
+    let corner = read_square_path(&anim);
+
+    match (corner[2], corner[3]) {
+        (PathComponent::Bezier(_, _, cp2), PathComponent::Bezier(_, cp1, _)) => {
+            assert!(cp2 == PathPoint::new(100.0, 100.0));
+            assert!(cp1 == PathPoint::new(100.0, 100.0));
+        },
+        _ => assert!(false, "Node was not made a corner")
+    }
+}
+
+#[test]
+fn break_and_join_path() {
+    let anim = create_square_path();
+
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::BreakPath(0))]);
+    assert!(read_square_path(&anim) == vec![
+        PathComponent::Move(PathPoint::new(0.0, 0.0)),
+        PathComponent::Line(PathPoint::new(100.0, 0.0)),
+        PathComponent::Line(PathPoint::new(100.0, 100.0)),
+        PathComponent::Line(PathPoint::new(0.0, 100.0)),
+        PathComponent::Line(PathPoint::new(0.0, 0.0))
+    ]);
+
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::JoinPathNodes(0, 4))]);
+    assert!(read_square_path(&anim) == vec![
+        PathComponent::Move(PathPoint::new(0.0, 0.0)),
+        PathComponent::Line(PathPoint::new(100.0, 0.0)),
+        PathComponent::Line(PathPoint::new(100.0, 100.0)),
+        PathComponent::Line(PathPoint::new(0.0, 100.0)),
+        PathComponent::Line(PathPoint::new(0.0, 0.0)),
+        PathComponent::Close
+    ]);
+}
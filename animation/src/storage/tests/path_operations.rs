@@ -0,0 +1,131 @@
+use super::*;
+
+use std::time::{Duration};
+
+///
+/// Creates an animation with two overlapping rectangles (IDs 100 and 101) combined into a single path element with ID 200
+///
+fn combine_two_rectangles(operation: PathOperation, second_rectangle: (f32, f32, f32, f32)) -> impl EditableAnimation {
+    let animation           = create_animation();
+    let (x1, y1, x2, y2)    = second_rectangle;
+
+    animation.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        rectangle(ElementId::Assigned(100), 0.0, 0.0, 100.0, 100.0),
+        rectangle(ElementId::Assigned(101), x1, y1, x2, y2),
+    ]);
+
+    animation.perform_edits(vec![AnimationEdit::Element(
+        vec![ElementId::Assigned(100), ElementId::Assigned(101)],
+        ElementEdit::CombinePaths(ElementId::Assigned(200), operation))
+    ]);
+
+    animation
+}
+
+///
+/// Reads the IDs of the elements in the first frame of an animation
+///
+fn element_ids<Anim: EditableAnimation>(animation: &Anim) -> Vec<ElementId> {
+    let layer       = animation.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+
+    frame.vector_elements().unwrap().map(|element| element.id()).collect()
+}
+
+///
+/// Retrieves the bounds of each of the subpaths of the path element with ID 200
+///
+fn combined_subpath_bounds<Anim: EditableAnimation>(animation: &Anim) -> Vec<Rect> {
+    let layer       = animation.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let element     = frame.element_with_id(ElementId::Assigned(200)).unwrap();
+
+    match element {
+        Vector::Path(path)  => path.path().to_subpaths().iter().map(|subpath| Rect::from(subpath).normalize()).collect(),
+        _                   => { assert!(false); unimplemented!() }
+    }
+}
+
+///
+/// Checks that a rectangle has the specified coordinates
+///
+fn assert_bounds(bounds: &Rect, x1: f32, y1: f32, x2: f32, y2: f32) {
+    assert!((bounds.x1 - x1).abs() < 0.1);
+    assert!((bounds.y1 - y1).abs() < 0.1);
+    assert!((bounds.x2 - x2).abs() < 0.1);
+    assert!((bounds.y2 - y2).abs() < 0.1);
+}
+
+#[test]
+fn union_rectangles() {
+    let animation = combine_two_rectangles(PathOperation::Union, (50.0, -50.0, 150.0, 150.0));
+
+    // The two rectangles are replaced by the combined element
+    assert!(element_ids(&animation) == vec![ElementId::Assigned(200)]);
+
+    let bounds = combined_subpath_bounds(&animation);
+    assert!(bounds.len() == 1);
+    assert_bounds(&bounds[0], 0.0, -50.0, 150.0, 150.0);
+}
+
+#[test]
+fn subtract_rectangle_makes_hole() {
+    let animation = combine_two_rectangles(PathOperation::Subtract, (25.0, 25.0, 75.0, 75.0));
+
+    assert!(element_ids(&animation) == vec![ElementId::Assigned(200)]);
+
+    // Subtracting a rectangle from the middle of the first one leaves the outline of the first rectangle and the outline of the hole
+    let mut bounds = combined_subpath_bounds(&animation);
+    bounds.sort_by(|a, b| a.x1.partial_cmp(&b.x1).unwrap());
+
+    assert!(bounds.len() == 2);
+    assert_bounds(&bounds[0], 0.0, 0.0, 100.0, 100.0);
+    assert_bounds(&bounds[1], 25.0, 25.0, 75.0, 75.0);
+}
+
+#[test]
+fn intersect_rectangles() {
+    let animation = combine_two_rectangles(PathOperation::Intersect, (50.0, -50.0, 150.0, 150.0));
+
+    assert!(element_ids(&animation) == vec![ElementId::Assigned(200)]);
+
+    let bounds = combined_subpath_bounds(&animation);
+    assert!(bounds.len() == 1);
+    assert_bounds(&bounds[0], 50.0, 0.0, 100.0, 100.0);
+}
+
+#[test]
+fn exclude_rectangles() {
+    let animation = combine_two_rectangles(PathOperation::Exclude, (50.0, -50.0, 150.0, 150.0));
+
+    assert!(element_ids(&animation) == vec![ElementId::Assigned(200)]);
+
+    // Covers the same area as the union, but has the intersection removed from its interior
+    let bounds  = combined_subpath_bounds(&animation);
+    let total   = bounds.iter().fold(bounds[0], |total, bounds| total.union(*bounds));
+
+    assert!(bounds.len() >= 2);
+    assert_bounds(&total, 0.0, -50.0, 150.0, 150.0);
+}
+
+#[test]
+fn empty_intersection_leaves_elements_unchanged() {
+    let animation = combine_two_rectangles(PathOperation::Intersect, (200.0, 200.0, 300.0, 300.0));
+
+    assert!(element_ids(&animation) == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+}
+
+#[test]
+fn undo_combine_paths() {
+    let animation = combine_two_rectangles(PathOperation::Union, (50.0, -50.0, 150.0, 150.0));
+
+    animation.undo();
+
+    assert!(element_ids(&animation) == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+use flo_canvas::*;
+
+use std::time::Duration;
+
+///
+/// Creates an animation with a text element (ID 100) on layer 1
+///
+fn create_text(text: &str, style: TextStyle) -> impl EditableAnimation {
+    let animation = create_animation();
+
+    animation.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::CreateText(ElementId::Assigned(100), text.to_string(), style, (100.0, 200.0))))
+    ]);
+
+    animation
+}
+
+///
+/// Reads the element with ID 100 from the first frame of an animation
+///
+fn read_element<Anim: EditableAnimation>(animation: &Anim) -> Option<Vector> {
+    let layer = animation.get_layer_with_id(1).unwrap();
+    let frame = layer.get_frame_at_time(Duration::from_millis(0));
+
+    frame.element_with_id(ElementId::Assigned(100))
+}
+
+#[test]
+fn create_text_element() {
+    let animation = create_text("Title", TextStyle::default());
+
+    match read_element(&animation) {
+        Some(Vector::Text(text)) => {
+            assert!(*text.text() == "Title".to_string());
+            assert!(*text.style() == TextStyle::default());
+            assert!(text.position() == (100.0, 200.0));
+        }
+
+        _ => { assert!(false) }
+    }
+}
+
+#[test]
+fn render_text_element() {
+    let style       = TextStyle { color: Color::Rgba(1.0, 0.0, 0.0, 1.0), ..TextStyle::default() };
+    let animation   = create_text("Ab", style);
+
+    let layer       = animation.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+
+    let mut drawing: Vec<Draw> = vec![];
+    frame.render_to(&mut drawing);
+
+    // Text is drawn as a single filled path made up of the glyph outlines
+    assert!(drawing.contains(&Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0))));
+    assert!(drawing.iter().filter(|draw| **draw == Draw::Fill).count() == 1);
+    assert!(drawing.iter().filter(|draw| match draw { Draw::Move(_, _) => true, _ => false }).count() >= 2);
+}
+
+#[test]
+fn edit_text_and_style() {
+    let animation   = create_text("Title", TextStyle::default());
+    let new_style   = TextStyle { font: "Lato Bold".to_string(), size: 48.0, color: Color::Rgba(0.0, 0.0, 1.0, 1.0), alignment: TextAlignment::Center };
+
+    animation.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetText("Speech".to_string())),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetTextStyle(new_style.clone()))
+    ]);
+
+    match read_element(&animation) {
+        Some(Vector::Text(text)) => {
+            assert!(*text.text() == "Speech".to_string());
+            assert!(*text.style() == new_style);
+            assert!(text.position() == (100.0, 200.0));
+        }
+
+        _ => { assert!(false) }
+    }
+}
+
+#[test]
+fn text_outline_is_near_position() {
+    let animation   = create_text("Title", TextStyle::default());
+    let element     = read_element(&animation).unwrap();
+    let paths       = element.to_path(&VectorProperties::default(), PathConversion::Fastest).unwrap();
+    let bounds      = paths.iter().fold(Rect::empty(), |bounds, path| bounds.union(Rect::from(path))).normalize();
+
+    // One path for each glyph
+    assert!(paths.len() == 5);
+
+    // Text sits on the baseline at the position and is about as high as the font size
+    assert!(bounds.x1 >= 100.0 && bounds.x1 < 110.0);
+    assert!((bounds.y1 - 200.0).abs() < 1.0);
+    assert!(bounds.y2 > 210.0 && bounds.y2 < 230.0);
+}
+
+#[test]
+fn convert_text_to_path() {
+    let style       = TextStyle { color: Color::Rgba(0.0, 1.0, 0.0, 1.0), ..TextStyle::default() };
+    let animation   = create_text("Hi", style);
+
+    animation.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::ConvertToPath)]);
+
+    match read_element(&animation) {
+        Some(Vector::Path(path)) => {
+            // The path has the outlines of both glyphs, and is filled in the colour of the text
+            assert!(path.path().to_subpaths().len() >= 2);
+            assert!(path.properties().brush_properties().color == Color::Rgba(0.0, 1.0, 0.0, 1.0));
+        }
+
+        _ => { assert!(false) }
+    }
+}
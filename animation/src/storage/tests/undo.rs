@@ -0,0 +1,320 @@
+use super::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates a layer with a keyframe and three brush strokes with the IDs 100, 101 and 102
+///
+fn draw_three_strokes<Anim: EditableAnimation>(anim: &Anim) {
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new())))
+    ]);
+
+    for element_id in 100..103 {
+        anim.perform_edits(vec![
+            AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(vec![
+                        RawPoint::from((10.0, 10.0)),
+                        RawPoint::from((20.0, 5.0))
+                    ]))))
+        ]);
+    }
+}
+
+///
+/// Reads the IDs of the elements in layer 2 at 442ms
+///
+fn element_ids<Anim: EditableAnimation>(anim: &Anim) -> Vec<ElementId> {
+    let layer   = anim.get_layer_with_id(2);
+    let layer   = match layer {
+        Some(layer) => layer,
+        None        => { return vec![]; }
+    };
+    let frame   = layer.get_frame_at_time(Duration::from_millis(442));
+
+    frame.vector_elements()
+        .map(|elements| elements.map(|elem| elem.id()).collect())
+        .unwrap_or_else(|| vec![])
+}
+
+#[test]
+fn nothing_to_undo_initially() {
+    let anim = create_animation();
+
+    assert!(!anim.can_undo());
+    assert!(!anim.can_redo());
+}
+
+#[test]
+fn undo_add_layer() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(2)]);
+    assert!(anim.get_layer_ids().len() == 1);
+    assert!(anim.can_undo());
+
+    anim.undo();
+    assert!(anim.get_layer_ids().len() == 0);
+    assert!(!anim.can_undo());
+    assert!(anim.can_redo());
+}
+
+#[test]
+fn redo_add_layer() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(2)]);
+    anim.undo();
+    anim.redo();
+
+    assert!(anim.get_layer_ids() == vec![2]);
+    assert!(anim.can_undo());
+    assert!(!anim.can_redo());
+}
+
+#[test]
+fn undo_set_size() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::SetSize(800.0, 600.0)]);
+    anim.perform_edits(vec![AnimationEdit::SetSize(1024.0, 768.0)]);
+    assert!(anim.size() == (1024.0, 768.0));
+
+    anim.undo();
+    assert!(anim.size() == (800.0, 600.0));
+
+    anim.undo();
+    assert!(anim.size() == (1920.0, 1080.0));
+
+    anim.redo();
+    assert!(anim.size() == (800.0, 600.0));
+}
+
+#[test]
+fn undo_set_layer_name() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(2), AnimationEdit::Layer(2, LayerEdit::SetName("Before".to_string()))]);
+    anim.perform_edits(vec![AnimationEdit::Layer(2, LayerEdit::SetName("After".to_string()))]);
+    assert!(anim.get_layer_with_id(2).unwrap().name() == Some("After".to_string()));
+
+    anim.undo();
+    assert!(anim.get_layer_with_id(2).unwrap().name() == Some("Before".to_string()));
+}
+
+#[test]
+fn undo_brush_stroke() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+
+    anim.undo();
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+
+    anim.undo();
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100)]);
+
+    anim.redo();
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+}
+
+#[test]
+fn undo_delete_element() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::Delete)]);
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(102)]);
+
+    anim.undo();
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+
+    anim.redo();
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(102)]);
+}
+
+#[test]
+fn undo_remove_layer() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::RemoveLayer(2)]);
+    assert!(anim.get_layer_ids().len() == 0);
+
+    anim.undo();
+    assert!(anim.get_layer_ids() == vec![2]);
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+}
+
+#[test]
+fn undo_remove_keyframe() {
+    let anim = create_animation();
+    draw_three_strokes(&anim);
+
+    anim.perform_edits(vec![AnimationEdit::Layer(2, LayerEdit::RemoveKeyFrame(Duration::from_millis(0)))]);
+    assert!(element_ids(&anim) == vec![]);
+
+    anim.undo();
+    assert!(element_ids(&anim) == vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)]);
+}
+
+#[test]
+fn new_edit_discards_redo() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(2)]);
+    anim.undo();
+    assert!(anim.can_redo());
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(3)]);
+    assert!(!anim.can_redo());
+
+    anim.redo();
+    assert!(anim.get_layer_ids() == vec![3]);
+}
+
+#[test]
+fn undo_is_written_to_edit_log() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(2)]);
+    anim.undo();
+    anim.redo();
+
+    let edit_log        = anim.read_edit_log(0..anim.get_num_edits());
+    let edit_log        = edit_log.collect();
+    let edits: Vec<_>   = executor::block_on(edit_log);
+
+    assert!(edits.len() == 3);
+    assert!(edits[0] == AnimationEdit::AddNewLayer(2));
+    assert!(edits[1] == AnimationEdit::Undo);
+    assert!(edits[2] == AnimationEdit::Redo);
+}
+
+#[test]
+fn undo_with_nothing_to_undo_leaves_animation_unchanged() {
+    let anim = create_animation();
+
+    anim.perform_edits_without_undo(vec![AnimationEdit::AddNewLayer(2)]);
+    anim.undo();
+    anim.redo();
+
+    // Undo and redo are still logged, so that a copy of the log performs them at the same point in its own undo history
+    assert!(anim.get_layer_ids() == vec![2]);
+    assert!(anim.get_num_edits() == 3);
+}
+
+#[test]
+fn undo_histories_are_separate() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::AddNewLayer(2)]);
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(3)]);
+
+    // Undo history 1 only contains the edit that added layer 2
+    anim.perform_edits(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::Undo]);
+    assert!(anim.get_layer_ids() == vec![3]);
+    assert!(anim.can_undo());
+
+    anim.undo();
+    assert!(anim.get_layer_ids().len() == 0);
+    assert!(!anim.can_undo());
+
+    anim.perform_edits(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::Redo]);
+    assert!(anim.get_layer_ids() == vec![2]);
+}
+
+#[test]
+fn edit_log_with_undo_replays_on_animation_with_different_history() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::AddNewLayer(2)]);
+    anim.perform_edits(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::AddNewLayer(3)]);
+    anim.perform_edits(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::Undo]);
+
+    let edit_log        = anim.read_edit_log(0..anim.get_num_edits());
+    let edit_log        = edit_log.collect();
+    let edits: Vec<_>   = executor::block_on(edit_log);
+
+    // The undo only acts on undo history 1, so it doesn't remove layer 5, which was added in the default history
+    let other_anim = create_animation();
+    other_anim.perform_edits(vec![AnimationEdit::AddNewLayer(5)]);
+    other_anim.perform_edits(edits);
+
+    let mut layer_ids = other_anim.get_layer_ids();
+    layer_ids.sort();
+
+    assert!(anim.get_layer_ids() == vec![2]);
+    assert!(layer_ids == vec![2, 5]);
+}
+
+#[test]
+fn edits_performed_without_undo_are_recorded_in_a_selected_history() {
+    let anim = create_animation();
+
+    anim.perform_edits_without_undo(vec![AnimationEdit::AddNewLayer(2)]);
+    anim.perform_edits_without_undo(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::AddNewLayer(3)]);
+    assert!(!anim.can_undo());
+
+    anim.perform_edits(vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::Undo]);
+    assert!(anim.get_layer_ids() == vec![2]);
+}
+
+#[test]
+fn undo_import_audio() {
+    let anim        = create_animation();
+    let first_clip  = Arc::new(AudioClip::new(8000, 1, vec![100; 800]));
+    let second_clip = Arc::new(AudioClip::new(8000, 1, vec![200; 800]));
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(2)]);
+    anim.perform_edits(vec![AnimationEdit::AddAudioClip(ElementId::Assigned(50), Arc::clone(&first_clip)), AnimationEdit::Layer(2, LayerEdit::ImportAudio(ElementId::Assigned(50)))]);
+    anim.perform_edits(vec![AnimationEdit::AddAudioClip(ElementId::Assigned(51), Arc::clone(&second_clip)), AnimationEdit::Layer(2, LayerEdit::ImportAudio(ElementId::Assigned(51)))]);
+    assert!(anim.get_layer_with_id(2).unwrap().audio_clip() == Some(Arc::clone(&second_clip)));
+
+    anim.undo();
+    assert!(anim.get_layer_with_id(2).unwrap().audio_clip() == Some(Arc::clone(&first_clip)));
+
+    anim.undo();
+    assert!(anim.get_layer_with_id(2).unwrap().audio_clip().is_none());
+
+    // The clips are still stored, so they can be attached again when the edits are redone
+    anim.redo();
+    anim.redo();
+    assert!(anim.get_layer_with_id(2).unwrap().audio_clip() == Some(Arc::clone(&second_clip)));
+}
+
+#[test]
+fn audio_data_is_not_copied_into_the_undo_history() {
+    let anim        = create_animation();
+    let clip        = Arc::new(AudioClip::new(8000, 1, vec![100; 8000]));
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(2)]);
+    anim.perform_edits(vec![AnimationEdit::AddAudioClip(ElementId::Assigned(50), Arc::clone(&clip)), AnimationEdit::Layer(2, LayerEdit::ImportAudio(ElementId::Assigned(50)))]);
+    anim.perform_edits(vec![AnimationEdit::Layer(2, LayerEdit::RemoveAudio)]);
+    anim.undo();
+    anim.undo();
+    anim.redo();
+
+    // The clip only appears in the edit log once, where it was added: undoing and redoing only changes the clip that's attached to the layer
+    let edit_log        = anim.read_edit_log(0..anim.get_num_edits());
+    let edit_log        = edit_log.collect();
+    let edits: Vec<_>   = executor::block_on(edit_log);
+
+    let num_clips       = edits.iter().filter(|edit| match edit { AnimationEdit::AddAudioClip(_, _) => true, _ => false }).count();
+    let num_undo_redo   = edits.iter().filter(|edit| match edit { AnimationEdit::Undo | AnimationEdit::Redo => true, _ => false }).count();
+
+    assert!(num_clips == 1);
+    assert!(num_undo_redo == 3);
+    assert!(anim.get_layer_with_id(2).unwrap().audio_clip() == Some(Arc::clone(&clip)));
+}
@@ -200,12 +200,17 @@ mod test {
             fn get_num_edits(&self) -> usize { unimplemented!() }
             fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> BoxStream<'a, AnimationEdit> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a dyn AnimationMotion { self }
+            fn get_annotations(&self) -> Vec<(ElementId, Annotation)> { unimplemented!() }
         }
 
         impl EditableAnimation for TestAnimation {
             fn edit(&self) -> Publisher<Arc<Vec<AnimationEdit>>> { unimplemented!() }
             fn perform_edits(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn perform_edits_without_undo(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
             fn flush_caches(&self) { unimplemented!() }
+            fn can_undo_in(&self, _undo_history: u64) -> bool { unimplemented!() }
+            fn can_redo_in(&self, _undo_history: u64) -> bool { unimplemented!() }
+            fn set_element_id_range(&self, _range: Range<i64>) { unimplemented!() }
 
             fn assign_element_id(&self) -> ElementId {
                 ElementId::Assigned(42)
@@ -261,12 +266,17 @@ mod test {
             fn get_num_edits(&self) -> usize { unimplemented!() }
             fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> BoxStream<'a, AnimationEdit> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a dyn AnimationMotion { self }
+            fn get_annotations(&self) -> Vec<(ElementId, Annotation)> { unimplemented!() }
         }
 
         impl EditableAnimation for TestAnimation {
             fn edit(&self) -> Publisher<Arc<Vec<AnimationEdit>>> { unimplemented!() }
             fn perform_edits(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn perform_edits_without_undo(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
             fn flush_caches(&self) { unimplemented!() }
+            fn can_undo_in(&self, _undo_history: u64) -> bool { unimplemented!() }
+            fn can_redo_in(&self, _undo_history: u64) -> bool { unimplemented!() }
+            fn set_element_id_range(&self, _range: Range<i64>) { unimplemented!() }
 
             fn assign_element_id(&self) -> ElementId {
                 ElementId::Assigned(43)
@@ -1,6 +1,7 @@
 use super::edit::*;
 use super::layer::*;
 use super::animation_motion::*;
+use super::annotation::*;
 
 use flo_stream::*;
 
@@ -53,6 +54,11 @@ pub trait Animation : Send+Sync {
     /// Supplies a reference which can be used to find the motions associated with this animation
     ///
     fn motion<'a>(&'a self) -> &'a dyn AnimationMotion;
+
+    ///
+    /// Retrieves the review annotations that have been left on this animation, along with their IDs
+    ///
+    fn get_annotations(&self) -> Vec<(ElementId, Annotation)>;
 }
 
 ///
@@ -66,6 +72,14 @@ pub trait EditableAnimation : Animation+Send+Sync {
     ///
     fn assign_element_id(&self) -> ElementId;
 
+    ///
+    /// Restricts the element IDs assigned by this animation to the specified range
+    ///
+    /// When an animation is being edited in more than one place at once (for example, by several clients of a sync server),
+    /// giving each editor its own range of IDs stops them from assigning the same ID to different elements.
+    ///
+    fn set_element_id_range(&self, range: Range<i64>);
+
     ///
     /// Retrieves a sink that can be used to send edits for this animation
     ///
@@ -81,8 +95,55 @@ pub trait EditableAnimation : Animation+Send+Sync {
     ///
     fn perform_edits(&self, edits: Vec<AnimationEdit>);
 
+    ///
+    /// Performs a set of edits on this animation without adding them to the undo history
+    ///
+    /// This is used for edits that were made somewhere else (for example, by another client of a sync server), which
+    /// shouldn't be undone by an undo made here. Edits that follow a `SelectUndoHistory` are still recorded in the undo
+    /// history that it selects.
+    ///
+    fn perform_edits_without_undo(&self, edits: Vec<AnimationEdit>);
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
     fn flush_caches(&self);
+
+    ///
+    /// Returns true if there are edits in the specified undo history that can be undone
+    ///
+    fn can_undo_in(&self, undo_history: u64) -> bool;
+
+    ///
+    /// Returns true if there are undone edits in the specified undo history that can be redone
+    ///
+    fn can_redo_in(&self, undo_history: u64) -> bool;
+
+    ///
+    /// Returns true if there are edits that can be undone
+    ///
+    fn can_undo(&self) -> bool {
+        self.can_undo_in(0)
+    }
+
+    ///
+    /// Returns true if there are undone edits that can be redone
+    ///
+    fn can_redo(&self) -> bool {
+        self.can_redo_in(0)
+    }
+
+    ///
+    /// Reverses the most recent set of edits performed on this animation
+    ///
+    fn undo(&self) {
+        self.perform_edits(vec![AnimationEdit::Undo]);
+    }
+
+    ///
+    /// Re-applies the most recently undone set of edits
+    ///
+    fn redo(&self) {
+        self.perform_edits(vec![AnimationEdit::Redo]);
+    }
 }
@@ -0,0 +1,45 @@
+use std::time::{Duration};
+
+///
+/// A review comment pinned to a particular frame of a layer
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Annotation {
+    /// The layer that this annotation is attached to
+    pub layer_id: u64,
+
+    /// The time of the frame that this annotation is attached to
+    pub when: Duration,
+
+    /// The name of the person who left the annotation
+    pub author: String,
+
+    /// When the annotation was written, in seconds since the unix epoch
+    pub timestamp: u64,
+
+    /// The position on the canvas that the annotation refers to, if it's about a specific part of the frame
+    pub position: Option<(f32, f32)>,
+
+    /// The text of the annotation
+    pub text: String,
+
+    /// True if the annotation has been dealt with
+    pub resolved: bool
+}
+
+impl Annotation {
+    ///
+    /// Creates a new, unresolved annotation
+    ///
+    pub fn new(layer_id: u64, when: Duration, author: &str, timestamp: u64, position: Option<(f32, f32)>, text: &str) -> Annotation {
+        Annotation {
+            layer_id:   layer_id,
+            when:       when,
+            author:     author.to_string(),
+            timestamp:  timestamp,
+            position:   position,
+            text:       text.to_string(),
+            resolved:   false
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod annotation;
+
+pub use self::annotation::*;
@@ -0,0 +1,387 @@
+use flo_canvas::*;
+
+use std::fmt;
+use std::sync::*;
+use std::ops::{Range};
+use std::time::{Duration};
+
+///
+/// Errors that can occur while reading an audio file
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AudioError {
+    /// The data is not a RIFF WAVE file
+    NotAWavFile,
+
+    /// The file is missing its format or its data chunk
+    MissingChunk,
+
+    /// The samples in the file are stored in an encoding that can't be read (parameters are the format tag and the bits per sample)
+    UnsupportedEncoding(u16, u16)
+}
+
+///
+/// A clip of audio that can be attached to a layer
+///
+/// Samples are stored as signed 16-bit values, interleaved by channel.
+///
+#[derive(Clone, PartialEq)]
+pub struct AudioClip {
+    /// The number of samples per second for each channel
+    sample_rate: u32,
+
+    /// The number of channels in this clip
+    channels: u16,
+
+    /// The samples in this clip
+    samples: Arc<Vec<i16>>
+}
+
+impl fmt::Debug for AudioClip {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The samples are left out as there are usually far too many of them to usefully display
+        write!(f, "AudioClip({}Hz, {} channels, {} samples)", self.sample_rate, self.channels, self.samples.len())
+    }
+}
+
+///
+/// Reads a little-endian u16 value from a byte slice
+///
+#[inline]
+fn read_u16(data: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([data[pos], data[pos+1]])
+}
+
+///
+/// Reads a little-endian u32 value from a byte slice
+///
+#[inline]
+fn read_u32(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([data[pos], data[pos+1], data[pos+2], data[pos+3]])
+}
+
+impl AudioClip {
+    ///
+    /// Creates a new audio clip from a set of interleaved samples
+    ///
+    pub fn new(sample_rate: u32, channels: u16, samples: Vec<i16>) -> AudioClip {
+        AudioClip {
+            sample_rate:    sample_rate,
+            channels:       channels,
+            samples:        Arc::new(samples)
+        }
+    }
+
+    ///
+    /// Reads an audio clip from the contents of a WAV file
+    ///
+    /// PCM files with 8, 16, 24 or 32 bits per sample and 32-bit floating point files are supported.
+    ///
+    pub fn from_wav(wav: &[u8]) -> Result<AudioClip, AudioError> {
+        // Check the header
+        if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+            return Err(AudioError::NotAWavFile);
+        }
+
+        // Read the chunks from the file
+        let mut format  = None;
+        let mut data    = None;
+        let mut pos     = 12;
+
+        while pos + 8 <= wav.len() {
+            let chunk_id    = &wav[pos..(pos+4)];
+            let chunk_len   = read_u32(wav, pos+4) as usize;
+            let start       = pos + 8;
+            let end         = start.saturating_add(chunk_len).min(wav.len());
+
+            match chunk_id {
+                b"fmt " => {
+                    if end - start < 16 { return Err(AudioError::NotAWavFile); }
+
+                    let mut tag     = read_u16(wav, start);
+                    let channels    = read_u16(wav, start+2);
+                    let sample_rate = read_u32(wav, start+4);
+                    let bits        = read_u16(wav, start+14);
+
+                    // WAVE_FORMAT_EXTENSIBLE files store the real format at the start of the sub-format GUID
+                    if tag == 0xfffe && end - start >= 26 {
+                        tag = read_u16(wav, start+24);
+                    }
+
+                    format = Some((tag, channels, sample_rate, bits));
+                }
+
+                b"data" => {
+                    data = Some(&wav[start..end]);
+                }
+
+                _ => { }
+            }
+
+            // Chunks are padded to an even length
+            pos = start.saturating_add(chunk_len).saturating_add(chunk_len & 1);
+        }
+
+        let (tag, channels, sample_rate, bits)  = format.ok_or(AudioError::MissingChunk)?;
+        let data                                = data.ok_or(AudioError::MissingChunk)?;
+
+        if channels == 0 || sample_rate == 0 {
+            return Err(AudioError::UnsupportedEncoding(tag, bits));
+        }
+
+        // Convert the samples to 16-bit
+        let mut samples: Vec<i16> = match (tag, bits) {
+            (1, 8)  => data.iter().map(|sample| ((*sample as i16) - 128) << 8).collect(),
+            (1, 16) => data.chunks_exact(2).map(|sample| i16::from_le_bytes([sample[0], sample[1]])).collect(),
+            (1, 24) => data.chunks_exact(3).map(|sample| i16::from_le_bytes([sample[1], sample[2]])).collect(),
+            (1, 32) => data.chunks_exact(4).map(|sample| i16::from_le_bytes([sample[2], sample[3]])).collect(),
+            (3, 32) => data.chunks_exact(4).map(|sample| (f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]).max(-1.0).min(1.0) * 32767.0) as i16).collect(),
+
+            _       => { return Err(AudioError::UnsupportedEncoding(tag, bits)); }
+        };
+
+        // Discard any partial sample at the end of the file
+        let num_frames = samples.len() / (channels as usize);
+        samples.truncate(num_frames * (channels as usize));
+
+        Ok(AudioClip::new(sample_rate, channels, samples))
+    }
+
+    ///
+    /// The number of samples per second for each channel in this clip
+    ///
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    ///
+    /// The number of channels in this clip
+    ///
+    #[inline]
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    ///
+    /// The samples in this clip, interleaved by channel
+    ///
+    #[inline]
+    pub fn samples(&self) -> &[i16] {
+        &self.samples
+    }
+
+    ///
+    /// The number of samples for each channel in this clip
+    ///
+    pub fn samples_per_channel(&self) -> usize {
+        if self.channels == 0 {
+            0
+        } else {
+            self.samples.len() / (self.channels as usize)
+        }
+    }
+
+    ///
+    /// The length of time that this clip plays for
+    ///
+    pub fn duration(&self) -> Duration {
+        if self.sample_rate == 0 {
+            Duration::from_millis(0)
+        } else {
+            Duration::from_nanos(((self.samples_per_channel() as u128) * 1_000_000_000 / (self.sample_rate as u128)) as u64)
+        }
+    }
+
+    ///
+    /// Finds the index of the sample (per channel) that plays at a particular time
+    ///
+    fn sample_index(&self, when: Duration) -> usize {
+        let index = when.as_nanos() * (self.sample_rate as u128) / 1_000_000_000;
+
+        (index as usize).min(self.samples_per_channel())
+    }
+
+    ///
+    /// Returns the peak amplitude of this clip during a particular time range, from 0.0 (silent) to 1.0 (maximum volume)
+    ///
+    pub fn peak_amplitude(&self, when: Range<Duration>) -> f32 {
+        let channels    = self.channels as usize;
+        let start       = self.sample_index(when.start) * channels;
+        let end         = self.sample_index(when.end) * channels;
+
+        if start >= end {
+            0.0
+        } else {
+            let peak = self.samples[start..end].iter()
+                .map(|sample| (*sample as i32).abs())
+                .max()
+                .unwrap_or(0);
+
+            ((peak as f32) / 32768.0).min(1.0)
+        }
+    }
+
+    ///
+    /// Returns the peak amplitude during a single frame of the animation
+    ///
+    pub fn frame_amplitude(&self, frame_length: Duration, frame: u32) -> f32 {
+        let start   = frame_length * frame;
+        let end     = start + frame_length;
+
+        self.peak_amplitude(start..end)
+    }
+
+    ///
+    /// Returns the peak amplitude of every frame that this clip plays during, for the specified frame length
+    ///
+    pub fn frame_amplitudes(&self, frame_length: Duration) -> Vec<f32> {
+        if frame_length == Duration::from_millis(0) {
+            return vec![];
+        }
+
+        let num_frames = (self.duration().as_nanos() + frame_length.as_nanos() - 1) / frame_length.as_nanos();
+
+        (0..(num_frames as u32))
+            .map(|frame| self.frame_amplitude(frame_length, frame))
+            .collect()
+    }
+
+    ///
+    /// Generates the drawing instructions for the waveform of this clip over a range of frames
+    ///
+    /// Each frame is drawn as a bar centered vertically in a cell of `frame_size`, whose height is proportional to the
+    /// amplitude during that frame. `origin` is the top-left corner of the cell for frame 0. The waveform is filled
+    /// using the current fill colour.
+    ///
+    pub fn waveform(&self, frame_length: Duration, frames: Range<u32>, origin: (f32, f32), frame_size: (f32, f32)) -> Vec<Draw> {
+        let (x, y)              = origin;
+        let (width, height)     = frame_size;
+        let center_y            = y + height/2.0;
+
+        let mut drawing         = vec![Draw::NewPath];
+
+        for frame in frames {
+            let amplitude   = self.frame_amplitude(frame_length, frame);
+            if amplitude <= 0.0 { continue; }
+
+            let xpos        = x + (frame as f32) * width;
+            let half_height = (amplitude * height / 2.0).max(0.5);
+
+            drawing.extend(draw_rect(xpos, center_y - half_height, xpos + width, center_y + half_height));
+        }
+
+        if drawing.len() <= 1 {
+            // Nothing to draw
+            vec![]
+        } else {
+            drawing.push(Draw::Fill);
+            drawing
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    ///
+    /// Creates the bytes of a PCM WAV file
+    ///
+    fn wav_file(sample_rate: u32, channels: u16, bits: u16, data: &[u8]) -> Vec<u8> {
+        let block_align = channels * (bits/8);
+        let mut wav     = vec![];
+
+        wav.extend(b"RIFF");
+        wav.extend(&(36 + data.len() as u32).to_le_bytes());
+        wav.extend(b"WAVE");
+
+        wav.extend(b"fmt ");
+        wav.extend(&16u32.to_le_bytes());
+        wav.extend(&1u16.to_le_bytes());
+        wav.extend(&channels.to_le_bytes());
+        wav.extend(&sample_rate.to_le_bytes());
+        wav.extend(&(sample_rate * (block_align as u32)).to_le_bytes());
+        wav.extend(&block_align.to_le_bytes());
+        wav.extend(&bits.to_le_bytes());
+
+        wav.extend(b"data");
+        wav.extend(&(data.len() as u32).to_le_bytes());
+        wav.extend(data);
+
+        wav
+    }
+
+    fn samples_16_bit(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|sample| sample.to_le_bytes().to_vec()).collect()
+    }
+
+    #[test]
+    fn read_16_bit_mono() {
+        let wav     = wav_file(8000, 1, 16, &samples_16_bit(&[0, 100, -100, 32767]));
+        let clip    = AudioClip::from_wav(&wav).unwrap();
+
+        assert!(clip.sample_rate() == 8000);
+        assert!(clip.channels() == 1);
+        assert!(clip.samples() == &[0, 100, -100, 32767]);
+    }
+
+    #[test]
+    fn read_8_bit_stereo() {
+        let wav     = wav_file(8000, 2, 8, &[128, 0, 255, 128]);
+        let clip    = AudioClip::from_wav(&wav).unwrap();
+
+        assert!(clip.channels() == 2);
+        assert!(clip.samples_per_channel() == 2);
+        assert!(clip.samples() == &[0, -32768, 127<<8, 0]);
+    }
+
+    #[test]
+    fn reject_files_that_are_not_wav() {
+        assert!(AudioClip::from_wav(b"not a wav file") == Err(AudioError::NotAWavFile));
+    }
+
+    #[test]
+    fn reject_unsupported_encoding() {
+        let mut wav = wav_file(8000, 1, 16, &samples_16_bit(&[0, 0]));
+        wav[20]     = 2;
+
+        assert!(AudioClip::from_wav(&wav) == Err(AudioError::UnsupportedEncoding(2, 16)));
+    }
+
+    #[test]
+    fn duration_of_clip() {
+        let clip = AudioClip::new(1000, 2, vec![0; 3000]);
+
+        assert!(clip.duration() == Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn amplitude_per_frame() {
+        // 1 second of audio at 100Hz, with a loud section between 0.5 and 0.6 seconds
+        let samples = (0..100).map(|index| if index >= 50 && index < 60 { 16384 } else { 0 }).collect();
+        let clip    = AudioClip::new(100, 1, samples);
+
+        let amplitudes = clip.frame_amplitudes(Duration::from_millis(100));
+
+        assert!(amplitudes.len() == 10);
+        assert!(amplitudes[4] == 0.0);
+        assert!((amplitudes[5] - 0.5).abs() < 0.001);
+        assert!(amplitudes[6] == 0.0);
+    }
+
+    #[test]
+    fn waveform_only_draws_loud_frames() {
+        let samples = (0..100).map(|index| if index >= 50 && index < 60 { 16384 } else { 0 }).collect();
+        let clip    = AudioClip::new(100, 1, samples);
+
+        let waveform = clip.waveform(Duration::from_millis(100), 0..10, (0.0, 0.0), (10.0, 20.0));
+
+        assert!(waveform[0] == Draw::NewPath);
+        assert!(waveform[1] == Draw::Move(50.0, 5.0));
+        assert!(waveform.last() == Some(&Draw::Fill));
+        assert!(waveform.len() == 1 + 6 + 1);
+
+        assert!(clip.waveform(Duration::from_millis(100), 0..5, (0.0, 0.0), (10.0, 20.0)).len() == 0);
+    }
+}
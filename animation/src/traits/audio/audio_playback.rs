@@ -0,0 +1,90 @@
+use super::audio_clip::*;
+
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// Trait implemented by objects that can play back audio clips
+///
+/// FlowBetween doesn't depend on any particular audio API: a user interface can supply an implementation of this
+/// trait that plays audio using whatever the platform provides.
+///
+pub trait AudioPlayback : Send+Sync {
+    ///
+    /// Starts playing a clip from the specified time (replacing any clip that is already playing)
+    ///
+    fn play(&self, clip: Arc<AudioClip>, from: Duration);
+
+    ///
+    /// Stops any clip that is currently playing
+    ///
+    fn stop(&self);
+
+    ///
+    /// Returns the position within the clip that is currently playing, or None if nothing is playing
+    ///
+    fn position(&self) -> Option<Duration>;
+}
+
+///
+/// Audio playback implementation that doesn't produce any sound
+///
+/// The position of a clip doesn't advance while it's 'playing', which makes this useful for tests and headless sessions.
+///
+pub struct NullAudioPlayback {
+    /// The clip that is 'playing' and the time it was started from
+    playing: Mutex<Option<(Arc<AudioClip>, Duration)>>
+}
+
+impl NullAudioPlayback {
+    ///
+    /// Creates a new null audio playback object
+    ///
+    pub fn new() -> NullAudioPlayback {
+        NullAudioPlayback {
+            playing: Mutex::new(None)
+        }
+    }
+
+    ///
+    /// Returns the clip that is currently 'playing'
+    ///
+    pub fn playing_clip(&self) -> Option<Arc<AudioClip>> {
+        self.playing.lock().unwrap().as_ref().map(|(clip, _)| Arc::clone(clip))
+    }
+}
+
+impl AudioPlayback for NullAudioPlayback {
+    fn play(&self, clip: Arc<AudioClip>, from: Duration) {
+        *self.playing.lock().unwrap() = Some((clip, from));
+    }
+
+    fn stop(&self) {
+        *self.playing.lock().unwrap() = None;
+    }
+
+    fn position(&self) -> Option<Duration> {
+        self.playing.lock().unwrap().as_ref().map(|(_, from)| *from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn null_playback_tracks_playing_clip() {
+        let playback    = NullAudioPlayback::new();
+        let clip        = Arc::new(AudioClip::new(8000, 1, vec![0; 8000]));
+
+        assert!(playback.position() == None);
+
+        playback.play(Arc::clone(&clip), Duration::from_millis(250));
+        assert!(playback.position() == Some(Duration::from_millis(250)));
+        assert!(playback.playing_clip() == Some(clip));
+
+        playback.stop();
+        assert!(playback.position() == None);
+        assert!(playback.playing_clip() == None);
+    }
+}
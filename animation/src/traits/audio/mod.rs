@@ -0,0 +1,5 @@
+mod audio_clip;
+mod audio_playback;
+
+pub use self::audio_clip::*;
+pub use self::audio_playback::*;
@@ -27,7 +27,13 @@ pub struct BrushPoint {
     pub cp2: (f32, f32),
 
     /// Width of this segment
-    pub width: f32
+    pub width: f32,
+
+    /// Opacity of this segment (as a proportion of the opacity of the brush)
+    pub opacity: f32,
+
+    /// How much of the brush's secondary colour is mixed into this segment
+    pub color_mix: f32
 }
 
 ///
@@ -53,9 +59,12 @@ pub trait Brush : Send+Sync {
     ///
     /// Renders a path using this brush's style
     ///
-    fn render_path<'a>(&'a self, _properties: &'a BrushProperties, path: &'a Path) -> Box<dyn 'a+Iterator<Item=Draw>> {
+    fn render_path<'a>(&'a self, properties: &'a BrushProperties, path: &'a Path) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        let fill = properties.fill.fill_drawing(properties.color, properties.opacity, Rect::from(path));
+
         Box::new(iter::once(Draw::NewPath)
             .chain(path.to_drawing())
+            .chain(fill.into_iter())
             .chain(iter::once(Draw::Fill)))
     }
 
@@ -0,0 +1,365 @@
+use super::super::raw_point::*;
+
+use flo_canvas::*;
+
+///
+/// An input from the drawing device that can drive the dynamics of a brush stroke
+///
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Debug)]
+pub enum DynamicsInput {
+    /// The pressure applied to the stylus
+    Pressure,
+
+    /// How far the stylus is tilted away from vertical
+    Tilt,
+
+    /// How fast the stylus is moving
+    Speed
+}
+
+///
+/// The values of the dynamics inputs at a point on a brush stroke (all in the range 0.0 to 1.0)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DynamicsInputs {
+    /// The pressure (0.0 for no pressure, 1.0 for full pressure)
+    pub pressure: f32,
+
+    /// The tilt (0.0 for a vertical stylus, 1.0 for a stylus lying flat)
+    pub tilt: f32,
+
+    /// The speed (0.0 for stationary, 1.0 for moving at the brush's full speed or faster)
+    pub speed: f32
+}
+
+///
+/// A response curve maps an input value to an output value (both in the range 0.0 to 1.0)
+///
+/// The curve is made up of straight lines between a set of points, ordered by input value. Inputs before the
+/// first point or after the last point use the output of the nearest point.
+///
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct ResponseCurve {
+    /// The (input, output) points that make up this curve
+    pub points: Vec<(f32, f32)>
+}
+
+///
+/// Describes how one input drives a property of a brush stroke
+///
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct DynamicsResponse {
+    /// The input that drives the property
+    pub input: DynamicsInput,
+
+    /// How the input maps to the value of the property
+    pub curve: ResponseCurve
+}
+
+///
+/// Describes how the inputs from a drawing device change the width, opacity and colour of a brush stroke
+///
+/// Each property can be driven by any number of inputs: their responses are multiplied together. A property with
+/// no responses has a fixed value: the full width or opacity, or the brush colour.
+///
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct BrushDynamics {
+    /// Responses that drive the width of the stroke (0.0 is the minimum width and 1.0 is the maximum width)
+    pub width: Vec<DynamicsResponse>,
+
+    /// Responses that drive the opacity of the stroke (as a proportion of the opacity of the brush)
+    pub opacity: Vec<DynamicsResponse>,
+
+    /// Responses that drive the colour of the stroke (0.0 is the brush colour and 1.0 is the secondary colour)
+    pub color: Vec<DynamicsResponse>,
+
+    /// The colour that the colour responses mix the brush colour towards
+    pub secondary_color: Color,
+
+    /// The distance moved between two input points that counts as full speed
+    pub full_speed: f32
+}
+
+impl ResponseCurve {
+    ///
+    /// Creates a response curve from a set of points
+    ///
+    pub fn from_points(points: Vec<(f32, f32)>) -> ResponseCurve {
+        let mut points = points;
+        points.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        ResponseCurve { points }
+    }
+
+    ///
+    /// A curve where the output is the same as the input
+    ///
+    pub fn linear() -> ResponseCurve {
+        ResponseCurve::from_points(vec![(0.0, 0.0), (1.0, 1.0)])
+    }
+
+    ///
+    /// A curve where the output rises quickly for small inputs
+    ///
+    pub fn soft() -> ResponseCurve {
+        ResponseCurve::from_points(vec![(0.0, 0.0), (0.25, 0.5), (1.0, 1.0)])
+    }
+
+    ///
+    /// A curve where the output rises slowly for small inputs
+    ///
+    pub fn firm() -> ResponseCurve {
+        ResponseCurve::from_points(vec![(0.0, 0.0), (0.75, 0.5), (1.0, 1.0)])
+    }
+
+    ///
+    /// A curve where the output falls as the input rises
+    ///
+    pub fn inverted() -> ResponseCurve {
+        ResponseCurve::from_points(vec![(0.0, 1.0), (1.0, 0.0)])
+    }
+
+    ///
+    /// Returns the output of this curve for a particular input
+    ///
+    pub fn response(&self, input: f32) -> f32 {
+        let points = &self.points;
+
+        // Curves with no points pass the input through unchanged
+        if points.len() == 0 {
+            return input;
+        }
+
+        // Inputs outside of the curve use the nearest point
+        let (first_in, first_out)   = points[0];
+        let (last_in, last_out)     = points[points.len()-1];
+
+        if input <= first_in    { return first_out; }
+        if input >= last_in     { return last_out; }
+
+        // Interpolate between the two points on either side of the input
+        for idx in 1..points.len() {
+            let (in1, out1) = points[idx-1];
+            let (in2, out2) = points[idx];
+
+            if input >= in1 && input <= in2 {
+                if in2 <= in1 {
+                    return out2;
+                }
+
+                let t = (input - in1) / (in2 - in1);
+                return out1 + (out2 - out1)*t;
+            }
+        }
+
+        last_out
+    }
+}
+
+impl DynamicsResponse {
+    ///
+    /// Creates a new dynamics response
+    ///
+    pub fn new(input: DynamicsInput, curve: ResponseCurve) -> DynamicsResponse {
+        DynamicsResponse { input, curve }
+    }
+
+    ///
+    /// Returns the value of the property driven by this response for a set of inputs
+    ///
+    pub fn response(&self, inputs: &DynamicsInputs) -> f32 {
+        let input = match self.input {
+            DynamicsInput::Pressure => inputs.pressure,
+            DynamicsInput::Tilt     => inputs.tilt,
+            DynamicsInput::Speed    => inputs.speed
+        };
+
+        self.curve.response(input).max(0.0).min(1.0)
+    }
+}
+
+impl BrushDynamics {
+    ///
+    /// Creates the default brush dynamics (where the pressure controls the width of the stroke)
+    ///
+    pub fn default() -> BrushDynamics {
+        BrushDynamics {
+            width:              vec![DynamicsResponse::new(DynamicsInput::Pressure, ResponseCurve::linear())],
+            opacity:            vec![],
+            color:              vec![],
+            secondary_color:    Color::Rgba(1.0, 1.0, 1.0, 1.0),
+            full_speed:         20.0
+        }
+    }
+
+    ///
+    /// Multiplies together a set of responses, returning the default value if there are none
+    ///
+    fn combined_response(responses: &Vec<DynamicsResponse>, inputs: &DynamicsInputs, default: f32) -> f32 {
+        if responses.len() == 0 {
+            default
+        } else {
+            responses.iter().fold(1.0, |value, response| value * response.response(inputs))
+        }
+    }
+
+    ///
+    /// Returns the width for a set of inputs (0.0 for the minimum width, 1.0 for the maximum width)
+    ///
+    pub fn width_for(&self, inputs: &DynamicsInputs) -> f32 {
+        Self::combined_response(&self.width, inputs, 1.0)
+    }
+
+    ///
+    /// Returns the opacity for a set of inputs (as a proportion of the brush opacity)
+    ///
+    pub fn opacity_for(&self, inputs: &DynamicsInputs) -> f32 {
+        Self::combined_response(&self.opacity, inputs, 1.0)
+    }
+
+    ///
+    /// Returns how much of the secondary colour to mix into the brush colour for a set of inputs
+    ///
+    pub fn color_mix_for(&self, inputs: &DynamicsInputs) -> f32 {
+        Self::combined_response(&self.color, inputs, 0.0)
+    }
+
+    ///
+    /// Returns the colour to use for a particular amount of the secondary colour
+    ///
+    pub fn mix_color(&self, color: Color, color_mix: f32) -> Color {
+        if color_mix <= 0.0 {
+            color
+        } else {
+            let (r1, g1, b1, a1)    = color.to_rgba_components();
+            let (r2, g2, b2, _)     = self.secondary_color.to_rgba_components();
+            let t                   = color_mix.min(1.0);
+
+            Color::Rgba(r1 + (r2-r1)*t, g1 + (g2-g1)*t, b1 + (b2-b1)*t, a1)
+        }
+    }
+
+    ///
+    /// Works out the dynamics inputs for each of a set of raw points
+    ///
+    /// Input devices report points at a steady rate, so the speed is measured as the distance moved since the previous point.
+    ///
+    pub fn inputs_for_raw_points(&self, points: &[RawPoint]) -> Vec<DynamicsInputs> {
+        let full_speed = if self.full_speed > 0.0 { self.full_speed } else { 1.0 };
+
+        points.iter()
+            .enumerate()
+            .map(|(idx, point)| {
+                // Tilt is measured in degrees in the X and Y directions
+                let (tilt_x, tilt_y)    = point.tilt;
+                let tilt                = (tilt_x*tilt_x + tilt_y*tilt_y).sqrt() / 90.0;
+
+                // Speed is the distance from the previous point (or the next point for the first point)
+                let neighbour           = if idx > 0 { points.get(idx-1) } else { points.get(idx+1) };
+                let speed               = neighbour.map(|neighbour| {
+                    let (dx, dy) = (point.position.0-neighbour.position.0, point.position.1-neighbour.position.1);
+                    (dx*dx + dy*dy).sqrt() / full_speed
+                }).unwrap_or(0.0);
+
+                DynamicsInputs {
+                    pressure:   point.pressure.max(0.0).min(1.0),
+                    tilt:       tilt.max(0.0).min(1.0),
+                    speed:      speed.max(0.0).min(1.0)
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// True if these dynamics can change the opacity or the colour of a brush stroke
+    ///
+    pub fn changes_color(&self) -> bool {
+        self.opacity.len() > 0 || self.color.len() > 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn linear_curve_passes_through_input() {
+        let curve = ResponseCurve::linear();
+
+        assert!((curve.response(0.0) - 0.0).abs() < 0.001);
+        assert!((curve.response(0.3) - 0.3).abs() < 0.001);
+        assert!((curve.response(1.0) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn curve_interpolates_between_points() {
+        let curve = ResponseCurve::soft();
+
+        assert!((curve.response(0.125) - 0.25).abs() < 0.001);
+        assert!((curve.response(0.25) - 0.5).abs() < 0.001);
+        assert!((curve.response(0.625) - 0.75).abs() < 0.001);
+    }
+
+    #[test]
+    fn curve_clamps_outside_points() {
+        let curve = ResponseCurve::from_points(vec![(0.75, 0.5), (0.25, 0.2)]);
+
+        assert!((curve.response(0.0) - 0.2).abs() < 0.001);
+        assert!((curve.response(1.0) - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn default_dynamics_follow_pressure() {
+        let dynamics    = BrushDynamics::default();
+        let inputs      = DynamicsInputs { pressure: 0.4, tilt: 0.9, speed: 0.9 };
+
+        assert!((dynamics.width_for(&inputs) - 0.4).abs() < 0.001);
+        assert!((dynamics.opacity_for(&inputs) - 1.0).abs() < 0.001);
+        assert!((dynamics.color_mix_for(&inputs) - 0.0).abs() < 0.001);
+        assert!(!dynamics.changes_color());
+    }
+
+    #[test]
+    fn responses_multiply_together() {
+        let mut dynamics    = BrushDynamics::default();
+        dynamics.opacity    = vec![DynamicsResponse::new(DynamicsInput::Tilt, ResponseCurve::linear()), DynamicsResponse::new(DynamicsInput::Speed, ResponseCurve::inverted())];
+
+        let inputs          = DynamicsInputs { pressure: 1.0, tilt: 0.5, speed: 0.25 };
+
+        assert!((dynamics.opacity_for(&inputs) - 0.375).abs() < 0.001);
+        assert!(dynamics.changes_color());
+    }
+
+    #[test]
+    fn inputs_from_raw_points() {
+        let mut dynamics    = BrushDynamics::default();
+        dynamics.full_speed = 10.0;
+
+        let inputs          = dynamics.inputs_for_raw_points(&vec![
+            RawPoint { position: (0.0, 0.0), pressure: 0.5, tilt: (0.0, 0.0) },
+            RawPoint { position: (3.0, 4.0), pressure: 0.6, tilt: (45.0, 0.0) },
+            RawPoint { position: (30.0, 4.0), pressure: 1.5, tilt: (90.0, 90.0) }
+        ]);
+
+        assert!(inputs.len() == 3);
+        assert!((inputs[0].speed - 0.5).abs() < 0.001);
+        assert!((inputs[1].speed - 0.5).abs() < 0.001);
+        assert!((inputs[1].tilt - 0.5).abs() < 0.001);
+        assert!((inputs[2].speed - 1.0).abs() < 0.001);
+        assert!((inputs[2].tilt - 1.0).abs() < 0.001);
+        assert!((inputs[2].pressure - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn mix_towards_secondary_color() {
+        let mut dynamics            = BrushDynamics::default();
+        dynamics.secondary_color    = Color::Rgba(1.0, 0.0, 0.0, 1.0);
+
+        let (r, g, b, a)            = dynamics.mix_color(Color::Rgba(0.0, 0.0, 1.0, 0.5), 0.5).to_rgba_components();
+
+        assert!((r - 0.5).abs() < 0.001);
+        assert!((g - 0.0).abs() < 0.001);
+        assert!((b - 0.5).abs() < 0.001);
+        assert!((a - 0.5).abs() < 0.001);
+    }
+}
@@ -1,3 +1,4 @@
+use super::dynamics::*;
 
 ///
 /// Ink brushes are solid lines of varying width. This defines how they behave.
@@ -12,7 +13,10 @@ pub struct InkDefinition {
     pub max_width: f32,
 
     // Distance to scale up at the start of the brush stroke
-    pub scale_up_distance: f32
+    pub scale_up_distance: f32,
+
+    /// How the pressure, tilt and speed of the stylus affect the stroke
+    pub dynamics: BrushDynamics
 }
 
 impl InkDefinition {
@@ -23,7 +27,8 @@ impl InkDefinition {
         InkDefinition {
             min_width:          0.25,
             max_width:          5.0,
-            scale_up_distance:  40.0
+            scale_up_distance:  40.0,
+            dynamics:           BrushDynamics::default()
         }
     }
 
@@ -34,7 +39,8 @@ impl InkDefinition {
         InkDefinition {
             min_width:          3.0,
             max_width:          20.0,
-            scale_up_distance:  5.0
+            scale_up_distance:  5.0,
+            dynamics:           BrushDynamics::default()
         }
     }
 }
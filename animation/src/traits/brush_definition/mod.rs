@@ -1,6 +1,8 @@
 mod ink;
+mod dynamics;
 
 pub use self::ink::*;
+pub use self::dynamics::*;
 
 ///
 /// Stores the definition of a particular brush
@@ -0,0 +1,68 @@
+use super::path::*;
+
+use flo_canvas::*;
+
+///
+/// How the interior of a path drawn with a brush is filled
+///
+/// The brush colour is used as the first colour of a gradient or the foreground colour of a texture. Gradient
+/// positions are fractions of the bounding box of the path being filled, so a gradient stretches along with its
+/// path.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BrushFill {
+    /// Fill with the brush colour
+    Solid,
+
+    /// Linear gradient from the brush colour to a second colour, between a start and an end point
+    LinearGradient(Color, (f32, f32), (f32, f32)),
+
+    /// Radial gradient from the brush colour at the center to a second colour at the radius (which is a fraction of the larger side of the bounding box)
+    RadialGradient(Color, (f32, f32), f32),
+
+    /// Repeating texture of the brush colour over a background colour, with the size of a tile in canvas units
+    Texture(TexturePattern, Color, f32)
+}
+
+impl Default for BrushFill {
+    fn default() -> BrushFill {
+        BrushFill::Solid
+    }
+}
+
+impl BrushFill {
+    ///
+    /// Returns the drawing instructions that set up this fill for a path with the specified bounds
+    ///
+    /// Solid fills generate no instructions, as they use the fill colour that's already set.
+    ///
+    pub fn fill_drawing(&self, color: Color, opacity: f32, bounds: Rect) -> Vec<Draw> {
+        let position     = |(x, y): (f32, f32)| (bounds.x1 + bounds.width()*x, bounds.y1 + bounds.height()*y);
+        let with_opacity = |color: Color| { let (_, _, _, alpha) = color.to_rgba_components(); color.with_alpha(alpha * opacity) };
+
+        // The brush colour uses the opacity as its alpha value, the same as when filling with a solid colour
+        let brush_color  = color.with_alpha(opacity);
+
+        match self {
+            BrushFill::Solid                                        => vec![],
+
+            BrushFill::LinearGradient(second_color, start, end)     => vec![
+                Draw::NewGradient,
+                Draw::GradientStop(0.0, brush_color),
+                Draw::GradientStop(1.0, with_opacity(*second_color)),
+                Draw::FillLinearGradient(position(*start), position(*end))
+            ],
+
+            BrushFill::RadialGradient(second_color, center, radius) => vec![
+                Draw::NewGradient,
+                Draw::GradientStop(0.0, brush_color),
+                Draw::GradientStop(1.0, with_opacity(*second_color)),
+                Draw::FillRadialGradient(position(*center), f32::max(bounds.width(), bounds.height()) * radius)
+            ],
+
+            BrushFill::Texture(pattern, background, size)           => vec![
+                Draw::FillTexture(*pattern, brush_color, with_opacity(*background), *size)
+            ]
+        }
+    }
+}
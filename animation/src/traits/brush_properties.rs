@@ -1,3 +1,5 @@
+use super::brush_fill::*;
+
 use flo_canvas::*;
 
 ///
@@ -15,7 +17,10 @@ pub struct BrushProperties {
     pub opacity: f32,
 
     /// The colour of the brush stroke
-    pub color: Color
+    pub color: Color,
+
+    /// How paths drawn with this brush are filled
+    pub fill: BrushFill
 }
 
 impl BrushProperties {
@@ -26,7 +31,8 @@ impl BrushProperties {
         BrushProperties {
             size:       5.0,
             opacity:    1.0,
-            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0)
+            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            fill:       BrushFill::Solid
         }
     }
 }
@@ -2,6 +2,12 @@ use super::element_id::*;
 use super::layer_edit::*;
 use super::motion_edit::*;
 use super::element_edit::*;
+use super::annotation_edit::*;
+use super::super::audio::*;
+use super::super::image::*;
+
+use std::sync::*;
+use std::time::{Duration};
 
 ///
 /// Represents an edit to an animation object
@@ -18,15 +24,51 @@ pub enum AnimationEdit {
     /// Motions have element IDs so can be treated as elements but are not attached to a layer
     Motion(ElementId, MotionEdit),
 
+    /// Edit to a review annotation (annotations have element IDs but are stored separately from the layers)
+    Annotation(ElementId, AnnotationEdit),
+
+    /// Stores a reference image so that it can be placed on keyframes with `PaintEdit::PlaceImage`
+    ///
+    /// Images have element IDs and can't be changed once they've been added
+    AddImage(ElementId, Arc<ReferenceImage>),
+
+    /// Stores an audio clip so that it can be attached to layers with `LayerEdit::ImportAudio`
+    ///
+    /// Audio clips have element IDs and can't be changed once they've been added
+    AddAudioClip(ElementId, Arc<AudioClip>),
+
     /// Sets the canvas size for this animation
     SetSize(f64, f64),
 
+    /// Sets the length of a single frame of this animation
+    SetFrameLength(Duration),
+
+    /// Sets the total length of this animation
+    SetDuration(Duration),
+
     /// Adds a new layer and assigns it the specified ID
     /// Has no effect if a layer with that ID already exists
     AddNewLayer(u64),
 
     /// Removes the layer with the specified ID
-    RemoveLayer(u64)
+    RemoveLayer(u64),
+
+    /// Reverses the most recent group of edits in the selected undo history that has not already been undone
+    ///
+    /// Each group of edits sent to the animation is undone as a single unit.
+    Undo,
+
+    /// Re-applies the most recently undone group of edits in the selected undo history
+    ///
+    /// Performing any edit other than an undo or a redo will discard any edits that are waiting to be redone
+    Redo,
+
+    /// Selects the undo history that the following edits are recorded in and that `Undo` and `Redo` act on
+    ///
+    /// An animation can keep several separate undo histories, so that (for example) each person editing it
+    /// only undoes their own edits. Every group of edits starts in undo history 0, and the edits after this
+    /// one are undone as a separate step from the edits before it.
+    SelectUndoHistory(u64)
 }
 
 impl AnimationEdit {
@@ -36,10 +78,13 @@ impl AnimationEdit {
     ///
     pub fn assign_element_id<AssignFn: FnOnce() -> i64>(self, assign_element_id: AssignFn) -> AnimationEdit {
         use self::AnimationEdit::*;
+        use self::ElementId::*;
 
         match self {
-            Layer(layer_id, layer_edit) => Layer(layer_id, layer_edit.assign_element_id(assign_element_id)),
-            other                       => other
+            Layer(layer_id, layer_edit)     => Layer(layer_id, layer_edit.assign_element_id(assign_element_id)),
+            AddImage(Unassigned, image)     => AddImage(Assigned(assign_element_id()), image),
+            AddAudioClip(Unassigned, clip)  => AddAudioClip(Assigned(assign_element_id()), clip),
+            other                           => other
         }
     }
 }
@@ -0,0 +1,16 @@
+use super::super::annotation::*;
+
+///
+/// Represents an edit to a review annotation
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum AnnotationEdit {
+    /// Creates a new annotation with this element ID
+    Create(Annotation),
+
+    /// Deletes the annotation with this ID
+    Delete,
+
+    /// Marks the annotation as resolved or unresolved
+    SetResolved(bool)
+}
@@ -1,7 +1,9 @@
 use super::element_id::*;
+use super::path_operation::*;
 use super::element_transform::*;
 use crate::traits::path::*;
 use crate::traits::group_type::*;
+use crate::traits::text::*;
 
 use std::sync::*;
 use std::time::{Duration};
@@ -35,6 +37,23 @@ pub enum ElementEdit {
     /// Updates the path for this element
     SetPath(Arc<Vec<PathComponent>>),
 
+    /// Inserts a new node into a path element by splitting the segment ending at the component with the specified index,
+    /// at a position (0-1) along that segment. The closing segment of a closed subpath is split by using the index of its
+    /// 'close' component.
+    InsertPathNode(usize, f32),
+
+    /// Removes the node at the end of the path component with the specified index, joining the segments either side of it
+    DeletePathNode(usize),
+
+    /// Aligns (smooth) or retracts (corner) the tangents either side of the node at the end of the specified path component
+    SetPathNodeType(usize, PathNodeType),
+
+    /// Breaks a path element at the node at the end of the specified path component
+    BreakPath(usize),
+
+    /// Joins two nodes at the ends of open subpaths in a path element (closing the subpath if they're both in the same one)
+    JoinPathNodes(usize, usize),
+
     /// Updates how this element is ordered relative to other elements in the same keyframe
     /// Note that new elements are always created 'in front' of the current set of elements in the frame.
     Order(ElementOrdering),
@@ -55,6 +74,12 @@ pub enum ElementEdit {
     /// Any groups in the list are broken into their constituent elements
     Ungroup,
 
+    /// Combines the paths of the elements using a boolean operation, replacing them with a single new path element with
+    /// the specified ID. The brush and properties of the new element are taken from the first element in the list.
+    ///
+    /// The elements are left unchanged if the operation produces an empty path.
+    CombinePaths(ElementId, PathOperation),
+
     /// Attempts to join these elements with matching elements in the same frame
     /// 
     /// The ID of the combined element is the same as the ID of the first element of the set. This makes it possible
@@ -63,8 +88,16 @@ pub enum ElementEdit {
     CollideWithExistingElements,
 
     /// Converts this element to a path
+    ///
+    /// Text elements are converted to the outlines of their glyphs, filled with the colour of the text.
     ConvertToPath,
 
+    /// Changes the string displayed by a text element
+    SetText(String),
+
+    /// Changes the font, size, colour and alignment of a text element
+    SetTextStyle(TextStyle),
+
     /// Applies one or more transformations to the elements
     Transform(Vec<ElementTransform>)
 }
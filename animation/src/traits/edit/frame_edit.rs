@@ -2,10 +2,12 @@ use super::element_id::*;
 
 use super::super::path::*;
 use super::super::raw_point::*;
+use super::super::brush::*;
 use super::super::fill_option::*;
 use super::super::brush_properties::*;
 use super::super::brush_definition::*;
 use super::super::brush_drawing_style::*;
+use super::super::text::*;
 
 use std::sync::*;
 
@@ -38,9 +40,22 @@ pub enum PaintEdit {
     /// Draws a brush stroke using the current brush and the specified set of input points
     BrushStroke(ElementId, Arc<Vec<RawPoint>>),
 
+    /// Creates a brush stroke from points that have already been processed by a brush (eg, when restoring a brush
+    /// stroke that was drawn earlier). The stroke is attached to the current brush and properties.
+    CreateBrushStroke(ElementId, Arc<Vec<BrushPoint>>),
+
     /// Creates a path by flood-filling at the specified point on the current layer. The current brush/properties are used to generate
     /// the fill path, and some other options can be set in the fill options.
-    Fill(ElementId, RawPoint, Vec<FillOption>)
+    Fill(ElementId, RawPoint, Vec<FillOption>),
+
+    /// Places an image stored by `AnimationEdit::AddImage` on the current keyframe, with its lower-left corner at the specified
+    /// position, stretched to the specified size and with the specified opacity (parameters are the element ID, the image ID,
+    /// the position, the size and the opacity)
+    PlaceImage(ElementId, ElementId, (f32, f32), (f32, f32), f32),
+
+    /// Creates a text element on the current keyframe, displaying the specified string in the specified style. The position is the
+    /// start of the baseline of the first line of text (or the middle or end if the text is centered or right-aligned)
+    CreateText(ElementId, String, TextStyle, (f32, f32))
 }
 
 impl PaintEdit {
@@ -51,10 +66,13 @@ impl PaintEdit {
         use self::PaintEdit::*;
 
         match self {
-            SelectBrush(id, _, _)   => *id,
-            BrushProperties(id, _)  => *id,
-            BrushStroke(id, _)      => *id,
-            Fill(id, _, _)          => *id
+            SelectBrush(id, _, _)       => *id,
+            BrushProperties(id, _)      => *id,
+            BrushStroke(id, _)          => *id,
+            CreateBrushStroke(id, _)    => *id,
+            Fill(id, _, _)              => *id,
+            PlaceImage(id, _, _, _, _)  => *id,
+            CreateText(id, _, _, _)     => *id
         }
     }
 
@@ -70,6 +88,9 @@ impl PaintEdit {
             SelectBrush(Unassigned, brush_def, brush_style) => SelectBrush(Assigned(assign_element_id()), brush_def, brush_style),
             BrushProperties(Unassigned, brush_props)        => BrushProperties(Assigned(assign_element_id()), brush_props),
             BrushStroke(Unassigned, points)                 => BrushStroke(Assigned(assign_element_id()), points),
+            CreateBrushStroke(Unassigned, points)           => CreateBrushStroke(Assigned(assign_element_id()), points),
+            PlaceImage(Unassigned, image_id, pos, size, a)  => PlaceImage(Assigned(assign_element_id()), image_id, pos, size, a),
+            CreateText(Unassigned, text, style, pos)        => CreateText(Assigned(assign_element_id()), text, style, pos),
 
             assigned => assigned
         }
@@ -1,5 +1,10 @@
 use super::frame_edit::*;
+use super::element_id::*;
+use super::super::inbetween_mode::*;
 
+use flo_canvas::{BlendMode};
+
+use std::ops::{Range};
 use std::time::Duration;
 
 ///
@@ -11,7 +16,13 @@ use std::time::Duration;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum LayerEditType {
-    Vector
+    Vector,
+
+    /// Bitmap images can be placed on the keyframes of the layer
+    Image,
+
+    /// The layer has an audio clip attached to it
+    Audio
 }
 
 ///
@@ -34,11 +45,60 @@ pub enum LayerEdit {
     /// Removes a keyframe previously added at a particular duration
     RemoveKeyFrame(Duration),
 
+    /// Moves the keyframe at the first time so that it starts at the second time, along with everything drawn on it
+    ///
+    /// The move is ignored if there's no keyframe at the first time or there's already a keyframe at the second time.
+    MoveKeyFrame(Duration, Duration),
+
+    /// Creates a new keyframe at the second time containing a copy of the drawing on the keyframe at the first time
+    ///
+    /// Nothing is copied if there's already a keyframe at the second time. The copied elements are given the IDs in the list, in
+    /// the order of the IDs of the original elements: this can be left empty, in which case the IDs are assigned when the edit is
+    /// performed.
+    DuplicateKeyFrame(Duration, Duration, Vec<ElementId>),
+
+    /// Moves all of the keyframes that start in the first range so that they're spread across the second range instead
+    ///
+    /// This can shift a section of the timeline (when the ranges are the same length), or speed it up or slow it down.
+    /// The edit is rejected and no keyframes are moved if either range is empty, or if any keyframe would land on top of another one.
+    RetimeKeyFrames(Range<Duration>, Range<Duration>),
+
+    /// Creates real keyframes from the generated in-betweens within a time range, placing one keyframe every time the second duration elapses
+    ///
+    /// Keyframes are created between pairs of existing keyframes using the in-betweening mode of the layer (or by matching
+    /// strokes in order if the layer has in-betweening turned off). The elements of the new keyframes are given the IDs in the
+    /// list, in the same way as for `DuplicateKeyFrame`.
+    BakeInbetweens(Range<Duration>, Duration, Vec<ElementId>),
+
     /// Changes the name of this layer
     SetName(String),
 
     /// Sets this layer so that it is ordered behind the specified layer
-    SetOrdering(u64)
+    SetOrdering(u64),
+
+    /// Shows or hides this layer
+    SetVisibility(bool),
+
+    /// Locks or unlocks this layer (paint and path edits are ignored while a layer is locked)
+    SetLocked(bool),
+
+    /// Sets the opacity of this layer (0.0 is fully transparent, 1.0 is fully opaque)
+    SetOpacity(f64),
+
+    /// Sets how this layer is blended with the layers underneath it
+    SetBlendMode(BlendMode),
+
+    /// Sets how the frames between the keyframes of this layer are generated
+    SetInbetweenMode(InbetweenMode),
+
+    /// Attaches an audio clip to this layer, replacing any clip that was already attached
+    ///
+    /// The clip itself is stored by `AnimationEdit::AddAudioClip`: this refers to it by its ID, so the audio data
+    /// isn't copied into the edit log or the undo history whenever a clip is attached to a layer.
+    ImportAudio(ElementId),
+
+    /// Removes the audio clip from this layer
+    RemoveAudio
 }
 
 impl LayerEdit {
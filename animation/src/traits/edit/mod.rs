@@ -5,7 +5,9 @@ mod frame_edit;
 mod element_edit;
 mod element_align;
 mod element_transform;
+mod path_operation;
 mod motion_edit;
+mod annotation_edit;
 
 pub use self::element_id::*;
 pub use self::animation_edit::*;
@@ -14,4 +16,6 @@ pub use self::frame_edit::*;
 pub use self::element_edit::*;
 pub use self::element_align::*;
 pub use self::element_transform::*;
+pub use self::path_operation::*;
 pub use self::motion_edit::*;
+pub use self::annotation_edit::*;
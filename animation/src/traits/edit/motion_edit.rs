@@ -22,4 +22,7 @@ pub enum MotionEdit {
 
     /// Sets the time curve for this motion
     SetPath(TimeCurve),
+
+    /// Sets the easing for a section of the time curve for this motion (sections are numbered from 0, starting at the first point in the curve)
+    SetEasing(usize, Easing),
 }
@@ -0,0 +1,20 @@
+///
+/// Boolean operations that can be used to combine the paths of a set of elements
+///
+/// The operations are applied in the order of the elements: for instance, a subtraction removes all of the
+/// later elements from the first one.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathOperation {
+    /// The area covered by any of the elements
+    Union,
+
+    /// The area covered by the first element that is not covered by any of the other elements
+    Subtract,
+
+    /// The area covered by all of the elements
+    Intersect,
+
+    /// The area covered by an odd number of the elements
+    Exclude
+}
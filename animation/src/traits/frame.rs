@@ -5,6 +5,27 @@ use flo_canvas::*;
 
 use std::sync::*;
 use std::time::Duration;
+use std::collections::{HashSet};
+
+///
+/// Declares the sprites for the image elements in an element (and any elements it contains) that haven't been declared already
+///
+fn render_image_sprites(element: &Vector, gc: &mut dyn GraphicsPrimitives, declared: &mut HashSet<SpriteId>) {
+    match element {
+        Vector::Image(image)    => {
+            if image.image().is_some() && !declared.contains(&image.sprite_id()) {
+                image.render_sprite(gc);
+                declared.insert(image.sprite_id());
+            }
+        }
+
+        Vector::Group(group)    => {
+            group.elements().for_each(|grouped_element| render_image_sprites(grouped_element, gc, declared));
+        }
+
+        _                       => { }
+    }
+}
 
 ///
 /// Represents a single frame in a layer of an animation
@@ -20,6 +41,30 @@ pub trait Frame : Send+Sync {
     ///
     fn render_to(&self, gc: &mut dyn GraphicsPrimitives);
 
+    ///
+    /// Declares the sprites that are used to draw the images in this frame
+    ///
+    /// This needs to be called before `render_to()`. It leaves a sprite selected, so it should be followed by selecting the
+    /// layer that the frame will be rendered to.
+    ///
+    fn render_sprites(&self, gc: &mut dyn GraphicsPrimitives) {
+        let mut declared = HashSet::new();
+
+        if let Some(elements) = self.vector_elements() {
+            elements.for_each(|element| render_image_sprites(&element, gc, &mut declared));
+        }
+    }
+
+    ///
+    /// The blend mode to use when compositing the canvas layer this frame is rendered to with the layers underneath it
+    ///
+    fn blend_mode(&self) -> BlendMode;
+
+    ///
+    /// The alpha value to use when compositing the canvas layer this frame is rendered to with the layers underneath it
+    ///
+    fn opacity(&self) -> f64;
+
     ///
     /// Applies all of the properties for the specified element (including those added by attached elements)
     ///
@@ -54,6 +99,21 @@ impl Frame for Arc<dyn Frame> {
     ///
     #[inline] fn render_to(&self, gc: &mut dyn GraphicsPrimitives) { (**self).render_to(gc) }
 
+    ///
+    /// Declares the sprites that are used to draw the images in this frame
+    ///
+    #[inline] fn render_sprites(&self, gc: &mut dyn GraphicsPrimitives) { (**self).render_sprites(gc) }
+
+    ///
+    /// The blend mode to use when compositing the canvas layer this frame is rendered to with the layers underneath it
+    ///
+    #[inline] fn blend_mode(&self) -> BlendMode { (**self).blend_mode() }
+
+    ///
+    /// The alpha value to use when compositing the canvas layer this frame is rendered to with the layers underneath it
+    ///
+    #[inline] fn opacity(&self) -> f64 { (**self).opacity() }
+
     ///
     /// Applies all of the properties for the specified element (including those added by attached elements)
     ///
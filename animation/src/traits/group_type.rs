@@ -9,14 +9,12 @@ pub enum GroupType {
     /// Elements are added together (the path properties of the first element are used for all elements)
     Added,
 
-    /*
-    /// Elements after the first element are subtracted from the first element
+    /// Elements after the first element are subtracted from the first element (the path properties of the first element are used for the result)
     Subtracted,
 
-    /// The first element is intersected with future elements
+    /// The first element is intersected with future elements (the first element is not drawn, but future elements are only drawn where it is)
     Masked,
 
-    /// The first element is subtracted from future elements
+    /// The first element is subtracted from future elements (the first element is not drawn, and future elements are only drawn where it isn't)
     InvertedMask
-    */
 }
@@ -0,0 +1,3 @@
+mod reference_image;
+
+pub use self::reference_image::*;
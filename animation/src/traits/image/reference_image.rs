@@ -0,0 +1,344 @@
+use std::fmt;
+use std::sync::*;
+use std::collections::{BTreeMap};
+
+///
+/// The largest width or height of the grid used when rendering a reference image
+///
+/// Images are rendered as a set of filled rectangles, so larger images are scaled down to fit this size
+/// to keep the number of drawing instructions manageable.
+///
+pub const MAX_IMAGE_RENDER_SIZE: u32 = 256;
+
+///
+/// Errors that can occur while reading an image file
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum ImageError {
+    /// The data could not be decoded as a PNG file (the parameter is the error reported by the decoder)
+    CannotDecode(String),
+
+    /// The data could not be encoded as a PNG file (the parameter is the error reported by the encoder)
+    CannotEncode(String),
+
+    /// The image has no pixels in it
+    EmptyImage
+}
+
+///
+/// A set of rectangles from a reference image that are all filled with the same colour
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImageRegion {
+    /// The colour of this region, as 8-bit RGBA components
+    pub color: (u8, u8, u8, u8),
+
+    /// The rectangles that make up this region, as (x1, y1, x2, y2) coordinates in the render grid (y=0 is the top row of the image)
+    pub rects: Vec<(u32, u32, u32, u32)>
+}
+
+///
+/// A bitmap image that can be placed on a keyframe (for example, as reference art to trace over)
+///
+/// The original PNG data is kept so the image can be stored without any loss of quality. The image is
+/// rendered from a scaled-down copy made up of regions of a single colour.
+///
+#[derive(Clone)]
+pub struct ReferenceImage {
+    /// The PNG file this image was read from
+    png_data: Arc<Vec<u8>>,
+
+    /// The width of the image in pixels
+    width: u32,
+
+    /// The height of the image in pixels
+    height: u32,
+
+    /// The size of the grid used to render this image
+    render_size: (u32, u32),
+
+    /// The regions used to render this image
+    regions: Arc<Vec<ImageRegion>>
+}
+
+impl PartialEq for ReferenceImage {
+    fn eq(&self, other: &ReferenceImage) -> bool {
+        // Images are the same if they were read from the same file
+        self.png_data == other.png_data
+    }
+}
+
+impl fmt::Debug for ReferenceImage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The image data is left out as there's usually far too much of it to usefully display
+        write!(f, "ReferenceImage({}x{}, {} bytes)", self.width, self.height, self.png_data.len())
+    }
+}
+
+///
+/// Reduces the precision of a colour component so that similar colours can be rendered as a single region
+///
+#[inline]
+fn quantize(component: u8, bits: u32) -> u8 {
+    let mask = !(0xffu8 >> bits);
+    let high = component & mask;
+
+    // Repeat the high bits in the low bits so that 0xff stays as 0xff
+    high | (high >> bits)
+}
+
+impl ReferenceImage {
+    ///
+    /// Reads a reference image from the contents of a PNG file
+    ///
+    pub fn from_png(png_data: &[u8]) -> Result<ReferenceImage, ImageError> {
+        // Decode to 8 bits per channel, with palettes and transparency expanded
+        let mut decoder = png::Decoder::new(png_data);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+        let (info, mut reader)  = decoder.read_info().map_err(|err| ImageError::CannotDecode(err.to_string()))?;
+        let mut buffer          = vec![0; info.buffer_size()];
+        reader.next_frame(&mut buffer).map_err(|err| ImageError::CannotDecode(err.to_string()))?;
+
+        if info.width == 0 || info.height == 0 {
+            return Err(ImageError::EmptyImage);
+        }
+
+        // Convert the pixels to RGBA
+        let (color_type, _) = reader.output_color_type();
+        let samples         = color_type.samples();
+        let mut pixels      = Vec::with_capacity((info.width as usize) * (info.height as usize) * 4);
+
+        for row in buffer.chunks(info.line_size).take(info.height as usize) {
+            for pixel in row.chunks_exact(samples).take(info.width as usize) {
+                let rgba = match color_type {
+                    png::ColorType::Grayscale       => [pixel[0], pixel[0], pixel[0], 255],
+                    png::ColorType::GrayscaleAlpha  => [pixel[0], pixel[0], pixel[0], pixel[1]],
+                    png::ColorType::RGB             => [pixel[0], pixel[1], pixel[2], 255],
+                    png::ColorType::RGBA            => [pixel[0], pixel[1], pixel[2], pixel[3]],
+
+                    // Palettes are removed by the EXPAND transformation
+                    png::ColorType::Indexed         => { return Err(ImageError::CannotDecode("Indexed image was not expanded".to_string())); }
+                };
+
+                pixels.extend_from_slice(&rgba);
+            }
+        }
+
+        let (render_size, regions) = Self::regions_for_pixels(info.width, info.height, &pixels);
+
+        Ok(ReferenceImage {
+            png_data:       Arc::new(png_data.to_vec()),
+            width:          info.width,
+            height:         info.height,
+            render_size:    render_size,
+            regions:        Arc::new(regions)
+        })
+    }
+
+    ///
+    /// Creates a reference image from a buffer of 8-bit RGBA pixels (stored row by row, starting at the top of the image)
+    ///
+    pub fn from_rgba(width: u32, height: u32, rgba: &[u8]) -> Result<ReferenceImage, ImageError> {
+        if width == 0 || height == 0 {
+            return Err(ImageError::EmptyImage);
+        }
+
+        // Encode the pixels as a PNG file
+        let mut png_data: Vec<u8> = vec![];
+
+        {
+            let mut encoder = png::Encoder::new(&mut png_data, width, height);
+            encoder.set_color(png::ColorType::RGBA);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            let mut writer  = encoder.write_header().map_err(|err| ImageError::CannotEncode(err.to_string()))?;
+            writer.write_image_data(rgba).map_err(|err| ImageError::CannotEncode(err.to_string()))?;
+        }
+
+        Self::from_png(&png_data)
+    }
+
+    ///
+    /// Returns the PNG data for this image
+    ///
+    pub fn png_data(&self) -> Arc<Vec<u8>> {
+        Arc::clone(&self.png_data)
+    }
+
+    ///
+    /// The width of this image in pixels
+    ///
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    ///
+    /// The height of this image in pixels
+    ///
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    ///
+    /// The width and height of the grid that the rectangles in the regions for this image are positioned on
+    ///
+    pub fn render_size(&self) -> (u32, u32) {
+        self.render_size
+    }
+
+    ///
+    /// The regions of a single colour that make up the rendered version of this image
+    ///
+    pub fn regions(&self) -> Arc<Vec<ImageRegion>> {
+        Arc::clone(&self.regions)
+    }
+
+    ///
+    /// Scales down a set of RGBA pixels to fit the render grid and divides them up into regions
+    ///
+    fn regions_for_pixels(width: u32, height: u32, pixels: &[u8]) -> ((u32, u32), Vec<ImageRegion>) {
+        // Each cell in the render grid covers a square of step*step pixels
+        let step            = (u32::max(width, height) + MAX_IMAGE_RENDER_SIZE - 1) / MAX_IMAGE_RENDER_SIZE;
+        let grid_width      = (width + step - 1) / step;
+        let grid_height     = (height + step - 1) / step;
+
+        // Colours are quantized so that neighbouring cells with similar colours can be combined into a single rectangle
+        let mut regions     = BTreeMap::<(u8, u8, u8, u8), Vec<(u32, u32, u32, u32)>>::new();
+
+        for grid_y in 0..grid_height {
+            let mut run: Option<((u8, u8, u8, u8), u32)> = None;
+
+            for grid_x in 0..=grid_width {
+                // Average the pixels in this cell (the cell past the end of the row finishes the last run)
+                let color = if grid_x < grid_width {
+                    let mut total       = [0u64; 4];
+                    let mut num_pixels  = 0u64;
+
+                    for y in (grid_y*step)..u32::min((grid_y+1)*step, height) {
+                        for x in (grid_x*step)..u32::min((grid_x+1)*step, width) {
+                            let pos     = ((y as usize)*(width as usize) + (x as usize)) * 4;
+                            let alpha   = pixels[pos+3] as u64;
+
+                            total[0]    += (pixels[pos] as u64)   * alpha;
+                            total[1]    += (pixels[pos+1] as u64) * alpha;
+                            total[2]    += (pixels[pos+2] as u64) * alpha;
+                            total[3]    += alpha;
+                            num_pixels  += 1;
+                        }
+                    }
+
+                    if total[3] == 0 {
+                        None
+                    } else {
+                        Some((
+                            quantize((total[0] / total[3]) as u8, 5),
+                            quantize((total[1] / total[3]) as u8, 5),
+                            quantize((total[2] / total[3]) as u8, 5),
+                            quantize((total[3] / num_pixels) as u8, 4)
+                        ))
+                    }
+                } else {
+                    None
+                };
+
+                // Skip pixels that are invisible
+                let color = color.filter(|(_, _, _, alpha)| *alpha != 0);
+
+                // Finish the current run if the colour changes
+                match (run, color) {
+                    (Some((run_color, _)), Some(color)) if run_color == color   => { }
+
+                    (run_start, color)                                          => {
+                        if let Some((run_color, start_x)) = run_start {
+                            regions.entry(run_color).or_default().push((start_x, grid_y, grid_x, grid_y+1));
+                        }
+
+                        run = color.map(|color| (color, grid_x));
+                    }
+                }
+            }
+        }
+
+        let regions = regions.into_iter()
+            .map(|(color, rects)| ImageRegion { color, rects })
+            .collect();
+
+        ((grid_width, grid_height), regions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checkerboard(width: u32, height: u32) -> Vec<u8> {
+        let mut pixels = vec![];
+
+        for y in 0..height {
+            for x in 0..width {
+                if (x+y)%2 == 0 {
+                    pixels.extend_from_slice(&[255, 0, 0, 255]);
+                } else {
+                    pixels.extend_from_slice(&[0, 0, 255, 255]);
+                }
+            }
+        }
+
+        pixels
+    }
+
+    #[test]
+    fn read_png_image() {
+        let image = ReferenceImage::from_rgba(4, 3, &checkerboard(4, 3)).unwrap();
+
+        assert!(image.width() == 4);
+        assert!(image.height() == 3);
+        assert!(image.render_size() == (4, 3));
+        assert!(ReferenceImage::from_png(&image.png_data()) == Ok(image));
+    }
+
+    #[test]
+    fn reject_data_that_is_not_a_png() {
+        assert!(ReferenceImage::from_png(&[1, 2, 3, 4, 5, 6, 7, 8]).is_err());
+    }
+
+    #[test]
+    fn regions_are_split_by_color() {
+        let image   = ReferenceImage::from_rgba(2, 2, &checkerboard(2, 2)).unwrap();
+        let regions = image.regions();
+
+        assert!(regions.len() == 2);
+        assert!(regions.iter().any(|region| region.color == (255, 0, 0, 255) && region.rects == vec![(0, 0, 1, 1), (1, 1, 2, 2)]));
+        assert!(regions.iter().any(|region| region.color == (0, 0, 255, 255) && region.rects == vec![(1, 0, 2, 1), (0, 1, 1, 2)]));
+    }
+
+    #[test]
+    fn rows_of_the_same_color_are_combined() {
+        let pixels  = [[0u8, 255, 0, 255]; 6].concat();
+        let image   = ReferenceImage::from_rgba(3, 2, &pixels).unwrap();
+        let regions = image.regions();
+
+        assert!(regions.len() == 1);
+        assert!(regions[0].rects == vec![(0, 0, 3, 1), (0, 1, 3, 2)]);
+    }
+
+    #[test]
+    fn transparent_pixels_are_not_rendered() {
+        let pixels  = [[10u8, 20, 30, 0]; 4].concat();
+        let image   = ReferenceImage::from_rgba(2, 2, &pixels).unwrap();
+
+        assert!(image.regions().len() == 0);
+    }
+
+    #[test]
+    fn large_images_are_scaled_down() {
+        let width   = MAX_IMAGE_RENDER_SIZE*2 + 1;
+        let pixels  = [0u8, 0, 0, 255].iter().cycle().take((width as usize) * 4 * 4).cloned().collect::<Vec<_>>();
+        let image   = ReferenceImage::from_rgba(width, 4, &pixels).unwrap();
+
+        assert!(image.width() == width);
+        assert!(image.render_size().0 <= MAX_IMAGE_RENDER_SIZE);
+        assert!(image.render_size().1 == 2);
+    }
+}
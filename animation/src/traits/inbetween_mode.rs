@@ -0,0 +1,20 @@
+///
+/// How the frames between two keyframes on a layer are generated
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InbetweenMode {
+    /// The drawing from the previous keyframe is displayed until the next keyframe
+    Off,
+
+    /// Strokes are matched with the stroke drawn in the same order on the next keyframe and interpolated
+    MatchByOrder,
+
+    /// Strokes are matched with the nearest similar stroke on the next keyframe and interpolated
+    MatchByProximity
+}
+
+impl Default for InbetweenMode {
+    fn default() -> InbetweenMode {
+        InbetweenMode::Off
+    }
+}
@@ -2,6 +2,10 @@ use super::vector::*;
 use super::super::edit::*;
 use super::super::frame::*;
 use super::super::cache::*;
+use super::super::audio::*;
+use super::super::inbetween_mode::*;
+
+use flo_canvas::{BlendMode};
 
 use std::u32;
 use std::sync::*;
@@ -22,11 +26,46 @@ pub trait Layer : Send+Sync {
     ///
     fn name(&self) -> Option<String>;
 
+    ///
+    /// True if this layer should be rendered
+    ///
+    fn is_visible(&self) -> bool;
+
+    ///
+    /// True if this layer is locked (paint and path edits to a locked layer are ignored)
+    ///
+    fn is_locked(&self) -> bool;
+
+    ///
+    /// The opacity of this layer (0.0 is fully transparent, 1.0 is fully opaque)
+    ///
+    fn opacity(&self) -> f64;
+
+    ///
+    /// How this layer is blended with the layers underneath it
+    ///
+    fn blend_mode(&self) -> BlendMode;
+
+    ///
+    /// How the frames between the keyframes of this layer are generated
+    ///
+    fn inbetween_mode(&self) -> InbetweenMode;
+
     ///
     /// The types of edit that are supported by this layer
     ///
     fn supported_edit_types(&self) -> Vec<LayerEditType>;
 
+    ///
+    /// Retrieves the ID of the audio clip attached to this layer, if there is one
+    ///
+    fn audio_clip_id(&self) -> Option<ElementId>;
+
+    ///
+    /// Retrieves the audio clip attached to this layer, if there is one
+    ///
+    fn audio_clip(&self) -> Option<Arc<AudioClip>>;
+
     ///
     /// Retrieves a frame from this layer with the specified parameters
     ///
@@ -8,6 +8,7 @@ mod layer;
 mod raw_point;
 mod brush;
 mod brush_properties;
+mod brush_fill;
 mod brush_definition;
 mod brush_drawing_style;
 mod vector;
@@ -18,6 +19,11 @@ mod cache;
 mod combine_result;
 mod group_type;
 mod fill_option;
+mod inbetween_mode;
+mod audio;
+mod image;
+mod text;
+mod annotation;
 
 pub use self::edit::*;
 pub use self::actions::*;
@@ -29,6 +35,7 @@ pub use self::layer::*;
 pub use self::raw_point::*;
 pub use self::brush::*;
 pub use self::brush_properties::*;
+pub use self::brush_fill::*;
 pub use self::brush_definition::*;
 pub use self::brush_drawing_style::*;
 pub use self::vector::*;
@@ -39,3 +46,8 @@ pub use self::cache::*;
 pub use self::combine_result::*;
 pub use self::group_type::*;
 pub use self::fill_option::*;
+pub use self::inbetween_mode::*;
+pub use self::audio::*;
+pub use self::image::*;
+pub use self::text::*;
+pub use self::annotation::*;
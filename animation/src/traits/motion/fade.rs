@@ -0,0 +1,93 @@
+use super::transform::*;
+use super::super::vector::*;
+use super::super::time_path::*;
+
+use smallvec::*;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes how the opacity of an element changes over time
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct FadeMotion {
+    /// The origin of this motion (fades don't move the element, but this is kept so the motion can be edited like the others)
+    pub origin: (f32, f32),
+
+    /// Curve describing the opacity over time. The x coordinate is the opacity (0.0 to 1.0), the y coordinate is ignored
+    pub opacity: TimeCurve
+}
+
+impl FadeMotion {
+    ///
+    /// Creates a fade motion that changes the opacity of an element between two values over a period of time
+    ///
+    pub fn fade_between(start: Duration, end: Duration, from_opacity: f32, to_opacity: f32) -> FadeMotion {
+        FadeMotion {
+            origin:     (0.0, 0.0),
+            opacity:    TimeCurve::new(TimePoint::new(from_opacity, 0.0, start), TimePoint::new(to_opacity, 0.0, end))
+        }
+    }
+
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the path of this motion
+    ///
+    #[inline]
+    pub fn set_path(&mut self, new_path: TimeCurve) {
+        self.opacity = new_path;
+    }
+}
+
+impl Default for FadeMotion {
+    ///
+    /// Creates a default fade motion
+    ///
+    fn default() -> FadeMotion {
+        FadeMotion {
+            origin:     (0.0, 0.0),
+            opacity:    TimeCurve::new(TimePoint::new(1.0, 0.0, Duration::from_millis(0)), TimePoint::new(1.0, 0.0, Duration::from_millis(0)))
+        }
+    }
+}
+
+impl MotionTransform for FadeMotion {
+    fn range_millis(&self) -> Range<f32> {
+        if self.opacity.points.len() == 0 {
+            0.0..0.0
+        } else {
+            let start   = self.opacity.points[0].point.milliseconds();
+            let end     = self.opacity.points.last().unwrap().point.milliseconds();
+
+            start..end
+        }
+    }
+
+    ///
+    /// Returns the transformations to apply for this motion at a particular point in time
+    ///
+    fn transformation(&self, _when: Duration) -> SmallVec<[Transformation; 2]> {
+        // Fading doesn't change the shape of an element
+        smallvec![]
+    }
+
+    ///
+    /// Returns the opacity to apply for this motion at a particular point in time
+    ///
+    fn opacity(&self, when: Duration) -> f64 {
+        let time_millis = ((when.as_secs() as f32) * 1_000.0) + ((when.subsec_nanos() as f32) / 1_000_000.0);
+        let opacity     = self.opacity.point_at_time(time_millis);
+
+        opacity
+            .map(|opacity| (opacity.0 as f64).max(0.0).min(1.0))
+            .unwrap_or(1.0)
+    }
+}
@@ -1,9 +1,15 @@
 mod transform;
 mod motion;
 mod translate;
+mod rotate;
+mod scale;
+mod fade;
 mod motion_type;
 
 pub use self::transform::*;
 pub use self::motion::*;
 pub use self::translate::*;
+pub use self::rotate::*;
+pub use self::scale::*;
+pub use self::fade::*;
 pub use self::motion_type::*;
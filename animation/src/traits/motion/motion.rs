@@ -1,4 +1,7 @@
 use super::translate::*;
+use super::rotate::*;
+use super::scale::*;
+use super::fade::*;
 use super::transform::*;
 use super::motion_type::*;
 use super::super::vector::*;
@@ -24,7 +27,16 @@ pub enum Motion {
     Reverse(Arc<Motion>),
 
     /// Describes how an element is translated over time
-    Translate(TranslateMotion)
+    Translate(TranslateMotion),
+
+    /// Describes how an element is rotated around its origin over time
+    Rotate(RotateMotion),
+
+    /// Describes how an element is scaled around its origin over time
+    Scale(ScaleMotion),
+
+    /// Describes how the opacity of an element changes over time
+    Fade(FadeMotion)
 }
 
 impl Motion {
@@ -37,7 +49,10 @@ impl Motion {
         match self {
             None            => MotionType::None,
             Reverse(_)      => MotionType::Reverse,
-            Translate(_)    => MotionType::Translate
+            Translate(_)    => MotionType::Translate,
+            Rotate(_)       => MotionType::Rotate,
+            Scale(_)        => MotionType::Scale,
+            Fade(_)         => MotionType::Fade
         }
     }
 
@@ -51,6 +66,9 @@ impl Motion {
             None        => { *self = Motion::None; },
             Reverse     => { *self = Motion::Reverse(Arc::new(Motion::None)); }
             Translate   => { *self = Motion::Translate(TranslateMotion::default()); }
+            Rotate      => { *self = Motion::Rotate(RotateMotion::default()); }
+            Scale       => { *self = Motion::Scale(ScaleMotion::default()); }
+            Fade        => { *self = Motion::Fade(FadeMotion::default()); }
         }
     }
 
@@ -64,6 +82,9 @@ impl Motion {
             None                    => { },
             Reverse(_)              => { },
             Translate(translate)    => { translate.set_origin(new_origin); }
+            Rotate(rotate)          => { rotate.set_origin(new_origin); }
+            Scale(scale)            => { scale.set_origin(new_origin); }
+            Fade(fade)              => { fade.set_origin(new_origin); }
         }
     }
 
@@ -77,6 +98,75 @@ impl Motion {
             None                    => { },
             Reverse(_)              => { },
             Translate(translate)    => { translate.set_path(new_path); }
+            Rotate(rotate)          => { rotate.set_path(new_path); }
+            Scale(scale)            => { scale.set_path(new_path); }
+            Fade(fade)              => { fade.set_path(new_path); }
+        }
+    }
+
+    ///
+    /// Sets the easing for a section of the path of this motion
+    ///
+    pub fn set_easing(&mut self, section: usize, easing: Easing) {
+        use self::Motion::*;
+
+        match self {
+            None                    => { },
+            Reverse(_)              => { },
+            Translate(translate)    => { translate.translate.set_easing(section, easing); }
+            Rotate(rotate)          => { rotate.rotate.set_easing(section, easing); }
+            Scale(scale)            => { scale.scale.set_easing(section, easing); }
+            Fade(fade)              => { fade.opacity.set_easing(section, easing); }
+        }
+    }
+
+    ///
+    /// Retrieves the origin of this motion, if it has one
+    ///
+    pub fn origin(&self) -> Option<(f32, f32)> {
+        use self::Motion::*;
+
+        match self {
+            None                    => Option::None,
+            Reverse(_)              => Option::None,
+            Translate(translate)    => Some(translate.origin),
+            Rotate(rotate)          => Some(rotate.origin),
+            Scale(scale)            => Some(scale.origin),
+            Fade(fade)              => Some(fade.origin)
+        }
+    }
+
+    ///
+    /// Retrieves the path of this motion, if it has one
+    ///
+    pub fn path(&self) -> Option<&TimeCurve> {
+        use self::Motion::*;
+
+        match self {
+            None                    => Option::None,
+            Reverse(_)              => Option::None,
+            Translate(translate)    => Some(&translate.translate),
+            Rotate(rotate)          => Some(&rotate.rotate),
+            Scale(scale)            => Some(&scale.scale),
+            Fade(fade)              => Some(&fade.opacity)
+        }
+    }
+
+    ///
+    /// Returns a copy of this motion that happens the specified number of milliseconds later (or earlier if the offset is negative)
+    ///
+    pub fn with_time_offset(&self, offset_millis: f32) -> Motion {
+        match self {
+            Motion::Reverse(reversed)   => Motion::Reverse(Arc::new(reversed.with_time_offset(offset_millis))),
+
+            motion                      => {
+                let mut offset_motion = motion.clone();
+                if let Some(path) = motion.path() {
+                    offset_motion.set_path(path.with_time_offset(offset_millis));
+                }
+
+                offset_motion
+            }
         }
     }
 
@@ -98,7 +188,10 @@ impl MotionTransform for Motion {
         match self {
             None                    => 0.0..0.0,
             Reverse(motion)         => motion.range_millis(),
-            Translate(translate)    => translate.range_millis()
+            Translate(translate)    => translate.range_millis(),
+            Rotate(rotate)          => rotate.range_millis(),
+            Scale(scale)            => scale.range_millis(),
+            Fade(fade)              => fade.range_millis()
         }
     }
 
@@ -111,6 +204,9 @@ impl MotionTransform for Motion {
         match self {
             None                    => smallvec![],
             Translate(translate)    => translate.transformation(when),
+            Rotate(rotate)          => rotate.transformation(when),
+            Scale(scale)            => scale.transformation(when),
+            Fade(fade)              => fade.transformation(when),
 
             Reverse(motion)         => {
                 let transform = motion.transformation(when);
@@ -121,4 +217,92 @@ impl MotionTransform for Motion {
             }
         }
     }
+
+    ///
+    /// Returns the opacity to apply for this motion at a particular point in time
+    ///
+    /// A reversed fade is always fully opaque, as the inverse of a fade would need an opacity above 1.0.
+    ///
+    fn opacity(&self, when: Duration) -> f64 {
+        use self::Motion::*;
+
+        match self {
+            Fade(fade)              => fade.opacity(when),
+            Reverse(_)              => 1.0,
+            _                       => 1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use flo_curves::*;
+
+    #[test]
+    fn rotate_quarter_turn() {
+        let motion      = Motion::Rotate(RotateMotion::rotate_to(Duration::from_millis(100), (10.0, 10.0), 90.0));
+        let transform   = motion.transformation(Duration::from_millis(100));
+
+        assert!(transform.len() == 1);
+
+        let rotated     = transform[0].transform_point(&Coord2(20.0, 10.0));
+        assert!((rotated.x() - 10.0).abs() < 0.01);
+        assert!((rotated.y() - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn scale_around_origin() {
+        let motion      = Motion::Scale(ScaleMotion::scale_to(Duration::from_millis(100), (10.0, 10.0), (2.0, 3.0)));
+        let transform   = motion.transformation(Duration::from_millis(100));
+
+        assert!(transform.len() == 1);
+
+        let scaled      = transform[0].transform_point(&Coord2(20.0, 20.0));
+        assert!((scaled.x() - 30.0).abs() < 0.01);
+        assert!((scaled.y() - 40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn fade_changes_opacity_only() {
+        let motion      = Motion::Fade(FadeMotion::fade_between(Duration::from_millis(0), Duration::from_millis(1000), 1.0, 0.0));
+
+        assert!(motion.transformation(Duration::from_millis(500)).len() == 0);
+        assert!((motion.opacity(Duration::from_millis(0)) - 1.0).abs() < 0.01);
+        assert!((motion.opacity(Duration::from_millis(1000)) - 0.0).abs() < 0.01);
+        assert!(motion.opacity(Duration::from_millis(500)) > 0.0 && motion.opacity(Duration::from_millis(500)) < 1.0);
+    }
+
+    #[test]
+    fn other_motions_are_opaque() {
+        let motion      = Motion::Rotate(RotateMotion::rotate_to(Duration::from_millis(100), (10.0, 10.0), 90.0));
+
+        assert!(motion.opacity(Duration::from_millis(100)) == 1.0);
+    }
+
+    #[test]
+    fn reverse_fade_is_opaque() {
+        let fade_out    = Motion::Fade(FadeMotion::fade_between(Duration::from_millis(0), Duration::from_millis(1000), 1.0, 0.0)).reverse();
+        let fade_in     = Motion::Fade(FadeMotion::fade_between(Duration::from_millis(0), Duration::from_millis(1000), 0.0, 0.5)).reverse();
+
+        for reverse in vec![fade_out, fade_in] {
+            assert!(reverse.opacity(Duration::from_millis(0)) == 1.0);
+            assert!(reverse.opacity(Duration::from_millis(500)) == 1.0);
+            assert!(reverse.opacity(Duration::from_millis(1000)) == 1.0);
+        }
+    }
+
+    #[test]
+    fn reverse_rotation_restores_point() {
+        let motion      = Motion::Rotate(RotateMotion::rotate_to(Duration::from_millis(100), (10.0, 10.0), 45.0));
+        let reverse     = motion.clone().reverse();
+
+        let point       = Coord2(25.0, 13.0);
+        let forward     = motion.transformation(Duration::from_millis(100)).iter().fold(point, |point, transform| transform.transform_point(&point));
+        let backward    = reverse.transformation(Duration::from_millis(100)).iter().fold(forward, |point, transform| transform.transform_point(&point));
+
+        assert!((backward.x() - point.x()).abs() < 0.01);
+        assert!((backward.y() - point.y()).abs() < 0.01);
+    }
 }
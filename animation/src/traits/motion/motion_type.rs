@@ -5,5 +5,8 @@
 pub enum MotionType {
     None,
     Reverse,
-    Translate
+    Translate,
+    Rotate,
+    Scale,
+    Fade
 }
@@ -0,0 +1,93 @@
+use super::transform::*;
+use super::super::vector::*;
+use super::super::time_path::*;
+
+use smallvec::*;
+
+use std::f64;
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes how an element is rotated over time
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct RotateMotion {
+    /// The point about which this rotation is taking place
+    pub origin: (f32, f32),
+
+    /// Curve describing the angle of rotation over time. The x coordinate is the angle in degrees, the y coordinate is ignored
+    pub rotate: TimeCurve
+}
+
+impl RotateMotion {
+    ///
+    /// Creates a rotate motion that instantaneously turns something by a particular angle (in degrees)
+    ///
+    pub fn rotate_to(when: Duration, origin: (f32, f32), degrees: f32) -> RotateMotion {
+        let to_angle = TimePoint::new(degrees, 0.0, when);
+
+        RotateMotion {
+            origin:     origin,
+            rotate:     TimeCurve::new(to_angle, to_angle)
+        }
+    }
+
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the path of this motion
+    ///
+    #[inline]
+    pub fn set_path(&mut self, new_path: TimeCurve) {
+        self.rotate = new_path;
+    }
+}
+
+impl Default for RotateMotion {
+    ///
+    /// Creates a default rotate motion
+    ///
+    fn default() -> RotateMotion {
+        RotateMotion {
+            origin:     (0.0, 0.0),
+            rotate:     TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(0.0, 0.0, Duration::from_millis(0)))
+        }
+    }
+}
+
+impl MotionTransform for RotateMotion {
+    fn range_millis(&self) -> Range<f32> {
+        if self.rotate.points.len() == 0 {
+            0.0..0.0
+        } else {
+            let start   = self.rotate.points[0].point.milliseconds();
+            let end     = self.rotate.points.last().unwrap().point.milliseconds();
+
+            start..end
+        }
+    }
+
+    ///
+    /// Returns the transformations to apply for this motion at a particular point in time
+    ///
+    fn transformation(&self, when: Duration) -> SmallVec<[Transformation; 2]> {
+        let time_millis = ((when.as_secs() as f32) * 1_000.0) + ((when.subsec_nanos() as f32) / 1_000_000.0);
+        let origin      = (self.origin.0 as f64, self.origin.1 as f64);
+        let angle       = self.rotate.point_at_time(time_millis);
+
+        if let Some(angle) = angle {
+            let radians = (angle.0 as f64) * f64::consts::PI / 180.0;
+            smallvec![Transformation::Rotate(radians, origin)]
+        } else {
+            smallvec![]
+        }
+    }
+}
+
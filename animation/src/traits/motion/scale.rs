@@ -0,0 +1,90 @@
+use super::transform::*;
+use super::super::vector::*;
+use super::super::time_path::*;
+
+use smallvec::*;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes how an element is scaled over time
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScaleMotion {
+    /// The point about which this scaling is taking place
+    pub origin: (f32, f32),
+
+    /// Curve describing the scale factors over time. The x and y coordinates are the horizontal and vertical scale factors
+    pub scale: TimeCurve
+}
+
+impl ScaleMotion {
+    ///
+    /// Creates a scale motion that instantaneously resizes something by a particular factor
+    ///
+    pub fn scale_to(when: Duration, origin: (f32, f32), scale: (f32, f32)) -> ScaleMotion {
+        let to_scale = TimePoint::new(scale.0, scale.1, when);
+
+        ScaleMotion {
+            origin:     origin,
+            scale:      TimeCurve::new(to_scale, to_scale)
+        }
+    }
+
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the path of this motion
+    ///
+    #[inline]
+    pub fn set_path(&mut self, new_path: TimeCurve) {
+        self.scale = new_path;
+    }
+}
+
+impl Default for ScaleMotion {
+    ///
+    /// Creates a default scale motion
+    ///
+    fn default() -> ScaleMotion {
+        ScaleMotion {
+            origin:     (0.0, 0.0),
+            scale:      TimeCurve::new(TimePoint::new(1.0, 1.0, Duration::from_millis(0)), TimePoint::new(1.0, 1.0, Duration::from_millis(0)))
+        }
+    }
+}
+
+impl MotionTransform for ScaleMotion {
+    fn range_millis(&self) -> Range<f32> {
+        if self.scale.points.len() == 0 {
+            0.0..0.0
+        } else {
+            let start   = self.scale.points[0].point.milliseconds();
+            let end     = self.scale.points.last().unwrap().point.milliseconds();
+
+            start..end
+        }
+    }
+
+    ///
+    /// Returns the transformations to apply for this motion at a particular point in time
+    ///
+    fn transformation(&self, when: Duration) -> SmallVec<[Transformation; 2]> {
+        let time_millis = ((when.as_secs() as f32) * 1_000.0) + ((when.subsec_nanos() as f32) / 1_000_000.0);
+        let origin      = (self.origin.0 as f64, self.origin.1 as f64);
+        let scale       = self.scale.point_at_time(time_millis);
+
+        match scale {
+            // A zero scale factor can't be inverted, so it's treated as having no effect
+            Some(scale) if scale.0 != 0.0 && scale.1 != 0.0    => smallvec![Transformation::Scale(scale.0 as f64, scale.1 as f64, origin)],
+            _                                                   => smallvec![]
+        }
+    }
+}
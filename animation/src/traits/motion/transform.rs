@@ -18,4 +18,11 @@ pub trait MotionTransform {
     /// Returns the transformations to apply for this motion at a particular point in time
     ///
     fn transformation(&self, when: Duration) -> SmallVec<[Transformation; 2]>;
+
+    ///
+    /// Returns the opacity to apply for this motion at a particular point in time (motions that don't fade leave the opacity at 1.0)
+    ///
+    fn opacity(&self, _when: Duration) -> f64 {
+        1.0
+    }
 }
@@ -4,6 +4,7 @@ mod path;
 mod bounds;
 mod rect;
 mod curve;
+mod node;
 
 pub use self::point::*;
 pub use self::component::*;
@@ -11,3 +12,4 @@ pub use self::path::*;
 pub use self::bounds::*;
 pub use self::rect::*;
 pub use self::curve::*;
+pub use self::node::*;
@@ -0,0 +1,786 @@
+use super::path::*;
+use super::point::*;
+use super::component::*;
+
+use std::sync::*;
+
+///
+/// How the tangents either side of a node in a path are related to each other
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PathNodeType {
+    /// The tangents either side of the node are aligned so the path passes smoothly through it
+    Smooth,
+
+    /// The tangents are retracted into the node so the path changes direction sharply at it
+    Corner
+}
+
+///
+/// Describes the range of components that make up a subpath
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct SubpathRange {
+    /// Index of the first node (usually a move) in the subpath
+    start: usize,
+
+    /// Index after the last node in the subpath (the index of the 'close' component if the subpath is closed)
+    end: usize,
+
+    /// True if this subpath is closed
+    closed: bool
+}
+
+///
+/// Returns the point at the end of a path component (if it has one)
+///
+fn end_point(component: &PathComponent) -> Option<PathPoint> {
+    match component {
+        PathComponent::Move(point)          |
+        PathComponent::Line(point)          |
+        PathComponent::Bezier(point, _, _)  => Some(*point),
+        PathComponent::Close                => None
+    }
+}
+
+///
+/// Linear interpolation between two points
+///
+fn lerp(from: PathPoint, to: PathPoint, t: f64) -> PathPoint {
+    from + (to - from) * t
+}
+
+///
+/// Returns the length of the vector represented by a point
+///
+fn length(point: PathPoint) -> f64 {
+    (point.position.0*point.position.0 + point.position.1*point.position.1).sqrt()
+}
+
+///
+/// Returns the unit vector for a point, or None if the point is at the origin
+///
+fn unit(point: PathPoint) -> Option<PathPoint> {
+    let len = length(point);
+
+    if len > 1e-6 {
+        Some(point * (1.0/len))
+    } else {
+        None
+    }
+}
+
+///
+/// Converts a segment to a bezier curve (lines become curves with their control points a third of the way along the line)
+///
+fn as_bezier(start: PathPoint, component: &PathComponent) -> PathComponent {
+    match component {
+        PathComponent::Line(end)    => PathComponent::Bezier(*end, lerp(start, *end, 1.0/3.0), lerp(start, *end, 2.0/3.0)),
+        other                       => *other
+    }
+}
+
+///
+/// Divides a list of path components into subpaths
+///
+fn subpaths(components: &[PathComponent]) -> Vec<SubpathRange> {
+    let mut result  = vec![];
+    let mut start   = None;
+
+    for (index, component) in components.iter().enumerate() {
+        match component {
+            PathComponent::Move(_)  => {
+                if let Some(start) = start {
+                    result.push(SubpathRange { start: start, end: index, closed: false });
+                }
+                start = Some(index);
+            }
+
+            PathComponent::Close    => {
+                if let Some(start) = start.take() {
+                    result.push(SubpathRange { start: start, end: index, closed: true });
+                }
+            }
+
+            _                       => {
+                if start.is_none() { start = Some(index); }
+            }
+        }
+    }
+
+    if let Some(start) = start {
+        result.push(SubpathRange { start: start, end: components.len(), closed: false });
+    }
+
+    result
+}
+
+///
+/// Finds the subpath that contains a particular component
+///
+fn subpath_for_component(components: &[PathComponent], index: usize) -> Option<SubpathRange> {
+    subpaths(components).into_iter()
+        .find(|subpath| subpath.start <= index && (index < subpath.end || (subpath.closed && index == subpath.end)))
+}
+
+///
+/// Reverses the direction of the components in an open subpath
+///
+fn reverse_subpath(components: &[PathComponent]) -> Vec<PathComponent> {
+    let points  = components.iter().map(|component| end_point(component).unwrap_or_else(|| PathPoint::new(0.0, 0.0))).collect::<Vec<_>>();
+    let mut result  = vec![];
+
+    if let Some(last) = points.last() {
+        result.push(PathComponent::Move(*last));
+    }
+
+    for index in (1..components.len()).rev() {
+        match components[index] {
+            PathComponent::Bezier(_, cp1, cp2)  => result.push(PathComponent::Bezier(points[index-1], cp2, cp1)),
+            _                                   => result.push(PathComponent::Line(points[index-1]))
+        }
+    }
+
+    result
+}
+
+impl Path {
+    ///
+    /// Given the index of a control point (as returned by the `control_points()` call on a path element), returns the index of
+    /// the path component that ends at that point, if it's a node rather than a tangent control point
+    ///
+    /// Nodes in a path are identified by the index of the component that ends at them.
+    ///
+    pub fn node_for_control_point(&self, control_point_index: usize) -> Option<usize> {
+        let mut cp_index = 0;
+
+        for (component_index, component) in self.elements.iter().enumerate() {
+            let num_cps = match component {
+                PathComponent::Move(_) | PathComponent::Line(_) => 1,
+                PathComponent::Bezier(_, _, _)                  => 3,
+                PathComponent::Close                            => 0
+            };
+
+            if control_point_index < cp_index + num_cps {
+                // The node is always the last control point for a component
+                return if control_point_index == cp_index + num_cps - 1 {
+                    Some(component_index)
+                } else {
+                    None
+                };
+            }
+
+            cp_index += num_cps;
+        }
+
+        None
+    }
+
+    ///
+    /// Returns true if the specified node is at the start or the end of an open subpath (ie, can be joined to another node)
+    ///
+    pub fn is_end_node(&self, node: usize) -> bool {
+        subpaths(&self.elements).into_iter()
+            .any(|subpath| !subpath.closed && (subpath.start == node || subpath.end == node+1))
+    }
+
+    ///
+    /// Returns the type of a node: smooth if the tangents either side of it are aligned, or corner if they are not
+    ///
+    pub fn node_type(&self, node: usize) -> PathNodeType {
+        let components  = &*self.elements;
+        let subpath     = if let Some(subpath) = subpath_for_component(components, node) { subpath } else { return PathNodeType::Corner; };
+        if node >= subpath.end { return PathNodeType::Corner; }
+
+        // The ends of a closed subpath are joined if the path is closed with a segment returning to the start point
+        let ends_joined = subpath.closed && subpath.end-subpath.start > 2 && end_point(&components[subpath.start]) == end_point(&components[subpath.end-1]);
+
+        let incoming    = if node > subpath.start { Some(node) } else if ends_joined { Some(subpath.end-1) } else { None };
+        let outgoing    = if node+1 < subpath.end { Some(node+1) } else if ends_joined { Some(subpath.start+1) } else { None };
+
+        let point       = end_point(&components[node]).unwrap();
+
+        match (incoming.map(|idx| components[idx]), outgoing.map(|idx| components[idx])) {
+            (Some(PathComponent::Bezier(_, _, cp2)), Some(PathComponent::Bezier(_, cp1, _))) => {
+                match (unit(point - cp2), unit(cp1 - point)) {
+                    (Some(in_dir), Some(out_dir)) => {
+                        let cross   = in_dir.position.0*out_dir.position.1 - in_dir.position.1*out_dir.position.0;
+                        let dot     = in_dir.position.0*out_dir.position.0 + in_dir.position.1*out_dir.position.1;
+
+                        if cross.abs() < 0.01 && dot > 0.0 { PathNodeType::Smooth } else { PathNodeType::Corner }
+                    }
+
+                    _ => PathNodeType::Corner
+                }
+            }
+
+            _ => PathNodeType::Corner
+        }
+    }
+
+    ///
+    /// Finds the segment nearest to the specified point, returning the index of the component, the position along the segment
+    /// and the distance to the point
+    ///
+    /// The closing segment of a closed subpath is identified by the index of its 'close' component.
+    ///
+    pub fn nearest_segment(&self, point: (f32, f32)) -> Option<(usize, f32, f32)> {
+        const SAMPLES: usize = 32;
+
+        let target          = PathPoint::new(point.0, point.1);
+        let mut nearest     = None;
+        let mut min_dist    = f64::MAX;
+
+        for subpath in subpaths(&self.elements) {
+            let start_point = end_point(&self.elements[subpath.start]);
+            let start_point = if let Some(start_point) = start_point { start_point } else { continue; };
+            let mut last    = start_point;
+
+            let last_index  = if subpath.closed { subpath.end } else { subpath.end-1 };
+            for index in (subpath.start+1)..=last_index {
+                // Work out the curve for this segment
+                let component       = self.elements[index];
+                let (p1, p2, p3)    = match component {
+                    PathComponent::Bezier(end, cp1, cp2)    => (cp1, cp2, end),
+                    PathComponent::Line(end)                => (lerp(last, end, 1.0/3.0), lerp(last, end, 2.0/3.0), end),
+                    PathComponent::Close                    => (lerp(last, start_point, 1.0/3.0), lerp(last, start_point, 2.0/3.0), start_point),
+                    PathComponent::Move(end)                => (end, end, end)
+                };
+
+                // Sample the curve to find the closest point
+                for sample in 0..=SAMPLES {
+                    let t       = (sample as f64)/(SAMPLES as f64);
+                    let a       = lerp(last, p1, t);
+                    let b       = lerp(p1, p2, t);
+                    let c       = lerp(p2, p3, t);
+                    let pos     = lerp(lerp(a, b, t), lerp(b, c, t), t);
+                    let dist    = length(pos - target);
+
+                    if dist < min_dist {
+                        min_dist    = dist;
+                        nearest     = Some((index, t as f32, dist as f32));
+                    }
+                }
+
+                last = p3;
+            }
+        }
+
+        nearest
+    }
+
+    ///
+    /// Returns a new path with a node inserted into the segment ending at the specified component, at the position `t`
+    /// (0-1) along that segment
+    ///
+    /// The shape of the path is unchanged. Moves cannot be split, but the closing segment of a closed subpath can be
+    /// split by passing in the index of its 'close' component.
+    ///
+    pub fn with_node_inserted(&self, component_index: usize, t: f32) -> Path {
+        let components  = &*self.elements;
+        let subpath     = if let Some(subpath) = subpath_for_component(components, component_index) { subpath } else { return self.clone(); };
+
+        // The first node of a subpath has no segment leading to it
+        if component_index <= subpath.start { return self.clone(); }
+
+        let t           = (t as f64).max(0.0).min(1.0);
+        let start       = end_point(&components[component_index-1]);
+        let start       = if let Some(start) = start { start } else { return self.clone(); };
+
+        let replacement = match components[component_index] {
+            PathComponent::Line(end)            => vec![PathComponent::Line(lerp(start, end, t)), PathComponent::Line(end)],
+
+            PathComponent::Bezier(end, cp1, cp2) => {
+                // Split using de Casteljau's algorithm
+                let a = lerp(start, cp1, t);
+                let b = lerp(cp1, cp2, t);
+                let c = lerp(cp2, end, t);
+                let d = lerp(a, b, t);
+                let e = lerp(b, c, t);
+                let f = lerp(d, e, t);
+
+                vec![PathComponent::Bezier(f, a, d), PathComponent::Bezier(end, e, c)]
+            }
+
+            PathComponent::Close                => {
+                // The closing segment becomes a line to the new node followed by the close
+                let first = end_point(&components[subpath.start]).unwrap_or(start);
+                vec![PathComponent::Line(lerp(start, first, t)), PathComponent::Close]
+            }
+
+            PathComponent::Move(_)              => { return self.clone(); }
+        };
+
+        let mut new_components = components[0..component_index].to_vec();
+        new_components.extend(replacement);
+        new_components.extend(components[(component_index+1)..].iter().cloned());
+
+        Path::from_elements_arc(Arc::new(new_components))
+    }
+
+    ///
+    /// Returns a new path with the specified node removed, joining the segments either side of it
+    ///
+    /// If this leaves a subpath with only one node in it, the whole subpath is removed.
+    ///
+    pub fn with_node_deleted(&self, node: usize) -> Path {
+        let components  = &*self.elements;
+        let subpath     = if let Some(subpath) = subpath_for_component(components, node) { subpath } else { return self.clone(); };
+        if node >= subpath.end { return self.clone(); }
+
+        let mut new_components = components[0..subpath.start].to_vec();
+
+        if subpath.end - subpath.start <= 2 {
+            // Subpath would only have one node left: remove it entirely
+            let after_subpath = if subpath.closed { subpath.end+1 } else { subpath.end };
+            new_components.extend(components[after_subpath..].iter().cloned());
+        } else if node == subpath.start {
+            // The following node becomes the start of the subpath
+            let next_point = end_point(&components[node+1]).unwrap();
+            new_components.push(PathComponent::Move(next_point));
+            new_components.extend(components[(node+2)..].iter().cloned());
+        } else if node == subpath.end-1 {
+            // Removing the last node of a subpath (a closed subpath will use its implicit closing line)
+            new_components.extend(components[subpath.start..node].iter().cloned());
+            new_components.extend(components[(node+1)..].iter().cloned());
+        } else {
+            // Merge the segments either side of the node
+            let previous    = end_point(&components[node-1]).unwrap();
+            let this_point  = end_point(&components[node]).unwrap();
+            let merged      = match (components[node], components[node+1]) {
+                (PathComponent::Line(_), PathComponent::Line(end))  => PathComponent::Line(end),
+                (incoming, outgoing)                                => {
+                    let incoming = as_bezier(previous, &incoming);
+                    let outgoing = as_bezier(this_point, &outgoing);
+
+                    match (incoming, outgoing) {
+                        (PathComponent::Bezier(_, cp1, _), PathComponent::Bezier(end, _, cp2))  => PathComponent::Bezier(end, cp1, cp2),
+                        (_, outgoing)                                                           => outgoing
+                    }
+                }
+            };
+
+            new_components.extend(components[subpath.start..node].iter().cloned());
+            new_components.push(merged);
+            new_components.extend(components[(node+2)..].iter().cloned());
+        }
+
+        Path::from_elements_arc(Arc::new(new_components))
+    }
+
+    ///
+    /// Returns a new path with the tangents either side of a node made smooth or retracted to form a corner
+    ///
+    pub fn with_node_type(&self, node: usize, node_type: PathNodeType) -> Path {
+        let mut components  = (*self.elements).clone();
+        let subpath         = if let Some(subpath) = subpath_for_component(&components, node) { subpath } else { return self.clone(); };
+        if node >= subpath.end { return self.clone(); }
+
+        // The closing segment of a closed subpath is made explicit so it can have tangents
+        let mut subpath     = subpath;
+        if subpath.closed {
+            let first   = end_point(&components[subpath.start]).unwrap();
+            let last    = end_point(&components[subpath.end-1]).unwrap();
+
+            if first != last {
+                components.insert(subpath.end, PathComponent::Line(first));
+                subpath.end += 1;
+            }
+        }
+
+        // Find the segments coming into and leaving the node (the start and end of a closed subpath are the same node)
+        let incoming = if node > subpath.start {
+            Some(node)
+        } else if subpath.closed && subpath.end-subpath.start > 2 {
+            Some(subpath.end-1)
+        } else {
+            None
+        };
+
+        let outgoing = if node+1 < subpath.end {
+            Some(node+1)
+        } else if subpath.closed && subpath.end-subpath.start > 2 {
+            Some(subpath.start+1)
+        } else {
+            None
+        };
+
+        let point       = end_point(&components[node]).unwrap();
+        let start_of    = |components: &Vec<PathComponent>, index: usize| end_point(&components[index-1]).unwrap();
+
+        match node_type {
+            PathNodeType::Corner => {
+                // Retract the tangents into the node
+                if let Some(incoming) = incoming {
+                    if let PathComponent::Bezier(end, cp1, _) = components[incoming] {
+                        components[incoming] = PathComponent::Bezier(end, cp1, end);
+                    }
+                }
+
+                if let Some(outgoing) = outgoing {
+                    if let PathComponent::Bezier(end, _, cp2) = components[outgoing] {
+                        components[outgoing] = PathComponent::Bezier(end, start_of(&components, outgoing), cp2);
+                    }
+                }
+            }
+
+            PathNodeType::Smooth => {
+                // Both segments need to be curves to have tangents
+                if let Some(incoming) = incoming    { components[incoming] = as_bezier(start_of(&components, incoming), &components[incoming]); }
+                if let Some(outgoing) = outgoing    { components[outgoing] = as_bezier(start_of(&components, outgoing), &components[outgoing]); }
+
+                // Read the tangents (when a tangent has been retracted, it's given a length of a third of the segment)
+                let incoming_arm = incoming.and_then(|incoming| match components[incoming] {
+                    PathComponent::Bezier(end, _, cp2)  => {
+                        let chord   = end - start_of(&components, incoming);
+                        let arm     = end - cp2;
+                        if length(arm) > 1e-6 { Some(arm) } else { Some(chord * (1.0/3.0)) }
+                    },
+                    _                                   => None
+                });
+                let outgoing_arm = outgoing.and_then(|outgoing| match components[outgoing] {
+                    PathComponent::Bezier(end, cp1, _)  => {
+                        let start   = start_of(&components, outgoing);
+                        let chord   = end - start;
+                        let arm     = cp1 - start;
+                        if length(arm) > 1e-6 { Some(arm) } else { Some(chord * (1.0/3.0)) }
+                    },
+                    _                                   => None
+                });
+
+                // Work out the direction of the tangent through the node
+                let direction = match (incoming_arm.and_then(unit), outgoing_arm.and_then(unit)) {
+                    (Some(in_dir), Some(out_dir))   => unit(in_dir + out_dir).or(Some(out_dir)),
+                    (Some(in_dir), None)            => Some(in_dir),
+                    (None, Some(out_dir))           => Some(out_dir),
+                    (None, None)                    => None
+                };
+
+                // Update the control points so the tangents are aligned with the direction
+                if let Some(direction) = direction {
+                    if let (Some(incoming), Some(arm)) = (incoming, incoming_arm) {
+                        if let PathComponent::Bezier(end, cp1, _) = components[incoming] {
+                            components[incoming] = PathComponent::Bezier(end, cp1, point - direction * length(arm));
+                        }
+                    }
+
+                    if let (Some(outgoing), Some(arm)) = (outgoing, outgoing_arm) {
+                        if let PathComponent::Bezier(end, _, cp2) = components[outgoing] {
+                            components[outgoing] = PathComponent::Bezier(end, point + direction * length(arm), cp2);
+                        }
+                    }
+                }
+            }
+        }
+
+        Path::from_elements_arc(Arc::new(components))
+    }
+
+    ///
+    /// Returns a new path broken at the specified node
+    ///
+    /// An open subpath is split into two subpaths that both end at the node. A closed subpath is opened so that it
+    /// starts and ends at the node.
+    ///
+    pub fn with_break_at_node(&self, node: usize) -> Path {
+        let components  = &*self.elements;
+        let subpath     = if let Some(subpath) = subpath_for_component(components, node) { subpath } else { return self.clone(); };
+        if node >= subpath.end { return self.clone(); }
+
+        let point               = end_point(&components[node]).unwrap();
+        let mut new_components  = components[0..subpath.start].to_vec();
+
+        if subpath.closed {
+            // Rotate the subpath so it starts at the node
+            let first   = end_point(&components[subpath.start]).unwrap();
+            let last    = end_point(&components[subpath.end-1]).unwrap();
+
+            new_components.push(PathComponent::Move(point));
+            new_components.extend(components[(node+1)..subpath.end].iter().cloned());
+            if first != last { new_components.push(PathComponent::Line(first)); }
+            new_components.extend(components[(subpath.start+1)..=node].iter().cloned());
+
+            // Skip the 'close' component
+            new_components.extend(components[(subpath.end+1)..].iter().cloned());
+        } else {
+            // Nodes at the end of an open subpath can't be broken any further
+            if node == subpath.start || node+1 == subpath.end { return self.clone(); }
+
+            new_components.extend(components[subpath.start..=node].iter().cloned());
+            new_components.push(PathComponent::Move(point));
+            new_components.extend(components[(node+1)..].iter().cloned());
+        }
+
+        Path::from_elements_arc(Arc::new(new_components))
+    }
+
+    ///
+    /// Returns a new path with two end nodes joined together
+    ///
+    /// If the nodes are the two ends of the same subpath, the subpath is closed. If they're in different subpaths, the
+    /// subpaths are joined by a line between the nodes.
+    ///
+    pub fn with_nodes_joined(&self, first_node: usize, second_node: usize) -> Path {
+        let components  = &*self.elements;
+        let subpaths    = subpaths(components);
+
+        // Both nodes must be at the end of open subpaths
+        let first_subpath   = subpaths.iter().find(|subpath| !subpath.closed && (subpath.start == first_node || subpath.end == first_node+1)).cloned();
+        let second_subpath  = subpaths.iter().find(|subpath| !subpath.closed && (subpath.start == second_node || subpath.end == second_node+1)).cloned();
+
+        let (first_subpath, second_subpath) = match (first_subpath, second_subpath) {
+            (Some(first), Some(second)) => (first, second),
+            _                           => { return self.clone(); }
+        };
+
+        if first_subpath == second_subpath {
+            // Join the ends of a subpath by closing it
+            if first_node == second_node { return self.clone(); }
+
+            let mut new_components = components[0..first_subpath.end].to_vec();
+            new_components.push(PathComponent::Close);
+            new_components.extend(components[first_subpath.end..].iter().cloned());
+
+            Path::from_elements_arc(Arc::new(new_components))
+        } else {
+            // Orient the subpaths so that the first ends with the first node and the second starts with the second node
+            let first_components    = &components[first_subpath.start..first_subpath.end];
+            let first_components    = if first_node == first_subpath.start { reverse_subpath(first_components) } else { first_components.to_vec() };
+            let second_components   = &components[second_subpath.start..second_subpath.end];
+            let second_components   = if second_node+1 == second_subpath.end && second_node != second_subpath.start { reverse_subpath(second_components) } else { second_components.to_vec() };
+
+            // Join them with a line
+            let mut joined          = first_components;
+            let join_start          = joined.last().and_then(end_point);
+            let join_end            = second_components.first().and_then(end_point);
+
+            if let (Some(join_start), Some(join_end)) = (join_start, join_end) {
+                if join_start != join_end { joined.push(PathComponent::Line(join_end)); }
+            }
+            joined.extend(second_components.into_iter().skip(1));
+
+            // Replace the earlier of the two subpaths with the joined path and remove the later one
+            let mut new_components  = vec![];
+            let mut last_index      = 0;
+            let mut joined          = Some(joined);
+
+            for subpath in subpaths.iter() {
+                let subpath_end = if subpath.closed { subpath.end+1 } else { subpath.end };
+
+                if *subpath == first_subpath || *subpath == second_subpath {
+                    new_components.extend(components[last_index..subpath.start].iter().cloned());
+                    if let Some(joined) = joined.take() { new_components.extend(joined); }
+                    last_index = subpath_end;
+                }
+            }
+            new_components.extend(components[last_index..].iter().cloned());
+
+            Path::from_elements_arc(Arc::new(new_components))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::PathComponent::*;
+
+    fn point(x: f32, y: f32) -> PathPoint {
+        PathPoint::new(x, y)
+    }
+
+    fn square() -> Path {
+        Path::from_elements(vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Line(point(100.0, 100.0)), Line(point(0.0, 100.0)), Close])
+    }
+
+    fn open_line() -> Path {
+        Path::from_elements(vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Line(point(200.0, 0.0))])
+    }
+
+    #[test]
+    fn node_for_control_point() {
+        let path = Path::from_elements(vec![Move(point(0.0, 0.0)), Bezier(point(100.0, 0.0), point(30.0, 30.0), point(70.0, 30.0)), Line(point(100.0, 100.0))]);
+
+        assert!(path.node_for_control_point(0) == Some(0));
+        assert!(path.node_for_control_point(1) == None);
+        assert!(path.node_for_control_point(2) == None);
+        assert!(path.node_for_control_point(3) == Some(1));
+        assert!(path.node_for_control_point(4) == Some(2));
+        assert!(path.node_for_control_point(5) == None);
+    }
+
+    #[test]
+    fn insert_node_in_line() {
+        let path = open_line().with_node_inserted(1, 0.5);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Line(point(50.0, 0.0)), Line(point(100.0, 0.0)), Line(point(200.0, 0.0))]);
+    }
+
+    #[test]
+    fn insert_node_in_bezier() {
+        let path = Path::from_elements(vec![Move(point(0.0, 0.0)), Bezier(point(100.0, 0.0), point(0.0, 100.0), point(100.0, 100.0))]);
+        let path = path.with_node_inserted(1, 0.5);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Bezier(point(50.0, 75.0), point(0.0, 50.0), point(25.0, 75.0)), Bezier(point(100.0, 0.0), point(75.0, 75.0), point(100.0, 50.0))]);
+    }
+
+    #[test]
+    fn insert_node_in_closing_segment() {
+        let path = square().with_node_inserted(4, 0.5);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Line(point(100.0, 100.0)), Line(point(0.0, 100.0)), Line(point(0.0, 50.0)), Close]);
+    }
+
+    #[test]
+    fn delete_middle_node() {
+        let path = open_line().with_node_deleted(1);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Line(point(200.0, 0.0))]);
+    }
+
+    #[test]
+    fn delete_first_node() {
+        let path = square().with_node_deleted(0);
+
+        assert!(*path.elements == vec![Move(point(100.0, 0.0)), Line(point(100.0, 100.0)), Line(point(0.0, 100.0)), Close]);
+    }
+
+    #[test]
+    fn delete_node_between_curves() {
+        let path = Path::from_elements(vec![Move(point(0.0, 0.0)), Bezier(point(50.0, 75.0), point(0.0, 50.0), point(25.0, 75.0)), Bezier(point(100.0, 0.0), point(75.0, 75.0), point(100.0, 50.0))]);
+        let path = path.with_node_deleted(1);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Bezier(point(100.0, 0.0), point(0.0, 50.0), point(100.0, 50.0))]);
+    }
+
+    #[test]
+    fn delete_leaves_single_node() {
+        let path = Path::from_elements(vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Move(point(0.0, 50.0)), Line(point(100.0, 50.0))]);
+        let path = path.with_node_deleted(3);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0))]);
+    }
+
+    #[test]
+    fn make_corner_smooth() {
+        let path = open_line().with_node_type(1, PathNodeType::Smooth);
+
+        // Straight line so the tangents should already be aligned
+        if let (Bezier(_, _, cp2), Bezier(_, cp1, _)) = (path.elements[1], path.elements[2]) {
+            assert!((cp2.y()-0.0).abs() < 0.01);
+            assert!((cp1.y()-0.0).abs() < 0.01);
+            assert!(cp2.x() < 100.0);
+            assert!(cp1.x() > 100.0);
+        } else {
+            assert!(false, "Segments not converted to curves");
+        }
+    }
+
+    #[test]
+    fn smooth_square_corner() {
+        let path = square().with_node_type(1, PathNodeType::Smooth);
+
+        if let (Bezier(_, _, cp2), Bezier(_, cp1, _)) = (path.elements[1], path.elements[2]) {
+            // The tangents should be on opposite sides of the node, on the same line
+            let in_dir  = (100.0-cp2.x(), 0.0-cp2.y());
+            let out_dir = (cp1.x()-100.0, cp1.y()-0.0);
+            let cross   = in_dir.0*out_dir.1 - in_dir.1*out_dir.0;
+            let dot     = in_dir.0*out_dir.0 + in_dir.1*out_dir.1;
+
+            assert!(cross.abs() < 0.01);
+            assert!(dot > 0.0);
+        } else {
+            assert!(false, "Segments not converted to curves");
+        }
+    }
+
+    #[test]
+    fn node_types() {
+        let path = open_line();
+        assert!(path.node_type(1) == PathNodeType::Corner);
+
+        let path = path.with_node_type(1, PathNodeType::Smooth);
+        assert!(path.node_type(1) == PathNodeType::Smooth);
+
+        let path = path.with_node_type(1, PathNodeType::Corner);
+        assert!(path.node_type(1) == PathNodeType::Corner);
+    }
+
+    #[test]
+    fn make_corner() {
+        let path = Path::from_elements(vec![Move(point(0.0, 0.0)), Bezier(point(50.0, 75.0), point(0.0, 50.0), point(25.0, 75.0)), Bezier(point(100.0, 0.0), point(75.0, 75.0), point(100.0, 50.0))]);
+        let path = path.with_node_type(1, PathNodeType::Corner);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Bezier(point(50.0, 75.0), point(0.0, 50.0), point(50.0, 75.0)), Bezier(point(100.0, 0.0), point(50.0, 75.0), point(100.0, 50.0))]);
+    }
+
+    #[test]
+    fn break_open_path() {
+        let path = open_line().with_break_at_node(1);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Move(point(100.0, 0.0)), Line(point(200.0, 0.0))]);
+    }
+
+    #[test]
+    fn break_end_of_open_path() {
+        let path = open_line().with_break_at_node(2);
+
+        assert!(*path.elements == *open_line().elements);
+    }
+
+    #[test]
+    fn break_closed_path() {
+        let path = square().with_break_at_node(2);
+
+        assert!(*path.elements == vec![Move(point(100.0, 100.0)), Line(point(0.0, 100.0)), Line(point(0.0, 0.0)), Line(point(100.0, 0.0)), Line(point(100.0, 100.0))]);
+    }
+
+    #[test]
+    fn join_ends_of_subpath() {
+        let path = square().with_break_at_node(2);
+        let path = path.with_nodes_joined(4, 0);
+
+        assert!(*path.elements == vec![Move(point(100.0, 100.0)), Line(point(0.0, 100.0)), Line(point(0.0, 0.0)), Line(point(100.0, 0.0)), Line(point(100.0, 100.0)), Close]);
+    }
+
+    #[test]
+    fn join_two_subpaths() {
+        let path = Path::from_elements(vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Move(point(200.0, 0.0)), Line(point(300.0, 0.0))]);
+        let path = path.with_nodes_joined(1, 2);
+
+        assert!(*path.elements == vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Line(point(200.0, 0.0)), Line(point(300.0, 0.0))]);
+    }
+
+    #[test]
+    fn join_two_subpaths_by_their_starts() {
+        let path = Path::from_elements(vec![Move(point(0.0, 0.0)), Line(point(100.0, 0.0)), Move(point(0.0, 50.0)), Line(point(100.0, 50.0))]);
+        let path = path.with_nodes_joined(0, 2);
+
+        assert!(*path.elements == vec![Move(point(100.0, 0.0)), Line(point(0.0, 0.0)), Line(point(0.0, 50.0)), Line(point(100.0, 50.0))]);
+    }
+
+    #[test]
+    fn cannot_join_middle_node() {
+        let path = open_line().with_nodes_joined(1, 2);
+
+        assert!(*path.elements == *open_line().elements);
+    }
+
+    #[test]
+    fn nearest_segment() {
+        let (index, t, distance) = square().nearest_segment((100.0, 25.0)).unwrap();
+
+        assert!(index == 2);
+        assert!((t-0.25).abs() < 0.01);
+        assert!(distance < 0.1);
+    }
+
+    #[test]
+    fn nearest_closing_segment() {
+        let (index, t, distance) = square().nearest_segment((5.0, 50.0)).unwrap();
+
+        assert!(index == 4);
+        assert!((t-0.5).abs() < 0.01);
+        assert!((distance-5.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,5 @@
+mod text_style;
+mod text_font;
+
+pub use self::text_style::*;
+pub use self::text_font::*;
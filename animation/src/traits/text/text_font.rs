@@ -0,0 +1,262 @@
+use super::text_style::*;
+use super::super::path::*;
+
+///
+/// The fonts that are bundled with FlowBetween (these are always available, so text can be rendered without access to the system fonts)
+///
+const LATO_REGULAR: &[u8]   = include_bytes!("../../../fonts/lato/Lato-Regular.ttf");
+const LATO_BOLD: &[u8]      = include_bytes!("../../../fonts/lato/Lato-Bold.ttf");
+const LATO_THIN: &[u8]      = include_bytes!("../../../fonts/lato/Lato-Thin.ttf");
+
+///
+/// The fonts that can be used to draw text elements
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TextFont {
+    Regular,
+    Bold,
+    Thin
+}
+
+impl Default for TextFont {
+    fn default() -> TextFont {
+        TextFont::Regular
+    }
+}
+
+impl TextFont {
+    ///
+    /// Returns all of the fonts that are available
+    ///
+    pub fn all() -> Vec<TextFont> {
+        vec![TextFont::Regular, TextFont::Bold, TextFont::Thin]
+    }
+
+    ///
+    /// The name of this font, as stored in a text style
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextFont::Regular   => "Lato",
+            TextFont::Bold      => "Lato Bold",
+            TextFont::Thin      => "Lato Thin"
+        }
+    }
+
+    ///
+    /// Finds the font with the specified name (the default font is used if the name is not recognised)
+    ///
+    pub fn from_name(name: &str) -> TextFont {
+        TextFont::all().into_iter()
+            .find(|font| font.name() == name)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Retrieves the TrueType data for this font
+    ///
+    fn font_data(&self) -> &'static [u8] {
+        match self {
+            TextFont::Regular   => LATO_REGULAR,
+            TextFont::Bold      => LATO_BOLD,
+            TextFont::Thin      => LATO_THIN
+        }
+    }
+
+    ///
+    /// Generates the outlines of the glyphs for some text, one path per glyph
+    ///
+    /// The position is the start of the baseline of the first line of text for left-aligned text (or the middle or the end for centered
+    /// or right-aligned text). Lines are separated by newline characters, and further lines are drawn below the first one.
+    ///
+    pub fn outline(&self, text: &str, size: f32, alignment: TextAlignment, position: (f32, f32)) -> Vec<Path> {
+        let face = match ttf_parser::Face::parse(self.font_data(), 0) {
+            Ok(face)    => face,
+            Err(_)      => { return vec![]; }
+        };
+
+        // Font units are scaled so the em square is the requested size
+        let scale       = size / (face.units_per_em() as f32);
+        let line_height = ((face.ascender() - face.descender() + face.line_gap()) as f32) * scale;
+        let (x, y)      = position;
+        let mut paths   = vec![];
+
+        for (line_num, line) in text.lines().enumerate() {
+            // Characters that aren't in the font are drawn as the 'missing glyph' symbol
+            let glyphs      = line.chars()
+                .map(|chr| face.glyph_index(chr).unwrap_or(ttf_parser::GlyphId(0)))
+                .collect::<Vec<_>>();
+            let advance     = |glyph| (face.glyph_hor_advance(glyph).unwrap_or(0) as f32) * scale;
+
+            // Work out where the line starts from its width
+            let width       = glyphs.iter().map(|glyph| advance(*glyph)).sum::<f32>();
+            let mut pos_x   = match alignment {
+                TextAlignment::Left     => x,
+                TextAlignment::Center   => x - width/2.0,
+                TextAlignment::Right    => x - width
+            };
+            let pos_y       = y - (line_num as f32) * line_height;
+
+            for glyph in glyphs {
+                let mut builder = GlyphPathBuilder::new(scale, (pos_x, pos_y));
+                face.outline_glyph(glyph, &mut builder);
+
+                if builder.components.len() > 0 {
+                    paths.push(Path::from_elements(builder.components));
+                }
+
+                pos_x += advance(glyph);
+            }
+        }
+
+        paths
+    }
+}
+
+///
+/// Converts the outline of a glyph into path components
+///
+struct GlyphPathBuilder {
+    /// Scale factor from font units to canvas units
+    scale: f32,
+
+    /// Where the origin of the glyph is on the canvas
+    origin: (f32, f32),
+
+    /// The start of the current contour
+    start_point: PathPoint,
+
+    /// The last point that was added to the path
+    last_point: PathPoint,
+
+    /// The components generated so far
+    components: Vec<PathComponent>
+}
+
+impl GlyphPathBuilder {
+    ///
+    /// Creates a new glyph path builder
+    ///
+    fn new(scale: f32, origin: (f32, f32)) -> GlyphPathBuilder {
+        GlyphPathBuilder {
+            scale:          scale,
+            origin:         origin,
+            start_point:    PathPoint::new(origin.0, origin.1),
+            last_point:     PathPoint::new(origin.0, origin.1),
+            components:     vec![]
+        }
+    }
+
+    ///
+    /// Converts a point in font units to a point on the canvas
+    ///
+    #[inline]
+    fn point(&self, x: f32, y: f32) -> PathPoint {
+        PathPoint::new(self.origin.0 + x*self.scale, self.origin.1 + y*self.scale)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point           = self.point(x, y);
+        self.start_point    = point;
+        self.last_point     = point;
+
+        self.components.push(PathComponent::Move(point));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point           = self.point(x, y);
+        self.last_point     = point;
+
+        self.components.push(PathComponent::Line(point));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        // TrueType fonts use quadratic curves, which are converted to the equivalent cubic curves
+        let start           = self.last_point;
+        let control         = self.point(x1, y1);
+        let end             = self.point(x, y);
+
+        let cp1             = PathPoint::new(start.x() + (control.x()-start.x())*2.0/3.0, start.y() + (control.y()-start.y())*2.0/3.0);
+        let cp2             = PathPoint::new(end.x() + (control.x()-end.x())*2.0/3.0, end.y() + (control.y()-end.y())*2.0/3.0);
+
+        self.last_point     = end;
+        self.components.push(PathComponent::Bezier(end, cp1, cp2));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let cp1             = self.point(x1, y1);
+        let cp2             = self.point(x2, y2);
+        let end             = self.point(x, y);
+
+        self.last_point     = end;
+        self.components.push(PathComponent::Bezier(end, cp1, cp2));
+    }
+
+    fn close(&mut self) {
+        self.last_point     = self.start_point;
+
+        self.components.push(PathComponent::Close);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn font_names_round_trip() {
+        for font in TextFont::all() {
+            assert!(TextFont::from_name(font.name()) == font);
+        }
+    }
+
+    #[test]
+    fn unknown_font_is_default() {
+        assert!(TextFont::from_name("Not a font") == TextFont::Regular);
+    }
+
+    #[test]
+    fn outline_has_path_per_glyph() {
+        let paths = TextFont::Regular.outline("Hello", 24.0, TextAlignment::Left, (0.0, 0.0));
+
+        assert!(paths.len() == 5);
+    }
+
+    #[test]
+    fn spaces_have_no_outline() {
+        let paths = TextFont::Regular.outline("a b", 24.0, TextAlignment::Left, (0.0, 0.0));
+
+        assert!(paths.len() == 2);
+    }
+
+    #[test]
+    fn outline_is_near_position() {
+        let paths   = TextFont::Regular.outline("H", 100.0, TextAlignment::Left, (200.0, 300.0));
+        let bounds  = paths.iter().fold(Rect::empty(), |bounds, path| bounds.union(Rect::from(path))).normalize();
+
+        // 'H' sits on the baseline and is a bit less than the font size high
+        assert!(bounds.x1 >= 200.0 && bounds.x1 < 220.0);
+        assert!((bounds.y1 - 300.0).abs() < 1.0);
+        assert!(bounds.y2 > 350.0 && bounds.y2 < 400.0);
+    }
+
+    #[test]
+    fn right_aligned_text_ends_at_position() {
+        let paths   = TextFont::Regular.outline("H", 100.0, TextAlignment::Right, (200.0, 0.0));
+        let bounds  = paths.iter().fold(Rect::empty(), |bounds, path| bounds.union(Rect::from(path))).normalize();
+
+        assert!(bounds.x2 <= 200.0 && bounds.x2 > 180.0);
+    }
+
+    #[test]
+    fn second_line_is_below_first() {
+        let paths   = TextFont::Regular.outline("H\nH", 100.0, TextAlignment::Left, (0.0, 0.0));
+        let first   = Rect::from(&paths[0]).normalize();
+        let second  = Rect::from(&paths[1]).normalize();
+
+        assert!(paths.len() == 2);
+        assert!(second.y2 < first.y1);
+    }
+}
@@ -0,0 +1,56 @@
+use super::text_font::*;
+
+use flo_canvas::*;
+
+///
+/// How the lines of a text element are aligned relative to its position
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TextAlignment {
+    /// Lines start at the position of the text element
+    Left,
+
+    /// Lines are centered on the position of the text element
+    Center,
+
+    /// Lines end at the position of the text element
+    Right
+}
+
+///
+/// Describes how a text element is drawn
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextStyle {
+    /// The name of the font to draw the text with (see `TextFont` for the fonts that are available)
+    pub font: String,
+
+    /// The size of the font, in canvas units
+    pub size: f32,
+
+    /// The colour of the text
+    pub color: Color,
+
+    /// How the lines of text are aligned
+    pub alignment: TextAlignment
+}
+
+impl TextStyle {
+    ///
+    /// Creates a new text style with the settings at their defaults
+    ///
+    pub fn new() -> TextStyle {
+        TextStyle {
+            font:       TextFont::default().name().to_string(),
+            size:       24.0,
+            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            alignment:  TextAlignment::Left
+        }
+    }
+}
+
+impl Default for TextStyle {
+    fn default() -> TextStyle {
+        TextStyle::new()
+    }
+}
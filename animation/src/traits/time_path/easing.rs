@@ -0,0 +1,128 @@
+///
+/// Describes how the progress through a section of a time curve changes over time
+///
+/// Easing maps the fraction of the time that has passed in a section (0.0 at the start, 1.0 at the end)
+/// to the fraction of the section that should have been travelled.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Easing {
+    /// Moves at a constant rate through the section
+    Linear,
+
+    /// Starts slowly and speeds up towards the end of the section
+    EaseIn,
+
+    /// Starts quickly and slows down towards the end of the section
+    EaseOut,
+
+    /// Starts slowly, speeds up and slows down again at the end of the section
+    EaseInOut,
+
+    /// Follows a cubic bezier curve from (0,0) to (1,1) with the two specified control points (x1, y1, x2, y2), in the same way as the CSS `cubic-bezier()` function
+    CubicBezier(f32, f32, f32, f32),
+
+    /// Holds at the start of the section until its end time is reached, then jumps to the end
+    Step
+}
+
+impl Default for Easing {
+    fn default() -> Easing {
+        Easing::Linear
+    }
+}
+
+impl Easing {
+    ///
+    /// Returns the fraction of a section that has been travelled after a fraction of its time has passed
+    ///
+    pub fn ease(&self, time_fraction: f64) -> f64 {
+        use self::Easing::*;
+
+        let time_fraction = time_fraction.max(0.0).min(1.0);
+
+        match self {
+            Linear                      => time_fraction,
+            EaseIn                      => Self::cubic_bezier(0.42, 0.0, 1.0, 1.0, time_fraction),
+            EaseOut                     => Self::cubic_bezier(0.0, 0.0, 0.58, 1.0, time_fraction),
+            EaseInOut                   => Self::cubic_bezier(0.42, 0.0, 0.58, 1.0, time_fraction),
+            CubicBezier(x1, y1, x2, y2) => Self::cubic_bezier(*x1 as f64, *y1 as f64, *x2 as f64, *y2 as f64, time_fraction),
+            Step                        => if time_fraction >= 1.0 { 1.0 } else { 0.0 }
+        }
+    }
+
+    ///
+    /// Evaluates one coordinate of a cubic bezier curve with endpoints at 0 and 1
+    ///
+    #[inline]
+    fn bezier_component(p1: f64, p2: f64, t: f64) -> f64 {
+        let inv_t = 1.0 - t;
+
+        3.0*inv_t*inv_t*t*p1 + 3.0*inv_t*t*t*p2 + t*t*t
+    }
+
+    ///
+    /// Finds the y value of a cubic bezier easing curve at a particular x value
+    ///
+    fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64, x: f64) -> f64 {
+        // The x control points are clamped so that the curve is a function of x, which makes it possible to solve by bisection
+        let x1 = x1.max(0.0).min(1.0);
+        let x2 = x2.max(0.0).min(1.0);
+
+        let mut min_t = 0.0;
+        let mut max_t = 1.0;
+
+        for _ in 0..32 {
+            let mid_t = (min_t + max_t) / 2.0;
+
+            if Self::bezier_component(x1, x2, mid_t) < x {
+                min_t = mid_t;
+            } else {
+                max_t = mid_t;
+            }
+        }
+
+        Self::bezier_component(y1, y2, (min_t + max_t) / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_easings_start_and_end_at_the_section_ends() {
+        for easing in vec![Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::CubicBezier(0.1, 0.7, 0.3, 1.2), Easing::Step] {
+            assert!(easing.ease(0.0).abs() < 0.001);
+            assert!((easing.ease(1.0) - 1.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn ease_in_is_slow_at_start() {
+        assert!(Easing::EaseIn.ease(0.25) < 0.25);
+        assert!(Easing::EaseOut.ease(0.25) > 0.25);
+    }
+
+    #[test]
+    fn ease_in_out_is_symmetric() {
+        assert!((Easing::EaseInOut.ease(0.5) - 0.5).abs() < 0.001);
+        assert!((Easing::EaseInOut.ease(0.2) + Easing::EaseInOut.ease(0.8) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn linear_cubic_bezier_is_linear() {
+        let easing = Easing::CubicBezier(1.0/3.0, 1.0/3.0, 2.0/3.0, 2.0/3.0);
+
+        for step in 0..=10 {
+            let t = (step as f64) / 10.0;
+            assert!((easing.ease(t) - t).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn step_holds_until_end() {
+        assert!(Easing::Step.ease(0.5) == 0.0);
+        assert!(Easing::Step.ease(0.999) == 0.0);
+        assert!(Easing::Step.ease(1.0) == 1.0);
+    }
+}
@@ -101,7 +101,8 @@ impl TimeCurve {
                         start:          previous.point,
                         end:            next.point,
                         control_point1: previous.future,
-                        control_point2: next.past
+                        control_point2: next.past,
+                        easing:         previous.easing
                     };
 
                     // Find where to subdivide
@@ -119,7 +120,8 @@ impl TimeCurve {
                         let mut new_point   = TimeControlPoint {
                             point:  first_section.end,
                             past:   first_section.control_point2,
-                            future: next_section.control_point1
+                            future: next_section.control_point1,
+                            easing: previous.easing
                         };
 
                         // Move the new point to its new location
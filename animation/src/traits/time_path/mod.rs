@@ -1,11 +1,13 @@
 mod time_point;
 mod time_control_point;
 mod time_curve;
+mod easing;
 mod convert;
 mod edit;
 
 pub use self::time_point::*;
 pub use self::time_control_point::*;
 pub use self::time_curve::*;
+pub use self::easing::*;
 pub use self::convert::*;
 pub use self::edit::*;
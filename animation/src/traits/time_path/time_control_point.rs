@@ -1,3 +1,4 @@
+use super::easing::*;
 use super::time_point::*;
 
 ///
@@ -17,7 +18,10 @@ pub struct TimeControlPoint {
     pub past: TimePoint,
 
     /// The control point in the future
-    pub future: TimePoint
+    pub future: TimePoint,
+
+    /// The easing to use for the section of the curve that starts at this point
+    pub easing: Easing
 }
 
 impl TimeControlPoint {
@@ -26,7 +30,8 @@ impl TimeControlPoint {
     ///
     pub fn new(past: TimePoint, point: TimePoint, future: TimePoint) -> TimeControlPoint {
         TimeControlPoint {
-            past, point, future
+            past, point, future,
+            easing: Easing::Linear
         }
     }
 
@@ -1,3 +1,4 @@
+use super::easing::*;
 use super::time_point::*;
 use super::time_control_point::*;
 
@@ -25,7 +26,8 @@ pub struct TimeCurveSection {
     pub start: TimePoint,
     pub control_point1: TimePoint,
     pub control_point2: TimePoint,
-    pub end: TimePoint
+    pub end: TimePoint,
+    pub easing: Easing
 }
 
 impl TimeCurve {
@@ -52,7 +54,8 @@ impl TimeCurve {
                 start:              self.points[index].point,
                 end:                self.points[index+1].point,
                 control_point1:     self.points[index].future,
-                control_point2:     self.points[index+1].past
+                control_point2:     self.points[index+1].past,
+                easing:             self.points[index].easing
             })
         }
 
@@ -77,6 +80,35 @@ impl TimeCurve {
             .unwrap_or(None)
     }
 
+    ///
+    /// Sets the easing for the section of this curve that starts at the specified point
+    ///
+    /// Sections are numbered from 0, and there is one fewer section than there are points in the curve
+    ///
+    pub fn set_easing(&mut self, section: usize, easing: Easing) {
+        if section+1 < self.points.len() {
+            self.points[section].easing = easing;
+        }
+    }
+
+    ///
+    /// Returns a copy of this curve with every point moved by the specified number of milliseconds
+    ///
+    pub fn with_time_offset(&self, offset_millis: f32) -> TimeCurve {
+        let offset = TimePoint(0.0, 0.0, offset_millis);
+
+        TimeCurve {
+            points: self.points.iter()
+                .map(|point| TimeControlPoint {
+                    past:   point.past + offset,
+                    point:  point.point + offset,
+                    future: point.future + offset,
+                    easing: point.easing
+                })
+                .collect()
+        }
+    }
+
     ///
     /// Returns true if this curve is a close match to another (mainly useful for testing)
     ///
@@ -91,7 +123,8 @@ impl TimeCurve {
                 .all(|(p1, p2)| {
                     (p1.past).is_close_to(&p2.past) &&
                     (p1.point).is_close_to(&p2.point) &&
-                    (p1.future).is_close_to(&p2.future)
+                    (p1.future).is_close_to(&p2.future) &&
+                    p1.easing == p2.easing
                 })
         }
     }
@@ -102,11 +135,25 @@ impl TimeCurveSection {
     /// Solves for the point on this curve at the specified time (if it exists)
     ///
     pub fn point_at_time(&self, milliseconds: f32) -> Option<TimePoint> {
-        let t_points = self.search_with_bounds(DELTA as f64, |min, max| min.milliseconds() <= milliseconds && max.milliseconds() >= milliseconds);
+        // The easing changes how far along the curve we are at the specified time
+        let start_millis    = self.start.milliseconds();
+        let end_millis      = self.end.milliseconds();
+        let eased_millis    = if self.easing == Easing::Linear || end_millis <= start_millis {
+            milliseconds
+        } else {
+            // Bezier easings can overshoot the ends of the section, so the result is clamped to the section (this holds at the end until the easing comes back)
+            let time_fraction   = ((milliseconds - start_millis) / (end_millis - start_millis)) as f64;
+            let eased_millis    = start_millis + (self.easing.ease(time_fraction) as f32) * (end_millis - start_millis);
+
+            eased_millis.max(start_millis).min(end_millis)
+        };
+
+        let t_points = self.search_with_bounds(DELTA as f64, |min, max| min.milliseconds() <= eased_millis && max.milliseconds() >= eased_millis);
 
         t_points.into_iter()
             .nth(0)
             .map(|point_t| self.point_at_pos(point_t))
+            .map(|point| if eased_millis != milliseconds { TimePoint(point.0, point.1, milliseconds) } else { point })
     }
 }
 
@@ -202,7 +249,8 @@ impl BezierCurveFactory for TimeCurveSection {
     #[inline]
     fn from_points(start: Self::Point, (control_point1, control_point2): (Self::Point, Self::Point), end: Self::Point) -> Self {
         TimeCurveSection {
-            start, control_point1, control_point2, end
+            start, control_point1, control_point2, end,
+            easing: Easing::Linear
         }
     }
 }
@@ -312,4 +360,82 @@ mod test {
         assert!(time_curve.point_at_time(-100.0) == None);
         assert!(time_curve.point_at_time(1001.0) == None);
     }
+
+    #[test]
+    pub fn eased_curve_keeps_end_points() {
+        let start_point         = TimePoint::new(20.0, 30.0, Duration::from_millis(0));
+        let end_point           = TimePoint::new(130.0, 110.0, Duration::from_millis(1000));
+        let mut time_curve      = TimeCurve::new(start_point, end_point);
+        time_curve.set_easing(0, Easing::EaseInOut);
+
+        assert!(time_curve.point_at_time(0.0).unwrap().distance_to(&start_point) <= 1.0);
+        assert!(time_curve.point_at_time(1000.0).unwrap().distance_to(&end_point) <= 1.0);
+    }
+
+    #[test]
+    pub fn ease_in_lags_behind_linear() {
+        let start_point         = TimePoint::new(0.0, 0.0, Duration::from_millis(0));
+        let end_point           = TimePoint::new(100.0, 0.0, Duration::from_millis(1000));
+        let mut time_curve      = TimeCurve::new(start_point, end_point);
+        time_curve.set_easing(0, Easing::EaseIn);
+
+        let quarter_point       = time_curve.point_at_time(250.0).unwrap();
+
+        assert!(quarter_point.0 < 20.0);
+        assert!((quarter_point.milliseconds() - 250.0).abs() < 0.01);
+    }
+
+    #[test]
+    pub fn overshooting_easing_stays_on_the_curve() {
+        let start_point         = TimePoint::new(0.0, 0.0, Duration::from_millis(0));
+        let end_point           = TimePoint::new(100.0, 0.0, Duration::from_millis(1000));
+        let mut time_curve      = TimeCurve::new(start_point, end_point);
+        time_curve.set_easing(0, Easing::CubicBezier(0.1, 0.7, 0.3, 1.2));
+
+        // The easing goes past the end of the section before coming back to it
+        for step in 1..10 {
+            let time    = (step as f32) * 100.0;
+            let point   = time_curve.point_at_time(time);
+
+            assert!(point.is_some());
+
+            let point   = point.unwrap();
+            assert!((point.milliseconds() - time).abs() < 0.01);
+            assert!(point.0 >= -1.0 && point.0 <= 101.0);
+        }
+
+        // The point at the end of the section is still the end point
+        assert!(time_curve.point_at_time(1000.0).unwrap().distance_to(&end_point) <= 1.0);
+    }
+
+    #[test]
+    pub fn step_holds_start_point() {
+        let start_point         = TimePoint::new(20.0, 30.0, Duration::from_millis(0));
+        let end_point           = TimePoint::new(130.0, 110.0, Duration::from_millis(1000));
+        let mut time_curve      = TimeCurve::new(start_point, end_point);
+        time_curve.set_easing(0, Easing::Step);
+
+        assert!(time_curve.point_at_time(999.0).unwrap().distance_to(&start_point) <= 1.0);
+        assert!(time_curve.point_at_time(1000.0).unwrap().distance_to(&end_point) <= 1.0);
+    }
+
+    #[test]
+    pub fn set_easing_ignores_missing_sections() {
+        let mut time_curve      = TimeCurve::new(TimePoint::new(20.0, 30.0, Duration::from_millis(0)), TimePoint::new(130.0, 110.0, Duration::from_millis(1000)));
+        time_curve.set_easing(1, Easing::Step);
+
+        assert!(time_curve.points.iter().all(|point| point.easing == Easing::Linear));
+    }
+
+    #[test]
+    pub fn offset_curve_moves_in_time() {
+        let start_point         = TimePoint::new(20.0, 30.0, Duration::from_millis(0));
+        let end_point           = TimePoint::new(130.0, 110.0, Duration::from_millis(1000));
+        let time_curve          = TimeCurve::new(start_point, end_point);
+        let offset_curve        = time_curve.with_time_offset(500.0);
+
+        assert!(offset_curve.point_at_time(500.0).unwrap().distance_to(&start_point) <= 1.0);
+        assert!(offset_curve.point_at_time(1500.0).unwrap().distance_to(&end_point) <= 1.0);
+        assert!(offset_curve.point_at_time(1750.0).is_none());
+    }
 }
@@ -72,7 +72,9 @@ impl BrushElement {
                 position:   transform(point.position),
                 cp1:        transform(point.cp1),
                 cp2:        transform(point.cp2),
-                width:      point.width
+                width:      point.width,
+                opacity:    point.opacity,
+                color_mix:  point.color_mix
             }
         };
 
@@ -151,8 +153,8 @@ impl VectorElement for BrushElement {
             .map(|Coord2(x, y)| (x as f32, y as f32))
             .collect::<Vec<_>>();
 
-        // The widths are kept the same as they are in this element
-        let widths = self.points.iter().map(|point| point.width);
+        // The widths, opacities and colours are kept the same as they are in this element
+        let widths = self.points.iter().map(|point| (point.width, point.opacity, point.color_mix));
 
         // The first element still has two control points, but we only actually care about its position. Generate two fake control points here.
         let initial_pos             = new_positions[0];
@@ -165,11 +167,13 @@ impl VectorElement for BrushElement {
         let brush_elements          = initial_control_points.into_iter().chain(new_positions)
             .tuples()
             .zip(widths)
-            .map(|((cp1, cp2, pos), width)| BrushPoint {
+            .map(|((cp1, cp2, pos), (width, opacity, color_mix))| BrushPoint {
                 position:   pos,
                 cp1:        cp1,
                 cp2:        cp2,
-                width:      width
+                width:      width,
+                opacity:    opacity,
+                color_mix:  color_mix
             });
 
         // Create a new brush element
@@ -198,21 +202,27 @@ mod test {
                 position: (1.0, 2.0),
                 cp1: (3.0, 4.0),
                 cp2: (5.0, 6.0),
-                width: 0.5
+                width: 0.5,
+                opacity: 1.0,
+                color_mix: 0.0
             },
 
             BrushPoint {
                 position: (7.0, 8.0),
                 cp1: (9.0, 10.0),
                 cp2: (11.0, 12.0),
-                width: 0.6
+                width: 0.6,
+                opacity: 1.0,
+                color_mix: 0.0
             },
 
             BrushPoint {
                 position: (13.0, 14.0),
                 cp1: (15.0, 16.0),
                 cp2: (17.0, 18.0),
-                width: 0.7
+                width: 0.7,
+                opacity: 1.0,
+                color_mix: 0.0
             },
         ];
         let element = BrushElement::new(ElementId::Assigned(5), Arc::new(points));
@@ -236,21 +246,27 @@ mod test {
                 position: (1.0, 2.0),
                 cp1: (3.0, 4.0),
                 cp2: (5.0, 6.0),
-                width: 0.5
+                width: 0.5,
+                opacity: 1.0,
+                color_mix: 0.0
             },
 
             BrushPoint {
                 position: (7.0, 8.0),
                 cp1: (9.0, 10.0),
                 cp2: (11.0, 12.0),
-                width: 0.6
+                width: 0.6,
+                opacity: 1.0,
+                color_mix: 0.0
             },
 
             BrushPoint {
                 position: (13.0, 14.0),
                 cp1: (15.0, 16.0),
                 cp2: (17.0, 18.0),
-                width: 0.7
+                width: 0.7,
+                opacity: 1.0,
+                color_mix: 0.0
             },
         ];
         let element = BrushElement::new(ElementId::Assigned(5), Arc::new(points));
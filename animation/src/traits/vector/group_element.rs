@@ -72,12 +72,19 @@ impl GroupElement {
     /// Renders the contents of this group in 'normal' mode
     ///
     fn render_normal(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, when: Duration) {
+        Self::render_elements(self.grouped_elements.iter(), gc, properties, when);
+    }
+
+    ///
+    /// Renders a set of elements one after the other, using their attachments to update the properties
+    ///
+    fn render_elements<'a, Elements: Iterator<Item=&'a Vector>>(elements: Elements, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, when: Duration) {
         // Properties update internally to the group
         let default_properties      = Arc::new(properties.clone());
         let mut properties          = Arc::clone(&default_properties);
         let mut active_attachments  = vec![];
 
-        for elem in self.grouped_elements.iter() {
+        for elem in elements {
             // Retrieve the attachments for the element
             let element_attachments     = (properties.retrieve_attachments)(elem.id());
 
@@ -125,12 +132,161 @@ impl GroupElement {
         }
     }
 
+    ///
+    /// Returns the paths for a single element in this group, divided into subpaths
+    ///
+    fn element_subpaths(element: &Vector, properties: &VectorProperties) -> Vec<Path> {
+        element.to_path(properties, PathConversion::RemoveInteriorPoints)
+            .unwrap_or_else(|| vec![])
+            .into_iter()
+            .flat_map(|path| path.to_subpaths())
+            .collect()
+    }
+
+    ///
+    /// Returns the path of the first element in this group and the combined path of all the other elements
+    ///
+    fn first_and_remaining_paths(&self, properties: &VectorProperties) -> (Vec<Path>, Vec<Path>) {
+        let first       = self.grouped_elements.iter().take(1)
+            .flat_map(|elem| Self::element_subpaths(elem, properties))
+            .collect::<Vec<_>>();
+        let remaining   = self.grouped_elements.iter().skip(1)
+            .map(|elem| Self::element_subpaths(elem, properties))
+            .filter(|paths| paths.len() > 0)
+            .collect::<Vec<_>>();
+
+        let remaining   = if remaining.len() > 0 {
+            path_add_chain::<_, Path>(&remaining, 0.01)
+        } else {
+            vec![]
+        };
+
+        (first, remaining)
+    }
+
+    ///
+    /// Returns the subtracted path for this element (the first element with the other elements removed from it)
+    ///
+    fn subtracted_path(&self, properties: &VectorProperties) -> Vec<Path> {
+        if let Some(hint_path) = self.hint_path.as_ref() {
+            // If a hint path has been set we can use this as the short-circuit for this path
+            (**hint_path).clone()
+        } else {
+            let (first, remaining) = self.first_and_remaining_paths(properties);
+
+            if first.len() == 0 {
+                // Nothing to subtract from
+                vec![]
+            } else if remaining.len() == 0 {
+                // Nothing to subtract
+                vec![Path::from_paths(&first)]
+            } else {
+                let paths = path_sub::<_, _, _, Path>(&first, &remaining, 0.01);
+                vec![Path::from_paths(&paths)]
+            }
+        }
+    }
+
+    ///
+    /// Returns the path covered by this element when it's a mask (the parts of the remaining elements that are inside the first element,
+    /// or outside of it for an inverted mask)
+    ///
+    fn masked_path(&self, properties: &VectorProperties) -> Vec<Path> {
+        let (mask, remaining) = self.first_and_remaining_paths(properties);
+
+        let paths = match (self.group_type, mask.len()) {
+            (_, _) if remaining.len() == 0          => vec![],
+            (GroupType::InvertedMask, 0)            => remaining,
+            (GroupType::InvertedMask, _)            => path_sub::<_, _, _, Path>(&remaining, &mask, 0.01),
+            (_, 0)                                  => vec![],
+            (_, _)                                  => path_intersect::<_, _, _, Path>(&remaining, &mask, 0.01)
+        };
+
+        if paths.len() > 0 {
+            vec![Path::from_paths(&paths)]
+        } else {
+            vec![]
+        }
+    }
+
+    ///
+    /// Renders the contents of this group in 'masked' or 'inverted mask' mode
+    ///
+    /// The first element is used as a clipping path for the remaining elements. For an inverted mask, the clipping path is
+    /// the bounding box of the remaining elements with the first element subtracted from it.
+    ///
+    fn render_masked(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, when: Duration) {
+        let (mask, remaining) = self.first_and_remaining_paths(properties);
+
+        let clip_path = if self.group_type == GroupType::InvertedMask {
+            // Nothing needs to be removed if the mask is empty
+            if mask.len() == 0 {
+                self.render_elements_after_mask(gc, properties, when);
+                return;
+            }
+
+            // Subtract the mask from a rectangle covering everything that's going to be drawn
+            let bounds = remaining.iter()
+                .chain(mask.iter())
+                .map(Rect::from)
+                .fold(Rect::empty(), |bounds, next| bounds.union(next))
+                .normalize();
+
+            // The brushes can render a little outside of their paths, so the rectangle is enlarged to make sure nothing is clipped off
+            let margin      = f32::max(bounds.width(), bounds.height()) * 0.1 + 1.0;
+            let bounds      = bounds.inset(-margin*2.0, -margin*2.0);
+            let bounds      = Path::from_elements(vec![
+                PathComponent::Move(PathPoint::new(bounds.x1, bounds.y1)),
+                PathComponent::Line(PathPoint::new(bounds.x2, bounds.y1)),
+                PathComponent::Line(PathPoint::new(bounds.x2, bounds.y2)),
+                PathComponent::Line(PathPoint::new(bounds.x1, bounds.y2)),
+                PathComponent::Close
+            ]);
+
+            path_sub::<_, _, _, Path>(&vec![bounds], &mask, 0.01)
+        } else {
+            mask
+        };
+
+        // Clip to the mask and render the remaining elements
+        gc.push_state();
+
+        gc.new_path();
+        clip_path.iter().for_each(|path| gc.draw_list(Box::new(path.to_drawing())));
+        gc.clip();
+
+        self.render_elements_after_mask(gc, properties, when);
+
+        gc.pop_state();
+    }
+
+    ///
+    /// Renders the elements after the first element (the elements affected by the mask)
+    ///
+    fn render_elements_after_mask(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, when: Duration) {
+        Self::render_elements(self.grouped_elements.iter().skip(1), gc, properties, when);
+    }
+
     ///
     /// Renders the contents of this group in 'added' mode
     ///
     fn render_added(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties) {
         let paths = self.added_path(properties);
+        self.render_combined_path(gc, properties, paths);
+    }
+
+    ///
+    /// Renders the contents of this group in 'subtracted' mode
+    ///
+    fn render_subtracted(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties) {
+        let paths = self.subtracted_path(properties);
+        self.render_combined_path(gc, properties, paths);
+    }
 
+    ///
+    /// Renders a path generated by combining the elements in this group using the group's properties
+    ///
+    fn render_combined_path(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, paths: Vec<Path>) {
         let paths = if properties.transformations.len() > 0 {
             paths.into_iter()
                 .map(|mut path| {
@@ -192,8 +348,11 @@ impl VectorElement for GroupElement {
     fn to_path(&self, properties: &VectorProperties, options: PathConversion) -> Option<Vec<Path>> {
         // With the added path type we can assume that the interior points are already removed so there's no need to apply the options
         let path = match self.group_type {
-            GroupType::Normal   => Some(self.grouped_elements.iter().flat_map(|elem| elem.to_path(properties, options)).flatten().collect()),
-            GroupType::Added    => Some(self.added_path(properties))
+            GroupType::Normal       => Some(self.grouped_elements.iter().flat_map(|elem| elem.to_path(properties, options)).flatten().collect()),
+            GroupType::Added        => Some(self.added_path(properties)),
+            GroupType::Subtracted   => Some(self.subtracted_path(properties)),
+            GroupType::Masked       |
+            GroupType::InvertedMask => Some(self.masked_path(properties))
         };
 
         // Apply any transformations in the properties
@@ -221,8 +380,11 @@ impl VectorElement for GroupElement {
     ///
     fn render(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, when: Duration) {
         match self.group_type {
-            GroupType::Normal   => self.render_normal(gc, properties, when),
-            GroupType::Added    => self.render_added(gc, properties)
+            GroupType::Normal       => self.render_normal(gc, properties, when),
+            GroupType::Added        => self.render_added(gc, properties),
+            GroupType::Subtracted   => self.render_subtracted(gc, properties),
+            GroupType::Masked       |
+            GroupType::InvertedMask => self.render_masked(gc, properties, when)
         }
     }
 
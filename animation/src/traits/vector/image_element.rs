@@ -0,0 +1,283 @@
+use super::vector::*;
+use super::properties::*;
+use super::control_point::*;
+use super::vector_element::*;
+use super::path_conversion_options::*;
+use super::super::path::*;
+use super::super::edit::*;
+use super::super::image::*;
+
+use flo_canvas::*;
+use flo_curves::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Element representing a bitmap image placed on a keyframe
+///
+/// Images are stored separately from the elements that display them (see `AnimationEdit::AddImage`), so an element
+/// read back from storage only knows the ID of its image until the image itself has been loaded.
+///
+#[derive(Clone, Debug)]
+pub struct ImageElement {
+    /// The ID of this element
+    id: ElementId,
+
+    /// The ID of the image that this element displays
+    image_id: ElementId,
+
+    /// The image that this element displays, if it has been loaded
+    image: Option<Arc<ReferenceImage>>,
+
+    /// The position of the lower-left corner of the image
+    position: (f32, f32),
+
+    /// The width and height of the image on the canvas
+    size: (f32, f32),
+
+    /// The opacity of the image (0.0 is fully transparent, 1.0 is fully opaque)
+    opacity: f32
+}
+
+impl ImageElement {
+    ///
+    /// Creates a new image element that displays the image with the specified ID (the image is not loaded)
+    ///
+    pub fn new(id: ElementId, image_id: ElementId, position: (f32, f32), size: (f32, f32), opacity: f32) -> ImageElement {
+        ImageElement {
+            id:         id,
+            image_id:   image_id,
+            image:      None,
+            position:   position,
+            size:       size,
+            opacity:    opacity
+        }
+    }
+
+    ///
+    /// Returns a copy of this element with its image loaded
+    ///
+    pub fn with_image(&self, image: Arc<ReferenceImage>) -> ImageElement {
+        ImageElement {
+            image: Some(image),
+            ..self.clone()
+        }
+    }
+
+    ///
+    /// Returns the ID of the image displayed by this element
+    ///
+    pub fn image_id(&self) -> ElementId {
+        self.image_id
+    }
+
+    ///
+    /// Returns the image displayed by this element, if it has been loaded
+    ///
+    pub fn image(&self) -> Option<Arc<ReferenceImage>> {
+        self.image.clone()
+    }
+
+    ///
+    /// Returns the position of the lower-left corner of this image
+    ///
+    pub fn position(&self) -> (f32, f32) {
+        self.position
+    }
+
+    ///
+    /// Returns the width and height of this image on the canvas
+    ///
+    pub fn size(&self) -> (f32, f32) {
+        self.size
+    }
+
+    ///
+    /// Returns the opacity of this image
+    ///
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    ///
+    /// Returns the ID of the sprite used to draw this element
+    ///
+    /// Elements that display the same image at the same opacity share a sprite. Image IDs are allocated from the same
+    /// space as element IDs, so the sprite IDs are kept well away from the ones used by the tools.
+    ///
+    pub fn sprite_id(&self) -> SpriteId {
+        let image_id    = self.image_id.id().unwrap_or(0) as u64;
+        let alpha       = (self.opacity.max(0.0).min(1.0) * 255.0).round() as u64;
+
+        SpriteId((1<<63) | (image_id.wrapping_mul(256).wrapping_add(alpha) & !(1<<63)))
+    }
+
+    ///
+    /// Declares the sprite that's used to draw this element (see `sprite_id()`)
+    ///
+    /// This leaves the sprite selected, so the caller needs to select a layer before drawing anything else. The image covers
+    /// the area from (0, 0) to its render size in the sprite.
+    ///
+    pub fn render_sprite(&self, gc: &mut dyn GraphicsPrimitives) {
+        let image                       = match self.image.as_ref() {
+            Some(image) => image,
+            None        => { return; }
+        };
+        let (_, grid_height)            = image.render_size();
+        let opacity                     = self.opacity.max(0.0).min(1.0);
+
+        gc.sprite(self.sprite_id());
+        gc.clear_sprite();
+        gc.blend_mode(BlendMode::SourceOver);
+
+        // Each region is rendered as a single path (the sprite has y=0 at the bottom, and the render grid has it at the top)
+        for region in image.regions().iter() {
+            let (r, g, b, a) = region.color;
+
+            gc.new_path();
+
+            for (x1, y1, x2, y2) in region.rects.iter() {
+                let (x1, x2) = (*x1 as f32, *x2 as f32);
+                let (y1, y2) = ((grid_height - *y1) as f32, (grid_height - *y2) as f32);
+
+                gc.move_to(x1, y1);
+                gc.line_to(x2, y1);
+                gc.line_to(x2, y2);
+                gc.line_to(x1, y2);
+                gc.close_path();
+            }
+
+            gc.fill_color(Color::Rgba((r as f32)/255.0, (g as f32)/255.0, (b as f32)/255.0, (a as f32)/255.0 * opacity));
+            gc.fill();
+        }
+    }
+
+    ///
+    /// Returns the corners of this image (lower-left, lower-right, upper-right, upper-left) after applying the transformations in the properties
+    ///
+    fn corners(&self, properties: &VectorProperties) -> Vec<Coord2> {
+        let (x1, y1) = self.position;
+        let (x2, y2) = (x1 + self.size.0, y1 + self.size.1);
+
+        vec![(x1, y1), (x2, y1), (x2, y2), (x1, y2)].into_iter()
+            .map(|(x, y)| properties.transform_point(&Coord2(x as f64, y as f64)))
+            .collect()
+    }
+}
+
+impl VectorElement for ImageElement {
+    ///
+    /// The ID of this element
+    ///
+    fn id(&self) -> ElementId {
+        self.id
+    }
+
+    ///
+    /// Modifies this element to have a new ID
+    ///
+    fn set_id(&mut self, new_id: ElementId) {
+        self.id = new_id
+    }
+
+    ///
+    /// Retrieves the paths for this element, if there are any
+    ///
+    fn to_path(&self, properties: &VectorProperties, _options: PathConversion) -> Option<Vec<Path>> {
+        // The path of an image is the rectangle that it covers
+        let corners     = self.corners(properties);
+        let corners     = corners.into_iter().map(|Coord2(x, y)| PathPoint::new(x as f32, y as f32)).collect::<Vec<_>>();
+
+        let mut path    = vec![PathComponent::Move(corners[0])];
+        path.extend(corners.into_iter().skip(1).map(PathComponent::Line));
+        path.push(PathComponent::Close);
+
+        Some(vec![Path::from_elements(path)])
+    }
+
+    ///
+    /// Renders this vector element
+    ///
+    /// Images are drawn using the sprite declared by `render_sprite()`, which must have been sent to the graphics context first
+    ///
+    fn render(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, _when: Duration) {
+        // Nothing is drawn until the image has been loaded
+        let image                       = match self.image.as_ref() {
+            Some(image) => image,
+            None        => { return; }
+        };
+
+        // The sprite covers the render grid, so map the grid onto the corners of the image
+        let (grid_width, grid_height)   = image.render_size();
+        let (x1, y1)                    = self.position;
+        let (x2, y2)                    = (x1 + self.size.0, y1 + self.size.1);
+
+        let origin                      = properties.transform_point(&Coord2(x1 as f64, y1 as f64));
+        let right                       = properties.transform_point(&Coord2(x2 as f64, y1 as f64));
+        let top                         = properties.transform_point(&Coord2(x1 as f64, y2 as f64));
+
+        let (grid_width, grid_height)   = (grid_width as f64, grid_height as f64);
+        let x_axis                      = ((right.x() - origin.x()) / grid_width, (right.y() - origin.y()) / grid_width);
+        let y_axis                      = ((top.x() - origin.x()) / grid_height, (top.y() - origin.y()) / grid_height);
+
+        let transform                   = Transform2D([
+            [x_axis.0 as f32,   y_axis.0 as f32,    origin.x() as f32],
+            [x_axis.1 as f32,   y_axis.1 as f32,    origin.y() as f32],
+            [0.0,               0.0,                1.0]
+        ]);
+
+        gc.sprite_transform(SpriteTransform::Identity);
+        gc.sprite_transform(SpriteTransform::Transform2D(transform));
+        gc.draw_sprite(self.sprite_id());
+    }
+
+    ///
+    /// Fetches the control points for this element
+    ///
+    fn control_points(&self, properties: &VectorProperties) -> Vec<ControlPoint> {
+        // The corners of the image can be used to move and resize it
+        self.corners(properties).into_iter()
+            .map(|Coord2(x, y)| ControlPoint::BezierPoint(x as f32, y as f32))
+            .collect()
+    }
+
+    ///
+    /// Creates a new vector element from this one with the control points updated to the specified set of new values
+    ///
+    /// The vector here specifies the updated position for each control point in control_points
+    ///
+    fn with_adjusted_control_points(&self, new_positions: Vec<(f32, f32)>, properties: &VectorProperties) -> Vector {
+        let inverse_properties = properties.with_inverse_transformation().unwrap_or_else(|| properties.clone());
+
+        // The image covers the bounding box of the new corner positions
+        let corners = new_positions.into_iter()
+            .map(|(x, y)| inverse_properties.transform_point(&Coord2(x as f64, y as f64)))
+            .collect::<Vec<_>>();
+
+        if corners.is_empty() {
+            return Vector::Image(self.clone());
+        }
+
+        let min_x   = corners.iter().map(|point| point.x()).fold(f64::MAX, f64::min);
+        let min_y   = corners.iter().map(|point| point.y()).fold(f64::MAX, f64::min);
+        let max_x   = corners.iter().map(|point| point.x()).fold(f64::MIN, f64::max);
+        let max_y   = corners.iter().map(|point| point.y()).fold(f64::MIN, f64::max);
+
+        Vector::Image(ImageElement {
+            id:         self.id,
+            image_id:   self.image_id,
+            image:      self.image.clone(),
+            position:   (min_x as f32, min_y as f32),
+            size:       ((max_x - min_x) as f32, (max_y - min_y) as f32),
+            opacity:    self.opacity
+        })
+    }
+}
+
+impl Into<Vector> for ImageElement {
+    #[inline]
+    fn into(self) -> Vector {
+        Vector::Image(self)
+    }
+}
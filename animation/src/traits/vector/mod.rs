@@ -3,6 +3,8 @@ mod properties;
 mod vector_element;
 mod vector_type;
 mod path_element;
+mod image_element;
+mod text_element;
 mod control_point;
 mod error_element;
 mod brush_element;
@@ -19,6 +21,8 @@ pub use self::properties::*;
 pub use self::vector_element::*;
 pub use self::vector_type::*;
 pub use self::path_element::*;
+pub use self::image_element::*;
+pub use self::text_element::*;
 pub use self::control_point::*;
 pub use self::error_element::*;
 pub use self::brush_element::*;
@@ -74,17 +74,19 @@ impl VectorElement for MotionElement {
     /// Returns the properties to use for future elements
     ///
     fn update_properties(&self, properties: Arc<VectorProperties>, when: Duration) -> Arc<VectorProperties> {
-        // Get the transformation for this motion
+        // Get the transformation and opacity for this motion
         let transform       = self.motion.transformation(when);
+        let opacity         = self.motion.opacity(when);
 
-        if transform.len() > 0 {
+        if transform.len() > 0 || opacity != 1.0 {
             // Add the transform to the properties
             let mut properties      = (*properties).clone();
             let mut full_transform  = (*properties.transformations).clone();
 
             full_transform.extend(transform);
 
-            properties.transformations = Arc::new(full_transform);
+            properties.transformations          = Arc::new(full_transform);
+            properties.brush_properties.opacity = ((properties.brush_properties.opacity as f64) * opacity) as f32;
 
             Arc::new(properties)
         } else {
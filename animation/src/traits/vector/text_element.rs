@@ -0,0 +1,194 @@
+use super::vector::*;
+use super::properties::*;
+use super::control_point::*;
+use super::vector_element::*;
+use super::path_conversion_options::*;
+use super::super::path::*;
+use super::super::edit::*;
+use super::super::text::*;
+
+use flo_canvas::*;
+use flo_curves::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Element representing some text
+///
+#[derive(Clone, Debug)]
+pub struct TextElement {
+    /// The ID of this element
+    id: ElementId,
+
+    /// The text that this element displays
+    text: Arc<String>,
+
+    /// The style used to draw the text
+    style: Arc<TextStyle>,
+
+    /// The position of the start of the baseline of the first line of text (adjusted by the alignment)
+    position: (f32, f32)
+}
+
+impl TextElement {
+    ///
+    /// Creates a new text element
+    ///
+    pub fn new(id: ElementId, text: Arc<String>, style: Arc<TextStyle>, position: (f32, f32)) -> TextElement {
+        TextElement {
+            id:         id,
+            text:       text,
+            style:      style,
+            position:   position
+        }
+    }
+
+    ///
+    /// Returns the text displayed by this element
+    ///
+    pub fn text(&self) -> Arc<String> {
+        Arc::clone(&self.text)
+    }
+
+    ///
+    /// Returns the style used to draw this element
+    ///
+    pub fn style(&self) -> Arc<TextStyle> {
+        Arc::clone(&self.style)
+    }
+
+    ///
+    /// Returns the position of this element
+    ///
+    pub fn position(&self) -> (f32, f32) {
+        self.position
+    }
+
+    ///
+    /// Creates a copy of this element displaying different text
+    ///
+    pub fn with_text(&self, text: Arc<String>) -> TextElement {
+        TextElement {
+            id:         self.id,
+            text:       text,
+            style:      Arc::clone(&self.style),
+            position:   self.position
+        }
+    }
+
+    ///
+    /// Creates a copy of this element with a different style
+    ///
+    pub fn with_style(&self, style: Arc<TextStyle>) -> TextElement {
+        TextElement {
+            id:         self.id,
+            text:       Arc::clone(&self.text),
+            style:      style,
+            position:   self.position
+        }
+    }
+
+    ///
+    /// Returns the outlines of the glyphs for this text, before any transformations are applied
+    ///
+    pub fn glyph_outlines(&self) -> Vec<Path> {
+        TextFont::from_name(&self.style.font).outline(&*self.text, self.style.size, self.style.alignment, self.position)
+    }
+
+    ///
+    /// Returns the glyph outlines for this text with the transformations from the properties applied
+    ///
+    fn transformed_outlines(&self, properties: &VectorProperties) -> Vec<Path> {
+        let mut outlines = self.glyph_outlines();
+
+        for transform in properties.transformations.iter() {
+            outlines = outlines.into_iter().map(|path| transform.transform_path(&path)).collect();
+        }
+
+        outlines
+    }
+}
+
+impl VectorElement for TextElement {
+    ///
+    /// The ID of this element
+    ///
+    fn id(&self) -> ElementId {
+        self.id
+    }
+
+    ///
+    /// Modifies this element to have a new ID
+    ///
+    fn set_id(&mut self, new_id: ElementId) {
+        self.id = new_id
+    }
+
+    ///
+    /// Retrieves the paths for this element, if there are any
+    ///
+    fn to_path(&self, properties: &VectorProperties, _options: PathConversion) -> Option<Vec<Path>> {
+        // The path of a text element is the outline of each of its glyphs
+        Some(self.transformed_outlines(properties))
+    }
+
+    ///
+    /// Renders this vector element
+    ///
+    fn render(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, _when: Duration) {
+        let outlines = self.transformed_outlines(properties);
+
+        gc.blend_mode(BlendMode::SourceOver);
+        gc.new_path();
+
+        for outline in outlines.iter() {
+            gc.draw_list(Box::new(outline.to_drawing()));
+        }
+
+        gc.fill_color(self.style.color);
+        gc.fill();
+    }
+
+    ///
+    /// Fetches the control points for this element
+    ///
+    fn control_points(&self, properties: &VectorProperties) -> Vec<ControlPoint> {
+        // Text can be moved by dragging its position
+        let (x, y)      = self.position;
+        let position    = properties.transform_point(&Coord2(x as f64, y as f64));
+
+        vec![ControlPoint::BezierPoint(position.x() as f32, position.y() as f32)]
+    }
+
+    ///
+    /// Creates a new vector element from this one with the control points updated to the specified set of new values
+    ///
+    /// The vector here specifies the updated position for each control point in control_points
+    ///
+    fn with_adjusted_control_points(&self, new_positions: Vec<(f32, f32)>, properties: &VectorProperties) -> Vector {
+        let inverse_properties = properties.with_inverse_transformation().unwrap_or_else(|| properties.clone());
+
+        match new_positions.into_iter().next() {
+            Some((x, y))    => {
+                let position = inverse_properties.transform_point(&Coord2(x as f64, y as f64));
+
+                Vector::Text(TextElement {
+                    id:         self.id,
+                    text:       Arc::clone(&self.text),
+                    style:      Arc::clone(&self.style),
+                    position:   (position.x() as f32, position.y() as f32)
+                })
+            }
+
+            None            => Vector::Text(self.clone())
+        }
+    }
+}
+
+impl Into<Vector> for TextElement {
+    #[inline]
+    fn into(self) -> Vector {
+        Vector::Text(self)
+    }
+}
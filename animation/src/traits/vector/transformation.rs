@@ -61,6 +61,24 @@ impl Transformation {
         }
     }
 
+    ///
+    /// Returns the element transforms that will recreate this transformation when applied to an element
+    ///
+    /// Matrix transformations can't be created by an edit, so these return an empty list
+    ///
+    pub fn to_element_transforms(&self) -> Vec<ElementTransform> {
+        use self::Transformation::*;
+
+        match self {
+            Translate(x, y)             => vec![ElementTransform::SetAnchor(0.0, 0.0), ElementTransform::MoveTo(*x, *y)],
+            FlipHoriz(x, y)             => vec![ElementTransform::SetAnchor(*x, *y), ElementTransform::FlipHorizontal],
+            FlipVert(x, y)              => vec![ElementTransform::SetAnchor(*x, *y), ElementTransform::FlipVertical],
+            Scale(sx, sy, (x, y))       => vec![ElementTransform::SetAnchor(*x, *y), ElementTransform::Scale(*sx, *sy)],
+            Rotate(angle, (x, y))       => vec![ElementTransform::SetAnchor(*x, *y), ElementTransform::Rotate(*angle)],
+            Matrix(_)                   => vec![]
+        }
+    }
+
     ///
     /// Returns the inverse of this transformation
     ///
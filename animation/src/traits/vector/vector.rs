@@ -1,4 +1,6 @@
 use super::path_element::*;
+use super::image_element::*;
+use super::text_element::*;
 use super::brush_element::*;
 use super::group_element::*;
 use super::error_element::*;
@@ -35,6 +37,12 @@ pub enum Vector {
     /// Path vector
     Path(PathElement),
 
+    /// Bitmap image
+    Image(ImageElement),
+
+    /// Text drawn with a font
+    Text(TextElement),
+
     /// Element describing a motion
     Motion(MotionElement),
 
@@ -129,6 +137,8 @@ impl DerefMut for Vector {
             BrushStroke(elem)               => elem,
 
             Path(elem)                      => elem,
+            Image(elem)                     => elem,
+            Text(elem)                      => elem,
             Motion(elem)                    => elem,
             Group(elem)                     => elem,
             Transformation(elem)            => elem,
@@ -152,6 +162,8 @@ impl Deref for Vector {
             BrushStroke(elem)               => elem,
 
             Path(elem)                      => elem,
+            Image(elem)                     => elem,
+            Text(elem)                      => elem,
             Motion(elem)                    => elem,
             Group(elem)                     => elem,
             Transformation(transform)       => transform,
@@ -17,6 +17,12 @@ pub enum VectorType {
     /// Vector element representing a path
     Path,
 
+    /// Vector element representing a bitmap image
+    Image,
+
+    /// Vector element representing some text
+    Text,
+
     /// Vector element representing the way something moves through space
     Motion,
 
@@ -40,6 +46,8 @@ impl From<&Vector> for VectorType {
             BrushProperties(_)              => VectorType::BrushProperties,
             BrushStroke(_)                  => VectorType::BrushStroke,
             Path(_)                         => VectorType::Path,
+            Image(_)                        => VectorType::Image,
+            Text(_)                         => VectorType::Text,
             Motion(_)                       => VectorType::Motion,
             Group(_)                        => VectorType::Group,
             Transformation(_)               => VectorType::Transformation,
@@ -93,6 +93,7 @@ impl CanvasCore {
                 match drawing {
                     &(_, Draw::ClearCanvas)         => true,
                     &(_, Draw::LayerBlend(_, _))    => true,
+                    &(_, Draw::LayerAlpha(_, _))    => true,
                     &(layer, _)                     => layer != layer_id
                 }
             })
@@ -291,6 +292,11 @@ impl<'a> GraphicsContext for CoreContext<'a> {
     fn dash_offset(&mut self, offset: f32)                      { self.pending.push(Draw::DashOffset(offset)); }
     fn fill_color(&mut self, col: Color)                        { self.pending.push(Draw::FillColor(col)); }
     fn stroke_color(&mut self, col: Color)                      { self.pending.push(Draw::StrokeColor(col)); }
+    fn new_gradient(&mut self)                                  { self.pending.push(Draw::NewGradient); }
+    fn gradient_stop(&mut self, pos: f32, col: Color)           { self.pending.push(Draw::GradientStop(pos, col)); }
+    fn fill_linear_gradient(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) { self.pending.push(Draw::FillLinearGradient((x1, y1), (x2, y2))); }
+    fn fill_radial_gradient(&mut self, x: f32, y: f32, radius: f32) { self.pending.push(Draw::FillRadialGradient((x, y), radius)); }
+    fn fill_texture(&mut self, pattern: TexturePattern, fg: Color, bg: Color, size: f32) { self.pending.push(Draw::FillTexture(pattern, fg, bg, size)); }
     fn blend_mode(&mut self, mode: BlendMode)                   { self.pending.push(Draw::BlendMode(mode)); }
     fn identity_transform(&mut self)                            { self.pending.push(Draw::IdentityTransform); }
     fn canvas_height(&mut self, height: f32)                    { self.pending.push(Draw::CanvasHeight(height)); }
@@ -306,6 +312,7 @@ impl<'a> GraphicsContext for CoreContext<'a> {
     fn clear_canvas(&mut self)                                  { self.pending.push(Draw::ClearCanvas); }
     fn layer(&mut self, layer_id: u32)                          { self.pending.push(Draw::Layer(layer_id)); }
     fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode) { self.pending.push(Draw::LayerBlend(layer_id, blend_mode)); }
+    fn layer_alpha(&mut self, layer_id: u32, alpha: f32)        { self.pending.push(Draw::LayerAlpha(layer_id, alpha)); }
     fn clear_layer(&mut self)                                   { self.pending.push(Draw::ClearLayer); }
     fn sprite(&mut self, sprite_id: SpriteId)                   { self.pending.push(Draw::Sprite(sprite_id)); }
     fn clear_sprite(&mut self)                                  { self.pending.push(Draw::ClearSprite); }
@@ -29,6 +29,7 @@ enum DecoderState {
     LineStyle,                          // 'L'
     Dash,                               // 'D'
     Color,                              // 'C'
+    Gradient,                           // 'G'
     Sprite,                             // 's'
     Transform,                          // 'T'
     State,                              // 'Z'
@@ -47,6 +48,11 @@ enum DecoderState {
 
     ColorStroke(String),                // 'Cs' (r, g, b, a)
     ColorFill(String),                  // 'Cf' (r, g, b, a)
+    ColorLinearGradient(String),        // 'Cl' (x1, y1, x2, y2)
+    ColorRadialGradient(String),        // 'Cr' (x, y, radius)
+    ColorTexture(String),               // 'Ct' (pattern, fg, bg, size)
+
+    GradientStop(String),               // 'Gs' (pos, r, g, b, a)
 
     BlendMode(String),                  // 'M' (mode)
 
@@ -56,6 +62,7 @@ enum DecoderState {
 
     NewLayer(String),                   // 'Nl' (id)
     NewLayerBlend(String),              // 'Nb' (id, mode)
+    NewLayerAlpha(String),              // 'Nt' (id, alpha)
 
     NewSprite(String),                  // 'Ns' (id)
     SpriteDraw(String),                 // 'sD' (id)
@@ -122,6 +129,7 @@ impl CanvasDecoder {
             LineStyle                       => Self::decode_line_style(next_chr)?,
             Dash                            => Self::decode_dash(next_chr)?,
             Color                           => Self::decode_color(next_chr)?,
+            Gradient                        => Self::decode_gradient(next_chr)?,
             Sprite                          => Self::decode_sprite(next_chr)?,
             Transform                       => Self::decode_transform(next_chr)?,
             State                           => Self::decode_state(next_chr)?,
@@ -140,6 +148,11 @@ impl CanvasDecoder {
 
             ColorStroke(param)              => Self::decode_color_stroke(next_chr, param)?,
             ColorFill(param)                => Self::decode_color_fill(next_chr, param)?,
+            ColorLinearGradient(param)      => Self::decode_color_linear_gradient(next_chr, param)?,
+            ColorRadialGradient(param)      => Self::decode_color_radial_gradient(next_chr, param)?,
+            ColorTexture(param)             => Self::decode_color_texture(next_chr, param)?,
+
+            GradientStop(param)             => Self::decode_gradient_stop(next_chr, param)?,
 
             BlendMode(param)                => Self::decode_blend_mode(next_chr, param)?,
 
@@ -149,6 +162,7 @@ impl CanvasDecoder {
 
             NewLayer(param)                 => Self::decode_new_layer(next_chr, param)?,
             NewLayerBlend(param)            => Self::decode_new_layer_blend(next_chr, param)?,
+            NewLayerAlpha(param)            => Self::decode_new_layer_alpha(next_chr, param)?,
 
             NewSprite(param)                => Self::decode_new_sprite(next_chr, param)?,
             SpriteDraw(param)               => Self::decode_sprite_draw(next_chr, param)?,
@@ -176,6 +190,7 @@ impl CanvasDecoder {
             'L' => Ok((DecoderState::LineStyle, None)),
             'D' => Ok((DecoderState::Dash, None)),
             'C' => Ok((DecoderState::Color, None)),
+            'G' => Ok((DecoderState::Gradient, None)),
             's' => Ok((DecoderState::Sprite, None)),
             'T' => Ok((DecoderState::Transform, None)),
             'Z' => Ok((DecoderState::State, None)),
@@ -207,6 +222,7 @@ impl CanvasDecoder {
 
             'l'     => Ok((DecoderState::NewLayer(String::new()), None)),
             'b'     => Ok((DecoderState::NewLayerBlend(String::new()), None)),
+            't'     => Ok((DecoderState::NewLayerAlpha(String::new()), None)),
             's'     => Ok((DecoderState::NewSprite(String::new()), None)),
 
             _       => Err(DecoderError::InvalidCharacter(next_chr))
@@ -242,6 +258,19 @@ impl CanvasDecoder {
         match next_chr {
             's'     => Ok((DecoderState::ColorStroke(String::new()), None)),
             'f'     => Ok((DecoderState::ColorFill(String::new()), None)),
+            'l'     => Ok((DecoderState::ColorLinearGradient(String::new()), None)),
+            'r'     => Ok((DecoderState::ColorRadialGradient(String::new()), None)),
+            't'     => Ok((DecoderState::ColorTexture(String::new()), None)),
+
+            _       => Err(DecoderError::InvalidCharacter(next_chr))
+        }
+    }
+
+    #[inline] fn decode_gradient(next_chr: char) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Matched 'G' so far
+        match next_chr {
+            'n'     => Ok((DecoderState::None, Some(Draw::NewGradient))),
+            's'     => Ok((DecoderState::GradientStop(String::new()), None)),
 
             _       => Err(DecoderError::InvalidCharacter(next_chr))
         }
@@ -446,6 +475,77 @@ impl CanvasDecoder {
         }
     }
 
+    #[inline] fn decode_color_linear_gradient(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 23 {
+            param.push(next_chr);
+            Ok((DecoderState::ColorLinearGradient(param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let x1          = Self::decode_f32(&mut param)?;
+            let y1          = Self::decode_f32(&mut param)?;
+            let x2          = Self::decode_f32(&mut param)?;
+            let y2          = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::FillLinearGradient((x1, y1), (x2, y2)))))
+        }
+    }
+
+    #[inline] fn decode_color_radial_gradient(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 17 {
+            param.push(next_chr);
+            Ok((DecoderState::ColorRadialGradient(param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let x           = Self::decode_f32(&mut param)?;
+            let y           = Self::decode_f32(&mut param)?;
+            let radius      = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::FillRadialGradient((x, y), radius))))
+        }
+    }
+
+    #[inline] fn decode_color_texture(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 56 {
+            param.push(next_chr);
+            Ok((DecoderState::ColorTexture(param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let pattern     = match param.next() {
+                Some('S')   => TexturePattern::Stripes,
+                Some('C')   => TexturePattern::Checks,
+                Some('D')   => TexturePattern::Dots,
+                Some(other) => Err(DecoderError::InvalidCharacter(other))?,
+                None        => Err(DecoderError::MissingCharacter)?
+            };
+            let fg          = Self::decode_color_only(&mut param)?;
+            let bg          = Self::decode_color_only(&mut param)?;
+            let size        = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::FillTexture(pattern, fg, bg, size))))
+        }
+    }
+
+    #[inline] fn decode_gradient_stop(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 30 {
+            param.push(next_chr);
+            Ok((DecoderState::GradientStop(param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let pos         = Self::decode_f32(&mut param)?;
+            let col         = Self::decode_color_only(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::GradientStop(pos, col))))
+        }
+    }
+
     #[inline] fn decode_blend_mode(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
         if param.len() < 1 {
             param.push(next_chr);
@@ -530,6 +630,21 @@ impl CanvasDecoder {
         }
     }
 
+    #[inline] fn decode_new_layer_alpha(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 11 {
+            param.push(next_chr);
+            Ok((DecoderState::NewLayerAlpha(param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let layer_id    = Self::decode_u32(&mut param)?;
+            let alpha       = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::LayerAlpha(layer_id, alpha))))
+        }
+    }
+
     #[inline] fn decode_new_sprite(next_chr: char, param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
         match Self::decode_sprite_id(next_chr, param)? {
             PartialResult::FullMatch(sprite_id) => Ok((DecoderState::None, Some(Draw::Sprite(sprite_id)))),
@@ -647,6 +762,23 @@ impl CanvasDecoder {
         }
     }
 
+    ///
+    /// Consumes 25 characters to decode a colour
+    ///
+    fn decode_color_only(param: &mut Chars) -> Result<Color, DecoderError> {
+        let col_type    = param.next();
+        let r           = Self::decode_f32(param)?;
+        let g           = Self::decode_f32(param)?;
+        let b           = Self::decode_f32(param)?;
+        let a           = Self::decode_f32(param)?;
+
+        if col_type != Some('R') {
+            Err(DecoderError::UnknownColorType)?;
+        }
+
+        Ok(Color::Rgba(r, g, b, a))
+    }
+
     ///
     /// Consumes characters until we have a sprite ID
     ///
@@ -907,6 +1039,33 @@ mod test {
         check_round_trip_single(Draw::FillColor(Color::Rgba(0.2, 0.3, 0.4, 0.5)));
     }
 
+    #[test]
+    fn decode_new_gradient() {
+        check_round_trip_single(Draw::NewGradient);
+    }
+
+    #[test]
+    fn decode_gradient_stop() {
+        check_round_trip_single(Draw::GradientStop(0.25, Color::Rgba(0.2, 0.3, 0.4, 0.5)));
+    }
+
+    #[test]
+    fn decode_fill_linear_gradient() {
+        check_round_trip_single(Draw::FillLinearGradient((1.0, 2.0), (3.0, 4.0)));
+    }
+
+    #[test]
+    fn decode_fill_radial_gradient() {
+        check_round_trip_single(Draw::FillRadialGradient((1.0, 2.0), 30.0));
+    }
+
+    #[test]
+    fn decode_fill_texture() {
+        check_round_trip_single(Draw::FillTexture(TexturePattern::Stripes, Color::Rgba(0.1, 0.2, 0.3, 0.4), Color::Rgba(0.5, 0.6, 0.7, 0.8), 12.0));
+        check_round_trip_single(Draw::FillTexture(TexturePattern::Checks, Color::Rgba(0.1, 0.2, 0.3, 0.4), Color::Rgba(0.5, 0.6, 0.7, 0.8), 12.0));
+        check_round_trip_single(Draw::FillTexture(TexturePattern::Dots, Color::Rgba(0.1, 0.2, 0.3, 0.4), Color::Rgba(0.5, 0.6, 0.7, 0.8), 12.0));
+    }
+
     #[test]
     fn decode_blend_mode() {
         check_round_trip_single(Draw::BlendMode(BlendMode::Lighten));
@@ -982,6 +1141,11 @@ mod test {
         check_round_trip_single(Draw::LayerBlend(76, BlendMode::Lighten))
     }
 
+    #[test]
+    fn decode_layer_alpha() {
+        check_round_trip_single(Draw::LayerAlpha(76, 0.5))
+    }
+
     #[test]
     fn decode_clear_layer() {
         check_round_trip_single(Draw::ClearLayer);
@@ -1065,6 +1229,11 @@ mod test {
             Draw::DashOffset(13.0),
             Draw::StrokeColor(Color::Rgba(0.1, 0.2, 0.3, 0.4)),
             Draw::FillColor(Color::Rgba(0.2, 0.3, 0.4, 0.5)),
+            Draw::NewGradient,
+            Draw::GradientStop(0.5, Color::Rgba(0.3, 0.4, 0.5, 0.6)),
+            Draw::FillLinearGradient((1.0, 2.0), (3.0, 4.0)),
+            Draw::FillRadialGradient((5.0, 6.0), 7.0),
+            Draw::FillTexture(TexturePattern::Dots, Color::Rgba(0.1, 0.2, 0.3, 0.4), Color::Rgba(0.5, 0.6, 0.7, 0.8), 9.0),
             Draw::BlendMode(BlendMode::Lighten),
             Draw::IdentityTransform,
             Draw::CanvasHeight(81.0),
@@ -1107,6 +1276,11 @@ mod test {
             Draw::DashOffset(13.0),
             Draw::StrokeColor(Color::Rgba(0.1, 0.2, 0.3, 0.4)),
             Draw::FillColor(Color::Rgba(0.2, 0.3, 0.4, 0.5)),
+            Draw::NewGradient,
+            Draw::GradientStop(0.5, Color::Rgba(0.3, 0.4, 0.5, 0.6)),
+            Draw::FillLinearGradient((1.0, 2.0), (3.0, 4.0)),
+            Draw::FillRadialGradient((5.0, 6.0), 7.0),
+            Draw::FillTexture(TexturePattern::Dots, Color::Rgba(0.1, 0.2, 0.3, 0.4), Color::Rgba(0.5, 0.6, 0.7, 0.8), 9.0),
             Draw::BlendMode(BlendMode::Lighten),
             Draw::IdentityTransform,
             Draw::CanvasHeight(81.0),
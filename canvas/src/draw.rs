@@ -45,6 +45,23 @@ pub enum BlendMode {
     Lighten
 }
 
+///
+/// Repeating pattern that can be used to fill a path
+///
+/// Patterns are defined in terms of a square tile that is repeated across the whole canvas.
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TexturePattern {
+    /// Diagonal stripes of the foreground colour over the background colour
+    Stripes,
+
+    /// A checkerboard of the foreground and background colours
+    Checks,
+
+    /// Dots of the foreground colour over the background colour
+    Dots
+}
+
 ///
 /// Identifier of a canvas 'sprite'
 ///
@@ -133,6 +150,27 @@ pub enum Draw {
     /// Set the line color
     StrokeColor(Color),
 
+    /// Resets the list of colour stops used by gradient fills
+    NewGradient,
+
+    /// Adds a colour stop to the current gradient (the position is in the range 0.0 to 1.0)
+    GradientStop(f32, Color),
+
+    /// Fills future paths with a linear gradient between a start and an end point, using the current gradient stops
+    ///
+    /// The coordinates are interpreted using the transformation that is in effect when the path is filled. Use
+    /// `FillColor` to go back to filling with a solid colour.
+    FillLinearGradient((f32, f32), (f32, f32)),
+
+    /// Fills future paths with a radial gradient with a center point and a radius, using the current gradient stops
+    FillRadialGradient((f32, f32), f32),
+
+    /// Fills future paths with a repeating texture pattern
+    ///
+    /// The parameters are the pattern, the foreground colour, the background colour and the size of a single tile
+    /// of the pattern.
+    FillTexture(TexturePattern, Color, Color, f32),
+
     /// Set how future renderings are blended with one another
     BlendMode(BlendMode),
 
@@ -190,6 +228,9 @@ pub enum Draw {
     /// Sets how a particular layer is blended with the underlying layer
     LayerBlend(u32, BlendMode),
 
+    /// Sets the alpha value used when a particular layer is blended with the underlying layer (0.0 is fully transparent, 1.0 is fully opaque)
+    LayerAlpha(u32, f32),
+
     /// Clears the current layer
     ClearLayer,
 
@@ -160,6 +160,18 @@ impl CanvasEncoding<String> for BlendMode {
     }
 }
 
+impl CanvasEncoding<String> for TexturePattern {
+    fn encode_canvas(&self, append_to: &mut String) {
+        use self::TexturePattern::*;
+
+        match self {
+            &Stripes    => 'S',
+            &Checks     => 'C',
+            &Dots       => 'D'
+        }.encode_canvas(append_to)
+    }
+}
+
 impl CanvasEncoding<String> for Transform2D {
     #[inline]
     fn encode_canvas(&self, append_to: &mut String) {
@@ -228,6 +240,11 @@ impl CanvasEncoding<String> for Draw {
             &DashOffset(offset)                     => ('D', 'o', offset).encode_canvas(append_to),
             &StrokeColor(col)                       => ('C', 's', col).encode_canvas(append_to),
             &FillColor(col)                         => ('C', 'f', col).encode_canvas(append_to),
+            &NewGradient                            => ('G', 'n').encode_canvas(append_to),
+            &GradientStop(pos, col)                 => ('G', 's', pos, col).encode_canvas(append_to),
+            &FillLinearGradient(start, end)         => ('C', 'l', start, end).encode_canvas(append_to),
+            &FillRadialGradient(center, radius)     => ('C', 'r', center, radius).encode_canvas(append_to),
+            &FillTexture(pattern, fg, bg, size)     => ('C', 't', pattern, (fg, bg), size).encode_canvas(append_to),
             &BlendMode(mode)                        => ('M', mode).encode_canvas(append_to),
             &IdentityTransform                      => ('T', 'i').encode_canvas(append_to),
             &CanvasHeight(height)                   => ('T', 'h', height).encode_canvas(append_to),
@@ -243,6 +260,7 @@ impl CanvasEncoding<String> for Draw {
             &ClearCanvas                            => ('N', 'A').encode_canvas(append_to),
             &Layer(layer_id)                        => ('N', 'l', layer_id).encode_canvas(append_to),
             &LayerBlend(layer_id, blend_mode)       => ('N', 'b', layer_id, blend_mode).encode_canvas(append_to),
+            &LayerAlpha(layer_id, alpha)            => ('N', 't', layer_id, alpha).encode_canvas(append_to),
             &ClearLayer                             => ('N', 'C').encode_canvas(append_to),
             &Sprite(sprite_id)                      => ('N', 's', sprite_id).encode_canvas(append_to),
             &ClearSprite                            => ('s', 'C').encode_canvas(append_to),
@@ -321,6 +339,16 @@ mod test {
     #[test]
     fn can_encode_fillcolor() { assert!(&encode_draw(Draw::FillColor(Color::Rgba(1.0, 1.0, 1.0, 1.0))) == "CfRAAAg/AAAAg/AAAAg/AAAAg/A") }
     #[test]
+    fn can_encode_newgradient() { assert!(&encode_draw(Draw::NewGradient) == "Gn") }
+    #[test]
+    fn can_encode_gradientstop() { assert!(&encode_draw(Draw::GradientStop(1.0, Color::Rgba(1.0, 1.0, 1.0, 1.0))) == "GsAAAg/ARAAAg/AAAAg/AAAAg/AAAAg/A") }
+    #[test]
+    fn can_encode_filllineargradient() { assert!(&encode_draw(Draw::FillLinearGradient((20.0, 20.0), (20.0, 20.0))) == "ClAAAoBBAAAoBBAAAoBBAAAoBB") }
+    #[test]
+    fn can_encode_fillradialgradient() { assert!(&encode_draw(Draw::FillRadialGradient((20.0, 20.0), 20.0)) == "CrAAAoBBAAAoBBAAAoBB") }
+    #[test]
+    fn can_encode_filltexture() { assert!(&encode_draw(Draw::FillTexture(TexturePattern::Checks, Color::Rgba(1.0, 1.0, 1.0, 1.0), Color::Rgba(1.0, 1.0, 1.0, 1.0), 20.0)) == "CtCRAAAg/AAAAg/AAAAg/AAAAg/ARAAAg/AAAAg/AAAAg/AAAAg/AAAAoBB") }
+    #[test]
     fn can_encode_blendmode() { assert!(&encode_draw(Draw::BlendMode(BlendMode::SourceOver)) == "MSV") }
     #[test]
     fn can_encode_identity_transform() { assert!(&encode_draw(Draw::IdentityTransform) == "Ti") }
@@ -0,0 +1,197 @@
+use super::draw::*;
+use super::color::*;
+
+///
+/// Describes how the interior of a path is filled
+///
+/// This is the state built up by the `FillColor`, `NewGradient`, `GradientStop`, `FillLinearGradient`, `FillRadialGradient`
+/// and `FillTexture` drawing instructions. Coordinates are in canvas units (ie, before any transformation is applied).
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum FillStyle {
+    /// Fill with a single colour
+    Solid(Color),
+
+    /// Linear gradient with a set of stops, between a start and an end point
+    LinearGradient(Vec<(f32, Color)>, (f32, f32), (f32, f32)),
+
+    /// Radial gradient with a set of stops, with a center point and a radius
+    RadialGradient(Vec<(f32, Color)>, (f32, f32), f32),
+
+    /// Repeating texture with a foreground colour, a background colour and a tile size
+    Texture(TexturePattern, Color, Color, f32)
+}
+
+impl Default for FillStyle {
+    fn default() -> FillStyle {
+        FillStyle::Solid(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+    }
+}
+
+impl TexturePattern {
+    ///
+    /// Returns true if a point within a tile (with coordinates in the range 0.0 to 1.0) is in the foreground colour
+    ///
+    pub fn is_foreground(&self, u: f32, v: f32) -> bool {
+        let u = u - u.floor();
+        let v = v - v.floor();
+
+        match self {
+            TexturePattern::Stripes => { let d = u + v; (d - d.floor()) < 0.5 },
+            TexturePattern::Checks  => (u < 0.5) == (v < 0.5),
+            TexturePattern::Dots    => { let (du, dv) = (u-0.5, v-0.5); (du*du + dv*dv) < 0.25*0.25 }
+        }
+    }
+}
+
+///
+/// Finds the colour at a particular position along a gradient
+///
+fn gradient_color(stops: &[(f32, Color)], pos: f32) -> Color {
+    // Order the stops by position
+    let mut stops = stops.to_vec();
+    stops.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Gradients with no stops are transparent
+    if stops.len() == 0 {
+        return Color::Rgba(0.0, 0.0, 0.0, 0.0);
+    }
+
+    // Positions before the first stop or after the last stop use the nearest stop's colour
+    let (first_pos, first_col)  = stops[0];
+    let (last_pos, last_col)    = stops[stops.len()-1];
+
+    if pos <= first_pos { return first_col; }
+    if pos >= last_pos  { return last_col; }
+
+    // Interpolate between the two stops that surround the position
+    for idx in 1..stops.len() {
+        let (pos1, col1) = stops[idx-1];
+        let (pos2, col2) = stops[idx];
+
+        if pos >= pos1 && pos <= pos2 {
+            if pos2 <= pos1 {
+                return col2;
+            }
+
+            let t                   = (pos - pos1) / (pos2 - pos1);
+            let (r1, g1, b1, a1)    = col1.to_rgba_components();
+            let (r2, g2, b2, a2)    = col2.to_rgba_components();
+
+            return Color::Rgba(r1 + (r2-r1)*t, g1 + (g2-g1)*t, b1 + (b2-b1)*t, a1 + (a2-a1)*t);
+        }
+    }
+
+    last_col
+}
+
+impl FillStyle {
+    ///
+    /// Returns the colour of this fill at a particular point
+    ///
+    pub fn color_at(&self, (x, y): (f32, f32)) -> Color {
+        match self {
+            FillStyle::Solid(color)                                 => *color,
+
+            FillStyle::LinearGradient(stops, (x1, y1), (x2, y2))    => {
+                let (dx, dy)    = (x2-x1, y2-y1);
+                let len_sq      = dx*dx + dy*dy;
+                let pos         = if len_sq > 0.0 { ((x-x1)*dx + (y-y1)*dy) / len_sq } else { 0.0 };
+
+                gradient_color(stops, pos)
+            }
+
+            FillStyle::RadialGradient(stops, (cx, cy), radius)      => {
+                let (dx, dy)    = (x-cx, y-cy);
+                let distance    = (dx*dx + dy*dy).sqrt();
+                let pos         = if *radius > 0.0 { distance / radius } else { 1.0 };
+
+                gradient_color(stops, pos)
+            }
+
+            FillStyle::Texture(pattern, fg, bg, size)               => {
+                if *size <= 0.0 {
+                    *fg
+                } else if pattern.is_foreground(x / size, y / size) {
+                    *fg
+                } else {
+                    *bg
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solid_fill_is_same_everywhere() {
+        let fill = FillStyle::Solid(Color::Rgba(0.1, 0.2, 0.3, 0.4));
+
+        assert!(fill.color_at((0.0, 0.0)) == Color::Rgba(0.1, 0.2, 0.3, 0.4));
+        assert!(fill.color_at((100.0, -50.0)) == Color::Rgba(0.1, 0.2, 0.3, 0.4));
+    }
+
+    #[test]
+    fn linear_gradient_interpolates_between_stops() {
+        let fill = FillStyle::LinearGradient(vec![(0.0, Color::Rgba(0.0, 0.0, 0.0, 1.0)), (1.0, Color::Rgba(1.0, 1.0, 1.0, 1.0))], (0.0, 0.0), (100.0, 0.0));
+
+        assert!(fill.color_at((0.0, 0.0)) == Color::Rgba(0.0, 0.0, 0.0, 1.0));
+        assert!(fill.color_at((50.0, 20.0)) == Color::Rgba(0.5, 0.5, 0.5, 1.0));
+        assert!(fill.color_at((100.0, 0.0)) == Color::Rgba(1.0, 1.0, 1.0, 1.0));
+        assert!(fill.color_at((200.0, 0.0)) == Color::Rgba(1.0, 1.0, 1.0, 1.0));
+        assert!(fill.color_at((-50.0, 0.0)) == Color::Rgba(0.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn gradient_stops_can_be_unordered() {
+        let fill = FillStyle::LinearGradient(vec![(1.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)), (0.0, Color::Rgba(0.0, 0.0, 1.0, 1.0)), (0.5, Color::Rgba(0.0, 1.0, 0.0, 1.0))], (0.0, 0.0), (0.0, 100.0));
+
+        assert!(fill.color_at((0.0, 0.0)) == Color::Rgba(0.0, 0.0, 1.0, 1.0));
+        assert!(fill.color_at((0.0, 50.0)) == Color::Rgba(0.0, 1.0, 0.0, 1.0));
+        assert!(fill.color_at((0.0, 75.0)) == Color::Rgba(0.5, 0.5, 0.0, 1.0));
+    }
+
+    #[test]
+    fn radial_gradient_uses_distance_from_center() {
+        let fill = FillStyle::RadialGradient(vec![(0.0, Color::Rgba(1.0, 1.0, 1.0, 1.0)), (1.0, Color::Rgba(0.0, 0.0, 0.0, 0.0))], (10.0, 10.0), 10.0);
+
+        assert!(fill.color_at((10.0, 10.0)) == Color::Rgba(1.0, 1.0, 1.0, 1.0));
+        assert!(fill.color_at((10.0, 15.0)) == Color::Rgba(0.5, 0.5, 0.5, 0.5));
+        assert!(fill.color_at((20.0, 10.0)) == Color::Rgba(0.0, 0.0, 0.0, 0.0));
+        assert!(fill.color_at((50.0, 50.0)) == Color::Rgba(0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn checks_texture_alternates() {
+        let fg      = Color::Rgba(1.0, 0.0, 0.0, 1.0);
+        let bg      = Color::Rgba(0.0, 0.0, 1.0, 1.0);
+        let fill    = FillStyle::Texture(TexturePattern::Checks, fg, bg, 10.0);
+
+        assert!(fill.color_at((2.0, 2.0)) == fg);
+        assert!(fill.color_at((7.0, 2.0)) == bg);
+        assert!(fill.color_at((7.0, 7.0)) == fg);
+        assert!(fill.color_at((12.0, 2.0)) == fg);
+        assert!(fill.color_at((-3.0, 2.0)) == bg);
+    }
+
+    #[test]
+    fn dots_texture_has_dot_in_center_of_tile() {
+        let fg      = Color::Rgba(1.0, 1.0, 1.0, 1.0);
+        let bg      = Color::Rgba(0.0, 0.0, 0.0, 1.0);
+        let fill    = FillStyle::Texture(TexturePattern::Dots, fg, bg, 8.0);
+
+        assert!(fill.color_at((4.0, 4.0)) == fg);
+        assert!(fill.color_at((0.5, 0.5)) == bg);
+        assert!(fill.color_at((12.0, 12.0)) == fg);
+    }
+
+    #[test]
+    fn stripes_texture_is_diagonal() {
+        assert!(TexturePattern::Stripes.is_foreground(0.1, 0.1));
+        assert!(!TexturePattern::Stripes.is_foreground(0.4, 0.4));
+        assert!(TexturePattern::Stripes.is_foreground(0.6, 0.6));
+    }
+}
@@ -28,6 +28,11 @@ pub trait GraphicsContext {
     fn dash_offset(&mut self, offset: f32);
     fn fill_color(&mut self, col: Color);
     fn stroke_color(&mut self, col: Color);
+    fn new_gradient(&mut self);
+    fn gradient_stop(&mut self, pos: f32, col: Color);
+    fn fill_linear_gradient(&mut self, x1: f32, y1: f32, x2: f32, y2: f32);
+    fn fill_radial_gradient(&mut self, x: f32, y: f32, radius: f32);
+    fn fill_texture(&mut self, pattern: TexturePattern, foreground: Color, background: Color, size: f32);
     fn blend_mode(&mut self, mode: BlendMode);
     fn identity_transform(&mut self);
     fn canvas_height(&mut self, height: f32);
@@ -43,6 +48,7 @@ pub trait GraphicsContext {
     fn clear_canvas(&mut self);
     fn layer(&mut self, layer_id: u32);
     fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode);
+    fn layer_alpha(&mut self, layer_id: u32, alpha: f32);
     fn clear_layer(&mut self);
     fn sprite(&mut self, sprite_id: SpriteId);
     fn clear_sprite(&mut self);
@@ -69,6 +75,11 @@ pub trait GraphicsContext {
             DashOffset(dash_offset)                     => self.dash_offset(dash_offset),
             FillColor(col)                              => self.fill_color(col),
             StrokeColor(col)                            => self.stroke_color(col),
+            NewGradient                                 => self.new_gradient(),
+            GradientStop(pos, col)                      => self.gradient_stop(pos, col),
+            FillLinearGradient((x1, y1), (x2, y2))      => self.fill_linear_gradient(x1, y1, x2, y2),
+            FillRadialGradient((x, y), radius)          => self.fill_radial_gradient(x, y, radius),
+            FillTexture(pattern, fg, bg, size)          => self.fill_texture(pattern, fg, bg, size),
             BlendMode(blendmode)                        => self.blend_mode(blendmode),
             IdentityTransform                           => self.identity_transform(),
             CanvasHeight(height)                        => self.canvas_height(height),
@@ -84,6 +95,7 @@ pub trait GraphicsContext {
             ClearCanvas                                 => self.clear_canvas(),
             Layer(layer_id)                             => self.layer(layer_id),
             LayerBlend(layer_id, blend_mode)            => self.layer_blend(layer_id, blend_mode),
+            LayerAlpha(layer_id, alpha)                 => self.layer_alpha(layer_id, alpha),
             ClearLayer                                  => self.clear_layer(),
             Sprite(sprite_id)                           => self.sprite(sprite_id),
             ClearSprite                                 => self.clear_sprite(),
@@ -201,6 +213,11 @@ impl GraphicsContext for Vec<Draw> {
     #[inline] fn dash_offset(&mut self, offset: f32)                                    { self.push(Draw::DashOffset(offset)); }
     #[inline] fn fill_color(&mut self, col: Color)                                      { self.push(Draw::FillColor(col)); }
     #[inline] fn stroke_color(&mut self, col: Color)                                    { self.push(Draw::StrokeColor(col)); }
+    #[inline] fn new_gradient(&mut self)                                                { self.push(Draw::NewGradient); }
+    #[inline] fn gradient_stop(&mut self, pos: f32, col: Color)                         { self.push(Draw::GradientStop(pos, col)); }
+    #[inline] fn fill_linear_gradient(&mut self, x1: f32, y1: f32, x2: f32, y2: f32)    { self.push(Draw::FillLinearGradient((x1, y1), (x2, y2))); }
+    #[inline] fn fill_radial_gradient(&mut self, x: f32, y: f32, radius: f32)           { self.push(Draw::FillRadialGradient((x, y), radius)); }
+    #[inline] fn fill_texture(&mut self, pattern: TexturePattern, fg: Color, bg: Color, size: f32) { self.push(Draw::FillTexture(pattern, fg, bg, size)); }
     #[inline] fn blend_mode(&mut self, mode: BlendMode)                                 { self.push(Draw::BlendMode(mode)); }
     #[inline] fn identity_transform(&mut self)                                          { self.push(Draw::IdentityTransform); }
     #[inline] fn canvas_height(&mut self, height: f32)                                  { self.push(Draw::CanvasHeight(height)); }
@@ -216,6 +233,7 @@ impl GraphicsContext for Vec<Draw> {
     #[inline] fn clear_canvas(&mut self)                                                { self.push(Draw::ClearCanvas); }
     #[inline] fn layer(&mut self, layer_id: u32)                                        { self.push(Draw::Layer(layer_id)); }
     #[inline] fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode)           { self.push(Draw::LayerBlend(layer_id, blend_mode)); }
+    #[inline] fn layer_alpha(&mut self, layer_id: u32, alpha: f32)                      { self.push(Draw::LayerAlpha(layer_id, alpha)); }
     #[inline] fn clear_layer(&mut self)                                                 { self.push(Draw::ClearLayer); }
     #[inline] fn sprite(&mut self, sprite_id: SpriteId)                                 { self.push(Draw::Sprite(sprite_id)); }
     #[inline] fn clear_sprite(&mut self)                                                { self.push(Draw::ClearSprite); }
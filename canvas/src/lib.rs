@@ -14,15 +14,19 @@ extern crate hsluv;
 mod gc;
 mod draw;
 mod color;
+mod fill_style;
 mod canvas;
 mod encoding;
 mod decoding;
 mod transform2d;
+mod raster;
 
 pub use self::gc::*;
 pub use self::draw::*;
 pub use self::color::*;
+pub use self::fill_style::*;
 pub use self::canvas::*;
 pub use self::encoding::*;
 pub use self::decoding::*;
 pub use self::transform2d::*;
+pub use self::raster::*;
@@ -0,0 +1,766 @@
+use super::stroke::*;
+use super::pixels::*;
+use super::coverage::*;
+use super::super::draw::*;
+use super::super::color::*;
+use super::super::fill_style::*;
+use super::super::transform2d::*;
+
+use std::sync::*;
+use std::collections::{HashMap};
+
+/// How deeply sprites can be nested inside other sprites when drawing
+const MAX_SPRITE_DEPTH: usize = 16;
+
+/// The length of each line that a bezier curve is divided into (in pixels)
+const CURVE_SEGMENT_LENGTH: f32 = 2.0;
+
+///
+/// The drawing state that's saved and restored by `PushState` and `PopState`
+///
+#[derive(Clone)]
+struct RasterState {
+    /// Transformation from canvas coordinates to pixel coordinates
+    transform: Transform2D,
+
+    /// The transformation applied to sprites when they're drawn
+    sprite_transform: Transform2D,
+
+    /// How paths are filled
+    fill_style: FillStyle,
+
+    /// The colour stops for the next gradient fill
+    gradient_stops: Vec<(f32, Color)>,
+
+    /// The colour used for strokes
+    stroke_color: Color,
+
+    /// The line width
+    line_width: f32,
+
+    /// True if the line width is in pixels rather than canvas units
+    line_width_pixels: bool,
+
+    /// How lines are joined
+    line_join: LineJoin,
+
+    /// How lines are capped
+    line_cap: LineCap,
+
+    /// The dash pattern, in canvas units
+    dash_pattern: Vec<f32>,
+
+    /// The offset into the dash pattern
+    dash_offset: f32,
+
+    /// How new drawing is blended with the existing pixels
+    blend_mode: BlendMode,
+
+    /// The clipping region, if there is one
+    clip: Option<Arc<Coverage>>
+}
+
+///
+/// A layer in the raster
+///
+struct RasterLayer {
+    /// The pixels for this layer
+    pixels: PixelBuffer,
+
+    /// How this layer is blended with the layers beneath it
+    blend_mode: BlendMode,
+
+    /// The alpha value applied to this layer when it's blended with the layers beneath it
+    alpha: f32,
+
+    /// The pixels saved by the `Store` instruction
+    stored: Option<PixelBuffer>
+}
+
+///
+/// A subpath of the path that's currently being defined (in pixel coordinates)
+///
+struct RasterSubpath {
+    points: Vec<(f32, f32)>,
+    closed: bool
+}
+
+///
+/// Renders canvas drawing instructions into a pixel buffer using only the CPU
+///
+/// This is useful for generating images where no GPU or display is available. Drawing instructions are
+/// interpreted in the same way as the other canvas implementations: (0,0) is at the center of the image and
+/// y increases upwards.
+///
+pub struct CanvasRaster {
+    /// The width of the image in pixels
+    width: usize,
+
+    /// The height of the image in pixels
+    height: usize,
+
+    /// The layers that have been drawn on
+    layers: HashMap<u32, RasterLayer>,
+
+    /// The layer that is currently being drawn on
+    current_layer: u32,
+
+    /// The drawing instructions for each sprite
+    sprites: HashMap<SpriteId, Vec<Draw>>,
+
+    /// If a sprite is being defined, the sprite that is being drawn
+    current_sprite: Option<SpriteId>,
+
+    /// How many sprites are currently being drawn
+    sprite_depth: usize,
+
+    /// The current drawing state
+    state: RasterState,
+
+    /// The states saved by PushState
+    state_stack: Vec<RasterState>,
+
+    /// The path that's currently being defined
+    path: Vec<RasterSubpath>
+}
+
+///
+/// Applies a transform to a point
+///
+#[inline]
+fn transform_point(transform: &Transform2D, (x, y): (f32, f32)) -> (f32, f32) {
+    let Transform2D(matrix) = transform;
+
+    (matrix[0][0]*x + matrix[0][1]*y + matrix[0][2], matrix[1][0]*x + matrix[1][1]*y + matrix[1][2])
+}
+
+impl RasterState {
+    ///
+    /// Creates the default drawing state
+    ///
+    fn new() -> RasterState {
+        RasterState {
+            transform:          Transform2D::identity(),
+            sprite_transform:   Transform2D::identity(),
+            fill_style:         FillStyle::Solid(Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            gradient_stops:     vec![],
+            stroke_color:       Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            line_width:         1.0,
+            line_width_pixels:  false,
+            line_join:          LineJoin::Round,
+            line_cap:           LineCap::Butt,
+            dash_pattern:       vec![],
+            dash_offset:        0.0,
+            blend_mode:         BlendMode::SourceOver,
+            clip:               None
+        }
+    }
+
+    ///
+    /// Returns the factor that the current transform scales distances by
+    ///
+    fn scale(&self) -> f32 {
+        let Transform2D(matrix) = &self.transform;
+
+        (matrix[0][0]*matrix[1][1] - matrix[0][1]*matrix[1][0]).abs().sqrt()
+    }
+}
+
+impl CanvasRaster {
+    ///
+    /// Creates a new, transparent, raster of the specified size
+    ///
+    pub fn new(width: usize, height: usize) -> CanvasRaster {
+        let mut raster = CanvasRaster {
+            width:          width,
+            height:         height,
+            layers:         HashMap::new(),
+            current_layer:  0,
+            sprites:        HashMap::new(),
+            current_sprite: None,
+            sprite_depth:   0,
+            state:          RasterState::new(),
+            state_stack:    vec![],
+            path:           vec![]
+        };
+
+        raster.canvas_height(2.0);
+        raster
+    }
+
+    ///
+    /// The width of the image in pixels
+    ///
+    pub fn width(&self) -> usize { self.width }
+
+    ///
+    /// The height of the image in pixels
+    ///
+    pub fn height(&self) -> usize { self.height }
+
+    ///
+    /// Retrieves the current layer, creating it if it doesn't exist
+    ///
+    fn layer(&mut self) -> &mut RasterLayer {
+        let (width, height) = (self.width, self.height);
+
+        self.layers.entry(self.current_layer)
+            .or_insert_with(|| RasterLayer {
+                pixels:     PixelBuffer::new(width, height),
+                blend_mode: BlendMode::SourceOver,
+                alpha:      1.0,
+                stored:     None
+            })
+    }
+
+    ///
+    /// Sets the transform so that the canvas is the specified number of units high, with (0,0) in the center
+    ///
+    fn canvas_height(&mut self, height: f32) {
+        let ratio_y     = -(self.height as f32) / height;
+        let ratio_x     = if height < 0.0 { ratio_y } else { -ratio_y };
+
+        self.state.transform = Transform2D([
+            [ratio_x,   0.0,        (self.width as f32)/2.0],
+            [0.0,       ratio_y,    (self.height as f32)/2.0],
+            [0.0,       0.0,        1.0]
+        ]);
+    }
+
+    ///
+    /// Moves the center of the specified region to the center of the canvas
+    ///
+    fn center_region(&mut self, (min_x, min_y): (f32, f32), (max_x, max_y): (f32, f32)) {
+        let Transform2D(matrix) = self.state.transform;
+
+        // Get the current scaling of the canvas
+        let x_scale     = (matrix[0][0]*matrix[0][0] + matrix[1][0]*matrix[1][0]).sqrt();
+        let y_scale     = (matrix[0][1]*matrix[0][1] + matrix[1][1]*matrix[1][1]).sqrt();
+        let x_scale     = if x_scale == 0.0 { 1.0 } else { x_scale };
+        let y_scale     = if y_scale == 0.0 { 1.0 } else { y_scale };
+
+        // Current coordinates of the center
+        let current_x   = (matrix[0][2] - (self.width as f32)/2.0) / x_scale;
+        let current_y   = (matrix[1][2] - (self.height as f32)/2.0) / y_scale;
+
+        // Move the center of the region to the center of the canvas
+        let center_x    = (min_x + max_x) / 2.0;
+        let center_y    = (min_y + max_y) / 2.0;
+
+        self.state.transform = self.state.transform * Transform2D::translate(current_x - center_x, current_y - center_y);
+    }
+
+    ///
+    /// Returns the subpath that new path components should be added to
+    ///
+    fn current_subpath(&mut self, start_point: (f32, f32)) -> &mut RasterSubpath {
+        // Closed subpaths start a new subpath at the same place
+        let start_point = match self.path.last() {
+            Some(subpath) if subpath.closed => Some(subpath.points[0]),
+            Some(_)                         => None,
+            None                            => Some(start_point)
+        };
+
+        if let Some(start_point) = start_point {
+            self.path.push(RasterSubpath { points: vec![start_point], closed: false });
+        }
+
+        self.path.last_mut().unwrap()
+    }
+
+    ///
+    /// Adds a line to the current path
+    ///
+    fn line_to(&mut self, point: (f32, f32)) {
+        let point = transform_point(&self.state.transform, point);
+        self.current_subpath(point).points.push(point);
+    }
+
+    ///
+    /// Adds a bezier curve to the current path
+    ///
+    fn bezier_curve_to(&mut self, end_point: (f32, f32), cp1: (f32, f32), cp2: (f32, f32)) {
+        let end_point   = transform_point(&self.state.transform, end_point);
+        let cp1         = transform_point(&self.state.transform, cp1);
+        let cp2         = transform_point(&self.state.transform, cp2);
+        let subpath     = self.current_subpath(end_point);
+        let start_point = subpath.points[subpath.points.len()-1];
+
+        // Divide the curve into line segments according to the length of its control polygon
+        let distance    = |(x1, y1): (f32, f32), (x2, y2): (f32, f32)| ((x2-x1)*(x2-x1) + (y2-y1)*(y2-y1)).sqrt();
+        let length      = distance(start_point, cp1) + distance(cp1, cp2) + distance(cp2, end_point);
+        let num_points  = ((length / CURVE_SEGMENT_LENGTH).ceil() as usize).max(1).min(1000);
+
+        for point_num in 1..=num_points {
+            let t   = (point_num as f32) / (num_points as f32);
+            let mt  = 1.0 - t;
+
+            let a   = mt*mt*mt;
+            let b   = 3.0*mt*mt*t;
+            let c   = 3.0*mt*t*t;
+            let d   = t*t*t;
+
+            subpath.points.push((
+                a*start_point.0 + b*cp1.0 + c*cp2.0 + d*end_point.0,
+                a*start_point.1 + b*cp1.1 + c*cp2.1 + d*end_point.1
+            ));
+        }
+    }
+
+    ///
+    /// Fills the current path
+    ///
+    fn fill(&mut self) {
+        let polygons    = self.path.iter().map(|subpath| subpath.points.clone()).collect::<Vec<_>>();
+        let coverage    = Coverage::fill_polygons(&polygons, self.width, self.height);
+        let fill_style  = self.state.fill_style.clone();
+        let blend_mode  = self.state.blend_mode;
+        let clip        = self.state.clip.clone();
+
+        match fill_style {
+            FillStyle::Solid(color) => {
+                self.layer().pixels.fill(&coverage, &color, blend_mode, clip.as_ref().map(|clip| &**clip));
+            }
+
+            fill_style              => {
+                // Gradients and textures are specified in canvas coordinates, so map the center of each pixel back to the canvas
+                let pixel_to_canvas = match self.state.transform.invert() {
+                    Some(inverse)   => inverse,
+                    None            => { return; }
+                };
+
+                self.layer().pixels.fill_with(&coverage, |col, row| {
+                    let point           = transform_point(&pixel_to_canvas, ((col as f32) + 0.5, (row as f32) + 0.5));
+                    let (r, g, b, a)    = fill_style.color_at(point).to_rgba_components();
+
+                    [r*a, g*a, b*a, a]
+                }, blend_mode, clip.as_ref().map(|clip| &**clip));
+            }
+        }
+    }
+
+    ///
+    /// Strokes the current path
+    ///
+    fn stroke(&mut self) {
+        // Line widths and dash patterns are in canvas units unless the width is set in pixels
+        let scale       = self.state.scale();
+        let width       = if self.state.line_width_pixels { self.state.line_width } else { self.state.line_width * scale };
+        let options     = StrokeOptions {
+            width:          width,
+            join:           self.state.line_join,
+            cap:            self.state.line_cap,
+            dash_pattern:   self.state.dash_pattern.iter().map(|len| len * scale).collect(),
+            dash_offset:    self.state.dash_offset * scale
+        };
+
+        let subpaths    = self.path.iter().map(|subpath| (subpath.points.clone(), subpath.closed)).collect::<Vec<_>>();
+        let polygons    = stroke_polygons(&subpaths, &options);
+        let coverage    = Coverage::fill_polygons(&polygons, self.width, self.height);
+        let color       = self.state.stroke_color;
+        let blend_mode  = self.state.blend_mode;
+        let clip        = self.state.clip.clone();
+
+        self.layer().pixels.fill(&coverage, &color, blend_mode, clip.as_ref().map(|clip| &**clip));
+    }
+
+    ///
+    /// Sets the clipping region to the current path (intersected with any existing clipping region)
+    ///
+    fn clip(&mut self) {
+        let polygons    = self.path.iter().map(|subpath| subpath.points.clone()).collect::<Vec<_>>();
+        let coverage    = Coverage::fill_polygons(&polygons, self.width, self.height);
+        let coverage    = match &self.state.clip {
+            Some(existing)  => coverage.intersect(existing),
+            None            => coverage
+        };
+
+        self.state.clip = Some(Arc::new(coverage));
+    }
+
+    ///
+    /// Updates the transform applied to sprites
+    ///
+    fn sprite_transform(&mut self, transform: SpriteTransform) {
+        self.state.sprite_transform = match transform {
+            SpriteTransform::Identity   => Transform2D::identity(),
+            other                       => self.state.sprite_transform * Transform2D::from(other)
+        };
+    }
+
+    ///
+    /// Draws the instructions stored for a sprite
+    ///
+    fn draw_sprite(&mut self, sprite_id: SpriteId) {
+        if self.sprite_depth >= MAX_SPRITE_DEPTH {
+            return;
+        }
+
+        let sprite = match self.sprites.get(&sprite_id) {
+            Some(sprite)    => sprite.clone(),
+            None            => { return; }
+        };
+
+        // Sprites are drawn with their own state and path, using the sprite transform
+        let old_state           = self.state.clone();
+        let old_path            = std::mem::replace(&mut self.path, vec![]);
+        self.state.transform    = self.state.transform * self.state.sprite_transform;
+        self.state.sprite_transform = Transform2D::identity();
+        self.sprite_depth       += 1;
+
+        sprite.into_iter().for_each(|draw| self.draw(draw));
+
+        self.sprite_depth       -= 1;
+        self.path               = old_path;
+        self.state              = old_state;
+    }
+
+    ///
+    /// Processes a single drawing instruction
+    ///
+    pub fn draw(&mut self, drawing: Draw) {
+        use self::Draw::*;
+
+        // While a sprite is selected, instructions are stored for later instead of being drawn
+        if let Some(sprite_id) = self.current_sprite {
+            match drawing {
+                Sprite(new_sprite_id)   => { self.current_sprite = Some(new_sprite_id); return; }
+                ClearSprite             => { self.sprites.insert(sprite_id, vec![]); return; }
+
+                // Selecting a layer or clearing the canvas stops drawing the sprite
+                Layer(_)                |
+                ClearCanvas             => { self.current_sprite = None; }
+
+                other                   => { self.sprites.entry(sprite_id).or_insert_with(|| vec![]).push(other); return; }
+            }
+        }
+
+        match drawing {
+            NewPath                             => { self.path = vec![]; }
+            Move(x, y)                          => {
+                let point = transform_point(&self.state.transform, (x, y));
+                self.path.push(RasterSubpath { points: vec![point], closed: false });
+            }
+            Line(x, y)                          => { self.line_to((x, y)); }
+            BezierCurve(end, cp1, cp2)          => { self.bezier_curve_to(end, cp1, cp2); }
+            ClosePath                           => { if let Some(subpath) = self.path.last_mut() { subpath.closed = true; } }
+            Fill                                => { self.fill(); }
+            Stroke                              => { self.stroke(); }
+
+            LineWidth(width)                    => { self.state.line_width = width; self.state.line_width_pixels = false; }
+            LineWidthPixels(width)              => { self.state.line_width = width; self.state.line_width_pixels = true; }
+            LineJoin(join)                      => { self.state.line_join = join; }
+            LineCap(cap)                        => { self.state.line_cap = cap; }
+            NewDashPattern                      => { self.state.dash_pattern = vec![]; }
+            DashLength(length)                  => { self.state.dash_pattern.push(length); }
+            DashOffset(offset)                  => { self.state.dash_offset = offset; }
+            FillColor(color)                    => { self.state.fill_style = FillStyle::Solid(color); }
+            NewGradient                         => { self.state.gradient_stops = vec![]; }
+            GradientStop(pos, color)            => { self.state.gradient_stops.push((pos, color)); }
+            FillLinearGradient(start, end)      => { self.state.fill_style = FillStyle::LinearGradient(self.state.gradient_stops.clone(), start, end); }
+            FillRadialGradient(center, radius)  => { self.state.fill_style = FillStyle::RadialGradient(self.state.gradient_stops.clone(), center, radius); }
+            FillTexture(pattern, fg, bg, size)  => { self.state.fill_style = FillStyle::Texture(pattern, fg, bg, size); }
+            StrokeColor(color)                  => { self.state.stroke_color = color; }
+            BlendMode(blend_mode)               => { self.state.blend_mode = blend_mode; }
+
+            IdentityTransform                   => { self.canvas_height(2.0); }
+            CanvasHeight(height)                => { self.canvas_height(height); }
+            CenterRegion(min, max)              => { self.center_region(min, max); }
+            MultiplyTransform(transform)        => { self.state.transform = self.state.transform * transform; }
+
+            Unclip                              => { self.state.clip = None; }
+            Clip                                => { self.clip(); }
+
+            Store                               => {
+                let layer       = self.layer();
+                layer.stored    = Some(layer.pixels.clone());
+            }
+
+            Restore                             => {
+                let clip        = self.state.clip.clone();
+                let layer       = self.layer();
+
+                if let Some(stored) = layer.stored.as_ref() {
+                    layer.pixels.restore_from(stored, clip.as_ref().map(|clip| &**clip));
+                }
+            }
+
+            FreeStoredBuffer                    => { self.layer().stored = None; }
+            PushState                           => { self.state_stack.push(self.state.clone()); }
+            PopState                            => { if let Some(state) = self.state_stack.pop() { self.state = state; } }
+
+            ClearCanvas                         => {
+                // Sprites survive clearing the canvas, but everything else is reset
+                self.layers         = HashMap::new();
+                self.current_layer  = 0;
+                self.state          = RasterState::new();
+                self.state_stack    = vec![];
+                self.path           = vec![];
+                self.canvas_height(2.0);
+            }
+
+            Layer(layer_id)                     => { self.current_layer = layer_id; }
+            LayerBlend(layer_id, blend_mode)    => {
+                let current_layer   = self.current_layer;
+                self.current_layer  = layer_id;
+                self.layer().blend_mode = blend_mode;
+                self.current_layer  = current_layer;
+            }
+            LayerAlpha(layer_id, alpha)         => {
+                let current_layer   = self.current_layer;
+                self.current_layer  = layer_id;
+                self.layer().alpha  = alpha;
+                self.current_layer  = current_layer;
+            }
+            ClearLayer                          => { self.layer().pixels.clear(); }
+
+            Sprite(sprite_id)                   => { self.current_sprite = Some(sprite_id); }
+            ClearSprite                         => { }
+            SpriteTransform(transform)          => { self.sprite_transform(transform); }
+            DrawSprite(sprite_id)               => { self.draw_sprite(sprite_id); }
+        }
+    }
+
+    ///
+    /// Processes a series of drawing instructions
+    ///
+    pub fn draw_all<DrawIter: IntoIterator<Item=Draw>>(&mut self, drawing: DrawIter) {
+        drawing.into_iter().for_each(|draw| self.draw(draw));
+    }
+
+    ///
+    /// Combines the layers of this raster into a single pixel buffer
+    ///
+    pub fn to_pixels(&self) -> PixelBuffer {
+        let mut result      = PixelBuffer::new(self.width, self.height);
+        let mut layer_ids   = self.layers.keys().cloned().collect::<Vec<_>>();
+        layer_ids.sort();
+
+        for layer_id in layer_ids {
+            let layer = &self.layers[&layer_id];
+            result.draw_buffer_with_alpha(&layer.pixels, layer.blend_mode, layer.alpha);
+        }
+
+        result
+    }
+
+    ///
+    /// Generates the 8-bit RGBA data for this raster (suitable for writing out as a PNG file)
+    ///
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.to_pixels().to_rgba8()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rectangle(raster: &mut CanvasRaster, (x1, y1): (f32, f32), (x2, y2): (f32, f32)) {
+        raster.draw_all(vec![
+            Draw::NewPath,
+            Draw::Move(x1, y1),
+            Draw::Line(x2, y1),
+            Draw::Line(x2, y2),
+            Draw::Line(x1, y2),
+            Draw::ClosePath
+        ]);
+    }
+
+    fn pixel(raster: &CanvasRaster, x: usize, y: usize) -> [u8; 4] {
+        let rgba    = raster.to_rgba8();
+        let pos     = (x + y*raster.width())*4;
+
+        [rgba[pos], rgba[pos+1], rgba[pos+2], rgba[pos+3]]
+    }
+
+    #[test]
+    fn starts_transparent() {
+        let raster = CanvasRaster::new(10, 10);
+
+        assert!(pixel(&raster, 5, 5) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn y_increases_upwards() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw(Draw::CanvasHeight(100.0));
+        raster.draw(Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)));
+        raster.draw(Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        rectangle(&mut raster, (0.0, 0.0), (100.0, 50.0));
+        raster.draw(Draw::Fill);
+
+        // Bottom half is filled
+        assert!(pixel(&raster, 50, 75) == [255, 0, 0, 255]);
+        assert!(pixel(&raster, 50, 25) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn stroke_line() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw_all(vec![
+            Draw::CanvasHeight(100.0),
+            Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)),
+            Draw::StrokeColor(Color::Rgba(0.0, 0.0, 1.0, 1.0)),
+            Draw::LineWidth(4.0),
+            Draw::NewPath,
+            Draw::Move(10.0, 50.0),
+            Draw::Line(90.0, 50.0),
+            Draw::Stroke
+        ]);
+
+        assert!(pixel(&raster, 50, 49) == [0, 0, 255, 255]);
+        assert!(pixel(&raster, 50, 40) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn clip_fill() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw(Draw::CanvasHeight(100.0));
+        raster.draw(Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)));
+        rectangle(&mut raster, (0.0, 0.0), (50.0, 100.0));
+        raster.draw(Draw::Clip);
+
+        raster.draw(Draw::FillColor(Color::Rgba(0.0, 1.0, 0.0, 1.0)));
+        rectangle(&mut raster, (0.0, 0.0), (100.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        assert!(pixel(&raster, 25, 50) == [0, 255, 0, 255]);
+        assert!(pixel(&raster, 75, 50) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill_linear_gradient() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw_all(vec![
+            Draw::CanvasHeight(100.0),
+            Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)),
+            Draw::NewGradient,
+            Draw::GradientStop(0.0, Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            Draw::GradientStop(1.0, Color::Rgba(1.0, 1.0, 1.0, 1.0)),
+            Draw::FillLinearGradient((0.0, 0.0), (100.0, 0.0))
+        ]);
+        rectangle(&mut raster, (0.0, 0.0), (100.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        let left    = pixel(&raster, 10, 50);
+        let middle  = pixel(&raster, 50, 50);
+        let right   = pixel(&raster, 90, 50);
+
+        assert!(left[0] < middle[0] && middle[0] < right[0]);
+        assert!((middle[0] as i32 - 128).abs() <= 2);
+        assert!(left[3] == 255 && right[3] == 255);
+    }
+
+    #[test]
+    fn fill_texture() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw_all(vec![
+            Draw::CanvasHeight(100.0),
+            Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)),
+            Draw::FillTexture(TexturePattern::Checks, Color::Rgba(1.0, 0.0, 0.0, 1.0), Color::Rgba(0.0, 0.0, 1.0, 1.0), 20.0)
+        ]);
+        rectangle(&mut raster, (0.0, 0.0), (100.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        // Canvas y coordinates increase upwards, so pixel row 95 is canvas y = 4.5
+        assert!(pixel(&raster, 5, 95) == [255, 0, 0, 255]);
+        assert!(pixel(&raster, 15, 95) == [0, 0, 255, 255]);
+        assert!(pixel(&raster, 15, 85) == [255, 0, 0, 255]);
+
+        // Switching back to a colour fills with a solid colour again
+        raster.draw(Draw::FillColor(Color::Rgba(0.0, 1.0, 0.0, 1.0)));
+        raster.draw(Draw::Fill);
+
+        assert!(pixel(&raster, 5, 95) == [0, 255, 0, 255]);
+        assert!(pixel(&raster, 15, 95) == [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn layers_are_drawn_in_order() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw(Draw::CanvasHeight(100.0));
+        raster.draw(Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)));
+
+        raster.draw(Draw::Layer(1));
+        raster.draw(Draw::FillColor(Color::Rgba(0.0, 1.0, 0.0, 1.0)));
+        rectangle(&mut raster, (0.0, 0.0), (100.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        raster.draw(Draw::Layer(0));
+        raster.draw(Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        rectangle(&mut raster, (0.0, 0.0), (100.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        assert!(pixel(&raster, 50, 50) == [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn layer_blend_mode() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw(Draw::CanvasHeight(100.0));
+        raster.draw(Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)));
+
+        raster.draw(Draw::FillColor(Color::Rgba(1.0, 1.0, 1.0, 1.0)));
+        rectangle(&mut raster, (0.0, 0.0), (100.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        // Layer 1 erases the left half of layer 0
+        raster.draw(Draw::LayerBlend(1, BlendMode::DestinationOut));
+        raster.draw(Draw::Layer(1));
+        rectangle(&mut raster, (0.0, 0.0), (50.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        assert!(pixel(&raster, 25, 50) == [0, 0, 0, 0]);
+        assert!(pixel(&raster, 75, 50) == [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn layer_alpha() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw(Draw::CanvasHeight(100.0));
+        raster.draw(Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)));
+
+        // Layer 1 is drawn opaque, but is composited at half opacity
+        raster.draw(Draw::LayerAlpha(1, 0.5));
+        raster.draw(Draw::Layer(1));
+        raster.draw(Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        rectangle(&mut raster, (0.0, 0.0), (50.0, 100.0));
+        raster.draw(Draw::Fill);
+
+        assert!(pixel(&raster, 25, 50) == [255, 0, 0, 128]);
+        assert!(pixel(&raster, 75, 50) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn draw_sprite_with_transform() {
+        let mut raster = CanvasRaster::new(100, 100);
+
+        raster.draw(Draw::CanvasHeight(100.0));
+        raster.draw(Draw::CenterRegion((0.0, 0.0), (100.0, 100.0)));
+
+        // Sprite is a 10x10 square at the origin
+        raster.draw(Draw::Sprite(SpriteId(1)));
+        raster.draw(Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        rectangle(&mut raster, (0.0, 0.0), (10.0, 10.0));
+        raster.draw(Draw::Fill);
+
+        // Nothing drawn until the sprite is rendered
+        raster.draw(Draw::Layer(0));
+        assert!(pixel(&raster, 5, 95) == [0, 0, 0, 0]);
+
+        raster.draw(Draw::SpriteTransform(SpriteTransform::Translate(50.0, 50.0)));
+        raster.draw(Draw::DrawSprite(SpriteId(1)));
+
+        assert!(pixel(&raster, 55, 45) == [255, 0, 0, 255]);
+        assert!(pixel(&raster, 5, 95) == [0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,293 @@
+use std::f32;
+use std::cmp::{Ordering};
+
+/// The number of scanlines that are sampled for each row of pixels
+const SUBSAMPLES: usize = 4;
+
+///
+/// An edge of a polygon, with y1 < y2
+///
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+
+    /// +1 if the edge originally went downwards, -1 if it went upwards
+    direction: i32
+}
+
+///
+/// Describes how much of each pixel in a rectangular region is covered by a shape
+///
+/// Pixels outside of the region are not covered at all. Coverage values range from 0.0 (not covered)
+/// to 1.0 (fully covered).
+///
+#[derive(Clone, Debug)]
+pub struct Coverage {
+    /// The left-hand side of the region
+    x: usize,
+
+    /// The top of the region
+    y: usize,
+
+    /// The width of the region
+    width: usize,
+
+    /// The height of the region
+    height: usize,
+
+    /// The coverage value for each pixel in the region
+    values: Vec<f32>
+}
+
+impl Coverage {
+    ///
+    /// Creates a coverage object that doesn't cover any pixels
+    ///
+    pub fn empty() -> Coverage {
+        Coverage {
+            x:      0,
+            y:      0,
+            width:  0,
+            height: 0,
+            values: vec![]
+        }
+    }
+
+    ///
+    /// Creates a coverage object that covers every pixel in an image
+    ///
+    pub fn full(width: usize, height: usize) -> Coverage {
+        Coverage {
+            x:      0,
+            y:      0,
+            width:  width,
+            height: height,
+            values: vec![1.0; width*height]
+        }
+    }
+
+    ///
+    /// Returns the region covered by this object as (x, y, width, height)
+    ///
+    pub fn region(&self) -> (usize, usize, usize, usize) {
+        (self.x, self.y, self.width, self.height)
+    }
+
+    ///
+    /// Retrieves the coverage of a particular pixel
+    ///
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        if x < self.x || y < self.y || x >= self.x + self.width || y >= self.y + self.height {
+            0.0
+        } else {
+            self.values[(x-self.x) + (y-self.y)*self.width]
+        }
+    }
+
+    ///
+    /// Returns the coverage of the pixels that are covered by both this object and another one
+    ///
+    pub fn intersect(&self, other: &Coverage) -> Coverage {
+        let mut values = self.values.clone();
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                values[col + row*self.width] *= other.get(col + self.x, row + self.y);
+            }
+        }
+
+        Coverage {
+            x:      self.x,
+            y:      self.y,
+            width:  self.width,
+            height: self.height,
+            values: values
+        }
+    }
+
+    ///
+    /// Adds the coverage for the span from x1 to x2 to a row of values starting at x0
+    ///
+    #[inline]
+    fn add_span(row: &mut [f32], x0: usize, x1: f32, x2: f32, weight: f32) {
+        // Clip the span to the row
+        let x1 = (x1 - x0 as f32).max(0.0);
+        let x2 = (x2 - x0 as f32).min(row.len() as f32);
+
+        if x1 >= x2 {
+            return;
+        }
+
+        // Add the part of the span that overlaps each pixel
+        let first   = x1.floor() as usize;
+        let last    = (x2.ceil() as usize).min(row.len());
+
+        for pixel in first..last {
+            let overlap = x2.min((pixel+1) as f32) - x1.max(pixel as f32);
+            row[pixel] += overlap * weight;
+        }
+    }
+
+    ///
+    /// Computes the coverage of a set of polygons using the non-zero winding rule
+    ///
+    /// The polygons are in pixel coordinates, and the result is clipped to an image of the specified size.
+    ///
+    pub fn fill_polygons(polygons: &[Vec<(f32, f32)>], image_width: usize, image_height: usize) -> Coverage {
+        // Gather the edges of the polygons
+        let mut edges                           = vec![];
+        let (mut min_x, mut min_y)              = (f32::MAX, f32::MAX);
+        let (mut max_x, mut max_y)              = (f32::MIN, f32::MIN);
+
+        for polygon in polygons.iter() {
+            if polygon.len() < 2 { continue; }
+
+            for idx in 0..polygon.len() {
+                let (x1, y1) = polygon[idx];
+                let (x2, y2) = polygon[(idx+1) % polygon.len()];
+
+                if !x1.is_finite() || !y1.is_finite() || !x2.is_finite() || !y2.is_finite() {
+                    continue;
+                }
+
+                min_x = min_x.min(x1).min(x2);
+                min_y = min_y.min(y1).min(y2);
+                max_x = max_x.max(x1).max(x2);
+                max_y = max_y.max(y1).max(y2);
+
+                if y1 < y2 {
+                    edges.push(Edge { x1: x1, y1: y1, x2: x2, y2: y2, direction: 1 });
+                } else if y2 < y1 {
+                    edges.push(Edge { x1: x2, y1: y2, x2: x1, y2: y1, direction: -1 });
+                }
+            }
+        }
+
+        if edges.len() == 0 {
+            return Coverage::empty();
+        }
+
+        // Work out the region covered by the polygons
+        let x_start = (min_x.floor().max(0.0) as usize).min(image_width);
+        let y_start = (min_y.floor().max(0.0) as usize).min(image_height);
+        let x_end   = (max_x.ceil().max(0.0) as usize).min(image_width);
+        let y_end   = (max_y.ceil().max(0.0) as usize).min(image_height);
+
+        if x_start >= x_end || y_start >= y_end {
+            return Coverage::empty();
+        }
+
+        let width       = x_end - x_start;
+        let height      = y_end - y_start;
+        let mut values  = vec![0.0; width*height];
+
+        // Scan the edges from top to bottom
+        edges.sort_by(|a, b| a.y1.partial_cmp(&b.y1).unwrap_or(Ordering::Equal));
+
+        let weight          = 1.0 / (SUBSAMPLES as f32);
+        let mut next_edge   = 0;
+        let mut active      = vec![];
+        let mut crossings   = vec![];
+
+        for row in 0..height {
+            let row_values = &mut values[(row*width)..((row+1)*width)];
+
+            for subsample in 0..SUBSAMPLES {
+                let sample_y = ((y_start + row) as f32) + ((subsample as f32) + 0.5) * weight;
+
+                // Update the list of edges that cross this scanline
+                while next_edge < edges.len() && edges[next_edge].y1 <= sample_y {
+                    active.push(edges[next_edge]);
+                    next_edge += 1;
+                }
+                active.retain(|edge: &Edge| edge.y2 > sample_y);
+
+                // Find where the active edges cross the scanline
+                crossings.clear();
+                crossings.extend(active.iter().map(|edge| {
+                    let t = (sample_y - edge.y1) / (edge.y2 - edge.y1);
+                    (edge.x1 + t*(edge.x2 - edge.x1), edge.direction)
+                }));
+                crossings.sort_by(|a: &(f32, i32), b: &(f32, i32)| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+                // Fill the spans where the winding number is non-zero
+                let mut winding = 0;
+                for idx in 0..crossings.len().saturating_sub(1) {
+                    winding += crossings[idx].1;
+
+                    if winding != 0 {
+                        Self::add_span(row_values, x_start, crossings[idx].0, crossings[idx+1].0, weight);
+                    }
+                }
+            }
+        }
+
+        // Overlapping spans can't cover a pixel more than once
+        values.iter_mut().for_each(|value| *value = value.min(1.0));
+
+        Coverage {
+            x:      x_start,
+            y:      y_start,
+            width:  width,
+            height: height,
+            values: values
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fill_square() {
+        let square      = vec![(2.0, 2.0), (6.0, 2.0), (6.0, 6.0), (2.0, 6.0)];
+        let coverage    = Coverage::fill_polygons(&[square], 10, 10);
+
+        assert!(coverage.region() == (2, 2, 4, 4));
+        assert!((coverage.get(3, 3) - 1.0).abs() < 0.001);
+        assert!(coverage.get(1, 3) == 0.0);
+        assert!(coverage.get(7, 3) == 0.0);
+    }
+
+    #[test]
+    fn partial_coverage_at_edges() {
+        let square      = vec![(2.5, 2.0), (6.0, 2.0), (6.0, 6.0), (2.5, 6.0)];
+        let coverage    = Coverage::fill_polygons(&[square], 10, 10);
+
+        assert!((coverage.get(2, 3) - 0.5).abs() < 0.001);
+        assert!((coverage.get(3, 3) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn clip_to_image() {
+        let square      = vec![(-5.0, -5.0), (20.0, -5.0), (20.0, 20.0), (-5.0, 20.0)];
+        let coverage    = Coverage::fill_polygons(&[square], 10, 10);
+
+        assert!(coverage.region() == (0, 0, 10, 10));
+        assert!((coverage.get(9, 9) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn non_zero_winding_fills_hole_with_same_direction() {
+        let outer       = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let inner       = vec![(3.0, 3.0), (7.0, 3.0), (7.0, 7.0), (3.0, 7.0)];
+        let coverage    = Coverage::fill_polygons(&[outer, inner], 10, 10);
+
+        assert!((coverage.get(5, 5) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn non_zero_winding_leaves_hole_with_opposite_direction() {
+        let outer       = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let inner       = vec![(3.0, 3.0), (3.0, 7.0), (7.0, 7.0), (7.0, 3.0)];
+        let coverage    = Coverage::fill_polygons(&[outer, inner], 10, 10);
+
+        assert!(coverage.get(5, 5) == 0.0);
+        assert!((coverage.get(1, 1) - 1.0).abs() < 0.001);
+    }
+}
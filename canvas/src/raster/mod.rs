@@ -0,0 +1,15 @@
+//!
+//! Software rendering for canvas drawing instructions
+//!
+//! This renders a stream of `Draw` instructions to a pixel buffer without needing a GPU or a window, which makes
+//! it suitable for generating images from the command line.
+//!
+
+mod coverage;
+mod stroke;
+mod pixels;
+mod canvas_raster;
+
+pub use self::coverage::*;
+pub use self::pixels::*;
+pub use self::canvas_raster::*;
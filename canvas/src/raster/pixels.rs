@@ -0,0 +1,234 @@
+use super::coverage::*;
+use super::super::draw::*;
+use super::super::color::*;
+
+///
+/// Blends a source pixel onto a destination pixel (both with premultiplied alpha)
+///
+#[inline]
+pub fn blend_pixel(src: [f32; 4], dst: [f32; 4], blend_mode: BlendMode) -> [f32; 4] {
+    use self::BlendMode::*;
+
+    let src_alpha   = src[3];
+    let dst_alpha   = dst[3];
+    let mut result  = [0.0; 4];
+
+    // The formulae for premultiplied alpha work the same way for the colour and alpha components
+    for component in 0..4 {
+        let s = src[component];
+        let d = dst[component];
+
+        result[component] = match blend_mode {
+            SourceOver      => s + d*(1.0-src_alpha),
+            SourceIn        => s*dst_alpha,
+            SourceOut       => s*(1.0-dst_alpha),
+            DestinationOver => d + s*(1.0-dst_alpha),
+            DestinationIn   => d*src_alpha,
+            DestinationOut  => d*(1.0-src_alpha),
+            SourceAtop      => s*dst_alpha + d*(1.0-src_alpha),
+            DestinationAtop => d*src_alpha + s*(1.0-dst_alpha),
+
+            Multiply        => s*(1.0-dst_alpha) + d*(1.0-src_alpha) + s*d,
+            Screen          => s + d - s*d,
+            Darken          => s + d - f32::max(s*dst_alpha, d*src_alpha),
+            Lighten         => s + d - f32::min(s*dst_alpha, d*src_alpha)
+        };
+    }
+
+    result
+}
+
+///
+/// A buffer of pixels, stored as RGBA values with premultiplied alpha
+///
+#[derive(Clone)]
+pub struct PixelBuffer {
+    /// The width of the buffer in pixels
+    width: usize,
+
+    /// The height of the buffer in pixels
+    height: usize,
+
+    /// The pixels, row by row from the top-left
+    pixels: Vec<[f32; 4]>
+}
+
+impl PixelBuffer {
+    ///
+    /// Creates a new, transparent, pixel buffer
+    ///
+    pub fn new(width: usize, height: usize) -> PixelBuffer {
+        PixelBuffer {
+            width:  width,
+            height: height,
+            pixels: vec![[0.0, 0.0, 0.0, 0.0]; width*height]
+        }
+    }
+
+    ///
+    /// The width of this buffer in pixels
+    ///
+    pub fn width(&self) -> usize { self.width }
+
+    ///
+    /// The height of this buffer in pixels
+    ///
+    pub fn height(&self) -> usize { self.height }
+
+    ///
+    /// Retrieves the pixel at the specified position (as premultiplied RGBA)
+    ///
+    pub fn pixel(&self, x: usize, y: usize) -> [f32; 4] {
+        self.pixels[x + y*self.width]
+    }
+
+    ///
+    /// Makes every pixel in this buffer transparent
+    ///
+    pub fn clear(&mut self) {
+        self.pixels.iter_mut().for_each(|pixel| *pixel = [0.0, 0.0, 0.0, 0.0]);
+    }
+
+    ///
+    /// Blends a colour into the pixels covered by a shape
+    ///
+    pub fn fill(&mut self, coverage: &Coverage, color: &Color, blend_mode: BlendMode, clip: Option<&Coverage>) {
+        let (r, g, b, a)    = color.to_rgba_components();
+        let color           = [r*a, g*a, b*a, a];
+
+        self.fill_with(coverage, |_, _| color, blend_mode, clip);
+    }
+
+    ///
+    /// Blends a colour that varies from pixel to pixel into the pixels covered by a shape
+    ///
+    /// The colour function is passed the column and row of each pixel and should return a premultiplied colour.
+    ///
+    pub fn fill_with<ColorFn: Fn(usize, usize) -> [f32; 4]>(&mut self, coverage: &Coverage, color_at: ColorFn, blend_mode: BlendMode, clip: Option<&Coverage>) {
+        let (x, y, width, height)   = coverage.region();
+
+        for row in y..(y+height).min(self.height) {
+            for col in x..(x+width).min(self.width) {
+                // Work out how much of this pixel to blend
+                let mut amount = coverage.get(col, row);
+                if let Some(clip) = clip {
+                    amount *= clip.get(col, row);
+                }
+
+                if amount <= 0.0 { continue; }
+
+                // Blend the colour with the existing pixel
+                let color   = color_at(col, row);
+                let pixel   = &mut self.pixels[col + row*self.width];
+                let blended = blend_pixel(color, *pixel, blend_mode);
+
+                for component in 0..4 {
+                    pixel[component] += (blended[component] - pixel[component]) * amount;
+                }
+            }
+        }
+    }
+
+    ///
+    /// Blends another buffer of the same size on top of this one
+    ///
+    pub fn draw_buffer(&mut self, src: &PixelBuffer, blend_mode: BlendMode) {
+        self.draw_buffer_with_alpha(src, blend_mode, 1.0);
+    }
+
+    ///
+    /// Blends another buffer of the same size on top of this one, scaling its pixels by an alpha value first
+    ///
+    pub fn draw_buffer_with_alpha(&mut self, src: &PixelBuffer, blend_mode: BlendMode, alpha: f32) {
+        let alpha = alpha.max(0.0).min(1.0);
+
+        for (dst, src) in self.pixels.iter_mut().zip(src.pixels.iter()) {
+            let src = [src[0]*alpha, src[1]*alpha, src[2]*alpha, src[3]*alpha];
+            *dst    = blend_pixel(src, *dst, blend_mode);
+        }
+    }
+
+    ///
+    /// Replaces the pixels in this buffer with the pixels from another buffer of the same size, within a clipping region
+    ///
+    pub fn restore_from(&mut self, stored: &PixelBuffer, clip: Option<&Coverage>) {
+        match clip {
+            None        => { self.pixels.clone_from(&stored.pixels); }
+
+            Some(clip)  => {
+                for row in 0..self.height {
+                    for col in 0..self.width {
+                        let amount = clip.get(col, row);
+                        if amount <= 0.0 { continue; }
+
+                        let pixel   = &mut self.pixels[col + row*self.width];
+                        let stored  = stored.pixels[col + row*self.width];
+
+                        for component in 0..4 {
+                            pixel[component] += (stored[component] - pixel[component]) * amount;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    ///
+    /// Converts this buffer to 8-bit RGBA values (without premultiplied alpha), as used by most image formats
+    ///
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.pixels.len()*4);
+
+        for pixel in self.pixels.iter() {
+            let alpha = pixel[3].max(0.0).min(1.0);
+
+            for component in 0..3 {
+                let value = if alpha > 0.0 { pixel[component] / alpha } else { 0.0 };
+                result.push((value.max(0.0).min(1.0) * 255.0).round() as u8);
+            }
+
+            result.push((alpha * 255.0).round() as u8);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn source_over_opaque() {
+        let result = blend_pixel([1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0], BlendMode::SourceOver);
+        assert!(result == [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn source_over_transparent() {
+        let result = blend_pixel([0.5, 0.0, 0.0, 0.5], [0.0, 0.0, 1.0, 1.0], BlendMode::SourceOver);
+        assert!(result == [0.5, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn destination_out_erases() {
+        let result = blend_pixel([1.0, 1.0, 1.0, 1.0], [0.0, 0.0, 1.0, 1.0], BlendMode::DestinationOut);
+        assert!(result == [0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn multiply() {
+        let result = blend_pixel([0.5, 0.5, 0.5, 1.0], [1.0, 0.5, 0.0, 1.0], BlendMode::Multiply);
+        assert!(result == [0.5, 0.25, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn rgba8_is_not_premultiplied() {
+        let mut buffer  = PixelBuffer::new(1, 1);
+        let coverage    = Coverage::full(1, 1);
+
+        buffer.fill(&coverage, &Color::Rgba(1.0, 0.0, 0.0, 0.5), BlendMode::SourceOver, None);
+
+        assert!(buffer.to_rgba8() == vec![255, 0, 0, 128]);
+    }
+}
@@ -0,0 +1,325 @@
+use super::super::draw::*;
+
+use std::f32;
+
+/// Miters longer than this multiple of the line width are drawn as bevels instead
+const MITER_LIMIT: f32 = 10.0;
+
+///
+/// Describes how a path should be stroked (with all distances in pixels)
+///
+#[derive(Clone, Debug)]
+pub struct StrokeOptions {
+    /// The width of the line
+    pub width: f32,
+
+    /// How lines are joined
+    pub join: LineJoin,
+
+    /// How the ends of lines are capped
+    pub cap: LineCap,
+
+    /// The dash pattern (empty for a solid line)
+    pub dash_pattern: Vec<f32>,
+
+    /// The offset into the dash pattern
+    pub dash_offset: f32
+}
+
+///
+/// Makes sure that a polygon goes around clockwise (so that the non-zero winding rule forms the union of a set of polygons)
+///
+fn orient(mut polygon: Vec<(f32, f32)>) -> Vec<(f32, f32)> {
+    let mut area = 0.0;
+
+    for idx in 0..polygon.len() {
+        let (x1, y1) = polygon[idx];
+        let (x2, y2) = polygon[(idx+1) % polygon.len()];
+
+        area += x1*y2 - x2*y1;
+    }
+
+    if area < 0.0 {
+        polygon.reverse();
+    }
+
+    polygon
+}
+
+///
+/// Creates a polygon approximating a circle
+///
+fn circle((x, y): (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+    let num_points = ((radius * f32::consts::PI).ceil() as usize).max(8).min(64);
+
+    (0..num_points)
+        .map(|idx| {
+            let angle = (idx as f32) / (num_points as f32) * 2.0 * f32::consts::PI;
+            (x + radius*angle.cos(), y + radius*angle.sin())
+        })
+        .collect()
+}
+
+///
+/// Splits a polyline up according to a dash pattern
+///
+fn dash(points: &[(f32, f32)], pattern: &[f32], offset: f32) -> Vec<Vec<(f32, f32)>> {
+    let total_length: f32 = pattern.iter().sum();
+
+    // Nothing to do if the pattern is empty or can't make progress
+    if pattern.len() == 0 || total_length <= 0.0 || pattern.iter().any(|len| *len < 0.0) {
+        return vec![points.to_vec()];
+    }
+
+    // Work out where in the pattern we start
+    let mut pattern_idx = 0;
+    let mut remaining   = pattern[0];
+    let mut offset      = offset % total_length;
+    if offset < 0.0 { offset += total_length; }
+
+    while offset > 0.0 {
+        if offset >= remaining {
+            offset      -= remaining;
+            pattern_idx = (pattern_idx + 1) % pattern.len();
+            remaining   = pattern[pattern_idx];
+        } else {
+            remaining   -= offset;
+            offset      = 0.0;
+        }
+    }
+
+    // Even entries in the pattern are dashes, odd entries are gaps
+    let mut dashes  = vec![];
+    let mut current = if pattern_idx % 2 == 0 { Some(vec![points[0]]) } else { None };
+
+    for idx in 1..points.len() {
+        let (mut x1, mut y1)    = points[idx-1];
+        let (x2, y2)            = points[idx];
+        let mut length          = ((x2-x1)*(x2-x1) + (y2-y1)*(y2-y1)).sqrt();
+
+        while length > remaining {
+            // Move to the end of the current dash or gap
+            let t       = remaining / length;
+            x1          = x1 + (x2-x1)*t;
+            y1          = y1 + (y2-y1)*t;
+            length      -= remaining;
+
+            match current.take() {
+                Some(mut dash)  => { dash.push((x1, y1)); dashes.push(dash); }
+                None            => { current = Some(vec![(x1, y1)]); }
+            }
+
+            pattern_idx = (pattern_idx + 1) % pattern.len();
+            remaining   = pattern[pattern_idx];
+        }
+
+        remaining -= length;
+        if let Some(dash) = current.as_mut() {
+            dash.push((x2, y2));
+        }
+    }
+
+    if let Some(dash) = current {
+        dashes.push(dash);
+    }
+
+    dashes
+}
+
+///
+/// Generates the polygons for a single line without dashes
+///
+fn stroke_line(points: &[(f32, f32)], closed: bool, options: &StrokeOptions, polygons: &mut Vec<Vec<(f32, f32)>>) {
+    let half_width = options.width / 2.0;
+
+    // A single point is drawn as a dot if the cap extends beyond it
+    if points.len() == 1 {
+        let (x, y) = points[0];
+
+        match options.cap {
+            LineCap::Butt   => { }
+            LineCap::Round  => { polygons.push(orient(circle((x, y), half_width))); }
+            LineCap::Square => { polygons.push(orient(vec![(x-half_width, y-half_width), (x+half_width, y-half_width), (x+half_width, y+half_width), (x-half_width, y+half_width)])); }
+        }
+
+        return;
+    }
+
+    // Work out the direction and normal of each segment
+    let segments = points.windows(2)
+        .map(|segment| {
+            let (x1, y1)    = segment[0];
+            let (x2, y2)    = segment[1];
+            let length      = ((x2-x1)*(x2-x1) + (y2-y1)*(y2-y1)).sqrt();
+            let (dx, dy)    = ((x2-x1)/length, (y2-y1)/length);
+
+            ((x1, y1), (x2, y2), (dx, dy), (-dy*half_width, dx*half_width))
+        })
+        .collect::<Vec<_>>();
+
+    // Each segment is a rectangle
+    for ((x1, y1), (x2, y2), _, (nx, ny)) in segments.iter() {
+        polygons.push(orient(vec![(x1+nx, y1+ny), (x2+nx, y2+ny), (x2-nx, y2-ny), (x1-nx, y1-ny)]));
+    }
+
+    // Join the segments
+    let num_joins = if closed { segments.len() } else { segments.len()-1 };
+
+    for idx in 0..num_joins {
+        let (_, (x, y), (dx1, dy1), (nx1, ny1)) = segments[idx];
+        let (_, _, (dx2, dy2), (nx2, ny2))      = segments[(idx+1) % segments.len()];
+
+        // The join is on the outside of the turn
+        let cross               = dx1*dy2 - dy1*dx2;
+        let side                = if cross > 0.0 { -1.0 } else { 1.0 };
+        let (nx1, ny1)          = (nx1*side, ny1*side);
+        let (nx2, ny2)          = (nx2*side, ny2*side);
+
+        match options.join {
+            LineJoin::Round => { polygons.push(orient(circle((x, y), half_width))); }
+            LineJoin::Bevel => { polygons.push(orient(vec![(x, y), (x+nx1, y+ny1), (x+nx2, y+ny2)])); }
+
+            LineJoin::Miter => {
+                // The miter point is along the bisector of the two normals
+                let (bx, by)    = (nx1+nx2, ny1+ny2);
+                let bisect_len  = (bx*bx + by*by).sqrt();
+                let cos_half    = bisect_len / (2.0*half_width);
+
+                if cos_half > 0.0 && 1.0/cos_half <= MITER_LIMIT {
+                    let miter_len   = half_width / cos_half;
+                    let (mx, my)    = (x + bx/bisect_len*miter_len, y + by/bisect_len*miter_len);
+                    polygons.push(orient(vec![(x, y), (x+nx1, y+ny1), (mx, my), (x+nx2, y+ny2)]));
+                } else {
+                    polygons.push(orient(vec![(x, y), (x+nx1, y+ny1), (x+nx2, y+ny2)]));
+                }
+            }
+        }
+    }
+
+    // Cap the ends of open lines
+    if !closed {
+        let ((sx, sy), _, (sdx, sdy), (snx, sny))   = segments[0];
+        let (_, (ex, ey), (edx, edy), (enx, eny))   = segments[segments.len()-1];
+
+        match options.cap {
+            LineCap::Butt   => { }
+
+            LineCap::Round  => {
+                polygons.push(orient(circle((sx, sy), half_width)));
+                polygons.push(orient(circle((ex, ey), half_width)));
+            }
+
+            LineCap::Square => {
+                let (sdx, sdy) = (sdx*half_width, sdy*half_width);
+                let (edx, edy) = (edx*half_width, edy*half_width);
+
+                polygons.push(orient(vec![(sx+snx, sy+sny), (sx+snx-sdx, sy+sny-sdy), (sx-snx-sdx, sy-sny-sdy), (sx-snx, sy-sny)]));
+                polygons.push(orient(vec![(ex+enx, ey+eny), (ex+enx+edx, ey+eny+edy), (ex-enx+edx, ey-eny+edy), (ex-enx, ey-eny)]));
+            }
+        }
+    }
+}
+
+///
+/// Generates the polygons that make up the stroke of a path
+///
+/// Each subpath is a list of points along with whether or not it is closed. The result should be filled using the non-zero
+/// winding rule.
+///
+pub fn stroke_polygons(subpaths: &[(Vec<(f32, f32)>, bool)], options: &StrokeOptions) -> Vec<Vec<(f32, f32)>> {
+    let mut polygons = vec![];
+
+    if !(options.width > 0.0) {
+        return polygons;
+    }
+
+    for (points, closed) in subpaths.iter() {
+        // Remove any points that don't move the line
+        let mut line: Vec<(f32, f32)> = vec![];
+        for point in points.iter() {
+            let is_duplicate = line.last()
+                .map(|(x, y)| (x-point.0).abs() < 0.0001 && (y-point.1).abs() < 0.0001)
+                .unwrap_or(false);
+
+            if !is_duplicate {
+                line.push(*point);
+            }
+        }
+
+        if line.len() == 0 { continue; }
+
+        // Closed lines need a segment back to the start
+        let mut closed = *closed && line.len() > 2;
+        if closed {
+            let first = line[0];
+            let last  = line[line.len()-1];
+
+            if (first.0-last.0).abs() >= 0.0001 || (first.1-last.1).abs() >= 0.0001 {
+                line.push(first);
+            }
+        }
+
+        // Dashed lines are split into a set of open lines
+        if options.dash_pattern.len() > 0 {
+            let dashes  = dash(&line, &options.dash_pattern, options.dash_offset);
+            closed      = false;
+
+            for dash in dashes {
+                stroke_line(&dash, closed, options, &mut polygons);
+            }
+        } else {
+            stroke_line(&line, closed, options, &mut polygons);
+        }
+    }
+
+    polygons
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::coverage::*;
+
+    fn options(width: f32) -> StrokeOptions {
+        StrokeOptions {
+            width:          width,
+            join:           LineJoin::Miter,
+            cap:            LineCap::Butt,
+            dash_pattern:   vec![],
+            dash_offset:    0.0
+        }
+    }
+
+    #[test]
+    fn horizontal_line() {
+        let polygons = stroke_polygons(&[(vec![(2.0, 5.0), (8.0, 5.0)], false)], &options(2.0));
+        let coverage = Coverage::fill_polygons(&polygons, 10, 10);
+
+        assert!((coverage.get(5, 4) - 1.0).abs() < 0.001);
+        assert!((coverage.get(5, 5) - 1.0).abs() < 0.001);
+        assert!(coverage.get(5, 7) == 0.0);
+        assert!(coverage.get(9, 5) == 0.0);
+    }
+
+    #[test]
+    fn mitered_corner_is_filled() {
+        let polygons = stroke_polygons(&[(vec![(2.0, 5.0), (8.0, 5.0), (8.0, 9.0)], false)], &options(2.0));
+        let coverage = Coverage::fill_polygons(&polygons, 10, 10);
+
+        // The outer corner of the miter
+        assert!((coverage.get(8, 4) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn dashed_line_has_gaps() {
+        let mut dashed          = options(2.0);
+        dashed.dash_pattern     = vec![2.0, 2.0];
+
+        let polygons = stroke_polygons(&[(vec![(0.0, 5.0), (10.0, 5.0)], false)], &dashed);
+        let coverage = Coverage::fill_polygons(&polygons, 10, 10);
+
+        assert!((coverage.get(0, 5) - 1.0).abs() < 0.001);
+        assert!(coverage.get(2, 5) == 0.0);
+        assert!((coverage.get(4, 5) - 1.0).abs() < 0.001);
+    }
+}
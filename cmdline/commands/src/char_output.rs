@@ -16,6 +16,7 @@ where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
                 Message(msg)                => stream::iter((msg + "\n").chars().collect::<Vec<_>>()).boxed(),
                 BeginOutput(_file)          => stream::iter(vec![]).boxed(),
                 Output(_output)             => stream::iter(vec![]).boxed(),
+                OutputBytes(_bytes)         => stream::iter(vec![]).boxed(),
                 Error(err)                  => stream::iter((err + "\n").chars().collect::<Vec<_>>()).boxed(),
                 FinishCommand(_cmd)         => stream::iter(vec![]).boxed(),
                 State(_new_state)           => stream::iter(vec![]).boxed(),
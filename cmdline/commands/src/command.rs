@@ -3,6 +3,9 @@ use super::storage_descriptor::*;
 
 use flo_animation::*;
 
+use std::sync::*;
+use std::ops::{Range};
+
 ///
 /// Command that can be issued to a FlowBetween instance
 ///
@@ -56,6 +59,9 @@ pub enum FloCommand {
     /// Lists all the layers in the input animation (IDs and names)
     ListLayers,
 
+    /// Lists all of the review annotations left on the input animation
+    ListAnnotations,
+
     /// Selects the specified frame in the inout animation
     SelectFrame(u64, usize),
 
@@ -63,5 +69,17 @@ pub enum FloCommand {
     ListElements,
 
     /// Writes out debugging SVG files for raycasting a particular element
-    RayCastToSvg(ElementId)
+    RayCastToSvg(ElementId),
+
+    /// Adds edits to the edit buffer that place a PNG image (supplied as the file data) on the specified layer and frame of the output animation
+    PlaceImage(u64, usize, Arc<Vec<u8>>),
+
+    /// Adds edits to the edit buffer that attach a WAV file (supplied as the file data) to the specified layer of the output animation
+    ImportAudio(u64, Arc<Vec<u8>>),
+
+    /// Writes out an SVG file for each of the frames in the specified range of the input animation
+    ExportFramesAsSvg(Range<usize>),
+
+    /// Writes out a PNG file for every frame of the input animation, optionally at a specific resolution
+    ExportFramesAsPng(Option<(u32, u32)>)
 }
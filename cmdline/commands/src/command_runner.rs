@@ -9,6 +9,8 @@ use futures::prelude::*;
 use futures::stream;
 use futures::task::{Poll};
 
+use std::sync::*;
+
 ///
 /// Runs a series of commands provided by a stream and returns a stream of the resulting output
 ///
@@ -67,9 +69,14 @@ fn run_command<'a>(command: FloCommand, output: &'a mut Publisher<FloCommandOutp
             FloCommand::DumpCatalogAsEdits              => { dump_catalog_as_edits(output, state).await; }
             FloCommand::DeserializeEdits(ref edits)     => { deserialize_edits(stream::iter(edits.chars()), output, state).await?; }
             FloCommand::ListLayers                      => { list_layers(output, state).await; }
+            FloCommand::ListAnnotations                 => { list_annotations(output, state).await; }
             FloCommand::SelectFrame(layer, when)        => { select_frame(output, state, layer, when).await; }
             FloCommand::ListElements                    => { list_elements(output, state).await; }
             FloCommand::RayCastToSvg(element_id)        => { raycast_to_svg(output, state, element_id).await?; }
+            FloCommand::PlaceImage(layer, frame, ref png_data) => { place_image(output, state, layer, frame, Arc::clone(png_data)).await?; }
+            FloCommand::ImportAudio(layer, ref wav_data) => { import_audio(output, state, layer, Arc::clone(wav_data)).await?; }
+            FloCommand::ExportFramesAsSvg(ref frames)   => { export_frames_as_svg(output, state, frames.clone()).await; }
+            FloCommand::ExportFramesAsPng(size)         => { export_frames_as_png(output, state, size).await?; }
         }
 
         // Finish the command
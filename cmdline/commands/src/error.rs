@@ -21,7 +21,16 @@ pub enum CommandError {
     NoFrameSelected,
 
     /// The element ID was not found
-    ElementNotFound(ElementId)
+    ElementNotFound(ElementId),
+
+    /// An image file could not be read (the parameter describes why)
+    CannotReadImage(String),
+
+    /// An audio file could not be read (the parameter describes why)
+    CannotReadAudio(String),
+
+    /// An image could not be generated (the parameter describes why)
+    CannotWriteImage(String)
 }
 
 impl Display for CommandError {
@@ -33,7 +42,10 @@ impl Display for CommandError {
             CouldNotCreateAnimation(name)   => write!(fmt, "Coult not create animation '{}'", name),
             CannotParseEdit(line, edit)     => write!(fmt, "{}: cannot parse edit '{}'", line, edit),
             NoFrameSelected                 => write!(fmt, "A frame must be selected for this operation"),
-            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string()))
+            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string())),
+            CannotReadImage(reason)         => write!(fmt, "Cannot read image: {}", reason),
+            CannotReadAudio(reason)         => write!(fmt, "Cannot read audio: {}", reason),
+            CannotWriteImage(reason)        => write!(fmt, "Cannot write image: {}", reason)
         }
     }
 }
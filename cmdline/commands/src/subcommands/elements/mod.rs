@@ -1,3 +1,5 @@
 mod raycast_to_svg;
+mod place_image;
 
 pub use self::raycast_to_svg::*;
+pub use self::place_image::*;
@@ -0,0 +1,47 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+
+///
+/// Adds the edits that place a PNG image in the middle of a frame of the output animation to the edit buffer
+///
+/// The image is scaled down if it's larger than the animation. The IDs for the image and its element are assigned by the
+/// output animation, so any edits already in the buffer should be written out before this command is used.
+///
+pub fn place_image<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, layer_id: u64, frame_number: usize, png_data: Arc<Vec<u8>>) -> impl 'a+Future<Output=Result<(), CommandError>>+Send {
+    async move {
+        // Decode the image
+        let image                       = ReferenceImage::from_png(&png_data).map_err(|err| CommandError::CannotReadImage(format!("{:?}", err)))?;
+
+        // Work out where to put it
+        let output_animation            = state.output_animation();
+        let when                        = output_animation.frame_length() * (frame_number as u32);
+        let (anim_width, anim_height)   = output_animation.size();
+        let (width, height)             = (image.width() as f64, image.height() as f64);
+        let scale                       = f64::min(1.0, f64::min(anim_width/width, anim_height/height));
+        let size                        = (width*scale, height*scale);
+        let position                    = ((anim_width-size.0)/2.0, (anim_height-size.1)/2.0);
+
+        // Store the image and place it on the frame
+        let image_id                    = output_animation.assign_element_id();
+        let element_id                  = output_animation.assign_element_id();
+        let mut edits                   = state.edit_buffer().clone();
+
+        edits.push(AnimationEdit::AddImage(image_id, Arc::new(image)));
+        edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::PlaceImage(element_id, image_id, (position.0 as f32, position.1 as f32), (size.0 as f32, size.1 as f32), 1.0))));
+
+        *state = state.set_edit_buffer(edits);
+
+        let msg = format!("Placing image as element {} in frame {}:{}", element_id.id().unwrap_or(0), layer_id, frame_number);
+        output.publish(FloCommandOutput::Message(msg)).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_canvas::*;
+use flo_animation::*;
+use flo_ui::image::*;
+
+use futures::prelude::*;
+
+use std::io::{Read};
+use std::time::{Duration};
+
+///
+/// Renders a frame of an animation to a set of drawing instructions
+///
+/// The animation is scaled to fit the height of the image. Each animation layer is drawn on its own canvas layer,
+/// above a white background.
+///
+fn draw_frame(animation: &dyn Animation, when: Duration) -> Vec<Draw> {
+    let (width, height) = animation.size();
+    let (width, height) = (width as f32, height as f32);
+    let mut drawing     = vec![];
+
+    // Set up the canvas so that it displays the animation
+    drawing.canvas_height(height);
+    drawing.center_region(0.0, 0.0, width, height);
+
+    // Fill in the background
+    drawing.layer(0);
+    drawing.fill_color(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+    drawing.new_path();
+    drawing.rect(0.0, 0.0, width, height);
+    drawing.fill();
+
+    // Render the layers
+    for (layer_idx, layer_id) in animation.get_layer_ids().into_iter().enumerate() {
+        if let Some(layer) = animation.get_layer_with_id(layer_id) {
+            let frame = layer.get_frame_at_time(when);
+
+            // Images are drawn using sprites, which are declared before the layer is selected
+            frame.render_sprites(&mut drawing);
+
+            drawing.layer((layer_idx+1) as u32);
+            drawing.layer_blend((layer_idx+1) as u32, frame.blend_mode());
+            drawing.layer_alpha((layer_idx+1) as u32, frame.opacity() as f32);
+            frame.render_to(&mut drawing);
+        }
+    }
+
+    drawing
+}
+
+///
+/// Writes out a PNG file for every frame of the input animation
+///
+/// The size is the resolution of the images to generate: if this is `None` the images are the same size as the animation.
+///
+pub fn export_frames_as_png<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, size: Option<(u32, u32)>) -> impl 'a+Future<Output=Result<(), CommandError>>+Send {
+    async move {
+        use self::FloCommandOutput::*;
+
+        let input_animation     = state.input_animation();
+        let frame_length        = input_animation.frame_length();
+        let duration            = input_animation.duration();
+        let num_frames          = if frame_length.as_nanos() > 0 { (duration.as_nanos() / frame_length.as_nanos()) as usize } else { 0 };
+
+        let (anim_w, anim_h)    = input_animation.size();
+        let (width, height)     = size.unwrap_or((anim_w.round().max(1.0) as u32, anim_h.round().max(1.0) as u32));
+
+        output.publish(StartTask(format!("Export {} frames as {}x{} PNG", num_frames, width, height))).await;
+
+        for frame_number in 0..num_frames {
+            // Rasterize the frame
+            let when        = frame_length * (frame_number as u32);
+            let mut raster  = CanvasRaster::new(width as usize, height as usize);
+            raster.draw_all(draw_frame(&*input_animation, when));
+
+            // Encode as a PNG
+            let png         = png_data_for_rgba(&raster.to_rgba8(), width, height);
+            let mut bytes   = vec![];
+            png.read().read_to_end(&mut bytes).map_err(|err| CommandError::CannotWriteImage(err.to_string()))?;
+
+            // Write to a file named after the frame
+            let filename    = format!("frame_{:05}.png", frame_number);
+            output.publish(Message(format!("  Writing {} (T+{}ms)", filename, when.as_millis()))).await;
+            output.publish(BeginOutput(filename)).await;
+            output.publish(OutputBytes(bytes)).await;
+
+            output.publish(TaskProgress((frame_number+1) as f64, num_frames as f64)).await;
+        }
+
+        output.publish(FinishTask).await;
+
+        Ok(())
+    }
+}
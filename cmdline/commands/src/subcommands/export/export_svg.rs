@@ -0,0 +1,40 @@
+use crate::state::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::export::*;
+
+use futures::prelude::*;
+
+use std::ops::{Range};
+
+///
+/// Writes out an SVG file for each frame in the specified range of the input animation
+///
+pub fn export_frames_as_svg<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, frames: Range<usize>) -> impl 'a+Future<Output=()>+Send {
+    async move {
+        use self::FloCommandOutput::*;
+
+        let input_animation = state.input_animation();
+        let frame_length    = input_animation.frame_length();
+        let num_frames      = frames.end.max(frames.start) - frames.start;
+
+        output.publish(StartTask(format!("Export {} frames as SVG", num_frames))).await;
+
+        for (count, frame_number) in frames.enumerate() {
+            // Generate the SVG for this frame
+            let when        = frame_length * (frame_number as u32);
+            let svg         = animation_frame_to_svg(&*input_animation, when);
+
+            // Write to a file named after the frame
+            let filename    = format!("frame_{:05}.svg", frame_number);
+            output.publish(Message(format!("  Writing {} (T+{}ms)", filename, when.as_millis()))).await;
+            output.publish(BeginOutput(filename)).await;
+            output.publish(Output(svg)).await;
+
+            output.publish(TaskProgress((count+1) as f64, num_frames as f64)).await;
+        }
+
+        output.publish(FinishTask).await;
+    }
+}
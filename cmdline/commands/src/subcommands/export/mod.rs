@@ -0,0 +1,5 @@
+mod export_svg;
+mod export_png;
+
+pub use self::export_svg::*;
+pub use self::export_png::*;
@@ -0,0 +1,37 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+
+///
+/// Adds the edits that attach a WAV file to a layer of the output animation to the edit buffer
+///
+/// The ID for the audio clip is assigned by the output animation, so any edits already in the buffer should be written out
+/// before this command is used.
+///
+pub fn import_audio<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, layer_id: u64, wav_data: Arc<Vec<u8>>) -> impl 'a+Future<Output=Result<(), CommandError>>+Send {
+    async move {
+        // Decode the audio
+        let clip        = AudioClip::from_wav(&wav_data).map_err(|err| CommandError::CannotReadAudio(format!("{:?}", err)))?;
+
+        // Store the clip and attach it to the layer
+        let clip_id     = state.output_animation().assign_element_id();
+        let mut edits   = state.edit_buffer().clone();
+
+        edits.push(AnimationEdit::AddAudioClip(clip_id, Arc::new(clip)));
+        edits.push(AnimationEdit::Layer(layer_id, LayerEdit::ImportAudio(clip_id)));
+
+        *state = state.set_edit_buffer(edits);
+
+        let msg = format!("Attaching audio clip {} to layer {}", clip_id.id().unwrap_or(0), layer_id);
+        output.publish(FloCommandOutput::Message(msg)).await;
+
+        Ok(())
+    }
+}
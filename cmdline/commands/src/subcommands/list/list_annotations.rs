@@ -0,0 +1,34 @@
+use crate::state::*;
+use crate::output::*;
+
+use futures::prelude::*;
+
+use flo_stream::*;
+
+///
+/// Writes out the review annotations in the input animation to the output
+///
+pub fn list_annotations<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl 'a+Future<Output=()>+Send {
+    async move {
+        use self::FloCommandOutput::*;
+
+        let input_animation = state.input_animation();
+
+        // Retrieve all the annotations for the animation
+        let annotations     = input_animation.get_annotations();
+
+        output.publish(Message("Animation annotations:".to_string())).await;
+
+        for (annotation_id, annotation) in annotations {
+            let annotation_id   = annotation_id.id().map(|id| id.to_string()).unwrap_or("?".to_string());
+            let position        = annotation.position.map(|(x, y)| format!("({}, {})", x, y)).unwrap_or("-".to_string());
+            let status          = if annotation.resolved { "resolved" } else { "open" };
+
+            // Display information on this annotation
+            let annotation_info = format!("  Annotation ({}): layer {:02} at {}ms, {} by '{}' (timestamp {}), position {}",
+                annotation_id, annotation.layer_id, annotation.when.as_millis(), status, annotation.author, annotation.timestamp, position);
+            output.publish(Message(annotation_info)).await;
+            output.publish(Message(format!("    {}", annotation.text))).await;
+        }
+    }
+}
@@ -19,14 +19,24 @@ fn describe_vector(vec: &Vector) -> String {
         BrushProperties(_props)         => { format!("Brush properties") }
         BrushStroke(brush_stroke)       => { format!("Brush stroke, {} points", brush_stroke.points().len()) }
         Path(path)                      => { format!("Path, {} elements", path.path().elements().count()) }
+        Image(image)                    => {
+            match image.image() {
+                Some(image_data)    => format!("Image, {}x{} pixels", image_data.width(), image_data.height()),
+                None                => format!("Image, missing image {:?}", image.image_id())
+            }
+        }
+        Text(text)                      => { format!("Text, {:?} in {} {}pt", text.text(), text.style().font, text.style().size) }
         Motion(_motion)                 => { format!("Motion description") }
         Transformation(_transform)      => { format!("Transformation description") }
         Error                           => { format!("Error :-(") }
 
         Group(group)                    => { 
             let group_type  = match group.group_type() {
-                GroupType::Normal => "Group",
-                GroupType::Added  => "Boolean addition"
+                GroupType::Normal       => "Group",
+                GroupType::Added        => "Boolean addition",
+                GroupType::Subtracted   => "Boolean subtraction",
+                GroupType::Masked       => "Mask",
+                GroupType::InvertedMask => "Inverted mask"
             };
             let elements    = group.elements().map(|elem| describe_vector(elem)).join(", ");
 
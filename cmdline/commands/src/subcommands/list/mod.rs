@@ -1,7 +1,9 @@
 mod list_files;
 mod list_layers;
 mod list_elements;
+mod list_annotations;
 
 pub use self::list_files::*;
 pub use self::list_layers::*;
 pub use self::list_elements::*;
+pub use self::list_annotations::*;
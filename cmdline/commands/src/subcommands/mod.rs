@@ -1,17 +1,21 @@
 mod list;
 mod edits;
 mod elements;
+mod export;
 mod read_from;
 mod dump_catalog;
 mod select_frame;
+mod import_audio;
 mod write_to_catalog;
 mod set_catalog_folder;
 
 pub (super) use self::list::*;
 pub (super) use self::edits::*;
 pub (super) use self::elements::*;
+pub (super) use self::export::*;
 pub (super) use self::read_from::*;
 pub (super) use self::dump_catalog::*;
 pub (super) use self::select_frame::*;
+pub (super) use self::import_audio::*;
 pub (super) use self::write_to_catalog::*;
 pub (super) use self::set_catalog_folder::*;
@@ -49,6 +49,17 @@ where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
                         pos                 += num_written;
                     }
                 }
+
+                OutputBytes(bytes)              => {
+                    let mut pos = 0;
+
+                    while pos < bytes.len() {
+                        let remaining_bytes = &bytes[pos..bytes.len()];
+
+                        let num_written     = output_stream.write(remaining_bytes).await.unwrap();
+                        pos                 += num_written;
+                    }
+                }
             }
         }
     }
@@ -10,6 +10,7 @@ use clap::{App, Arg, SubCommand};
 mod console;
 use self::console::*;
 
+use std::sync::*;
 use std::str::{FromStr};
 
 #[tokio::main]
@@ -58,6 +59,8 @@ async fn main() {
             .about("Lists animations in the main index"))
         .subcommand(SubCommand::with_name("ls-layers")
             .about("Lists the layers defined in the input animation"))
+        .subcommand(SubCommand::with_name("ls-annotations")
+            .about("Lists the review annotations left on the input animation"))
         .subcommand(SubCommand::with_name("ls-elements")
             .about("Lists all of the elements in the selected frame"))
         .subcommand(SubCommand::with_name("summarize-edits")
@@ -80,6 +83,39 @@ async fn main() {
                 .help("The element ID in the selected frame to raycast")
                 .required(true)
                 .index(1)))
+        .subcommand(SubCommand::with_name("place-image")
+            .about("Copies the input animation to the output animation and places a PNG image in the middle of the frame selected by --frame")
+            .arg(Arg::with_name("IMAGE")
+                .help("The PNG file to place")
+                .required(true)
+                .index(1)))
+        .subcommand(SubCommand::with_name("import-audio")
+            .about("Copies the input animation to the output animation and attaches a WAV file to one of its layers")
+            .arg(Arg::with_name("LAYER")
+                .help("The ID of the layer to attach the audio to")
+                .required(true)
+                .index(1))
+            .arg(Arg::with_name("WAV")
+                .help("The WAV file to import")
+                .required(true)
+                .index(2)))
+        .subcommand(SubCommand::with_name("export-svg")
+            .about("Writes out an SVG file for each frame in a range of the input animation")
+            .arg(Arg::with_name("FIRST")
+                .help("The first frame to export")
+                .required(true)
+                .index(1))
+            .arg(Arg::with_name("LAST")
+                .help("The last frame to export (defaults to exporting just the first frame)")
+                .required(false)
+                .index(2)))
+        .subcommand(SubCommand::with_name("export-png")
+            .about("Renders every frame of the input animation to a PNG file")
+            .arg(Arg::with_name("size")
+                .long("size")
+                .takes_value(true)
+                .value_name("WIDTHxHEIGHT")
+                .help("The resolution of the images to generate (defaults to the size of the animation)")))
         .get_matches();
 
     tokio::spawn(async move {
@@ -110,6 +146,8 @@ async fn main() {
         }
 
         // Pick a frame if the user wanted one
+        let mut selected_frame = None;
+
         if let Some(frame) = params.value_of("frame") {
             // Expect two numbers seperated by a ':'
             if let Some(sep_pos) = frame.find(':') {
@@ -119,6 +157,7 @@ async fn main() {
                 if let (Ok(layer_num), Ok(frame_num)) = (u64::from_str(&layer_num), usize::from_str(&frame_num)) {
                     // Request this frame in the input
                     input.push(FloCommand::SelectFrame(layer_num, frame_num));
+                    selected_frame = Some((layer_num, frame_num));
                 } else {
                     // Bad frame format
                     stderr().write(format!("'{}:{}' is not a valid value for --frame. The parameter must be of the format <layer_id>:<frame_number> (eg: 3:5 for frame 5 of layer 3)\n\n", layer_num, frame_num).as_bytes()).await.unwrap();
@@ -141,6 +180,11 @@ async fn main() {
             input.push(FloCommand::ListLayers);
         }
 
+        // Ls-annotations command
+        if let Some(_ls_params) = params.subcommand_matches("ls-annotations") {
+            input.push(FloCommand::ListAnnotations);
+        }
+
         // Ls-elements command
         if let Some(_ls_params) = params.subcommand_matches("ls-elements") {
             input.push(FloCommand::ListElements);
@@ -208,6 +252,107 @@ async fn main() {
             // Add a raycast command
             input.push(FloCommand::RayCastToSvg(element_id));
         }
+
+        // Place image command
+        if let Some(place_image) = params.subcommand_matches("place-image") {
+            let (layer_num, frame_num) = match selected_frame {
+                Some(selected_frame)    => selected_frame,
+                None                    => {
+                    stderr().write("A frame must be selected with --frame to place an image\n\n".as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            // Read the image file
+            let image_file = place_image.value_of("IMAGE").unwrap();
+            let image_data = match fs::read(image_file).await {
+                Ok(image_data)  => image_data,
+                Err(err)        => {
+                    stderr().write(format!("Could not read '{}': {}\n\n", image_file, err).as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            // Copy the input animation, then place the image (the IDs for the image are assigned once the existing edits have been written)
+            input.push(FloCommand::ReadAllEdits);
+            input.push(FloCommand::WriteAllEdits);
+            input.push(FloCommand::ClearEdits);
+            input.push(FloCommand::PlaceImage(layer_num, frame_num, Arc::new(image_data)));
+            input.push(FloCommand::WriteAllEdits);
+        }
+
+        // Import audio command
+        if let Some(import_audio) = params.subcommand_matches("import-audio") {
+            let layer_num = match u64::from_str(import_audio.value_of("LAYER").unwrap()) {
+                Ok(layer_num)   => layer_num,
+                Err(_)          => {
+                    stderr().write(format!("'{}' is not a valid layer ID\n\n", import_audio.value_of("LAYER").unwrap()).as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            // Read the audio file
+            let wav_file = import_audio.value_of("WAV").unwrap();
+            let wav_data = match fs::read(wav_file).await {
+                Ok(wav_data)    => wav_data,
+                Err(err)        => {
+                    stderr().write(format!("Could not read '{}': {}\n\n", wav_file, err).as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            // Copy the input animation, then attach the audio (the ID for the clip is assigned once the existing edits have been written)
+            input.push(FloCommand::ReadAllEdits);
+            input.push(FloCommand::WriteAllEdits);
+            input.push(FloCommand::ClearEdits);
+            input.push(FloCommand::ImportAudio(layer_num, Arc::new(wav_data)));
+            input.push(FloCommand::WriteAllEdits);
+        }
+
+        // Export SVG command
+        if let Some(export_svg) = params.subcommand_matches("export-svg") {
+            // Parse the frame range
+            let first_frame = export_svg.value_of("FIRST").and_then(|first| usize::from_str(first).ok());
+            let last_frame  = match export_svg.value_of("LAST") {
+                Some(last)  => usize::from_str(last).ok(),
+                None        => first_frame
+            };
+
+            let (first_frame, last_frame) = match (first_frame, last_frame) {
+                (Some(first), Some(last)) if first <= last  => (first, last),
+                _                                           => {
+                    stderr().write(format!("'{}-{}' is not a valid range of frames\n\n", export_svg.value_of("FIRST").unwrap_or("-"), export_svg.value_of("LAST").unwrap_or("-")).as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            // Add an export command
+            input.push(FloCommand::ExportFramesAsSvg(first_frame..(last_frame+1)));
+        }
+
+        // Export PNG command
+        if let Some(export_png) = params.subcommand_matches("export-png") {
+            // Parse the resolution
+            let size = match export_png.value_of("size") {
+                None        => None,
+                Some(size)  => {
+                    let mut parts   = size.split('x');
+                    let width       = parts.next().and_then(|width| u32::from_str(width).ok());
+                    let height      = parts.next().and_then(|height| u32::from_str(height).ok());
+
+                    match (width, height, parts.next()) {
+                        (Some(width), Some(height), None) if width > 0 && height > 0    => Some((width, height)),
+                        _                                                               => {
+                            stderr().write(format!("'{}' is not a valid resolution (expected WIDTHxHEIGHT)\n\n", size).as_bytes()).await.unwrap();
+                            return;
+                        }
+                    }
+                }
+            };
+
+            // Add an export command
+            input.push(FloCommand::ExportFramesAsPng(size));
+        }
         
         // Prepare as a stream as input to the command line
         let input       = stream::iter(input);
@@ -0,0 +1,49 @@
+use super::marker::*;
+use super::overlay_layers::*;
+use super::canvas_renderer::*;
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_animation::*;
+
+/// Radius of the marker showing where a review annotation was left
+const MARKER_RADIUS: f32 = 8.0;
+
+///
+/// The annotation marker renderer draws markers showing where review annotations have been left on the current frame
+///
+pub struct AnnotationMarkerRenderer {
+
+}
+
+impl AnnotationMarkerRenderer {
+    ///
+    /// Creates a new annotation marker renderer
+    ///
+    pub fn new() -> AnnotationMarkerRenderer {
+        AnnotationMarkerRenderer {
+
+        }
+    }
+
+    ///
+    /// Renders markers for the annotations on the current frame
+    ///
+    pub fn render(&self, canvas: &BindingCanvas, renderer: &mut CanvasRenderer, annotations: Vec<(ElementId, Annotation)>, show_annotations: bool) {
+        let mut drawing = vec![Draw::ClearCanvas];
+
+        // Only annotations that refer to a particular part of the frame have a marker
+        if show_annotations {
+            for (_, annotation) in annotations.into_iter() {
+                if let Some(position) = annotation.position {
+                    let color = if annotation.resolved { ANNOTATION_RESOLVED_MARKER } else { ANNOTATION_MARKER };
+
+                    draw_marker(&mut drawing, position, MARKER_RADIUS, color, 0.6, Color::Rgba(0.0, 0.0, 0.0, 0.6));
+                }
+            }
+        }
+
+        renderer.overlay(canvas, OVERLAY_ANNOTATIONS, drawing);
+    }
+}
@@ -169,6 +169,12 @@ impl CanvasRenderer {
                     gc.layer_blend(canvas_layer, blend_style);
                 },
 
+                LayerAlpha(overlay_layer, alpha) => {
+                    // Pick the layer from the canvas
+                    let canvas_layer = *overlay.layers.entry(overlay_layer).or_insert_with(|| next_free_layer());
+                    gc.layer_alpha(canvas_layer, alpha);
+                },
+
                 unchanged => gc.draw(unchanged)
             }
         }
@@ -258,9 +264,16 @@ impl CanvasRenderer {
 
         // Draw the active set of layers
         canvas.draw(move |gc| {
+            // Images are drawn using sprites, which need to be declared before any layer is selected
+            for layer in self.frame_layers.values() {
+                layer.layer_frame.render_sprites(gc);
+            }
+
             // Draw the layers
             for layer in self.frame_layers.values() {
                 gc.layer(layer.layer_id);
+                gc.layer_blend(layer.layer_id, layer.layer_frame.blend_mode());
+                gc.layer_alpha(layer.layer_id, layer.layer_frame.opacity() as f32);
                 layer.layer_frame.render_to(gc);
             }
         });
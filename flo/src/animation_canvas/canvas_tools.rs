@@ -195,8 +195,8 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
             }
         }
 
-        // Commit any animation edits that the tool produced
-        if animation_edits.len() > 0 {
+        // Commit any animation edits that the tool produced (read-only sessions reject all edits)
+        if animation_edits.len() > 0 && !self.animation.is_read_only() {
             self.animation.perform_edits(animation_edits);
         }
 
@@ -391,3 +391,37 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
         self.combine_after_commit = true;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flo_animation::storage::*;
+    use futures::prelude::*;
+    use futures::executor;
+
+    #[test]
+    fn read_only_session_rejects_tool_edits() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+        let editor          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+        let viewer          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions).into_read_only();
+
+        let canvas          = BindingCanvas::new();
+        let mut renderer    = CanvasRenderer::new();
+
+        // Edits generated by a tool in the viewer session are discarded
+        let mut viewer_tools = CanvasTools::from_model(&viewer);
+        viewer_tools.process_actions(&canvas, &mut renderer, vec![ToolAction::Edit(AnimationEdit::AddNewLayer(1))].into_iter());
+        executor::block_on(viewer.when_complete());
+
+        assert!(animation.get_layer_ids().len() == 0);
+
+        // The same edit is performed when it's generated in the editor session
+        let mut editor_tools = CanvasTools::from_model(&editor);
+        editor_tools.process_actions(&canvas, &mut renderer, vec![ToolAction::Edit(AnimationEdit::AddNewLayer(1))].into_iter());
+        executor::block_on(editor.when_complete());
+
+        assert!(animation.get_layer_ids() == vec![1]);
+    }
+}
@@ -0,0 +1,21 @@
+use flo_canvas::*;
+
+///
+/// Draws a circular marker at a point on the canvas
+///
+/// The marker is filled with a translucent version of its colour and given a two-tone outline, so it shows up
+/// against the drawing underneath whatever colour that happens to be.
+///
+pub fn draw_marker<Gc: GraphicsPrimitives+?Sized>(gc: &mut Gc, (x, y): (f32, f32), radius: f32, color: Color, fill_alpha: f32, outline: Color) {
+    gc.new_path();
+    gc.circle(x, y, radius);
+
+    gc.fill_color(color.with_alpha(fill_alpha));
+    gc.fill();
+    gc.line_width_pixels(2.0);
+    gc.stroke_color(outline);
+    gc.stroke();
+    gc.line_width_pixels(1.0);
+    gc.stroke_color(color);
+    gc.stroke();
+}
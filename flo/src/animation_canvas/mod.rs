@@ -1,8 +1,13 @@
 mod canvas_renderer;
 mod canvas_tools;
 mod onion_skin_renderer;
+mod presence_renderer;
+mod annotation_marker_renderer;
+mod marker;
 pub mod overlay_layers;
 
 pub use self::canvas_renderer::*;
 pub use self::canvas_tools::*;
 pub use self::onion_skin_renderer::*;
+pub use self::presence_renderer::*;
+pub use self::annotation_marker_renderer::*;
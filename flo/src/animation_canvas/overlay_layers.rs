@@ -1,2 +1,4 @@
 pub const OVERLAY_TOOL: u32         = 0;
 pub const OVERLAY_ONIONSKINS: u32   = 1;
+pub const OVERLAY_PRESENCE: u32     = 2;
+pub const OVERLAY_ANNOTATIONS: u32  = 3;
@@ -0,0 +1,46 @@
+use super::marker::*;
+use super::overlay_layers::*;
+use super::canvas_renderer::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+
+use std::time::Duration;
+
+/// Radius of the marker showing where another user is painting
+const MARKER_RADIUS: f32 = 6.0;
+
+///
+/// The presence renderer draws markers showing where the other users editing an animation are working
+///
+pub struct PresenceRenderer {
+
+}
+
+impl PresenceRenderer {
+    ///
+    /// Creates a new presence renderer
+    ///
+    pub fn new() -> PresenceRenderer {
+        PresenceRenderer {
+
+        }
+    }
+
+    ///
+    /// Renders the other users that are working on the frame at the specified time
+    ///
+    pub fn render(&self, canvas: &BindingCanvas, renderer: &mut CanvasRenderer, other_users: Vec<UserPresence>, current_time: Duration) {
+        let mut drawing = vec![Draw::ClearCanvas];
+
+        // Only users who are working on the same frame and who have painted something are shown
+        for user in other_users.into_iter().filter(|user| user.time == current_time) {
+            if let Some(pointer) = user.pointer {
+                draw_marker(&mut drawing, pointer, MARKER_RADIUS, user.color, 0.4, Color::Rgba(1.0, 1.0, 1.0, 0.8));
+            }
+        }
+
+        renderer.overlay(canvas, OVERLAY_PRESENCE, drawing);
+    }
+}
@@ -7,7 +7,17 @@ use flo_ui_files::*;
 use flo_ui_files::ui::*;
 use flo_ui_files::sqlite::*;
 
+use ::desync::*;
+
 use std::sync::*;
+use std::any::{Any, TypeId};
+use std::path::{PathBuf};
+use std::collections::HashMap;
+
+lazy_static! {
+    // The open file stores for each type of loader and folder of files (so sessions that open the same file share the animation)
+    static ref OPEN_FILE_STORES: Desync<HashMap<(TypeId, PathBuf), Arc<dyn Any+Send+Sync>>> = Desync::new(HashMap::new());
+}
 
 ///
 /// The default file chooser for FlowBetween
@@ -18,7 +28,13 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     file_manager: Arc<SqliteFileManager>,
 
     /// The shared open file store for this animation
-    file_store: Arc<OpenFileStore<FloSharedModel<Loader>>>
+    file_store: Arc<OpenFileStore<FloSharedModel<Loader>>>,
+
+    /// True if files opened from this chooser are displayed in viewer mode
+    read_only: bool,
+
+    /// True if review annotations can be left on files opened in viewer mode
+    can_annotate: bool
 }
 
 impl<Loader: 'static+FileAnimation> FloChooser<Loader>
@@ -27,18 +43,55 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     /// Creates a new chooser
     ///
     pub fn new(loader: Arc<Loader>) -> FloChooser<Loader> {
-        // Create the file manager (we use a single default user by default)
-        let file_manager = Arc::new(SqliteFileManager::new(APP_NAME, DEFAULT_USER_FOLDER));
+        // We use a single default user by default
+        Self::with_user_folder(loader, DEFAULT_USER_FOLDER)
+    }
+
+    ///
+    /// Creates a new chooser that shows the files in a particular user's folder
+    ///
+    /// Users can only see and open the files in their own folder.
+    ///
+    pub fn with_user_folder(loader: Arc<Loader>, user_folder: &str) -> FloChooser<Loader> {
+        // Create the file manager
+        let file_manager = Arc::new(SqliteFileManager::new(APP_NAME, user_folder));
+
+        // Use the same file store for every chooser with this type of loader and folder, so sessions can edit the same file together
+        let store_key   = (TypeId::of::<Loader>(), file_manager.root_path().to_path_buf());
+        let file_store  = OPEN_FILE_STORES.sync(move |stores| {
+            let store = stores.entry(store_key)
+                .or_insert_with(move || Arc::new(OpenFileStore::<FloSharedModel<Loader>>::new(loader)));
 
-        // Create the file store
-        let file_store = Arc::new(OpenFileStore::new(loader));
+            Arc::clone(store)
+        });
+        let file_store  = file_store.downcast::<OpenFileStore<FloSharedModel<Loader>>>().unwrap();
 
         // Put everything together
         FloChooser {
             file_manager:   file_manager,
-            file_store:     file_store
+            file_store:     file_store,
+            read_only:      false,
+            can_annotate:   false
         }
     }
+
+    ///
+    /// Converts this chooser into one that opens files in viewer mode (where they can be played back but not edited)
+    ///
+    pub fn into_read_only(mut self) -> FloChooser<Loader> {
+        self.read_only      = true;
+        self.can_annotate   = false;
+        self
+    }
+
+    ///
+    /// Converts this chooser into one that opens files in reviewer mode (where they can't be edited but new review notes can be left)
+    ///
+    pub fn into_reviewer(mut self) -> FloChooser<Loader> {
+        self.read_only      = true;
+        self.can_annotate   = true;
+        self
+    }
 }
 
 impl<Loader: 'static+FileAnimation> FileChooser for FloChooser<Loader>
@@ -62,4 +115,22 @@ where Loader::NewAnimation: 'static+EditableAnimation  {
     fn get_file_store(&self) -> Arc<OpenFileStore<FloSharedModel<Loader>>> {
         Arc::clone(&self.file_store)
     }
+
+    ///
+    /// Creates the editor for a file that has been opened from this chooser
+    ///
+    fn open_file(&self, model: FloModel<Loader::NewAnimation>) -> EditorController<Loader> {
+        match (self.read_only, self.can_annotate) {
+            (false, _)      => EditorController::from_model(model),
+            (true, false)   => EditorController::from_model(model.into_read_only()),
+            (true, true)    => EditorController::from_model(model.into_reviewer())
+        }
+    }
+
+    ///
+    /// Viewers can't manage the files in the chooser
+    ///
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
 }
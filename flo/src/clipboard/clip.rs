@@ -0,0 +1,626 @@
+use flo_animation::*;
+use flo_animation::serializer::*;
+
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::{HashMap, HashSet};
+
+///
+/// A set of elements that have been copied from a frame
+///
+/// The elements are stored in serialized form, along with the brushes, motions and transformations that are attached to them
+/// and the images that they display, so a clip can be pasted into any layer of any animation.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Clip {
+    /// The serialized elements in this clip
+    serialized: Arc<String>
+}
+
+///
+/// The elements read back from a clip
+///
+struct ClipElements {
+    /// The elements that were copied, in order from back to front
+    root_elements: Vec<ElementId>,
+
+    /// The elements in the clip (including the elements attached to or contained by the root elements)
+    elements: HashMap<ElementId, Vector>,
+
+    /// The attachments for each element
+    attachments: HashMap<ElementId, Vec<ElementId>>,
+
+    /// The images displayed by the image elements in the clip
+    images: HashMap<ElementId, Arc<ReferenceImage>>
+}
+
+///
+/// Generates the edits that paste the contents of a clip into a layer
+///
+struct PasteEdits<'a> {
+    /// The layer that the elements are being pasted into
+    layer_id: u64,
+
+    /// The time where the elements are being pasted
+    when: Duration,
+
+    /// The elements that are being pasted
+    clip: &'a ClipElements,
+
+    /// Function used to assign new element IDs
+    assign_element_id: &'a mut dyn FnMut() -> ElementId,
+
+    /// Maps the IDs of the elements in the clip to the IDs of the elements that have been pasted
+    new_ids: HashMap<ElementId, ElementId>,
+
+    /// The brush definition and properties (as IDs from the clip) that will be attached to new brush strokes
+    paint_brush: (Option<ElementId>, Option<ElementId>),
+
+    /// The brush definition and properties (as IDs from the clip) that will be used for new paths
+    path_brush: (Option<ElementId>, Option<ElementId>),
+
+    /// The edits generated so far
+    edits: Vec<AnimationEdit>
+}
+
+impl Clip {
+    ///
+    /// Copies the specified elements from a frame (elements are pasted in the order they're supplied here, so
+    /// these should usually be in order from back to front)
+    ///
+    pub fn copy_from_frame(frame: &dyn Frame, element_ids: &[ElementId]) -> Clip {
+        let mut elements    = vec![];
+        let mut copied      = HashSet::new();
+        let mut root_ids    = vec![];
+
+        for element_id in element_ids.iter() {
+            if let Some(element) = frame.element_with_id(*element_id) {
+                Self::copy_element(frame, &element, &mut elements, &mut copied);
+                root_ids.push(*element_id);
+            }
+        }
+
+        // Each image is only stored once, even if several of the elements display it
+        let mut images = vec![];
+        for (_, element, _) in elements.iter() {
+            if let Vector::Image(image_element) = element {
+                if let Some(image) = image_element.image() {
+                    if !images.iter().any(|(image_id, _)| *image_id == image_element.image_id()) {
+                        images.push((image_element.image_id(), image));
+                    }
+                }
+            }
+        }
+
+        // Write out the clip
+        let mut serialized = String::new();
+
+        // v1
+        serialized.write_small_u64(1);
+
+        serialized.write_usize(root_ids.len());
+        root_ids.iter().for_each(|root_id| root_id.serialize(&mut serialized));
+
+        serialized.write_usize(elements.len());
+        for (element_id, element, attachments) in elements {
+            element_id.serialize(&mut serialized);
+            element.serialize(&mut serialized);
+
+            serialized.write_usize(attachments.len());
+            attachments.iter().for_each(|attachment_id| attachment_id.serialize(&mut serialized));
+        }
+
+        serialized.write_usize(images.len());
+        for (image_id, image) in images {
+            image_id.serialize(&mut serialized);
+            image.serialize(&mut serialized);
+        }
+
+        Clip {
+            serialized: Arc::new(serialized)
+        }
+    }
+
+    ///
+    /// Adds an element and the elements it depends on to a list of elements to copy
+    ///
+    fn copy_element(frame: &dyn Frame, element: &Vector, elements: &mut Vec<(ElementId, Vector, Vec<ElementId>)>, copied: &mut HashSet<ElementId>) {
+        let element_id = element.id();
+
+        // Each element is only copied once
+        if copied.contains(&element_id) || element_id.is_unassigned() {
+            return;
+        }
+        copied.insert(element_id);
+
+        // Copy the elements that this element refers to (elements with unassigned IDs are serialized alongside the element that contains them)
+        match element {
+            Vector::Path(path)      => {
+                let brush       = path.brush();
+                let properties  = path.properties();
+
+                Self::copy_element(frame, &Vector::BrushDefinition((*brush).clone()), elements, copied);
+                Self::copy_element(frame, &Vector::BrushProperties((*properties).clone()), elements, copied);
+            }
+
+            Vector::Group(group)    => {
+                for grouped_element in group.elements() {
+                    Self::copy_element(frame, grouped_element, elements, copied);
+                }
+            }
+
+            _                       => { }
+        }
+
+        // Copy the attachments (brushes, motions and transformations)
+        let attachment_ids = frame.attached_elements(element_id).into_iter()
+            .map(|(attachment_id, _attachment_type)| attachment_id)
+            .collect::<Vec<_>>();
+
+        for attachment_id in attachment_ids.iter() {
+            if let Some(attachment) = frame.element_with_id(*attachment_id) {
+                Self::copy_element(frame, &attachment, elements, copied);
+            }
+        }
+
+        elements.push((element_id, element.clone(), attachment_ids));
+    }
+
+    ///
+    /// Reads the elements from this clip
+    ///
+    fn read_elements(&self) -> Option<ClipElements> {
+        let mut data = self.serialized.chars();
+
+        match data.next_small_u64() {
+            version @ 0..=1 => {
+                let num_roots       = data.next_usize();
+                let root_elements   = (0..num_roots).into_iter()
+                    .map(|_| ElementId::deserialize(&mut data))
+                    .collect::<Option<Vec<_>>>()?;
+
+                // Deserialize the elements
+                let num_elements    = data.next_usize();
+                let mut unresolved  = HashMap::new();
+                let mut attachments = HashMap::new();
+
+                for _ in 0..num_elements {
+                    let element_id      = ElementId::deserialize(&mut data)?;
+                    let resolver        = Vector::deserialize(element_id, &mut data)?;
+
+                    let num_attachments = data.next_usize();
+                    let attached_to     = (0..num_attachments).into_iter()
+                        .map(|_| ElementId::deserialize(&mut data))
+                        .collect::<Option<Vec<_>>>()?;
+
+                    unresolved.insert(element_id, resolver);
+                    attachments.insert(element_id, attached_to);
+                }
+
+                // v1 clips also contain the images displayed by the elements
+                let mut images = HashMap::new();
+
+                if version >= 1 {
+                    let num_images = data.next_usize();
+
+                    for _ in 0..num_images {
+                        let image_id    = ElementId::deserialize(&mut data)?;
+                        let image       = ReferenceImage::deserialize(&mut data)?;
+
+                        images.insert(image_id, Arc::new(image));
+                    }
+                }
+
+                // Resolve them against each other
+                let element_ids     = unresolved.keys().cloned().collect::<Vec<_>>();
+                let mut elements    = HashMap::new();
+
+                for element_id in element_ids {
+                    Self::resolve_element(element_id, &mut unresolved, &mut elements);
+                }
+
+                Some(ClipElements {
+                    root_elements:  root_elements,
+                    elements:       elements,
+                    attachments:    attachments,
+                    images:         images
+                })
+            }
+
+            _ => None
+        }
+    }
+
+    ///
+    /// Resolves an element (and any elements it refers to) from a set of deserialized elements
+    ///
+    fn resolve_element<Resolver: ResolveElements<Vector>>(element_id: ElementId, unresolved: &mut HashMap<ElementId, Resolver>, elements: &mut HashMap<ElementId, Vector>) -> Option<Vector> {
+        if let Some(element) = elements.get(&element_id) {
+            return Some(element.clone());
+        }
+
+        let resolver    = unresolved.remove(&element_id)?;
+        let element     = resolver.resolve(&mut |referenced_id| Self::resolve_element(referenced_id, unresolved, elements))?;
+
+        elements.insert(element_id, element.clone());
+
+        Some(element)
+    }
+
+    ///
+    /// Returns the edits needed to paste this clip into a layer at a particular time, along with the IDs of the elements that
+    /// will be created, in order from back to front
+    ///
+    /// The pasted elements are all given new IDs, using the supplied function (which should usually call `assign_element_id()`
+    /// on the animation that the clip is being pasted into)
+    ///
+    pub fn paste_edits<AssignFn: FnMut() -> ElementId>(&self, layer_id: u64, when: Duration, assign_element_id: AssignFn) -> (Vec<AnimationEdit>, Vec<ElementId>) {
+        let mut assign_element_id   = assign_element_id;
+        let clip                    = match self.read_elements() {
+            Some(clip)  => clip,
+            None        => { return (vec![], vec![]); }
+        };
+
+        let mut paste               = PasteEdits {
+            layer_id:           layer_id,
+            when:               when,
+            clip:               &clip,
+            assign_element_id:  &mut assign_element_id,
+            new_ids:            HashMap::new(),
+            paint_brush:        (None, None),
+            path_brush:         (None, None),
+            edits:              vec![]
+        };
+
+        let pasted_ids = clip.root_elements.iter()
+            .filter_map(|element_id| paste.element(*element_id))
+            .collect();
+
+        (paste.edits, pasted_ids)
+    }
+}
+
+impl<'a> PasteEdits<'a> {
+    ///
+    /// Finds the elements attached to an element in the clip
+    ///
+    fn attachments_for(&self, element_id: ElementId) -> Vec<(ElementId, &'a Vector)> {
+        let clip = self.clip;
+
+        clip.attachments.get(&element_id)
+            .map(|attachments| attachments.iter()
+                .filter_map(|attachment_id| clip.elements.get(attachment_id).map(|attachment| (*attachment_id, attachment)))
+                .collect())
+            .unwrap_or_else(|| vec![])
+    }
+
+    ///
+    /// Generates the edits to paste an element from the clip, returning its new ID
+    ///
+    fn element(&mut self, element_id: ElementId) -> Option<ElementId> {
+        // Each element is only pasted once
+        if let Some(new_id) = self.new_ids.get(&element_id) {
+            return Some(*new_id);
+        }
+
+        let clip        = self.clip;
+        let element     = clip.elements.get(&element_id)?;
+        let layer_id    = self.layer_id;
+        let when        = self.when;
+
+        // Create the element
+        let new_id = match element {
+            Vector::BrushStroke(brush_stroke)   => {
+                // Brush strokes take on the brush that's selected when they're created
+                for (attachment_id, attachment) in self.attachments_for(element_id) {
+                    match attachment {
+                        Vector::BrushDefinition(defn)   => {
+                            if self.paint_brush.0 != Some(attachment_id) {
+                                let brush_id = (self.assign_element_id)();
+                                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(brush_id, defn.definition().clone(), defn.drawing_style()))));
+                                self.paint_brush.0 = Some(attachment_id);
+                            }
+                        }
+
+                        Vector::BrushProperties(props)  => {
+                            if self.paint_brush.1 != Some(attachment_id) {
+                                let properties_id = (self.assign_element_id)();
+                                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(properties_id, props.brush_properties().clone()))));
+                                self.paint_brush.1 = Some(attachment_id);
+                            }
+                        }
+
+                        _                               => { }
+                    }
+                }
+
+                let new_id = (self.assign_element_id)();
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::CreateBrushStroke(new_id, brush_stroke.points()))));
+
+                new_id
+            }
+
+            Vector::Path(path)                  => {
+                let brush       = path.brush();
+                let properties  = path.properties();
+
+                if self.path_brush.0 != Some(brush.id()) {
+                    let brush_id = (self.assign_element_id)();
+                    self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::SelectBrush(brush_id, brush.definition().clone(), brush.drawing_style()))));
+                    self.path_brush.0 = Some(brush.id());
+                }
+
+                if self.path_brush.1 != Some(properties.id()) {
+                    let properties_id = (self.assign_element_id)();
+                    self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::BrushProperties(properties_id, properties.brush_properties().clone()))));
+                    self.path_brush.1 = Some(properties.id());
+                }
+
+                let new_id = (self.assign_element_id)();
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::CreatePath(new_id, Arc::new(path.path().elements().collect())))));
+
+                new_id
+            }
+
+            Vector::Image(image)                => {
+                let image_id    = self.image(image.image_id())?;
+                let new_id      = (self.assign_element_id)();
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::PlaceImage(new_id, image_id, image.position(), image.size(), image.opacity()))));
+
+                new_id
+            }
+
+            Vector::Text(text)                  => {
+                let new_id = (self.assign_element_id)();
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::CreateText(new_id, (*text.text()).clone(), (*text.style()).clone(), text.position()))));
+
+                new_id
+            }
+
+            Vector::Group(group)                => {
+                // The grouped elements are pasted first, then combined into a new group
+                let grouped_ids = group.elements()
+                    .filter_map(|grouped_element| self.element(grouped_element.id()))
+                    .collect::<Vec<_>>();
+
+                if grouped_ids.len() == 0 {
+                    return None;
+                }
+
+                let new_id = (self.assign_element_id)();
+                self.edits.push(AnimationEdit::Element(grouped_ids, ElementEdit::Group(new_id, group.group_type())));
+
+                new_id
+            }
+
+            // Other elements are only pasted as attachments
+            _                                   => { return None; }
+        };
+
+        self.new_ids.insert(element_id, new_id);
+
+        // Recreate the motions and transformations attached to the element
+        for (attachment_id, attachment) in self.attachments_for(element_id) {
+            match attachment {
+                Vector::Motion(motion)                          => {
+                    let motion_id = self.motion(attachment_id, motion);
+                    self.edits.push(AnimationEdit::Element(vec![new_id], ElementEdit::AddAttachment(motion_id)));
+                }
+
+                Vector::Transformation((_, transformations))    => {
+                    let transforms = transformations.iter()
+                        .flat_map(|transformation| transformation.to_element_transforms())
+                        .collect::<Vec<_>>();
+
+                    if transforms.len() > 0 {
+                        self.edits.push(AnimationEdit::Element(vec![new_id], ElementEdit::Transform(transforms)));
+                    }
+                }
+
+                // Brushes are attached when the element is created
+                _                                               => { }
+            }
+        }
+
+        Some(new_id)
+    }
+
+    ///
+    /// Generates the edit to add a copy of an image from the clip, returning its new ID (images displayed by several elements are only added once)
+    ///
+    fn image(&mut self, image_id: ElementId) -> Option<ElementId> {
+        if let Some(new_id) = self.new_ids.get(&image_id) {
+            return Some(*new_id);
+        }
+
+        let image   = self.clip.images.get(&image_id)?;
+        let new_id  = (self.assign_element_id)();
+
+        self.edits.push(AnimationEdit::AddImage(new_id, Arc::clone(image)));
+        self.new_ids.insert(image_id, new_id);
+
+        Some(new_id)
+    }
+
+    ///
+    /// Generates the edits to create a copy of a motion, returning its new ID (motions shared between several elements are only created once)
+    ///
+    fn motion(&mut self, motion_id: ElementId, motion: &MotionElement) -> ElementId {
+        if let Some(new_id) = self.new_ids.get(&motion_id) {
+            return *new_id;
+        }
+
+        let new_id  = (self.assign_element_id)();
+        let motion  = motion.motion();
+
+        self.edits.push(AnimationEdit::Motion(new_id, MotionEdit::Create));
+        self.edits.push(AnimationEdit::Motion(new_id, MotionEdit::SetType(motion.motion_type())));
+
+        if let (Some(origin), Some(path)) = (motion.origin(), motion.path()) {
+            self.edits.push(AnimationEdit::Motion(new_id, MotionEdit::SetOrigin(origin.0, origin.1)));
+            self.edits.push(AnimationEdit::Motion(new_id, MotionEdit::SetPath(path.clone())));
+        }
+
+        self.new_ids.insert(motion_id, new_id);
+
+        new_id
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flo_animation::storage::*;
+    use futures::prelude::*;
+
+    fn create_animation() -> impl EditableAnimation {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+        ]);
+
+        animation
+    }
+
+    fn frame<Anim: EditableAnimation>(animation: &Anim) -> Arc<dyn Frame> {
+        animation.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0))
+    }
+
+    #[test]
+    fn paste_into_another_animation() {
+        let source = create_animation();
+
+        source.perform_edits(vec![
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Assigned(10), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Assigned(11), BrushProperties { size: 12.0, ..BrushProperties::new() }))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(12), Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0)),
+                RawPoint::from((30.0, 15.0))
+            ])))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::CreateText(ElementId::Assigned(13), "Title".to_string(), TextStyle::default(), (100.0, 100.0))))
+        ]);
+
+        let clip    = Clip::copy_from_frame(&*frame(&source), &[ElementId::Assigned(12), ElementId::Assigned(13)]);
+
+        // Paste into a different animation, where the same IDs are already in use
+        let target  = create_animation();
+        target.perform_edits(vec![
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::CreateText(ElementId::Assigned(12), "Existing".to_string(), TextStyle::default(), (0.0, 0.0))))
+        ]);
+
+        let (edits, pasted) = clip.paste_edits(1, Duration::from_millis(0), || target.assign_element_id());
+        target.perform_edits(edits);
+
+        assert!(pasted.len() == 2);
+        assert!(!pasted.contains(&ElementId::Assigned(12)));
+
+        let target_frame = frame(&target);
+
+        match target_frame.element_with_id(pasted[0]) {
+            Some(Vector::BrushStroke(_))    => { }
+            _                               => { assert!(false) }
+        }
+
+        match target_frame.element_with_id(pasted[1]) {
+            Some(Vector::Text(text))        => { assert!(*text.text() == "Title".to_string()); }
+            _                               => { assert!(false) }
+        }
+
+        match target_frame.element_with_id(ElementId::Assigned(12)) {
+            Some(Vector::Text(text))        => { assert!(*text.text() == "Existing".to_string()); }
+            _                               => { assert!(false) }
+        }
+
+        // The brush stroke should be pasted with its brush properties
+        let properties = target_frame.attached_elements(pasted[0]).into_iter()
+            .filter_map(|(attachment_id, _)| target_frame.element_with_id(attachment_id))
+            .filter_map(|attachment| match attachment { Vector::BrushProperties(props) => Some(props.brush_properties().size), _ => None })
+            .collect::<Vec<_>>();
+        assert!(properties == vec![12.0]);
+    }
+
+    #[test]
+    fn paste_image_into_another_animation() {
+        let source  = create_animation();
+        let image   = Arc::new(ReferenceImage::from_rgba(2, 1, &[255, 0, 0, 255, 0, 0, 255, 255]).unwrap());
+
+        source.perform_edits(vec![
+            AnimationEdit::AddImage(ElementId::Assigned(10), Arc::clone(&image)),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::PlaceImage(ElementId::Assigned(11), ElementId::Assigned(10), (0.0, 0.0), (200.0, 100.0), 1.0))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::PlaceImage(ElementId::Assigned(12), ElementId::Assigned(10), (50.0, 50.0), (20.0, 10.0), 0.5)))
+        ]);
+
+        let clip            = Clip::copy_from_frame(&*frame(&source), &[ElementId::Assigned(11), ElementId::Assigned(12)]);
+
+        let target          = create_animation();
+        let (edits, pasted) = clip.paste_edits(1, Duration::from_millis(0), || target.assign_element_id());
+
+        // The image is only added once, even though two elements display it
+        assert!(edits.iter().filter(|edit| match edit { AnimationEdit::AddImage(_, _) => true, _ => false }).count() == 1);
+
+        target.perform_edits(edits);
+
+        assert!(pasted.len() == 2);
+
+        let target_frame = frame(&target);
+
+        for pasted_id in pasted {
+            match target_frame.element_with_id(pasted_id) {
+                Some(Vector::Image(image_element))  => { assert!(image_element.image() == Some(Arc::clone(&image))); }
+                _                                   => { assert!(false) }
+            }
+        }
+    }
+
+    #[test]
+    fn paste_twice_with_motion() {
+        let animation = create_animation();
+
+        animation.perform_edits(vec![
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(20), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(21), BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(22), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 20.0)),
+                PathComponent::Line(PathPoint::new(20.0, 30.0)),
+                PathComponent::Line(PathPoint::new(30.0, 20.0)),
+                PathComponent::Close
+            ])))),
+
+            AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::Create),
+            AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::SetType(MotionType::Translate)),
+            AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::SetOrigin(20.0, 25.0)),
+            AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(200.0, 200.0, Duration::from_millis(0)), TimePoint::new(300.0, 250.0, Duration::from_millis(1000))))),
+            AnimationEdit::Element(vec![ElementId::Assigned(22)], ElementEdit::AddAttachment(ElementId::Assigned(30)))
+        ]);
+
+        let clip = Clip::copy_from_frame(&*frame(&animation), &[ElementId::Assigned(22)]);
+
+        let (edits, first_paste) = clip.paste_edits(1, Duration::from_millis(0), || animation.assign_element_id());
+        animation.perform_edits(edits);
+        let (edits, second_paste) = clip.paste_edits(1, Duration::from_millis(0), || animation.assign_element_id());
+        animation.perform_edits(edits);
+
+        assert!(first_paste.len() == 1);
+        assert!(second_paste.len() == 1);
+        assert!(first_paste[0] != second_paste[0]);
+
+        // Each copy gets its own copy of the motion
+        let frame           = frame(&animation);
+        let first_motions   = frame.attached_elements(first_paste[0]).into_iter().filter(|(_, vector_type)| *vector_type == VectorType::Motion).collect::<Vec<_>>();
+        let second_motions  = frame.attached_elements(second_paste[0]).into_iter().filter(|(_, vector_type)| *vector_type == VectorType::Motion).collect::<Vec<_>>();
+
+        assert!(first_motions.len() == 1);
+        assert!(second_motions.len() == 1);
+        assert!(first_motions[0].0 != ElementId::Assigned(30));
+        assert!(first_motions[0].0 != second_motions[0].0);
+
+        match frame.element_with_id(first_paste[0]) {
+            Some(Vector::Path(path))    => { assert!(path.path().elements().count() == 4); }
+            _                           => { assert!(false) }
+        }
+    }
+}
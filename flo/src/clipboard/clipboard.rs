@@ -0,0 +1,94 @@
+use super::clip::*;
+use super::super::model::*;
+
+use flo_animation::*;
+
+use std::sync::*;
+
+lazy_static! {
+    /// The clipboard shared between all of the animations that are open in this process
+    static ref CLIPBOARD: Mutex<Option<Clip>> = Mutex::new(None);
+}
+
+///
+/// Copies the selected elements from the current frame to the clipboard
+///
+/// Returns false if nothing was copied (eg, because nothing is selected)
+///
+pub fn copy_selection<Anim: 'static+EditableAnimation+Animation>(model: &FloModel<Anim>) -> bool {
+    let selection   = model.selection().selection_in_order.get();
+    let frame       = model.frame().frame.get();
+
+    if let (Some(frame), true) = (frame, selection.len() > 0) {
+        let clip = Clip::copy_from_frame(&*frame, &*selection);
+        set_clipboard(clip);
+
+        true
+    } else {
+        false
+    }
+}
+
+///
+/// Copies the selected elements from the current frame to the clipboard, then deletes them
+///
+pub fn cut_selection<Anim: 'static+EditableAnimation+Animation>(model: &FloModel<Anim>) -> bool {
+    let selection = model.selection().selection_in_order.get();
+
+    if copy_selection(model) {
+        model.perform_edits(vec![AnimationEdit::Element((*selection).clone(), ElementEdit::Delete)]);
+        model.selection().clear_selection();
+        model.timeline().invalidate_canvas();
+
+        true
+    } else {
+        false
+    }
+}
+
+///
+/// Pastes the contents of the clipboard into the selected layer at the current time, and selects the pasted elements
+///
+/// The pasted elements are given new IDs, so the same clip can be pasted many times into any open animation. Returns the IDs of
+/// the new elements.
+///
+pub fn paste_clipboard<Anim: 'static+EditableAnimation+Animation>(model: &FloModel<Anim>) -> Vec<ElementId> {
+    let clip    = match clipboard_contents() {
+        Some(clip)  => clip,
+        None        => { return vec![]; }
+    };
+
+    let when    = model.timeline().current_time.get();
+    let layer   = model.timeline().selected_layer.get();
+    let frame   = model.frame().frame.get();
+
+    // Elements can only be pasted into an existing keyframe
+    if let (Some(_frame), Some(layer)) = (frame, layer) {
+        let (edits, pasted_ids) = clip.paste_edits(layer, when, || model.assign_element_id());
+
+        model.perform_edits(edits);
+
+        // The pasted elements replace the selection
+        model.selection().clear_selection();
+        pasted_ids.iter().for_each(|element_id| model.selection().select(*element_id));
+        model.timeline().invalidate_canvas();
+
+        pasted_ids
+    } else {
+        vec![]
+    }
+}
+
+///
+/// Replaces the contents of the clipboard
+///
+pub fn set_clipboard(clip: Clip) {
+    *CLIPBOARD.lock().unwrap() = Some(clip);
+}
+
+///
+/// Retrieves the current contents of the clipboard
+///
+pub fn clipboard_contents() -> Option<Clip> {
+    CLIPBOARD.lock().unwrap().clone()
+}
@@ -0,0 +1,9 @@
+//!
+//! The clipboard stores elements that have been copied from a frame so they can be pasted into another frame, layer or animation
+//!
+
+mod clip;
+mod clipboard;
+
+pub use self::clip::*;
+pub use self::clipboard::*;
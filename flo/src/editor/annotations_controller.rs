@@ -0,0 +1,261 @@
+use super::super::style::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use std::sync::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///
+/// Controller for the sidebar that lists the review annotations on the current frame and can be used to leave or
+/// resolve them
+///
+pub struct AnnotationsController<Anim: Animation> {
+    /// The UI for this controller
+    ui: BindRef<Control>,
+
+    /// The model that annotations will be added to
+    model: FloModel<Anim>,
+
+    /// The text of the note that's being written
+    note_text: Binding<String>,
+
+    /// True if new notes should be pinned to the last position the user painted at on the canvas
+    pin_notes: Binding<bool>
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> AnnotationsController<Anim> {
+    ///
+    /// Creates a new annotations controller
+    ///
+    pub fn new(model: &FloModel<Anim>) -> AnnotationsController<Anim> {
+        let note_text   = bind("".to_string());
+        let pin_notes   = bind(true);
+        let ui          = Self::ui(model.annotations(), model.is_read_only(), model.can_annotate(), BindRef::from(&note_text), BindRef::from(&pin_notes));
+
+        AnnotationsController {
+            ui:         ui,
+            model:      model.clone(),
+            note_text:  note_text,
+            pin_notes:  pin_notes
+        }
+    }
+
+    ///
+    /// Creates a small button with a text label
+    ///
+    fn button(label: &str, action: String, width: f32, selected: bool) -> Control {
+        Control::button()
+            .with(vec![Control::label().with(label).with(TextAlign::Center).with(Bounds::fill_all())])
+            .with(Font::Size(11.0))
+            .with(State::Selected(Property::Bool(selected)))
+            .with(Bounds::next_horiz(width))
+            .with((ActionTrigger::Click, action))
+    }
+
+    ///
+    /// Creates the control that displays a single annotation
+    ///
+    /// Viewers can't resolve or reopen notes, so the button to do this is left out if `read_only` is set
+    ///
+    fn annotation_control(annotation_id: ElementId, annotation: &Annotation, read_only: bool) -> Control {
+        let id              = annotation_id.id().unwrap_or(-1);
+        let (label, action) = if annotation.resolved {
+            ("Reopen", format!("ReopenNote-{}", id))
+        } else {
+            ("Resolve", format!("ResolveNote-{}", id))
+        };
+        let author          = if annotation.resolved { format!("{} (resolved)", annotation.author) } else { annotation.author.clone() };
+
+        let mut header      = vec![
+            Control::label()
+                .with(author)
+                .with(FontWeight::Bold)
+                .with(Bounds::stretch_horiz(1.0))
+        ];
+        if !read_only {
+            header.push(Self::button(label, action, 56.0, false));
+        }
+
+        Control::container()
+            .with(Bounds::next_vert(44.0))
+            .with(ControlAttribute::Padding((0, 2), (0, 2)))
+            .with(vec![
+                Control::container()
+                    .with(Bounds::next_vert(18.0))
+                    .with(header),
+                Control::label()
+                    .with(annotation.text.clone())
+                    .with(FontWeight::Light)
+                    .with(Bounds::stretch_vert(1.0)),
+                Control::empty()
+                    .with(Appearance::Background(TIMESCALE_BORDER))
+                    .with(Bounds::next_vert(1.0))
+            ])
+    }
+
+    ///
+    /// Creates the UI for the annotations controller
+    ///
+    /// The controls for leaving a new note are left out if `can_annotate` is not set
+    ///
+    fn ui(annotations: &AnnotationModel, read_only: bool, can_annotate: bool, note_text: BindRef<String>, pin_notes: BindRef<bool>) -> BindRef<Control> {
+        let frame_annotations   = annotations.frame_annotations.clone();
+        let show_annotations    = annotations.show_annotations.clone();
+        let show_resolved       = annotations.show_resolved.clone();
+        let author              = annotations.author.clone();
+
+        let ui = computed(move || {
+            let notes = frame_annotations.get()
+                .into_iter()
+                .map(|(annotation_id, annotation)| Self::annotation_control(annotation_id, &annotation, read_only))
+                .collect::<Vec<_>>();
+
+            let mut controls = vec![
+                Control::container()
+                    .with(Bounds::next_vert(22.0))
+                    .with(vec![
+                        Control::label()
+                            .with("Notes")
+                            .with(FontWeight::ExtraBold)
+                            .with(Bounds::stretch_horiz(1.0)),
+                        Self::button("Show", "ToggleShowAnnotations".to_string(), 48.0, show_annotations.get()),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::button("Resolved", "ToggleShowResolved".to_string(), 64.0, show_resolved.get())
+                    ]),
+                Control::empty()
+                    .with(Appearance::Background(TIMESCALE_BORDER))
+                    .with(Bounds::next_vert(1.0)),
+
+                Control::container()
+                    .with(Bounds::stretch_vert(1.0))
+                    .with(notes)
+            ];
+
+            // Only sessions that can annotate the animation get the controls for leaving a new note
+            if can_annotate {
+                controls.extend(vec![
+                    Control::empty()
+                        .with(Appearance::Background(TIMESCALE_BORDER))
+                        .with(Bounds::next_vert(1.0)),
+                    Control::container()
+                        .with(Bounds::next_vert(22.0))
+                        .with(ControlAttribute::Padding((0, 2), (0, 2)))
+                        .with(vec![
+                            Control::label()
+                                .with("Name:")
+                                .with(FontWeight::Light)
+                                .with(Bounds::next_horiz(40.0)),
+                            Control::text_box()
+                                .with(author.get())
+                                .with(Bounds::stretch_horiz(1.0))
+                                .with((ActionTrigger::SetValue, "SetAuthor"))
+                        ]),
+                    Control::text_box()
+                        .with(note_text.get())
+                        .with(Bounds::next_vert(22.0))
+                        .with((ActionTrigger::SetValue, "SetNoteText")),
+                    Control::container()
+                        .with(Bounds::next_vert(22.0))
+                        .with(ControlAttribute::Padding((0, 2), (0, 2)))
+                        .with(vec![
+                            Self::button("Pin", "TogglePinNotes".to_string(), 40.0, pin_notes.get()),
+                            Control::empty().with(Bounds::stretch_horiz(1.0)),
+                            Self::button("Add note", "AddNote".to_string(), 64.0, false)
+                        ])
+                ]);
+            }
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Appearance::Background(TIMELINE_BACKGROUND))
+                .with(Font::Size(12.0))
+                .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                .with(controls)
+        });
+
+        BindRef::from(ui)
+    }
+
+    ///
+    /// Leaves a new note on the selected layer at the current time
+    ///
+    /// Viewers that aren't reviewers can't leave notes, so this does nothing for them
+    ///
+    fn add_note(&self) {
+        if !self.model.can_annotate() {
+            return;
+        }
+
+        let text = self.note_text.get();
+        if text.trim().len() == 0 {
+            return;
+        }
+
+        let layer_id = match self.model.timeline().selected_layer.get() {
+            Some(layer_id)  => layer_id,
+            None            => { return; }
+        };
+
+        let when        = self.model.timeline().current_time.get();
+        let author      = self.model.annotations().author.get();
+        let timestamp   = SystemTime::now().duration_since(UNIX_EPOCH).map(|since_epoch| since_epoch.as_secs()).unwrap_or(0);
+        let position    = if self.pin_notes.get() { self.model.collaboration().pointer.get() } else { None };
+        let annotation  = Annotation::new(layer_id, when, &author, timestamp, position, &text);
+
+        self.model.perform_edits(vec![AnimationEdit::Annotation(ElementId::Unassigned, AnnotationEdit::Create(annotation))]);
+        self.note_text.set("".to_string());
+    }
+
+    ///
+    /// Marks a note as resolved or unresolved
+    ///
+    /// Reviewers can only leave new notes, so this does nothing in a read-only session
+    ///
+    fn set_resolved(&self, annotation_id: &str, resolved: bool) {
+        if self.model.is_read_only() {
+            return;
+        }
+
+        if let Ok(annotation_id) = annotation_id.parse::<i64>() {
+            self.model.perform_edits(vec![AnimationEdit::Annotation(ElementId::Assigned(annotation_id), AnnotationEdit::SetResolved(resolved))]);
+        }
+    }
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> Controller for AnnotationsController<Anim> {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        match (action_id, action_parameter) {
+            ("SetNoteText", &Value(PropertyValue::String(ref text)))    => self.note_text.set(text.clone()),
+            ("SetAuthor", &Value(PropertyValue::String(ref author)))    => self.model.annotations().author.set(author.clone()),
+            ("AddNote", _)                                              => self.add_note(),
+
+            ("TogglePinNotes", _)                                       => self.pin_notes.set(!self.pin_notes.get()),
+            ("ToggleShowAnnotations", _)                                => {
+                let show_annotations = &self.model.annotations().show_annotations;
+                show_annotations.set(!show_annotations.get());
+            },
+            ("ToggleShowResolved", _)                                   => {
+                let show_resolved = &self.model.annotations().show_resolved;
+                show_resolved.set(!show_resolved.get());
+            },
+
+            _                                                           => {
+                // 'ResolveNote-x' resolves note 'x', 'ReopenNote-x' marks it as unresolved again
+                if action_id.starts_with("ResolveNote-") {
+                    self.set_resolved(&action_id["ResolveNote-".len()..], true);
+                } else if action_id.starts_with("ReopenNote-") {
+                    self.set_resolved(&action_id["ReopenNote-".len()..], false);
+                }
+            }
+        }
+    }
+}
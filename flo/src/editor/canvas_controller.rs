@@ -44,6 +44,8 @@ pub struct CanvasController<Anim: Animation+EditableAnimation> {
     anim_model:         FloModel<Anim>,
     tool_changed:       Arc<Mutex<bool>>,
     _onion_skin_model:  BindRef<(Color, Color, Vec<(OnionSkinTime, Arc<Vec<Draw>>)>)>,
+    _presence_model:    BindRef<(Duration, Vec<UserPresence>)>,
+    _annotation_model:  BindRef<(bool, Vec<(ElementId, Annotation)>)>,
 
     core:               Arc<Desync<CanvasCore<Anim>>>
 }
@@ -62,6 +64,8 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
         let ui                  = Self::ui(main_canvas.clone(), view_model.size.clone());
         let tool_changed        = Arc::new(Mutex::new(true));
         let onion_skin_model    = Self::onion_skin_binding(view_model);
+        let presence_model      = Self::presence_binding(view_model);
+        let annotation_model    = Self::annotation_binding(view_model);
 
         // Set the tool changed flag whenever the effective tool changes
         // Note: the keep_alive() here will leak if the controller lives for less time than the model
@@ -82,6 +86,8 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
 
         // Connect events to the core
         Self::pipe_onion_skin_renders(main_canvas.clone(), onion_skin_model.clone(), core.clone());
+        Self::pipe_presence_renders(main_canvas.clone(), presence_model.clone(), core.clone());
+        Self::pipe_annotation_renders(main_canvas.clone(), annotation_model.clone(), core.clone());
 
         // Create the controller
         let controller = CanvasController {
@@ -90,6 +96,8 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
             anim_model:         view_model.clone(),
             tool_changed:       tool_changed,
             _onion_skin_model:  onion_skin_model,
+            _presence_model:    presence_model,
+            _annotation_model:  annotation_model,
 
             core:               core
         };
@@ -128,6 +136,56 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
         })
     }
 
+    ///
+    /// Creates a binding from a model to the parameters of the presence renderer function
+    ///
+    fn presence_binding(view_model: &FloModel<Anim>) -> BindRef<(Duration, Vec<UserPresence>)> {
+        let current_time        = view_model.timeline().current_time.clone();
+        let other_users         = view_model.collaboration().other_users.clone();
+
+        BindRef::from(computed(move || {
+            (current_time.get(), other_users.get())
+        }))
+    }
+
+    ///
+    /// Updates the rendering in the core whenever the other users editing the animation move
+    ///
+    fn pipe_presence_renders(canvas: Resource<BindingCanvas>, binding: BindRef<(Duration, Vec<UserPresence>)>, core: Arc<Desync<CanvasCore<Anim>>>) {
+        let presence_stream     = follow(binding);
+        let renderer            = PresenceRenderer::new();
+
+        pipe_in(core, presence_stream, move |core, (current_time, other_users)| {
+            renderer.render(&*canvas, &mut core.renderer, other_users, current_time);
+            Box::pin(future::ready(()))
+        })
+    }
+
+    ///
+    /// Creates a binding from a model to the parameters of the annotation marker renderer function
+    ///
+    fn annotation_binding(view_model: &FloModel<Anim>) -> BindRef<(bool, Vec<(ElementId, Annotation)>)> {
+        let show_annotations    = view_model.annotations().show_annotations.clone();
+        let frame_annotations   = view_model.annotations().frame_annotations.clone();
+
+        BindRef::from(computed(move || {
+            (show_annotations.get(), frame_annotations.get())
+        }))
+    }
+
+    ///
+    /// Updates the rendering in the core whenever the annotations on the current frame change
+    ///
+    fn pipe_annotation_renders(canvas: Resource<BindingCanvas>, binding: BindRef<(bool, Vec<(ElementId, Annotation)>)>, core: Arc<Desync<CanvasCore<Anim>>>) {
+        let annotation_stream   = follow(binding);
+        let renderer            = AnnotationMarkerRenderer::new();
+
+        pipe_in(core, annotation_stream, move |core, (show_annotations, annotations)| {
+            renderer.render(&*canvas, &mut core.renderer, annotations, show_annotations);
+            Box::pin(future::ready(()))
+        })
+    }
+
     ///
     /// Creates the ui for the canvas controller
     ///
@@ -217,6 +275,16 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
             self.anim_model.tools().current_pointer.set((device, actions[0].pointer_id));
         }
 
+        // Show the other users editing this animation where we're painting
+        if let Some(last_action) = actions.last() {
+            self.anim_model.collaboration().pointer.set(Some(last_action.location));
+        }
+
+        // Tools can't be used in read-only sessions
+        if self.anim_model.is_read_only() {
+            return;
+        }
+
         // Fetch the canvas we're going to draw to
         let canvas = self.canvases.get_named_resource(MAIN_CANVAS).unwrap();
 
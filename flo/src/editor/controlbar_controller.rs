@@ -16,6 +16,9 @@ pub struct ControlBarController<Anim: 'static+Animation+EditableAnimation> {
     /// The UI for this control bar
     ui: BindRef<Control>,
 
+    /// The model for the animation being edited
+    model: FloModel<Anim>,
+
     /// The keyframe controls controller
     keyframe_controls: Arc<KeyFrameControlsController<Anim>>,
 
@@ -33,7 +36,7 @@ impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
     pub fn new(model: &FloModel<Anim>) -> ControlBarController<Anim> {
         // Create the UI
         let images              = Arc::new(Self::images());
-        let ui                  = Self::ui(Arc::clone(&images));
+        let ui                  = Self::ui(Arc::clone(&images), model.is_read_only());
 
         // Create the subcontrollers
         let keyframe_controls   = KeyFrameControlsController::new(model);
@@ -45,6 +48,7 @@ impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
         // Build the controller itself
         ControlBarController {
             ui:                 ui,
+            model:              model.clone(),
             keyframe_controls:  keyframe_controls,
             frame_controls:     frame_controls,
             images:             images
@@ -60,36 +64,70 @@ impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
         images
     }
 
+    ///
+    /// Creates the undo and redo buttons (these are left out when viewing an animation, as it can't be edited)
+    ///
+    fn undo_controls(read_only: bool) -> Vec<Control> {
+        if read_only {
+            return vec![];
+        }
+
+        vec![
+            Control::empty()
+                .with(Bounds::next_horiz(6.0)),
+            Control::container()
+                .with(Hint::Class("button-group".to_string()))
+                .with(vec![
+                    Control::button()
+                        .with(vec![Control::label().with("Undo").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with((ActionTrigger::Click, "Undo"))
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Redo").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with((ActionTrigger::Click, "Redo"))
+                        .with(Bounds::next_horiz(40.0))
+                ])
+                .with(Bounds::next_horiz(80.0))
+        ]
+    }
+
     ///
     /// Creates the UI for this controller
     ///
-    fn ui(_images: Arc<ResourceManager<Image>>) -> BindRef<Control> {
+    fn ui(_images: Arc<ResourceManager<Image>>, read_only: bool) -> BindRef<Control> {
+        // The frame controls are on the left, followed by undo and redo
+        let mut controls = vec![
+            Control::empty()
+                .with(Bounds::next_horiz(6.0)),
+
+            Control::container()
+                .with_controller("FrameControls")
+                .with(Bounds::next_horiz(22.0*6.0+80.0)),
+
+            Control::empty()
+                .with(Bounds::next_horiz(3.0)),
+            Control::empty()
+                .with(Appearance::Background(TIMESCALE_LAYERS))
+                .with(Bounds::next_horiz(1.0))
+        ];
+        controls.extend(Self::undo_controls(read_only));
+
+        // The keyframe controls are on the right
+        controls.extend(vec![
+            Control::empty()
+                .with(Bounds::stretch_horiz(1.0)),
+            Control::container()
+                .with_controller("KeyFrameControls")
+                .with(Bounds::next_horiz(188.0)),
+            Control::empty()
+                .with(Bounds::next_horiz(32.0))
+        ]);
+
         // Create the UI itself
         let ui = Control::container()
             .with(Bounds::fill_all())
             .with(ControlAttribute::Padding((0, 2), (0, 2)))
-            .with(vec![
-                Control::empty()
-                    .with(Bounds::next_horiz(6.0)),
-
-                Control::container()
-                    .with_controller("FrameControls")
-                    .with(Bounds::next_horiz(22.0*6.0+80.0)),
-
-                Control::empty()
-                    .with(Bounds::next_horiz(3.0)),
-                Control::empty()
-                    .with(Appearance::Background(TIMESCALE_LAYERS))
-                    .with(Bounds::next_horiz(1.0)),
-
-                Control::empty()
-                    .with(Bounds::stretch_horiz(1.0)),
-                Control::container()
-                    .with_controller("KeyFrameControls")
-                    .with(Bounds::next_horiz(188.0)),
-                Control::empty()
-                    .with(Bounds::next_horiz(32.0))
-            ]);
+            .with(controls);
 
         // Create the binding
         let ui = bind(ui);
@@ -114,4 +152,13 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for ControlBarControl
     fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
         Some(Arc::clone(&self.images))
     }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "Undo"  => { if self.model.can_undo() { self.model.undo(); } },
+            "Redo"  => { if self.model.can_redo() { self.model.redo(); } },
+
+            _       => ()
+        }
+    }
 }
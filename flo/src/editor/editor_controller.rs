@@ -3,8 +3,10 @@ use super::canvas_controller::*;
 use super::toolbox_controller::*;
 use super::timeline_controller::*;
 use super::controlbar_controller::*;
+use super::annotations_controller::*;
 use super::super::model::*;
 use super::super::style::*;
+use super::super::clipboard::*;
 
 use flo_ui::*;
 use flo_ui_files::ui::*;
@@ -23,7 +25,8 @@ enum SubController {
     Menu,
     ControlBar,
     Timeline,
-    Toolbox
+    Toolbox,
+    Annotations
 }
 
 ///
@@ -37,7 +40,10 @@ pub struct EditorController<Anim: FileAnimation> {
     ui: Binding<Control>,
 
     /// The subcontrollers for this editor
-    subcontrollers: HashMap<SubController, Arc<dyn Controller>>
+    subcontrollers: HashMap<SubController, Arc<dyn Controller>>,
+
+    /// The animation being edited
+    animation: FloModel<Anim::NewAnimation>
 }
 
 impl<Loader: 'static+FileAnimation> EditorController<Loader>
@@ -54,26 +60,35 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     ///
     /// Creates a new editor controller from a model
     ///
+    /// If the model is read-only, the editor is created in viewer mode: the animation can be played back but the
+    /// toolbox is hidden and no edits can be made. Review notes can only be left if the model is a reviewer.
+    ///
     pub fn from_model(animation: FloModel<Loader::NewAnimation>) -> EditorController<Loader> {
         let canvas      = Arc::new(CanvasController::new(&animation));
         let menu        = Arc::new(MenuController::new(&animation));
         let timeline    = Arc::new(TimelineController::new(&animation));
-        let toolbox     = Arc::new(ToolboxController::new(&animation));
         let control_bar = Arc::new(ControlBarController::new(&animation));
+        let annotations = Arc::new(AnnotationsController::new(&animation));
 
-        let ui          = bind(Self::ui());
+        let ui          = if animation.is_read_only() { bind(Self::viewer_ui()) } else { bind(Self::ui()) };
         let mut subcontrollers: HashMap<SubController, Arc<dyn Controller>> = HashMap::new();
 
         subcontrollers.insert(SubController::Canvas,        canvas);
         subcontrollers.insert(SubController::Menu,          menu);
         subcontrollers.insert(SubController::Timeline,      timeline);
-        subcontrollers.insert(SubController::Toolbox,       toolbox);
         subcontrollers.insert(SubController::ControlBar,    control_bar);
+        subcontrollers.insert(SubController::Annotations,   annotations);
+
+        if !animation.is_read_only() {
+            let toolbox = Arc::new(ToolboxController::new(&animation));
+            subcontrollers.insert(SubController::Toolbox,   toolbox);
+        }
 
         EditorController {
             anim:           PhantomData,
             ui:             ui,
             subcontrollers: subcontrollers,
+            animation:      animation
         }
     }
 
@@ -141,6 +156,22 @@ where Loader::NewAnimation: 'static+EditableAnimation {
             .with_controller(&serde_json::to_string(&SubController::Canvas).unwrap())
     }
 
+    ///
+    /// Creates the sidebar control used to list and leave review notes
+    ///
+    pub fn annotations() -> Control {
+        use self::Position::*;
+
+        Control::container()
+            .with(Bounds {
+                x1: After,
+                y1: Start,
+                x2: Offset(240.0),
+                y2: End
+            })
+            .with_controller(&serde_json::to_string(&SubController::Annotations).unwrap())
+    }
+
     ///
     /// Creates the control bar control
     ///
@@ -157,12 +188,24 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     /// Creates the UI tree for this controller
     ///
     pub fn ui() -> Control {
+        Self::layout(vec![Self::toolbox(), Self::canvas(), Self::annotations()])
+    }
+
+    ///
+    /// Creates the UI tree for this controller when it's in viewer mode (the toolbox is hidden, but review notes are still shown)
+    ///
+    pub fn viewer_ui() -> Control {
+        Self::layout(vec![Self::canvas(), Self::annotations()])
+    }
+
+    ///
+    /// Lays out the editor UI around the controls that make up the main editing area
+    ///
+    fn layout(editing_area: Vec<Control>) -> Control {
         use self::Position::*;
 
         let menu_bar    = Self::menu_bar();
         let timeline    = Self::timeline();
-        let toolbar     = Self::toolbox();
-        let canvas      = Self::canvas();
         let control_bar = Self::control_bar();
 
         Control::container()
@@ -170,7 +213,7 @@ where Loader::NewAnimation: 'static+EditableAnimation {
             .with(vec![
                 menu_bar,
                 Control::container()
-                    .with((vec![toolbar, canvas],
+                    .with((editing_area,
                         Bounds { x1: Start, y1: After, x2: End, y2: Stretch(1.0) })),
                 Control::empty()
                     .with(Bounds::next_vert(1.0))
@@ -198,6 +241,16 @@ where Loader::NewAnimation: 'static+EditableAnimation {
             None
         }
     }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "Copy"  => { copy_selection(&self.animation); },
+            "Cut"   => { cut_selection(&self.animation); },
+            "Paste" => { paste_clipboard(&self.animation); },
+
+            _       => ()
+        }
+    }
 }
 
 impl<Loader: 'static+FileAnimation> FileController for EditorController<Loader>
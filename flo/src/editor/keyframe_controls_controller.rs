@@ -62,12 +62,20 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let keyframe_selected       = frame.keyframe_selected.clone();
         let prev_next_1             = frame.previous_and_next_keyframe.clone();
         let prev_next_2             = frame.previous_and_next_keyframe.clone();
+        let prev_next_3             = frame.previous_and_next_keyframe.clone();
+        let bake_keyframe_selected  = frame.keyframe_selected.clone();
+        let inbetween_mode          = Self::inbetween_mode(model);
 
         view_model.set_computed("CreateKeyFrameOnDrawSelected", move || PropertyValue::Bool(create_keyframe_on_draw.get()));
         view_model.set_computed("ShowOnionSkinsSelected",       move || PropertyValue::Bool(show_onion_skins.get()));
         view_model.set_computed("CanCreateKeyFrame",            move || PropertyValue::Bool(selected_layer.get().is_some() && !keyframe_selected.get()));
         view_model.set_computed("CanMoveToPreviousKeyFrame",    move || PropertyValue::Bool(prev_next_1.get().0.is_some()));
         view_model.set_computed("CanMoveToNextKeyFrame",        move || PropertyValue::Bool(prev_next_2.get().1.is_some()));
+        view_model.set_computed("InbetweenSelected",            move || PropertyValue::Bool(inbetween_mode.get() != InbetweenMode::Off));
+        view_model.set_computed("CanBakeInbetweens",            move || {
+            let (previous, next) = prev_next_3.get();
+            PropertyValue::Bool((previous.is_some() || bake_keyframe_selected.get()) && next.is_some())
+        });
 
         // The edit sink lets us send edits to the animation (in particular, the 'new keyframe' edits)
         let edit_sink       = model.edit();
@@ -90,6 +98,25 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         }
     }
 
+    ///
+    /// Creates a binding that tracks the in-betweening mode of the selected layer
+    ///
+    fn inbetween_mode(model: &FloModel<Anim>) -> BindRef<InbetweenMode> {
+        let model           = model.clone();
+        let selected_layer  = model.timeline().selected_layer.clone();
+        let frame_updates   = model.frame_update_count();
+
+        BindRef::from(computed(move || {
+            // Layer properties are re-read whenever the frame is edited
+            frame_updates.get();
+
+            selected_layer.get()
+                .and_then(|layer_id| model.get_layer_with_id(layer_id))
+                .map(|layer| layer.inbetween_mode())
+                .unwrap_or(InbetweenMode::Off)
+        }))
+    }
+
     ///
     /// Creates the UI for this controller
     ///
@@ -100,6 +127,8 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let next_key_frame      = images.get_named_resource("next_key_frame").unwrap();
         let onion_skins         = images.get_named_resource("onion_skins").unwrap();
         let previous_key_frame  = images.get_named_resource("previous_key_frame").unwrap();
+        let inbetween           = images.get_named_resource("inbetween").unwrap();
+        let bake_inbetweens     = images.get_named_resource("bake_inbetweens").unwrap();
 
         // Get the parts of the model we want to use
 
@@ -110,6 +139,8 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
             let next_key_frame      = next_key_frame.clone();
             let onion_skins         = onion_skins.clone();
             let previous_key_frame  = previous_key_frame.clone();
+            let inbetween           = inbetween.clone();
+            let bake_inbetweens     = bake_inbetweens.clone();
 
             Control::container()
                 .with(vec![
@@ -157,6 +188,21 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
                                 .with((ActionTrigger::Click, "ToggleCreateKeyFrameOnDraw"))
                                 .with(Bounds::next_horiz(22.0)),
 
+                            Control::button()
+                                .with(vec![Control::empty().with(inbetween).with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                                .with(State::Selected(Property::bound("InbetweenSelected")))
+                                .with(State::Enabled(Property::Bool(true)))
+                                .with((ActionTrigger::Click, "ToggleInbetween"))
+                                .with(Bounds::next_horiz(22.0)),
+
+                            Control::button()
+                                .with(vec![Control::empty().with(bake_inbetweens).with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                                .with(State::Enabled(Property::bound("CanBakeInbetweens")))
+                                .with((ActionTrigger::Click, "BakeInbetweens"))
+                                .with(Bounds::next_horiz(22.0)),
+
                             Control::button()
                                 .with(vec![Control::empty().with(next_key_frame).with(TextAlign::Center).with(Bounds::fill_all())])
                                 .with(ControlAttribute::Padding((4, 4), (9, 4)))
@@ -164,7 +210,7 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
                                 .with((ActionTrigger::Click, "MoveToNextKeyFrame"))
                                 .with(Bounds::next_horiz(22.0)),
                         ])
-                        .with(Bounds::next_horiz(22.0*5.0))
+                        .with(Bounds::next_horiz(22.0*7.0))
 
                 ])
                 .with(Bounds::fill_all())
@@ -185,12 +231,16 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let next_key_frame      = images.register(svg_static(include_bytes!("../../svg/keyframes/next_key_frame.svg")));
         let onion_skins         = images.register(svg_static(include_bytes!("../../svg/keyframes/onion_skins.svg")));
         let previous_key_frame  = images.register(svg_static(include_bytes!("../../svg/keyframes/previous_key_frame.svg")));
+        let inbetween           = images.register(svg_static(include_bytes!("../../svg/keyframes/inbetween.svg")));
+        let bake_inbetweens     = images.register(svg_static(include_bytes!("../../svg/keyframes/bake_inbetweens.svg")));
 
         images.assign_name(&new_key_frame,      "new_key_frame");
         images.assign_name(&new_on_paint,       "new_on_paint");
         images.assign_name(&next_key_frame,     "next_key_frame");
         images.assign_name(&onion_skins,        "onion_skins");
         images.assign_name(&previous_key_frame, "previous_key_frame");
+        images.assign_name(&inbetween,          "inbetween");
+        images.assign_name(&bake_inbetweens,    "bake_inbetweens");
 
         images
     }
@@ -246,6 +296,33 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for KeyFrameControlsC
                 }
             },
 
+            "ToggleInbetween" => {
+                // Turns in-betweening on or off for the selected layer
+                if let Some(selected_layer) = self.selected_layer.get() {
+                    let current_mode    = self.debug_model.get_layer_with_id(selected_layer).map(|layer| layer.inbetween_mode()).unwrap_or(InbetweenMode::Off);
+                    let new_mode        = if current_mode == InbetweenMode::Off { InbetweenMode::MatchByOrder } else { InbetweenMode::Off };
+
+                    self.debug_model.perform_edits(vec![AnimationEdit::Layer(selected_layer, LayerEdit::SetInbetweenMode(new_mode))]);
+                    self.timeline.invalidate_canvas();
+                }
+            },
+
+            "BakeInbetweens" => {
+                // Creates keyframes for every frame between the keyframes either side of the current time
+                let current_time        = self.current_time.get();
+                let selected_layer      = self.selected_layer.get();
+                let (previous, next)    = self.frame.previous_and_next_keyframe.get();
+                let start               = if self.frame.keyframe_selected.get() { Some(current_time) } else { previous };
+
+                if let (Some(selected_layer), Some(start), Some(end)) = (selected_layer, start, next) {
+                    let frame_duration = self.timeline.frame_duration.get();
+
+                    self.debug_model.perform_edits(vec![AnimationEdit::Layer(selected_layer, LayerEdit::BakeInbetweens(start..end, frame_duration, vec![]))]);
+                    self.timeline.invalidate_canvas();
+                    self.timeline.update_keyframe_bindings();
+                }
+            },
+
             "CreateKeyFrame" => {
                 let current_time        = self.current_time.get();
                 let selected_layer      = self.selected_layer.get();
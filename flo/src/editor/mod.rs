@@ -8,9 +8,11 @@ mod controlbar_controller;
 mod frame_controls_controller;
 mod keyframe_controls_controller;
 mod toolbox_controller;
+mod annotations_controller;
 
 pub use self::editor_controller::*;
 pub use self::canvas_controller::*;
 pub use self::menu_controller::*;
 pub use self::timeline_controller::*;
 pub use self::toolbox_controller::*;
+pub use self::annotations_controller::*;
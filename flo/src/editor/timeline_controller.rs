@@ -18,6 +18,18 @@ enum Onion {
     Right
 }
 
+///
+/// What the user is dragging in the keyframe area of the timeline
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum KeyFrameDrag {
+    /// Dragging the keyframe at the specified time in a layer. It's moved if it's dropped in the same layer, and the drag is cancelled if it's dropped outside it
+    KeyFrame(u64, Duration),
+
+    /// Dragging from an empty part of a layer: the keyframes from this time onwards are shifted by the distance dragged
+    Shift(u64, Duration)
+}
+
 /// Action when the user drags the timeline 'time' indicator
 const DRAG_TIMELINE_POSITION: &str = "DragTime";
 
@@ -33,6 +45,9 @@ const CLICK_AND_DRAG_TIMELINE_POSITION: &str = "ClickTime";
 /// Action when the virtual scroll position changes
 const SCROLL_TIMELINE: &str     = "Scroll";
 
+/// Action when the user drags a keyframe or an empty part of a layer
+const DRAG_KEYFRAME: &str       = "DragKeyFrame";
+
 /// Width of an item in a virtualised canvas
 const VIRTUAL_WIDTH: f32        = 400.0;
 
@@ -76,6 +91,9 @@ pub struct TimelineController<Anim: Animation> {
     /// The setting of frames_before/frames_after when the drag on the onion skin start/end indicators started
     drag_start_frames:          Binding<usize>,
 
+    /// The keyframe (or part of a layer) that the user is currently dragging
+    drag_keyframe:              Binding<Option<KeyFrameDrag>>,
+
     /// A virtual control that draws the timeline scale
     virtual_scale:              VirtualCanvas,
 
@@ -181,6 +199,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
             virtual_keyframes:          virtual_keyframes,
             drag_start_time:            bind(Duration::from_millis(0)),
             drag_start_frames:          bind(0),
+            drag_keyframe:              bind(None),
             canvases:                   canvases,
             layer_list_controller:      Arc::new(layer_list_controller),
             layer_controls_controller:  Arc::new(layer_controls_controller),
@@ -331,6 +350,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
                         .with(vec![
                             virtual_keyframes_control.get()
                         ])
+                        .with((ActionTrigger::Drag, DRAG_KEYFRAME))
                         .with(ControlAttribute::ZIndex(2)),
                     Control::canvas()           // Selected frame indicator (upper part, arrow indicator)
                         .with(timescale_indicator)
@@ -375,13 +395,15 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
             let end_tick    = ((tick_x+VIRTUAL_WIDTH)/TICK_LENGTH).ceil() + 1.0;
             let start_tick  = start_tick.max(0.0) as u32;
             let end_tick    = end_tick.max(0.0) as u32;
-            let keyframes   = timeline.get_keyframe_binding(start_tick..end_tick);
-            let layers      = BindRef::new(&timeline.layers);
+            let keyframes       = timeline.get_keyframe_binding(start_tick..end_tick);
+            let layers          = BindRef::new(&timeline.layers);
+            let frame_duration  = BindRef::new(&timeline.frame_duration);
 
             // Generate the drawing function for this part of the canvas
             Box::new(move |gc| {
-                let layers      = layers.get();
-                let keyframes   = keyframes.get();
+                let layers          = layers.get();
+                let keyframes       = keyframes.get();
+                let frame_duration  = frame_duration.get();
 
                 let last_layer  = last_layer.min(layers.len());
                 let end_tick    = end_tick;
@@ -427,6 +449,17 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
                 }
                 gc.stroke();
 
+                // Draw the waveforms for any layers with audio attached (underneath the keyframes)
+                gc.fill_color(TIMESCALE_AUDIO);
+                for layer_index in first_layer..last_layer {
+                    if let Some(audio) = layers[layer_index].audio.as_ref() {
+                        let origin      = (LAYER_PANEL_WIDTH, (layer_index as f32) * TIMELINE_LAYER_HEIGHT);
+                        let waveform    = audio.waveform(frame_duration, start_tick..end_tick, origin, (TICK_LENGTH, TIMELINE_LAYER_HEIGHT));
+
+                        gc.draw_list(Box::new(waveform.into_iter()));
+                    }
+                }
+
                 // Draw the keyframes that are in this region
                 gc.fill_color(TIMESCALE_KEYFRAME);
                 for keyframe in keyframes.iter() {
@@ -651,6 +684,79 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
 
         time_ns
     }
+
+    ///
+    /// Finds what will be dragged if the user starts a drag at the specified position in the keyframe area
+    ///
+    fn keyframe_drag_at(&self, xpos: f32, ypos: f32) -> Option<KeyFrameDrag> {
+        let timeline        = self.anim_model.timeline();
+
+        // Find the layer and frame at this position
+        if ypos < 0.0 || xpos < LAYER_PANEL_WIDTH {
+            return None;
+        }
+
+        let layer_index     = (ypos / TIMELINE_LAYER_HEIGHT).floor() as usize;
+        let layer_id        = timeline.layers.get().get(layer_index)?.id;
+        let frame           = ((xpos - LAYER_PANEL_WIDTH) / TICK_LENGTH).floor() as u32;
+
+        // Drag the keyframe if there's one in this frame, otherwise shift the keyframes from this point onwards
+        let keyframe        = timeline.get_keyframe_binding(frame..(frame+1)).get()
+            .into_iter()
+            .filter(|keyframe| keyframe.layer_id == layer_id && keyframe.frame == frame)
+            .nth(0);
+
+        match keyframe {
+            Some(keyframe)  => Some(KeyFrameDrag::KeyFrame(layer_id, keyframe.when)),
+            None            => Some(KeyFrameDrag::Shift(layer_id, timeline.frame_duration.get() * frame))
+        }
+    }
+
+    ///
+    /// Returns the time that a keyframe drag moves things to, given how far the user has dragged
+    ///
+    fn keyframe_drag_time(&self, drag: KeyFrameDrag, diff_x: f32) -> Duration {
+        let start_time = match drag {
+            KeyFrameDrag::KeyFrame(_, when) => when,
+            KeyFrameDrag::Shift(_, when)    => when
+        };
+
+        Self::ns_to_duration(Self::duration_to_ns(start_time) + self.xpos_to_ns(diff_x))
+    }
+
+    ///
+    /// Generates the edit for a finished keyframe drag
+    ///
+    fn keyframe_drag_edit(&self, drag: KeyFrameDrag, diff_x: f32, start_y: f32, end_y: f32) -> Option<AnimationEdit> {
+        let new_time = self.keyframe_drag_time(drag, diff_x);
+
+        match drag {
+            KeyFrameDrag::KeyFrame(layer_id, when) => {
+                let same_layer = (start_y / TIMELINE_LAYER_HEIGHT).floor() == (end_y / TIMELINE_LAYER_HEIGHT).floor();
+
+                if !same_layer {
+                    // Keyframes can't be moved between layers, so dropping one on a different layer cancels the drag
+                    None
+                } else if new_time != when {
+                    Some(AnimationEdit::Layer(layer_id, LayerEdit::MoveKeyFrame(when, new_time)))
+                } else {
+                    None
+                }
+            }
+
+            KeyFrameDrag::Shift(layer_id, when) => {
+                if new_time != when {
+                    // Every keyframe from the start of the drag onwards moves by the same amount
+                    let end_of_time     = Duration::from_micros(i64::max_value() as u64);
+                    let new_end_of_time = if new_time > when { end_of_time + (new_time - when) } else { end_of_time - (when - new_time) };
+
+                    Some(AnimationEdit::Layer(layer_id, LayerEdit::RetimeKeyFrames(when..end_of_time, new_time..new_end_of_time)))
+                } else {
+                    None
+                }
+            }
+        }
+    }
 }
 
 impl<Anim: EditableAnimation+Animation+'static> Controller for TimelineController<Anim> {
@@ -707,6 +813,34 @@ impl<Anim: EditableAnimation+Animation+'static> Controller for TimelineControlle
                 timeline.current_time.set(new_time);
             },
 
+            (DRAG_KEYFRAME, &Drag(DragAction::Start, (start_x, start_y), _)) => {
+                // Work out if the user is dragging a keyframe or an empty part of a layer
+                self.drag_keyframe.set(self.keyframe_drag_at(start_x, start_y));
+            },
+
+            (DRAG_KEYFRAME, &Drag(DragAction::Drag, (start_x, _start_y), (x, _y))) => {
+                // The time indicator follows the drag to show where the keyframe will end up
+                if let Some(drag) = self.drag_keyframe.get() {
+                    self.anim_model.timeline().current_time.set(self.keyframe_drag_time(drag, x - start_x));
+                }
+            },
+
+            (DRAG_KEYFRAME, &Drag(DragAction::Finish, (start_x, start_y), (x, y))) => {
+                if let Some(edit) = self.drag_keyframe.get().and_then(|drag| self.keyframe_drag_edit(drag, x - start_x, start_y, y)) {
+                    let timeline = self.anim_model.timeline();
+
+                    self.anim_model.perform_edits(vec![edit]);
+                    timeline.update_keyframe_bindings();
+                    timeline.invalidate_canvas();
+                }
+
+                self.drag_keyframe.set(None);
+            },
+
+            (DRAG_KEYFRAME, &Drag(DragAction::Cancel, _, _)) => {
+                self.drag_keyframe.set(None);
+            },
+
             (DRAG_ONION_FRAMES_AFTER, &Drag(DragAction::Start, _, _)) => {
                 self.drag_start_frames.set(self.anim_model.onion_skin().frames_after.get());
             },
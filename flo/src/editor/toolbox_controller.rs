@@ -102,6 +102,12 @@ impl<Anim: 'static+EditableAnimation+Animation> ToolboxController<Anim> {
         let eraser      = images.register(svg_static(include_bytes!("../../svg/tools/eraser.svg")));
         let floodfill   = images.register(svg_static(include_bytes!("../../svg/tools/floodfill.svg")));
 
+        let rectangle   = images.register(svg_static(include_bytes!("../../svg/tools/rectangle.svg")));
+        let ellipse     = images.register(svg_static(include_bytes!("../../svg/tools/ellipse.svg")));
+        let polygon     = images.register(svg_static(include_bytes!("../../svg/tools/polygon.svg")));
+        let line        = images.register(svg_static(include_bytes!("../../svg/tools/line.svg")));
+        let text        = images.register(svg_static(include_bytes!("../../svg/tools/text.svg")));
+
         // Assign names to them
         images.assign_name(&select, "select");
         images.assign_name(&adjust, "adjust");
@@ -112,6 +118,12 @@ impl<Anim: 'static+EditableAnimation+Animation> ToolboxController<Anim> {
         images.assign_name(&eraser, "eraser");
         images.assign_name(&floodfill, "floodfill");
 
+        images.assign_name(&rectangle, "rectangle");
+        images.assign_name(&ellipse, "ellipse");
+        images.assign_name(&polygon, "polygon");
+        images.assign_name(&line, "line");
+        images.assign_name(&text, "text");
+
         images
     }
 
@@ -26,6 +26,7 @@ pub mod tools;
 pub mod standard_tools;
 pub mod menu;
 pub mod color;
+pub mod clipboard;
 
 mod model;
 
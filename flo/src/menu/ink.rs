@@ -12,12 +12,109 @@ use flo_animation::brushes::*;
 use std::f32;
 use std::sync::*;
 
+///
+/// The properties of a brush stroke that can be driven by the brush dynamics
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum DynamicsProperty {
+    Width,
+    Opacity,
+    Color
+}
+
+///
+/// The response curves that can be chosen from the ink menu
+///
+const RESPONSE_CURVES: [(&str, fn() -> ResponseCurve); 4] = [
+    ("Linear",      ResponseCurve::linear),
+    ("Soft",        ResponseCurve::soft),
+    ("Firm",        ResponseCurve::firm),
+    ("Inverted",    ResponseCurve::inverted)
+];
+
+impl DynamicsProperty {
+    ///
+    /// Retrieves the responses for this property from a brush dynamics object
+    ///
+    fn responses<'a>(&self, dynamics: &'a BrushDynamics) -> &'a Vec<DynamicsResponse> {
+        match self {
+            DynamicsProperty::Width     => &dynamics.width,
+            DynamicsProperty::Opacity   => &dynamics.opacity,
+            DynamicsProperty::Color     => &dynamics.color
+        }
+    }
+
+    ///
+    /// Retrieves the responses for this property from a brush dynamics object so they can be edited
+    ///
+    fn responses_mut<'a>(&self, dynamics: &'a mut BrushDynamics) -> &'a mut Vec<DynamicsResponse> {
+        match self {
+            DynamicsProperty::Width     => &mut dynamics.width,
+            DynamicsProperty::Opacity   => &mut dynamics.opacity,
+            DynamicsProperty::Color     => &mut dynamics.color
+        }
+    }
+
+    ///
+    /// Returns the name of the input that drives this property (the menu only edits the first response for each property)
+    ///
+    fn input_name(&self, dynamics: &BrushDynamics) -> &'static str {
+        match self.responses(dynamics).first().map(|response| response.input) {
+            None                            => "Off",
+            Some(DynamicsInput::Pressure)   => "Pressure",
+            Some(DynamicsInput::Tilt)       => "Tilt",
+            Some(DynamicsInput::Speed)      => "Speed"
+        }
+    }
+
+    ///
+    /// Returns the name of the response curve for this property
+    ///
+    fn curve_name(&self, dynamics: &BrushDynamics) -> &'static str {
+        match self.responses(dynamics).first() {
+            None            => "-",
+            Some(response)  => RESPONSE_CURVES.iter()
+                .filter(|(_, curve)| curve() == response.curve)
+                .map(|(name, _)| *name)
+                .next()
+                .unwrap_or("Custom")
+        }
+    }
+
+    ///
+    /// Changes the input that drives this property to the next one (turning it off after the last input)
+    ///
+    fn next_input(&self, dynamics: &mut BrushDynamics) {
+        let responses = self.responses_mut(dynamics);
+
+        match responses.first().map(|response| response.input) {
+            None                            => { responses.push(DynamicsResponse::new(DynamicsInput::Pressure, ResponseCurve::linear())); }
+            Some(DynamicsInput::Pressure)   => { responses[0].input = DynamicsInput::Tilt; }
+            Some(DynamicsInput::Tilt)       => { responses[0].input = DynamicsInput::Speed; }
+            Some(DynamicsInput::Speed)      => { responses.remove(0); }
+        }
+    }
+
+    ///
+    /// Changes the response curve for this property to the next one
+    ///
+    fn next_curve(&self, dynamics: &mut BrushDynamics) {
+        if let Some(response) = self.responses_mut(dynamics).first_mut() {
+            let current_idx = RESPONSE_CURVES.iter().position(|(_, curve)| curve() == response.curve);
+            let next_idx    = current_idx.map(|idx| (idx+1) % RESPONSE_CURVES.len()).unwrap_or(0);
+
+            response.curve  = (RESPONSE_CURVES[next_idx].1)();
+        }
+    }
+}
+
 ///
 /// Controller used for the ink tool
 ///
 pub struct InkMenuController {
     size:               Binding<f32>,
     opacity:            Binding<f32>,
+    dynamics:           Binding<BrushDynamics>,
     modification_mode:  Binding<BrushModificationMode>,
     representation:     Binding<BrushRepresentation>,
     brush_panel_open:   Binding<bool>,
@@ -28,7 +125,10 @@ pub struct InkMenuController {
     view_model:         Arc<DynamicViewModel>,
 
     color_picker_open:  Binding<bool>,
-    color_picker:       Arc<PopupController<ColorPickerController>>
+    color_picker:       Arc<PopupController<ColorPickerController>>,
+
+    secondary_color_picker_open:    Binding<bool>,
+    secondary_color_picker:         Arc<PopupController<ColorPickerController>>
 }
 
 impl InkMenuController {
@@ -61,10 +161,29 @@ impl InkMenuController {
         images
     }
 
+    ///
+    /// Creates the ink definition for a brush with the specified dynamics
+    ///
+    pub fn ink_definition(dynamics: BrushDynamics, secondary_color: Color) -> InkDefinition {
+        InkDefinition {
+            dynamics: BrushDynamics { secondary_color, ..dynamics },
+            ..InkDefinition::default()
+        }
+    }
+
+    ///
+    /// Makes a change to the brush dynamics
+    ///
+    fn update_dynamics<UpdateFn: FnOnce(&mut BrushDynamics)>(&self, update: UpdateFn) {
+        let mut dynamics = self.dynamics.get();
+        update(&mut dynamics);
+        self.dynamics.set(dynamics);
+    }
+
     ///
     /// Creates a new ink menu controller
     ///
-    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> InkMenuController {
+    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>, dynamics: &Binding<BrushDynamics>, secondary_colour: &Binding<Color>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> InkMenuController {
         // Set up the view model
         let view_model = Arc::new(DynamicViewModel::new());
 
@@ -90,13 +209,23 @@ impl InkMenuController {
         let vm_color_picker_open = color_picker_open.clone();
         view_model.set_computed("ColorPickerOpen", move || PropertyValue::Bool(vm_color_picker_open.get()));
 
+        // Create the popup for the colour used by the brush dynamics
+        let secondary_color_picker_open = Binding::new(false);
+        let secondary_color_picker      = ColorPickerController::new(secondary_colour);
+        let secondary_color_picker      = PopupController::new(secondary_color_picker, &secondary_color_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+
+        let vm_secondary_color_picker_open = secondary_color_picker_open.clone();
+        view_model.set_computed("SecondaryColorPickerOpen", move || PropertyValue::Bool(vm_secondary_color_picker_open.get()));
+
         // Images
         let images                      = Arc::new(Self::images());
 
         // Create the canvases
         let canvases                = Arc::new(ResourceManager::new());
 
-        let brush_preview           = Self::brush_preview(size, opacity, colour);
+        let brush_preview           = Self::brush_preview(size, opacity, colour, dynamics, secondary_colour);
         let brush_preview           = canvases.register(brush_preview);
         canvases.assign_name(&brush_preview, "BrushPreview");
 
@@ -120,13 +249,18 @@ impl InkMenuController {
         let colour_preview          = canvases.register(colour_preview);
         canvases.assign_name(&colour_preview, "ColourPreview");
 
+        let secondary_preview       = Self::colour_preview(secondary_colour);
+        let secondary_preview       = canvases.register(secondary_preview);
+        canvases.assign_name(&secondary_preview, "SecondaryColourPreview");
+
         // Generate the UI
-        let ui = Self::ui(&canvases, &images, &brush_panel_open, modification_mode, representation);
+        let ui = Self::ui(&canvases, &images, &brush_panel_open, dynamics, modification_mode, representation);
 
         // Finalize the control
         InkMenuController {
             size:               size.clone(),
             opacity:            opacity.clone(),
+            dynamics:           dynamics.clone(),
             modification_mode:  modification_mode.clone(),
             representation:     representation.clone(),
             brush_panel_open:   brush_panel_open,
@@ -137,15 +271,19 @@ impl InkMenuController {
             view_model:         view_model,
 
             color_picker_open:  color_picker_open,
-            color_picker:       Arc::new(color_picker)
+            color_picker:       Arc::new(color_picker),
+
+            secondary_color_picker_open:    secondary_color_picker_open,
+            secondary_color_picker:         Arc::new(secondary_color_picker)
         }
     }
 
     ///
     /// Creates the UI for the ink menu bar
     ///
-    fn ui(canvases: &ResourceManager<BindingCanvas>, images: &ResourceManager<Image>, brush_panel_open: &Binding<bool>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> BindRef<Control> {
+    fn ui(canvases: &ResourceManager<BindingCanvas>, images: &ResourceManager<Image>, brush_panel_open: &Binding<bool>, dynamics: &Binding<BrushDynamics>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> BindRef<Control> {
         // Model
+        let dynamics                    = dynamics.clone();
         let modification_mode           = modification_mode.clone();
         let representation              = representation.clone();
         let brush_panel_open            = brush_panel_open.clone();
@@ -168,12 +306,14 @@ impl InkMenuController {
         let opacity_preview             = canvases.get_named_resource("OpacityPreview");
         let opacity_preview_large       = canvases.get_named_resource("OpacityPreview2");
         let colour_preview              = canvases.get_named_resource("ColourPreview");
+        let secondary_preview           = canvases.get_named_resource("SecondaryColourPreview");
 
         // Generate the UI control
         let ui = computed(move || {
             let modification_mode   = modification_mode.get();
             let representation      = representation.get();
             let brush_panel_open    = brush_panel_open.get();
+            let dynamics            = dynamics.get();
 
             let modification_icon   = match modification_mode {
                 BrushModificationMode::Additive     => additive_mode.clone(),
@@ -308,7 +448,7 @@ impl InkMenuController {
                                 .with(if brush_panel_open { vec![
                                     Control::popup()
                                         .with(Popup::Direction(PopupDirection::Below))
-                                        .with(Popup::Size(220, 131))
+                                        .with(Popup::Size(260, 252))
                                         .with(Popup::Offset(14))
                                         .with(ControlAttribute::ZIndex(1000))
                                         .with(Popup::IsOpen(Property::Bind("EditBrushProperties".to_string())))
@@ -374,6 +514,35 @@ impl InkMenuController {
                                                         ]),
                                                     Control::empty()
                                                         .with(Bounds::next_vert(3.0)),
+                                                    Control::empty()
+                                                        .with(Appearance::Background(MENU_BACKGROUND_ALT))
+                                                        .with(Bounds::next_vert(2.0)),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(3.0)),
+                                                    Control::label()
+                                                        .with(Bounds::next_vert(20.0))
+                                                        .with("Dynamics")
+                                                        .with(Font::Size(13.0))
+                                                        .with(Font::Align(TextAlign::Center))
+                                                        .with(Font::Weight(FontWeight::Light)),
+                                                    Self::dynamics_row("Width", DynamicsProperty::Width, &dynamics, &combo_picker, vec![]),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(3.0)),
+                                                    Self::dynamics_row("Opacity", DynamicsProperty::Opacity, &dynamics, &combo_picker, vec![]),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(3.0)),
+                                                    Self::dynamics_row("Color", DynamicsProperty::Color, &dynamics, &combo_picker, vec![
+                                                        Control::empty()
+                                                            .with(Bounds::next_horiz(4.0)),
+                                                        Control::canvas()
+                                                            .with(secondary_preview.clone())
+                                                            .with(Bounds::next_horiz(26.0))
+                                                            .with(State::Badged(Property::Bind("SecondaryColorPickerOpen".to_string())))
+                                                            .with((ActionTrigger::Click, "ShowSecondaryColorPopup"))
+                                                            .with_controller("SecondaryColorPopup")
+                                                    ]),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(3.0)),
                                                 ])
                                         ]),
                                 ] } else { vec![] }),
@@ -395,6 +564,49 @@ impl InkMenuController {
         BindRef::from(ui)
     }
 
+    ///
+    /// Creates a row of the dynamics section of the brush settings popup
+    ///
+    fn dynamics_row(label: &str, property: DynamicsProperty, dynamics: &BrushDynamics, combo_picker: &Option<Resource<Image>>, extra_controls: Vec<Control>) -> Control {
+        let action_name = format!("{:?}", property);
+
+        Control::container()
+            .with(Bounds::next_vert(26.0))
+            .with(vec![
+                Control::label()
+                    .with(Bounds::next_horiz(52.0))
+                    .with(Font::Size(11.0))
+                    .with(TextAlign::Right)
+                    .with(label),
+                Control::empty()
+                    .with(Bounds::next_horiz(6.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(86.0))
+                    .with(combo_picker.clone())
+                    .with((ActionTrigger::Click, format!("NextDynamicsInput{}", action_name)))
+                    .with(ControlAttribute::Padding((8, 4), (8, 4)))
+                    .with(vec![
+                        Control::label()
+                            .with(Bounds::fill_horiz())
+                            .with(Font::Size(11.0))
+                            .with(property.input_name(dynamics))
+                    ]),
+                Control::empty()
+                    .with(Bounds::next_horiz(4.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(76.0))
+                    .with(combo_picker.clone())
+                    .with((ActionTrigger::Click, format!("NextDynamicsCurve{}", action_name)))
+                    .with(ControlAttribute::Padding((8, 4), (8, 4)))
+                    .with(vec![
+                        Control::label()
+                            .with(Bounds::fill_horiz())
+                            .with(Font::Size(11.0))
+                            .with(property.curve_name(dynamics))
+                    ])
+            ].into_iter().chain(extra_controls).collect::<Vec<_>>())
+    }
+
     ///
     /// Creates the size preview canvas
     ///
@@ -459,10 +671,12 @@ impl InkMenuController {
     ///
     /// Creates the brush preview canvas
     ///
-    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>) -> BindingCanvas {
-        let size    = size.clone();
-        let opacity = opacity.clone();
-        let color   = color.clone();
+    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>, dynamics: &Binding<BrushDynamics>, secondary_color: &Binding<Color>) -> BindingCanvas {
+        let size            = size.clone();
+        let opacity         = opacity.clone();
+        let color           = color.clone();
+        let dynamics        = dynamics.clone();
+        let secondary_color = secondary_color.clone();
 
         let control_height  = 32.0 - 6.0;
         let control_width   = 64.0;
@@ -479,18 +693,21 @@ impl InkMenuController {
             gc.rect(-control_width/2.0, -control_height/2.0, control_width/2.0, control_height/2.0);
             gc.fill();
 
-            // Create an ink brush
-            let brush = InkBrush::new(&InkDefinition::default(), BrushDrawingStyle::Draw);
+            // Create an ink brush with the current dynamics (the preview stroke is much smaller than a real stroke, so the full speed is scaled to suit it)
+            let mut definition              = Self::ink_definition(dynamics.get(), secondary_color.get());
+            definition.dynamics.full_speed  = preview_width * 2.0 / 100.0;
 
-            // Render a test brush stroke
+            let brush = InkBrush::new(&definition, BrushDrawingStyle::Draw);
+
+            // Render a test brush stroke (which gets faster, more tilted and presses harder as it goes)
             let mut points = vec![];
             for point in 0..100 {
                 let point   = (point as f32)/100.0;
                 let offset  = -(point*f32::consts::PI*1.5).cos();
 
                 points.push(RawPoint {
-                    position:   (point*preview_width-(preview_width/2.0), offset*preview_height/2.0),
-                    tilt:       (0.0, 0.0),
+                    position:   (point*point*preview_width-(preview_width/2.0), offset*preview_height/2.0),
+                    tilt:       (point*90.0, 0.0),
                     pressure:   point
                 })
             }
@@ -499,7 +716,8 @@ impl InkMenuController {
             let brush_properties = BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                fill:       BrushFill::Solid
             };
 
             let points = brush.brush_points_for_raw_points(&points);
@@ -521,8 +739,9 @@ impl Controller for InkMenuController {
 
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         match id {
-            "ColorPopup"        => Some(self.color_picker.clone()),
-            _                   => None
+            "ColorPopup"            => Some(self.color_picker.clone()),
+            "SecondaryColorPopup"   => Some(self.secondary_color_picker.clone()),
+            _                       => None
         }
     }
 
@@ -567,6 +786,18 @@ impl Controller for InkMenuController {
                 self.color_picker_open.set(true)
             },
 
+            ("ShowSecondaryColorPopup", _) => {
+                // User has clicked the colour used by the brush dynamics
+                self.secondary_color_picker_open.set(true)
+            },
+
+            ("NextDynamicsInputWidth", _)   => { self.update_dynamics(|dynamics| DynamicsProperty::Width.next_input(dynamics)); },
+            ("NextDynamicsInputOpacity", _) => { self.update_dynamics(|dynamics| DynamicsProperty::Opacity.next_input(dynamics)); },
+            ("NextDynamicsInputColor", _)   => { self.update_dynamics(|dynamics| DynamicsProperty::Color.next_input(dynamics)); },
+            ("NextDynamicsCurveWidth", _)   => { self.update_dynamics(|dynamics| DynamicsProperty::Width.next_curve(dynamics)); },
+            ("NextDynamicsCurveOpacity", _) => { self.update_dynamics(|dynamics| DynamicsProperty::Opacity.next_curve(dynamics)); },
+            ("NextDynamicsCurveColor", _)   => { self.update_dynamics(|dynamics| DynamicsProperty::Color.next_curve(dynamics)); },
+
             ("ShowBrushPropertiesPopup", _) => {
                 // User has clicked the brush properties icon
                 self.brush_panel_open.set(true);
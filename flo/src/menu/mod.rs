@@ -5,6 +5,8 @@ mod eraser;
 mod flood_fill;
 mod select;
 mod adjust;
+mod shape;
+mod text;
 
 pub use self::empty::*;
 pub use self::ink::*;
@@ -12,3 +14,5 @@ pub use self::eraser::*;
 pub use self::flood_fill::*;
 pub use self::select::*;
 pub use self::adjust::*;
+pub use self::shape::*;
+pub use self::text::*;
@@ -1,5 +1,6 @@
 use super::controls;
 use super::super::model::*;
+use super::super::clipboard::*;
 use super::super::standard_tools::*;
 
 use flo_ui::*;
@@ -31,6 +32,9 @@ pub struct SelectMenuController<Anim: Animation> {
     /// The timeline model for the animation
     timeline: TimelineModel<Anim>,
 
+    /// The model that the clipboard actions copy from and paste into
+    flo_model: FloModel<Anim>,
+
     // The UI for this control
     ui: BindRef<Control>
 }
@@ -46,6 +50,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         let selected            = flo_model.selection().selected_elements.clone();
         let selection_in_order  = flo_model.selection().selection_in_order.clone();
         let timeline            = flo_model.timeline().clone();
+        let model               = flo_model.clone();
 
         SelectMenuController {
             ui:                 ui,
@@ -53,7 +58,8 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
             images:             Arc::new(images),
             selected:           selected,
             selection_in_order: selection_in_order,
-            timeline:           timeline
+            timeline:           timeline,
+            flo_model:          model
         }
     }
 
@@ -83,6 +89,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         let path_add        = images.register(svg_static(include_bytes!("../../svg/selection_controls/add.svg")));
         let path_subtract   = images.register(svg_static(include_bytes!("../../svg/selection_controls/subtract.svg")));
         let path_intersect  = images.register(svg_static(include_bytes!("../../svg/selection_controls/intersect.svg")));
+        let path_exclude    = images.register(svg_static(include_bytes!("../../svg/selection_controls/exclude.svg")));
 
         images.assign_name(&order_to_back, "OrderToBack");
         images.assign_name(&order_behind, "OrderBehind");
@@ -104,6 +111,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         images.assign_name(&path_add, "PathAdd");
         images.assign_name(&path_subtract, "PathSubtract");
         images.assign_name(&path_intersect, "PathIntersect");
+        images.assign_name(&path_exclude, "PathExclude");
 
         images
     }
@@ -133,6 +141,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         let path_add            = images.get_named_resource("PathAdd");
         let path_subtract       = images.get_named_resource("PathSubtract");
         let path_intersect      = images.get_named_resource("PathIntersect");
+        let path_exclude        = images.get_named_resource("PathExclude");
 
         // Parts of the model
         let anything_selected   = tool_model.anything_selected.clone();
@@ -312,7 +321,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                             .with(Hint::Class("button-group".to_string()))
                             .with(ControlAttribute::Padding((0,2), (0,2)))
                             .with(Font::Size(9.0))
-                            .with(Bounds::next_horiz(22.0*2.0 + 28.0*2.0))
+                            .with(Bounds::next_horiz(22.0*3.0 + 28.0*2.0))
                             .with(vec![
                                 Control::button()
                                     .with(vec![Control::empty().with(group.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
@@ -336,6 +345,12 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                                     .with(vec![Control::empty().with(path_intersect.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
                                     .with(Font::Size(10.0))
                                     .with((ActionTrigger::Click, "PathIntersect"))
+                                    .with(Bounds::next_horiz(22.0))
+                                    .with(ControlAttribute::Padding((0, 0), (0, 2))),
+                                Control::button()
+                                    .with(vec![Control::empty().with(path_exclude.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with(Font::Size(10.0))
+                                    .with((ActionTrigger::Click, "PathExclude"))
                                     .with(Bounds::next_horiz(28.0))
                                     .with(ControlAttribute::Padding((0, 0), (6, 2)))
                             ])
@@ -344,8 +359,38 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                     vec![]
                 };
 
+                let clipboard_controls = vec![
+                    controls::divider(),
+
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Font::Size(9.0))
+                        .with(Bounds::next_horiz(40.0*3.0))
+                        .with(vec![
+                            Control::button()
+                                .with(vec![Control::label().with("Cut").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(Font::Size(11.0))
+                                .with(State::Enabled(Property::Bool(anything_selected)))
+                                .with((ActionTrigger::Click, "Cut"))
+                                .with(Bounds::next_horiz(40.0)),
+                            Control::button()
+                                .with(vec![Control::label().with("Copy").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(Font::Size(11.0))
+                                .with(State::Enabled(Property::Bool(anything_selected)))
+                                .with((ActionTrigger::Click, "Copy"))
+                                .with(Bounds::next_horiz(40.0)),
+                            Control::button()
+                                .with(vec![Control::label().with("Paste").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(Font::Size(11.0))
+                                .with((ActionTrigger::Click, "Paste"))
+                                .with(Bounds::next_horiz(40.0))
+                        ])
+                ];
+
                 // Extra controls to display when there's a selection to edit
-                let selection_controls = order_controls.into_iter()
+                let selection_controls = clipboard_controls.into_iter()
+                    .chain(order_controls)
                     .chain(align_controls)
                     .chain(flip_controls)
                     .chain(group_controls);
@@ -473,6 +518,32 @@ impl<Anim: 'static+EditableAnimation+Animation> Controller for SelectMenuControl
                 self.timeline.invalidate_canvas();
             }
 
+            // Combining paths
+            "PathAdd" | "PathSubtract" | "PathIntersect" | "PathExclude" => {
+                let selection   = self.selection_in_order.get();
+                let operation   = match action_id {
+                    "PathAdd"       => PathOperation::Union,
+                    "PathSubtract"  => PathOperation::Subtract,
+                    "PathIntersect" => PathOperation::Intersect,
+                    "PathExclude"   => PathOperation::Exclude,
+                    _               => PathOperation::Union
+                };
+
+                // The selected elements are replaced by the combined path
+                let _           = self.edit.future(move |animation| {
+                    animation.publish(Arc::new(vec![AnimationEdit::Element(selection.iter().cloned().collect(),
+                        ElementEdit::CombinePaths(ElementId::Unassigned, operation))]))
+                });
+                self.edit.sync(|_| { });
+                self.flo_model.selection().clear_selection();
+                self.timeline.invalidate_canvas();
+            }
+
+            // Clipboard
+            "Cut"   => { cut_selection(&self.flo_model); }
+            "Copy"  => { copy_selection(&self.flo_model); }
+            "Paste" => { paste_clipboard(&self.flo_model); }
+
             _ => { }
         }
     }
@@ -0,0 +1,275 @@
+use super::controls;
+use super::ink::*;
+use super::super::color::*;
+use super::super::standard_tools::*;
+
+use flo_ui::*;
+use flo_binding::*;
+
+use std::sync::*;
+
+///
+/// Menu controller used for the shape tools
+///
+pub struct ShapeMenuController {
+    stroke_width:       Binding<f32>,
+    opacity:            Binding<f32>,
+    fill_mode:          Binding<ShapeFillMode>,
+    num_sides:          Binding<u32>,
+
+    canvases:           Arc<ResourceManager<BindingCanvas>>,
+    ui:                 BindRef<Control>,
+    view_model:         Arc<DynamicViewModel>,
+
+    fill_picker_open:   Binding<bool>,
+    fill_picker:        Arc<PopupController<ColorPickerController>>,
+    stroke_picker_open: Binding<bool>,
+    stroke_picker:      Arc<PopupController<ColorPickerController>>
+}
+
+impl ShapeMenuController {
+    ///
+    /// Creates a new shape menu controller
+    ///
+    pub fn new(shape_type: ShapeType, model: &ShapeModel) -> ShapeMenuController {
+        // Create the colour pickers
+        let fill_picker_open        = bind(false);
+        let fill_picker             = ColorPickerController::new(&model.fill_color);
+        let fill_picker             = PopupController::new(fill_picker, &fill_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+
+        let stroke_picker_open      = bind(false);
+        let stroke_picker           = ColorPickerController::new(&model.stroke_color);
+        let stroke_picker           = PopupController::new(stroke_picker, &stroke_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+
+        // Create the viewmodel
+        let vm_stroke_width         = model.stroke_width.clone();
+        let vm_opacity              = model.opacity.clone();
+        let vm_num_sides            = model.num_sides.clone();
+        let vm_fill_picker_open     = fill_picker_open.clone();
+        let vm_stroke_picker_open   = stroke_picker_open.clone();
+        let view_model              = Arc::new(DynamicViewModel::new());
+
+        view_model.set_computed("StrokeWidth", move || PropertyValue::Float(vm_stroke_width.get() as f64));
+        view_model.set_computed("Opacity", move || PropertyValue::Float(vm_opacity.get() as f64));
+        view_model.set_computed("Sides", move || PropertyValue::Float(vm_num_sides.get() as f64));
+        view_model.set_computed("FillPickerOpen", move || PropertyValue::Bool(vm_fill_picker_open.get()));
+        view_model.set_computed("StrokePickerOpen", move || PropertyValue::Bool(vm_stroke_picker_open.get()));
+
+        // Create the canvases
+        let canvases                = Arc::new(ResourceManager::new());
+        let fill_preview            = canvases.register(InkMenuController::colour_preview(&model.fill_color));
+        let stroke_preview          = canvases.register(InkMenuController::colour_preview(&model.stroke_color));
+
+        // Build the UI
+        let ui = Self::ui(shape_type, model.fill_mode.clone(), model.num_sides.clone(), fill_preview, stroke_preview);
+
+        ShapeMenuController {
+            stroke_width:       model.stroke_width.clone(),
+            opacity:            model.opacity.clone(),
+            fill_mode:          model.fill_mode.clone(),
+            num_sides:          model.num_sides.clone(),
+
+            canvases:           canvases,
+            ui:                 ui,
+            view_model:         view_model,
+
+            fill_picker_open:   fill_picker_open,
+            fill_picker:        Arc::new(fill_picker),
+            stroke_picker_open: stroke_picker_open,
+            stroke_picker:      Arc::new(stroke_picker)
+        }
+    }
+
+    ///
+    /// Creates the UI for this menu
+    ///
+    fn ui(shape_type: ShapeType, fill_mode: Binding<ShapeFillMode>, num_sides: Binding<u32>, fill_preview: Resource<BindingCanvas>, stroke_preview: Resource<BindingCanvas>) -> BindRef<Control> {
+        let ui = computed(move || {
+            // Lines are always drawn as an outline
+            let fill_mode   = if shape_type == ShapeType::Line { ShapeFillMode::StrokeOnly } else { fill_mode.get() };
+            let num_sides   = num_sides.get();
+
+            let mut controls = vec![
+                controls::divider(),
+
+                Control::label()
+                    .with(format!("{}:", shape_type.name()))
+                    .with(FontWeight::Light)
+                    .with(TextAlign::Right)
+                    .with(Font::Size(14.0))
+                    .with(Bounds::next_horiz(72.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(8.0))
+            ];
+
+            // Lines can only be drawn as outlines so they don't get the fill mode option
+            if shape_type != ShapeType::Line {
+                let fill_mode_text = match fill_mode {
+                    ShapeFillMode::FillAndStroke    => "Fill and outline",
+                    ShapeFillMode::FillOnly         => "Fill only",
+                    ShapeFillMode::StrokeOnly       => "Outline only"
+                };
+
+                controls.extend(vec![
+                    Control::button()
+                        .with(vec![Control::label().with(fill_mode_text).with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Font::Size(11.0))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::Click, "NextFillMode")),
+
+                    controls::divider()
+                ]);
+            }
+
+            if fill_mode != ShapeFillMode::StrokeOnly {
+                controls.extend(vec![
+                    Control::label()
+                        .with("Fill:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(28.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(fill_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("FillPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowFillColorPopup"))
+                        .with_controller("FillColorPopup"),
+
+                    controls::divider()
+                ]);
+            }
+
+            if fill_mode != ShapeFillMode::FillOnly {
+                controls.extend(vec![
+                    Control::label()
+                        .with("Outline:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(52.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(stroke_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("StrokePickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowStrokeColorPopup"))
+                        .with_controller("StrokeColorPopup"),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 50.0.to_property())))
+                        .with(State::Value(Property::Bind("StrokeWidth".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeStrokeWidth".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeStrokeWidth".to_string())),
+
+                    controls::divider()
+                ]);
+            }
+
+            controls.extend(vec![
+                Control::label()
+                    .with("Opacity:")
+                    .with(TextAlign::Right)
+                    .with(Bounds::next_horiz(56.0)),
+                Control::empty().with(Bounds::next_horiz(6.0)),
+                Control::slider()
+                    .with(State::Range((0.0.to_property(), 1.0.to_property())))
+                    .with(State::Value(Property::Bind("Opacity".to_string())))
+                    .with(Bounds::next_horiz(96.0))
+                    .with((ActionTrigger::EditValue, "ChangeOpacity".to_string()))
+                    .with((ActionTrigger::SetValue, "ChangeOpacity".to_string()))
+            ]);
+
+            // Polygons can have their number of sides changed
+            if shape_type == ShapeType::Polygon {
+                controls.extend(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with(format!("Sides: {}", num_sides))
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(56.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((3.0.to_property(), 12.0.to_property())))
+                        .with(State::Value(Property::Bind("Sides".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeSides".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeSides".to_string()))
+                ]);
+            }
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                .with(controls)
+        });
+
+        BindRef::from(ui)
+    }
+}
+
+impl Controller for ShapeMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "FillColorPopup"    => Some(self.fill_picker.clone()),
+            "StrokeColorPopup"  => Some(self.stroke_picker.clone()),
+            _                   => None
+        }
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(self.canvases.clone())
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        match (action_id, action_parameter) {
+            ("ChangeStrokeWidth", &Value(PropertyValue::Float(new_width))) => {
+                // User has dragged the stroke width slider
+                self.stroke_width.set(new_width as f32);
+            },
+
+            ("ChangeOpacity", &Value(PropertyValue::Float(new_opacity))) => {
+                // User has dragged the opacity slider
+                self.opacity.set(new_opacity as f32);
+            },
+
+            ("ChangeSides", &Value(PropertyValue::Float(new_sides))) => {
+                // Polygons have a whole number of sides
+                self.num_sides.set(new_sides.round().max(3.0) as u32);
+            },
+
+            ("NextFillMode", _) => {
+                self.fill_mode.set(match self.fill_mode.get() {
+                    ShapeFillMode::FillAndStroke    => ShapeFillMode::FillOnly,
+                    ShapeFillMode::FillOnly         => ShapeFillMode::StrokeOnly,
+                    ShapeFillMode::StrokeOnly       => ShapeFillMode::FillAndStroke
+                });
+            },
+
+            ("ShowFillColorPopup", _) => {
+                // User has clicked the fill colour icon
+                self.fill_picker_open.set(true)
+            },
+
+            ("ShowStrokeColorPopup", _) => {
+                // User has clicked the outline colour icon
+                self.stroke_picker_open.set(true)
+            },
+
+            _ => ()
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use super::controls;
+use super::ink::*;
+use super::super::color::*;
+use super::super::standard_tools::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use std::sync::*;
+
+///
+/// Menu controller used for the text tool
+///
+pub struct TextMenuController {
+    text:               Binding<String>,
+    font:               Binding<TextFont>,
+    size:               Binding<f32>,
+    alignment:          Binding<TextAlignment>,
+
+    canvases:           Arc<ResourceManager<BindingCanvas>>,
+    ui:                 BindRef<Control>,
+    view_model:         Arc<DynamicViewModel>,
+
+    color_picker_open:  Binding<bool>,
+    color_picker:       Arc<PopupController<ColorPickerController>>
+}
+
+impl TextMenuController {
+    ///
+    /// Creates a new text menu controller
+    ///
+    pub fn new(model: &TextModel) -> TextMenuController {
+        // Create the colour picker
+        let color_picker_open       = bind(false);
+        let color_picker            = ColorPickerController::new(&model.color);
+        let color_picker            = PopupController::new(color_picker, &color_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+
+        // Create the viewmodel
+        let vm_size                 = model.size.clone();
+        let vm_color_picker_open    = color_picker_open.clone();
+        let view_model              = Arc::new(DynamicViewModel::new());
+
+        view_model.set_computed("Size", move || PropertyValue::Float(vm_size.get() as f64));
+        view_model.set_computed("ColorPickerOpen", move || PropertyValue::Bool(vm_color_picker_open.get()));
+
+        // Create the canvases
+        let canvases                = Arc::new(ResourceManager::new());
+        let color_preview           = canvases.register(InkMenuController::colour_preview(&model.color));
+
+        // Build the UI
+        let ui = Self::ui(model, color_preview);
+
+        TextMenuController {
+            text:               model.text.clone(),
+            font:               model.font.clone(),
+            size:               model.size.clone(),
+            alignment:          model.alignment.clone(),
+
+            canvases:           canvases,
+            ui:                 ui,
+            view_model:         view_model,
+
+            color_picker_open:  color_picker_open,
+            color_picker:       Arc::new(color_picker)
+        }
+    }
+
+    ///
+    /// Creates the UI for this menu
+    ///
+    fn ui(model: &TextModel, color_preview: Resource<BindingCanvas>) -> BindRef<Control> {
+        let text        = model.text.clone();
+        let font        = model.font.clone();
+        let size        = model.size.clone();
+        let alignment   = model.alignment.clone();
+
+        let ui = computed(move || {
+            let text            = text.get();
+            let font            = font.get();
+            let size            = size.get();
+            let alignment_text  = match alignment.get() {
+                TextAlignment::Left     => "Left",
+                TextAlignment::Center   => "Center",
+                TextAlignment::Right    => "Right"
+            };
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                .with(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Text:")
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(14.0))
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(8.0)),
+                    Control::text_box()
+                        .with(text)
+                        .with(Bounds::next_horiz(160.0))
+                        .with((ActionTrigger::SetValue, "SetText")),
+
+                    controls::divider(),
+
+                    Control::button()
+                        .with(vec![Control::label().with(font.name()).with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Font::Size(11.0))
+                        .with(Bounds::next_horiz(72.0))
+                        .with((ActionTrigger::Click, "NextFont")),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with(alignment_text).with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Font::Size(11.0))
+                        .with(Bounds::next_horiz(56.0))
+                        .with((ActionTrigger::Click, "NextAlignment")),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with(format!("Size: {}", size.round()))
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(56.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((6.0.to_property(), 144.0.to_property())))
+                        .with(State::Value(Property::Bind("Size".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeSize".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeSize".to_string())),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Colour:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(48.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(color_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("ColorPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowColorPopup"))
+                        .with_controller("ColorPopup")
+                ])
+        });
+
+        BindRef::from(ui)
+    }
+}
+
+impl Controller for TextMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "ColorPopup"    => Some(self.color_picker.clone()),
+            _               => None
+        }
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(self.canvases.clone())
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        match (action_id, action_parameter) {
+            ("SetText", &Value(PropertyValue::String(ref new_text))) => {
+                // User has finished typing some new text
+                self.text.set(new_text.clone());
+            },
+
+            ("ChangeSize", &Value(PropertyValue::Float(new_size))) => {
+                // User has dragged the size slider
+                self.size.set(new_size as f32);
+            },
+
+            ("NextFont", _) => {
+                // Cycle through the available fonts
+                let fonts       = TextFont::all();
+                let current     = fonts.iter().position(|font| *font == self.font.get()).unwrap_or(0);
+
+                self.font.set(fonts[(current + 1) % fonts.len()]);
+            },
+
+            ("NextAlignment", _) => {
+                self.alignment.set(match self.alignment.get() {
+                    TextAlignment::Left     => TextAlignment::Center,
+                    TextAlignment::Center   => TextAlignment::Right,
+                    TextAlignment::Right    => TextAlignment::Left
+                });
+            },
+
+            ("ShowColorPopup", _) => {
+                // User has clicked the colour icon
+                self.color_picker_open.set(true)
+            },
+
+            _ => ()
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use super::timeline::*;
+
+use flo_stream::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// The model for the review annotations that have been left on an animation
+///
+#[derive(Clone)]
+pub struct AnnotationModel {
+    /// The annotations in the animation, along with their IDs
+    pub annotations: BindRef<Vec<(ElementId, Annotation)>>,
+
+    /// The annotations attached to the selected layer at the current frame
+    pub frame_annotations: BindRef<Vec<(ElementId, Annotation)>>,
+
+    /// True if the annotation markers should be drawn on the canvas
+    pub show_annotations: Binding<bool>,
+
+    /// True if resolved annotations should be listed and drawn alongside the unresolved ones
+    pub show_resolved: Binding<bool>,
+
+    /// The name recorded as the author of any new annotations left by this session
+    pub author: Binding<String>
+}
+
+impl AnnotationModel {
+    ///
+    /// Creates a new annotation model that follows the edits made to an animation
+    ///
+    pub fn new<Anim: 'static+Animation>(animation: Arc<Anim>, edits: Subscriber<Arc<Vec<AnimationEdit>>>, timeline: &TimelineModel<Anim>, author: &str) -> AnnotationModel {
+        let show_annotations    = bind(true);
+        let show_resolved       = bind(false);
+        let author              = bind(author.to_string());
+        let annotations         = Self::annotations(animation, edits);
+        let frame_annotations   = Self::frame_annotations(annotations.clone(), timeline, BindRef::from(&show_resolved));
+
+        AnnotationModel {
+            annotations:        annotations,
+            frame_annotations:  frame_annotations,
+            show_annotations:   show_annotations,
+            show_resolved:      show_resolved,
+            author:             author
+        }
+    }
+
+    ///
+    /// True if an edit can be performed by a session that is reviewing the animation
+    ///
+    /// Reviewers can leave new annotations under their own name, but can't change or remove the annotations that are
+    /// already there.
+    ///
+    pub fn reviewer_can_perform(&self, edit: &AnimationEdit) -> bool {
+        match edit {
+            AnimationEdit::Annotation(ElementId::Unassigned, AnnotationEdit::Create(annotation))    => annotation.author == self.author.get(),
+            _                                                                                       => false
+        }
+    }
+
+    ///
+    /// True if an edit can change the annotations in an animation
+    ///
+    /// Removing a layer removes its annotations, and undo and redo can change anything
+    ///
+    fn is_annotation_update(edit: &AnimationEdit) -> bool {
+        match edit {
+            AnimationEdit::Annotation(_, _)             |
+            AnimationEdit::RemoveLayer(_)               |
+            AnimationEdit::Undo | AnimationEdit::Redo   => true,
+            _                                           => false
+        }
+    }
+
+    ///
+    /// Returns a binding of the annotations in an animation, which is reloaded whenever they're edited
+    ///
+    fn annotations<Anim: 'static+Animation>(animation: Arc<Anim>, edits: Subscriber<Arc<Vec<AnimationEdit>>>) -> BindRef<Vec<(ElementId, Annotation)>> {
+        let annotation_updates  = edits.filter(|edits| future::ready(edits.iter().any(|edit| Self::is_annotation_update(edit))));
+        let initial_annotations = animation.get_annotations();
+
+        let annotations         = bind_stream(annotation_updates, initial_annotations, move |_, _| {
+            animation.get_annotations()
+        });
+
+        BindRef::from(annotations)
+    }
+
+    ///
+    /// Returns a binding of the annotations attached to the selected layer at the current frame
+    ///
+    fn frame_annotations<Anim: 'static+Animation>(annotations: BindRef<Vec<(ElementId, Annotation)>>, timeline: &TimelineModel<Anim>, show_resolved: BindRef<bool>) -> BindRef<Vec<(ElementId, Annotation)>> {
+        let current_time    = timeline.current_time.clone();
+        let frame_duration  = timeline.frame_duration.clone();
+        let selected_layer  = timeline.selected_layer.clone();
+
+        BindRef::from(computed(move || {
+            let selected_layer  = match selected_layer.get() {
+                Some(layer_id)  => layer_id,
+                None            => { return vec![]; }
+            };
+            let frame_duration  = frame_duration.get();
+            let current_frame   = frame_number(current_time.get(), frame_duration);
+            let show_resolved   = show_resolved.get();
+
+            annotations.get()
+                .into_iter()
+                .filter(|(_, annotation)| annotation.layer_id == selected_layer)
+                .filter(|(_, annotation)| frame_number(annotation.when, frame_duration) == current_frame)
+                .filter(|(_, annotation)| show_resolved || !annotation.resolved)
+                .collect()
+        }))
+    }
+}
+
+///
+/// Returns the number of the frame that contains the specified time
+///
+fn frame_number(when: Duration, frame_duration: Duration) -> u128 {
+    if frame_duration.as_nanos() == 0 {
+        when.as_nanos()
+    } else {
+        when.as_nanos() / frame_duration.as_nanos()
+    }
+}
@@ -0,0 +1,290 @@
+use super::timeline::*;
+
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// The colours used to indicate the users editing an animation (assigned in the order that users join)
+///
+const PRESENCE_COLORS: [Color; 6] = [
+    Color::Rgba(0.9, 0.3, 0.2, 1.0),
+    Color::Rgba(0.2, 0.6, 0.9, 1.0),
+    Color::Rgba(0.3, 0.8, 0.3, 1.0),
+    Color::Rgba(0.9, 0.7, 0.1, 1.0),
+    Color::Rgba(0.7, 0.3, 0.9, 1.0),
+    Color::Rgba(0.1, 0.8, 0.8, 1.0)
+];
+
+///
+/// Describes where a user editing a shared animation is working
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct UserPresence {
+    /// The ID of the session this user is editing from
+    pub session_id: u64,
+
+    /// The name displayed for this user
+    pub name: String,
+
+    /// The colour used to indicate this user
+    pub color: Color,
+
+    /// The time of the frame that this user is editing
+    pub time: Duration,
+
+    /// The layer that this user has selected
+    pub layer: Option<u64>,
+
+    /// The last place on the canvas where this user painted
+    pub pointer: Option<(f32, f32)>
+}
+
+///
+/// Core data for the shared sessions object
+///
+struct SharedSessionsCore {
+    /// The ID to assign to the next session that joins
+    next_session_id: u64,
+
+    /// The presence bindings for each session that is editing the animation
+    sessions: Vec<(u64, BindRef<UserPresence>)>
+}
+
+///
+/// Tracks the sessions that are editing a single shared animation
+///
+pub struct SharedSessions {
+    /// The sessions that have joined
+    core: Mutex<SharedSessionsCore>,
+
+    /// Updated whenever a session joins or leaves
+    version: Binding<u64>
+}
+
+impl SharedSessions {
+    ///
+    /// Creates a new set of shared sessions with no members
+    ///
+    pub fn new() -> SharedSessions {
+        SharedSessions {
+            core:       Mutex::new(SharedSessionsCore { next_session_id: 0, sessions: vec![] }),
+            version:    bind(0)
+        }
+    }
+
+    ///
+    /// Adds a new session, with a function that generates the presence binding for the new session ID, returning the ID of the session
+    ///
+    fn join<MakePresence: FnOnce(u64) -> BindRef<UserPresence>>(&self, make_presence: MakePresence) -> u64 {
+        let session_id = {
+            let mut core    = self.core.lock().unwrap();
+            let session_id  = core.next_session_id;
+            core.next_session_id += 1;
+
+            let presence    = make_presence(session_id);
+            core.sessions.push((session_id, presence));
+
+            session_id
+        };
+
+        self.version.set(self.version.get()+1);
+        session_id
+    }
+
+    ///
+    /// Removes a session
+    ///
+    fn leave(&self, session_id: u64) {
+        self.core.lock().unwrap().sessions.retain(|(id, _)| *id != session_id);
+        self.version.set(self.version.get()+1);
+    }
+
+    ///
+    /// Returns the number of sessions that are editing the animation
+    ///
+    pub fn num_sessions(&self) -> usize {
+        self.version.get();
+        self.core.lock().unwrap().sessions.len()
+    }
+
+    ///
+    /// Returns where each of the sessions editing the animation are
+    ///
+    pub fn users(&self) -> Vec<UserPresence> {
+        // Reading the version means that computed bindings are updated when sessions join or leave
+        self.version.get();
+
+        let sessions = self.core.lock().unwrap().sessions.iter()
+            .map(|(_, presence)| presence.clone())
+            .collect::<Vec<_>>();
+
+        sessions.into_iter()
+            .map(|presence| presence.get())
+            .collect()
+    }
+}
+
+///
+/// Removes a session from the shared sessions when it's dropped
+///
+struct SessionMembership {
+    /// The ID of this session
+    session_id: u64,
+
+    /// The sessions this is a member of
+    sessions: Arc<SharedSessions>
+}
+
+impl Drop for SessionMembership {
+    fn drop(&mut self) {
+        self.sessions.leave(self.session_id);
+    }
+}
+
+///
+/// Model describing the other users that are editing the same animation as this session
+///
+/// Every session editing a file shares the same animation object: this serializes the edits (and the element IDs they
+/// assign) between sessions, and means that the edits made by any session are published to all of the others.
+///
+pub struct CollaborationModel {
+    /// The sessions sharing the animation
+    sessions: Arc<SharedSessions>,
+
+    /// The membership of this session (the session leaves when the last copy of the model is dropped)
+    _membership: Arc<SessionMembership>,
+
+    /// The ID of this session
+    pub session_id: u64,
+
+    /// The colour used to indicate this user to the other users
+    pub color: Color,
+
+    /// The last position on the canvas where this user painted
+    pub pointer: Binding<Option<(f32, f32)>>,
+
+    /// Where the other users editing this animation are working
+    pub other_users: BindRef<Vec<UserPresence>>
+}
+
+impl Clone for CollaborationModel {
+    fn clone(&self) -> CollaborationModel {
+        CollaborationModel {
+            sessions:       Arc::clone(&self.sessions),
+            _membership:    Arc::clone(&self._membership),
+            session_id:     self.session_id,
+            color:          self.color,
+            pointer:        Binding::clone(&self.pointer),
+            other_users:    BindRef::clone(&self.other_users)
+        }
+    }
+}
+
+impl CollaborationModel {
+    ///
+    /// Joins a set of shared sessions, publishing the location of the user in the specified timeline
+    ///
+    pub fn join<Anim: 'static+Animation>(sessions: &Arc<SharedSessions>, timeline: &TimelineModel<Anim>) -> CollaborationModel {
+        let pointer         = bind(None);
+
+        // The presence for this session is computed from its timeline
+        let current_time    = timeline.current_time.clone();
+        let selected_layer  = timeline.selected_layer.clone();
+        let presence_ptr    = pointer.clone();
+        let session_id      = sessions.join(move |session_id| {
+            let name    = format!("User {}", session_id+1);
+            let color   = PRESENCE_COLORS[(session_id as usize) % PRESENCE_COLORS.len()];
+
+            BindRef::from(computed(move || {
+                UserPresence {
+                    session_id: session_id,
+                    name:       name.clone(),
+                    color:      color,
+                    time:       current_time.get(),
+                    layer:      selected_layer.get(),
+                    pointer:    presence_ptr.get()
+                }
+            }))
+        });
+
+        // The other users are everyone apart from this session
+        let all_sessions    = Arc::clone(sessions);
+        let other_users     = computed(move || {
+            all_sessions.users()
+                .into_iter()
+                .filter(|user| user.session_id != session_id)
+                .collect::<Vec<_>>()
+        });
+
+        CollaborationModel {
+            sessions:       Arc::clone(sessions),
+            _membership:    Arc::new(SessionMembership { session_id: session_id, sessions: Arc::clone(sessions) }),
+            session_id:     session_id,
+            color:          PRESENCE_COLORS[(session_id as usize) % PRESENCE_COLORS.len()],
+            pointer:        pointer,
+            other_users:    BindRef::from(other_users)
+        }
+    }
+
+    ///
+    /// True if other sessions are editing the same animation as this one
+    ///
+    pub fn is_shared(&self) -> bool {
+        self.sessions.num_sessions() > 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flo_animation::storage::*;
+    use futures::prelude::*;
+
+    #[test]
+    fn sessions_see_each_other() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+
+        let timeline1       = TimelineModel::new(Arc::clone(&animation), animation.edit().subscribe());
+        let timeline2       = TimelineModel::new(Arc::clone(&animation), animation.edit().subscribe());
+        let user1           = CollaborationModel::join(&sessions, &timeline1);
+        let user2           = CollaborationModel::join(&sessions, &timeline2);
+
+        assert!(user1.session_id != user2.session_id);
+        assert!(user1.is_shared());
+        assert!(user1.other_users.get().len() == 1);
+        assert!(user1.other_users.get()[0].session_id == user2.session_id);
+
+        // Moving the second user is seen by the first user
+        timeline2.current_time.set(Duration::from_millis(500));
+        user2.pointer.set(Some((100.0, 200.0)));
+
+        assert!(user1.other_users.get()[0].time == Duration::from_millis(500));
+        assert!(user1.other_users.get()[0].pointer == Some((100.0, 200.0)));
+    }
+
+    #[test]
+    fn session_leaves_when_dropped() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+
+        let timeline1       = TimelineModel::new(Arc::clone(&animation), animation.edit().subscribe());
+        let timeline2       = TimelineModel::new(Arc::clone(&animation), animation.edit().subscribe());
+        let user1           = CollaborationModel::join(&sessions, &timeline1);
+        let user2           = CollaborationModel::join(&sessions, &timeline2);
+        let user2_copy      = user2.clone();
+
+        drop(user2);
+        assert!(user1.other_users.get().len() == 1);
+
+        drop(user2_copy);
+        assert!(user1.other_users.get().len() == 0);
+        assert!(!user1.is_shared());
+    }
+}
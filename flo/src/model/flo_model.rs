@@ -3,6 +3,8 @@ use super::frame::*;
 use super::timeline::*;
 use super::selection::*;
 use super::onion_skin::*;
+use super::collaboration::*;
+use super::annotations::*;
 
 use flo_stream::*;
 use flo_binding::*;
@@ -11,6 +13,7 @@ use futures::*;
 use futures::stream::{BoxStream};
 use ::desync::*;
 
+use std::iter;
 use std::ops::Range;
 use std::time::Duration;
 use std::sync::*;
@@ -37,6 +40,12 @@ pub struct FloModel<Anim: Animation> {
     /// The onion skin model
     onion_skin: OnionSkinModel<Anim>,
 
+    /// The model describing the other sessions editing this animation
+    collaboration: CollaborationModel,
+
+    /// The model of the review annotations left on this animation
+    annotations: AnnotationModel,
+
     /// The size of the animation
     pub size: BindRef<(f64, f64)>,
 
@@ -47,7 +56,19 @@ pub struct FloModel<Anim: Animation> {
     frame_edit_counter: Binding<u64>,
 
     /// Publisher where we send edits to this stream
-    edit_publisher: Arc<Desync<Publisher<Arc<Vec<AnimationEdit>>>>>
+    edit_publisher: Arc<Desync<Publisher<Arc<Vec<AnimationEdit>>>>>,
+
+    /// Publisher for the edits made in this session (these are sent on to the animation in this session's undo history)
+    session_edits: Arc<Desync<Publisher<Arc<Vec<AnimationEdit>>>>>,
+
+    /// Publisher that sends the edits made in this session to the animation
+    shared_edits: Arc<Desync<Publisher<Arc<Vec<AnimationEdit>>>>>,
+
+    /// True if this model discards any edits made to it
+    read_only: bool,
+
+    /// True if new review annotations can be left through this model even if it's read-only
+    can_annotate: bool
 }
 
 impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
@@ -55,18 +76,41 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
     /// Creates a new model
     ///
     pub fn new(animation: Anim) -> FloModel<Anim> {
+        Self::with_shared_animation(Arc::new(animation), &Arc::new(SharedSessions::new()))
+    }
+
+    ///
+    /// Creates a new model for a session editing an animation that may be shared with other sessions
+    ///
+    pub fn with_shared_animation(animation: Arc<Anim>, sessions: &Arc<SharedSessions>) -> FloModel<Anim> {
         let mut edit_publisher  = animation.edit();
-        let animation           = Arc::new(animation);
         let tools               = ToolModel::new();
         let timeline            = TimelineModel::new(Arc::clone(&animation), edit_publisher.subscribe());
         let frame_edit_counter  = bind(0);
         let frame               = FrameModel::new(Arc::clone(&animation), edit_publisher.subscribe(), BindRef::new(&timeline.current_time), BindRef::new(&frame_edit_counter), BindRef::new(&timeline.selected_layer));
         let selection           = SelectionModel::new(&frame, &timeline);
         let onion_skin          = OnionSkinModel::new(Arc::clone(&animation), &timeline);
+        let collaboration       = CollaborationModel::join(sessions, &timeline);
+        let annotations         = AnnotationModel::new(Arc::clone(&animation), edit_publisher.subscribe(), &timeline, &format!("User {}", collaboration.session_id+1));
 
         let size_binding        = bind(animation.size());
         let edit_publisher      = Arc::new(Desync::new(edit_publisher));
 
+        // Each session has its own undo history, so edits made in this session are sent to the animation in that history
+        let mut session_edits   = Publisher::new(10);
+        let shared_edits        = Arc::new(Desync::new(animation.edit()));
+        let undo_history        = collaboration.session_id;
+
+        pipe_in(Arc::clone(&shared_edits), session_edits.subscribe(), move |shared_edits, edits| {
+            let edits = Self::in_undo_history(undo_history, (*edits).clone());
+
+            async move {
+                shared_edits.publish(Arc::new(edits)).await;
+            }.boxed()
+        });
+
+        let session_edits       = Arc::new(Desync::new(session_edits));
+
         let mut model           = FloModel {
             animation:          animation,
             tools:              tools,
@@ -75,11 +119,17 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
             frame:              frame,
             selection:          selection,
             onion_skin:         onion_skin,
+            collaboration:      collaboration,
+            annotations:        annotations,
 
             size:               BindRef::from(size_binding.clone()),
             size_binding:       size_binding,
 
-            edit_publisher:     edit_publisher
+            edit_publisher:     edit_publisher,
+            session_edits:      session_edits,
+            shared_edits:       shared_edits,
+            read_only:          false,
+            can_annotate:       true
         };
 
         model.subscribe_to_animation_edits();
@@ -98,18 +148,25 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
         let size_binding            = self.size_binding.clone();
         let timeline                = self.timeline.clone();
         let frame_edit_counter      = self.frame_edit_counter.clone();
+        let collaboration           = self.collaboration.clone();
 
         // Process edits for this subscription
         pipe_in(Arc::clone(&self.edit_publisher), subscription, move |_, edits| {
-            Self::process_edits(&*edits, &size_binding, &timeline, &frame_edit_counter);
+            let frame_changed = Self::process_edits(&*edits, &size_binding, &timeline, &frame_edit_counter);
+
+            // Edits made by other sessions need the canvas to be redrawn (our own tools invalidate the canvas when they make their edits)
+            if frame_changed && collaboration.is_shared() {
+                timeline.invalidate_canvas();
+            }
+
             future::ready(()).boxed()
         });
     }
 
     ///
-    /// Updates the model based on edits to the animation
+    /// Updates the model based on edits to the animation, returning true if the edits changed the frame
     ///
-    fn process_edits(edits: &Vec<AnimationEdit>, size_binding: &Binding<(f64, f64)>, timeline: &TimelineModel<Anim>, frame_edit_counter: &Binding<u64>) {
+    fn process_edits(edits: &Vec<AnimationEdit>, size_binding: &Binding<(f64, f64)>, timeline: &TimelineModel<Anim>, frame_edit_counter: &Binding<u64>) -> bool {
         use self::AnimationEdit::*;
         use self::LayerEdit::*;
 
@@ -123,6 +180,23 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
                     advance_edit_counter = true;
                 },
 
+                SetFrameLength(frame_length) => {
+                    timeline.frame_duration.set(*frame_length);
+                    advance_edit_counter = true;
+                },
+
+                SetDuration(duration) => {
+                    timeline.duration.set(*duration);
+                    advance_edit_counter = true;
+                },
+
+                // The annotation model reloads the annotations itself, and they're not part of the frame
+                Annotation(_, _)            => { },
+
+                // Images and audio clips don't change anything until they're placed on a keyframe or attached to a layer
+                AddImage(_, _)              |
+                AddAudioClip(_, _)          => { },
+
                 AddNewLayer(_)              |
                 RemoveLayer(_)              |
                 Element(_, _)               |
@@ -132,11 +206,27 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
                     advance_edit_counter = true;
                 }
 
-                Layer(_, AddKeyFrame(_))    |
-                Layer(_, RemoveKeyFrame(_)) => {
+                Layer(_, AddKeyFrame(_))                |
+                Layer(_, RemoveKeyFrame(_))             |
+                Layer(_, MoveKeyFrame(_, _))            |
+                Layer(_, DuplicateKeyFrame(_, _, _))    |
+                Layer(_, RetimeKeyFrames(_, _))         |
+                Layer(_, BakeInbetweens(_, _, _))       => {
+                    advance_edit_counter = true;
+                },
+
+                Layer(_, SetVisibility(_))      |
+                Layer(_, SetLocked(_))          |
+                Layer(_, SetOpacity(_))         |
+                Layer(_, SetBlendMode(_))       |
+                Layer(_, SetInbetweenMode(_))   => {
                     advance_edit_counter = true;
                 },
 
+                // The timeline reloads its layers to pick up the new audio clip
+                Layer(_, ImportAudio(_))    |
+                Layer(_, RemoveAudio)       => { },
+
                 Layer(layer_id, SetName(new_name)) => {
                     timeline.layers.get()
                         .iter()
@@ -147,6 +237,12 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
                 Layer(layer_id, SetOrdering(at_index)) => {
                     unimplemented!("Cannot update model with layer ordering yet")
                 }
+
+                SelectUndoHistory(_)        => { },
+
+                Undo | Redo                 => {
+                    advance_edit_counter = true;
+                }
             }
         }
 
@@ -154,6 +250,62 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
         if advance_edit_counter {
             frame_edit_counter.set(frame_edit_counter.get()+1);
         }
+
+        advance_edit_counter
+    }
+
+    ///
+    /// Returns a set of edits with the undo history they should be recorded in selected
+    ///
+    fn in_undo_history(undo_history: u64, edits: Vec<AnimationEdit>) -> Vec<AnimationEdit> {
+        if edits.len() == 0 {
+            return edits;
+        }
+
+        iter::once(AnimationEdit::SelectUndoHistory(undo_history))
+            .chain(edits)
+            .collect()
+    }
+
+    ///
+    /// Waits for any edits published in this session to be sent to the animation
+    ///
+    fn wait_for_session_edits(&self) {
+        let when_empty      = self.session_edits.sync(|publisher| publisher.republish()).when_empty();
+        let shared_edits    = Arc::clone(&self.shared_edits);
+
+        // Use a desync to wait for the edits to be published, as this might be called from within another future
+        let wait_for_edits  = Desync::new(());
+        let _               = wait_for_edits.future(move |_| async move {
+            when_empty.await;
+            shared_edits.future(|_| Box::pin(future::ready(()))).await.ok();
+        }.boxed());
+
+        wait_for_edits.sync(|_| { });
+    }
+
+    ///
+    /// Updates the model for a set of edits, then performs them on the animation using a function
+    ///
+    fn update_and_perform_edits<PerformFn: FnOnce(&Anim, Vec<AnimationEdit>)>(&self, edits: Vec<AnimationEdit>, perform: PerformFn) {
+        if edits.len() == 0 {
+            return;
+        }
+
+        let is_undo = edits.iter().any(|edit| match edit {
+            AnimationEdit::Undo | AnimationEdit::Redo   => true,
+            _                                           => false
+        });
+
+        Self::process_edits(&edits, &self.size_binding, &self.timeline, &self.frame_edit_counter);
+        perform(&*self.animation, edits);
+
+        // Undoing an edit can change anything, so reload the parts of the model that can't be updated from the edits alone
+        if is_undo {
+            self.size_binding.set(self.animation.size());
+            self.timeline.update_keyframe_bindings();
+            self.timeline.invalidate_canvas();
+        }
     }
 
     ///
@@ -161,11 +313,17 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
     ///
     pub fn when_complete(&self) -> impl Future<Output=()> {
         let edit_publisher  = self.edit_publisher.clone();
-        let when_empty      = self.animation.edit().when_empty();
+        let shared_edits    = self.shared_edits.clone();
+        let animation       = self.animation.clone();
+        let session_empty   = self.session_edits.sync(|publisher| publisher.republish()).when_empty();
 
         async move {
+            // Wait for the edits made in this session to be sent to the animation
+            session_empty.await;
+            shared_edits.future(|_| Box::pin(future::ready(()))).await.ok();
+
             // Wait for all of the pending edits to be published
-            when_empty.await;
+            animation.edit().when_empty().await;
 
             // Wait for the edit publisher to finish processing them
             edit_publisher.future(|_| Box::pin(future::ready(()))).await.ok();
@@ -174,6 +332,44 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
 }
 
 impl<Anim: Animation+'static> FloModel<Anim> {
+    ///
+    /// Converts this model into one that discards any edits made through it
+    ///
+    /// The animation can still be played back, and edits made by other sessions are still displayed. Review annotations
+    /// are displayed but can't be left or changed: use `into_reviewer()` for a session that can leave new annotations.
+    ///
+    pub fn into_read_only(mut self) -> FloModel<Anim> {
+        self.read_only      = true;
+        self.can_annotate   = false;
+        self
+    }
+
+    ///
+    /// Converts this model into one that discards any edits made through it except for new review annotations
+    ///
+    /// This works like `into_read_only()`, except that new annotations can be left on the animation. Existing annotations
+    /// still can't be changed.
+    ///
+    pub fn into_reviewer(mut self) -> FloModel<Anim> {
+        self.read_only      = true;
+        self.can_annotate   = true;
+        self
+    }
+
+    ///
+    /// True if this model discards any edits made through it (other than new annotations if it's a reviewer)
+    ///
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    ///
+    /// True if new review annotations can be left through this model
+    ///
+    pub fn can_annotate(&self) -> bool {
+        self.can_annotate
+    }
+
     ///
     /// Retrieves the model for the drawing tools for this animation
     ///
@@ -209,6 +405,20 @@ impl<Anim: Animation+'static> FloModel<Anim> {
         &self.onion_skin
     }
 
+    ///
+    /// Retrieves the model describing the other sessions that are editing this animation
+    ///
+    pub fn collaboration(&self) -> &CollaborationModel {
+        &self.collaboration
+    }
+
+    ///
+    /// Retrieves the model of the review annotations left on this animation
+    ///
+    pub fn annotations(&self) -> &AnnotationModel {
+        &self.annotations
+    }
+
     ///
     /// Retrieves the frame update binding for this animation
     ///
@@ -235,11 +445,17 @@ impl<Anim: Animation> Clone for FloModel<Anim> {
             frame:              self.frame.clone(),
             selection:          self.selection.clone(),
             onion_skin:         self.onion_skin.clone(),
+            collaboration:      self.collaboration.clone(),
+            annotations:        self.annotations.clone(),
 
             size:               self.size.clone(),
             size_binding:       self.size_binding.clone(),
 
-            edit_publisher:     self.edit_publisher.clone()
+            edit_publisher:     self.edit_publisher.clone(),
+            session_edits:      self.session_edits.clone(),
+            shared_edits:       self.shared_edits.clone(),
+            read_only:          self.read_only,
+            can_annotate:       self.can_annotate
         }
     }
 }
@@ -300,6 +516,13 @@ impl<Anim: Animation> Animation for FloModel<Anim> {
     fn motion<'a>(&'a self) -> &'a dyn AnimationMotion {
         self
     }
+
+    ///
+    /// Retrieves the review annotations that have been left on this animation, along with their IDs
+    ///
+    fn get_annotations(&self) -> Vec<(ElementId, Annotation)> {
+        self.animation.get_annotations()
+    }
 }
 
 impl<Anim: Animation> AnimationMotion for FloModel<Anim> {
@@ -326,19 +549,79 @@ impl<Anim: Animation> AnimationMotion for FloModel<Anim> {
 }
 
 impl<Anim: 'static+Animation+EditableAnimation> EditableAnimation for FloModel<Anim> {
+    ///
+    /// Performs a set of edits, recording them in the undo history for this session
+    ///
     fn perform_edits(&self, edits: Vec<AnimationEdit>) {
-        Self::process_edits(&edits, &self.size_binding, &self.timeline, &self.frame_edit_counter);
-        self.animation.perform_edits(edits);
+        // Read-only models can only be used to leave review annotations, and only if they're reviewers
+        let edits = match (self.read_only, self.can_annotate) {
+            (false, _)      => edits,
+            (true, false)   => vec![],
+            (true, true)    => {
+                edits.into_iter()
+                    .filter(|edit| self.annotations.reviewer_can_perform(edit))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // Edits published earlier in this session are performed first
+        self.wait_for_session_edits();
+
+        let edits = Self::in_undo_history(self.collaboration.session_id, edits);
+        self.update_and_perform_edits(edits, |animation, edits| animation.perform_edits(edits));
+    }
+
+    ///
+    /// Performs a set of edits made somewhere else without adding them to the undo history
+    ///
+    /// These are applied even if this model is read-only, as they aren't being made by this model's user.
+    ///
+    fn perform_edits_without_undo(&self, edits: Vec<AnimationEdit>) {
+        self.update_and_perform_edits(edits, |animation, edits| animation.perform_edits_without_undo(edits));
+    }
+
+    ///
+    /// True if there are any edits in an undo history that can be undone
+    ///
+    fn can_undo_in(&self, undo_history: u64) -> bool {
+        !self.read_only && self.animation.can_undo_in(undo_history)
+    }
+
+    ///
+    /// True if there are any edits in an undo history that can be redone
+    ///
+    fn can_redo_in(&self, undo_history: u64) -> bool {
+        !self.read_only && self.animation.can_redo_in(undo_history)
+    }
+
+    ///
+    /// True if there are any edits made in this session that can be undone
+    ///
+    fn can_undo(&self) -> bool {
+        self.can_undo_in(self.collaboration.session_id)
+    }
+
+    ///
+    /// True if there are any edits made in this session that can be redone
+    ///
+    fn can_redo(&self) -> bool {
+        self.can_redo_in(self.collaboration.session_id)
     }
 
     ///
     /// Retrieves a sink that can be used to send edits for this animation
     ///
     /// Edits are supplied as groups (stored in a vec) so that it's possible to ensure that
-    /// a set of related edits are performed atomically
+    /// a set of related edits are performed atomically. Each group is recorded in the undo
+    /// history for this session, so undo only reverses the edits made in this session.
     ///
     fn edit(&self) -> Publisher<Arc<Vec<AnimationEdit>>> {
-        self.edit_publisher.sync(|publisher| publisher.republish())
+        if self.read_only {
+            // Nothing subscribes to this publisher, so any edits sent to it are discarded
+            Publisher::new(1)
+        } else {
+            self.session_edits.sync(|publisher| publisher.republish())
+        }
     }
 
     ///
@@ -350,6 +633,13 @@ impl<Anim: 'static+Animation+EditableAnimation> EditableAnimation for FloModel<A
         self.animation.assign_element_id()
     }
 
+    ///
+    /// Restricts the element IDs assigned by this animation to the specified range
+    ///
+    fn set_element_id_range(&self, range: Range<i64>) {
+        self.animation.set_element_id_range(range)
+    }
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
@@ -386,4 +676,173 @@ mod test {
         assert!(model.size()        == (800.0, 600.0));
         assert!(model.size.get()    == (800.0, 600.0));
     }
+
+    #[test]
+    fn edits_are_shared_between_sessions() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+        let model1          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+        let model2          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+
+        // Edit the size in the first session
+        executor::block_on(async {
+            let mut edit_log = model1.edit();
+            edit_log.publish(Arc::new(vec![AnimationEdit::SetSize(800.0, 600.0)])).await;
+            edit_log.when_empty().await;
+            model1.when_complete().await;
+            model2.when_complete().await;
+        });
+
+        // Second session should see the edit
+        assert!(model2.size()       == (800.0, 600.0));
+        assert!(model2.size.get()   == (800.0, 600.0));
+
+        // Element IDs assigned by the two sessions should not conflict
+        let id1 = model1.assign_element_id();
+        let id2 = model2.assign_element_id();
+        assert!(id1 != id2);
+
+        // Each session should see the other
+        assert!(model1.collaboration().other_users.get().len() == 1);
+        assert!(model2.collaboration().other_users.get().len() == 1);
+    }
+
+    #[test]
+    fn sessions_undo_their_own_edits() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+        let model1          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+        let model2          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+
+        model1.perform_edits(vec![AnimationEdit::AddNewLayer(1)]);
+        model2.perform_edits(vec![AnimationEdit::AddNewLayer(2)]);
+        executor::block_on(async {
+            let mut edit_log = model1.edit();
+            edit_log.publish(Arc::new(vec![AnimationEdit::AddNewLayer(3)])).await;
+            edit_log.when_empty().await;
+            model1.when_complete().await;
+        });
+
+        assert!(model1.can_undo());
+        assert!(model2.can_undo());
+
+        // Undoing in the first session only reverses the edits made in that session, even though the second session edited the animation more recently
+        model1.undo();
+        model1.undo();
+
+        let mut layer_ids = animation.get_layer_ids();
+        layer_ids.sort();
+
+        assert!(layer_ids == vec![2]);
+        assert!(!model1.can_undo());
+        assert!(model1.can_redo());
+        assert!(model2.can_undo());
+        assert!(!model2.can_redo());
+
+        model2.undo();
+        assert!(animation.get_layer_ids().len() == 0);
+
+        model1.redo();
+        assert!(animation.get_layer_ids() == vec![1]);
+    }
+
+    #[test]
+    fn read_only_model_discards_edits() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+        let editor          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+        let viewer          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions).into_read_only();
+
+        assert!(viewer.is_read_only());
+        assert!(!viewer.can_annotate());
+        assert!(!editor.is_read_only());
+
+        // Edits made through the viewer are discarded, including new annotations
+        editor.perform_edits(vec![AnimationEdit::AddNewLayer(1)]);
+
+        let author = viewer.annotations().author.get();
+        viewer.perform_edits(vec![AnimationEdit::SetSize(800.0, 600.0)]);
+        viewer.perform_edits(vec![AnimationEdit::Annotation(ElementId::Unassigned, AnnotationEdit::Create(Annotation::new(1, Duration::from_millis(0), &author, 0, None, "Looks good")))]);
+        executor::block_on(async {
+            let mut edit_log = viewer.edit();
+            edit_log.publish(Arc::new(vec![AnimationEdit::AddNewLayer(2)])).await;
+            edit_log.when_empty().await;
+            viewer.when_complete().await;
+        });
+
+        assert!(viewer.size()       == (1920.0, 1080.0));
+        assert!(viewer.size.get()   == (1920.0, 1080.0));
+        assert!(animation.get_layer_ids() == vec![1]);
+        assert!(viewer.get_annotations().len() == 0);
+        assert!(!viewer.can_undo());
+
+        // Edits from other sessions are still displayed
+        executor::block_on(async {
+            let mut edit_log = editor.edit();
+            edit_log.publish(Arc::new(vec![AnimationEdit::SetSize(640.0, 480.0)])).await;
+            edit_log.when_empty().await;
+            editor.when_complete().await;
+            viewer.when_complete().await;
+        });
+
+        assert!(viewer.size.get()   == (640.0, 480.0));
+    }
+
+    #[test]
+    fn reviewer_model_can_leave_annotations() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+        let editor          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+        let viewer          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions).into_reviewer();
+
+        assert!(viewer.is_read_only());
+        assert!(viewer.can_annotate());
+
+        editor.perform_edits(vec![AnimationEdit::AddNewLayer(1)]);
+
+        // Annotations are kept, but the other edits in the same group are discarded
+        let author = viewer.annotations().author.get();
+        viewer.perform_edits(vec![
+            AnimationEdit::SetSize(800.0, 600.0),
+            AnimationEdit::Annotation(ElementId::Unassigned, AnnotationEdit::Create(Annotation::new(1, Duration::from_millis(0), &author, 0, None, "Looks good")))
+        ]);
+
+        let annotations = viewer.get_annotations();
+
+        assert!(viewer.size() == (1920.0, 1080.0));
+        assert!(annotations.len() == 1);
+        assert!(annotations[0].1.text == "Looks good".to_string());
+        assert!(editor.get_annotations() == annotations);
+    }
+
+    #[test]
+    fn reviewer_model_cannot_change_existing_annotations() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()));
+        let sessions        = Arc::new(SharedSessions::new());
+        let editor          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions);
+        let viewer          = FloModel::with_shared_animation(Arc::clone(&animation), &sessions).into_reviewer();
+
+        editor.perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Annotation(ElementId::Assigned(100), AnnotationEdit::Create(Annotation::new(1, Duration::from_millis(0), "Animator", 0, None, "Is this better?")))
+        ]);
+        let annotations = editor.get_annotations();
+
+        // The viewer can't resolve, delete or replace the note, or leave one under somebody else's name
+        let author = viewer.annotations().author.get();
+        viewer.perform_edits(vec![AnimationEdit::Annotation(ElementId::Assigned(100), AnnotationEdit::SetResolved(true))]);
+        viewer.perform_edits(vec![AnimationEdit::Annotation(ElementId::Assigned(100), AnnotationEdit::Delete)]);
+        viewer.perform_edits(vec![AnimationEdit::Annotation(ElementId::Assigned(100), AnnotationEdit::Create(Annotation::new(1, Duration::from_millis(0), &author, 0, None, "Replaced")))]);
+        viewer.perform_edits(vec![AnimationEdit::Annotation(ElementId::Unassigned, AnnotationEdit::Create(Annotation::new(1, Duration::from_millis(0), "Animator", 0, None, "Not really")))]);
+
+        assert!(annotations.len() == 1);
+        assert!(!annotations[0].1.resolved);
+        assert!(viewer.get_annotations() == annotations);
+        assert!(editor.get_annotations() == annotations);
+    }
 }
@@ -188,10 +188,17 @@ impl FrameModel {
     ///
     /// True if the animation edit affects the keyframes on the specified layer
     ///
+    /// Undo and redo can change any keyframe, so these are always treated as an update
+    ///
     fn is_key_frame_update(layer_id: u64, edit: &AnimationEdit) -> bool {
         match edit {
             AnimationEdit::Layer(edit_layer_id, LayerEdit::AddKeyFrame(_)) |
-            AnimationEdit::Layer(edit_layer_id, LayerEdit::RemoveKeyFrame(_)) => edit_layer_id == &layer_id,
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::RemoveKeyFrame(_)) |
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::MoveKeyFrame(_, _)) |
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::DuplicateKeyFrame(_, _, _)) |
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::RetimeKeyFrames(_, _)) |
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::BakeInbetweens(_, _, _)) => edit_layer_id == &layer_id,
+            AnimationEdit::Undo | AnimationEdit::Redo => true,
             _ => false
         }
     }
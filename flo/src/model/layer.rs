@@ -1,6 +1,8 @@
 use flo_binding::*;
 use flo_animation::*;
 
+use std::sync::*;
+
 ///
 /// Viewmodel for a layer
 ///
@@ -10,20 +12,29 @@ pub struct LayerModel {
     pub id: u64,
 
     /// The name of this layer
-    pub name: Binding<String>
+    pub name: Binding<String>,
+
+    /// The ID of the audio clip attached to this layer, if there is one
+    pub audio_id: Option<ElementId>,
+
+    /// The audio clip attached to this layer, if there is one
+    pub audio: Option<Arc<AudioClip>>
 }
 
 impl PartialEq for LayerModel {
     fn eq(&self, other: &LayerModel) -> bool {
-        other.id == self.id
+        // Stored audio clips never change, so comparing their IDs avoids comparing the samples
+        other.id == self.id && other.audio_id == self.audio_id
     }
 }
 
 impl LayerModel {
     pub fn new<'a>(layer: &'a dyn Layer) -> LayerModel {
         LayerModel {
-            id:     layer.id(),
-            name:   bind(layer.name().unwrap_or_else(|| format!("Layer {}", layer.id())))
+            id:         layer.id(),
+            name:       bind(layer.name().unwrap_or_else(|| format!("Layer {}", layer.id()))),
+            audio_id:   layer.audio_clip_id(),
+            audio:      layer.audio_clip()
         }
     }
 }
@@ -1,25 +1,29 @@
 use super::flo_model::*;
+use super::collaboration::*;
 
 use flo_animation::*;
 use flo_ui_files::*;
 
 use std::sync::*;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 ///
 /// Represents the file model for FlowBetween animations
 ///
-pub struct FloSharedModel<Loader> {
-    /// The loader that can load in a new copy of the animation
-    loader: Arc<Loader>,
+/// The animation is opened once and shared between every session that is editing the file, so edits made in one
+/// session are published to all of the others. Each session records its edits in its own undo history (identified by
+/// its session ID), so undo only reverses the edits made in the same session.
+///
+pub struct FloSharedModel<Loader: FileAnimation> {
+    /// The animation shared between all of the sessions editing this file
+    animation: Arc<Loader::NewAnimation>,
 
-    /// The path where the animation can be opened from
-    path: PathBuf
+    /// The sessions that are editing this file
+    sessions: Arc<SharedSessions>
 }
 
 impl<Loader: FileAnimation+Send+Sync+'static> FileModel for FloSharedModel<Loader>
 where Loader::NewAnimation: 'static+EditableAnimation {
-    // TODO: we should probably actually share the file between instances :-)
     type InstanceModel  = FloModel<Loader::NewAnimation>;
     type Loader         = Loader;
 
@@ -29,8 +33,8 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     ///
     fn open(loader: Arc<Loader>, path: &Path) -> FloSharedModel<Loader> {
         FloSharedModel {
-            loader: loader,
-            path:   PathBuf::from(path),
+            animation:  Arc::new(loader.open(path)),
+            sessions:   Arc::new(SharedSessions::new())
         }
     }
 
@@ -38,8 +42,6 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     /// Creates a new instance model from the shared model. This is used for a single session.
     ///
     fn new_instance(&self) -> FloModel<Loader::NewAnimation> {
-        let animation = self.loader.open(self.path.as_path());
-
-        FloModel::new(animation)
+        FloModel::with_shared_animation(Arc::clone(&self.animation), &self.sessions)
     }
 }
@@ -143,6 +143,11 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
                     layers.retain(|model| model.id != layer_id)
                 },
 
+                ReloadLayers => {
+                    // Replace all of the layers with the layers currently in the animation
+                    layers = Self::get_layers(&animation);
+                },
+
                 _ => { }
             }
 
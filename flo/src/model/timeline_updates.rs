@@ -13,7 +13,13 @@ pub enum TimelineModelUpdate {
     AddNewLayer(u64),
     RemoveLayer(u64),
     AddKeyFrame(u64, Duration),
-    RemoveKeyFrame(u64, Duration)
+    RemoveKeyFrame(u64, Duration),
+
+    /// The keyframes in a layer have been moved, copied or retimed
+    KeyFramesChanged(u64),
+
+    /// The layers might have changed in any way (eg, after an undo), so should be reloaded from the animation
+    ReloadLayers
 }
 
 impl TimelineModelUpdate {
@@ -25,7 +31,8 @@ impl TimelineModelUpdate {
 
         match self {
             AddNewLayer(_)  |
-            RemoveLayer(_)  =>  true,
+            RemoveLayer(_)  |
+            ReloadLayers    =>  true,
 
             _               => false
         }
@@ -44,12 +51,20 @@ pub fn get_timeline_updates<EditStream: Stream<Item=Arc<Vec<AnimationEdit>>>>(ed
             animation_edits.iter()
                 .filter_map(|animation_edit| {
                     match animation_edit {
-                        AddNewLayer(layer_id)                   => Some(TimelineModelUpdate::AddNewLayer(*layer_id)),
-                        RemoveLayer(layer_id)                   => Some(TimelineModelUpdate::RemoveLayer(*layer_id)),
-                        Layer(layer_id, AddKeyFrame(when))      => Some(TimelineModelUpdate::AddKeyFrame(*layer_id, *when)),
-                        Layer(layer_id, RemoveKeyFrame(when))   => Some(TimelineModelUpdate::RemoveKeyFrame(*layer_id, *when)),
+                        AddNewLayer(layer_id)                        => Some(TimelineModelUpdate::AddNewLayer(*layer_id)),
+                        RemoveLayer(layer_id)                        => Some(TimelineModelUpdate::RemoveLayer(*layer_id)),
+                        Layer(layer_id, AddKeyFrame(when))           => Some(TimelineModelUpdate::AddKeyFrame(*layer_id, *when)),
+                        Layer(layer_id, RemoveKeyFrame(when))        => Some(TimelineModelUpdate::RemoveKeyFrame(*layer_id, *when)),
+                        Layer(layer_id, MoveKeyFrame(_, _))          |
+                        Layer(layer_id, DuplicateKeyFrame(_, _, _))  |
+                        Layer(layer_id, RetimeKeyFrames(_, _))       |
+                        Layer(layer_id, BakeInbetweens(_, _, _))     => Some(TimelineModelUpdate::KeyFramesChanged(*layer_id)),
+                        Layer(_, ImportAudio(_))                     |
+                        Layer(_, RemoveAudio)                        => Some(TimelineModelUpdate::ReloadLayers),
+                        Undo                                         |
+                        Redo                                         => Some(TimelineModelUpdate::ReloadLayers),
 
-                        _                                       => None
+                        _                                        => None
                     }
                 })
                 .collect::<Vec<_>>()
@@ -37,7 +37,8 @@ impl<Anim: EditableAnimation+Animation+'static> ToolModel<Anim> {
         // Create the initial set of tools
         let default_tool_sets: Vec<Arc<dyn ToolSet<Anim>>> = vec![
             Arc::new(SelectionTools::new()),
-            Arc::new(PaintTools::new())
+            Arc::new(PaintTools::new()),
+            Arc::new(ShapeTools::new())
         ];
 
         // Create the bindings
@@ -15,6 +15,7 @@ use itertools::*;
 
 use std::f32;
 use std::sync::*;
+use std::cmp::Ordering;
 use std::time::Duration;
 use std::collections::HashSet;
 
@@ -30,7 +31,10 @@ enum AdjustAction {
     Select,
 
     /// A control point is being adjusted
-    DragControlPoint(ElementId, usize, (f32, f32), (f32, f32))
+    DragControlPoint(ElementId, usize, (f32, f32), (f32, f32)),
+
+    /// The node at the end of a path is being dragged onto another node to join them
+    DragJoin(ElementId, usize, (f32, f32), (f32, f32))
 }
 
 ///
@@ -84,6 +88,10 @@ impl AdjustData {
 ///
 /// The Adjust tool (adjusts control points of existing objects)
 ///
+/// Nodes in path elements can be edited by clicking on them with a modifier key held down: alt switches between
+/// smooth and corner nodes, control deletes a node and shift breaks a path. Shift-dragging from the end of a path
+/// to another end joins them, and control-clicking on the outline of a path inserts a new node.
+///
 pub struct Adjust { }
 
 impl Adjust {
@@ -184,8 +192,7 @@ impl Adjust {
 
         let control_points = element.control_points(properties);
 
-        /*
-        // Draw the control point connecting lines
+        // Draw the control point connecting lines (the tangents for each node)
         draw.new_path();
         for (prev, next) in control_points.iter().tuple_windows() {
             match (prev, next) {
@@ -205,7 +212,6 @@ impl Adjust {
         draw.line_width_pixels(1.0);
         draw.stroke_color(CP_LINES);
         draw.stroke();
-        */
 
         // Draw the control points themselves
         for cp in control_points.iter() {
@@ -404,7 +410,8 @@ impl Adjust {
             let state = tool_state.get();
 
             match state {
-                AdjustAction::DragControlPoint(_, _, _, _) =>  {
+                AdjustAction::DragControlPoint(_, _, _, _)  |
+                AdjustAction::DragJoin(_, _, _, _)          =>  {
                     // Only fetch the elements while actually dragging an element
                     Some((state, elements.get()))
                 },
@@ -446,6 +453,27 @@ impl Adjust {
                         vec![ToolAction::Overlay(OverlayAction::Draw(draw_drag))]
                     },
 
+                    Some((AdjustAction::DragJoin(_, _, from, to), _elements)) => {
+                        // Draw a line showing where the path will be joined
+                        let mut draw_join   = vec![];
+
+                        draw_join.layer(1);
+                        draw_join.clear_layer();
+
+                        draw_join.new_path();
+                        draw_join.move_to(from.0, from.1);
+                        draw_join.line_to(to.0, to.1);
+
+                        draw_join.line_width_pixels(2.0);
+                        draw_join.stroke_color(SELECTION_OUTLINE);
+                        draw_join.stroke();
+                        draw_join.line_width_pixels(1.0);
+                        draw_join.stroke_color(CP_LINES);
+                        draw_join.stroke();
+
+                        vec![ToolAction::Overlay(OverlayAction::Draw(draw_join))]
+                    },
+
                     _ => {
                         // No edits to draw
                         let mut clear_layer = vec![];
@@ -463,6 +491,114 @@ impl Adjust {
             .flatten()
     }
 
+    ///
+    /// Returns the path for an element, if it's a path element
+    ///
+    fn path_for_element(data: &AdjustData, element_id: ElementId) -> Option<Path> {
+        match data.frame.as_ref().and_then(|frame| frame.element_with_id(element_id)) {
+            Some(Vector::Path(path_element))    => Some(path_element.path().clone()),
+            _                                   => None
+        }
+    }
+
+    ///
+    /// Returns the actions for performing an edit on the nodes of a path element
+    ///
+    fn edit_path(element_id: ElementId, edit: ElementEdit) -> Vec<ToolAction<AdjustData>> {
+        vec![
+            ToolAction::Edit(AnimationEdit::Element(vec![element_id], edit)),
+            ToolAction::InvalidateFrame
+        ]
+    }
+
+    ///
+    /// Edits a node in a path element if the user clicks on it with a modifier key held down
+    ///
+    /// Returns None if the control point is not a node in a path or no modifier keys are held down.
+    ///
+    fn node_action(painting: &Painting, data: &AdjustData, element_id: ElementId, cp_index: usize) -> Option<Vec<ToolAction<AdjustData>>> {
+        let modifiers   = painting.modifiers;
+        let path        = Self::path_for_element(data, element_id)?;
+        let node        = path.node_for_control_point(cp_index)?;
+
+        if modifiers.alt {
+            // Switch between smooth and corner nodes
+            let node_type = match path.node_type(node) {
+                PathNodeType::Smooth    => PathNodeType::Corner,
+                PathNodeType::Corner    => PathNodeType::Smooth
+            };
+
+            data.state.set(AdjustAction::NoAction);
+            Some(Self::edit_path(element_id, ElementEdit::SetPathNodeType(node, node_type)))
+        } else if modifiers.control {
+            // Remove the node
+            data.state.set(AdjustAction::NoAction);
+            Some(Self::edit_path(element_id, ElementEdit::DeletePathNode(node)))
+        } else if modifiers.shift {
+            if path.is_end_node(node) {
+                // Start dragging to join this node to another one
+                data.state.set(AdjustAction::DragJoin(element_id, node, painting.location, painting.location));
+                Some(vec![])
+            } else {
+                // Break the path at this node
+                data.state.set(AdjustAction::NoAction);
+                Some(Self::edit_path(element_id, ElementEdit::BreakPath(node)))
+            }
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Finds the segment of a selected path element that's nearest to a point, returning the element, the index of the
+    /// segment and the position along it
+    ///
+    fn nearest_path_segment(data: &AdjustData, location: (f32, f32)) -> Option<(ElementId, usize, f32)> {
+        let frame           = data.frame.as_ref()?;
+        let mut nearest     = None;
+        let mut min_dist    = 8.0;
+
+        for element_id in data.selected_elements.iter() {
+            if let Some(element) = frame.element_with_id(*element_id) {
+                if let Vector::Path(_) = &element {
+                    // The path is transformed by the properties in the same way as the control points (with the same components)
+                    let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+                    let paths       = element.to_path(&*properties, PathConversion::Fastest);
+
+                    for path in paths.into_iter().flatten() {
+                        if let Some((segment, pos, distance)) = path.nearest_segment(location) {
+                            if distance < min_dist {
+                                min_dist    = distance;
+                                nearest     = Some((*element_id, segment, pos));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
+    ///
+    /// Finds the end node of a path element near to a location that a node can be joined to
+    ///
+    fn join_target(data: &AdjustData, element_id: ElementId, node: usize, location: (f32, f32)) -> Option<usize> {
+        let path = Self::path_for_element(data, element_id)?;
+
+        data.control_points.iter()
+            .filter(|(cp_element, _, _)| *cp_element == element_id)
+            .filter_map(|(_, cp_index, pos)| path.node_for_control_point(*cp_index).map(|target| (target, *pos)))
+            .filter(|(target, _)| *target != node && path.is_end_node(*target))
+            .map(|(target, pos)| {
+                let (diff_x, diff_y) = (location.0-pos.0, location.1-pos.1);
+                (target, (diff_x*diff_x + diff_y*diff_y).sqrt())
+            })
+            .filter(|(_, distance)| *distance < 8.0)
+            .min_by(|(_, dist_a), (_, dist_b)| dist_a.partial_cmp(dist_b).unwrap_or(Ordering::Equal))
+            .map(|(target, _)| target)
+    }
+
     ///
     /// Generates the tool actions for a painting action
     ///
@@ -479,10 +615,26 @@ impl Adjust {
                 // If the user clicks on a control point, dragging that takes priority
                 if let Some((cp_index, distance)) = data.nearest_control_point_index(painting.location) {
                     if distance < 8.0 {
-                        // Start dragging this control point
                         let &(element_id, index, _pos) = &data.control_points[cp_index];
 
-                        data.state.set(AdjustAction::DragControlPoint(element_id, index, painting.location, painting.location));
+                        if let Some(node_actions) = Self::node_action(&painting, data, element_id, index) {
+                            // Clicking with a modifier key held down edits the node instead
+                            actions.extend(node_actions);
+                        } else {
+                            // Start dragging this control point
+                            data.state.set(AdjustAction::DragControlPoint(element_id, index, painting.location, painting.location));
+                        }
+
+                        started_drag = true;
+                    }
+                }
+
+                // Control-clicking on the outline of a selected path inserts a new node
+                if !started_drag && painting.modifiers.control {
+                    if let Some((element_id, segment, pos)) = Self::nearest_path_segment(data, painting.location) {
+                        data.state.set(AdjustAction::NoAction);
+                        actions.extend(Self::edit_path(element_id, ElementEdit::InsertPathNode(segment, pos)));
+
                         started_drag = true;
                     }
                 }
@@ -560,6 +712,25 @@ impl Adjust {
                 edit_element
             },
 
+            (AdjustAction::DragJoin(element_id, node, from, _to), PaintAction::Continue)   |
+            (AdjustAction::DragJoin(element_id, node, from, _to), PaintAction::Prediction) => {
+                // Track where the join will end
+                data.state.set(AdjustAction::DragJoin(element_id, node, from, painting.location));
+
+                vec![]
+            },
+
+            (AdjustAction::DragJoin(element_id, node, _from, _to), PaintAction::Finish) => {
+                data.state.set(AdjustAction::NoAction);
+
+                // Join to the end node where the user released the drag, if there is one
+                if let Some(target) = Self::join_target(data, element_id, node, painting.location) {
+                    Self::edit_path(element_id, ElementEdit::JoinPathNodes(node, target))
+                } else {
+                    vec![]
+                }
+            },
+
             // Default 'paint end' action is to reset to the 'no action' state
             (_, PaintAction::Finish) |
             (_, PaintAction::Cancel) => {
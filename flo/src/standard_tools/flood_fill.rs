@@ -130,7 +130,8 @@ impl<Anim: 'static+Animation> Tool<Anim> for FloodFill {
             BrushProperties {
                 size:       1.0,
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                fill:       BrushFill::Solid
             }
         });
 
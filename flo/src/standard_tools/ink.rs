@@ -36,6 +36,12 @@ pub struct InkModel {
     /// The colour of the brush (in general alpha should be left at 1.0 here)
     pub color: Binding<Color>,
 
+    /// How the pressure, tilt and speed of the stylus affect the brush
+    pub dynamics: Binding<BrushDynamics>,
+
+    /// The colour that the brush dynamics can mix into the brush colour
+    pub secondary_color: Binding<Color>,
+
     /// The brush properties for the current brush view model
     pub brush_properties: BindRef<BrushProperties>,
 
@@ -70,6 +76,8 @@ impl InkModel {
         let color               = bind(Color::Hsluv(0.0, 100.0, 0.0, 1.0));
         let modification_mode   = bind(BrushModificationMode::Individual);
         let representation      = bind(BrushRepresentation::BrushStroke);
+        let dynamics            = bind(BrushDynamics::default());
+        let secondary_color     = bind(BrushDynamics::default().secondary_color);
 
         let brush_properties    = Self::brush_properties(size.clone(), opacity.clone(), color.clone());
 
@@ -77,6 +85,8 @@ impl InkModel {
             size:               size,
             opacity:            opacity,
             color:              color,
+            dynamics:           dynamics,
+            secondary_color:    secondary_color,
             brush_properties:   brush_properties,
             modification_mode:  modification_mode,
             representation:     representation
@@ -91,7 +101,8 @@ impl InkModel {
             BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                fill:       BrushFill::Solid
             }
         });
 
@@ -124,7 +135,7 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
     /// Creates the menu controller for this tool (or None if this tool has no menu controller)
     ///
     fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Option<Arc<dyn Controller>> {
-        Some(Arc::new(InkMenuController::new(&tool_model.size, &tool_model.opacity, &tool_model.color, &tool_model.modification_mode, &tool_model.representation)))
+        Some(Arc::new(InkMenuController::new(&tool_model.size, &tool_model.opacity, &tool_model.color, &tool_model.dynamics, &tool_model.secondary_color, &tool_model.modification_mode, &tool_model.representation)))
     }
 
     ///
@@ -136,11 +147,13 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
         let selected_layer      = flo_model.timeline().selected_layer.clone();
         let representation      = tool_model.representation.clone();
         let modification_mode   = tool_model.modification_mode.clone();
+        let dynamics            = tool_model.dynamics.clone();
+        let secondary_color     = tool_model.secondary_color.clone();
 
         // Create a computed binding that generates the data for the brush
         let ink_data            = computed(move || {
             InkData {
-                brush:              BrushDefinition::Ink(InkDefinition::default()),
+                brush:              BrushDefinition::Ink(InkMenuController::ink_definition(dynamics.get(), secondary_color.get())),
                 brush_properties:   brush_properties.get(),
                 selected_layer:     selected_layer.get().unwrap_or(0),
                 representation:     representation.get(),
@@ -5,6 +5,8 @@ mod pan;
 mod ink;
 mod eraser;
 mod flood_fill;
+mod shape;
+mod text;
 mod tool_sets;
 
 pub use self::select::*;
@@ -14,4 +16,6 @@ pub use self::pan::*;
 pub use self::ink::*;
 pub use self::eraser::*;
 pub use self::flood_fill::*;
+pub use self::shape::*;
+pub use self::text::*;
 pub use self::tool_sets::*;
@@ -0,0 +1,585 @@
+use super::super::menu::*;
+use super::super::tools::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+use futures::stream::{BoxStream};
+
+use std::f32;
+use std::sync::*;
+
+///
+/// The angle that lines and polygons snap to while the shift key is held down (15 degrees)
+///
+const SNAP_ANGLE: f32 = f32::consts::PI / 12.0;
+
+///
+/// The distance of the control points from the end points of a bezier curve approximating a quarter of an ellipse (as a fraction of the radius)
+///
+const ELLIPSE_KAPPA: f32 = 0.552_284_8;
+
+///
+/// The shapes that can be drawn by the shape tools
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShapeType {
+    /// Rectangle, from one corner to the other (shift draws squares)
+    Rectangle,
+
+    /// Ellipse, fitted to the rectangle between two corners (shift draws circles)
+    Ellipse,
+
+    /// Regular polygon, from the center to one of the corners (shift snaps the rotation to 15 degree steps)
+    Polygon,
+
+    /// Straight line (shift snaps the angle to 15 degree steps)
+    Line
+}
+
+///
+/// How a shape is painted on the canvas
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShapeFillMode {
+    /// The shape is filled and has an outline
+    FillAndStroke,
+
+    /// The shape is filled with no outline
+    FillOnly,
+
+    /// Only the outline of the shape is drawn
+    StrokeOnly
+}
+
+///
+/// The current action being performed by a shape tool
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ShapeAction {
+    /// The tool is idle
+    NoAction,
+
+    /// A shape is being drawn between two points
+    Drawing((f32, f32), (f32, f32))
+}
+
+///
+/// Data for the shape tools
+///
+#[derive(Clone)]
+pub struct ShapeData {
+    /// The brush properties to fill the shape with (or None if the shape has no fill)
+    pub fill: Option<BrushProperties>,
+
+    /// The brush properties to draw the outline of the shape with (or None if the shape has no outline). The size is the width of the outline.
+    pub stroke: Option<BrushProperties>,
+
+    /// The number of sides to use when drawing polygons
+    pub num_sides: u32,
+
+    /// The current state of the tool
+    state: Binding<ShapeAction>
+}
+
+///
+/// The UI model for a shape tool
+///
+pub struct ShapeModel {
+    /// The colour used to fill new shapes
+    pub fill_color: Binding<Color>,
+
+    /// The colour used for the outline of new shapes
+    pub stroke_color: Binding<Color>,
+
+    /// The width of the outline of new shapes
+    pub stroke_width: Binding<f32>,
+
+    /// The opacity of new shapes
+    pub opacity: Binding<f32>,
+
+    /// Whether or not new shapes are filled or outlined
+    pub fill_mode: Binding<ShapeFillMode>,
+
+    /// The number of sides for new polygons
+    pub num_sides: Binding<u32>
+}
+
+///
+/// A tool that draws a geometric shape as a set of paths
+///
+pub struct Shape {
+    shape_type: ShapeType
+}
+
+impl ShapeModel {
+    ///
+    /// Creates a new shape model with the default settings for a particular type of shape
+    ///
+    pub fn new(shape_type: ShapeType) -> ShapeModel {
+        // Lines have no interior, so they're always drawn as an outline
+        let fill_mode = match shape_type {
+            ShapeType::Line     => ShapeFillMode::StrokeOnly,
+            _                   => ShapeFillMode::FillAndStroke
+        };
+
+        ShapeModel {
+            fill_color:     bind(Color::Rgba(0.0, 0.6, 0.35, 1.0)),
+            stroke_color:   bind(Color::Hsluv(0.0, 100.0, 0.0, 1.0)),
+            stroke_width:   bind(2.0),
+            opacity:        bind(1.0),
+            fill_mode:      bind(fill_mode),
+            num_sides:      bind(6)
+        }
+    }
+}
+
+impl ShapeType {
+    ///
+    /// The name of the tool that draws this shape
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShapeType::Rectangle    => "Rectangle",
+            ShapeType::Ellipse      => "Ellipse",
+            ShapeType::Polygon      => "Polygon",
+            ShapeType::Line         => "Line"
+        }
+    }
+
+    ///
+    /// Moves the end point of a shape that's being drawn to take account of the modifier keys
+    ///
+    pub fn constrain(&self, from: (f32, f32), to: (f32, f32), modifiers: PaintModifiers) -> (f32, f32) {
+        if !modifiers.shift {
+            return to;
+        }
+
+        let (dx, dy) = (to.0-from.0, to.1-from.1);
+
+        match self {
+            ShapeType::Rectangle | ShapeType::Ellipse => {
+                // Make the width and height the same to draw a square or a circle
+                let size = f32::max(dx.abs(), dy.abs());
+                (from.0 + size*dx.signum(), from.1 + size*dy.signum())
+            }
+
+            ShapeType::Polygon | ShapeType::Line => {
+                // Round the angle to the nearest snapping angle
+                let length  = (dx*dx + dy*dy).sqrt();
+                let angle   = (dy.atan2(dx) / SNAP_ANGLE).round() * SNAP_ANGLE;
+                (from.0 + length*angle.cos(), from.1 + length*angle.sin())
+            }
+        }
+    }
+
+    ///
+    /// Returns the path that covers the interior of this shape (or None if the shape has no interior)
+    ///
+    pub fn fill_path(&self, from: (f32, f32), to: (f32, f32), num_sides: u32) -> Option<Vec<PathComponent>> {
+        match self {
+            ShapeType::Line => None,
+            _               => self.offset_path(from, to, num_sides, 0.0, false)
+        }
+    }
+
+    ///
+    /// Returns the path that covers the outline of this shape, when drawn with a particular width
+    ///
+    pub fn stroke_path(&self, from: (f32, f32), to: (f32, f32), num_sides: u32, width: f32) -> Option<Vec<PathComponent>> {
+        if width <= 0.0 {
+            return None;
+        }
+
+        match self {
+            ShapeType::Line => self.offset_path(from, to, num_sides, width/2.0, false),
+
+            _               => {
+                // The outline is the shape made larger with a smaller version of the shape removed from the middle
+                // (the inner path goes in the opposite direction so that it forms a hole)
+                let mut outline = self.offset_path(from, to, num_sides, width/2.0, false)?;
+                if let Some(inner) = self.offset_path(from, to, num_sides, -width/2.0, true) {
+                    outline.extend(inner);
+                }
+
+                Some(outline)
+            }
+        }
+    }
+
+    ///
+    /// Generates the path for this shape with its edges moved outwards by a particular distance (or inwards if the distance is negative)
+    ///
+    /// Lines are generated as a rectangle that extends by the offset distance either side. Returns None if the shape has no area.
+    ///
+    fn offset_path(&self, from: (f32, f32), to: (f32, f32), num_sides: u32, offset: f32, reverse: bool) -> Option<Vec<PathComponent>> {
+        match self {
+            ShapeType::Rectangle    => {
+                let (x1, x2) = (f32::min(from.0, to.0) - offset, f32::max(from.0, to.0) + offset);
+                let (y1, y2) = (f32::min(from.1, to.1) - offset, f32::max(from.1, to.1) + offset);
+
+                if x2 <= x1 || y2 <= y1 {
+                    None
+                } else {
+                    Some(Self::polygon_path(vec![(x1, y1), (x2, y1), (x2, y2), (x1, y2)], reverse))
+                }
+            }
+
+            ShapeType::Ellipse      => {
+                let center = ((from.0+to.0)/2.0, (from.1+to.1)/2.0);
+                let radius = ((to.0-from.0).abs()/2.0 + offset, (to.1-from.1).abs()/2.0 + offset);
+
+                if radius.0 <= 0.0 || radius.1 <= 0.0 {
+                    None
+                } else {
+                    Some(Self::ellipse_path(center, radius, reverse))
+                }
+            }
+
+            ShapeType::Polygon      => {
+                // The offset is applied to the edges, which moves the corners a little further
+                let num_sides   = u32::max(num_sides, 3);
+                let (dx, dy)    = (to.0-from.0, to.1-from.1);
+                let radius      = (dx*dx + dy*dy).sqrt() + offset / (f32::consts::PI / (num_sides as f32)).cos();
+                let start_angle = dy.atan2(dx);
+
+                if radius <= 0.0 {
+                    None
+                } else {
+                    let corners = (0..num_sides)
+                        .map(|corner| start_angle + (corner as f32) * 2.0 * f32::consts::PI / (num_sides as f32))
+                        .map(|angle| (from.0 + radius*angle.cos(), from.1 + radius*angle.sin()))
+                        .collect();
+
+                    Some(Self::polygon_path(corners, reverse))
+                }
+            }
+
+            ShapeType::Line         => {
+                let (dx, dy)    = (to.0-from.0, to.1-from.1);
+                let length      = (dx*dx + dy*dy).sqrt();
+
+                if length <= 0.0 || offset <= 0.0 {
+                    None
+                } else {
+                    // Normal to the line, with a length of the offset
+                    let (nx, ny) = (-dy/length*offset, dx/length*offset);
+
+                    Some(Self::polygon_path(vec![(from.0+nx, from.1+ny), (from.0-nx, from.1-ny), (to.0-nx, to.1-ny), (to.0+nx, to.1+ny)], reverse))
+                }
+            }
+        }
+    }
+
+    ///
+    /// Creates a closed path joining a set of points with straight lines
+    ///
+    fn polygon_path(points: Vec<(f32, f32)>, reverse: bool) -> Vec<PathComponent> {
+        let mut points = points;
+        if reverse { points.reverse(); }
+
+        let mut path = vec![];
+        path.push(PathComponent::Move(PathPoint::new(points[0].0, points[0].1)));
+        path.extend(points.iter().skip(1).map(|(x, y)| PathComponent::Line(PathPoint::new(*x, *y))));
+        path.push(PathComponent::Close);
+
+        path
+    }
+
+    ///
+    /// Creates a closed path for an ellipse made up of four bezier curves
+    ///
+    fn ellipse_path(center: (f32, f32), radius: (f32, f32), reverse: bool) -> Vec<PathComponent> {
+        let direction   = if reverse { -1.0 } else { 1.0 };
+        let point_at    = |angle: f32| (center.0 + radius.0*angle.cos(), center.1 + radius.1*angle.sin());
+        let tangent_at  = |angle: f32| (-radius.0*angle.sin()*direction*ELLIPSE_KAPPA, radius.1*angle.cos()*direction*ELLIPSE_KAPPA);
+
+        let start       = point_at(0.0);
+        let mut path    = vec![PathComponent::Move(PathPoint::new(start.0, start.1))];
+
+        for quarter in 0..4 {
+            let start_angle = direction * (quarter as f32) * f32::consts::PI / 2.0;
+            let end_angle   = direction * ((quarter+1) as f32) * f32::consts::PI / 2.0;
+
+            let (start, end)    = (point_at(start_angle), point_at(end_angle));
+            let (t1, t2)        = (tangent_at(start_angle), tangent_at(end_angle));
+
+            path.push(PathComponent::Bezier(PathPoint::new(end.0, end.1), PathPoint::new(start.0+t1.0, start.1+t1.1), PathPoint::new(end.0-t2.0, end.1-t2.1)));
+        }
+
+        path.push(PathComponent::Close);
+
+        path
+    }
+}
+
+impl Shape {
+    ///
+    /// Creates a new tool that draws a particular kind of shape
+    ///
+    pub fn new(shape_type: ShapeType) -> Shape {
+        Shape {
+            shape_type: shape_type
+        }
+    }
+
+    ///
+    /// Returns the paths to fill and to outline for a shape drawn between two points
+    ///
+    fn shape_paths(&self, data: &ShapeData, from: (f32, f32), to: (f32, f32)) -> Vec<(BrushProperties, Vec<PathComponent>)> {
+        let fill    = data.fill.as_ref()
+            .and_then(|fill| self.shape_type.fill_path(from, to, data.num_sides).map(|path| (fill.clone(), path)));
+        let stroke  = data.stroke.as_ref()
+            .and_then(|stroke| self.shape_type.stroke_path(from, to, data.num_sides, stroke.size).map(|path| (stroke.clone(), path)));
+
+        fill.into_iter().chain(stroke).collect()
+    }
+
+    ///
+    /// Draws a preview of the shape being drawn in the overlay
+    ///
+    fn draw_preview(&self, data: &ShapeData, from: (f32, f32), to: (f32, f32)) -> Vec<Draw> {
+        let mut draw = vec![
+            Draw::Layer(1),
+            Draw::ClearLayer
+        ];
+
+        for (properties, path) in self.shape_paths(data, from, to) {
+            draw.push(Draw::NewPath);
+            draw.extend(Path::from_elements(path).to_drawing());
+            draw.push(Draw::FillColor(properties.color.with_alpha(properties.opacity)));
+            draw.push(Draw::Fill);
+        }
+
+        draw
+    }
+
+    ///
+    /// Generates the actions that add a finished shape to the current frame
+    ///
+    fn create_shape<Anim: 'static+Animation>(&self, model: &FloModel<Anim>, data: &ShapeData, from: (f32, f32), to: (f32, f32)) -> Vec<ToolAction<ShapeData>> {
+        // Get the current frame information
+        let when    = model.timeline().current_time.get();
+        let layer   = model.timeline().selected_layer.get();
+        let frame   = model.frame().frame.get();
+
+        // Shapes that are too small to see are not added to the frame (usually the result of a click rather than a drag)
+        let (dx, dy) = (to.0-from.0, to.1-from.1);
+        if (dx*dx + dy*dy) < 1.0 {
+            return vec![];
+        }
+
+        if let (Some(_frame), Some(layer)) = (frame, layer) {
+            // Each path is created with its own brush properties
+            let path_edits = self.shape_paths(data, from, to).into_iter()
+                .flat_map(|(properties, path)| vec![
+                    PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw),
+                    PathEdit::BrushProperties(ElementId::Unassigned, properties),
+                    PathEdit::CreatePath(ElementId::Unassigned, Arc::new(path))
+                ]);
+            let edits = path_edits.map(|edit| ToolAction::Edit(AnimationEdit::Layer(layer, LayerEdit::Path(when, edit))));
+
+            // Redraw the frame once the shape has been added
+            edits.chain(vec![
+                ToolAction::InvalidateFrame,
+                ToolAction::ClearSelection
+            ]).collect()
+        } else {
+            // Nowhere to draw the shape
+            vec![]
+        }
+    }
+
+    ///
+    /// Generates the tool actions for a painting action
+    ///
+    fn paint<Anim: 'static+Animation>(&self, painting: Painting, data: &ShapeData, model: &FloModel<Anim>) -> Vec<ToolAction<ShapeData>> {
+        let clear_overlay = || ToolAction::Overlay(OverlayAction::Draw(vec![Draw::Layer(1), Draw::ClearLayer]));
+
+        match (data.state.get(), painting.action) {
+            (_, PaintAction::Start) => {
+                // Start drawing a new shape
+                data.state.set(ShapeAction::Drawing(painting.location, painting.location));
+                vec![]
+            },
+
+            (ShapeAction::Drawing(from, _to), PaintAction::Continue)    |
+            (ShapeAction::Drawing(from, _to), PaintAction::Prediction)  => {
+                // Update the preview of the shape
+                let to = self.shape_type.constrain(from, painting.location, painting.modifiers);
+                data.state.set(ShapeAction::Drawing(from, to));
+
+                vec![ToolAction::Overlay(OverlayAction::Draw(self.draw_preview(data, from, to)))]
+            },
+
+            (ShapeAction::Drawing(from, _to), PaintAction::Finish)      => {
+                // Add the shape to the frame
+                let to = self.shape_type.constrain(from, painting.location, painting.modifiers);
+                data.state.set(ShapeAction::NoAction);
+
+                let mut actions = vec![clear_overlay()];
+                actions.extend(self.create_shape(model, data, from, to));
+                actions
+            },
+
+            (_, PaintAction::Finish)    |
+            (_, PaintAction::Cancel)    => {
+                // Stop drawing without creating a shape
+                data.state.set(ShapeAction::NoAction);
+                vec![clear_overlay()]
+            },
+
+            // Other actions don't affect the shape
+            _ => vec![]
+        }
+    }
+}
+
+impl<Anim: 'static+Animation> Tool<Anim> for Shape {
+    type ToolData   = ShapeData;
+    type Model      = ShapeModel;
+
+    fn tool_name(&self) -> String { self.shape_type.name().to_string() }
+
+    fn image_name(&self) -> String { self.shape_type.name().to_lowercase() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> ShapeModel {
+        ShapeModel::new(self.shape_type)
+    }
+
+    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &ShapeModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(ShapeMenuController::new(self.shape_type, tool_model)))
+    }
+
+    fn actions_for_model(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &ShapeModel) -> BoxStream<'static, ToolAction<ShapeData>> {
+        let shape_type      = self.shape_type;
+        let fill_color      = tool_model.fill_color.clone();
+        let stroke_color    = tool_model.stroke_color.clone();
+        let stroke_width    = tool_model.stroke_width.clone();
+        let opacity         = tool_model.opacity.clone();
+        let fill_mode       = tool_model.fill_mode.clone();
+        let num_sides       = tool_model.num_sides.clone();
+
+        // State is initially 'no action'
+        let state           = bind(ShapeAction::NoAction);
+
+        // Compute the data from the model
+        let shape_data      = computed(move || {
+            let fill_mode   = if shape_type == ShapeType::Line { ShapeFillMode::StrokeOnly } else { fill_mode.get() };
+            let opacity     = opacity.get();
+
+            let fill        = match fill_mode {
+                ShapeFillMode::StrokeOnly                               => None,
+                ShapeFillMode::FillOnly | ShapeFillMode::FillAndStroke  => Some(BrushProperties { size: 1.0, opacity: opacity, color: fill_color.get(), fill: BrushFill::Solid })
+            };
+            let stroke      = match fill_mode {
+                ShapeFillMode::FillOnly                                 => None,
+                ShapeFillMode::StrokeOnly | ShapeFillMode::FillAndStroke => Some(BrushProperties { size: stroke_width.get(), opacity: opacity, color: stroke_color.get(), fill: BrushFill::Solid })
+            };
+
+            ShapeData {
+                fill:       fill,
+                stroke:     stroke,
+                num_sides:  num_sides.get(),
+                state:      state.clone()
+            }
+        });
+
+        // Turn the computed values into a stream and update the data whenever the values change
+        Box::pin(follow(shape_data).map(|shape_data| ToolAction::Data(shape_data)))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<ShapeData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<ShapeData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<ShapeData>>> {
+        let mut data    = data;
+        let mut actions = vec![];
+        let input       = ToolInput::last_paint_actions_only(input);
+
+        // Process the input
+        for input in input {
+            match input {
+                ToolInput::Data(new_data) => {
+                    // Keep tracking the data as it changes
+                    data = Some(new_data);
+                },
+
+                ToolInput::Paint(painting) => {
+                    if let Some(data) = data.as_ref() {
+                        actions.extend(self.paint(painting, &**data, &*flo_model));
+                    }
+                },
+
+                _ => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn shift() -> PaintModifiers {
+        PaintModifiers { shift: true, control: false, alt: false }
+    }
+
+    #[test]
+    fn shift_draws_squares() {
+        let to = ShapeType::Rectangle.constrain((10.0, 10.0), (30.0, -50.0), shift());
+
+        assert!(to == (70.0, -50.0));
+    }
+
+    #[test]
+    fn no_constraint_without_modifiers() {
+        let to = ShapeType::Ellipse.constrain((10.0, 10.0), (30.0, -50.0), PaintModifiers::default());
+
+        assert!(to == (30.0, -50.0));
+    }
+
+    #[test]
+    fn shift_snaps_lines_to_15_degrees() {
+        let (x, y) = ShapeType::Line.constrain((0.0, 0.0), (100.0, 20.0), shift());
+        let angle  = y.atan2(x).to_degrees();
+
+        assert!((angle - 15.0).abs() < 0.01);
+        assert!(((x*x + y*y).sqrt() - (100.0f32*100.0 + 20.0*20.0).sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn rectangle_outline_has_a_hole() {
+        let outline = ShapeType::Rectangle.stroke_path((0.0, 0.0), (100.0, 50.0), 0, 10.0).unwrap();
+        let moves   = outline.iter().filter(|component| match component { PathComponent::Move(_) => true, _ => false }).count();
+
+        assert!(moves == 2);
+        assert!(outline[0] == PathComponent::Move(PathPoint::new(-5.0, -5.0)));
+    }
+
+    #[test]
+    fn thin_rectangle_outline_has_no_hole() {
+        let outline = ShapeType::Rectangle.stroke_path((0.0, 0.0), (100.0, 4.0), 0, 10.0).unwrap();
+        let moves   = outline.iter().filter(|component| match component { PathComponent::Move(_) => true, _ => false }).count();
+
+        assert!(moves == 1);
+    }
+
+    #[test]
+    fn polygon_has_requested_number_of_corners() {
+        let path = ShapeType::Polygon.fill_path((0.0, 0.0), (10.0, 0.0), 5).unwrap();
+
+        // Move, 4 lines and a close
+        assert!(path.len() == 6);
+        assert!(path[0] == PathComponent::Move(PathPoint::new(10.0, 0.0)));
+    }
+
+    #[test]
+    fn lines_have_no_fill() {
+        assert!(ShapeType::Line.fill_path((0.0, 0.0), (10.0, 10.0), 0).is_none());
+        assert!(ShapeType::Line.stroke_path((0.0, 0.0), (10.0, 10.0), 0, 2.0).is_some());
+    }
+}
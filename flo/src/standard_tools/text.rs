@@ -0,0 +1,339 @@
+use super::super::menu::*;
+use super::super::tools::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+use futures::stream::{BoxStream};
+
+use std::sync::*;
+
+///
+/// Model for the text tool
+///
+#[derive(Clone)]
+pub struct TextModel {
+    /// The text to display in new text elements (or in the element being edited)
+    pub text: Binding<String>,
+
+    /// The font to draw the text in
+    pub font: Binding<TextFont>,
+
+    /// The size of the font
+    pub size: Binding<f32>,
+
+    /// The colour of the text
+    pub color: Binding<Color>,
+
+    /// How the lines of text are aligned
+    pub alignment: Binding<TextAlignment>,
+
+    /// The text element that is being edited, if there is one
+    pub editing: Binding<Option<ElementId>>
+}
+
+///
+/// Data passed through to the text tool
+///
+#[derive(Clone)]
+pub struct TextData {
+    /// The text to display
+    pub text: String,
+
+    /// The style of the text
+    pub style: TextStyle,
+
+    /// The element that is being edited
+    pub editing: Option<ElementId>,
+
+    /// The model that this data was generated from (used to load the properties of existing elements)
+    model: TextModel
+}
+
+///
+/// The text tool, which creates and edits text elements
+///
+pub struct Text { }
+
+impl TextModel {
+    ///
+    /// Creates a new text model with the default settings
+    ///
+    pub fn new() -> TextModel {
+        let default_style = TextStyle::default();
+
+        TextModel {
+            text:       bind("Text".to_string()),
+            font:       bind(TextFont::from_name(&default_style.font)),
+            size:       bind(default_style.size),
+            color:      bind(default_style.color),
+            alignment:  bind(default_style.alignment),
+            editing:    bind(None)
+        }
+    }
+
+    ///
+    /// Returns the text style described by this model
+    ///
+    pub fn style(&self) -> TextStyle {
+        TextStyle {
+            font:       self.font.get().name().to_string(),
+            size:       self.size.get(),
+            color:      self.color.get(),
+            alignment:  self.alignment.get()
+        }
+    }
+
+    ///
+    /// Starts editing an existing text element, updating the model to match its text and style
+    ///
+    pub fn edit_element(&self, text_element: &TextElement) {
+        let style = text_element.style();
+
+        // Stop editing whatever element was being edited before so the new settings aren't applied to it
+        self.editing.set(None);
+
+        self.text.set((*text_element.text()).clone());
+        self.font.set(TextFont::from_name(&style.font));
+        self.size.set(style.size);
+        self.color.set(style.color);
+        self.alignment.set(style.alignment);
+
+        self.editing.set(Some(text_element.id()));
+    }
+}
+
+impl Text {
+    ///
+    /// Creates the text tool
+    ///
+    pub fn new() -> Text {
+        Text { }
+    }
+
+    ///
+    /// Finds the text element at the specified point in the current frame
+    ///
+    fn text_at_point<Anim: 'static+Animation>(model: &FloModel<Anim>, point: (f32, f32)) -> Option<TextElement> {
+        let frame       = model.frame();
+        let elements    = frame.elements.get();
+
+        // Elements are returned with the topmost first: text can be picked from anywhere in its bounding box as it's hard to click on the glyphs themselves
+        frame.elements_at_point(point)
+            .map(ElementId::from)
+            .filter_map(|element_id| {
+                elements.iter()
+                    .find(|(vector, _properties)| vector.id() == element_id)
+                    .and_then(|(vector, _properties)| {
+                        match vector {
+                            Vector::Text(text)  => Some(text.clone()),
+                            _                   => None
+                        }
+                    })
+            })
+            .next()
+    }
+
+    ///
+    /// Creates a new text element at the specified position
+    ///
+    fn create_text<Anim: 'static+Animation>(model: &FloModel<Anim>, data: &TextData, position: (f32, f32)) -> Vec<ToolAction<TextData>> {
+        let when    = model.timeline().current_time.get();
+        let layer   = model.timeline().selected_layer.get();
+        let frame   = model.frame().frame.get();
+
+        // Text with no characters would be invisible, so it's not created
+        if data.text.trim().is_empty() {
+            return vec![];
+        }
+
+        if let (Some(_frame), Some(layer)) = (frame, layer) {
+            vec![
+                ToolAction::Edit(AnimationEdit::Layer(layer, LayerEdit::Paint(when, PaintEdit::CreateText(ElementId::Unassigned, data.text.clone(), data.style.clone(), position)))),
+                ToolAction::InvalidateFrame
+            ]
+        } else {
+            vec![]
+        }
+    }
+
+    ///
+    /// Handles a click on the canvas
+    ///
+    fn click<Anim: 'static+Animation>(&self, model: &FloModel<Anim>, data: &TextData, position: (f32, f32)) -> Vec<ToolAction<TextData>> {
+        if let Some(text_element) = Self::text_at_point(model, position) {
+            // Clicking on an existing text element starts editing it
+            data.model.edit_element(&text_element);
+
+            vec![ToolAction::ClearSelection, ToolAction::Select(text_element.id())]
+        } else if data.editing.is_some() {
+            // Clicking away from the text that's being edited finishes editing it
+            data.model.editing.set(None);
+
+            vec![ToolAction::ClearSelection]
+        } else {
+            // Clicking on an empty part of the canvas creates some new text
+            Self::create_text(model, data, position)
+        }
+    }
+
+    ///
+    /// Generates the edits needed to update the element being edited when the text data changes
+    ///
+    fn edits_for_data_change(last_data: &TextData, new_data: &TextData) -> Vec<ToolAction<TextData>> {
+        // Only the element that was being edited before the change is updated (changes caused by picking a new element don't generate edits)
+        let element_id = match (last_data.editing, new_data.editing) {
+            (Some(last_id), Some(new_id)) if last_id == new_id  => new_id,
+            _                                                   => { return vec![]; }
+        };
+
+        let mut edits = vec![];
+
+        if last_data.text != new_data.text {
+            edits.push(ToolAction::Edit(AnimationEdit::Element(vec![element_id], ElementEdit::SetText(new_data.text.clone()))));
+        }
+
+        if last_data.style != new_data.style {
+            edits.push(ToolAction::Edit(AnimationEdit::Element(vec![element_id], ElementEdit::SetTextStyle(new_data.style.clone()))));
+        }
+
+        if edits.len() > 0 {
+            edits.push(ToolAction::InvalidateFrame);
+        }
+
+        edits
+    }
+}
+
+impl<Anim: 'static+Animation> Tool<Anim> for Text {
+    type ToolData   = TextData;
+    type Model      = TextModel;
+
+    fn tool_name(&self) -> String { "Text".to_string() }
+
+    fn image_name(&self) -> String { "text".to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> TextModel {
+        TextModel::new()
+    }
+
+    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &TextModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(TextMenuController::new(tool_model)))
+    }
+
+    fn actions_for_model(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &TextModel) -> BoxStream<'static, ToolAction<TextData>> {
+        let model       = tool_model.clone();
+
+        // Compute the data from the model
+        let text_data   = computed(move || {
+            TextData {
+                text:       model.text.get(),
+                style:      model.style(),
+                editing:    model.editing.get(),
+                model:      model.clone()
+            }
+        });
+
+        // Update the data whenever the model changes, and edit the element that's being edited if the text or style is changed
+        let actions     = follow(text_data)
+            .scan(None, |last_data: &mut Option<TextData>, new_data: TextData| {
+                let mut actions = last_data.as_ref()
+                    .map(|last_data| Self::edits_for_data_change(last_data, &new_data))
+                    .unwrap_or_else(|| vec![]);
+
+                *last_data = Some(new_data.clone());
+                actions.push(ToolAction::Data(new_data));
+
+                future::ready(Some(stream::iter(actions)))
+            })
+            .flatten();
+
+        Box::pin(actions)
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<TextData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<TextData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<TextData>>> {
+        let mut data    = data;
+        let mut actions = vec![];
+
+        // Process the input
+        for input in input {
+            match input {
+                ToolInput::Data(new_data) => {
+                    // Keep tracking the data as it changes
+                    data = Some(new_data);
+                },
+
+                ToolInput::Paint(painting) => {
+                    if let (Some(data), PaintAction::Start) = (data.as_ref(), painting.action) {
+                        actions.extend(self.click(&*flo_model, &**data, painting.location));
+                    }
+                },
+
+                ToolInput::Deselect => {
+                    // Stop editing text when the user picks another tool
+                    if let Some(data) = data.as_ref() {
+                        data.model.editing.set(None);
+                    }
+                },
+
+                _ => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn text_data(text: &str, size: f32, editing: Option<ElementId>) -> TextData {
+        TextData {
+            text:       text.to_string(),
+            style:      TextStyle { size: size, ..TextStyle::default() },
+            editing:    editing,
+            model:      TextModel::new()
+        }
+    }
+
+    #[test]
+    fn no_edits_when_not_editing() {
+        let edits = Text::edits_for_data_change(&text_data("a", 12.0, None), &text_data("b", 12.0, None));
+
+        assert!(edits.len() == 0);
+    }
+
+    #[test]
+    fn no_edits_when_changing_element() {
+        let edits = Text::edits_for_data_change(&text_data("a", 12.0, Some(ElementId::Assigned(1))), &text_data("b", 12.0, Some(ElementId::Assigned(2))));
+
+        assert!(edits.len() == 0);
+    }
+
+    #[test]
+    fn set_text_for_edited_element() {
+        let edits = Text::edits_for_data_change(&text_data("a", 12.0, Some(ElementId::Assigned(1))), &text_data("b", 12.0, Some(ElementId::Assigned(1))));
+
+        assert!(edits.len() == 2);
+        assert!(match &edits[0] {
+            ToolAction::Edit(AnimationEdit::Element(ids, ElementEdit::SetText(text)))   => ids == &vec![ElementId::Assigned(1)] && text == "b",
+            _                                                                           => false
+        });
+    }
+
+    #[test]
+    fn set_style_for_edited_element() {
+        let edits = Text::edits_for_data_change(&text_data("a", 12.0, Some(ElementId::Assigned(1))), &text_data("a", 24.0, Some(ElementId::Assigned(1))));
+
+        assert!(edits.len() == 2);
+        assert!(match &edits[0] {
+            ToolAction::Edit(AnimationEdit::Element(_, ElementEdit::SetTextStyle(style)))  => style.size == 24.0,
+            _                                                                           => false
+        });
+    }
+}
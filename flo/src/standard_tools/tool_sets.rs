@@ -23,6 +23,17 @@ pub struct PaintTools<Anim: 'static+Animation> {
     flood_fill: Arc<FloTool<Anim>>
 }
 
+///
+/// The shape toolset
+///
+pub struct ShapeTools<Anim: 'static+Animation> {
+    rectangle:  Arc<FloTool<Anim>>,
+    ellipse:    Arc<FloTool<Anim>>,
+    polygon:    Arc<FloTool<Anim>>,
+    line:       Arc<FloTool<Anim>>,
+    text:       Arc<FloTool<Anim>>
+}
+
 impl<Anim: EditableAnimation+Animation> SelectionTools<Anim> {
     pub fn new() -> SelectionTools<Anim> {
         SelectionTools {
@@ -43,6 +54,18 @@ impl<Anim: Animation> PaintTools<Anim> {
     }
 }
 
+impl<Anim: Animation> ShapeTools<Anim> {
+    pub fn new() -> ShapeTools<Anim> {
+        ShapeTools {
+            rectangle:  Shape::new(ShapeType::Rectangle).to_flo_tool(),
+            ellipse:    Shape::new(ShapeType::Ellipse).to_flo_tool(),
+            polygon:    Shape::new(ShapeType::Polygon).to_flo_tool(),
+            line:       Shape::new(ShapeType::Line).to_flo_tool(),
+            text:       Text::new().to_flo_tool()
+        }
+    }
+}
+
 impl<Anim: Animation> ToolSet<Anim> for SelectionTools<Anim> {
     fn set_name(&self) -> String { "Selection".to_string() }
 
@@ -66,3 +89,17 @@ impl<Anim: Animation> ToolSet<Anim> for PaintTools<Anim> {
         ]
     }
 }
+
+impl<Anim: Animation> ToolSet<Anim> for ShapeTools<Anim> {
+    fn set_name(&self) -> String { "Shapes".to_string() }
+
+    fn tools(&self) -> Vec<Arc<FloTool<Anim>>> {
+        vec![
+            Arc::clone(&self.rectangle),
+            Arc::clone(&self.ellipse),
+            Arc::clone(&self.polygon),
+            Arc::clone(&self.line),
+            Arc::clone(&self.text)
+        ]
+    }
+}
@@ -12,6 +12,7 @@ pub const TIMESCALE_LAYERS:                 Color = Color::Rgba(0.42, 0.45, 0.45
 pub const TIMESCALE_CELL:                   Color = Color::Rgba(0.36, 0.4, 0.4, 1.0);
 pub const TIMESCALE_BACKGROUND:             Color = Color::Rgba(0.3, 0.3, 0.3, 1.0);
 pub const TIMESCALE_KEYFRAME:               Color = Color::Rgba(0.2, 0.6, 0.7, 1.0);
+pub const TIMESCALE_AUDIO:                  Color = Color::Rgba(0.5, 0.55, 0.45, 1.0);
 pub const TIMESCALE_INDICATOR:              Color = Color::Rgba(0.2, 0.6, 0.7, 1.0);
 pub const TIMESCALE_INDICATOR2:             Color = Color::Rgba(0.5, 0.85, 1.0, 1.0);
 pub const TIMESCALE_INDICATOR_OUTER_GLOW:   Color = Color::Rgba(0.2, 0.5, 0.8, 1.0);
@@ -40,4 +41,7 @@ pub const CP_LINES:                         Color = Color::Rgba(0.6, 0.6, 0.6, 0
 pub const ONIONSKIN_PAST:                   Color = Color::Rgba(0.8, 0.3, 0.3, 1.0);
 pub const ONIONSKIN_FUTURE:                 Color = Color::Rgba(0.3, 0.6, 0.8, 1.0);
 
+pub const ANNOTATION_MARKER:                Color = Color::Rgba(1.0, 0.8, 0.2, 1.0);
+pub const ANNOTATION_RESOLVED_MARKER:       Color = Color::Rgba(0.6, 0.6, 0.6, 1.0);
+
 pub const FILE_CHOOSER_BACKGROUND:          Color = Color::Rgba(0.165, 0.250, 0.198, 1.0);
@@ -17,5 +17,8 @@ typedef enum FragmentInputIndex {
     FragmentIndexTexture        = 0,
 
     /// The eraser texture to render
-    FragmentIndexEraseTexture   = 1
+    FragmentIndexEraseTexture   = 1,
+
+    /// The alpha value to apply to a texture
+    FragmentIndexAlpha          = 2
 } FragmentInputIndex;
@@ -68,7 +68,8 @@ fn compile_metal() {
     compile_metal_shader("shaders/simple/simple.metal", "simple.air");
     compile_metal_shader("shaders/simple/texture_fragment.metal", "texture_fragment.air");
     compile_metal_shader("shaders/simple/eraser.metal", "eraser.air");
-    link_metal_shaders(vec!["simple.air", "texture_fragment.air", "eraser.air"], "flo.metallib");
+    compile_metal_shader("shaders/simple/fill_texture.metal", "fill_texture.air");
+    link_metal_shaders(vec!["simple.air", "texture_fragment.air", "eraser.air", "fill_texture.air"], "flo.metallib");
 
     // Generate .rs files from the binding headers
     println!("cargo:rerun-if-changed=bindings");
@@ -0,0 +1,64 @@
+#include <metal_stdlib>
+
+#import "./bindings/metal_vertex2d.h"
+#import "rasterizer.metal"
+
+///
+/// Reads how much of a fragment has been erased by an eraser texture
+///
+float eraser_alpha(RasterizerData in, metal::texture2d_ms<half> eraser_texture) {
+    // Work out the coordinates in the eraser texture (which applies to the whole screen)
+    float2 paperCoord           = in.v_PaperCoord;
+    paperCoord[0]               *= float(eraser_texture.get_width());
+    paperCoord[1]               *= float(eraser_texture.get_height());
+
+    // Sample the eraser
+    const uint num_samples      = eraser_texture.get_num_samples();
+    const uint2 eraser_coord    = uint2(paperCoord);
+    half eraser_total           = 0;
+
+    for (uint sample_num=0; sample_num<num_samples; ++sample_num) {
+        const half4 sample      = eraser_texture.read(eraser_coord, sample_num);
+        eraser_total            += sample[0];
+    }
+
+    return float(eraser_total) / float(num_samples);
+}
+
+fragment float4 fill_texture_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]]) {
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear, metal::address::clamp_to_edge);
+
+    return float4(texture.sample(texture_sampler, in.v_TexCoord)) * in.v_Color;
+}
+
+fragment float4 fill_texture_repeat_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]]) {
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear, metal::address::repeat);
+
+    return float4(texture.sample(texture_sampler, in.v_TexCoord)) * in.v_Color;
+}
+
+fragment float4 fill_texture_eraser_multisample_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]],
+    metal::texture2d_ms<half>   eraser_texture [[ texture(FragmentIndexEraseTexture) ]]) {
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear, metal::address::clamp_to_edge);
+
+    const float4 color          = float4(texture.sample(texture_sampler, in.v_TexCoord)) * in.v_Color;
+
+    return color * (1-eraser_alpha(in, eraser_texture));
+}
+
+fragment float4 fill_texture_repeat_eraser_multisample_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]],
+    metal::texture2d_ms<half>   eraser_texture [[ texture(FragmentIndexEraseTexture) ]]) {
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear, metal::address::repeat);
+
+    const float4 color          = float4(texture.sample(texture_sampler, in.v_TexCoord)) * in.v_Color;
+
+    return color * (1-eraser_alpha(in, eraser_texture));
+}
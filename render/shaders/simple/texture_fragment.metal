@@ -3,19 +3,24 @@
 #import "./bindings/metal_vertex2d.h"
 #import "rasterizer.metal"
 
-fragment float4 texture_fragment(
-    RasterizerData              in [[stage_in]],
-    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]]) {
+///
+/// Reads the colour of a texture at a fragment, multiplying its alpha channel by the specified value
+///
+float4 texture_color(RasterizerData in, metal::texture2d<half> texture, float alpha) {
     constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear);
 
     const half4 color_sample = texture.sample(texture_sampler, in.v_TexCoord);
 
-    return float4(color_sample);
+    float4 color                = float4(color_sample);
+    color[3]                    *= alpha;
+
+    return color;
 }
 
-fragment float4 texture_multisample_fragment(
-    RasterizerData              in [[stage_in]],
-    metal::texture2d_ms<half>   texture [[ texture(FragmentIndexTexture) ]]) {
+///
+/// Reads the colour of a multisampled texture at a fragment, multiplying its alpha channel by the specified value
+///
+float4 texture_multisample_color(RasterizerData in, metal::texture2d_ms<half> texture, float alpha) {
     const uint num_samples      = texture.get_num_samples();
     const uint2 tex_coord       = uint2(in.v_TexCoord);
     half4 color_totals          = half4(0,0,0,0);
@@ -27,6 +32,58 @@ fragment float4 texture_multisample_fragment(
 
     float4 color                = float4(color_totals);
     color /= float(num_samples);
+    color[3]                    *= alpha;
 
     return color;
 }
+
+///
+/// Replaces the colour of the transparent parts of a frame buffer with a colour that leaves the destination unchanged (for the blend modes that ignore the source alpha)
+///
+float4 transparent_color(float4 color, float transparent) {
+    const float3 rgb            = metal::mix(float3(transparent, transparent, transparent), color.rgb, color[3]);
+
+    return float4(rgb, color[3]);
+}
+
+fragment float4 texture_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]],
+    constant float              &alpha  [[ buffer(FragmentIndexAlpha) ]]) {
+    return texture_color(in, texture, alpha);
+}
+
+fragment float4 texture_multisample_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d_ms<half>   texture [[ texture(FragmentIndexTexture) ]],
+    constant float              &alpha  [[ buffer(FragmentIndexAlpha) ]]) {
+    return texture_multisample_color(in, texture, alpha);
+}
+
+fragment float4 texture_transparent_white_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]],
+    constant float              &alpha  [[ buffer(FragmentIndexAlpha) ]]) {
+    return transparent_color(texture_color(in, texture, alpha), 1.0);
+}
+
+fragment float4 texture_transparent_black_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d<half>      texture [[ texture(FragmentIndexTexture) ]],
+    constant float              &alpha  [[ buffer(FragmentIndexAlpha) ]]) {
+    return transparent_color(texture_color(in, texture, alpha), 0.0);
+}
+
+fragment float4 texture_multisample_transparent_white_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d_ms<half>   texture [[ texture(FragmentIndexTexture) ]],
+    constant float              &alpha  [[ buffer(FragmentIndexAlpha) ]]) {
+    return transparent_color(texture_multisample_color(in, texture, alpha), 1.0);
+}
+
+fragment float4 texture_multisample_transparent_black_fragment(
+    RasterizerData              in [[stage_in]],
+    metal::texture2d_ms<half>   texture [[ texture(FragmentIndexTexture) ]],
+    constant float              &alpha  [[ buffer(FragmentIndexAlpha) ]]) {
+    return transparent_color(texture_multisample_color(in, texture, alpha), 0.0);
+}
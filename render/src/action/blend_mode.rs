@@ -1,6 +1,10 @@
 ///
 /// The blending modes that the renderer must support (most of the Porter-Duff modes)
 ///
+/// The Multiply, Screen, Darken and Lighten modes are for blending frame buffers with `BlendFrameBuffer`: the transparent parts
+/// of the source frame buffer are treated as the colour that leaves the destination unchanged, and the destination is treated
+/// as opaque.
+///
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BlendMode {
     SourceOver,
@@ -13,5 +17,10 @@ pub enum BlendMode {
     DestinationATop,
 
     AllChannelAlphaSourceOver,
-    AllChannelAlphaDestinationOver
+    AllChannelAlphaDestinationOver,
+
+    Multiply,
+    Screen,
+    Darken,
+    Lighten
 }
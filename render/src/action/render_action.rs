@@ -7,6 +7,7 @@ use super::shader_type::*;
 use crate::buffer::*;
 
 use std::ops::{Range};
+use std::sync::*;
 
 ///
 /// Represents an action for a render target
@@ -73,11 +74,24 @@ pub enum RenderAction {
     ///
     DrawFrameBuffer(RenderTargetId, i32, i32),
 
+    ///
+    /// Blends the specified render target onto the current render target using the current blend mode, with its alpha channel
+    /// multiplied by the specified value. The two render targets should be the same size.
+    ///
+    BlendFrameBuffer(RenderTargetId, f32),
+
     ///
     /// Creates an 8-bit BGRA texture of the specified size
     ///
     CreateTextureBgra(TextureId, usize, usize),
 
+    ///
+    /// Writes 8-bit BGRA pixel data to a region of a texture, given by its lower-left and upper-right corners
+    ///
+    /// The rows of pixels are stored from the bottom of the region upwards.
+    ///
+    WriteTextureData(TextureId, (usize, usize), (usize, usize), Arc<Vec<u8>>),
+
     ///
     /// Frees up an existing texture
     ///
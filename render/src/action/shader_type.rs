@@ -8,4 +8,9 @@ pub enum ShaderType {
     /// Flat colour shader
     /// The erase texture (which should be a MSAA texture) is subtracted from anything drawn, if present
     Simple { erase_texture: Option<TextureId> },
+
+    /// Fills with a texture read using the texture coordinates of each vertex, multiplied by the vertex colour
+    /// If repeat is true the texture is tiled, otherwise the colours at its edges extend outwards. The erase texture
+    /// is applied in the same way as for the simple shader.
+    Texture { texture: TextureId, repeat: bool, erase_texture: Option<TextureId> },
 }
@@ -42,7 +42,25 @@ pub struct GlRenderer {
     simple_shader: ShaderProgram<ShaderUniform>,
 
     /// The shader program that applies an erase buffer
-    simple_shader_with_erase: ShaderProgram<ShaderUniform>
+    simple_shader_with_erase: ShaderProgram<ShaderUniform>,
+
+    /// The shader program that fills shapes using a texture
+    texture_shader: ShaderProgram<ShaderUniform>,
+
+    /// The shader program that fills shapes using a texture and applies an erase buffer
+    texture_shader_with_erase: ShaderProgram<ShaderUniform>,
+
+    /// The blend mode that's currently in use
+    blend_mode: BlendMode,
+
+    /// The shader program used to blend a frame buffer onto the current render target
+    framebuffer_shader: ShaderProgram<ShaderUniform>,
+
+    /// The shader program used to blend a frame buffer where its transparent parts should be treated as white
+    framebuffer_shader_transparent_white: ShaderProgram<ShaderUniform>,
+
+    /// The shader program used to blend a frame buffer where its transparent parts should be treated as black
+    framebuffer_shader_transparent_black: ShaderProgram<ShaderUniform>
 }
 
 impl GlRenderer {
@@ -58,6 +76,26 @@ impl GlRenderer {
         let simple_erase_fragment_shader    = Shader::compile(&(String::from("#version 330 core\n#define ERASE_MASK\n") + &String::from_utf8(include_bytes!["../../shaders/simple/simple.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
         let simple_shader_with_erase        = ShaderProgram::from_shaders(vec![simple_vertex_shader, simple_erase_fragment_shader]);
 
+        let simple_vertex_shader            = Shader::compile(&String::from_utf8(include_bytes!["../../shaders/simple/simple.glslv"].to_vec()).unwrap(), GlShaderType::Vertex, vec!["a_Pos", "a_Color", "a_TexCoord"]);
+        let texture_fragment_shader         = Shader::compile(&(String::from("#version 330 core\n") + &String::from_utf8(include_bytes!["../../shaders/simple/texture.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
+        let texture_shader                  = ShaderProgram::from_shaders(vec![simple_vertex_shader, texture_fragment_shader]);
+
+        let simple_vertex_shader            = Shader::compile(&String::from_utf8(include_bytes!["../../shaders/simple/simple.glslv"].to_vec()).unwrap(), GlShaderType::Vertex, vec!["a_Pos", "a_Color", "a_TexCoord"]);
+        let texture_erase_fragment_shader   = Shader::compile(&(String::from("#version 330 core\n#define ERASE_MASK\n") + &String::from_utf8(include_bytes!["../../shaders/simple/texture.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
+        let texture_shader_with_erase       = ShaderProgram::from_shaders(vec![simple_vertex_shader, texture_erase_fragment_shader]);
+
+        let simple_vertex_shader            = Shader::compile(&String::from_utf8(include_bytes!["../../shaders/simple/simple.glslv"].to_vec()).unwrap(), GlShaderType::Vertex, vec!["a_Pos", "a_Color", "a_TexCoord"]);
+        let framebuffer_fragment_shader     = Shader::compile(&(String::from("#version 330 core\n") + &String::from_utf8(include_bytes!["../../shaders/simple/framebuffer.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
+        let framebuffer_shader              = ShaderProgram::from_shaders(vec![simple_vertex_shader, framebuffer_fragment_shader]);
+
+        let simple_vertex_shader            = Shader::compile(&String::from_utf8(include_bytes!["../../shaders/simple/simple.glslv"].to_vec()).unwrap(), GlShaderType::Vertex, vec!["a_Pos", "a_Color", "a_TexCoord"]);
+        let framebuffer_white_fragment      = Shader::compile(&(String::from("#version 330 core\n#define TRANSPARENT_WHITE\n") + &String::from_utf8(include_bytes!["../../shaders/simple/framebuffer.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
+        let framebuffer_shader_transparent_white = ShaderProgram::from_shaders(vec![simple_vertex_shader, framebuffer_white_fragment]);
+
+        let simple_vertex_shader            = Shader::compile(&String::from_utf8(include_bytes!["../../shaders/simple/simple.glslv"].to_vec()).unwrap(), GlShaderType::Vertex, vec!["a_Pos", "a_Color", "a_TexCoord"]);
+        let framebuffer_black_fragment      = Shader::compile(&(String::from("#version 330 core\n#define TRANSPARENT_BLACK\n") + &String::from_utf8(include_bytes!["../../shaders/simple/framebuffer.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
+        let framebuffer_shader_transparent_black = ShaderProgram::from_shaders(vec![simple_vertex_shader, framebuffer_black_fragment]);
+
         GlRenderer {
             buffers:                    vec![],
             index_buffers:              vec![],
@@ -67,7 +105,13 @@ impl GlRenderer {
             transform_matrix:           None,
             render_targets:             vec![],
             simple_shader:              simple_shader,
-            simple_shader_with_erase:   simple_shader_with_erase
+            simple_shader_with_erase:   simple_shader_with_erase,
+            texture_shader:             texture_shader,
+            texture_shader_with_erase:  texture_shader_with_erase,
+            blend_mode:                 BlendMode::SourceOver,
+            framebuffer_shader:         framebuffer_shader,
+            framebuffer_shader_transparent_white: framebuffer_shader_transparent_white,
+            framebuffer_shader_transparent_black: framebuffer_shader_transparent_black
         }
     }
 
@@ -115,8 +159,10 @@ impl GlRenderer {
                 SelectRenderTarget(render_id)                                           => { self.select_render_target(render_id); }
                 RenderToFrameBuffer                                                     => { self.select_main_frame_buffer(); }
                 DrawFrameBuffer(render_id, x, y)                                        => { self.draw_frame_buffer(render_id, x, y); }
+                BlendFrameBuffer(render_id, alpha)                                      => { self.blend_frame_buffer(render_id, alpha); }
                 ShowFrameBuffer                                                         => { /* This doesn't double-buffer so nothing to do */ }
                 CreateTextureBgra(texture_id, width, height)                            => { self.create_bgra_texture(texture_id, width, height); }
+                WriteTextureData(texture_id, lower_left, upper_right, data)             => { self.write_texture_data(texture_id, lower_left, upper_right, &*data); }
                 FreeTexture(texture_id)                                                 => { self.free_texture(texture_id); }
                 Clear(color)                                                            => { self.clear(color); }
                 UseShader(shader_type)                                                  => { self.use_shader(shader_type); }
@@ -250,7 +296,16 @@ impl GlRenderer {
     fn blend_mode(&mut self, blend_mode: BlendMode) {
         use self::BlendMode::*;
 
+        self.blend_mode = blend_mode;
+
         unsafe {
+            // Darken and lighten take the minimum or maximum colour, and every other mode adds the source and destination
+            match blend_mode {
+                Darken  => gl::BlendEquationSeparate(gl::MIN, gl::FUNC_ADD),
+                Lighten => gl::BlendEquationSeparate(gl::MAX, gl::FUNC_ADD),
+                _       => gl::BlendEquationSeparate(gl::FUNC_ADD, gl::FUNC_ADD)
+            }
+
             match blend_mode {
                 SourceOver          => gl::BlendFuncSeparate(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA, gl::ONE, gl::ONE_MINUS_SRC_ALPHA),
                 DestinationOver     => gl::BlendFuncSeparate(gl::ONE_MINUS_DST_ALPHA, gl::DST_ALPHA, gl::ONE_MINUS_DST_ALPHA, gl::ONE),
@@ -263,6 +318,11 @@ impl GlRenderer {
 
                 AllChannelAlphaSourceOver       => gl::BlendFuncSeparate(gl::ONE, gl::ONE_MINUS_SRC_COLOR, gl::ONE, gl::ONE_MINUS_SRC_ALPHA),
                 AllChannelAlphaDestinationOver  => gl::BlendFuncSeparate(gl::ONE_MINUS_DST_COLOR, gl::ONE, gl::ONE_MINUS_DST_ALPHA, gl::ONE),
+
+                Multiply                        => gl::BlendFuncSeparate(gl::ZERO, gl::SRC_COLOR, gl::ONE, gl::ONE_MINUS_SRC_ALPHA),
+                Screen                          => gl::BlendFuncSeparate(gl::ONE, gl::ONE_MINUS_SRC_COLOR, gl::ONE, gl::ONE_MINUS_SRC_ALPHA),
+                Darken                          => gl::BlendFuncSeparate(gl::ONE, gl::ONE, gl::ONE, gl::ONE_MINUS_SRC_ALPHA),
+                Lighten                         => gl::BlendFuncSeparate(gl::ONE, gl::ONE, gl::ONE, gl::ONE_MINUS_SRC_ALPHA),
             }
         }
     }
@@ -289,6 +349,15 @@ impl GlRenderer {
         self.textures[texture_id] = Some(new_texture);
     }
 
+    ///
+    /// Writes BGRA pixel data to a region of a texture
+    ///
+    fn write_texture_data(&mut self, TextureId(texture_id): TextureId, (x1, y1): (usize, usize), (x2, y2): (usize, usize), data: &[u8]) {
+        if let Some(Some(texture)) = self.textures.get_mut(texture_id) {
+            texture.set_data_bgra(x1 as u16, y1 as u16, (x2-x1) as u16, (y2-y1) as u16, data);
+        }
+    }
+
     ///
     /// Releases an existing render target
     ///
@@ -364,6 +433,71 @@ impl GlRenderer {
         });
     }
 
+    ///
+    /// Blends a frame buffer onto the current render target using the current blend mode, multiplying its alpha channel by the specified value
+    ///
+    /// The source render target must have a multisampled texture, and should be the same size as the current render target
+    ///
+    fn blend_frame_buffer(&mut self, RenderTargetId(source_buffer): RenderTargetId, alpha: f32) {
+        let texture = match self.render_targets[source_buffer].as_ref().and_then(|source_buffer| source_buffer.texture()) {
+            Some(texture)   => texture,
+            None            => { return; }
+        };
+
+        unsafe {
+            // Cover the whole render target with a pair of triangles
+            let vertices            = vec![
+                Vertex2D { pos: [-1.0, -1.0], tex_coord: [0.0, 0.0], color: [0, 0, 0, 0] },
+                Vertex2D { pos: [1.0, -1.0], tex_coord: [0.0, 0.0], color: [0, 0, 0, 0] },
+                Vertex2D { pos: [1.0, 1.0], tex_coord: [0.0, 0.0], color: [0, 0, 0, 0] },
+
+                Vertex2D { pos: [-1.0, -1.0], tex_coord: [0.0, 0.0], color: [0, 0, 0, 0] },
+                Vertex2D { pos: [1.0, 1.0], tex_coord: [0.0, 0.0], color: [0, 0, 0, 0] },
+                Vertex2D { pos: [-1.0, 1.0], tex_coord: [0.0, 0.0], color: [0, 0, 0, 0] },
+            ];
+
+            let mut buffer          = Buffer::new();
+            let vertex_array        = VertexArray::new();
+            buffer.static_draw(&vertices);
+
+            gl::BindVertexArray(*vertex_array);
+            gl::BindBuffer(gl::ARRAY_BUFFER, *buffer);
+            Vertex2D::define_attributes();
+
+            // Set up the framebuffer shader (the blend modes that ignore the source alpha need the transparent parts of the frame buffer to be a neutral colour)
+            let identity            = Matrix::identity().to_opengl_matrix();
+            let framebuffer_shader  = match self.blend_mode {
+                BlendMode::Multiply | BlendMode::Darken => &mut self.framebuffer_shader_transparent_white,
+                BlendMode::Screen | BlendMode::Lighten  => &mut self.framebuffer_shader_transparent_black,
+                _                                       => &mut self.framebuffer_shader
+            };
+            gl::UseProgram(**framebuffer_shader);
+
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D_MULTISAMPLE, *texture);
+
+            framebuffer_shader.uniform_location(ShaderUniform::Transform, "transform")
+                .map(|transform_uniform| gl::UniformMatrix4fv(transform_uniform, 1, gl::FALSE, identity.as_ptr()));
+            framebuffer_shader.uniform_location(ShaderUniform::FrameBufferTexture, "t_FrameBuffer")
+                .map(|texture_uniform| gl::Uniform1i(texture_uniform, 0));
+            framebuffer_shader.uniform_location(ShaderUniform::FrameBufferAlpha, "alpha")
+                .map(|alpha_uniform| gl::Uniform1f(alpha_uniform, alpha));
+
+            // Draw the frame buffer
+            gl::DrawArrays(gl::TRIANGLES, 0, 6);
+
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+        }
+
+        // Restore the shader that was in use before
+        if let Some(active_shader) = self.active_shader {
+            self.use_shader(active_shader);
+        } else {
+            unsafe { gl::UseProgram(*self.simple_shader); }
+        }
+    }
+
     ///
     /// Releases an existing render target
     ///
@@ -398,6 +532,38 @@ impl GlRenderer {
                     }
 
                 }
+
+                Texture { texture: TextureId(texture_id), repeat, erase_texture } => {
+                    let shader = if erase_texture.is_some() { &mut self.texture_shader_with_erase } else { &mut self.texture_shader };
+                    gl::UseProgram(**shader);
+
+                    if let Some(TextureId(erase_texture_id)) = erase_texture {
+                        if let Some(erase_texture) = &self.textures[erase_texture_id] {
+                            // Set the erase texture
+                            gl::ActiveTexture(gl::TEXTURE0);
+                            gl::BindTexture(gl::TEXTURE_2D_MULTISAMPLE, **erase_texture);
+
+                            shader.uniform_location(ShaderUniform::EraseTexture, "t_EraseMask")
+                                .map(|erase_mask| {
+                                    gl::Uniform1i(erase_mask, 0);
+                                });
+                        }
+                    }
+
+                    if let Some(Some(texture)) = self.textures.get_mut(texture_id) {
+                        // The fill texture goes in the second texture unit so it doesn't clash with the erase mask
+                        texture.set_repeat(repeat);
+
+                        gl::ActiveTexture(gl::TEXTURE1);
+                        gl::BindTexture(gl::TEXTURE_2D, **texture);
+                        gl::ActiveTexture(gl::TEXTURE0);
+
+                        shader.uniform_location(ShaderUniform::FillTexture, "t_Texture")
+                            .map(|fill_texture| {
+                                gl::Uniform1i(fill_texture, 1);
+                            });
+                    }
+                }
             }
 
             // Set the transform for the newly selected shader
@@ -460,6 +626,8 @@ impl GlRenderer {
             let shader = match &self.active_shader {
                 Some(Simple { erase_texture: None })        => Some(&mut self.simple_shader),
                 Some(Simple { erase_texture: Some(_) })     => Some(&mut self.simple_shader_with_erase),
+                Some(Texture { erase_texture: None, .. })   => Some(&mut self.texture_shader),
+                Some(Texture { erase_texture: Some(_), .. })=> Some(&mut self.texture_shader_with_erase),
 
                 None                                        => None
             };
@@ -7,5 +7,14 @@ pub enum ShaderUniform {
     Transform,
     
     /// The texture bound to the 'erase' operation
-    EraseTexture
+    EraseTexture,
+
+    /// The texture used to fill shapes by the texture shader
+    FillTexture,
+
+    /// The texture containing the frame buffer being blended by the framebuffer shader
+    FrameBufferTexture,
+
+    /// The alpha value to apply when blending a frame buffer
+    FrameBufferAlpha
 }
@@ -51,6 +51,35 @@ impl Texture {
         }
     }
 
+    ///
+    /// Writes BGRA pixel data to a region of this texture (which should have been created by `create_empty()`)
+    ///
+    pub fn set_data_bgra(&mut self, x: u16, y: u16, width: u16, height: u16, data: &[u8]) {
+        unsafe {
+            let texture_id = self.texture.texture_id;
+
+            gl::BindTexture(gl::TEXTURE_2D, texture_id);
+            gl::TexSubImage2D(gl::TEXTURE_2D, 0, x as gl::types::GLint, y as gl::types::GLint, width as gl::types::GLsizei, height as gl::types::GLsizei, gl::BGRA, gl::UNSIGNED_BYTE, data.as_ptr() as *const gl::types::GLvoid);
+
+            panic_on_gl_error("Write texture data");
+        }
+    }
+
+    ///
+    /// Sets whether or not this texture repeats when it's read outside of the range 0-1
+    ///
+    pub fn set_repeat(&mut self, repeat: bool) {
+        unsafe {
+            let texture_id  = self.texture.texture_id;
+            let wrap        = if repeat { gl::REPEAT } else { gl::CLAMP_TO_EDGE };
+
+            gl::TextureParameteri(texture_id, gl::TEXTURE_WRAP_S, wrap as i32);
+            gl::TextureParameteri(texture_id, gl::TEXTURE_WRAP_T, wrap as i32);
+
+            panic_on_gl_error("Set texture wrapping");
+        }
+    }
+
     ///
     /// Creates an empty MSAA texture
     ///
@@ -1,9 +1,11 @@
 mod action;
 mod buffer;
+mod software_renderer;
 #[cfg(feature="gl")] mod gl_renderer;
 #[cfg(feature="osx-metal")] mod metal_renderer;
 
 pub use self::action::*;
 pub use self::buffer::*;
+pub use self::software_renderer::{SoftwareRenderer, SoftwareFrameBuffer};
 #[cfg(feature="gl")] pub use self::gl_renderer::{GlRenderer};
 #[cfg(feature="osx-metal")] pub use self::metal_renderer::{MetalRenderer};
\ No newline at end of file
@@ -59,6 +59,9 @@ struct RenderState<'a> {
     /// The texture used in the eraser slot
     erase_texture: Option<metal::Texture>,
 
+    /// The texture used to fill shapes by the texture shader
+    fill_texture: Option<metal::Texture>,
+
     /// Buffer containing the current transformation matrix
     matrix: MatrixBuffer,
 
@@ -157,6 +160,7 @@ impl MetalRenderer {
         // Set the constant buffers
         state.command_encoder.set_vertex_buffer(VertexInputIndex_VertexInputIndexMatrix as u64, Some(&state.matrix), 0);
         state.command_encoder.set_fragment_texture(FragmentInputIndex_FragmentIndexEraseTexture as u64, state.erase_texture.as_ref().map::<&metal::TextureRef, _>(|t| t));
+        state.command_encoder.set_fragment_texture(FragmentInputIndex_FragmentIndexTexture as u64, state.fill_texture.as_ref().map::<&metal::TextureRef, _>(|t| t));
     }
 
     ///
@@ -176,6 +180,7 @@ impl MetalRenderer {
             main_texture:           target_texture.clone(),
             target_texture:         target_texture.clone(),
             erase_texture:          None,
+            fill_texture:           None,
             matrix:                 matrix,
             pipeline_config:        pipeline_config,
             pipeline_state:         pipeline_state,
@@ -200,9 +205,11 @@ impl MetalRenderer {
                 FreeRenderTarget(render_id)                                             => { self.free_render_target(render_id); }
                 SelectRenderTarget(render_id)                                           => { self.select_render_target(render_id, &mut render_state); }
                 RenderToFrameBuffer                                                     => { self.select_main_frame_buffer(&mut render_state); }
-                DrawFrameBuffer(render_id, x, y)                                        => { self.draw_frame_buffer(render_id, x, y, &mut render_state); }
+                DrawFrameBuffer(render_id, x, y)                                        => { self.draw_frame_buffer(render_id, x, y, BlendMode::SourceOver, 1.0, &mut render_state); }
+                BlendFrameBuffer(render_id, alpha)                                      => { let blend_mode = render_state.pipeline_config.blend_mode; self.draw_frame_buffer(render_id, 0, 0, blend_mode, alpha, &mut render_state); }
                 ShowFrameBuffer                                                         => { /* This doesn't double-buffer so nothing to do */ }
                 CreateTextureBgra(texture_id, width, height)                            => { self.create_bgra_texture(texture_id, width, height); }
+                WriteTextureData(texture_id, lower_left, upper_right, data)             => { self.write_texture_data(texture_id, lower_left, upper_right, &*data); }
                 FreeTexture(texture_id)                                                 => { self.free_texture(texture_id); }
                 Clear(color)                                                            => { self.clear(color, &mut render_state); }
                 UseShader(shader_type)                                                  => { self.use_shader(shader_type, &mut render_state); }
@@ -371,9 +378,10 @@ impl MetalRenderer {
     }
 
     ///
-    /// Renders a frame buffer to another texture (resolving multi-sampling if there is any)
+    /// Renders a frame buffer to another texture (resolving multi-sampling if there is any), using the specified blend mode and
+    /// multiplying its alpha channel by the specified value
     ///
-    fn draw_frame_buffer(&mut self, RenderTargetId(source_buffer): RenderTargetId, x: i32, y: i32, state: &mut RenderState) {
+    fn draw_frame_buffer(&mut self, RenderTargetId(source_buffer): RenderTargetId, x: i32, y: i32, blend_mode: BlendMode, alpha: f32, state: &mut RenderState) {
         let render_targets  = &self.render_targets;
 
         if let Some(source_buffer) = &render_targets[source_buffer] {
@@ -387,8 +395,17 @@ impl MetalRenderer {
 
             // Basic vertex shader and blend mode
             config.vertex_shader    = String::from("simple_vertex");
-            config.blend_mode       = BlendMode::SourceOver;
-            config.fragment_shader  = if source_buffer.is_multisampled() { String::from("texture_multisample_fragment") } else { String::from("texture_fragment") };
+            config.blend_mode       = blend_mode;
+            config.fragment_shader  = match (source_buffer.is_multisampled(), blend_mode) {
+                // The blend modes that ignore the source alpha need the transparent parts of the frame buffer to be a neutral colour
+                (true, BlendMode::Multiply) | (true, BlendMode::Darken)     => String::from("texture_multisample_transparent_white_fragment"),
+                (true, BlendMode::Screen) | (true, BlendMode::Lighten)      => String::from("texture_multisample_transparent_black_fragment"),
+                (false, BlendMode::Multiply) | (false, BlendMode::Darken)   => String::from("texture_transparent_white_fragment"),
+                (false, BlendMode::Screen) | (false, BlendMode::Lighten)    => String::from("texture_transparent_black_fragment"),
+
+                (true, _)                                                   => String::from("texture_multisample_fragment"),
+                (false, _)                                                  => String::from("texture_fragment")
+            };
 
             // Convert to a pipeline state
             let pipeline_state      = self.get_pipeline_state(&config);
@@ -419,20 +436,55 @@ impl MetalRenderer {
             state.command_encoder.set_vertex_buffer(VertexInputIndex_VertexInputIndexMatrix as u64, Some(&viewport_matrix), 0);
             state.command_encoder.set_vertex_buffer(VertexInputIndex_VertexInputIndexVertices as u64, Some(&triangle_strip), 0);
             state.command_encoder.set_fragment_texture(FragmentInputIndex_FragmentIndexTexture as u64, Some(&source_texture));
+            state.command_encoder.set_fragment_bytes(FragmentInputIndex_FragmentIndexAlpha as u64, std::mem::size_of::<f32>() as u64, &alpha as *const f32 as *const std::ffi::c_void);
 
             // Draw the texture
             state.command_encoder.draw_primitives(metal::MTLPrimitiveType::TriangleStrip, 0, 4);
 
             // Reset the pipeline state to the one in the render state
-            state.command_encoder.set_fragment_texture(FragmentInputIndex_FragmentIndexTexture as u64, None);
-
             state.command_encoder.set_render_pipeline_state(&state.pipeline_state);
             self.setup_command_encoder(state);
         }
     }
 
+    ///
+    /// Creates a BGRA texture that can be read by the shaders
+    ///
     fn create_bgra_texture(&mut self, TextureId(texture_id): TextureId, width: usize, height: usize) {
+        // Allocate space for the texture
+        if texture_id >= self.textures.len() {
+            self.textures.extend((self.textures.len()..(texture_id+1))
+                .into_iter()
+                .map(|_| None));
+        }
+
+        // Free any existing texture
+        self.textures[texture_id] = None;
 
+        // Create the new texture
+        let texture_descriptor = metal::TextureDescriptor::new();
+
+        texture_descriptor.set_texture_type(metal::MTLTextureType::D2);
+        texture_descriptor.set_width(width as u64);
+        texture_descriptor.set_height(height as u64);
+        texture_descriptor.set_pixel_format(metal::MTLPixelFormat::BGRA8Unorm);
+        texture_descriptor.set_usage(metal::MTLTextureUsage::ShaderRead);
+
+        self.textures[texture_id] = Some(self.device.new_texture(&texture_descriptor));
+    }
+
+    ///
+    /// Writes BGRA pixel data to a region of a texture
+    ///
+    fn write_texture_data(&mut self, TextureId(texture_id): TextureId, (x1, y1): (usize, usize), (x2, y2): (usize, usize), data: &[u8]) {
+        if let Some(Some(texture)) = self.textures.get(texture_id) {
+            let region = metal::MTLRegion {
+                origin: metal::MTLOrigin { x: x1 as u64, y: y1 as u64, z: 0 },
+                size:   metal::MTLSize { width: (x2-x1) as u64, height: (y2-y1) as u64, depth: 1 }
+            };
+
+            texture.replace_region(region, 0, ((x2-x1)*4) as u64, data.as_ptr() as *const std::ffi::c_void);
+        }
     }
 
     ///
@@ -459,6 +511,7 @@ impl MetalRenderer {
     fn use_shader(&mut self, shader_type: ShaderType, state: &mut RenderState) {
         // Reset the current shader state
         state.erase_texture = None;
+        state.fill_texture  = None;
 
         // Update the state according to the shader type
         match shader_type {
@@ -470,6 +523,17 @@ impl MetalRenderer {
                 state.pipeline_config.fragment_shader   = String::from("simple_eraser_multisample_fragment");
                 state.erase_texture                     = self.textures[texture_id].clone();
             }
+
+            ShaderType::Texture { texture: TextureId(texture_id), repeat, erase_texture } => {
+                state.pipeline_config.fragment_shader   = String::from(match (repeat, erase_texture.is_some()) {
+                    (false, false)  => "fill_texture_fragment",
+                    (true, false)   => "fill_texture_repeat_fragment",
+                    (false, true)   => "fill_texture_eraser_multisample_fragment",
+                    (true, true)    => "fill_texture_repeat_eraser_multisample_fragment"
+                });
+                state.fill_texture                      = self.textures[texture_id].clone();
+                state.erase_texture                     = erase_texture.and_then(|TextureId(erase_texture_id)| self.textures[erase_texture_id].clone());
+            }
         }
 
         // Update the command encoder with the new state
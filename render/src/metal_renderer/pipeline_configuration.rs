@@ -80,7 +80,7 @@ impl PipelineConfiguration {
 
         // Set the blend mode
         use self::BlendMode::*;
-        use metal::MTLBlendFactor::{SourceAlpha, OneMinusSourceAlpha, One, DestinationAlpha, OneMinusDestinationAlpha, Zero, OneMinusSourceColor, OneMinusDestinationColor};
+        use metal::MTLBlendFactor::{SourceAlpha, OneMinusSourceAlpha, One, DestinationAlpha, OneMinusDestinationAlpha, Zero, SourceColor, OneMinusSourceColor, OneMinusDestinationColor};
         let (src_rgb, dst_rgb, src_alpha, dst_alpha) = match self.blend_mode {
             SourceOver                      => (SourceAlpha, OneMinusSourceAlpha, One, OneMinusSourceAlpha),
             DestinationOver                 => (OneMinusDestinationAlpha, DestinationAlpha, OneMinusDestinationAlpha, One),
@@ -92,7 +92,19 @@ impl PipelineConfiguration {
             DestinationATop                 => (OneMinusDestinationAlpha, OneMinusSourceAlpha, OneMinusDestinationAlpha, OneMinusSourceAlpha),
 
             AllChannelAlphaSourceOver       => (One, OneMinusSourceColor, One, OneMinusSourceAlpha),
-            AllChannelAlphaDestinationOver  => (OneMinusDestinationColor, One, OneMinusDestinationAlpha, One)
+            AllChannelAlphaDestinationOver  => (OneMinusDestinationColor, One, OneMinusDestinationAlpha, One),
+
+            Multiply                        => (Zero, SourceColor, One, OneMinusSourceAlpha),
+            Screen                          => (One, OneMinusSourceColor, One, OneMinusSourceAlpha),
+            Darken                          => (One, One, One, OneMinusSourceAlpha),
+            Lighten                         => (One, One, One, OneMinusSourceAlpha)
+        };
+
+        // Darken and lighten take the minimum or maximum colour, and every other mode adds the source and destination
+        let rgb_operation = match self.blend_mode {
+            Darken  => metal::MTLBlendOperation::Min,
+            Lighten => metal::MTLBlendOperation::Max,
+            _       => metal::MTLBlendOperation::Add
         };
 
         descriptor.color_attachments().object_at(0).unwrap().set_pixel_format(self.pixel_format);
@@ -101,6 +113,8 @@ impl PipelineConfiguration {
         descriptor.color_attachments().object_at(0).unwrap().set_destination_rgb_blend_factor(dst_rgb);
         descriptor.color_attachments().object_at(0).unwrap().set_source_alpha_blend_factor(src_alpha);
         descriptor.color_attachments().object_at(0).unwrap().set_destination_alpha_blend_factor(dst_alpha);
+        descriptor.color_attachments().object_at(0).unwrap().set_rgb_blend_operation(rgb_operation);
+        descriptor.color_attachments().object_at(0).unwrap().set_alpha_blend_operation(metal::MTLBlendOperation::Add);
 
         // Create the state
         device.new_render_pipeline_state(&descriptor).unwrap()
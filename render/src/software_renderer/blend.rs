@@ -0,0 +1,145 @@
+use crate::action::*;
+
+///
+/// The factors that the source and destination values can be multiplied by when blending (these work the same way as the OpenGL blend functions)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    DstColor,
+    OneMinusDstColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstAlpha,
+    OneMinusDstAlpha
+}
+
+///
+/// The operations that combine the source and destination values when blending (these work the same way as the OpenGL blend equations)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BlendOperation {
+    /// Adds the source and destination values after multiplying them by their blend factors
+    Add,
+
+    /// Takes the smaller of the source and destination values (the blend factors are ignored)
+    Min,
+
+    /// Takes the larger of the source and destination values (the blend factors are ignored)
+    Max
+}
+
+///
+/// The blend factors for a blend mode, as (source colour, destination colour, source alpha, destination alpha)
+///
+pub fn blend_factors(blend_mode: BlendMode) -> (BlendFactor, BlendFactor, BlendFactor, BlendFactor) {
+    use self::BlendMode::*;
+    use self::BlendFactor::*;
+
+    match blend_mode {
+        SourceOver                      => (SrcAlpha, OneMinusSrcAlpha, One, OneMinusSrcAlpha),
+        DestinationOver                 => (OneMinusDstAlpha, DstAlpha, OneMinusDstAlpha, One),
+        SourceIn                        => (DstAlpha, Zero, DstAlpha, Zero),
+        DestinationIn                   => (Zero, SrcAlpha, Zero, SrcAlpha),
+        SourceOut                       => (Zero, OneMinusDstAlpha, Zero, OneMinusDstAlpha),
+        DestinationOut                  => (Zero, OneMinusSrcAlpha, Zero, OneMinusSrcAlpha),
+        SourceATop                      => (OneMinusDstAlpha, SrcAlpha, OneMinusDstAlpha, SrcAlpha),
+        DestinationATop                 => (OneMinusDstAlpha, OneMinusSrcAlpha, OneMinusDstAlpha, OneMinusSrcAlpha),
+
+        AllChannelAlphaSourceOver       => (One, OneMinusSrcColor, One, OneMinusSrcAlpha),
+        AllChannelAlphaDestinationOver  => (OneMinusDstColor, One, OneMinusDstAlpha, One),
+
+        Multiply                        => (Zero, SrcColor, One, OneMinusSrcAlpha),
+        Screen                          => (One, OneMinusSrcColor, One, OneMinusSrcAlpha),
+        Darken                          => (One, One, One, OneMinusSrcAlpha),
+        Lighten                         => (One, One, One, OneMinusSrcAlpha)
+    }
+}
+
+///
+/// The blend operations for a blend mode, as (colour, alpha)
+///
+pub fn blend_operations(blend_mode: BlendMode) -> (BlendOperation, BlendOperation) {
+    use self::BlendMode::*;
+    use self::BlendOperation::*;
+
+    match blend_mode {
+        Darken  => (Min, Add),
+        Lighten => (Max, Add),
+        _       => (Add, Add)
+    }
+}
+
+///
+/// Adjusts a pixel from a frame buffer before it's blended using `BlendFrameBuffer`
+///
+/// The colour of the transparent parts of the frame buffer is replaced with the colour that leaves the destination unchanged
+/// for the blend modes that don't use the source alpha (white for Multiply and Darken, and black for Screen and Lighten)
+///
+pub fn frame_buffer_source(src: [f32; 4], blend_mode: BlendMode) -> [f32; 4] {
+    use self::BlendMode::*;
+
+    let transparent_color = match blend_mode {
+        Multiply | Darken   => 1.0,
+        Screen | Lighten    => 0.0,
+        _                   => { return src; }
+    };
+
+    let alpha = src[3];
+    [
+        transparent_color + (src[0]-transparent_color)*alpha,
+        transparent_color + (src[1]-transparent_color)*alpha,
+        transparent_color + (src[2]-transparent_color)*alpha,
+        alpha
+    ]
+}
+
+impl BlendFactor {
+    ///
+    /// Returns the value of this factor for a particular component of a pair of pixels
+    ///
+    #[inline]
+    fn value(&self, component: usize, src: &[f32; 4], dst: &[f32; 4]) -> f32 {
+        use self::BlendFactor::*;
+
+        match self {
+            Zero                => 0.0,
+            One                 => 1.0,
+            SrcColor            => src[component],
+            OneMinusSrcColor    => 1.0 - src[component],
+            DstColor            => dst[component],
+            OneMinusDstColor    => 1.0 - dst[component],
+            SrcAlpha            => src[3],
+            OneMinusSrcAlpha    => 1.0 - src[3],
+            DstAlpha            => dst[3],
+            OneMinusDstAlpha    => 1.0 - dst[3]
+        }
+    }
+}
+
+///
+/// Blends a source fragment with a destination pixel using the specified blend mode
+///
+#[inline]
+pub fn blend(src: &[f32; 4], dst: &[f32; 4], blend_mode: BlendMode) -> [f32; 4] {
+    let (src_color, dst_color, src_alpha, dst_alpha) = blend_factors(blend_mode);
+    let (color_op, alpha_op)                         = blend_operations(blend_mode);
+    let mut result = [0.0; 4];
+
+    for component in 0..4 {
+        let (src_factor, dst_factor, operation) = if component < 3 { (src_color, dst_color, color_op) } else { (src_alpha, dst_alpha, alpha_op) };
+        let value = match operation {
+            BlendOperation::Add => src[component] * src_factor.value(component, src, dst) + dst[component] * dst_factor.value(component, src, dst),
+            BlendOperation::Min => src[component].min(dst[component]),
+            BlendOperation::Max => src[component].max(dst[component])
+        };
+
+        // Frame buffers store values between 0 and 1
+        result[component] = value.max(0.0).min(1.0);
+    }
+
+    result
+}
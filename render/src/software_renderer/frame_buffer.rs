@@ -0,0 +1,177 @@
+use crate::action::*;
+
+/// Positions within a pixel used when a frame buffer has a single sample
+const SINGLE_SAMPLE: [(f32, f32); 1] = [(0.5, 0.5)];
+
+/// Positions within a pixel used when a frame buffer is multisampled
+const MULTI_SAMPLE: [(f32, f32); 4] = [(0.375, 0.125), (0.875, 0.375), (0.125, 0.625), (0.625, 0.875)];
+
+///
+/// A frame buffer stored in memory
+///
+/// Rows are stored from the top of the image downwards. Each pixel can have several samples, which are averaged
+/// together when the frame buffer is read.
+///
+#[derive(Clone)]
+pub struct SoftwareFrameBuffer {
+    /// The width in pixels
+    width: usize,
+
+    /// The height in pixels
+    height: usize,
+
+    /// True if only the red channel is written to this frame buffer
+    monochrome: bool,
+
+    /// The sample positions within each pixel
+    sample_positions: &'static [(f32, f32)],
+
+    /// The samples in this frame buffer
+    samples: Vec<[f32; 4]>
+}
+
+impl SoftwareFrameBuffer {
+    ///
+    /// Creates a new frame buffer (cleared to transparent)
+    ///
+    pub fn new(width: usize, height: usize, multisampled: bool, monochrome: bool) -> SoftwareFrameBuffer {
+        let sample_positions: &'static [(f32, f32)] = if multisampled { &MULTI_SAMPLE } else { &SINGLE_SAMPLE };
+        let initial_value                           = if monochrome { [0.0, 0.0, 0.0, 1.0] } else { [0.0, 0.0, 0.0, 0.0] };
+
+        SoftwareFrameBuffer {
+            width:              width,
+            height:             height,
+            monochrome:         monochrome,
+            sample_positions:   sample_positions,
+            samples:            vec![initial_value; width*height*sample_positions.len()]
+        }
+    }
+
+    ///
+    /// Creates a frame buffer suitable for a particular type of render target
+    ///
+    pub fn for_render_target(width: usize, height: usize, render_type: RenderTargetType) -> SoftwareFrameBuffer {
+        use self::RenderTargetType::*;
+
+        match render_type {
+            Standard                        => SoftwareFrameBuffer::new(width, height, false, false),
+            Multisampled                    => SoftwareFrameBuffer::new(width, height, true, false),
+            MultisampledTexture             => SoftwareFrameBuffer::new(width, height, true, false),
+            Monochrome                      => SoftwareFrameBuffer::new(width, height, false, true),
+            MonochromeMultisampledTexture   => SoftwareFrameBuffer::new(width, height, true, true)
+        }
+    }
+
+    ///
+    /// The size of this frame buffer in pixels
+    ///
+    #[inline]
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    ///
+    /// True if this frame buffer only stores the red channel (the other channels always read as (0, 0, 1))
+    ///
+    #[inline]
+    pub fn is_monochrome(&self) -> bool {
+        self.monochrome
+    }
+
+    ///
+    /// The positions of the samples within each pixel (where (0,0) is the top-left corner of the pixel)
+    ///
+    #[inline]
+    pub fn sample_positions(&self) -> &'static [(f32, f32)] {
+        self.sample_positions
+    }
+
+    ///
+    /// Sets every sample in this frame buffer to the specified colour
+    ///
+    pub fn clear(&mut self, color: [f32; 4]) {
+        let color = if self.monochrome { [color[0], 0.0, 0.0, 1.0] } else { color };
+
+        self.samples.iter_mut().for_each(|sample| *sample = color);
+    }
+
+    ///
+    /// Retrieves a sample from this frame buffer
+    ///
+    #[inline]
+    pub fn sample(&self, x: usize, y: usize, sample_num: usize) -> [f32; 4] {
+        self.samples[(x + y*self.width)*self.sample_positions.len() + sample_num]
+    }
+
+    ///
+    /// Updates a sample in this frame buffer
+    ///
+    #[inline]
+    pub fn set_sample(&mut self, x: usize, y: usize, sample_num: usize, value: [f32; 4]) {
+        let value = if self.monochrome { [value[0], 0.0, 0.0, 1.0] } else { value };
+
+        self.samples[(x + y*self.width)*self.sample_positions.len() + sample_num] = value;
+    }
+
+    ///
+    /// Sets every sample of a pixel to the same value
+    ///
+    #[inline]
+    pub fn set_pixel(&mut self, x: usize, y: usize, value: [f32; 4]) {
+        for sample_num in 0..self.sample_positions.len() {
+            self.set_sample(x, y, sample_num, value);
+        }
+    }
+
+    ///
+    /// Retrieves the value of a pixel (the average of all its samples)
+    ///
+    pub fn pixel(&self, x: usize, y: usize) -> [f32; 4] {
+        let num_samples = self.sample_positions.len();
+        let mut total   = [0.0; 4];
+
+        for sample_num in 0..num_samples {
+            let sample = self.sample(x, y, sample_num);
+
+            for component in 0..4 {
+                total[component] += sample[component];
+            }
+        }
+
+        for component in 0..4 {
+            total[component] /= num_samples as f32;
+        }
+
+        total
+    }
+
+    ///
+    /// Converts the value of a pixel to 8-bit RGBA
+    ///
+    pub fn pixel_rgba8(&self, x: usize, y: usize) -> Rgba8 {
+        let pixel = self.pixel(x, y);
+
+        Rgba8([
+            (pixel[0] * 255.0).round() as u8,
+            (pixel[1] * 255.0).round() as u8,
+            (pixel[2] * 255.0).round() as u8,
+            (pixel[3] * 255.0).round() as u8
+        ])
+    }
+
+    ///
+    /// Reads this frame buffer as 8-bit RGBA values, from the top row down
+    ///
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.width*self.height*4);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let Rgba8(pixel) = self.pixel_rgba8(x, y);
+                result.extend(pixel.iter());
+            }
+        }
+
+        result
+    }
+}
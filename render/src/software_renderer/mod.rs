@@ -0,0 +1,11 @@
+mod renderer;
+
+mod blend;
+mod frame_buffer;
+mod rasterizer;
+
+pub use self::renderer::*;
+
+pub use self::blend::*;
+pub use self::frame_buffer::*;
+pub use self::rasterizer::*;
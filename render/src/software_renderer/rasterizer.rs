@@ -0,0 +1,138 @@
+use super::blend::*;
+use super::frame_buffer::*;
+
+use crate::action::*;
+
+///
+/// A vertex that has been transformed into pixel coordinates (with y increasing downwards)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PixelVertex {
+    /// The x position of this vertex in pixels
+    pub x: f32,
+
+    /// The y position of this vertex in pixels
+    pub y: f32,
+
+    /// The colour of this vertex (as values from 0-1)
+    pub color: [f32; 4],
+
+    /// The texture coordinates of this vertex
+    pub tex_coord: [f32; 2]
+}
+
+///
+/// Evaluates the edge function for the edge from v0 to v1 at a point
+///
+#[inline]
+fn edge_function(v0: &PixelVertex, v1: &PixelVertex, x: f32, y: f32) -> f32 {
+    (v1.x - v0.x) * (y - v0.y) - (v1.y - v0.y) * (x - v0.x)
+}
+
+///
+/// True if an edge is a 'top' or 'left' edge of a triangle
+///
+/// Samples that lie exactly on an edge are only drawn if it's a top or left edge: this means that triangles that share an
+/// edge never both draw the same sample.
+///
+#[inline]
+fn is_top_left(v0: &PixelVertex, v1: &PixelVertex) -> bool {
+    let dx = v1.x - v0.x;
+    let dy = v1.y - v0.y;
+
+    dy < 0.0 || (dy == 0.0 && dx > 0.0)
+}
+
+///
+/// True if a sample with the specified edge function value is inside an edge
+///
+#[inline]
+fn is_inside(edge_value: f32, top_left: bool) -> bool {
+    edge_value > 0.0 || (edge_value == 0.0 && top_left)
+}
+
+///
+/// Draws a triangle to a frame buffer
+///
+/// The shader function is called once for each pixel that the triangle covers, with the pixel coordinates and the colour
+/// and texture coordinates interpolated from the vertices at the center of the pixel. It returns the fragment colour, which
+/// is blended with each of the samples covered by the triangle.
+///
+pub fn draw_triangle<Shader>(target: &mut SoftwareFrameBuffer, triangle: [PixelVertex; 3], blend_mode: BlendMode, mut shader: Shader)
+where Shader: FnMut(usize, usize, [f32; 4], [f32; 2]) -> [f32; 4] {
+    let [a, mut b, mut c] = triangle;
+
+    // Ensure that the triangle has a positive area
+    let mut area = edge_function(&a, &b, c.x, c.y);
+
+    if !(area != 0.0 && area.is_finite()) {
+        return;
+    }
+
+    if area < 0.0 {
+        std::mem::swap(&mut b, &mut c);
+        area = -area;
+    }
+
+    // Work out the pixels that the triangle might cover
+    let (width, height) = target.size();
+    let min_x           = a.x.min(b.x).min(c.x).floor().max(0.0) as usize;
+    let min_y           = a.y.min(b.y).min(c.y).floor().max(0.0) as usize;
+    let max_x           = (a.x.max(b.x).max(c.x).ceil().max(0.0) as usize).min(width);
+    let max_y           = (a.y.max(b.y).max(c.y).ceil().max(0.0) as usize).min(height);
+
+    let top_left_bc     = is_top_left(&b, &c);
+    let top_left_ca     = is_top_left(&c, &a);
+    let top_left_ab     = is_top_left(&a, &b);
+
+    let sample_positions    = target.sample_positions();
+    let mut covered         = vec![false; sample_positions.len()];
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            // Find the samples covered by the triangle
+            let mut any_covered = false;
+
+            for (sample_num, (sample_x, sample_y)) in sample_positions.iter().enumerate() {
+                let sample_x    = (x as f32) + sample_x;
+                let sample_y    = (y as f32) + sample_y;
+
+                let inside      = is_inside(edge_function(&b, &c, sample_x, sample_y), top_left_bc)
+                    && is_inside(edge_function(&c, &a, sample_x, sample_y), top_left_ca)
+                    && is_inside(edge_function(&a, &b, sample_x, sample_y), top_left_ab);
+
+                covered[sample_num] = inside;
+                any_covered         = any_covered || inside;
+            }
+
+            if !any_covered { continue; }
+
+            // Interpolate the colour and texture coordinates at the center of the pixel
+            let center_x    = (x as f32) + 0.5;
+            let center_y    = (y as f32) + 0.5;
+            let weight_a    = edge_function(&b, &c, center_x, center_y) / area;
+            let weight_b    = edge_function(&c, &a, center_x, center_y) / area;
+            let weight_c    = edge_function(&a, &b, center_x, center_y) / area;
+
+            let mut color   = [0.0; 4];
+            for component in 0..4 {
+                color[component] = (a.color[component]*weight_a + b.color[component]*weight_b + c.color[component]*weight_c).max(0.0).min(1.0);
+            }
+
+            let mut tex_coord = [0.0; 2];
+            for component in 0..2 {
+                tex_coord[component] = a.tex_coord[component]*weight_a + b.tex_coord[component]*weight_b + c.tex_coord[component]*weight_c;
+            }
+
+            // Run the shader and blend the fragment with the covered samples
+            let fragment = shader(x, y, color, tex_coord);
+
+            for sample_num in 0..sample_positions.len() {
+                if covered[sample_num] {
+                    let existing = target.sample(x, y, sample_num);
+                    target.set_sample(x, y, sample_num, blend(&fragment, &existing, blend_mode));
+                }
+            }
+        }
+    }
+}
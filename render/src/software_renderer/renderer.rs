@@ -0,0 +1,444 @@
+use super::blend::*;
+use super::rasterizer::*;
+use super::frame_buffer::*;
+
+use crate::action::*;
+use crate::buffer::*;
+
+use std::ops::{Range};
+
+///
+/// Renderer that executes render actions on the CPU, into frame buffers stored in memory
+///
+/// This produces the same results regardless of the hardware it's running on, so it's suitable for testing rendering
+/// pipelines or for rendering in environments with no GPU.
+///
+pub struct SoftwareRenderer {
+    /// The vertex buffers allocated to this renderer
+    buffers: Vec<Option<Vec<Vertex2D>>>,
+
+    /// The index buffers defined for this renderer
+    index_buffers: Vec<Option<Vec<u16>>>,
+
+    /// The textures allocated to this renderer (render targets draw to their textures)
+    textures: Vec<Option<SoftwareFrameBuffer>>,
+
+    /// The render targets assigned to this renderer (as the texture that they draw to)
+    render_targets: Vec<Option<TextureId>>,
+
+    /// The 'main' frame buffer that represents the output for this renderer
+    frame_buffer: SoftwareFrameBuffer,
+
+    /// The render target that's currently being drawn to (None for the main frame buffer)
+    current_render_target: Option<RenderTargetId>,
+
+    /// The shader that's currently set to be used
+    active_shader: ShaderType,
+
+    /// The blend mode that's currently set to be used
+    blend_mode: BlendMode,
+
+    /// The matrix that's currently in use
+    transform_matrix: Matrix
+}
+
+impl SoftwareRenderer {
+    ///
+    /// Creates a new software renderer with a main frame buffer of the specified size
+    ///
+    pub fn new(width: usize, height: usize) -> SoftwareRenderer {
+        SoftwareRenderer {
+            buffers:                vec![],
+            index_buffers:          vec![],
+            textures:               vec![],
+            render_targets:         vec![],
+            frame_buffer:           SoftwareFrameBuffer::new(width, height, false, false),
+            current_render_target:  None,
+            active_shader:          ShaderType::Simple { erase_texture: None },
+            blend_mode:             BlendMode::SourceOver,
+            transform_matrix:       Matrix::identity()
+        }
+    }
+
+    ///
+    /// Performs rendering of the specified actions
+    ///
+    pub fn render<Actions: IntoIterator<Item=RenderAction>>(&mut self, actions: Actions) {
+        for action in actions {
+            use self::RenderAction::*;
+
+            match action {
+                SetTransform(matrix)                                                    => { self.transform_matrix = matrix; }
+                CreateVertex2DBuffer(id, vertices)                                      => { self.create_vertex_buffer_2d(id, vertices); }
+                CreateIndexBuffer(id, indices)                                          => { self.create_index_buffer(id, indices); }
+                FreeVertexBuffer(VertexBufferId(id))                                    => { Self::free(&mut self.buffers, id); }
+                FreeIndexBuffer(IndexBufferId(id))                                      => { Self::free(&mut self.index_buffers, id); }
+                BlendMode(blend_mode)                                                   => { self.blend_mode = blend_mode; }
+                CreateRenderTarget(render_id, texture_id, width, height, render_type)   => { self.create_render_target(render_id, texture_id, width, height, render_type); }
+                FreeRenderTarget(RenderTargetId(render_id))                             => { Self::free(&mut self.render_targets, render_id); }
+                SelectRenderTarget(render_id)                                           => { self.current_render_target = Some(render_id); }
+                RenderToFrameBuffer                                                     => { self.current_render_target = None; }
+                DrawFrameBuffer(render_id, x, y)                                        => { self.draw_frame_buffer(render_id, x, y); }
+                BlendFrameBuffer(render_id, alpha)                                      => { self.blend_frame_buffer(render_id, alpha); }
+                ShowFrameBuffer                                                         => { /* The frame buffer is read by calling frame_buffer() */ }
+                CreateTextureBgra(texture_id, width, height)                            => { self.create_bgra_texture(texture_id, width, height); }
+                WriteTextureData(texture_id, lower_left, upper_right, data)             => { self.write_texture_data(texture_id, lower_left, upper_right, &*data); }
+                FreeTexture(TextureId(texture_id))                                      => { Self::free(&mut self.textures, texture_id); }
+                Clear(color)                                                            => { self.clear(color); }
+                UseShader(shader_type)                                                  => { self.active_shader = shader_type; }
+                DrawTriangles(buffer_id, buffer_range)                                  => { self.draw_triangles(buffer_id, buffer_range); }
+                DrawIndexedTriangles(vertex_buffer, index_buffer, num_vertices)         => { self.draw_indexed_triangles(vertex_buffer, index_buffer, num_vertices); }
+            }
+        }
+    }
+
+    ///
+    /// The main frame buffer for this renderer
+    ///
+    pub fn frame_buffer(&self) -> &SoftwareFrameBuffer {
+        &self.frame_buffer
+    }
+
+    ///
+    /// Retrieves the frame buffer that a render target draws to
+    ///
+    pub fn render_target(&self, RenderTargetId(render_id): RenderTargetId) -> Option<&SoftwareFrameBuffer> {
+        self.render_targets.get(render_id)
+            .and_then(|texture_id| texture_id.as_ref())
+            .and_then(|TextureId(texture_id)| self.textures.get(*texture_id))
+            .and_then(|texture| texture.as_ref())
+    }
+
+    ///
+    /// Stores a value in one of the lists of resources used by this renderer, extending the list if needed
+    ///
+    fn store<T>(list: &mut Vec<Option<T>>, id: usize, value: T) {
+        if id >= list.len() {
+            list.extend((list.len()..(id+1))
+                .into_iter()
+                .map(|_| None));
+        }
+
+        list[id] = Some(value);
+    }
+
+    ///
+    /// Releases a resource stored by `store()`
+    ///
+    fn free<T>(list: &mut Vec<Option<T>>, id: usize) {
+        if id < list.len() {
+            list[id] = None;
+        }
+    }
+
+    ///
+    /// Creates a 2D vertex buffer
+    ///
+    fn create_vertex_buffer_2d(&mut self, VertexBufferId(buffer_id): VertexBufferId, vertices: Vec<Vertex2D>) {
+        Self::store(&mut self.buffers, buffer_id, vertices);
+    }
+
+    ///
+    /// Creates an index buffer
+    ///
+    fn create_index_buffer(&mut self, IndexBufferId(buffer_id): IndexBufferId, indices: Vec<u16>) {
+        Self::store(&mut self.index_buffers, buffer_id, indices);
+    }
+
+    ///
+    /// Creates a new BGRA texture
+    ///
+    fn create_bgra_texture(&mut self, TextureId(texture_id): TextureId, width: usize, height: usize) {
+        Self::store(&mut self.textures, texture_id, SoftwareFrameBuffer::new(width, height, false, false));
+    }
+
+    ///
+    /// Writes BGRA pixel data to a region of a texture
+    ///
+    /// Rows of a BGRA texture are stored in the order they're written, so the first row is the one at texture coordinate 0.
+    ///
+    fn write_texture_data(&mut self, TextureId(texture_id): TextureId, (x1, y1): (usize, usize), (x2, y2): (usize, usize), data: &[u8]) {
+        if let Some(Some(texture)) = self.textures.get_mut(texture_id) {
+            let (width, height) = texture.size();
+            let row_width       = x2-x1;
+
+            for (row, y) in (y1..y2.min(height)).enumerate() {
+                for (col, x) in (x1..x2.min(width)).enumerate() {
+                    let pos = (row*row_width + col)*4;
+                    if pos+4 > data.len() { return; }
+
+                    let [b, g, r, a] = [data[pos], data[pos+1], data[pos+2], data[pos+3]];
+                    texture.set_pixel(x, y, [(r as f32)/255.0, (g as f32)/255.0, (b as f32)/255.0, (a as f32)/255.0]);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Creates a new render target, along with the texture that it renders to
+    ///
+    fn create_render_target(&mut self, RenderTargetId(render_id): RenderTargetId, TextureId(texture_id): TextureId, width: usize, height: usize, render_type: RenderTargetType) {
+        Self::store(&mut self.textures, texture_id, SoftwareFrameBuffer::for_render_target(width, height, render_type));
+        Self::store(&mut self.render_targets, render_id, TextureId(texture_id));
+    }
+
+    ///
+    /// Removes the frame buffer that's currently being rendered to from this object, so it can be updated while reading other resources
+    ///
+    fn take_target(&mut self) -> Option<SoftwareFrameBuffer> {
+        match self.current_render_target {
+            None                            => Some(std::mem::replace(&mut self.frame_buffer, SoftwareFrameBuffer::new(0, 0, false, false))),
+            Some(RenderTargetId(render_id)) => {
+                let texture_id = self.render_targets.get(render_id).and_then(|texture_id| *texture_id);
+                texture_id.and_then(|TextureId(texture_id)| self.textures.get_mut(texture_id).and_then(|texture| texture.take()))
+            }
+        }
+    }
+
+    ///
+    /// Returns a frame buffer removed by take_target
+    ///
+    fn return_target(&mut self, target: SoftwareFrameBuffer) {
+        match self.current_render_target {
+            None                            => { self.frame_buffer = target; }
+            Some(RenderTargetId(render_id)) => {
+                if let Some(Some(TextureId(texture_id))) = self.render_targets.get(render_id) {
+                    self.textures[*texture_id] = Some(target);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Clears the current render target
+    ///
+    fn clear(&mut self, Rgba8([r, g, b, a]): Rgba8) {
+        let color = [(r as f32)/255.0, (g as f32)/255.0, (b as f32)/255.0, (a as f32)/255.0];
+
+        if let Some(mut target) = self.take_target() {
+            target.clear(color);
+            self.return_target(target);
+        }
+    }
+
+    ///
+    /// Copies a render target to the current render target (this is a copy and does not use the blend mode)
+    ///
+    /// The x and y coordinates are measured from the bottom-left corner of the current render target.
+    ///
+    fn draw_frame_buffer(&mut self, source: RenderTargetId, x: i32, y: i32) {
+        if Some(source) == self.current_render_target { return; }
+
+        if let Some(mut target) = self.take_target() {
+            if let Some(source) = self.render_target(source) {
+                let (src_width, src_height) = source.size();
+                let (dst_width, dst_height) = target.size();
+
+                for src_y in 0..src_height {
+                    // Rows are stored from the top down, but the position is measured from the bottom up
+                    let dst_y = (dst_height as i32) - (y + (src_height as i32) - (src_y as i32));
+                    if dst_y < 0 || dst_y >= (dst_height as i32) { continue; }
+
+                    for src_x in 0..src_width {
+                        let dst_x = x + (src_x as i32);
+                        if dst_x < 0 || dst_x >= (dst_width as i32) { continue; }
+
+                        target.set_pixel(dst_x as usize, dst_y as usize, source.pixel(src_x, src_y));
+                    }
+                }
+            }
+
+            self.return_target(target);
+        }
+    }
+
+    ///
+    /// Blends a render target onto the current render target using the current blend mode, multiplying its alpha channel by the specified value
+    ///
+    fn blend_frame_buffer(&mut self, source: RenderTargetId, alpha: f32) {
+        if Some(source) == self.current_render_target { return; }
+
+        if let Some(mut target) = self.take_target() {
+            if let Some(source) = self.render_target(source) {
+                let (src_width, src_height) = source.size();
+                let (dst_width, dst_height) = target.size();
+                let num_samples             = target.sample_positions().len();
+                let blend_mode              = self.blend_mode;
+
+                for y in 0..src_height.min(dst_height) {
+                    for x in 0..src_width.min(dst_width) {
+                        let mut src = source.pixel(x, y);
+                        src[3]      *= alpha;
+                        let src     = frame_buffer_source(src, blend_mode);
+
+                        for sample_num in 0..num_samples {
+                            let dst = target.sample(x, y, sample_num);
+                            target.set_sample(x, y, sample_num, blend(&src, &dst, blend_mode));
+                        }
+                    }
+                }
+            }
+
+            self.return_target(target);
+        }
+    }
+
+    ///
+    /// Transforms a vertex to pixel coordinates for a frame buffer of the specified size
+    ///
+    fn to_pixel_vertex(&self, vertex: Vertex2D, width: usize, height: usize) -> PixelVertex {
+        let Matrix(matrix)  = &self.transform_matrix;
+        let pos             = vertex.pos;
+        let color           = vertex.color;
+        let tex_coord       = vertex.tex_coord;
+
+        // Transform to normalized device coordinates (where -1,-1 is the bottom-left corner)
+        let w               = matrix[3][0]*pos[0] + matrix[3][1]*pos[1] + matrix[3][3];
+        let w               = if w == 0.0 { 1.0 } else { w };
+        let ndc_x           = (matrix[0][0]*pos[0] + matrix[0][1]*pos[1] + matrix[0][3]) / w;
+        let ndc_y           = (matrix[1][0]*pos[0] + matrix[1][1]*pos[1] + matrix[1][3]) / w;
+
+        PixelVertex {
+            x:          (ndc_x + 1.0) / 2.0 * (width as f32),
+            y:          (1.0 - ndc_y) / 2.0 * (height as f32),
+            color:      [(color[0] as f32)/255.0, (color[1] as f32)/255.0, (color[2] as f32)/255.0, (color[3] as f32)/255.0],
+            tex_coord:  tex_coord
+        }
+    }
+
+    ///
+    /// Draws a list of triangles (as indexes into a vertex buffer) to the current render target
+    ///
+    fn draw_vertices<Indices: Iterator<Item=usize>>(&mut self, VertexBufferId(buffer_id): VertexBufferId, mut indices: Indices) {
+        let mut target = match self.take_target() {
+            Some(target)    => target,
+            None            => { return; }
+        };
+
+        if let Some(Some(vertices)) = self.buffers.get(buffer_id) {
+            let (width, height) = target.size();
+            let blend_mode      = self.blend_mode;
+
+            // The erase texture is a mask that's subtracted from anything that's drawn
+            let erase_texture   = match self.active_shader {
+                ShaderType::Simple { erase_texture }        => erase_texture,
+                ShaderType::Texture { erase_texture, .. }   => erase_texture
+            };
+            let erase_texture   = erase_texture.and_then(|TextureId(texture_id)| self.textures.get(texture_id)).and_then(|texture| texture.as_ref());
+
+            // The fill texture is multiplied by the vertex colour
+            let fill_texture    = match self.active_shader {
+                ShaderType::Simple { .. }                                           => None,
+                ShaderType::Texture { texture: TextureId(texture_id), repeat, .. }  => self.textures.get(texture_id).and_then(|texture| texture.as_ref()).map(|texture| (texture, repeat))
+            };
+
+            let shader          = |x: usize, y: usize, color: [f32; 4], tex_coord: [f32; 2]| {
+                let color = match fill_texture {
+                    None                        => color,
+                    Some((texture, repeat))     => {
+                        let texture_color = sample_texture(texture, tex_coord, repeat);
+                        [color[0]*texture_color[0], color[1]*texture_color[1], color[2]*texture_color[2], color[3]*texture_color[3]]
+                    }
+                };
+
+                match erase_texture {
+                    None                => color,
+                    Some(erase_texture) => {
+                        // Find the pixel in the erase texture that corresponds to this pixel
+                        let (erase_width, erase_height) = erase_texture.size();
+                        let erase_x                     = ((((x as f32) + 0.5) / (width as f32)) * (erase_width as f32)) as usize;
+                        let erase_y                     = ((((y as f32) + 0.5) / (height as f32)) * (erase_height as f32)) as usize;
+
+                        if erase_x >= erase_width || erase_y >= erase_height {
+                            color
+                        } else {
+                            let erase = erase_texture.pixel(erase_x, erase_y)[0];
+                            [color[0]*(1.0-erase), color[1]*(1.0-erase), color[2]*(1.0-erase), color[3]*(1.0-erase)]
+                        }
+                    }
+                }
+            };
+
+            // Draw the triangles
+            while let (Some(a), Some(b), Some(c)) = (indices.next(), indices.next(), indices.next()) {
+                if a >= vertices.len() || b >= vertices.len() || c >= vertices.len() { continue; }
+
+                let triangle = [
+                    self.to_pixel_vertex(vertices[a], width, height),
+                    self.to_pixel_vertex(vertices[b], width, height),
+                    self.to_pixel_vertex(vertices[c], width, height)
+                ];
+
+                draw_triangle(&mut target, triangle, blend_mode, shader);
+            }
+        }
+
+        self.return_target(target);
+    }
+
+    ///
+    /// Draw triangles from a buffer
+    ///
+    fn draw_triangles(&mut self, buffer_id: VertexBufferId, buffer_range: Range<usize>) {
+        self.draw_vertices(buffer_id, buffer_range);
+    }
+
+    ///
+    /// Draw triangles from a buffer, using an index buffer
+    ///
+    fn draw_indexed_triangles(&mut self, vertex_buffer: VertexBufferId, IndexBufferId(index_buffer): IndexBufferId, num_vertices: usize) {
+        let indices = match self.index_buffers.get(index_buffer) {
+            Some(Some(indices)) => indices.iter().take(num_vertices).map(|index| *index as usize).collect::<Vec<_>>(),
+            _                   => { return; }
+        };
+
+        self.draw_vertices(vertex_buffer, indices.into_iter());
+    }
+}
+
+///
+/// Reads the colour of a texture at a texture coordinate, interpolating between the nearest pixels
+///
+/// Coordinates outside of the range 0-1 either wrap around or read the pixels at the edge of the texture, depending on the
+/// value of `repeat`.
+///
+fn sample_texture(texture: &SoftwareFrameBuffer, tex_coord: [f32; 2], repeat: bool) -> [f32; 4] {
+    let (width, height) = texture.size();
+    if width == 0 || height == 0 { return [0.0, 0.0, 0.0, 0.0]; }
+
+    // Find the pixel position (pixel centers are at 0.5)
+    let x           = tex_coord[0] * (width as f32) - 0.5;
+    let y           = tex_coord[1] * (height as f32) - 0.5;
+    let x0          = x.floor();
+    let y0          = y.floor();
+    let frac_x      = x - x0;
+    let frac_y      = y - y0;
+
+    let wrap        = |pos: i64, size: usize| {
+        if repeat {
+            pos.rem_euclid(size as i64) as usize
+        } else {
+            pos.max(0).min((size as i64) - 1) as usize
+        }
+    };
+
+    let x0          = x0 as i64;
+    let y0          = y0 as i64;
+    let (xa, xb)    = (wrap(x0, width), wrap(x0+1, width));
+    let (ya, yb)    = (wrap(y0, height), wrap(y0+1, height));
+
+    // Interpolate between the four nearest pixels
+    let top_left        = texture.pixel(xa, ya);
+    let top_right       = texture.pixel(xb, ya);
+    let bottom_left     = texture.pixel(xa, yb);
+    let bottom_right    = texture.pixel(xb, yb);
+
+    let mut result      = [0.0; 4];
+    for component in 0..4 {
+        let top             = top_left[component]*(1.0-frac_x) + top_right[component]*frac_x;
+        let bottom          = bottom_left[component]*(1.0-frac_x) + bottom_right[component]*frac_x;
+        result[component]   = top*(1.0-frac_y) + bottom*frac_y;
+    }
+
+    result
+}
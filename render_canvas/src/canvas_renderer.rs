@@ -79,7 +79,11 @@ impl CanvasRenderer {
             layer_definitions:      vec![],
             sprites:                HashMap::new(),
             unused_vertex_buffer:   0,
-            free_vertex_buffers:    vec![]
+            free_vertex_buffers:    vec![],
+            unused_texture:         2,
+            free_textures:          vec![],
+            offscreen_size:         (1, 1),
+            offscreen_targets:      vec![]
         };
         let core = Arc::new(Desync::new(core));
 
@@ -184,16 +188,35 @@ impl CanvasRenderer {
             render_order:       vec![RenderEntity::SetTransform(canvas::Transform2D::identity())],
             state:              LayerState {
                 fill_color:         render::Rgba8([0, 0, 0, 255]),
+                fill_style:         None,
+                gradient_stops:     vec![],
                 stroke_settings:    StrokeSettings::new(),
                 current_matrix:     canvas::Transform2D::identity(),
                 sprite_matrix:      canvas::Transform2D::identity(),
                 blend_mode:         canvas::BlendMode::SourceOver,
                 restore_point:      None
             },
-            stored_states:      vec![]
+            stored_states:      vec![],
+            alpha:              1.0,
+            blend_mode:         canvas::BlendMode::SourceOver
         }
     }
 
+    ///
+    /// Returns the handle of the layer with the specified ID, creating it if it doesn't exist yet
+    ///
+    fn layer_with_id(core: &mut RenderCore, layer_id: u32) -> LayerHandle {
+        let layer_id = layer_id as usize;
+
+        while core.layers.len() <= layer_id  {
+            let new_layer = Self::create_default_layer();
+            let new_layer = core.allocate_layer_handle(new_layer);
+            core.layers.push(new_layer);
+        }
+
+        core.layers[layer_id]
+    }
+
     ///
     /// Changes a colour component to a u8 format
     ///
@@ -210,7 +233,7 @@ impl CanvasRenderer {
     ///
     /// Converts a canvas colour to a render colour
     ///
-    fn render_color(color: canvas::Color) -> render::Rgba8 {
+    pub (crate) fn render_color(color: canvas::Color) -> render::Rgba8 {
         let (r, g, b, a)    = color.to_rgba_components();
         let (r, g, b, a)    = (Self::col_to_u8(r), Self::col_to_u8(g), Self::col_to_u8(b), Self::col_to_u8(a));
 
@@ -304,16 +327,20 @@ impl CanvasRenderer {
                                 // Create the render entity in the tessellating state
                                 let color               = layer.state.fill_color;
                                 let entity_index        = layer.render_order.len();
+                                let erase               = layer.state.blend_mode == canvas::BlendMode::DestinationOut;
 
                                 // When drawing to the erase layer (DesintationOut blend mode), all colour components are alpha components
-                                let color               = if layer.state.blend_mode == canvas::BlendMode::DestinationOut { render::Rgba8([color.0[3], color.0[3], color.0[3], color.0[3]]) } else { color };
+                                let color               = if erase { render::Rgba8([color.0[3], color.0[3], color.0[3], color.0[3]]) } else { color };
 
                                 layer.render_order.push(RenderEntity::Tessellating(entity_id));
 
                                 let entity          = LayerEntityRef { layer_id, entity_index, entity_id };
 
-                                // Create the canvas job
-                                CanvasJob::Fill { path, color, entity }
+                                // Create the canvas job (gradients and textures need a more detailed tessellation)
+                                match &layer.state.fill_style {
+                                    None                => CanvasJob::Fill { path, color, entity },
+                                    Some(fill_style)    => CanvasJob::FillStyled { path, fill_style: fill_style.clone(), erase, entity }
+                                }
                             });
 
                             pending_jobs.push(job);
@@ -412,7 +439,42 @@ impl CanvasRenderer {
 
                     // Set the fill color
                     FillColor(color) => {
-                        core.sync(|core| core.layer(self.current_layer).state.fill_color = Self::render_color(color));
+                        core.sync(|core| {
+                            let state           = &mut core.layer(self.current_layer).state;
+                            state.fill_color    = Self::render_color(color);
+                            state.fill_style    = None;
+                        });
+                    }
+
+                    // Clear the stops for the next gradient
+                    NewGradient => {
+                        core.sync(|core| core.layer(self.current_layer).state.gradient_stops = vec![]);
+                    }
+
+                    // Add a stop to the next gradient
+                    GradientStop(pos, color) => {
+                        core.sync(|core| core.layer(self.current_layer).state.gradient_stops.push((pos, color)));
+                    }
+
+                    // Fill with a linear gradient
+                    FillLinearGradient(start, end) => {
+                        core.sync(|core| {
+                            let state           = &mut core.layer(self.current_layer).state;
+                            state.fill_style    = Some(canvas::FillStyle::LinearGradient(state.gradient_stops.clone(), start, end));
+                        });
+                    }
+
+                    // Fill with a radial gradient
+                    FillRadialGradient(center, radius) => {
+                        core.sync(|core| {
+                            let state           = &mut core.layer(self.current_layer).state;
+                            state.fill_style    = Some(canvas::FillStyle::RadialGradient(state.gradient_stops.clone(), center, radius));
+                        });
+                    }
+
+                    // Fill with a repeating texture
+                    FillTexture(pattern, foreground, background, size) => {
+                        core.sync(|core| core.layer(self.current_layer).state.fill_style = Some(canvas::FillStyle::Texture(pattern, foreground, background, size)));
                     }
 
                     // Set the line color
@@ -511,6 +573,15 @@ impl CanvasRenderer {
                         //unimplemented!()
                     }
 
+                    // Sets the alpha value used when a particular layer is blended with the underlying layer
+                    LayerAlpha(layer_id, alpha) => {
+                        // Layers with an alpha value are rendered off-screen and then composited by the render stream
+                        core.sync(|core| {
+                            let layer_handle                = Self::layer_with_id(core, layer_id);
+                            core.layer(layer_handle).alpha  = alpha.max(0.0).min(1.0);
+                        });
+                    }
+
                     // Clip to the currently set path
                     Clip => {
                         //unimplemented!()
@@ -608,26 +679,19 @@ impl CanvasRenderer {
                     // Layer 0 is selected initially. Layers are drawn in order starting from 0.
                     // Layer IDs don't have to be sequential.
                     Layer(layer_id) => {
-                        let layer_id = layer_id as usize;
-
                         // Generate layers 
                         core.sync(|core| {
-                            while core.layers.len() <= layer_id  {
-                                let new_layer = Self::create_default_layer();
-                                let new_layer = core.allocate_layer_handle(new_layer);
-                                core.layers.push(new_layer);
-                            }
-
-                            self.current_layer = core.layers[layer_id];
+                            self.current_layer = Self::layer_with_id(core, layer_id);
                         });
                     }
 
                     // Sets how a particular layer is blended with the underlying layer
-                    LayerBlend(_layer_id, _blend_mode) => {
-                        // TODO: this needs some more work: for some blending modes we probably need to render the layer off-screen
-                        // and the current 'reverse order' drawing makes drawing it in the right order tricky
-
-                        //unimplemented!()
+                    LayerBlend(layer_id, blend_mode) => {
+                        // Layers with a blend mode are rendered off-screen and then composited by the render stream
+                        core.sync(|core| {
+                            let layer_handle                    = Self::layer_with_id(core, layer_id);
+                            core.layer(layer_handle).blend_mode = blend_mode;
+                        });
                     }
 
                     // Clears the current layer
@@ -639,6 +703,10 @@ impl CanvasRenderer {
                             // Swap into the layer list to replace the old one
                             mem::swap(core.layer(self.current_layer), &mut layer);
 
+                            // The way the layer is composited is not affected by clearing it
+                            core.layer(self.current_layer).alpha        = layer.alpha;
+                            core.layer(self.current_layer).blend_mode   = layer.blend_mode;
+
                             // Free the data for the current layer
                             core.free_layer_entities(layer);
                         });
@@ -759,6 +827,15 @@ impl CanvasRenderer {
                 self.viewport_size.1 as usize,
                 RenderTargetType::MonochromeMultisampledTexture));
 
+            // The render targets for compositing layers are created by the render stream when they're needed
+            let viewport_size = (self.viewport_size.0 as usize, self.viewport_size.1 as usize);
+            self.core.sync(move |core| {
+                core.offscreen_size     = viewport_size;
+
+                let old_targets         = mem::replace(&mut core.offscreen_targets, vec![]);
+                core.free_textures.extend(old_targets.into_iter().map(|TextureId(texture_id)| texture_id));
+            });
+
             self.created_render_surface = true;
         }
 
@@ -11,6 +11,12 @@ pub struct LayerState {
     /// The current fill colour
     pub fill_color: render::Rgba8,
 
+    /// The gradient or texture to fill paths with instead of the fill colour, if there is one
+    pub fill_style: Option<canvas::FillStyle>,
+
+    /// The colour stops for the next gradient fill
+    pub gradient_stops: Vec<(f32, canvas::Color)>,
+
     /// The blend mode set for this layer
     pub blend_mode: canvas::BlendMode,
 
@@ -3,6 +3,20 @@ use flo_render as render;
 
 use lyon::tessellation::{VertexBuffers};
 
+///
+/// Texture used to fill a path with a gradient or a pattern
+///
+pub struct FillTexture {
+    /// The width and height of the texture in pixels
+    pub size: (usize, usize),
+
+    /// The pixels of the texture, as 8-bit BGRA values from the bottom row upwards
+    pub pixels: Vec<u8>,
+
+    /// True if the texture should be tiled across the path
+    pub repeat: bool
+}
+
 ///
 /// Single rendering operation for a layer
 ///
@@ -16,9 +30,15 @@ pub enum RenderEntity {
     /// Tessellation waiting to be sent to the renderer
     VertexBuffer(VertexBuffers<render::Vertex2D, u16>),
 
+    /// Tessellation waiting to be sent to the renderer along with the texture it's filled with
+    TexturedVertexBuffer(VertexBuffers<render::Vertex2D, u16>, FillTexture),
+
     /// Render a vertex buffer
     DrawIndexed(render::VertexBufferId, render::IndexBufferId, usize),
 
+    /// Render a vertex buffer filled with a texture (which is repeated if the flag is set)
+    DrawTexturedIndexed(render::TextureId, bool, render::VertexBufferId, render::IndexBufferId, usize),
+
     /// Render the sprite layer with the specified ID
     RenderSprite(canvas::SpriteId, canvas::Transform2D),
 
@@ -8,6 +8,7 @@ use flo_canvas as canvas;
 use flo_render as render;
 
 use std::mem;
+use std::sync::*;
 use std::collections::{HashMap};
 
 ///
@@ -36,7 +37,19 @@ pub struct RenderCore {
     pub unused_vertex_buffer: usize,
 
     /// Vertex buffers that were previously used but are now free
-    pub free_vertex_buffers: Vec<usize>
+    pub free_vertex_buffers: Vec<usize>,
+
+    /// The first unused texture ID (textures 0 and 1 are used by the main render targets)
+    pub unused_texture: usize,
+
+    /// Textures that were previously used but are now free
+    pub free_textures: Vec<usize>,
+
+    /// The size of the off-screen render targets used to composite layers
+    pub offscreen_size: (usize, usize),
+
+    /// The textures of the off-screen render targets that have been created at the current size
+    pub offscreen_targets: Vec<render::TextureId>
 }
 
 impl RenderCore {
@@ -59,6 +72,7 @@ impl RenderCore {
             Missing                         => { }
             Tessellating(_entity_id)        => { }
             VertexBuffer(_buffers)          => { }
+            TexturedVertexBuffer(_, _)      => { }
             SetTransform(_)                 => { }
             SetBlendMode(_)                 => { }
             RenderSprite(_, _)              => { }
@@ -70,6 +84,15 @@ impl RenderCore {
                     self.free_vertex_buffers.push(index_id);
                 }
             }
+
+            DrawTexturedIndexed(render::TextureId(texture_id), _repeat, render::VertexBufferId(vertex_id), render::IndexBufferId(index_id), _num_vertices) => {
+                // The texture is only used by this drawing operation too
+                self.free_textures.push(texture_id);
+                self.free_vertex_buffers.push(vertex_id);
+                if index_id != vertex_id {
+                    self.free_vertex_buffers.push(index_id);
+                }
+            }
         }
     }
 
@@ -120,6 +143,18 @@ impl RenderCore {
             })
     }
 
+    ///
+    /// Allocates a free texture ID
+    ///
+    pub fn allocate_texture(&mut self) -> usize {
+        self.free_textures.pop()
+            .unwrap_or_else(|| {
+                let texture_id = self.unused_texture;
+                self.unused_texture += 1;
+                texture_id
+            })
+    }
+
     ///
     /// Returns the render actions required to send a vertex buffer (as a stack, so in reverse order)
     ///
@@ -147,6 +182,24 @@ impl RenderCore {
                 ]
             }
 
+            RenderEntity::TexturedVertexBuffer(vertices, texture) => {
+                // Allocate a buffer and a texture
+                let buffer_id       = self.allocate_vertex_buffer();
+                let texture_id      = self.allocate_texture();
+                let (width, height) = texture.size;
+
+                // Draw these buffers as the action at this position
+                self.layer_definitions[layer_idx].render_order[render_index] = RenderEntity::DrawTexturedIndexed(render::TextureId(texture_id), texture.repeat, render::VertexBufferId(buffer_id), render::IndexBufferId(buffer_id), vertices.indices.len());
+
+                // Send the texture, vertices and indices to the rendering engine
+                vec![
+                    render::RenderAction::WriteTextureData(render::TextureId(texture_id), (0, 0), (width, height), Arc::new(texture.pixels)),
+                    render::RenderAction::CreateTextureBgra(render::TextureId(texture_id), width, height),
+                    render::RenderAction::CreateIndexBuffer(render::IndexBufferId(buffer_id), vertices.indices),
+                    render::RenderAction::CreateVertex2DBuffer(render::VertexBufferId(buffer_id), vertices.vertices),
+                ]
+            }
+
             _ => panic!("send_vertex_buffer must be used on a vertex buffer item")
         }
     }
@@ -163,7 +216,8 @@ impl RenderCore {
 
         for render_idx in 0..layer.render_order.len() {
             match &layer.render_order[render_idx] {
-                VertexBuffer(_buffers)                      => { 
+                VertexBuffer(_buffers)                      |
+                TexturedVertexBuffer(_buffers, _)           => { 
                     send_vertex_buffers.extend(self.send_layer_vertex_buffer(layer_handle, render_idx)); 
                     layer = self.layer(layer_handle);
                 },
@@ -212,13 +266,17 @@ impl RenderCore {
             render_order:       vec![RenderEntity::SetTransform(canvas::Transform2D::identity())],
             state:              LayerState {
                 fill_color:         render::Rgba8([0, 0, 0, 255]),
+                fill_style:         None,
+                gradient_stops:     vec![],
                 stroke_settings:    StrokeSettings::new(),
                 current_matrix:     canvas::Transform2D::identity(),
                 sprite_matrix:      canvas::Transform2D::identity(),
                 blend_mode:         canvas::BlendMode::SourceOver,
                 restore_point:      None
             },
-            stored_states:      vec![]
+            stored_states:      vec![],
+            alpha:              1.0,
+            blend_mode:         canvas::BlendMode::SourceOver
         };
 
         mem::swap(&mut old_layer, &mut self.layer_definitions[layer_idx as usize]);
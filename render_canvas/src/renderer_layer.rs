@@ -14,7 +14,13 @@ pub struct Layer {
     pub state: LayerState,
 
     /// The stored states for this layer
-    pub stored_states: Vec<LayerState>
+    pub stored_states: Vec<LayerState>,
+
+    /// The alpha value to apply when this layer is composited with the layers beneath it
+    pub alpha: f32,
+
+    /// The blend mode to use when this layer is composited with the layers beneath it
+    pub blend_mode: canvas::BlendMode
 }
 
 impl Layer {
@@ -23,12 +23,6 @@ pub struct RenderStream<'a> {
     /// The future that is processing new drawing instructions
     processing_future: Option<LocalBoxFuture<'a, ()>>,
 
-    /// The current layer ID that we're processing
-    layer_id: usize,
-
-    /// The render entity within the layer that we're processing
-    render_index: usize,
-
     /// Render actions waiting to be sent
     pending_stack: Vec<render::RenderAction>,
 
@@ -68,9 +62,7 @@ impl<'a> RenderStream<'a> {
             processing_future:  Some(processing_future.boxed_local()),
             pending_stack:      initial_action_stack,
             final_stack:        Some(final_action_stack),
-            viewport_transform: viewport_transform,
-            layer_id:           0,
-            render_index:       0
+            viewport_transform: viewport_transform
         }
     }
 }
@@ -130,7 +122,7 @@ impl RenderCore {
     /// before the rendering is completed. This slightly weird arrangement is because the rendering operations are returned as a stack:
     /// ie, they'll run in reverse order.
     ///
-    fn render_layer(&mut self, viewport_transform: canvas::Transform2D, layer_handle: LayerHandle, render_state: &mut RenderStreamState) -> Vec<render::RenderAction> {
+    fn render_layer(&mut self, viewport_transform: canvas::Transform2D, layer_handle: LayerHandle, render_target: render::RenderTargetId, render_state: &mut RenderStreamState) -> Vec<render::RenderAction> {
         use self::RenderEntity::*;
 
         let core = self;
@@ -139,10 +131,12 @@ impl RenderCore {
         let mut render_layer_stack  = vec![];
         let mut active_transform    = canvas::Transform2D::identity();
         let mut use_erase_texture   = false;
+        let mut textured_shaders    = vec![];
         let mut layer               = core.layer(layer_handle);
 
         render_state.transform      = Some(&viewport_transform * &active_transform);
-        render_state.render_target  = Some(render::RenderTargetId(0));
+        render_state.render_target  = Some(render_target);
+        render_state.blend_mode     = Some(render::BlendMode::DestinationOver);
         render_state.shader         = Some(render::ShaderType::Simple { erase_texture: None });
 
         for render_idx in 0..layer.render_order.len() {
@@ -157,7 +151,7 @@ impl RenderCore {
                     panic!("Tessellation is not complete (tried to render too early)");
                 },
 
-                VertexBuffer(_buffers) => {
+                VertexBuffer(_buffers) | TexturedVertexBuffer(_buffers, _) => {
                     // Should already have sent all the vertex buffers
                     panic!("Tessellation is not complete (found unexpected vertex buffer in layer)");
                 },
@@ -177,7 +171,7 @@ impl RenderCore {
                         let old_state           = *render_state;
 
                         // Render the layer associated with the sprite
-                        let render_sprite       = core.render_layer(sprite_transform, sprite_layer, render_state);
+                        let render_sprite       = core.render_layer(sprite_transform, sprite_layer, render_target, render_state);
 
                         // Items before the sprite are rendered using the 'pre-sprite' rendering
                         render_layer_stack.extend(old_state.update_from_state(render_state));
@@ -212,8 +206,18 @@ impl RenderCore {
 
                     if new_blend_mode == &render::BlendMode::DestinationOut {
                         // The previous state should use the eraser texture that we're abount to generate
-                        if old_state.render_target == Some(render::RenderTargetId(0)) {
+                        if old_state.render_target == Some(render_target) {
                             old_state.shader = Some(render::ShaderType::Simple { erase_texture: Some(render::TextureId(1)) });
+
+                            // Textured fills that use the previous state need to use the eraser texture too
+                            for action_idx in textured_shaders.iter() {
+                                if let render::RenderAction::UseShader(shader) = &mut render_layer_stack[*action_idx] {
+                                    *shader = match *shader {
+                                        render::ShaderType::Simple { .. }                   => render::ShaderType::Simple { erase_texture: Some(render::TextureId(1)) },
+                                        render::ShaderType::Texture { texture, repeat, .. } => render::ShaderType::Texture { texture, repeat, erase_texture: Some(render::TextureId(1)) }
+                                    };
+                                }
+                            }
                         }
 
                         // Render to the eraser texture
@@ -226,7 +230,7 @@ impl RenderCore {
                     } else {
                         // Render the main buffer
                         render_state.blend_mode     = Some(*new_blend_mode);
-                        render_state.render_target  = Some(render::RenderTargetId(0));
+                        render_state.render_target  = Some(render_target);
                         render_state.shader         = Some(render::ShaderType::Simple { erase_texture: None });
 
                         // Use the eraser texture if one is specified
@@ -237,11 +241,36 @@ impl RenderCore {
 
                     // Apply the old state for the preceding instrucitons
                     render_layer_stack.extend(old_state.update_from_state(render_state));
+                    textured_shaders.clear();
                 },
 
                 DrawIndexed(vertex_buffer, index_buffer, num_items) => {
                     // Draw the triangles
                     render_layer_stack.push(render::RenderAction::DrawIndexedTriangles(*vertex_buffer, *index_buffer, *num_items));
+                },
+
+                DrawTexturedIndexed(texture, repeat, vertex_buffer, index_buffer, num_items) => {
+                    // The texture shader uses the same eraser texture as the current shader
+                    let erase_texture           = match render_state.shader {
+                        Some(render::ShaderType::Simple { erase_texture })          => erase_texture,
+                        Some(render::ShaderType::Texture { erase_texture, .. })     => erase_texture,
+                        None                                                        => None
+                    };
+                    let mut textured_state      = *render_state;
+                    textured_state.shader       = Some(render::ShaderType::Texture { texture: *texture, repeat: *repeat, erase_texture: erase_texture });
+
+                    // Switch back to the current state after drawing the triangles
+                    let restore_state           = render_state.update_from_state(&textured_state);
+                    textured_shaders.extend(Self::shader_action_indexes(&restore_state, render_layer_stack.len()));
+                    render_layer_stack.extend(restore_state);
+
+                    // Draw the triangles
+                    render_layer_stack.push(render::RenderAction::DrawIndexedTriangles(*vertex_buffer, *index_buffer, *num_items));
+
+                    // Switch to the texture shader before drawing the triangles
+                    let use_texture             = textured_state.update_from_state(render_state);
+                    textured_shaders.extend(Self::shader_action_indexes(&use_texture, render_layer_stack.len()));
+                    render_layer_stack.extend(use_texture);
                 }
             }
         }
@@ -259,6 +288,105 @@ impl RenderCore {
         // Generate a pending set of actions for the current layer
         return render_layer_stack;
     }
+
+    ///
+    /// Returns the indexes of the `UseShader` actions in a list of actions that will be added to a stack at the specified position
+    ///
+    fn shader_action_indexes(actions: &[render::RenderAction], stack_len: usize) -> impl Iterator<Item=usize> {
+        actions.iter()
+            .enumerate()
+            .filter(|(_, action)| match action { render::RenderAction::UseShader(_) => true, _ => false })
+            .map(|(idx, _)| idx + stack_len)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    ///
+    /// Generates the actions to draw a single layer to a render target, in the order that they should be executed
+    ///
+    fn draw_layer(&mut self, viewport_transform: canvas::Transform2D, layer_handle: LayerHandle, render_target: render::RenderTargetId) -> Vec<render::RenderAction> {
+        // Send any pending vertex buffers, then render the layer (note that the rendering is a stack, so the vertex buffers go on the end)
+        let send_vertex_buffers = self.send_vertex_buffers(layer_handle);
+        let mut render_state    = RenderStreamState::new();
+
+        let mut render_layer    = self.render_layer(viewport_transform, layer_handle, render_target, &mut render_state);
+        render_layer.extend(render_state.update_from_state(&RenderStreamState::new()));
+        render_layer.extend(send_vertex_buffers);
+
+        // Reverse the stack so the actions are in the order they'll be executed
+        render_layer.reverse();
+        render_layer
+    }
+
+    ///
+    /// Returns one of the off-screen render targets used for compositing layers, adding the actions to create it to the
+    /// specified list if it doesn't exist yet
+    ///
+    fn offscreen_render_target(&mut self, index: usize, actions: &mut Vec<render::RenderAction>) -> render::RenderTargetId {
+        // Render target 0 is the main MSAA buffer and 1 is the eraser mask, so the off-screen targets start at 2
+        while self.offscreen_targets.len() <= index {
+            let target_id       = self.offscreen_targets.len() + 2;
+            let texture_id      = render::TextureId(self.allocate_texture());
+            let (width, height) = self.offscreen_size;
+
+            actions.push(render::RenderAction::CreateRenderTarget(render::RenderTargetId(target_id), texture_id, width, height, render::RenderTargetType::MultisampledTexture));
+            self.offscreen_targets.push(texture_id);
+        }
+
+        render::RenderTargetId(index + 2)
+    }
+
+    ///
+    /// Generates the actions to draw a set of layers to a render target, in the order that they should be executed
+    ///
+    /// Layers are rendered from the top down, underneath whatever is already in the render target. Layers with an alpha
+    /// value are rendered off-screen and composited underneath the layers above, and layers with a blend mode are blended
+    /// off-screen with all of the layers beneath them before the result is composited.
+    ///
+    fn render_layers(&mut self, viewport_transform: canvas::Transform2D, layers: &[LayerHandle], render_target: render::RenderTargetId, depth: usize) -> Vec<render::RenderAction> {
+        use render::RenderAction::*;
+
+        let mut actions = vec![];
+
+        for layer_idx in (0..layers.len()).rev() {
+            let layer_handle    = layers[layer_idx];
+            let layer           = self.layer(layer_handle);
+            let alpha           = layer.alpha;
+            let blend_mode      = layer.blend_mode;
+
+            if blend_mode == canvas::BlendMode::SourceOver && alpha >= 1.0 {
+                // Draw directly underneath the existing layers
+                actions.extend(self.draw_layer(viewport_transform, layer_handle, render_target));
+            } else if blend_mode == canvas::BlendMode::SourceOver {
+                // Draw the layer off-screen, then composite it underneath the existing layers
+                let layer_target = self.offscreen_render_target(depth*2, &mut actions);
+
+                actions.extend(vec![SelectRenderTarget(layer_target), Clear(render::Rgba8([0, 0, 0, 0]))]);
+                actions.extend(self.draw_layer(viewport_transform, layer_handle, layer_target));
+                actions.extend(vec![SelectRenderTarget(render_target), BlendMode(render::BlendMode::DestinationOver), BlendFrameBuffer(layer_target, alpha)]);
+            } else {
+                // The layers beneath this one need to be drawn before it can be blended with them
+                let layer_target    = self.offscreen_render_target(depth*2, &mut actions);
+                let backdrop_target = self.offscreen_render_target(depth*2+1, &mut actions);
+
+                actions.extend(vec![SelectRenderTarget(backdrop_target), Clear(render::Rgba8([0, 0, 0, 0]))]);
+                actions.extend(self.render_layers(viewport_transform, &layers[0..layer_idx], backdrop_target, depth+1));
+
+                // Draw this layer off-screen and blend it with the layers beneath it
+                actions.extend(vec![SelectRenderTarget(layer_target), Clear(render::Rgba8([0, 0, 0, 0]))]);
+                actions.extend(self.draw_layer(viewport_transform, layer_handle, layer_target));
+                actions.extend(vec![SelectRenderTarget(backdrop_target), BlendMode(composite_blend_mode(blend_mode)), BlendFrameBuffer(layer_target, alpha)]);
+
+                // Composite the result underneath the existing layers
+                actions.extend(vec![SelectRenderTarget(render_target), BlendMode(render::BlendMode::DestinationOver), BlendFrameBuffer(backdrop_target, 1.0)]);
+
+                // All of the remaining layers have been drawn
+                break;
+            }
+        }
+
+        actions
+    }
 }
 
 impl<'a> Stream for RenderStream<'a> {
@@ -280,48 +408,25 @@ impl<'a> Stream for RenderStream<'a> {
                 return Poll::Pending;
             } else {
                 // Finished processing the rendering: can send the actual rendering commands to the hardware layer
-                // Layers are rendered in reverse order
                 self.processing_future  = None;
-                self.layer_id           = self.core.sync(|core| core.layers.len());
-                self.render_index       = 0;
-            }
 
-        }
+                // Layers are composited with the layers beneath them, so the actions are generated for all the layers at once
+                let viewport_transform  = self.viewport_transform;
+                let render_layers       = self.core.sync(|core| {
+                    let layers = core.layers.clone();
+                    core.render_layers(viewport_transform, &layers, render::RenderTargetId(0), 0)
+                });
 
-        // We've generated all the vertex buffers: generate the instructions to render them
-        let mut layer_id        = self.layer_id;
-        let viewport_transform  = self.viewport_transform;
+                // Pending actions are a stack, so they're stored in reverse
+                self.pending_stack      = render_layers.into_iter().rev().collect();
 
-        let result              = if layer_id == 0 {
-            // Stop if we've processed all the layers
-            None
-        } else {
-            // Move to the previous layer
-            layer_id -= 1;
-
-            self.core.sync(|core| {
-                // Send any pending vertex buffers, then render the layer (note that the rendering is a stack, so the vertex buffers go on the end)
-                let layer_handle        = core.layers[layer_id];
-                let send_vertex_buffers = core.send_vertex_buffers(layer_handle);
-                let mut render_state    = RenderStreamState::new();
-
-                let mut render_layer    = core.render_layer(viewport_transform, layer_handle, &mut render_state);
-                render_layer.extend(render_state.update_from_state(&RenderStreamState::new()));
-                render_layer.extend(send_vertex_buffers);
-
-                Some(render_layer)
-            })
-        };
-
-        // Update the layer ID to continue iterating
-        self.layer_id       = layer_id;
-
-        // Add the result to the pending queue
-        if let Some(result) = result {
-            // There are more actions to add to the pending stack
-            self.pending_stack = result;
-            return Poll::Ready(self.pending_stack.pop());
-        } else if let Some(final_actions) = self.final_stack.take() {
+                if self.pending_stack.len() > 0 {
+                    return Poll::Ready(self.pending_stack.pop());
+                }
+            }
+        }
+
+        if let Some(final_actions) = self.final_stack.take() {
             // There are no more drawing actions, but we have a set of final post-render instructions to execute
             self.pending_stack = final_actions;
             return Poll::Ready(self.pending_stack.pop());
@@ -332,6 +437,28 @@ impl<'a> Stream for RenderStream<'a> {
     }
 }
 
+///
+/// Converts the blend mode for a layer to the blend mode used to composite it with the layers beneath it
+///
+fn composite_blend_mode(blend_mode: canvas::BlendMode) -> render::BlendMode {
+    use canvas::BlendMode::*;
+
+    match blend_mode {
+        SourceOver      => render::BlendMode::SourceOver,
+        SourceIn        => render::BlendMode::SourceIn,
+        SourceOut       => render::BlendMode::SourceOut,
+        DestinationOver => render::BlendMode::DestinationOver,
+        DestinationIn   => render::BlendMode::DestinationIn,
+        DestinationOut  => render::BlendMode::DestinationOut,
+        SourceAtop      => render::BlendMode::SourceATop,
+        DestinationAtop => render::BlendMode::DestinationATop,
+        Multiply        => render::BlendMode::Multiply,
+        Screen          => render::BlendMode::Screen,
+        Darken          => render::BlendMode::Darken,
+        Lighten         => render::BlendMode::Lighten
+    }
+}
+
 ///
 /// Converts a canvas transform to a rendering matrix
 ///
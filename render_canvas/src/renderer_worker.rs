@@ -1,5 +1,6 @@
 use super::render_entity::*;
 use super::renderer_core::*;
+use super::canvas_renderer::*;
 use super::stroke_settings::*;
 
 use flo_render as render;
@@ -10,6 +11,15 @@ use lyon::math::{Point};
 use lyon::tessellation;
 use lyon::tessellation::{VertexBuffers, BuffersBuilder, StrokeOptions, FillOptions, FillRule, FillAttributes, StrokeAttributes};
 
+/// The number of pixels in the texture used to draw a linear gradient
+const LINEAR_GRADIENT_TEXTURE_SIZE: usize = 256;
+
+/// The width and height of the texture used to draw a radial gradient
+const RADIAL_GRADIENT_TEXTURE_SIZE: usize = 128;
+
+/// The width and height of the texture used to draw a single tile of a texture pattern
+const PATTERN_TEXTURE_SIZE: usize = 32;
+
 ///
 /// References an entity in a layer
 ///
@@ -33,6 +43,18 @@ pub enum CanvasJob {
         entity:         LayerEntityRef
     },
 
+    ///
+    /// Tessellates a path by filling it with a gradient or a texture
+    ///
+    /// The fill style is drawn into a texture that's mapped onto the tessellated path.
+    ///
+    FillStyled {
+        path:           path::Path,
+        fill_style:     canvas::FillStyle,
+        erase:          bool,
+        entity:         LayerEntityRef
+    },
+
     Stroke {
         path:           path::Path,
         stroke_options: StrokeSettings,
@@ -63,6 +85,7 @@ impl CanvasWorker {
 
         match job {
             Fill    { path, color, entity }             => self.fill(path, color, entity),
+            FillStyled { path, fill_style, erase, entity }  => self.fill_styled(path, fill_style, erase, entity),
             Stroke  { path, stroke_options, entity }    => self.stroke(path, stroke_options, entity)
         }
     }
@@ -93,6 +116,145 @@ impl CanvasWorker {
         (entity, RenderEntity::VertexBuffer(geometry))
     }
 
+    ///
+    /// Fills the current path with a gradient or a texture and returns the resulting render entity
+    ///
+    fn fill_styled(&mut self, path: path::Path, fill_style: canvas::FillStyle, erase: bool, entity: LayerEntityRef) -> (LayerEntityRef, RenderEntity) {
+        // Generate the texture to fill the path with, and the transformation from canvas coordinates to texture coordinates
+        let (texture, tex_transform)    = Self::fill_texture(&fill_style, erase);
+
+        // Create the tessellator and geometry
+        let mut tessellator             = tessellation::FillTessellator::new();
+        let mut geometry                = VertexBuffers::new();
+
+        // Set up the fill options
+        let mut fill_options            = FillOptions::default();
+        fill_options.fill_rule          = FillRule::NonZero;
+
+        // Tessellate the current path (the vertices are white so the texture colour is used unchanged)
+        tessellator.tessellate_path(&path, &fill_options,
+            &mut BuffersBuilder::new(&mut geometry, move |point: Point, _attr: FillAttributes| {
+                let (u, v) = transform_point(&tex_transform, point.x, point.y);
+
+                render::Vertex2D {
+                    pos:        point.to_array(),
+                    tex_coord:  [u, v],
+                    color:      [255, 255, 255, 255]
+                }
+            })).unwrap();
+
+        // Result is a vertex buffer render entity with a texture
+        (entity, RenderEntity::TexturedVertexBuffer(geometry, texture))
+    }
+
+    ///
+    /// Generates the texture for a fill style, along with the transformation from canvas coordinates to texture coordinates
+    ///
+    /// When drawing to the erase layer, all of the colour components of the texture are set to its alpha value.
+    ///
+    fn fill_texture(fill_style: &canvas::FillStyle, erase: bool) -> (FillTexture, canvas::Transform2D) {
+        use canvas::FillStyle::*;
+
+        // Work out the size of the texture, how to map canvas coordinates onto it and the canvas position of each of its pixels
+        let (size, repeat, tex_transform, pixel_transform) = match fill_style {
+            Solid(_) => {
+                let tex_transform   = canvas::Transform2D([[0.0, 0.0, 0.5], [0.0, 0.0, 0.5], [0.0, 0.0, 1.0]]);
+                let pixel_transform = canvas::Transform2D::identity();
+
+                ((1, 1), false, tex_transform, pixel_transform)
+            }
+
+            LinearGradient(_, (x1, y1), (x2, y2)) => {
+                // One row of pixels from the start to the end of the gradient, with the first and last pixel centers on the end points
+                let (dx, dy)        = (x2-x1, y2-y1);
+                let len_sq          = dx*dx + dy*dy;
+                let len_sq          = if len_sq > 0.0 { len_sq } else { 1.0 };
+                let width           = LINEAR_GRADIENT_TEXTURE_SIZE as f32;
+                let scale           = (width-1.0) / width;
+
+                let tex_transform   = canvas::Transform2D([
+                    [dx/len_sq*scale,   dy/len_sq*scale,    0.5/width - (x1*dx + y1*dy)/len_sq*scale],
+                    [0.0,               0.0,                0.5],
+                    [0.0,               0.0,                1.0]
+                ]);
+                let pixel_transform = canvas::Transform2D([
+                    [dx/(width-1.0),    0.0,                *x1],
+                    [dy/(width-1.0),    0.0,                *y1],
+                    [0.0,               0.0,                1.0]
+                ]);
+
+                ((LINEAR_GRADIENT_TEXTURE_SIZE, 1), false, tex_transform, pixel_transform)
+            }
+
+            RadialGradient(_, (cx, cy), radius) => {
+                // A square of pixels covering the circle (the colour beyond the circle is the same as the colour at its edges)
+                let radius          = radius.max(0.001);
+                let width           = RADIAL_GRADIENT_TEXTURE_SIZE as f32;
+                let (x1, y1)        = (cx - radius, cy - radius);
+                let pixel_size      = radius*2.0/width;
+
+                let tex_transform   = canvas::Transform2D([
+                    [0.5/radius,        0.0,                -x1*0.5/radius],
+                    [0.0,               0.5/radius,         -y1*0.5/radius],
+                    [0.0,               0.0,                1.0]
+                ]);
+                let pixel_transform = canvas::Transform2D([
+                    [pixel_size,        0.0,                x1 + pixel_size*0.5],
+                    [0.0,               pixel_size,         y1 + pixel_size*0.5],
+                    [0.0,               0.0,                1.0]
+                ]);
+
+                ((RADIAL_GRADIENT_TEXTURE_SIZE, RADIAL_GRADIENT_TEXTURE_SIZE), false, tex_transform, pixel_transform)
+            }
+
+            Texture(_, _, _, tile_size) => {
+                // A single tile that's repeated across the path
+                let tile_size       = tile_size.max(0.001);
+                let width           = PATTERN_TEXTURE_SIZE as f32;
+                let pixel_size      = tile_size/width;
+
+                let tex_transform   = canvas::Transform2D([
+                    [1.0/tile_size,     0.0,                0.0],
+                    [0.0,               1.0/tile_size,      0.0],
+                    [0.0,               0.0,                1.0]
+                ]);
+                let pixel_transform = canvas::Transform2D([
+                    [pixel_size,        0.0,                pixel_size*0.5],
+                    [0.0,               pixel_size,         pixel_size*0.5],
+                    [0.0,               0.0,                1.0]
+                ]);
+
+                ((PATTERN_TEXTURE_SIZE, PATTERN_TEXTURE_SIZE), true, tex_transform, pixel_transform)
+            }
+        };
+
+        // Generate the pixels, as BGRA values from the bottom row upwards
+        let (width, height) = size;
+        let mut pixels      = Vec::with_capacity(width*height*4);
+
+        for y in 0..height {
+            for x in 0..width {
+                let pos                         = transform_point(&pixel_transform, x as f32, y as f32);
+                let render::Rgba8([r, g, b, a]) = CanvasRenderer::render_color(fill_style.color_at(pos));
+
+                // When drawing to the erase layer (DesintationOut blend mode), all colour components are alpha components
+                if erase {
+                    pixels.extend([a, a, a, a].iter());
+                } else {
+                    pixels.extend([b, g, r, a].iter());
+                }
+            }
+        }
+
+        let texture = FillTexture {
+            size:   size,
+            pixels: pixels,
+            repeat: repeat
+        };
+
+        (texture, tex_transform)
+    }
+
     ///
     /// Converts some stroke settings to Lyon stroke options
     ///
@@ -142,3 +304,13 @@ impl CanvasWorker {
         (entity, RenderEntity::VertexBuffer(geometry))
     }
 }
+
+///
+/// Applies a transformation to a point
+///
+#[inline]
+fn transform_point(transform: &canvas::Transform2D, x: f32, y: f32) -> (f32, f32) {
+    let canvas::Transform2D(matrix) = transform;
+
+    (matrix[0][0]*x + matrix[0][1]*y + matrix[0][2], matrix[1][0]*x + matrix[1][1]*y + matrix[1][2])
+}
@@ -0,0 +1,230 @@
+use flo_render::*;
+use flo_render_canvas::*;
+use flo_canvas::*;
+
+use futures::prelude::*;
+use futures::executor;
+
+///
+/// Renders some drawing instructions to a 100x100 frame buffer using the software renderer
+///
+fn render_drawing(drawing: Vec<Draw>) -> SoftwareRenderer {
+    executor::block_on(async {
+        // Create the canvas renderer
+        let mut canvas_renderer = CanvasRenderer::new();
+        canvas_renderer.set_viewport(0.0..100.0, 0.0..100.0, 100.0, 100.0, 1.0);
+
+        // Generate the render actions
+        let actions             = canvas_renderer.draw(drawing.into_iter()).collect::<Vec<_>>().await;
+
+        // Run them through the software renderer
+        let mut renderer        = SoftwareRenderer::new(100, 100);
+        renderer.render(actions);
+
+        renderer
+    })
+}
+
+#[test]
+fn fill_circle() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    drawing.circle(0.0, 0.0, 25.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    assert!(frame_buffer.size() == (100, 100));
+    assert!(frame_buffer.pixel_rgba8(50, 50) == Rgba8([255, 0, 0, 255]));
+    assert!(frame_buffer.pixel_rgba8(50, 30) == Rgba8([255, 0, 0, 255]));
+    assert!(frame_buffer.pixel_rgba8(5, 5) == Rgba8([0, 0, 0, 0]));
+    assert!(frame_buffer.pixel_rgba8(50, 10) == Rgba8([0, 0, 0, 0]));
+}
+
+#[test]
+fn y_axis_points_upwards() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.fill_color(Color::Rgba(0.0, 1.0, 0.0, 1.0));
+    drawing.rect(-50.0, 0.0, 50.0, 50.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    // The frame buffer is stored from the top down
+    assert!(frame_buffer.pixel_rgba8(50, 25) == Rgba8([0, 255, 0, 255]));
+    assert!(frame_buffer.pixel_rgba8(50, 75) == Rgba8([0, 0, 0, 0]));
+}
+
+#[test]
+fn erase_from_rectangle() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    drawing.rect(-50.0, -50.0, 50.0, 50.0);
+    drawing.fill();
+
+    drawing.blend_mode(flo_canvas::BlendMode::DestinationOut);
+    drawing.circle(0.0, 0.0, 25.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    assert!(frame_buffer.pixel_rgba8(50, 50) == Rgba8([0, 0, 0, 0]));
+    assert!(frame_buffer.pixel_rgba8(5, 5) == Rgba8([0, 0, 255, 255]));
+}
+
+#[test]
+fn shared_triangle_edges_are_drawn_once() {
+    // Two triangles forming a square, drawn with a translucent colour
+    let color       = [0, 0, 0, 128];
+    let vertices    = vec![
+        Vertex2D { pos: [-1.0, -1.0], tex_coord: [0.0, 0.0], color: color },
+        Vertex2D { pos: [1.0, -1.0], tex_coord: [0.0, 0.0], color: color },
+        Vertex2D { pos: [1.0, 1.0], tex_coord: [0.0, 0.0], color: color },
+        Vertex2D { pos: [-1.0, 1.0], tex_coord: [0.0, 0.0], color: color },
+    ];
+
+    let mut renderer = SoftwareRenderer::new(16, 16);
+    renderer.render(vec![
+        RenderAction::CreateVertex2DBuffer(VertexBufferId(0), vertices),
+        RenderAction::CreateIndexBuffer(IndexBufferId(0), vec![0, 1, 2, 0, 2, 3]),
+        RenderAction::BlendMode(flo_render::BlendMode::SourceOver),
+        RenderAction::DrawIndexedTriangles(VertexBufferId(0), IndexBufferId(0), 6)
+    ]);
+
+    // Every pixel (including those on the diagonal) should have been blended exactly once
+    let expected = renderer.frame_buffer().pixel_rgba8(0, 15);
+    for y in 0..16 {
+        for x in 0..16 {
+            assert!(renderer.frame_buffer().pixel_rgba8(x, y) == expected);
+        }
+    }
+}
+
+#[test]
+fn half_opacity_layer_is_half_transparent() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.layer(1);
+    drawing.layer_alpha(1, 0.5);
+    drawing.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    drawing.circle(0.0, 0.0, 25.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    assert!(frame_buffer.pixel_rgba8(50, 50) == Rgba8([255, 0, 0, 128]));
+    assert!(frame_buffer.pixel_rgba8(5, 5) == Rgba8([0, 0, 0, 0]));
+}
+
+#[test]
+fn half_opacity_layer_blends_with_layer_beneath() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    drawing.rect(-50.0, -50.0, 50.0, 50.0);
+    drawing.fill();
+
+    drawing.layer(1);
+    drawing.layer_alpha(1, 0.5);
+    drawing.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    drawing.circle(0.0, 0.0, 25.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    assert!(frame_buffer.pixel_rgba8(50, 50) == Rgba8([128, 0, 128, 255]));
+    assert!(frame_buffer.pixel_rgba8(5, 5) == Rgba8([0, 0, 255, 255]));
+}
+
+#[test]
+fn multiply_layer_multiplies_layer_beneath() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.fill_color(Color::Rgba(1.0, 1.0, 0.0, 1.0));
+    drawing.rect(-50.0, -50.0, 50.0, 50.0);
+    drawing.fill();
+
+    drawing.layer(1);
+    drawing.layer_blend(1, flo_canvas::BlendMode::Multiply);
+    drawing.fill_color(Color::Rgba(1.0, 0.0, 1.0, 1.0));
+    drawing.circle(0.0, 0.0, 25.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    assert!(frame_buffer.pixel_rgba8(50, 50) == Rgba8([255, 0, 0, 255]));
+    assert!(frame_buffer.pixel_rgba8(5, 5) == Rgba8([255, 255, 0, 255]));
+}
+
+#[test]
+fn darken_layer_keeps_darker_colors() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.fill_color(Color::Rgba(0.0, 1.0, 1.0, 1.0));
+    drawing.rect(-50.0, -50.0, 50.0, 50.0);
+    drawing.fill();
+
+    drawing.layer(1);
+    drawing.layer_blend(1, flo_canvas::BlendMode::Darken);
+    drawing.fill_color(Color::Rgba(1.0, 0.0, 1.0, 1.0));
+    drawing.circle(0.0, 0.0, 25.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    assert!(frame_buffer.pixel_rgba8(50, 50) == Rgba8([0, 0, 255, 255]));
+    assert!(frame_buffer.pixel_rgba8(5, 5) == Rgba8([0, 255, 255, 255]));
+}
+
+#[test]
+fn fill_linear_gradient() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.new_gradient();
+    drawing.gradient_stop(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    drawing.gradient_stop(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    drawing.fill_linear_gradient(-50.0, 0.0, 50.0, 0.0);
+    drawing.rect(-50.0, -50.0, 50.0, 50.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    // The gradient runs from red on the left to blue on the right
+    let Rgba8([r, g, b, a]) = frame_buffer.pixel_rgba8(1, 50);
+    assert!(r > 245 && g == 0 && b < 10 && a == 255);
+
+    let Rgba8([r, g, b, a]) = frame_buffer.pixel_rgba8(50, 50);
+    assert!(r > 120 && r < 135 && g == 0 && b > 120 && b < 135 && a == 255);
+
+    let Rgba8([r, g, b, a]) = frame_buffer.pixel_rgba8(98, 50);
+    assert!(r < 10 && g == 0 && b > 245 && a == 255);
+}
+
+#[test]
+fn fill_checks_texture() {
+    let mut drawing = vec![];
+    drawing.canvas_height(100.0);
+    drawing.fill_texture(TexturePattern::Checks, Color::Rgba(1.0, 0.0, 0.0, 1.0), Color::Rgba(0.0, 0.0, 1.0, 1.0), 20.0);
+    drawing.rect(-50.0, -50.0, 50.0, 50.0);
+    drawing.fill();
+
+    let renderer        = render_drawing(drawing);
+    let frame_buffer    = renderer.frame_buffer();
+
+    // The tiles are 20 units across, with the foreground colour in the first quarter of each tile
+    assert!(frame_buffer.pixel_rgba8(55, 45) == Rgba8([255, 0, 0, 255]));
+    assert!(frame_buffer.pixel_rgba8(65, 45) == Rgba8([0, 0, 255, 255]));
+    assert!(frame_buffer.pixel_rgba8(65, 35) == Rgba8([255, 0, 0, 255]));
+    assert!(frame_buffer.pixel_rgba8(75, 45) == Rgba8([255, 0, 0, 255]));
+}
@@ -0,0 +1,19 @@
+/***
+ **
+ ** Review annotations for FlowBetween files
+ **
+ **   Files created before annotations were introduced do not have this table, so it is created
+ **   whenever a file is opened if it's not already present.
+ **
+ ***/
+
+/**
+ * The serialized review annotations, along with the layer that each one is attached to
+ */
+CREATE TABLE IF NOT EXISTS Annotations (
+    AnnotationId INTEGER NOT NULL PRIMARY KEY,
+    LayerId INTEGER NOT NULL,
+    Annotation TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS Idx_AnnotationLayer ON Annotations (LayerId);
@@ -0,0 +1,24 @@
+/***
+ **
+ ** Audio clips for FlowBetween files
+ **
+ **   Files created before audio layers were introduced do not have this table, so it is created
+ **   whenever a file is opened if it's not already present.
+ **
+ ***/
+
+/**
+ * The serialized audio clips that can be attached to layers (clips share their IDs with the elements)
+ */
+CREATE TABLE IF NOT EXISTS AudioClips (
+    ClipId INTEGER NOT NULL PRIMARY KEY,
+    Audio TEXT NOT NULL
+);
+
+/**
+ * The audio clip attached to each layer
+ */
+CREATE TABLE IF NOT EXISTS LayerAudio (
+    LayerId INTEGER NOT NULL PRIMARY KEY,
+    ClipId INTEGER NOT NULL
+);
@@ -0,0 +1,16 @@
+/***
+ **
+ ** Reference images for FlowBetween files
+ **
+ **   Files created before reference images were stored separately from the elements that display them do not have
+ **   this table, so it is created whenever a file is opened if it's not already present.
+ **
+ ***/
+
+/**
+ * The serialized reference images that can be placed on keyframes (images share their IDs with the elements)
+ */
+CREATE TABLE IF NOT EXISTS Images (
+    ImageId INTEGER NOT NULL PRIMARY KEY,
+    Image TEXT NOT NULL
+);
@@ -0,0 +1,23 @@
+/***
+ **
+ ** Undo history for FlowBetween files
+ **
+ **   Files created before the undo history was introduced do not have this table, so it is created
+ **   whenever a file is opened if it's not already present.
+ **
+ ***/
+
+/**
+ * The steps in the undo and redo stacks (Stack is 0 for the undo stack and 1 for the redo stack)
+ *
+ * Each undo history has its own pair of stacks (so, for example, each editor can undo their own edits)
+ */
+CREATE TABLE IF NOT EXISTS UndoStep (
+    StepId INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    History INTEGER NOT NULL,
+    Stack INTEGER NOT NULL,
+    Step TEXT NOT NULL
+);
+
+/* Index to find the topmost step in a stack */
+CREATE INDEX IF NOT EXISTS Idx_UndoStack ON UndoStep (History, Stack, StepId);
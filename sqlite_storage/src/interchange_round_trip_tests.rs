@@ -0,0 +1,371 @@
+use super::*;
+
+use flo_animation::*;
+use flo_animation::interchange::*;
+
+use futures::*;
+use futures::executor;
+use std::time::Duration;
+use std::sync::*;
+
+///
+/// Creates an in-memory animation, returning the storage so the animation can be exported
+///
+fn create_animation() -> (Arc<SqliteAnimationStorage>, impl EditableAnimation) {
+    let sqlite_store    = Arc::new(SqliteAnimationStorage::new_from_connection(rusqlite::Connection::open_in_memory().unwrap()));
+    let editor_store    = Arc::clone(&sqlite_store);
+    let animation       = create_animation_editor(move |commands| editor_store.get_responses(commands).boxed());
+
+    (sqlite_store, animation)
+}
+
+///
+/// Exports the animation held in a storage object
+///
+fn export(storage: &Arc<SqliteAnimationStorage>) -> String {
+    let storage = Arc::clone(storage);
+
+    executor::block_on(export_animation(move |commands| storage.get_responses(commands).boxed()))
+}
+
+///
+/// Creates an animation containing a variety of elements
+///
+fn create_test_animation() -> (Arc<SqliteAnimationStorage>, impl EditableAnimation) {
+    let (storage, anim) = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetSize(800.0, 600.0),
+        AnimationEdit::SetFrameLength(Duration::from_micros(41_667)),
+        AnimationEdit::SetDuration(Duration::from_secs(30)),
+
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::SetName("Background".to_string())),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetName("Foreground".to_string())),
+
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(500))),
+
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Assigned(10), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Assigned(11), BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(12), Arc::new(vec![
+            RawPoint::from((10.0, 10.0)),
+            RawPoint::from((20.0, 5.0)),
+            RawPoint::from((30.0, 15.0))
+        ])))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(13), Arc::new(vec![
+            RawPoint::from((50.0, 50.0)),
+            RawPoint::from((60.0, 70.0))
+        ])))),
+
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(500), PathEdit::SelectBrush(ElementId::Assigned(20), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(500), PathEdit::BrushProperties(ElementId::Assigned(21), BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(500), PathEdit::CreatePath(ElementId::Assigned(22), Arc::new(vec![
+            PathComponent::Move(PathPoint::new(10.0, 20.0)),
+            PathComponent::Line(PathPoint::new(20.0, 30.0)),
+            PathComponent::Bezier(PathPoint::new(40.0, 40.0), PathPoint::new(30.0, 30.0), PathPoint::new(20.0, 20.0)),
+            PathComponent::Close
+        ])))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(500), PathEdit::CreatePath(ElementId::Assigned(23), Arc::new(vec![
+            PathComponent::Move(PathPoint::new(100.0, 100.0)),
+            PathComponent::Line(PathPoint::new(200.0, 100.0)),
+            PathComponent::Line(PathPoint::new(200.0, 200.0)),
+            PathComponent::Close
+        ])))),
+        AnimationEdit::Element(vec![ElementId::Assigned(22), ElementId::Assigned(23)], ElementEdit::Group(ElementId::Assigned(24), GroupType::Normal)),
+
+        AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::SetOrigin(50.0, 60.0)),
+        AnimationEdit::Motion(ElementId::Assigned(30), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(200.0, 200.0, Duration::from_millis(0)), TimePoint::new(300.0, 250.0, Duration::from_millis(1000))))),
+        AnimationEdit::Element(vec![ElementId::Assigned(12)], ElementEdit::AddAttachment(ElementId::Assigned(30)))
+    ]);
+
+    (storage, anim)
+}
+
+#[test]
+fn export_contains_header() {
+    let (storage, _anim)    = create_test_animation();
+    let exported            = export(&storage);
+
+    assert!(exported.lines().any(|line| line == format!("flo-animation {}", INTERCHANGE_FORMAT_VERSION)));
+    assert!(exported.lines().any(|line| line == "size 800 600"));
+    assert!(exported.lines().any(|line| line == "frame-length 41667"));
+}
+
+#[test]
+fn import_animation_properties() {
+    let (storage, _anim)        = create_test_animation();
+    let exported                = export(&storage);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    assert!((imported.size().0-800.0).abs() < 0.01);
+    assert!((imported.size().1-600.0).abs() < 0.01);
+    assert!(imported.frame_length() == Duration::from_micros(41_667));
+    assert!(imported.duration() == Duration::from_secs(30));
+}
+
+#[test]
+fn import_layers_and_keyframes() {
+    let (storage, _anim)        = create_test_animation();
+    let exported                = export(&storage);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    assert!(imported.get_layer_ids() == vec![1, 2]);
+
+    let layer1 = imported.get_layer_with_id(1).unwrap();
+    let layer2 = imported.get_layer_with_id(2).unwrap();
+
+    assert!(layer1.name() == Some("Background".to_string()));
+    assert!(layer2.name() == Some("Foreground".to_string()));
+
+    assert!(layer1.get_key_frames().collect::<Vec<_>>() == vec![Duration::from_millis(0)]);
+    assert!(layer2.get_key_frames().collect::<Vec<_>>() == vec![Duration::from_millis(0), Duration::from_millis(500)]);
+}
+
+#[test]
+fn import_layer_display_properties() {
+    let (storage, anim)         = create_test_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::SetVisibility(false)),
+        AnimationEdit::Layer(1, LayerEdit::SetLocked(true)),
+        AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.5)),
+        AnimationEdit::Layer(2, LayerEdit::SetBlendMode(flo_canvas::BlendMode::Multiply)),
+        AnimationEdit::Layer(2, LayerEdit::SetInbetweenMode(InbetweenMode::MatchByProximity))
+    ]);
+
+    let exported                = export(&storage);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let layer1 = imported.get_layer_with_id(1).unwrap();
+    let layer2 = imported.get_layer_with_id(2).unwrap();
+
+    assert!(!layer1.is_visible());
+    assert!(layer1.is_locked());
+    assert!(layer2.is_visible());
+    assert!(!layer2.is_locked());
+    assert!((layer2.opacity()-0.5).abs() < 0.001);
+    assert!(layer2.blend_mode() == flo_canvas::BlendMode::Multiply);
+    assert!(layer1.inbetween_mode() == InbetweenMode::Off);
+    assert!(layer2.inbetween_mode() == InbetweenMode::MatchByProximity);
+
+    // Elements on the locked layer are still imported
+    let frame = layer1.get_frame_at_time(Duration::from_millis(0));
+    assert!(frame.element_with_id(ElementId::Assigned(12)).is_some());
+}
+
+#[test]
+fn import_layer_audio() {
+    let (storage, anim)         = create_test_animation();
+    let clip                    = Arc::new(AudioClip::new(8000, 1, (0..800).map(|index| (index*10) as i16).collect()));
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddAudioClip(ElementId::Assigned(50), Arc::clone(&clip)),
+        AnimationEdit::Layer(2, LayerEdit::ImportAudio(ElementId::Assigned(50)))
+    ]);
+
+    let exported                = export(&storage);
+    assert!(exported.lines().any(|line| line.starts_with("audio 2 ")));
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    assert!(imported.get_layer_with_id(1).unwrap().audio_clip().is_none());
+    assert!(imported.get_layer_with_id(2).unwrap().audio_clip() == Some(clip));
+}
+
+#[test]
+fn import_reference_image() {
+    let (storage, anim)         = create_test_animation();
+    let image                   = Arc::new(ReferenceImage::from_rgba(2, 2, &[255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 0, 0, 0, 0]).unwrap());
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddImage(ElementId::Assigned(49), Arc::clone(&image)),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::PlaceImage(ElementId::Assigned(50), ElementId::Assigned(49), (0.0, 0.0), (800.0, 600.0), 0.4))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::PlaceImage(ElementId::Assigned(51), ElementId::Assigned(49), (100.0, 100.0), (80.0, 60.0), 1.0)))
+    ]);
+
+    let exported                = export(&storage);
+
+    // Both elements display the same image, so it should only be exported once
+    assert!(exported.lines().filter(|line| line.starts_with("image ")).count() == 1);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let imported_frame          = imported.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+
+    match imported_frame.element_with_id(ElementId::Assigned(51)) {
+        Some(Vector::Image(image_element)) => { assert!(image_element.image() == Some(Arc::clone(&image))); }
+        _ => { assert!(false) }
+    }
+
+    match imported_frame.element_with_id(ElementId::Assigned(50)) {
+        Some(Vector::Image(image_element)) => {
+            assert!(image_element.image() == Some(image));
+            assert!(image_element.size() == (800.0, 600.0));
+            assert!(image_element.opacity() == 0.4);
+        }
+
+        _ => { assert!(false) }
+    }
+}
+
+#[test]
+fn import_text() {
+    let (storage, anim)         = create_test_animation();
+    let style                   = TextStyle { font: "Lato Bold".to_string(), size: 36.0, color: flo_canvas::Color::Rgba(0.0, 0.5, 1.0, 1.0), alignment: TextAlignment::Center };
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::CreateText(ElementId::Assigned(50), "Title".to_string(), style.clone(), (400.0, 500.0))))
+    ]);
+
+    let exported                = export(&storage);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let imported_frame          = imported.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+
+    match imported_frame.element_with_id(ElementId::Assigned(50)) {
+        Some(Vector::Text(text_element)) => {
+            assert!(*text_element.text() == "Title".to_string());
+            assert!(*text_element.style() == style);
+            assert!(text_element.position() == (400.0, 500.0));
+        }
+
+        _ => { assert!(false) }
+    }
+}
+
+#[test]
+fn import_annotations() {
+    let (storage, anim)         = create_test_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Annotation(ElementId::Assigned(60), AnnotationEdit::Create(Annotation::new(2, Duration::from_millis(500), "Reviewer", 1_600_000_000, Some((100.0, 150.0)), "Move this\nleft"))),
+        AnimationEdit::Annotation(ElementId::Assigned(61), AnnotationEdit::Create(Annotation::new(1, Duration::from_millis(0), "Director", 1_600_000_100, None, "Looks good"))),
+        AnimationEdit::Annotation(ElementId::Assigned(61), AnnotationEdit::SetResolved(true))
+    ]);
+
+    let exported                = export(&storage);
+    assert!(exported.lines().filter(|line| line.starts_with("annotation ")).count() == 2);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let mut original_annotations = anim.get_annotations();
+    let mut imported_annotations = imported.get_annotations();
+    original_annotations.sort_by_key(|(annotation_id, _)| annotation_id.id());
+    imported_annotations.sort_by_key(|(annotation_id, _)| annotation_id.id());
+
+    assert!(imported_annotations.len() == 2);
+    assert!(imported_annotations == original_annotations);
+    assert!(imported_annotations[1].1.resolved);
+}
+
+#[test]
+fn import_brush_strokes() {
+    let (storage, anim)         = create_test_animation();
+    let exported                = export(&storage);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let original_frame  = anim.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+    let imported_frame  = imported.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+
+    let original_ids    = original_frame.vector_elements().unwrap().map(|element| element.id()).collect::<Vec<_>>();
+    let imported_ids    = imported_frame.vector_elements().unwrap().map(|element| element.id()).collect::<Vec<_>>();
+
+    assert!(imported_ids == vec![ElementId::Assigned(12), ElementId::Assigned(13)]);
+    assert!(imported_ids == original_ids);
+
+    for element_id in original_ids {
+        let original_points = match original_frame.element_with_id(element_id) { Some(Vector::BrushStroke(brush_stroke)) => Some(brush_stroke.points()), _ => None };
+        let imported_points = match imported_frame.element_with_id(element_id) { Some(Vector::BrushStroke(brush_stroke)) => Some(brush_stroke.points()), _ => None };
+
+        assert!(original_points.is_some());
+        assert!(imported_points == original_points);
+    }
+}
+
+#[test]
+fn import_motion_attachment() {
+    let (storage, _anim)        = create_test_animation();
+    let exported                = export(&storage);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let frame       = imported.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+    let attached    = frame.attached_elements(ElementId::Assigned(12));
+
+    assert!(attached.contains(&(ElementId::Assigned(30), VectorType::Motion)));
+    assert!(frame.attached_elements(ElementId::Assigned(13)).iter().all(|(_, vector_type)| *vector_type != VectorType::Motion));
+}
+
+#[test]
+fn import_grouped_paths() {
+    let (storage, _anim)        = create_test_animation();
+    let exported                = export(&storage);
+
+    let (_new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let frame       = imported.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(500));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(elements.len() == 1);
+    assert!(elements[0].id() == ElementId::Assigned(24));
+
+    if let Vector::Group(group) = &elements[0] {
+        let grouped_ids = group.elements().map(|element| element.id()).collect::<Vec<_>>();
+        assert!(grouped_ids == vec![ElementId::Assigned(22), ElementId::Assigned(23)]);
+
+        if let Some(Vector::Path(path)) = group.elements().nth(0) {
+            assert!(path.path().elements().collect::<Vec<_>>() == vec![
+                PathComponent::Move(PathPoint::new(10.0, 20.0)),
+                PathComponent::Line(PathPoint::new(20.0, 30.0)),
+                PathComponent::Bezier(PathPoint::new(40.0, 40.0), PathPoint::new(30.0, 30.0), PathPoint::new(20.0, 20.0)),
+                PathComponent::Close
+            ]);
+        } else {
+            assert!(false);
+        }
+    } else {
+        // Not a group
+        assert!(false);
+    }
+}
+
+#[test]
+fn export_after_import_is_unchanged() {
+    let (storage, _anim)        = create_test_animation();
+    let exported                = export(&storage);
+
+    let (new_storage, imported) = create_animation();
+    import_animation(&exported, &imported).unwrap();
+
+    let exported_again          = export(&new_storage);
+
+    assert!(AnimationDocument::from_text(&exported_again) == AnimationDocument::from_text(&exported));
+}
+
+#[test]
+fn reject_document_from_future_version() {
+    let (_storage, imported)    = create_animation();
+
+    assert!(import_animation("flo-animation 1000\n", &imported) == Err(InterchangeError::UnsupportedVersion(1000)));
+    assert!(imported.get_layer_ids().len() == 0);
+}
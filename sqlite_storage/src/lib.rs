@@ -6,6 +6,7 @@ mod sqlite_loader;
 
 #[cfg(test)] mod sqlite_core_tests;
 #[cfg(test)] mod round_trip_tests;
+#[cfg(test)] mod interchange_round_trip_tests;
 
 pub use self::sqlite_storage::*;
 pub use self::sqlite_loader::*;
@@ -9,6 +9,9 @@ use futures::*;
 use futures::executor;
 use std::time::Duration;
 use std::sync::*;
+use std::env;
+use std::fs;
+use std::process;
 
 ///
 /// Creates an in-memory animaton for the tests
@@ -454,7 +457,7 @@ fn read_brush_strokes_from_edit_log() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -488,7 +491,7 @@ fn read_brush_strokes_from_edit_log() {
     assert!(match edits[3] {
         AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::
             BrushProperties(ElementId::Assigned(_element_id), ref brush_properties)))
-                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 },
+                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid },
             _ => false
     });
     assert!(match edits[6] {
@@ -514,7 +517,7 @@ fn read_element_delete_from_edit_log() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -552,7 +555,7 @@ fn will_assign_element_ids() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                 RawPoint::from((10.0, 10.0)),
                 RawPoint::from((20.0, 5.0))
@@ -586,7 +589,7 @@ fn fetch_element_by_id() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -636,7 +639,7 @@ fn read_frame_after_edits() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -707,7 +710,7 @@ fn delete_element() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -1573,3 +1576,36 @@ fn retrieve_cached_onionskin_from_different_time() {
 
     assert!(cached_drawing == None);
 }
+
+#[test]
+fn undo_after_reopening_file() {
+    let path = env::temp_dir().join(format!("flo_undo_after_reopening_file_{}.flo", process::id()));
+    fs::remove_file(&path).ok();
+
+    {
+        let sqlite_store    = SqliteAnimationStorage::new_with_file(&path).unwrap();
+        let anim            = create_animation_editor(move |commands| sqlite_store.get_responses(commands).boxed());
+
+        anim.perform_edits(vec![AnimationEdit::AddNewLayer(24)]);
+        anim.perform_edits(vec![AnimationEdit::SetSize(100.0, 200.0)]);
+    }
+
+    // The undo history is stored in the file along with the animation
+    {
+        let sqlite_store    = SqliteAnimationStorage::open_file(&path).unwrap();
+        let anim            = create_animation_editor(move |commands| sqlite_store.get_responses(commands).boxed());
+
+        assert!(anim.size() == (100.0, 200.0));
+        assert!(anim.can_undo());
+
+        anim.undo();
+        assert!(anim.size() == (1920.0, 1080.0));
+        assert!(anim.get_layer_ids() == vec![24]);
+
+        anim.undo();
+        assert!(anim.get_layer_ids().len() == 0);
+        assert!(!anim.can_undo());
+    }
+
+    fs::remove_file(&path).ok();
+}
@@ -8,6 +8,10 @@ use std::ops::{Range};
 use std::time::{Duration};
 
 const BASE_DATA_DEFN: &[u8]          = include_bytes!["../sql/flo_storage.sql"];
+const UNDO_DATA_DEFN: &[u8]          = include_bytes!["../sql/flo_undo.sql"];
+const AUDIO_DATA_DEFN: &[u8]         = include_bytes!["../sql/flo_audio.sql"];
+const IMAGE_DATA_DEFN: &[u8]         = include_bytes!["../sql/flo_images.sql"];
+const ANNOTATION_DATA_DEFN: &[u8]    = include_bytes!["../sql/flo_annotations.sql"];
 
 ///
 /// The SQLite core stores the synchronous data for the SQLite database
@@ -51,7 +55,23 @@ impl SqliteCore {
     pub fn initialize(&mut self) -> Result<(), rusqlite::Error> {
         let defn = String::from_utf8_lossy(BASE_DATA_DEFN);
 
-        self.check_error(self.connection.execute_batch(&defn))
+        self.check_error(self.connection.execute_batch(&defn))?;
+        self.upgrade()
+    }
+
+    ///
+    /// Adds any tables that are missing from databases created by older versions of FlowBetween
+    ///
+    pub fn upgrade(&mut self) -> Result<(), rusqlite::Error> {
+        let undo_defn       = String::from_utf8_lossy(UNDO_DATA_DEFN);
+        let audio_defn      = String::from_utf8_lossy(AUDIO_DATA_DEFN);
+        let image_defn      = String::from_utf8_lossy(IMAGE_DATA_DEFN);
+        let annotation_defn = String::from_utf8_lossy(ANNOTATION_DATA_DEFN);
+
+        self.check_error(self.connection.execute_batch(&undo_defn))?;
+        self.check_error(self.connection.execute_batch(&audio_defn))?;
+        self.check_error(self.connection.execute_batch(&image_defn))?;
+        self.check_error(self.connection.execute_batch(&annotation_defn))
     }
 
     ///
@@ -86,6 +106,7 @@ impl SqliteCore {
             ReadAnimationProperties                             => { self.read_animation_properties() },
             WriteEdit(edit)                                     => { self.write_edit(edit) },
             ReadHighestUnusedElementId                          => { self.read_highest_unused_element_id() },
+            ReadHighestUnusedElementIdInRange(range)            => { self.read_highest_unused_element_id_in_range(range) },
             ReadEditLogLength                                   => { self.read_edit_log_length() },
             ReadEdits(edit_range)                               => { self.read_edits(edit_range) },
             WriteElement(element_id, value)                     => { self.write_element(element_id, value) },
@@ -98,6 +119,7 @@ impl SqliteCore {
             ReadLayerProperties(layer_id)                       => { self.read_layer_properties(layer_id) },
             AddKeyFrame(layer_id, when)                         => { self.add_key_frame(layer_id, when) },
             DeleteKeyFrame(layer_id, when)                      => { self.delete_key_frame(layer_id, when) },
+            MoveKeyFrame(layer_id, from, to)                    => { self.move_key_frame(layer_id, from, to) },
             ReadKeyFrames(layer_id, time_range)                 => { self.read_keyframes(layer_id, time_range) },
             AttachElementToLayer(layer_id, element_id, when)    => { self.attach_element_to_layer(layer_id, element_id, when) },
             DetachElementFromLayer(element_id)                  => { self.detach_element_from_layer(element_id) },
@@ -106,6 +128,21 @@ impl SqliteCore {
             WriteLayerCache(layer_id, when, cache_type, value)  => { self.write_layer_cache(layer_id, when, cache_type, value) },
             DeleteLayerCache(layer_id, when, cache_type)        => { self.delete_layer_cache(layer_id, when, cache_type) },
             ReadLayerCache(layer_id, when, cache_type)          => { self.read_layer_cache(layer_id, when, cache_type) },
+            WriteImage(image_id, image)                         => { self.write_image(image_id, image) },
+            ReadImage(image_id)                                 => { self.read_image(image_id) },
+            WriteAudioClip(clip_id, audio)                      => { self.write_audio_clip(clip_id, audio) },
+            ReadAudioClip(clip_id)                              => { self.read_audio_clip(clip_id) },
+            WriteLayerAudio(layer_id, clip_id)                  => { self.write_layer_audio(layer_id, clip_id) },
+            ReadLayerAudio(layer_id)                            => { self.read_layer_audio(layer_id) },
+            DeleteLayerAudio(layer_id)                          => { self.delete_layer_audio(layer_id) },
+            WriteAnnotation(annotation_id, layer_id, text)      => { self.write_annotation(annotation_id, layer_id, text) },
+            ReadAnnotation(annotation_id)                       => { self.read_annotation(annotation_id) },
+            ReadAnnotations                                     => { self.read_annotations() },
+            DeleteAnnotation(annotation_id)                     => { self.delete_annotation(annotation_id) },
+            PushUndoStep(history, stack, step)                  => { self.push_undo_step(history, stack, step) },
+            PopUndoStep(history, stack)                         => { self.pop_undo_step(history, stack) },
+            ClearUndoStack(history, stack)                      => { self.clear_undo_stack(history, stack) },
+            ReadUndoStackDepth(history)                         => { self.read_undo_stack_depth(history) }
         };
 
         self.check_error(result)
@@ -129,6 +166,16 @@ impl SqliteCore {
         }
     }
 
+    ///
+    /// Converts an undo stack identifier to the value used to represent it in the database
+    ///
+    fn stack_to_int(stack: UndoStack) -> i64 {
+        match stack {
+            UndoStack::Undo => 0,
+            UndoStack::Redo => 1
+        }
+    }
+
     ///
     /// Updates the animation properties for this animation
     ///
@@ -180,7 +227,8 @@ impl SqliteCore {
     fn read_highest_unused_element_id(&mut self) -> Result<Vec<StorageResponse>, rusqlite::Error> {
         use rusqlite::Error::QueryReturnedNoRows;
 
-        let mut read    = self.connection.prepare_cached("SELECT COALESCE(MAX(ElementId)+1, 0) FROM Elements;")?;
+        // Annotations, images and audio clips share their IDs with the elements
+        let mut read    = self.connection.prepare_cached("SELECT MAX(COALESCE((SELECT MAX(ElementId) FROM Elements), -1), COALESCE((SELECT MAX(AnnotationId) FROM Annotations), -1), COALESCE((SELECT MAX(ImageId) FROM Images), -1), COALESCE((SELECT MAX(ClipId) FROM AudioClips), -1)) + 1;")?;
         let count       = read.query_row(NO_PARAMS, |row| row.get::<_, i64>(0));
 
         match count {
@@ -190,6 +238,17 @@ impl SqliteCore {
         }
     }
 
+    ///
+    /// Reads the ID after the highest element ID in use within a range
+    ///
+    fn read_highest_unused_element_id_in_range(&mut self, range: Range<i64>) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        // Annotations, images and audio clips share their IDs with the elements
+        let mut read    = self.connection.prepare_cached("SELECT MAX(COALESCE((SELECT MAX(ElementId) FROM Elements WHERE ElementId >= ?1 AND ElementId < ?2), ?1-1), COALESCE((SELECT MAX(AnnotationId) FROM Annotations WHERE AnnotationId >= ?1 AND AnnotationId < ?2), ?1-1), COALESCE((SELECT MAX(ImageId) FROM Images WHERE ImageId >= ?1 AND ImageId < ?2), ?1-1), COALESCE((SELECT MAX(ClipId) FROM AudioClips WHERE ClipId >= ?1 AND ClipId < ?2), ?1-1)) + 1;")?;
+        let next_id     = read.query_row(params![range.start, range.end], |row| row.get::<_, i64>(0))?;
+
+        Ok(vec![StorageResponse::HighestUnusedElementId(next_id)])
+    }
+
     ///
     /// Updates the animation properties for this animation
     ///
@@ -269,6 +328,12 @@ impl SqliteCore {
             let mut delete  = transaction.prepare_cached("DELETE FROM LayerCache WHERE LayerId = ?;")?;
             delete.execute(&[layer_id as i64])?;
 
+            let mut delete  = transaction.prepare_cached("DELETE FROM LayerAudio WHERE LayerId = ?;")?;
+            delete.execute(&[layer_id as i64])?;
+
+            let mut delete  = transaction.prepare_cached("DELETE FROM Annotations WHERE LayerId = ?;")?;
+            delete.execute(&[layer_id as i64])?;
+
             let mut delete  = transaction.prepare_cached("DELETE FROM Layers WHERE LayerId = ?;")?;
             delete.execute(&[layer_id as i64])?;
         }
@@ -338,6 +403,45 @@ impl SqliteCore {
         Ok(vec![StorageResponse::Updated])
     }
 
+    ///
+    /// Moves a keyframe to a new time, along with the elements that are attached to it
+    ///
+    fn move_key_frame(&mut self, layer_id: u64, from: Duration, to: Duration) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let from_microseconds   = Self::time_to_int(from);
+        let to_microseconds     = Self::time_to_int(to);
+
+        // The keyframe must exist and must not be moved on top of another keyframe
+        if self.read_previous_key_frame(layer_id, from_microseconds)? != Some(from_microseconds) {
+            return Ok(vec![StorageResponse::NotFound]);
+        }
+
+        if from_microseconds == to_microseconds {
+            return Ok(vec![StorageResponse::Updated]);
+        }
+
+        if self.read_previous_key_frame(layer_id, to_microseconds)? == Some(to_microseconds) {
+            return Ok(vec![StorageResponse::NotReplacingExisting]);
+        }
+
+        // The elements themselves don't need to change: only the keyframe and the attachments to it
+        let transaction = self.connection.transaction()?;
+
+        {
+            let mut update  = transaction.prepare_cached("UPDATE Keyframe SET TimeMicroseconds = ? WHERE LayerId = ? AND TimeMicroseconds = ?;")?;
+            update.execute(&[to_microseconds, layer_id as i64, from_microseconds])?;
+
+            let mut update  = transaction.prepare_cached("UPDATE ElementKeyframeAttachment SET TimeMicroseconds = ? WHERE LayerId = ? AND TimeMicroseconds = ?;")?;
+            update.execute(&[to_microseconds, layer_id as i64, from_microseconds])?;
+
+            let mut delete  = transaction.prepare_cached("DELETE FROM LayerCache WHERE LayerId = ? AND TimeMicroseconds = ?;")?;
+            delete.execute(&[layer_id as i64, from_microseconds])?;
+        }
+
+        transaction.commit()?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
     ///
     /// Reads where the keyframe preceding or at the specified time is located
     ///
@@ -543,4 +647,203 @@ impl SqliteCore {
             Err(other)                  => Err(other)
         }
     }
+
+    ///
+    /// Stores a reference image
+    ///
+    fn write_image(&mut self, image_id: i64, image: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO Images (ImageId, Image) VALUES (?, ?);")?;
+        write.execute(params![image_id, image])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Reads a stored reference image
+    ///
+    fn read_image(&mut self, image_id: i64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        use rusqlite::Error::QueryReturnedNoRows;
+
+        let mut read    = self.connection.prepare_cached("SELECT Image FROM Images WHERE ImageId = ?;")?;
+        let result      = read.query_row(&[image_id], |row| row.get(0));
+
+        match result {
+            Ok(image)                   => Ok(vec![StorageResponse::Image(image_id, image)]),
+            Err(QueryReturnedNoRows)    => Ok(vec![StorageResponse::NotFound]),
+            Err(other)                  => Err(other)
+        }
+    }
+
+    ///
+    /// Stores an audio clip
+    ///
+    fn write_audio_clip(&mut self, clip_id: i64, audio: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO AudioClips (ClipId, Audio) VALUES (?, ?);")?;
+        write.execute(params![clip_id, audio])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Reads a stored audio clip
+    ///
+    fn read_audio_clip(&mut self, clip_id: i64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        use rusqlite::Error::QueryReturnedNoRows;
+
+        let mut read    = self.connection.prepare_cached("SELECT Audio FROM AudioClips WHERE ClipId = ?;")?;
+        let result      = read.query_row(&[clip_id], |row| row.get(0));
+
+        match result {
+            Ok(audio)                   => Ok(vec![StorageResponse::AudioClip(clip_id, audio)]),
+            Err(QueryReturnedNoRows)    => Ok(vec![StorageResponse::NotFound]),
+            Err(other)                  => Err(other)
+        }
+    }
+
+    ///
+    /// Attaches a stored audio clip to a layer
+    ///
+    fn write_layer_audio(&mut self, layer_id: u64, clip_id: i64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        // The clip must have been stored before it can be attached
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO LayerAudio (LayerId, ClipId) SELECT ?, ClipId FROM AudioClips WHERE ClipId = ?;")?;
+        let num_written = write.execute(params![layer_id as i64, clip_id])?;
+
+        if num_written > 0 {
+            Ok(vec![StorageResponse::Updated])
+        } else {
+            Ok(vec![StorageResponse::NotFound])
+        }
+    }
+
+    ///
+    /// Reads the ID of the audio clip attached to a layer
+    ///
+    fn read_layer_audio(&mut self, layer_id: u64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        use rusqlite::Error::QueryReturnedNoRows;
+
+        let mut read    = self.connection.prepare_cached("SELECT ClipId FROM LayerAudio WHERE LayerId = ?;")?;
+        let result      = read.query_row(&[layer_id as i64], |row| row.get(0));
+
+        match result {
+            Ok(clip_id)                 => Ok(vec![StorageResponse::LayerAudio(layer_id, clip_id)]),
+            Err(QueryReturnedNoRows)    => Ok(vec![StorageResponse::NotFound]),
+            Err(other)                  => Err(other)
+        }
+    }
+
+    ///
+    /// Removes the audio clip attached to a layer
+    ///
+    fn delete_layer_audio(&mut self, layer_id: u64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut delete  = self.connection.prepare_cached("DELETE FROM LayerAudio WHERE LayerId = ?;")?;
+        delete.execute(&[layer_id as i64])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Writes a review annotation
+    ///
+    fn write_annotation(&mut self, annotation_id: i64, layer_id: u64, annotation: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO Annotations (AnnotationId, LayerId, Annotation) VALUES (?, ?, ?);")?;
+        write.execute(params![annotation_id, layer_id as i64, annotation])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Reads a single review annotation
+    ///
+    fn read_annotation(&mut self, annotation_id: i64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        use rusqlite::Error::QueryReturnedNoRows;
+
+        let mut read    = self.connection.prepare_cached("SELECT LayerId, Annotation FROM Annotations WHERE AnnotationId = ?;")?;
+        let result      = read.query_row(&[annotation_id], |row| Ok(StorageResponse::Annotation(annotation_id, row.get::<_, i64>(0)? as u64, row.get(1)?)));
+
+        match result {
+            Ok(annotation)              => Ok(vec![annotation]),
+            Err(QueryReturnedNoRows)    => Ok(vec![StorageResponse::NotFound]),
+            Err(other)                  => Err(other)
+        }
+    }
+
+    ///
+    /// Reads all of the review annotations
+    ///
+    fn read_annotations(&mut self) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT AnnotationId, LayerId, Annotation FROM Annotations ORDER BY AnnotationId ASC;")?;
+        let annotations = read.query_map(NO_PARAMS, |row| Ok(StorageResponse::Annotation(row.get(0)?, row.get::<_, i64>(1)? as u64, row.get(2)?)))?;
+
+        Ok(annotations.collect::<Result<_, _>>()?)
+    }
+
+    ///
+    /// Removes a review annotation
+    ///
+    fn delete_annotation(&mut self, annotation_id: i64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut delete  = self.connection.prepare_cached("DELETE FROM Annotations WHERE AnnotationId = ?;")?;
+        delete.execute(&[annotation_id])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Pushes a step on to the top of one of the undo stacks in an undo history
+    ///
+    fn push_undo_step(&mut self, history: u64, stack: UndoStack, step: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT INTO UndoStep (History, Stack, Step) VALUES (?, ?, ?);")?;
+        write.execute(params![history as i64, Self::stack_to_int(stack), step])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Removes the topmost step from one of the undo stacks in an undo history and returns it
+    ///
+    fn pop_undo_step(&mut self, history: u64, stack: UndoStack) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        use rusqlite::Error::QueryReturnedNoRows;
+
+        let transaction = self.connection.transaction()?;
+
+        let result = {
+            let mut read    = transaction.prepare_cached("SELECT StepId, Step FROM UndoStep WHERE History = ? AND Stack = ? ORDER BY StepId DESC LIMIT 1;")?;
+            let step        = read.query_row(&[history as i64, Self::stack_to_int(stack)], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)));
+
+            match step {
+                Ok((step_id, step))         => {
+                    let mut delete  = transaction.prepare_cached("DELETE FROM UndoStep WHERE StepId = ?;")?;
+                    delete.execute(&[step_id])?;
+
+                    vec![StorageResponse::UndoStep(step)]
+                }
+                Err(QueryReturnedNoRows)    => vec![StorageResponse::NotFound],
+                Err(other)                  => { return Err(other); }
+            }
+        };
+
+        transaction.commit()?;
+
+        Ok(result)
+    }
+
+    ///
+    /// Removes all of the steps from one of the undo stacks in an undo history
+    ///
+    fn clear_undo_stack(&mut self, history: u64, stack: UndoStack) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut delete  = self.connection.prepare_cached("DELETE FROM UndoStep WHERE History = ? AND Stack = ?;")?;
+        delete.execute(&[history as i64, Self::stack_to_int(stack)])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Reads the number of steps in the undo and redo stacks for an undo history
+    ///
+    fn read_undo_stack_depth(&mut self, history: u64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT COUNT(*) FROM UndoStep WHERE History = ? AND Stack = ?;")?;
+        let undo_depth  = read.query_row(&[history as i64, Self::stack_to_int(UndoStack::Undo)], |row| row.get::<_, i64>(0))?;
+        let redo_depth  = read.query_row(&[history as i64, Self::stack_to_int(UndoStack::Redo)], |row| row.get::<_, i64>(0))?;
+
+        Ok(vec![StorageResponse::UndoStackDepth(undo_depth as usize, redo_depth as usize)])
+    }
 }
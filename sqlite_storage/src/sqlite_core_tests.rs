@@ -350,6 +350,65 @@ fn delete_nonexistent_keyframe() {
         ]);
 }
 
+#[test]
+fn move_keyframe() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()), 
+
+            StorageCommand::AddKeyFrame(1, Duration::from_millis(420)),
+            StorageCommand::AddKeyFrame(1, Duration::from_millis(500)),
+
+            StorageCommand::WriteElement(1, "Test1".to_string()),
+            StorageCommand::AttachElementToLayer(1, 1, Duration::from_millis(420)),
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![
+            StorageCommand::MoveKeyFrame(1, Duration::from_millis(420), Duration::from_millis(600))
+        ]) == vec![StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadKeyFrames(1, Duration::from_millis(0)..Duration::from_millis(700))]) ==
+        vec![
+            StorageResponse::KeyFrame(Duration::from_millis(500), Duration::from_millis(600)),
+            StorageResponse::KeyFrame(Duration::from_millis(600), Duration::from_micros(i64::MAX as u64))
+        ]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadElementsForKeyFrame(1, Duration::from_millis(600))]) ==
+        vec![
+            StorageResponse::Element(1, "Test1".to_string())
+        ]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadElementAttachments(1)]) ==
+        vec![
+            StorageResponse::ElementAttachments(1, vec![(1, Duration::from_millis(600))]),
+        ]);
+}
+
+#[test]
+fn move_keyframe_onto_existing_keyframe() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()), 
+            StorageCommand::AddKeyFrame(1, Duration::from_millis(420)),
+            StorageCommand::AddKeyFrame(1, Duration::from_millis(500))
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![
+            StorageCommand::MoveKeyFrame(1, Duration::from_millis(420), Duration::from_millis(500)),
+            StorageCommand::MoveKeyFrame(1, Duration::from_millis(450), Duration::from_millis(600))
+        ]) == vec![StorageResponse::NotReplacingExisting, StorageResponse::NotFound]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadKeyFrames(1, Duration::from_millis(0)..Duration::from_millis(700))]) ==
+        vec![
+            StorageResponse::KeyFrame(Duration::from_millis(420), Duration::from_millis(500)),
+            StorageResponse::KeyFrame(Duration::from_millis(500), Duration::from_micros(i64::MAX as u64))
+        ]);
+}
+
 #[test]
 fn attach_element_to_keyframe() {
     let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
@@ -586,3 +645,141 @@ fn delete_from_layer_cache() {
     assert!(core.run_commands(vec![StorageCommand::ReadLayerCache(1, Duration::from_millis(500), "Type".to_string())]) ==
         vec![StorageResponse::LayerCache("Cache2".to_string())]);
 }
+
+#[test]
+fn write_and_read_layer_audio() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerAudio(1)]) == vec![StorageResponse::NotFound]);
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()),
+            StorageCommand::WriteAudioClip(42, "Audio1".to_string()),
+            StorageCommand::WriteLayerAudio(1, 42)
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerAudio(1)]) == vec![StorageResponse::LayerAudio(1, 42)]);
+    assert!(core.run_commands(vec![StorageCommand::ReadAudioClip(42)]) == vec![StorageResponse::AudioClip(42, "Audio1".to_string())]);
+
+    assert!(core.run_commands(vec![StorageCommand::DeleteLayerAudio(1)]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerAudio(1)]) == vec![StorageResponse::NotFound]);
+}
+
+#[test]
+fn cannot_attach_missing_audio_clip() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()),
+            StorageCommand::WriteLayerAudio(1, 42)
+        ]) == vec![StorageResponse::Updated, StorageResponse::NotFound]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerAudio(1)]) == vec![StorageResponse::NotFound]);
+}
+
+#[test]
+fn audio_clips_share_ids_with_elements() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::WriteAudioClip(42, "Audio1".to_string())]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedElementId]) == vec![StorageResponse::HighestUnusedElementId(43)]);
+}
+
+#[test]
+fn deleting_layer_deletes_audio() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()),
+            StorageCommand::WriteAudioClip(42, "Audio1".to_string()),
+            StorageCommand::WriteLayerAudio(1, 42),
+            StorageCommand::DeleteLayer(1)
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerAudio(1)]) == vec![StorageResponse::NotFound]);
+}
+
+#[test]
+fn write_and_read_image() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::ReadImage(42)]) == vec![StorageResponse::NotFound]);
+    assert!(core.run_commands(vec![StorageCommand::WriteImage(42, "Image1".to_string())]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadImage(42)]) == vec![StorageResponse::Image(42, "Image1".to_string())]);
+}
+
+#[test]
+fn images_share_ids_with_elements() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::WriteImage(42, "Image1".to_string())]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedElementId]) == vec![StorageResponse::HighestUnusedElementId(43)]);
+}
+
+#[test]
+fn write_and_read_annotations() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::ReadAnnotations]) == vec![]);
+    assert!(core.run_commands(vec![StorageCommand::ReadAnnotation(10)]) == vec![StorageResponse::NotFound]);
+
+    assert!(core.run_commands(vec![
+            StorageCommand::WriteAnnotation(11, 2, "Note2".to_string()),
+            StorageCommand::WriteAnnotation(10, 1, "Note1".to_string())
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadAnnotation(10)]) == vec![StorageResponse::Annotation(10, 1, "Note1".to_string())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadAnnotations]) == vec![
+        StorageResponse::Annotation(10, 1, "Note1".to_string()),
+        StorageResponse::Annotation(11, 2, "Note2".to_string())
+    ]);
+
+    assert!(core.run_commands(vec![StorageCommand::DeleteAnnotation(10)]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadAnnotations]) == vec![StorageResponse::Annotation(11, 2, "Note2".to_string())]);
+}
+
+#[test]
+fn deleting_layer_deletes_annotations() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()),
+            StorageCommand::WriteAnnotation(10, 1, "Note1".to_string()),
+            StorageCommand::DeleteLayer(1)
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadAnnotation(10)]) == vec![StorageResponse::NotFound]);
+}
+
+#[test]
+fn annotation_ids_are_not_reused_for_elements() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedElementId]) == vec![StorageResponse::HighestUnusedElementId(0)]);
+
+    core.run_commands(vec![StorageCommand::WriteElement(5, "Element".to_string()), StorageCommand::WriteAnnotation(10, 1, "Note".to_string())]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedElementId]) == vec![StorageResponse::HighestUnusedElementId(11)]);
+}
+
+#[test]
+fn read_highest_unused_element_id_in_range() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedElementIdInRange(100..200)]) == vec![StorageResponse::HighestUnusedElementId(100)]);
+
+    core.run_commands(vec![StorageCommand::WriteElement(120, "Element".to_string()), StorageCommand::WriteAnnotation(130, 1, "Note".to_string()), StorageCommand::WriteElement(250, "Element".to_string())]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedElementIdInRange(100..200)]) == vec![StorageResponse::HighestUnusedElementId(131)]);
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedElementIdInRange(200..300)]) == vec![StorageResponse::HighestUnusedElementId(251)]);
+}
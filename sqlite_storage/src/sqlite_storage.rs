@@ -45,6 +45,9 @@ impl SqliteAnimationStorage {
         let core    = SqliteCore::new(connection);
         let core    = Arc::new(Desync::new(core));
 
+        // Add any tables that might be missing from older files
+        core.desync(|core| { core.upgrade().ok(); });
+
         // Create the storage object
         SqliteAnimationStorage {
             core:   core
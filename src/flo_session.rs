@@ -4,6 +4,7 @@ use flo_ui::*;
 use flo_ui::Image;
 use flo_ui_files::ui::*;
 use flo_binding::*;
+use flo_animation::{FileAnimation, EditableAnimation};
 use flo_sqlite_storage::*;
 
 use flo::style::*;
@@ -20,6 +21,21 @@ enum SubController {
     Editor
 }
 
+///
+/// How the files opened in a session can be changed
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SessionMode {
+    /// Files can be edited
+    Editor,
+
+    /// Files can be played back but not changed
+    Viewer,
+
+    /// Files can be played back but not changed, except that new review notes can be left on them
+    Reviewer
+}
+
 ///
 /// The main flowbetween session object
 ///
@@ -34,6 +50,34 @@ impl FlowBetweenSession {
     /// Creates a new FlowBetween session
     ///
     pub fn new() -> FlowBetweenSession {
+        Self::with_file_chooser(FloChooser::new(Arc::new(sqlite_animation_loader())))
+    }
+
+    ///
+    /// Creates a new FlowBetween session for a HTTP user
+    ///
+    /// Users who have logged in can only see their own files. Viewer and reviewer sessions can't edit the files they open.
+    ///
+    #[cfg(feature="http")]
+    pub fn for_http_user(user: Option<&str>, mode: SessionMode) -> FlowBetweenSession {
+        let loader          = Arc::new(sqlite_animation_loader());
+        let file_chooser    = match user {
+            Some(user)  => FloChooser::with_user_folder(loader, user),
+            None        => FloChooser::new(loader)
+        };
+
+        match mode {
+            SessionMode::Editor     => Self::with_file_chooser(file_chooser),
+            SessionMode::Viewer     => Self::with_file_chooser(file_chooser.into_read_only()),
+            SessionMode::Reviewer   => Self::with_file_chooser(file_chooser.into_reviewer())
+        }
+    }
+
+    ///
+    /// Creates a new FlowBetween session using the specified file chooser
+    ///
+    fn with_file_chooser<Loader: 'static+FileAnimation>(file_chooser: FloChooser<Loader>) -> FlowBetweenSession
+    where Loader::NewAnimation: 'static+EditableAnimation {
         let images = Arc::new(ResourceManager::new());
 
         // Some images for the root controller
@@ -41,7 +85,6 @@ impl FlowBetweenSession {
         images.assign_name(&flo, "flo");
 
         // Create the file chooser
-        let file_chooser = FloChooser::new(Arc::new(sqlite_animation_loader()));
         let file_chooser = FileChooserController::new(file_chooser, FloLogoController::new());
 
         file_chooser.set_background(FILE_CHOOSER_BACKGROUND);
@@ -2,6 +2,8 @@ use super::flo_session::*;
 
 use flo_http_ui::*;
 
+use std::collections::HashMap;
+
 impl HttpController for FlowBetweenSession {
     /// Creates a new session
     fn start_new() -> Self {
@@ -9,4 +11,16 @@ impl HttpController for FlowBetweenSession {
 
         session
     }
+
+    /// Creates a new session for a user (adding `?mode=viewer` to the URL opens files in viewer mode, and `?mode=reviewer` opens
+    /// them in a viewer mode where review notes can be left)
+    fn start_with_parameters(user: Option<&str>, parameters: &HashMap<String, String>) -> Self {
+        let mode = match parameters.get("mode").map(|mode| mode.as_str()) {
+            Some("viewer")      => SessionMode::Viewer,
+            Some("reviewer")    => SessionMode::Reviewer,
+            _                   => SessionMode::Editor
+        };
+
+        FlowBetweenSession::for_http_user(user, mode)
+    }
 }
@@ -31,6 +31,8 @@ mod flo_session;
 
 use std::sync::*;
 use std::thread;
+#[cfg(feature="http")]  use std::env;
+#[cfg(feature="http")]  use std::path::Path;
 use std::thread::JoinHandle;
 
 use log::*;
@@ -50,6 +52,27 @@ use self::flo_session::*;
 #[cfg(feature="http")]  const PACKAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(feature="http")]  const SERVER_PORT: u32      = 3000;
 #[cfg(feature="http")]  const BIND_ADDRESS: &str    = "0.0.0.0";
+#[cfg(feature="http")]  const LOCAL_ADDRESS: &str   = "127.0.0.1";
+#[cfg(feature="http")]  const LOGIN_PATH: &str      = "/flowbetween/login";
+
+///
+/// Reads the authentication settings for the HTTP server from the environment
+///
+/// `FLO_PASSWORD_FILE` specifies a file of `user:password` lines, and `FLO_ACCESS_TOKEN` specifies a token that logs in
+/// as the default user. Authentication is turned off if neither is set. An error is returned if the settings are invalid.
+///
+#[cfg(feature="http")]
+fn http_authentication() -> Result<Option<Arc<flo_actix::Authentication>>, String> {
+    if let Some(password_file) = env::var_os("FLO_PASSWORD_FILE") {
+        let password_file = flo_actix::PasswordFile::open(Path::new(&password_file)).map_err(|err| format!("Could not read the password file: {}", err))?;
+        Ok(Some(Arc::new(flo_actix::Authentication::new(password_file))))
+    } else if let Ok(access_token) = env::var("FLO_ACCESS_TOKEN") {
+        let access_token = flo_actix::AccessToken::new("default", &access_token).map_err(|err| format!("FLO_ACCESS_TOKEN is not valid: {}", err))?;
+        Ok(Some(Arc::new(flo_actix::Authentication::new(access_token))))
+    } else {
+        Ok(None)
+    }
+}
 
 #[cfg(feature="http")]
 fn main_actix() -> Option<JoinHandle<()>> {
@@ -60,17 +83,41 @@ fn main_actix() -> Option<JoinHandle<()>> {
             // Create the web session structure
             let sessions: Arc<WebSessions<FlowBetweenSession>> = Arc::new(WebSessions::new());
 
+            // Only accept connections from other machines if users have to log in
+            let authentication  = match http_authentication() {
+                Ok(authentication)  => authentication,
+                Err(err)            => {
+                    log.log((Level::Error, format!("Not starting the HTTP server: {}", err)));
+                    return;
+                }
+            };
+            let bind_address    = if authentication.is_some() { BIND_ADDRESS } else { LOCAL_ADDRESS };
+
+            if authentication.is_none() {
+                log.log("Authentication is not configured (set FLO_PASSWORD_FILE or FLO_ACCESS_TOKEN): only accepting local connections");
+            }
+
             // Log that we're getting ready
-            log.log(format!("{} v{} preparing to serve requests at {}", PACKAGE_NAME, PACKAGE_VERSION, &format!("{}:{}", BIND_ADDRESS, SERVER_PORT)));
+            log.log(format!("{} v{} preparing to serve requests at {}", PACKAGE_NAME, PACKAGE_VERSION, &format!("{}:{}", bind_address, SERVER_PORT)));
 
             // Start the actix server
             aw::HttpServer::new(move || {
                     // Something in Actix's type system involving the private Factory type is unhappy with using the static file handler function directlyh
                     // (Error is unhelpful but I think it's to do with if the function can be cloned or not)
-                    let static_file_handler = Arc::new(flo_actix::flowbetween_static_file_handler());
-
-                    aw::App::new()
-                        .app_data(sessions.clone())
+                    let static_file_handler = Arc::new(flo_actix::login_required(LOGIN_PATH, flo_actix::flowbetween_static_file_handler()));
+
+                    let app = aw::App::new()
+                        .app_data(sessions.clone());
+                    let app = match &authentication {
+                        Some(authentication)    => app.app_data(authentication.clone()),
+                        None                    => app
+                    };
+
+                    app
+                        .service(web::resource(LOGIN_PATH)
+                            .route(web::get().to(flo_actix::login_get_handler))
+                            .route(web::post().to(flo_actix::login_post_handler)))
+                        .service(web::resource("/flowbetween/logout").route(web::post().to(flo_actix::logout_handler)))
                         .service(web::resource("/flowbetween/session")
                             .route(web::get().to(flo_actix::session_get_handler::<WebSessions<FlowBetweenSession>>))
                             .route(web::post().to(flo_actix::session_post_handler::<WebSessions<FlowBetweenSession>>)))
@@ -81,7 +128,7 @@ fn main_actix() -> Option<JoinHandle<()>> {
                         .service(web::resource("/ws/{tail:.*}").route(web::to(flo_actix::session_websocket_handler::<WebSessions<FlowBetweenSession>>)))
                         .service(web::resource("/{tail:.*}").route(web::to(move |r| static_file_handler(r))))
                 })
-                .bind(&format!("{}:{}", BIND_ADDRESS, SERVER_PORT))
+                .bind(&format!("{}:{}", bind_address, SERVER_PORT))
                 .expect("Failed to bind HTTP server to port")
                 .run()
                 .await
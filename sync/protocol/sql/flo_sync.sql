@@ -0,0 +1,32 @@
+/***
+ **
+ ** FlowBetween edit log synchronisation
+ **
+ **   The sync server stores the authoritative edit log for each animation that its clients are editing
+ **
+ ***/
+
+/**
+ * The clients that have connected to the server (client IDs are never reused)
+ *
+ * A client has to supply the secret it was issued along with its ID when it reconnects
+ */
+CREATE TABLE IF NOT EXISTS SyncClients (
+    ClientId INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    Secret TEXT NOT NULL
+);
+
+/**
+ * The edits that have been committed for each animation, in the order that they should be applied
+ */
+CREATE TABLE IF NOT EXISTS SyncEdits (
+    Animation TEXT NOT NULL,
+    Seq INTEGER NOT NULL,
+    ClientId INTEGER NOT NULL,
+    ClientSeq INTEGER NOT NULL,
+    Edit TEXT NOT NULL,
+
+    PRIMARY KEY (Animation, Seq)
+) WITHOUT ROWID;
+
+CREATE INDEX IF NOT EXISTS SyncEdits_Client ON SyncEdits (Animation, ClientId, ClientSeq);
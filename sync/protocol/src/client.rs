@@ -0,0 +1,435 @@
+use super::message::*;
+use super::keep_alive::*;
+
+use flo_stream::*;
+use flo_animation::*;
+
+use ::desync::*;
+use futures::prelude::*;
+use futures::future;
+use futures::stream;
+use futures::channel::mpsc;
+
+use std::iter;
+use std::sync::*;
+use std::ops::{Range};
+use std::convert::{TryFrom};
+
+/// The number of element IDs reserved for each client (IDs below this are assigned by animations that are not being synchronised)
+const ELEMENT_IDS_PER_CLIENT: i64 = 1<<32;
+
+/// The number of undo histories reserved for each client (histories below this are used by the local editor)
+const UNDO_HISTORIES_PER_CLIENT: u64 = 1<<32;
+
+///
+/// Returns the range of element IDs that the client with the specified ID can assign
+///
+/// Clients never assign IDs from each other's ranges, so elements created at the same time by different clients can't collide.
+/// This is `None` for client IDs that are too large to be given a range (the server never assigns these).
+///
+pub fn client_element_id_range(client_id: u64) -> Option<Range<i64>> {
+    let start   = i64::try_from(client_id).ok()?
+        .checked_add(1)?
+        .checked_mul(ELEMENT_IDS_PER_CLIENT)?;
+    let end     = start.checked_add(ELEMENT_IDS_PER_CLIENT)?;
+
+    Some(start..end)
+}
+
+///
+/// Returns the local undo history that is used for edits made in an undo history belonging to another client
+///
+/// This is `None` if the client ID or the undo history is too large to be represented.
+///
+pub fn client_undo_history(client_id: u64, undo_history: u64) -> Option<u64> {
+    if undo_history >= UNDO_HISTORIES_PER_CLIENT {
+        return None;
+    }
+
+    client_id.checked_add(1)?
+        .checked_mul(UNDO_HISTORIES_PER_CLIENT)?
+        .checked_add(undo_history)
+}
+
+///
+/// Splits the edits made by another client into the groups to perform on the local animation
+///
+/// Each group starts by selecting the local undo history for the undo history the other client performed it in, so
+/// undo and redo act on the same edits as they did on that client.
+///
+fn remote_undo_steps(client_id: u64, edits: Vec<AnimationEdit>) -> Vec<Vec<AnimationEdit>> {
+    // Start a new group wherever the other client selected an undo history
+    let mut groups: Vec<Vec<AnimationEdit>> = vec![];
+
+    for edit in edits {
+        match edit {
+            AnimationEdit::SelectUndoHistory(_) => groups.push(vec![edit]),
+            _                                   => match groups.last_mut() {
+                Some(group) => group.push(edit),
+                None        => groups.push(vec![edit])
+            }
+        }
+    }
+
+    // Edits made before any undo history was selected were made in undo history 0
+    groups.into_iter()
+        .map(|group| {
+            let mut group       = group.into_iter().peekable();
+            let undo_history    = match group.peek() {
+                Some(AnimationEdit::SelectUndoHistory(undo_history))    => { let undo_history = *undo_history; group.next(); undo_history }
+                _                                                       => 0
+            };
+
+            match client_undo_history(client_id, undo_history) {
+                Some(undo_history)  => iter::once(AnimationEdit::SelectUndoHistory(undo_history)).chain(group).collect(),
+
+                // There's no way to tell what an undo or a redo would act on without an undo history
+                None                => group.filter(|edit| match edit {
+                    AnimationEdit::Undo | AnimationEdit::Redo   => false,
+                    _                                           => true
+                }).collect()
+            }
+        })
+        .collect()
+}
+
+///
+/// Events processed by a sync client
+///
+enum ClientEvent {
+    /// A message was received from the server
+    FromServer(SyncMessage),
+
+    /// The local animation has been edited
+    LocalEdits,
+
+    /// The connection to the server has closed
+    Disconnected
+}
+
+///
+/// The synchronisation state of a client (this is kept between connections so the client can resync when it reconnects)
+///
+struct SyncClientState {
+    /// The ID assigned to this client by the server, and the secret needed to use it again
+    client_id: Option<(u64, String)>,
+
+    /// The number of edits from the server's log that have been applied to the local animation
+    server_offset: usize,
+
+    /// The number of edits in the local edit log that have either been sent to the server or were received from it
+    local_offset: usize,
+
+    /// The sequence number to assign to the next set of edits sent to the server
+    next_client_seq: u64,
+
+    /// Edits that have been sent to the server but which have not been committed yet
+    unacknowledged: Vec<(u64, Vec<AnimationEdit>)>
+}
+
+///
+/// A sync client keeps a local animation in sync with the edit log stored on a sync server
+///
+/// Edits made to the local animation are read from its edit log (so any element IDs have been assigned) and submitted to the
+/// server, and the edits committed by other clients are applied to the local animation as they arrive. Local edits are
+/// applied immediately, so edits made at the same time by different clients are applied in a different order on each
+/// client: edits that affect different elements or layers converge, but edits that conflict with each other may not.
+///
+/// Once the server has assigned an ID to the client, the animation only assigns element IDs from the range reserved for
+/// that client (see `client_element_id_range()`), so elements created by different clients never share an ID.
+///
+/// Any edits that are already in the local animation are sent to the server the first time the client connects. These
+/// use IDs from outside of any client's range, so only one client should bring existing content to a new animation.
+///
+/// Undo and redo are synchronised too: each undo history of another client is kept in a separate undo history here (see
+/// `client_undo_history()`), so an undo only ever reverses the edits made by the client that performed it. Groups of
+/// edits that start with `SelectUndoHistory` are undone in exactly the same steps on every client. Other edits are in undo
+/// history 0, but the client can't tell where one group of these ends and the next starts, so other clients undo the edits
+/// that were sent to the server together as a single step.
+///
+pub struct SyncClient<Anim: 'static+EditableAnimation> {
+    /// The animation being synchronised
+    animation: Arc<Anim>,
+
+    /// The name of the animation on the server
+    name: String,
+
+    /// The synchronisation state
+    state: Arc<Mutex<SyncClientState>>
+}
+
+impl<Anim: 'static+EditableAnimation> SyncClient<Anim> {
+    ///
+    /// Creates a new sync client for an animation, which will be stored with the specified name on the server
+    ///
+    pub fn new(animation: Arc<Anim>, name: &str) -> SyncClient<Anim> {
+        let state = SyncClientState {
+            client_id:          None,
+            server_offset:      0,
+            local_offset:       0,
+            next_client_seq:    0,
+            unacknowledged:     vec![]
+        };
+
+        SyncClient {
+            animation:  animation,
+            name:       name.to_string(),
+            state:      Arc::new(Mutex::new(state))
+        }
+    }
+
+    ///
+    /// The number of edits from the server's log that have been received by this client
+    ///
+    pub fn server_offset(&self) -> usize {
+        self.state.lock().unwrap().server_offset
+    }
+
+    ///
+    /// True if there are edits that have been sent to the server which have not been committed yet
+    ///
+    pub fn has_unacknowledged_edits(&self) -> bool {
+        self.state.lock().unwrap().unacknowledged.len() > 0
+    }
+
+    ///
+    /// Connects to a server, given the stream of messages from the server, returning the stream of messages to send to the server
+    ///
+    /// The returned stream finishes when the stream of messages from the server finishes. The client can be connected again
+    /// after this, and will resync with the server from where it left off.
+    ///
+    pub fn connect<Incoming>(&self, from_server: Incoming) -> impl Send+Unpin+Stream<Item=SyncMessage>
+    where Incoming: 'static+Send+Unpin+Stream<Item=SyncMessage> {
+        // Generate a notification whenever the local animation is edited
+        let (notify_edits, local_edits) = mpsc::unbounded();
+        let notify_edits                = Arc::new(Desync::new(notify_edits));
+
+        pipe_in(Arc::clone(&notify_edits), self.animation.edit().subscribe(), |notify_edits, _edits| {
+            notify_edits.unbounded_send(()).ok();
+            future::ready(()).boxed()
+        });
+
+        // Process events until the server disconnects
+        let from_server = from_server.map(|message| ClientEvent::FromServer(message))
+            .chain(stream::once(future::ready(ClientEvent::Disconnected)));
+        let local_edits = local_edits.map(|_| ClientEvent::LocalEdits);
+        let events      = stream::select(from_server, local_edits)
+            .take_while(|event| future::ready(match event {
+                ClientEvent::Disconnected   => false,
+                _                           => true
+            }));
+
+        // Start by saying hello to the server, then send messages as the events arrive
+        let hello       = {
+            let state = self.state.lock().unwrap();
+            SyncMessage::Hello(self.name.clone(), state.client_id.clone(), state.server_offset)
+        };
+
+        let animation   = Arc::clone(&self.animation);
+        let state       = Arc::clone(&self.state);
+        let to_server   = events
+            .then(move |event| Self::process_event(Arc::clone(&animation), Arc::clone(&state), event))
+            .flat_map(|messages| stream::iter(messages));
+        let to_server   = stream::once(future::ready(hello)).chain(to_server);
+
+        KeepAliveStream::new(to_server.boxed(), notify_edits)
+    }
+
+    ///
+    /// Processes an event, returning the messages to send to the server
+    ///
+    fn process_event(animation: Arc<Anim>, state: Arc<Mutex<SyncClientState>>, event: ClientEvent) -> impl Send+Future<Output=Vec<SyncMessage>> {
+        async move {
+            use self::ClientEvent::*;
+            use self::SyncMessage::*;
+
+            match event {
+                LocalEdits                                                  => Self::send_local_edits(&animation, &state).await,
+
+                FromServer(Welcome(client_id, secret, _length))             => {
+                    // New elements are assigned IDs from the range reserved for this client
+                    if let Some(element_id_range) = client_element_id_range(client_id) {
+                        animation.set_element_id_range(element_id_range);
+                    }
+
+                    // Resend anything that the server hadn't committed when we were last connected (the server ignores anything it has already committed)
+                    let mut messages = {
+                        let mut state   = state.lock().unwrap();
+                        state.client_id = Some((client_id, secret));
+
+                        state.unacknowledged.iter()
+                            .map(|(client_seq, edits)| Submit(*client_seq, edits.clone()))
+                            .collect::<Vec<_>>()
+                    };
+
+                    // Send any edits that were made while we were disconnected
+                    messages.extend(Self::send_local_edits(&animation, &state).await);
+                    messages
+                }
+
+                FromServer(Committed(seq, client_id, client_seq, edits))    => Self::apply_committed(&animation, &state, seq, client_id, client_seq, edits).await,
+
+                FromServer(Error(_))                                        |
+                FromServer(Hello(_, _, _))                                  |
+                FromServer(Submit(_, _))                                    |
+                Disconnected                                                => vec![]
+            }
+        }
+    }
+
+    ///
+    /// Assigns a sequence number to a set of local edits, returning the messages to send them to the server
+    ///
+    fn submit(state: &mut SyncClientState, edits: Vec<AnimationEdit>) -> Vec<SyncMessage> {
+        if edits.len() == 0 {
+            return vec![];
+        }
+
+        let client_seq = state.next_client_seq;
+        state.next_client_seq += 1;
+        state.unacknowledged.push((client_seq, edits.clone()));
+
+        vec![SyncMessage::Submit(client_seq, edits)]
+    }
+
+    ///
+    /// Reads the edits from the local edit log that haven't been sent to the server yet, returning the messages to send them
+    ///
+    fn send_local_edits<'a>(animation: &'a Arc<Anim>, state: &'a Mutex<SyncClientState>) -> impl 'a+Send+Future<Output=Vec<SyncMessage>> {
+        async move {
+            let local_offset    = state.lock().unwrap().local_offset;
+            let num_edits       = animation.get_num_edits();
+
+            if num_edits <= local_offset {
+                return vec![];
+            }
+
+            let edits           = animation.read_edit_log(local_offset..num_edits).collect::<Vec<_>>().await;
+
+            let mut state       = state.lock().unwrap();
+            state.local_offset  = local_offset + edits.len();
+
+            Self::submit(&mut *state, edits)
+        }
+    }
+
+    ///
+    /// Applies a set of edits committed by the server, returning any messages to send back to the server
+    ///
+    fn apply_committed<'a>(animation: &'a Arc<Anim>, state: &'a Mutex<SyncClientState>, seq: usize, client_id: u64, client_seq: u64, edits: Vec<AnimationEdit>) -> impl 'a+Send+Future<Output=Vec<SyncMessage>> {
+        async move {
+            let end_seq                     = seq + edits.len();
+            let (server_offset, our_id)     = {
+                let state = state.lock().unwrap();
+                (state.server_offset, state.client_id.as_ref().map(|(client_id, _)| *client_id))
+            };
+
+            // Ignore edits that we've already seen
+            if end_seq <= server_offset {
+                return vec![];
+            }
+
+            let edits = if seq < server_offset {
+                edits.into_iter().skip(server_offset - seq).collect()
+            } else {
+                edits
+            };
+
+            // Our own edits have already been applied locally: this just acknowledges them
+            if our_id == Some(client_id) {
+                let mut state       = state.lock().unwrap();
+                state.server_offset = end_seq;
+                state.unacknowledged.retain(|(unacknowledged_seq, _)| *unacknowledged_seq != client_seq);
+
+                return vec![];
+            }
+
+            // Send any local edits first, so that everything after the local offset in the local log is from the server
+            let mut messages    = Self::send_local_edits(animation, state).await;
+            let before          = state.lock().unwrap().local_offset;
+
+            // Apply the edits from the server (these were made by another client, so they're kept out of the local undo history)
+            let remote_steps    = remote_undo_steps(client_id, edits);
+            let edits           = remote_steps.iter().flatten().cloned().collect::<Vec<_>>();
+
+            for step in remote_steps {
+                animation.perform_edits_without_undo(step);
+            }
+            let after           = animation.get_num_edits();
+
+            // Anything else that was added to the log while the remote edits were being applied was edited locally
+            let local_edits     = if after > before + edits.len() {
+                let log_edits       = animation.read_edit_log(before..after).collect::<Vec<_>>().await;
+                let mut remote      = edits.iter().peekable();
+
+                log_edits.into_iter()
+                    .filter(|edit| {
+                        if remote.peek() == Some(&edit) {
+                            remote.next();
+                            false
+                        } else {
+                            true
+                        }
+                    })
+                    .collect()
+            } else {
+                vec![]
+            };
+
+            // Update the state
+            let mut state       = state.lock().unwrap();
+            state.server_offset = end_seq;
+            state.local_offset  = usize::max(after, before);
+
+            messages.extend(Self::submit(&mut *state, local_edits));
+            messages
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clients_have_separate_element_id_ranges() {
+        let first   = client_element_id_range(0).unwrap();
+        let second  = client_element_id_range(1).unwrap();
+
+        assert!(first.start >= ELEMENT_IDS_PER_CLIENT);
+        assert!(first.end <= second.start);
+    }
+
+    #[test]
+    fn large_client_ids_have_no_element_id_range() {
+        assert!(client_element_id_range(u64::max_value()).is_none());
+        assert!(client_element_id_range(i64::max_value() as u64).is_none());
+        assert!(client_element_id_range((1<<31) - 2).is_none());
+        assert!(client_element_id_range((1<<31) - 3).is_some());
+    }
+
+    #[test]
+    fn clients_have_separate_undo_histories() {
+        assert!(client_undo_history(0, 0).unwrap() >= UNDO_HISTORIES_PER_CLIENT);
+        assert!(client_undo_history(0, UNDO_HISTORIES_PER_CLIENT-1).unwrap() < client_undo_history(1, 0).unwrap());
+        assert!(client_undo_history(0, UNDO_HISTORIES_PER_CLIENT).is_none());
+        assert!(client_undo_history(u64::max_value(), 0).is_none());
+    }
+
+    #[test]
+    fn remote_edits_are_moved_into_the_undo_histories_for_their_client() {
+        let history_0   = client_undo_history(1, 0).unwrap();
+        let history_2   = client_undo_history(1, 2).unwrap();
+        let steps       = remote_undo_steps(1, vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::SelectUndoHistory(2),
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Undo
+        ]);
+
+        assert!(steps == vec![
+            vec![AnimationEdit::SelectUndoHistory(history_0), AnimationEdit::AddNewLayer(1)],
+            vec![AnimationEdit::SelectUndoHistory(history_2), AnimationEdit::AddNewLayer(2), AnimationEdit::Undo]
+        ]);
+    }
+}
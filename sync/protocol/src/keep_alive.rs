@@ -0,0 +1,37 @@
+use futures::prelude::*;
+use futures::task::{Context, Poll};
+
+use std::pin::*;
+
+///
+/// Stream that keeps a resource alive for as long as the stream exists
+///
+/// (Used for the resources that are piped into the stream, as these only hold a weak reference to their target)
+///
+pub (crate) struct KeepAliveStream<TStream, TResource> {
+    /// The stream that is being read from
+    stream: TStream,
+
+    /// The resource to keep alive
+    _resource: TResource
+}
+
+impl<TStream, TResource> KeepAliveStream<TStream, TResource> {
+    ///
+    /// Creates a new stream that keeps a resource alive
+    ///
+    pub fn new(stream: TStream, resource: TResource) -> KeepAliveStream<TStream, TResource> {
+        KeepAliveStream {
+            stream:     stream,
+            _resource:  resource
+        }
+    }
+}
+
+impl<TStream: Stream+Unpin, TResource: Unpin> Stream for KeepAliveStream<TStream, TResource> {
+    type Item = TStream::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(context)
+    }
+}
@@ -0,0 +1,22 @@
+//!
+//! # flo_sync
+//!
+//! Synchronises the edit log of a FlowBetween animation between several instances of FlowBetween using a sync server.
+//!
+//! Clients and servers exchange `SyncMessage`s, which are sent one per line using the same serialized form as the
+//! animation edit log. The server stores the authoritative edit log for each animation in SQLite and assigns a
+//! sequence number (an offset into this log) to every set of edits it commits. Clients remember the offset they
+//! have reached, so they can resync from where they left off when they reconnect.
+//!
+
+mod message;
+mod keep_alive;
+mod sqlite_sync_log;
+mod server;
+mod client;
+mod tcp_client;
+
+pub use self::message::*;
+pub use self::sqlite_sync_log::*;
+pub use self::server::*;
+pub use self::client::*;
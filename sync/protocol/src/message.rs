@@ -0,0 +1,193 @@
+use flo_animation::*;
+use flo_animation::serializer::*;
+
+///
+/// The messages that are exchanged between a sync client and a sync server
+///
+/// Messages are sent one per line, using the same serialized form as the animation edit log (which never contains
+/// a newline character).
+///
+/// Sequence numbers in `Hello` and `Committed` messages are offsets into the server's edit log for the animation, and
+/// count individual edits rather than messages, so they can be used in the same way as `read_edit_log()` offsets.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum SyncMessage {
+    /// Client to server: start editing the named animation, with the client ID and secret assigned by a previous connection (if there is one) and the offset to resync the log from
+    Hello(String, Option<(u64, String)>, usize),
+
+    /// Server to client: the client ID assigned to this client, the secret it must supply to reuse the ID and the current length of the log
+    Welcome(u64, String, usize),
+
+    /// Client to server: a set of edits made by the client, with the client's sequence number for them
+    Submit(u64, Vec<AnimationEdit>),
+
+    /// Server to client: edits committed at an offset in the log, along with the client ID and client sequence number they were submitted with
+    Committed(usize, u64, u64, Vec<AnimationEdit>),
+
+    /// Server to client: a request could not be processed
+    Error(String)
+}
+
+///
+/// Writes out a list of edits
+///
+fn write_edits<Tgt: AnimationDataTarget>(edits: &Vec<AnimationEdit>, data: &mut Tgt) {
+    data.write_usize(edits.len());
+    edits.iter().for_each(|edit| edit.serialize(data));
+}
+
+///
+/// Reads a list of edits
+///
+fn next_edits<Src: AnimationDataSource>(data: &mut Src) -> Option<Vec<AnimationEdit>> {
+    let num_edits = data.next_usize();
+
+    (0..num_edits).into_iter()
+        .map(|_| AnimationEdit::deserialize(data))
+        .collect()
+}
+
+impl SyncMessage {
+    ///
+    /// Generates a serialized version of this message on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::SyncMessage::*;
+
+        match self {
+            Hello(name, client_id, since)                       => {
+                data.write_chr('H');
+                data.write_str(name);
+                match client_id {
+                    Some((client_id, secret))   => { data.write_chr('C'); data.write_small_u64(*client_id); data.write_str(secret); }
+                    None                        => { data.write_chr('N'); }
+                }
+                data.write_usize(*since);
+            }
+
+            Welcome(client_id, secret, length)                  => { data.write_chr('W'); data.write_small_u64(*client_id); data.write_str(secret); data.write_usize(*length); }
+            Submit(client_seq, edits)                           => { data.write_chr('S'); data.write_small_u64(*client_seq); write_edits(edits, data); }
+            Committed(seq, client_id, client_seq, edits)        => { data.write_chr('c'); data.write_usize(*seq); data.write_small_u64(*client_id); data.write_small_u64(*client_seq); write_edits(edits, data); }
+            Error(message)                                      => { data.write_chr('!'); data.write_str(message); }
+        }
+    }
+
+    ///
+    /// Deserializes a sync message
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<SyncMessage> {
+        match data.next_chr() {
+            'H' => {
+                let name        = data.next_string();
+                let client_id   = match data.next_chr() {
+                    'C' => { let client_id = data.next_small_u64(); Some((client_id, data.next_string())) },
+                    'N' => None,
+                    _   => { return None; }
+                };
+                let since       = data.next_usize();
+
+                Some(SyncMessage::Hello(name, client_id, since))
+            }
+
+            'W' => {
+                let client_id   = data.next_small_u64();
+                let secret      = data.next_string();
+                let length      = data.next_usize();
+
+                Some(SyncMessage::Welcome(client_id, secret, length))
+            }
+            'S' => { let client_seq = data.next_small_u64(); next_edits(data).map(move |edits| SyncMessage::Submit(client_seq, edits)) }
+            'c' => {
+                let seq         = data.next_usize();
+                let client_id   = data.next_small_u64();
+                let client_seq  = data.next_small_u64();
+
+                next_edits(data).map(move |edits| SyncMessage::Committed(seq, client_id, client_seq, edits))
+            }
+            '!' => { Some(SyncMessage::Error(data.next_string())) }
+
+            // Unknown message
+            _   => None
+        }
+    }
+
+    ///
+    /// Converts this message to a line of text to send to the other side of the connection
+    ///
+    pub fn to_line(&self) -> String {
+        let mut line = String::new();
+        self.serialize(&mut line);
+        line.push('\n');
+
+        line
+    }
+
+    ///
+    /// Reads a message from a line of text received from the other side of the connection
+    ///
+    pub fn from_line(line: &str) -> Option<SyncMessage> {
+        let line = line.trim_end();
+
+        if line.len() == 0 {
+            None
+        } else {
+            SyncMessage::deserialize(&mut line.chars())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration};
+
+    #[test]
+    fn hello() {
+        let message = SyncMessage::Hello("My animation".to_string(), None, 42);
+        assert!(SyncMessage::from_line(&message.to_line()) == Some(message));
+    }
+
+    #[test]
+    fn hello_with_client_id() {
+        let message = SyncMessage::Hello("My animation".to_string(), Some((3, "secret".to_string())), 0);
+        assert!(SyncMessage::from_line(&message.to_line()) == Some(message));
+    }
+
+    #[test]
+    fn welcome() {
+        let message = SyncMessage::Welcome(3, "secret".to_string(), 1000);
+        assert!(SyncMessage::from_line(&message.to_line()) == Some(message));
+    }
+
+    #[test]
+    fn submit() {
+        let message = SyncMessage::Submit(7, vec![AnimationEdit::AddNewLayer(1), AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))]);
+        assert!(SyncMessage::from_line(&message.to_line()) == Some(message));
+    }
+
+    #[test]
+    fn committed() {
+        let message = SyncMessage::Committed(12, 3, 7, vec![AnimationEdit::SetSize(800.0, 600.0), AnimationEdit::Element(vec![ElementId::Assigned(42)], ElementEdit::Delete)]);
+        assert!(SyncMessage::from_line(&message.to_line()) == Some(message));
+    }
+
+    #[test]
+    fn error() {
+        let message = SyncMessage::Error("Something went wrong".to_string());
+        assert!(SyncMessage::from_line(&message.to_line()) == Some(message));
+    }
+
+    #[test]
+    fn message_is_a_single_line() {
+        let message = SyncMessage::Committed(12, 3, 7, vec![AnimationEdit::SetSize(800.0, 600.0), AnimationEdit::AddNewLayer(2)]);
+        let line    = message.to_line();
+
+        assert!(line.ends_with('\n'));
+        assert!(line.trim_end().chars().all(|chr| chr != '\n'));
+    }
+
+    #[test]
+    fn blank_line_is_not_a_message() {
+        assert!(SyncMessage::from_line("\n") == None);
+    }
+}
@@ -0,0 +1,246 @@
+use super::message::*;
+use super::client::*;
+use super::keep_alive::*;
+use super::sqlite_sync_log::*;
+
+use flo_animation::*;
+
+use ::desync::*;
+use futures::prelude::*;
+use futures::future;
+use futures::stream;
+use futures::channel::mpsc;
+
+use std::sync::*;
+use std::collections::HashMap;
+
+///
+/// The core of the sync server
+///
+struct SyncServerCore {
+    /// The authoritative log of edits
+    log: SqliteSyncLog,
+
+    /// Where to send the edits committed to each animation (one sender per connected client)
+    clients: HashMap<String, Vec<mpsc::UnboundedSender<SyncMessage>>>
+}
+
+impl SyncServerCore {
+    ///
+    /// Adds a new client to an animation, sending it the welcome message and the edits it needs to catch up with the log
+    ///
+    /// Doing this in the core means that no edits can be committed between reading the log and the client starting to
+    /// receive new edits.
+    ///
+    /// A client that is reconnecting has to supply the secret that was issued with its ID: this stops clients from
+    /// submitting edits under IDs that belong to other clients. Clients are refused once their IDs are too large to
+    /// be given a range of element IDs.
+    ///
+    fn add_client(&mut self, animation: &str, client_id: Option<(u64, String)>, since: usize, to_client: mpsc::UnboundedSender<SyncMessage>) -> Result<u64, String> {
+        let (client_id, secret) = match client_id {
+            Some((client_id, secret))   => {
+                if !self.log.is_client(client_id, &secret).map_err(|err| err.to_string())? {
+                    return Err(format!("Client ID {} was not issued by this server", client_id));
+                }
+
+                (client_id, secret)
+            }
+
+            None                        => self.log.assign_client_id().map_err(|err| err.to_string())?
+        };
+
+        // Every client needs its own range of element IDs
+        if client_element_id_range(client_id).is_none() {
+            return Err(format!("Client ID {} is too large to be given a range of element IDs", client_id));
+        }
+
+        let replay              = self.log.read_from(animation, since).map_err(|err| err.to_string())?;
+        let length              = self.log.length(animation).map_err(|err| err.to_string())?;
+
+        // Send the welcome message and replay the log
+        to_client.unbounded_send(SyncMessage::Welcome(client_id, secret, length)).ok();
+        replay.into_iter()
+            .for_each(|committed| { to_client.unbounded_send(SyncMessage::Committed(committed.seq, committed.client_id, committed.client_seq, committed.edits)).ok(); });
+
+        // Send any future edits to the client
+        self.clients.entry(animation.to_string())
+            .or_insert_with(|| vec![])
+            .push(to_client);
+
+        Ok(client_id)
+    }
+
+    ///
+    /// Stops sending edits to a client
+    ///
+    fn remove_client(&mut self, animation: &str, to_client: &mpsc::UnboundedSender<SyncMessage>) {
+        if let Some(clients) = self.clients.get_mut(animation) {
+            clients.retain(|client| !client.same_receiver(to_client));
+        }
+    }
+
+    ///
+    /// Commits a set of edits submitted by a client to the log and sends them to every client editing the animation
+    ///
+    /// Edits that have already been committed (eg, because a client resent them after reconnecting) are ignored.
+    ///
+    fn commit(&mut self, animation: &str, client_id: u64, client_seq: u64, edits: Vec<AnimationEdit>) -> Result<(), rusqlite::Error> {
+        // Client sequence numbers increase, so anything at or below the last one has already been committed
+        if let Some(last_client_seq) = self.log.last_client_seq(animation, client_id)? {
+            if client_seq <= last_client_seq {
+                return Ok(());
+            }
+        }
+
+        // Write to the log
+        let seq = self.log.append(animation, client_id, client_seq, &edits)?;
+
+        // Send to the clients (including the one that submitted the edits, which uses this as an acknowledgement)
+        if let Some(clients) = self.clients.get_mut(animation) {
+            let committed = SyncMessage::Committed(seq, client_id, client_seq, edits);
+            clients.retain(|client| client.unbounded_send(committed.clone()).is_ok());
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// The state of a single client connected to the server
+///
+struct SyncSession {
+    /// The server this session is for
+    server: Arc<Desync<SyncServerCore>>,
+
+    /// Where messages for the client are sent (None once the client has disconnected)
+    to_client: Option<mpsc::UnboundedSender<SyncMessage>>,
+
+    /// The animation and the client ID once the client has said hello
+    client: Option<(String, u64)>
+}
+
+impl SyncSession {
+    ///
+    /// Sends a message to the client
+    ///
+    fn send(&self, message: SyncMessage) {
+        if let Some(to_client) = self.to_client.as_ref() {
+            to_client.unbounded_send(message).ok();
+        }
+    }
+
+    ///
+    /// Processes a message from the client (or None if the client has disconnected)
+    ///
+    fn process_message(&mut self, message: Option<SyncMessage>) {
+        use self::SyncMessage::*;
+
+        match message {
+            None                                        => {
+                // Stop sending edits to this client (the stream of messages to the client finishes once all of the senders are dropped)
+                let to_client = self.to_client.take();
+
+                if let (Some((animation, _)), Some(to_client)) = (self.client.take(), to_client) {
+                    self.server.desync(move |core| core.remove_client(&animation, &to_client));
+                }
+            }
+
+            Some(Hello(animation, client_id, since))    => {
+                if self.client.is_some() {
+                    self.send(Error("Already connected to an animation".to_string()));
+                    return;
+                }
+
+                let to_client = match self.to_client.clone() {
+                    Some(to_client) => to_client,
+                    None            => { return; }
+                };
+
+                // Add to the server
+                let client_animation    = animation.clone();
+                let client_id           = self.server.sync(move |core| core.add_client(&client_animation, client_id, since, to_client));
+
+                match client_id {
+                    Ok(client_id)   => { self.client = Some((animation, client_id)); }
+                    Err(err)        => { self.send(Error(err)); }
+                }
+            }
+
+            Some(Submit(client_seq, edits))             => {
+                if let (Some((animation, client_id)), Some(to_client)) = (self.client.clone(), self.to_client.clone()) {
+                    // Commit in the background (the edits come back to this client once they're committed)
+                    self.server.desync(move |core| {
+                        if let Err(err) = core.commit(&animation, client_id, client_seq, edits) {
+                            to_client.unbounded_send(Error(err.to_string())).ok();
+                        }
+                    });
+                } else {
+                    self.send(Error("Cannot submit edits before saying hello".to_string()));
+                }
+            }
+
+            // Messages that are only sent from the server to the client
+            Some(Welcome(_, _, _))                      |
+            Some(Committed(_, _, _, _))                 |
+            Some(Error(_))                              => { }
+        }
+    }
+}
+
+///
+/// A sync server manages the authoritative edit log for a set of animations, and relays the edits made by each
+/// client to all of the other clients editing the same animation.
+///
+/// The server is independent of the transport used to talk to the clients: `connect()` converts the stream of
+/// messages from a client into the stream of messages to send back to it.
+///
+pub struct SyncServer {
+    /// The core of the server
+    core: Arc<Desync<SyncServerCore>>
+}
+
+impl SyncServer {
+    ///
+    /// Creates a new sync server that stores its edits in the specified log
+    ///
+    pub fn new(log: SqliteSyncLog) -> SyncServer {
+        let core = SyncServerCore {
+            log:        log,
+            clients:    HashMap::new()
+        };
+
+        SyncServer {
+            core: Arc::new(Desync::new(core))
+        }
+    }
+
+    ///
+    /// Connects a new client to this server, returning the messages that should be sent to the client
+    ///
+    /// The returned stream finishes once the incoming stream has finished (ie, when the client disconnects)
+    ///
+    pub fn connect<Incoming>(&self, incoming: Incoming) -> impl Send+Unpin+Stream<Item=SyncMessage>
+    where Incoming: 'static+Send+Unpin+Stream<Item=SyncMessage> {
+        let (to_client, from_server) = mpsc::unbounded();
+
+        // Create the session for this client
+        let session = SyncSession {
+            server:     Arc::clone(&self.core),
+            to_client:  Some(to_client),
+            client:     None
+        };
+        let session = Arc::new(Desync::new(session));
+
+        // Process messages from the client (sending 'None' when the client disconnects)
+        let incoming = incoming.map(|message| Some(message))
+            .chain(stream::once(future::ready(None)));
+
+        pipe_in(Arc::clone(&session), incoming, |session, message| {
+            session.process_message(message);
+            future::ready(()).boxed()
+        });
+
+        // The session is kept alive until the client stops reading from it
+        KeepAliveStream::new(from_server, session)
+    }
+}
@@ -0,0 +1,241 @@
+use flo_animation::*;
+
+use rusqlite;
+use rusqlite::{params};
+use uuid::{Uuid};
+
+use std::path::{Path};
+
+const SYNC_DATA_DEFN: &[u8] = include_bytes!["../sql/flo_sync.sql"];
+
+///
+/// A set of edits that have been committed to the sync log
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct CommittedEdits {
+    /// The offset in the log of the first edit
+    pub seq: usize,
+
+    /// The client that submitted these edits
+    pub client_id: u64,
+
+    /// The sequence number assigned to these edits by the client
+    pub client_seq: u64,
+
+    /// The edits that were committed
+    pub edits: Vec<AnimationEdit>
+}
+
+///
+/// Stores the authoritative edit log for the animations managed by a sync server
+///
+pub struct SqliteSyncLog {
+    /// The database connection
+    connection: rusqlite::Connection
+}
+
+impl SqliteSyncLog {
+    ///
+    /// Creates a sync log from a SQLite connection, creating any tables that are missing
+    ///
+    pub fn from_connection(connection: rusqlite::Connection) -> Result<SqliteSyncLog, rusqlite::Error> {
+        let defn = String::from_utf8_lossy(SYNC_DATA_DEFN);
+        connection.execute_batch(&defn)?;
+
+        Ok(SqliteSyncLog {
+            connection: connection
+        })
+    }
+
+    ///
+    /// Opens (or creates) a sync log in a file
+    ///
+    pub fn open_file(path: &Path) -> Result<SqliteSyncLog, rusqlite::Error> {
+        Self::from_connection(rusqlite::Connection::open(path)?)
+    }
+
+    ///
+    /// Creates a sync log that is stored in memory
+    ///
+    pub fn new_in_memory() -> Result<SqliteSyncLog, rusqlite::Error> {
+        Self::from_connection(rusqlite::Connection::open_in_memory()?)
+    }
+
+    ///
+    /// Assigns an ID to a new client, returning the ID and the secret that the client must supply to use it again
+    ///
+    pub fn assign_client_id(&mut self) -> Result<(u64, String), rusqlite::Error> {
+        let secret      = Uuid::new_v4().to_simple().to_string();
+        let mut insert  = self.connection.prepare_cached("INSERT INTO SyncClients (Secret) VALUES (?);")?;
+        insert.execute(params![secret])?;
+
+        Ok((self.connection.last_insert_rowid() as u64, secret))
+    }
+
+    ///
+    /// True if a client ID was assigned by this log with the specified secret
+    ///
+    pub fn is_client(&mut self, client_id: u64, secret: &str) -> Result<bool, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT COUNT(*) FROM SyncClients WHERE ClientId = ? AND Secret = ?;")?;
+        let count: i64  = read.query_row(params![client_id as i64, secret], |row| row.get(0))?;
+
+        Ok(count > 0)
+    }
+
+    ///
+    /// Retrieves the number of edits in the log for an animation
+    ///
+    pub fn length(&mut self, animation: &str) -> Result<usize, rusqlite::Error> {
+        let mut read        = self.connection.prepare_cached("SELECT COUNT(*) FROM SyncEdits WHERE Animation = ?;")?;
+        let length: i64     = read.query_row(&[animation], |row| row.get(0))?;
+
+        Ok(length as usize)
+    }
+
+    ///
+    /// Retrieves the highest client sequence number that has been committed for a client (or None if the client has not committed any edits)
+    ///
+    pub fn last_client_seq(&mut self, animation: &str, client_id: u64) -> Result<Option<u64>, rusqlite::Error> {
+        let mut read                = self.connection.prepare_cached("SELECT MAX(ClientSeq) FROM SyncEdits WHERE Animation = ? AND ClientId = ?;")?;
+        let client_seq: Option<i64> = read.query_row(params![animation, client_id as i64], |row| row.get(0))?;
+
+        Ok(client_seq.map(|client_seq| client_seq as u64))
+    }
+
+    ///
+    /// Appends a set of edits to the log for an animation, returning the offset of the first edit
+    ///
+    pub fn append(&mut self, animation: &str, client_id: u64, client_seq: u64, edits: &Vec<AnimationEdit>) -> Result<usize, rusqlite::Error> {
+        let transaction = self.connection.transaction()?;
+        let seq;
+
+        {
+            let mut read_length = transaction.prepare_cached("SELECT COUNT(*) FROM SyncEdits WHERE Animation = ?;")?;
+            let mut insert      = transaction.prepare_cached("INSERT INTO SyncEdits (Animation, Seq, ClientId, ClientSeq, Edit) VALUES (?, ?, ?, ?, ?);")?;
+            let length: i64     = read_length.query_row(&[animation], |row| row.get(0))?;
+
+            for (index, edit) in edits.iter().enumerate() {
+                let mut serialized = String::new();
+                edit.serialize(&mut serialized);
+
+                insert.execute(params![animation, length + (index as i64), client_id as i64, client_seq as i64, serialized])?;
+            }
+
+            seq = length as usize;
+        }
+
+        transaction.commit()?;
+
+        Ok(seq)
+    }
+
+    ///
+    /// Reads the edits from a particular offset in the log, grouped into the sets they were committed in
+    ///
+    pub fn read_from(&mut self, animation: &str, since: usize) -> Result<Vec<CommittedEdits>, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT Seq, ClientId, ClientSeq, Edit FROM SyncEdits WHERE Animation = ? AND Seq >= ? ORDER BY Seq ASC;")?;
+        let rows        = read.query_map(params![animation, since as i64], |row| {
+            let seq: i64        = row.get(0)?;
+            let client_id: i64  = row.get(1)?;
+            let client_seq: i64 = row.get(2)?;
+            let edit: String    = row.get(3)?;
+
+            Ok((seq as usize, client_id as u64, client_seq as u64, edit))
+        })?;
+
+        // Group the edits by the client and sequence number that submitted them
+        let mut committed: Vec<CommittedEdits> = vec![];
+
+        for row in rows {
+            let (seq, client_id, client_seq, edit) = row?;
+
+            // Edits that can't be read are left out of the log (they would have been rejected when they were submitted)
+            let edit = match AnimationEdit::deserialize(&mut edit.chars()) {
+                Some(edit)  => edit,
+                None        => { continue; }
+            };
+
+            match committed.last_mut() {
+                Some(last) if last.client_id == client_id && last.client_seq == client_seq  => { last.edits.push(edit); }
+                _                                                                           => { committed.push(CommittedEdits { seq: seq, client_id: client_id, client_seq: client_seq, edits: vec![edit] }); }
+            }
+        }
+
+        Ok(committed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn client_ids_are_unique() {
+        let mut log         = SqliteSyncLog::new_in_memory().unwrap();
+        let (first, _)      = log.assign_client_id().unwrap();
+        let (second, _)     = log.assign_client_id().unwrap();
+
+        assert!(first != second);
+    }
+
+    #[test]
+    fn client_must_supply_its_secret() {
+        let mut log                 = SqliteSyncLog::new_in_memory().unwrap();
+        let (first, first_secret)   = log.assign_client_id().unwrap();
+        let (second, second_secret) = log.assign_client_id().unwrap();
+
+        assert!(log.is_client(first, &first_secret).unwrap());
+        assert!(log.is_client(second, &second_secret).unwrap());
+
+        assert!(!log.is_client(first, &second_secret).unwrap());
+        assert!(!log.is_client(second, "").unwrap());
+        assert!(!log.is_client(second+1, &second_secret).unwrap());
+    }
+
+    #[test]
+    fn append_and_read_edits() {
+        let mut log = SqliteSyncLog::new_in_memory().unwrap();
+
+        assert!(log.append("test", 1, 0, &vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)]).unwrap() == 0);
+        assert!(log.append("test", 2, 0, &vec![AnimationEdit::AddNewLayer(3)]).unwrap() == 2);
+        assert!(log.append("other", 2, 1, &vec![AnimationEdit::AddNewLayer(4)]).unwrap() == 0);
+
+        assert!(log.length("test").unwrap() == 3);
+        assert!(log.length("other").unwrap() == 1);
+
+        let committed = log.read_from("test", 0).unwrap();
+        assert!(committed == vec![
+            CommittedEdits { seq: 0, client_id: 1, client_seq: 0, edits: vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)] },
+            CommittedEdits { seq: 2, client_id: 2, client_seq: 0, edits: vec![AnimationEdit::AddNewLayer(3)] }
+        ]);
+    }
+
+    #[test]
+    fn read_from_middle_of_log() {
+        let mut log = SqliteSyncLog::new_in_memory().unwrap();
+
+        log.append("test", 1, 0, &vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)]).unwrap();
+        log.append("test", 2, 0, &vec![AnimationEdit::AddNewLayer(3)]).unwrap();
+
+        let committed = log.read_from("test", 1).unwrap();
+        assert!(committed == vec![
+            CommittedEdits { seq: 1, client_id: 1, client_seq: 0, edits: vec![AnimationEdit::AddNewLayer(2)] },
+            CommittedEdits { seq: 2, client_id: 2, client_seq: 0, edits: vec![AnimationEdit::AddNewLayer(3)] }
+        ]);
+    }
+
+    #[test]
+    fn last_client_seq() {
+        let mut log = SqliteSyncLog::new_in_memory().unwrap();
+
+        assert!(log.last_client_seq("test", 1).unwrap() == None);
+
+        log.append("test", 1, 0, &vec![AnimationEdit::AddNewLayer(1)]).unwrap();
+        log.append("test", 1, 1, &vec![AnimationEdit::AddNewLayer(2)]).unwrap();
+        log.append("test", 2, 5, &vec![AnimationEdit::AddNewLayer(3)]).unwrap();
+
+        assert!(log.last_client_seq("test", 1).unwrap() == Some(1));
+        assert!(log.last_client_seq("test", 2).unwrap() == Some(5));
+        assert!(log.last_client_seq("other", 1).unwrap() == None);
+    }
+}
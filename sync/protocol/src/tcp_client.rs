@@ -0,0 +1,62 @@
+use super::client::*;
+use super::message::*;
+
+use flo_animation::*;
+
+use futures::prelude::*;
+use futures::executor;
+use futures::channel::mpsc;
+
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs, Shutdown};
+use std::thread;
+use std::thread::{JoinHandle};
+
+impl<Anim: 'static+EditableAnimation> SyncClient<Anim> {
+    ///
+    /// Connects this client to a sync server over TCP
+    ///
+    /// The connection is run on background threads: the returned handle finishes when the connection is closed, after
+    /// which the client can be connected again.
+    ///
+    pub fn connect_tcp<Address: ToSocketAddrs>(&self, address: Address) -> io::Result<JoinHandle<()>> {
+        let connection      = TcpStream::connect(address)?;
+        let reader          = BufReader::new(connection.try_clone()?);
+        let mut writer      = connection;
+
+        // Read messages from the server on a background thread
+        let (to_client, from_server) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let line = match line {
+                    Ok(line)    => line,
+                    Err(_)      => { break; }
+                };
+
+                if let Some(message) = SyncMessage::from_line(&line) {
+                    if to_client.unbounded_send(message).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        // Send messages to the server on another thread
+        let mut to_server = self.connect(from_server);
+
+        Ok(thread::spawn(move || {
+            executor::block_on(async {
+                while let Some(message) = to_server.next().await {
+                    if writer.write_all(message.to_line().as_bytes()).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            // Close the connection (which will also stop the reader thread)
+            writer.shutdown(Shutdown::Both).ok();
+        }))
+    }
+}
@@ -0,0 +1,384 @@
+use flo_sync::*;
+use flo_animation::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+use futures::executor;
+use futures::stream;
+use futures::future::{AbortHandle, abortable};
+use futures::channel::mpsc;
+
+use std::sync::*;
+use std::thread;
+use std::time::{Duration, Instant};
+
+///
+/// Creates a new animation stored in memory
+///
+fn new_animation() -> Arc<impl 'static+EditableAnimation> {
+    let in_memory_store = InMemoryStorage::new();
+    Arc::new(create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed()))
+}
+
+///
+/// Connects a client to a server using an in-memory loopback, returning a handle that can be used to disconnect it
+///
+fn connect_loopback<Anim: 'static+EditableAnimation>(client: &SyncClient<Anim>, server: &SyncServer) -> AbortHandle {
+    let (to_client, from_server)    = mpsc::unbounded();
+    let to_server                   = client.connect(from_server);
+    let to_client_stream            = server.connect(to_server);
+
+    // Relay the messages from the server to the client until the connection is aborted
+    let (relay, abort_handle)       = abortable(to_client_stream.map(|message| Ok(message)).forward(to_client));
+    thread::spawn(move || { executor::block_on(relay).ok(); });
+
+    abort_handle
+}
+
+///
+/// Waits for a condition to become true, failing if it takes too long
+///
+fn wait_for<Condition: Fn() -> bool>(condition: Condition) {
+    let start = Instant::now();
+
+    while !condition() {
+        assert!(Instant::now().duration_since(start) < Duration::from_secs(10));
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+///
+/// Performs some edits on an animation
+///
+fn edit<Anim: EditableAnimation>(animation: &Anim, edits: Vec<AnimationEdit>) {
+    animation.perform_edits(edits);
+}
+
+///
+/// Returns the sorted list of layer IDs in an animation
+///
+fn layer_ids<Anim: Animation>(animation: &Anim) -> Vec<u64> {
+    let mut layer_ids = animation.get_layer_ids();
+    layer_ids.sort();
+    layer_ids
+}
+
+///
+/// Returns the edits to draw a brush stroke (with new element IDs) on a layer
+///
+fn brush_stroke(layer_id: u64, points: Vec<(f32, f32)>) -> Vec<AnimationEdit> {
+    let when = Duration::from_millis(0);
+
+    vec![
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(points.into_iter().map(|point| RawPoint::from(point)).collect()))))
+    ]
+}
+
+///
+/// Describes the elements in the first frame of a layer
+///
+fn frame_elements<Anim: Animation>(animation: &Anim, layer_id: u64) -> Vec<(ElementId, String)> {
+    frame_elements_at(animation, layer_id, Duration::from_millis(0))
+}
+
+///
+/// Describes the elements in the frame of a layer at a particular time
+///
+fn frame_elements_at<Anim: Animation>(animation: &Anim, layer_id: u64, when: Duration) -> Vec<(ElementId, String)> {
+    let layer = animation.get_layer_with_id(layer_id).unwrap();
+    let frame = layer.get_frame_at_time(when);
+
+    frame.vector_elements()
+        .map(|elements| elements.map(|element| (element.id(), format!("{:?}", element))).collect())
+        .unwrap_or_else(|| vec![])
+}
+
+#[test]
+fn two_clients_converge() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+    let _connection2 = connect_loopback(&client2, &server);
+
+    // Each client edits a different layer
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1), AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))]);
+    edit(&*animation2, vec![AnimationEdit::AddNewLayer(2), AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))]);
+    edit(&*animation1, vec![AnimationEdit::SetSize(800.0, 600.0)]);
+
+    // Both clients should end up with the same animation
+    wait_for(|| client1.server_offset() == 5 && client2.server_offset() == 5);
+    wait_for(|| !client1.has_unacknowledged_edits() && !client2.has_unacknowledged_edits());
+
+    assert!(layer_ids(&*animation1) == vec![1, 2]);
+    assert!(layer_ids(&*animation2) == vec![1, 2]);
+    assert!(animation1.size() == (800.0, 600.0));
+    assert!(animation2.size() == (800.0, 600.0));
+
+    // Edits from the server are not sent back to it, so each animation has the same number of edits as the server
+    assert!(animation1.get_num_edits() == 5);
+    assert!(animation2.get_num_edits() == 5);
+}
+
+#[test]
+fn new_client_receives_existing_log() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)]);
+    wait_for(|| client1.server_offset() == 2);
+
+    // A client that connects later should catch up with the log
+    let animation2  = new_animation();
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let _connection2 = connect_loopback(&client2, &server);
+
+    wait_for(|| client2.server_offset() == 2);
+    assert!(layer_ids(&*animation2) == vec![1, 2]);
+}
+
+#[test]
+fn clients_resync_after_reconnecting() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let connection1     = connect_loopback(&client1, &server);
+    let _connection2    = connect_loopback(&client2, &server);
+
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1)]);
+    wait_for(|| client1.server_offset() == 1 && client2.server_offset() == 1);
+
+    // Disconnect the first client, then edit both animations
+    connection1.abort();
+    thread::sleep(Duration::from_millis(50));
+
+    edit(&*animation2, vec![AnimationEdit::AddNewLayer(2)]);
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(3)]);
+    wait_for(|| client2.server_offset() == 2);
+
+    assert!(layer_ids(&*animation1) == vec![1, 3]);
+
+    // Reconnecting should send the edits made while disconnected and receive the edits that were missed
+    let _connection1 = connect_loopback(&client1, &server);
+
+    wait_for(|| client1.server_offset() == 3 && client2.server_offset() == 3);
+
+    assert!(layer_ids(&*animation1) == vec![1, 2, 3]);
+    assert!(layer_ids(&*animation2) == vec![1, 2, 3]);
+}
+
+#[test]
+fn clients_only_receive_their_animation() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "first");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "second");
+
+    let _connection1 = connect_loopback(&client1, &server);
+    let _connection2 = connect_loopback(&client2, &server);
+
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1)]);
+    edit(&*animation2, vec![AnimationEdit::AddNewLayer(2)]);
+
+    wait_for(|| client1.server_offset() == 1 && client2.server_offset() == 1);
+
+    assert!(layer_ids(&*animation1) == vec![1]);
+    assert!(layer_ids(&*animation2) == vec![2]);
+}
+
+#[test]
+fn clients_drawing_at_the_same_time_assign_different_ids() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+    let _connection2 = connect_loopback(&client2, &server);
+
+    edit(&*animation1, vec![
+        AnimationEdit::AddNewLayer(1), AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::AddNewLayer(2), AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+    wait_for(|| client1.server_offset() == 4 && client2.server_offset() == 4);
+
+    // Both clients draw before they've seen each other's strokes
+    edit(&*animation1, brush_stroke(1, vec![(10.0, 10.0), (20.0, 5.0)]));
+    edit(&*animation2, brush_stroke(2, vec![(30.0, 30.0), (40.0, 35.0)]));
+
+    wait_for(|| client1.server_offset() == 10 && client2.server_offset() == 10);
+    wait_for(|| !client1.has_unacknowledged_edits() && !client2.has_unacknowledged_edits());
+
+    // Every element should have its own ID, and both clients should have the same frames
+    let layer1 = frame_elements(&*animation1, 1);
+    let layer2 = frame_elements(&*animation1, 2);
+
+    assert!(layer1.len() > 0);
+    assert!(layer2.len() > 0);
+    assert!(layer1.iter().all(|(id1, _)| layer2.iter().all(|(id2, _)| id1 != id2)));
+
+    assert!(frame_elements(&*animation2, 1) == layer1);
+    assert!(frame_elements(&*animation2, 2) == layer2);
+}
+
+#[test]
+fn duplicated_keyframes_have_the_same_ids_on_every_client() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+    let _connection2 = connect_loopback(&client2, &server);
+
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1), AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))]);
+    edit(&*animation1, brush_stroke(1, vec![(10.0, 10.0), (20.0, 5.0)]));
+    edit(&*animation1, vec![AnimationEdit::Layer(1, LayerEdit::DuplicateKeyFrame(Duration::from_millis(0), Duration::from_millis(1000), vec![]))]);
+
+    wait_for(|| client1.server_offset() == 6 && client2.server_offset() == 6);
+    wait_for(|| !client1.has_unacknowledged_edits());
+
+    // The copy has new IDs, and they should be the same on both clients
+    let original    = frame_elements(&*animation1, 1);
+    let copy        = frame_elements_at(&*animation1, 1, Duration::from_millis(1000));
+
+    assert!(copy.len() > 0);
+    assert!(copy.iter().all(|(copy_id, _)| original.iter().all(|(original_id, _)| copy_id != original_id)));
+    assert!(frame_elements_at(&*animation2, 1, Duration::from_millis(1000)) == copy);
+
+    // Editing the copy on the second client should edit the same elements on the first client
+    let copy_ids = frame_elements_at(&*animation2, 1, Duration::from_millis(1000)).into_iter().map(|(id, _)| id).collect();
+    edit(&*animation2, vec![AnimationEdit::Element(copy_ids, ElementEdit::Transform(vec![ElementTransform::MoveTo(100.0, 100.0)]))]);
+
+    wait_for(|| client1.server_offset() == 7 && client2.server_offset() == 7);
+    wait_for(|| !client2.has_unacknowledged_edits());
+
+    let edited_copy = frame_elements_at(&*animation2, 1, Duration::from_millis(1000));
+
+    assert!(edited_copy != copy);
+    assert!(frame_elements_at(&*animation1, 1, Duration::from_millis(1000)) == edited_copy);
+    assert!(frame_elements(&*animation1, 1) == original);
+    assert!(frame_elements(&*animation2, 1) == original);
+}
+
+#[test]
+fn undo_reverses_the_same_edit_on_every_client() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+    let _connection2 = connect_loopback(&client2, &server);
+
+    // The clients see these edits in a different order, so they have different undo histories
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1)]);
+    edit(&*animation2, vec![AnimationEdit::AddNewLayer(2)]);
+
+    wait_for(|| client1.server_offset() == 2 && client2.server_offset() == 2);
+    wait_for(|| !client1.has_unacknowledged_edits() && !client2.has_unacknowledged_edits());
+
+    // Undoing on one client should make the same change on the other, rather than undoing whatever is on top of its own undo stack
+    animation1.undo();
+
+    wait_for(|| client1.server_offset() == 3 && client2.server_offset() == 3);
+    wait_for(|| !client1.has_unacknowledged_edits());
+
+    assert!(layer_ids(&*animation1).len() == 1);
+    assert!(layer_ids(&*animation2) == layer_ids(&*animation1));
+}
+
+#[test]
+fn undo_steps_are_synced_when_edits_select_an_undo_history() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+    let _connection2 = connect_loopback(&client2, &server);
+
+    // These edits are undone separately even if they're sent to the server together
+    edit(&*animation1, vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::AddNewLayer(1)]);
+    edit(&*animation1, vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::AddNewLayer(2)]);
+    edit(&*animation2, vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::AddNewLayer(3)]);
+
+    wait_for(|| client1.server_offset() == 6 && client2.server_offset() == 6);
+    wait_for(|| !client1.has_unacknowledged_edits() && !client2.has_unacknowledged_edits());
+
+    edit(&*animation1, vec![AnimationEdit::SelectUndoHistory(1), AnimationEdit::Undo]);
+
+    wait_for(|| client1.server_offset() == 8 && client2.server_offset() == 8);
+
+    assert!(layer_ids(&*animation1) == vec![1, 3]);
+    assert!(layer_ids(&*animation2) == vec![1, 3]);
+}
+
+#[test]
+fn edits_from_other_clients_cannot_be_undone() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let animation2  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+    let client2     = SyncClient::new(Arc::clone(&animation2), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+    let _connection2 = connect_loopback(&client2, &server);
+
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1)]);
+    wait_for(|| client1.server_offset() == 1 && client2.server_offset() == 1);
+
+    // Only the client that made the edit can undo it
+    assert!(animation1.can_undo());
+    assert!(!animation2.can_undo());
+
+    animation2.undo();
+    thread::sleep(Duration::from_millis(50));
+
+    assert!(client2.server_offset() == 1);
+    assert!(layer_ids(&*animation1) == vec![1]);
+    assert!(layer_ids(&*animation2) == vec![1]);
+}
+
+#[test]
+fn server_rejects_client_ids_it_did_not_issue() {
+    let server      = SyncServer::new(SqliteSyncLog::new_in_memory().unwrap());
+    let animation1  = new_animation();
+    let client1     = SyncClient::new(Arc::clone(&animation1), "test");
+
+    let _connection1 = connect_loopback(&client1, &server);
+
+    edit(&*animation1, vec![AnimationEdit::AddNewLayer(1)]);
+    wait_for(|| client1.server_offset() == 1);
+
+    // Try to connect as the first client without knowing its secret
+    let hello       = SyncMessage::Hello("test".to_string(), Some((1, "not the secret".to_string())), 0);
+    let to_client   = server.connect(stream::iter(vec![hello, SyncMessage::Submit(100, vec![AnimationEdit::AddNewLayer(2)])]));
+    let replies     = executor::block_on(to_client.collect::<Vec<_>>());
+
+    assert!(replies.len() == 2);
+    assert!(match replies[0] { SyncMessage::Error(_) => true, _ => false });
+    assert!(match replies[1] { SyncMessage::Error(_) => true, _ => false });
+
+    // The edit should not have been committed
+    thread::sleep(Duration::from_millis(50));
+    assert!(client1.server_offset() == 1);
+    assert!(layer_ids(&*animation1) == vec![1]);
+}
@@ -0,0 +1,98 @@
+use flo_sync::*;
+
+use tokio::prelude::*;
+use tokio::io::{BufReader, AsyncBufReadExt};
+use tokio::net::{TcpListener, TcpStream};
+use futures::prelude::*;
+use futures::future;
+use clap::{App, Arg};
+use log::*;
+
+use std::sync::*;
+use std::path::{PathBuf};
+
+/// The server doesn't authenticate its clients, so by default it only accepts connections from the local machine
+const DEFAULT_ADDRESS: &str = "127.0.0.1:3001";
+
+///
+/// Relays the messages for a single client connection
+///
+async fn serve_connection(server: Arc<SyncServer>, connection: TcpStream) {
+    let (reader, mut writer) = tokio::io::split(connection);
+
+    // Each line from the client is a message (the connection is closed if there's an error reading from it)
+    let from_client = BufReader::new(reader).lines()
+        .take_while(|line| future::ready(line.is_ok()))
+        .filter_map(|line| future::ready(line.ok().and_then(|line| SyncMessage::from_line(&line))));
+
+    // Send the messages from the server back to the client
+    let mut to_client = server.connect(from_client.boxed());
+
+    while let Some(message) = to_client.next().await {
+        if writer.write_all(message.to_line().as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    // Log connections by default (RUST_LOG can be used to change this)
+    pretty_env_logger::formatted_builder()
+        .filter_level(LevelFilter::Info)
+        .parse_filters(&std::env::var("RUST_LOG").unwrap_or_else(|_| String::new()))
+        .init();
+
+    // Fetch the parameters
+    let params = App::new(env!("CARGO_PKG_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .author("Copyright 2017-2020 Andrew Hunter <andrew@logicalshift.io>")
+        .about(env!("CARGO_PKG_DESCRIPTION"))
+        .after_help(concat!("Full source code is available at https://github.com/Logicalshift/flowbetween\n",
+            "\n",
+            "Licensed under the Apache License, Version 2.0 (the \"License\");\n",
+            "you may not use this file except in compliance with the License.\n",
+            "You may obtain a copy of the License at\n",
+            "\n",
+            "http://www.apache.org/licenses/LICENSE-2.0\n\n"))
+        .arg(Arg::with_name("database")
+            .long("database")
+            .short("d")
+            .takes_value(true)
+            .help("The SQLite database to store the edit logs in (the logs are kept in memory if this is not specified)"))
+        .arg(Arg::with_name("address")
+            .long("address")
+            .short("a")
+            .takes_value(true)
+            .help("The address and port to listen for connections on (defaults to 127.0.0.1:3001). Clients are not authenticated, so anyone who can connect can edit any animation"))
+        .get_matches();
+
+    // Open the log
+    let log = match params.value_of("database") {
+        Some(database)  => SqliteSyncLog::open_file(&PathBuf::from(database)),
+        None            => SqliteSyncLog::new_in_memory()
+    };
+    let log = log.expect("Could not open the sync log");
+
+    // Start listening for connections
+    let server          = Arc::new(SyncServer::new(log));
+    let address         = params.value_of("address").unwrap_or(DEFAULT_ADDRESS);
+    let mut listener    = TcpListener::bind(address).await.expect("Could not listen for connections");
+
+    info!("{} v{} listening for connections at {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), address);
+
+    loop {
+        let (connection, peer) = match listener.accept().await {
+            Ok(connection)  => connection,
+            Err(err)        => { warn!("Could not accept connection: {}", err); continue; }
+        };
+
+        info!("Connection from {}", peer);
+
+        let server = Arc::clone(&server);
+        tokio::spawn(async move {
+            serve_connection(server, connection).await;
+            info!("{} disconnected", peer);
+        });
+    }
+}
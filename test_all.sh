@@ -17,4 +17,6 @@ cargo test -p flo_static_files --features http
 cargo test -p flo_http_ui --features http
 cargo test -p flo_http_ui_actix --features http
 cargo test -p flo
+cargo test --manifest-path sync/protocol/Cargo.toml
+cargo test --manifest-path sync/server/Cargo.toml
 cargo test --features http
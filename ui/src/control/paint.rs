@@ -42,6 +42,21 @@ pub enum PaintAction {
     Cancel
 }
 
+///
+/// The modifier keys that were held down during a painting event
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, Default)]
+pub struct PaintModifiers {
+    /// The shift key is held down
+    pub shift: bool,
+
+    /// The control key (or the command key on OS X) is held down
+    pub control: bool,
+
+    /// The alt key (or the option key on OS X) is held down
+    pub alt: bool
+}
+
 ///
 /// Data for a painting event
 ///
@@ -63,5 +78,9 @@ pub struct Painting {
     pub tilt_x: f32,
 
     /// Y tilt (-90 to 90)
-    pub tilt_y: f32
+    pub tilt_y: f32,
+
+    /// The modifier keys held down during this event (user interfaces that can't read the keyboard leave these all unset)
+    #[serde(default)]
+    pub modifiers: PaintModifiers
 }
@@ -44,3 +44,4 @@ mod bytes_iterator;
 pub use self::inmemory::*;
 pub use self::static_data::*;
 pub use self::shortcuts::*;
+pub use self::png::*;
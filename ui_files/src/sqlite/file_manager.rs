@@ -129,6 +129,13 @@ impl SqliteFileManager {
         }
     }
 
+    ///
+    /// Retrieves the directory where this file manager stores its files
+    ///
+    pub fn root_path(&self) -> &Path {
+        self.root_path.as_path()
+    }
+
     ///
     /// Retrieves the log for this file manager
     ///
@@ -1,4 +1,5 @@
 use super::file_controller::*;
+use super::super::file_model::*;
 use super::super::file_manager::*;
 use super::super::open_file_store::*;
 
@@ -7,7 +8,7 @@ use std::sync::*;
 ///
 /// The file chooser trait is implemented by structs that describe a file chooser
 ///
-pub trait FileChooser {
+pub trait FileChooser : Send+Sync {
     /// The controller that edits/displays open files
     type Controller: FileController;
 
@@ -23,4 +24,18 @@ pub trait FileChooser {
     /// Retrieves the shared file store for this chooser
     ///
     fn get_file_store(&self) -> Arc<OpenFileStore<<Self::Controller as FileController>::Model>>;
+
+    ///
+    /// Creates the controller for a file that has been opened from this chooser
+    ///
+    fn open_file(&self, model: <<Self::Controller as FileController>::Model as FileModel>::InstanceModel) -> Self::Controller {
+        Self::Controller::open(model)
+    }
+
+    ///
+    /// True if the user of this chooser can only view files (files can't be created, deleted, renamed or reordered)
+    ///
+    fn is_read_only(&self) -> bool {
+        false
+    }
 }
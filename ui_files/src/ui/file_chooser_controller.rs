@@ -44,6 +44,12 @@ pub struct FileChooserController<Chooser: FileChooser> {
 
     /// The cache of open files
     open_file_store: Arc<OpenFileStore<<Chooser::Controller as FileController>::Model>>,
+
+    /// The chooser that describes how files are opened
+    chooser: Chooser,
+
+    /// True if the files can only be viewed, so the controls for creating, deleting, renaming and reordering them are hidden
+    read_only: bool
 }
 
 impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
@@ -56,6 +62,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
         // Fetch the file manager and file store from the chooser
         let file_manager        = chooser.get_file_manager();
         let open_file_store     = chooser.get_file_store();
+        let read_only           = chooser.is_read_only();
 
         // Create the model
         let model               = FileChooserModel::new(&chooser);
@@ -72,7 +79,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
 
         // Create the UI
         let background_color    = bind(Color::Rgba(0.1, 0.1, 0.1, 1.0));
-        let ui                  = Self::ui(&model, BindRef::from(background_color.clone()), Arc::clone(&viewmodel));
+        let ui                  = Self::ui(&model, read_only, BindRef::from(background_color.clone()), Arc::clone(&viewmodel));
 
         // Create the chooser controller
         FileChooserController {
@@ -82,7 +89,9 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
             ui:                 ui,
             file_manager:       file_manager,
             background_color:   background_color,
-            open_file_store:    open_file_store
+            open_file_store:    open_file_store,
+            chooser:            chooser,
+            read_only:          read_only
         }
     }
 
@@ -124,7 +133,9 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
     ///
     /// Creates a control representing a file
     ///
-    fn file_ui(file: &FileUiModel, index: u32, editing_filename_index: BindRef<Option<usize>>, viewmodel: Arc<DynamicViewModel>) -> Control {
+    /// Files can't be renamed or dragged to a new position when `read_only` is set
+    ///
+    fn file_ui(file: &FileUiModel, index: u32, read_only: bool, editing_filename_index: BindRef<Option<usize>>, viewmodel: Arc<DynamicViewModel>) -> Control {
         // If the user is editing the filename, then use a textbox instead of the label
         let label = if editing_filename_index.get() == Some(index as usize) {
             Control::container()
@@ -146,6 +157,12 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                     Control::empty()
                         .with(Bounds::next_horiz(8.0)),
                 ])
+        } else if read_only {
+            Control::label()
+                .with(TextAlign::Center)
+                .with(FontWeight::Normal)
+                .with(Bounds::next_vert(22.0))
+                .with(file.name.get())
         } else {
             Control::label()
                 .with(TextAlign::Center)
@@ -162,30 +179,39 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
         // Make sure the file is created in the viewmodel
         Self::create_viewmodel_for_file(viewmodel, file);
 
+        // The preview can be clicked to open the file
+        let preview     = Control::empty()
+            .with(Bounds::stretch_vert(1.0))
+            .with(Appearance::Background(Color::Rgba(0.0, 0.6, 0.9, 1.0)))
+            .with((ActionTrigger::Click, format!("Open-{}", index)));
+        let preview     = if read_only { preview } else { preview.with((ActionTrigger::Drag, format!("Drag-{}", index))) };
+
+        // Files are only selected so they can be deleted, so viewers don't get the checkbox
+        let mut controls = vec![
+            preview,
+            Control::empty()
+                .with(Bounds::next_vert(2.0)),
+            label
+        ];
+
+        if !read_only {
+            controls.push(Control::check_box()
+                .with(ControlAttribute::ZIndex(1))
+                .with(State::Value(Property::Bind(format!("Selected-{}", path_string))))
+                .with((ActionTrigger::SetValue, format!("SetSelect-{}", index)))
+                .with(Bounds { x1: Position::At(2.0), y1: Position::At(2.0), x2: Position::At(22.0), y2: Position::At(22.0) }));
+        }
+
         // Control consists of a panel showing a preview of the file and a label showing the 'filename'
         Control::container()
-            .with(vec![
-                Control::empty()
-                    .with(Bounds::stretch_vert(1.0))
-                    .with(Appearance::Background(Color::Rgba(0.0, 0.6, 0.9, 1.0)))
-                    .with((ActionTrigger::Click, format!("Open-{}", index)))
-                    .with((ActionTrigger::Drag, format!("Drag-{}", index))),
-                Control::empty()
-                    .with(Bounds::next_vert(2.0)),
-                label,
-                Control::check_box()
-                    .with(ControlAttribute::ZIndex(1))
-                    .with(State::Value(Property::Bind(format!("Selected-{}", path_string))))
-                    .with((ActionTrigger::SetValue, format!("SetSelect-{}", index)))
-                    .with(Bounds { x1: Position::At(2.0), y1: Position::At(2.0), x2: Position::At(22.0), y2: Position::At(22.0) })
-            ])
+            .with(controls)
             .with(ControlAttribute::Padding((2, 2), (2, 2)))
     }
 
     ///
     /// Creates the UI binding from the model
     ///
-    fn ui(model: &FileChooserModel<Chooser>, background: BindRef<Color>, viewmodel: Arc<DynamicViewModel>) -> BindRef<Control> {
+    fn ui(model: &FileChooserModel<Chooser>, read_only: bool, background: BindRef<Color>, viewmodel: Arc<DynamicViewModel>) -> BindRef<Control> {
         // Create references to the parts of the model we need
         let controller              = model.active_controller.clone();
         let file_list               = model.file_list.clone();
@@ -237,7 +263,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                     })
                                     .with(Appearance::Background(Color::Rgba(0.0, 0.0, 0.4, 0.1))),
 
-                                Self::file_ui(file_model, file_index, editing_filename_index, Arc::clone(&viewmodel))
+                                Self::file_ui(file_model, file_index, read_only, editing_filename_index, Arc::clone(&viewmodel))
                                     .with(Bounds {
                                         x1: Position::Floating(Property::bound("DragX"), x),
                                         y1: Position::Floating(Property::bound("DragY"), y),
@@ -250,7 +276,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                         } else if drag_after_index == Some((file_index as i64)-1) {
                             // Going to insert before this file
                             vec![
-                                Self::file_ui(file_model, file_index, editing_filename_index, Arc::clone(&viewmodel))
+                                Self::file_ui(file_model, file_index, read_only, editing_filename_index, Arc::clone(&viewmodel))
                                     .with(Bounds {
                                         x1: Position::At(x),
                                         y1: Position::At(y),
@@ -271,7 +297,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                         } else {
                             // File that is static and not being dragged
                             vec![
-                                Self::file_ui(file_model, file_index, editing_filename_index, Arc::clone(&viewmodel))
+                                Self::file_ui(file_model, file_index, read_only, editing_filename_index, Arc::clone(&viewmodel))
                                     .with(Bounds {
                                         x1: Position::At(x),
                                         y1: Position::At(y),
@@ -305,7 +331,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                 // If any files are selected, we display a set of selected file controls
                 let selected_file_count = selected_file_count.get();
                 let confirming_deletion = confirming_deletion.get();
-                let selected_file_controls = if selected_file_count > 0 {
+                let selected_file_controls = if selected_file_count > 0 && !read_only {
                     // Some files are selected: display the controls
                     vec![
                         Control::container()
@@ -386,9 +412,9 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                         Control::empty()
                             .with(Bounds::next_vert(8.0)),
 
-                        // Buttons
+                        // Buttons (viewers can't create new files)
                         Control::container()
-                            .with(vec![
+                            .with(if read_only { vec![] } else { vec![
                                 Control::empty()
                                     .with(Bounds::stretch_horiz(1.0)),
                                 Control::button()
@@ -400,7 +426,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                         .with((ActionTrigger::Click, "CreateNewFile")),
                                 Control::empty()
                                     .with(Bounds::stretch_horiz(1.0))
-                            ])
+                            ] })
                             .with(Bounds::next_vert(32.0)),
 
                         Control::empty()
@@ -486,6 +512,11 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
 
     /// Callback for when a control associated with this controller generates an action
     fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        // Viewers can only scroll through and open files
+        if self.read_only && !(action_id == "ScrollFiles" || action_id.starts_with("Open-")) {
+            return;
+        }
+
         match (action_id, action_data) {
             ("ScrollFiles", ActionParameter::VirtualScroll((_x, y), (_width, height))) => {
                 // Get the position of the files
@@ -600,14 +631,21 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                     // Create a new controller for the file
                     let shared_state    = self.open_file_store.open_shared(path.as_path());
                     let instance_state  = shared_state.new_instance();
-                    let new_controller  = Chooser::Controller::open(instance_state);
+                    let new_controller  = self.chooser.open_file(instance_state);
                     let new_controller  = Arc::new(new_controller);
 
                     // Set as the main controller
-                    *self.model.shared_state.lock().unwrap() = Some(shared_state);
+                    let old_state       = self.model.shared_state.lock().unwrap().replace(shared_state);
+                    let old_path        = self.model.open_file.get();
                     self.model.open_file.set(Some(path));
                     self.model.active_controller.set(Some(new_controller));
 
+                    // Release any file that was previously open
+                    if let (Some(old_state), Some(old_path)) = (old_state, old_path) {
+                        drop(old_state);
+                        self.open_file_store.close_shared(old_path.as_path());
+                    }
+
                 } else if action.starts_with("SetSelect-") {
 
                     if let ActionParameter::Value(PropertyValue::Bool(is_selected)) = action_parameter {
@@ -718,3 +756,16 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
         None
     }
 }
+
+impl<Chooser: FileChooser> Drop for FileChooserController<Chooser> {
+    fn drop(&mut self) {
+        // Release the file that this controller has open, so it's closed once no other sessions are using it
+        let shared_state    = self.model.shared_state.lock().unwrap().take();
+        let open_file       = self.model.open_file.get();
+
+        if let (Some(shared_state), Some(open_file)) = (shared_state, open_file) {
+            drop(shared_state);
+            self.open_file_store.close_shared(open_file.as_path());
+        }
+    }
+}
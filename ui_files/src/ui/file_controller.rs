@@ -7,7 +7,7 @@ use flo_ui::*;
 ///
 pub trait FileController : Controller+PartialEq {
     /// The model that this controller needs to be constructed
-    type Model: FileModel;
+    type Model: 'static+FileModel;
 
     ///
     /// Creates this controller with the specified instance model
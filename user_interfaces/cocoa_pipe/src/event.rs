@@ -2,6 +2,18 @@ use super::action::*;
 
 use flo_ui::*;
 
+/// NSEventModifierFlagShift
+const MODIFIER_FLAG_SHIFT: u64      = 1<<17;
+
+/// NSEventModifierFlagControl
+const MODIFIER_FLAG_CONTROL: u64    = 1<<18;
+
+/// NSEventModifierFlagOption
+const MODIFIER_FLAG_OPTION: u64     = 1<<19;
+
+/// NSEventModifierFlagCommand
+const MODIFIER_FLAG_COMMAND: u64    = 1<<20;
+
 ///
 /// Data provided by a point during a painting action
 ///
@@ -12,12 +24,15 @@ use flo_ui::*;
     pub position_y: f64,
     pub pressure:   f64,
     pub tilt_x:     f64,
-    pub tilt_y:     f64
+    pub tilt_y:     f64,
+
+    /// The NSEvent modifier flags for the event that generated this painting action
+    pub modifier_flags: u64
 }
 
 unsafe impl objc::Encode for AppPainting {
     fn encode() -> objc::Encoding {
-        unsafe { objc::Encoding::from_str("{AppPainting=iddddd}") }
+        unsafe { objc::Encoding::from_str("{AppPainting=idddddQ}") }
     }
 }
 
@@ -89,7 +104,23 @@ impl AppPainting {
             location:   (self.position_x as f32, self.position_y as f32),
             pressure:   self.pressure as f32,
             tilt_x:     self.tilt_x as f32,
-            tilt_y:     self.tilt_y as f32
+            tilt_y:     self.tilt_y as f32,
+            modifiers:  self.modifiers()
+        }
+    }
+
+    ///
+    /// Reads the modifier keys that were held down from the NSEvent modifier flags
+    ///
+    /// The command key acts as the control key, as it's the usual modifier key on OS X.
+    ///
+    pub fn modifiers(&self) -> PaintModifiers {
+        let flags = self.modifier_flags;
+
+        PaintModifiers {
+            shift:      (flags & MODIFIER_FLAG_SHIFT) != 0,
+            control:    (flags & (MODIFIER_FLAG_CONTROL | MODIFIER_FLAG_COMMAND)) != 0,
+            alt:        (flags & MODIFIER_FLAG_OPTION) != 0
         }
     }
 }
@@ -4,6 +4,9 @@ use flo_canvas::*;
 
 use objc::rc::*;
 
+use std::ptr;
+use std::os::raw::c_void;
+
 ///
 /// Possible actions stored in the path for this state
 ///
@@ -23,6 +26,8 @@ struct CanvasStateValues {
     sprite:             Option<SpriteId>,
     color_space:        CFRef<CGColorSpaceRef>,
     fill_color:         CFRef<CGColorRef>,
+    fill_style:         FillStyle,
+    gradient_stops:     Vec<(f32, Color)>,
     stroke_color:       CFRef<CGColorRef>,
     transform:          CGAffineTransform,
     blend_mode:         CGBlendMode,
@@ -62,6 +67,8 @@ impl CanvasState {
                     sprite:             None,
                     color_space:        color_space,
                     fill_color:         fill_color,
+                    fill_style:         FillStyle::Solid(Color::Rgba(0.0, 0.0, 0.0, 0.0)),
+                    gradient_stops:     vec![],
                     stroke_color:       stroke_color,
                     transform:          transform,
                     blend_mode:         CGBlendMode::Normal,
@@ -301,6 +308,7 @@ impl CanvasState {
 
             // Store it in this object
             self.values.fill_color  = new_color;
+            self.values.fill_style  = FillStyle::Solid(*new_fill_color);
 
             // Set in the context
             if let Some(ref context) = self.context {
@@ -309,6 +317,126 @@ impl CanvasState {
         }
     }
 
+    ///
+    /// Clears the colour stops for the next gradient fill
+    ///
+    pub fn new_gradient(&mut self) {
+        self.values.gradient_stops = vec![];
+    }
+
+    ///
+    /// Adds a colour stop for the next gradient fill
+    ///
+    pub fn add_gradient_stop(&mut self, pos: f32, color: &Color) {
+        self.values.gradient_stops.push((pos, *color));
+    }
+
+    ///
+    /// Fills future paths with a linear gradient using the current gradient stops
+    ///
+    pub fn set_fill_linear_gradient(&mut self, start: (f32, f32), end: (f32, f32)) {
+        self.values.fill_style = FillStyle::LinearGradient(self.values.gradient_stops.clone(), start, end);
+    }
+
+    ///
+    /// Fills future paths with a radial gradient using the current gradient stops
+    ///
+    pub fn set_fill_radial_gradient(&mut self, center: (f32, f32), radius: f32) {
+        self.values.fill_style = FillStyle::RadialGradient(self.values.gradient_stops.clone(), center, radius);
+    }
+
+    ///
+    /// Fills future paths with a repeating texture
+    ///
+    pub fn set_fill_texture(&mut self, pattern: TexturePattern, foreground: &Color, background: &Color, size: f32) {
+        self.values.fill_style = FillStyle::Texture(pattern, *foreground, *background, size);
+    }
+
+    ///
+    /// Creates a CGGradient from a set of gradient stops
+    ///
+    unsafe fn create_gradient(&self, stops: &Vec<(f32, Color)>) -> CFRef<CGGradientRef> {
+        let mut stops = stops.clone();
+        stops.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let locations   = stops.iter().map(|(pos, _)| *pos as CGFloat).collect::<Vec<_>>();
+        let components  = stops.iter()
+            .flat_map(|(_, color)| { let (r, g, b, a) = color.to_rgba_components(); vec![r as CGFloat, g as CGFloat, b as CGFloat, a as CGFloat] })
+            .collect::<Vec<_>>();
+
+        CFRef::from(CGGradientCreateWithColorComponents(*self.values.color_space, components.as_ptr(), locations.as_ptr(), stops.len()))
+    }
+
+    ///
+    /// Creates a CGPattern that draws a texture with tiles of the specified size in canvas coordinates
+    ///
+    unsafe fn create_texture(&self, pattern: TexturePattern, foreground: Color, background: Color, size: f32) -> CFRef<CGPatternRef> {
+        // The pattern draws a single tile in a unit square, which is scaled up to the tile size. Pattern space is relative to
+        // the base space of the context rather than the current transform, so the canvas transform is applied here too
+        let size        = (size as CGFloat).max(0.001);
+        let matrix      = CGAffineTransformConcat(CGAffineTransformScale(CGAffineTransformIdentity, size, size), self.values.transform);
+        let bounds      = CGRect { origin: CGPoint { x: 0.0, y: 0.0 }, size: CGSize { width: 1.0, height: 1.0 } };
+        let callbacks   = CGPatternCallbacks { version: 0, draw_pattern: Some(draw_texture_tile), release_info: Some(release_texture_tile) };
+        let info        = Box::new((pattern, foreground, background));
+
+        CFRef::from(CGPatternCreate(Box::into_raw(info) as *mut c_void, bounds, matrix, 1.0, 1.0, CGPatternTiling::ConstantSpacing, true, &callbacks))
+    }
+
+    ///
+    /// Fills the current path using the current fill style
+    ///
+    pub fn fill(&self) {
+        unsafe {
+            if let Some(ref context) = self.context {
+                let context         = **context;
+                let gradient_fill   = kCGGradientDrawsBeforeStartLocation | kCGGradientDrawsAfterEndLocation;
+
+                match &self.values.fill_style {
+                    FillStyle::Solid(_)                                     => {
+                        self.load_path();
+                        CGContextFillPath(context);
+                    }
+
+                    FillStyle::LinearGradient(stops, (x1, y1), (x2, y2))    => {
+                        // Gradients are drawn by clipping to the path
+                        let gradient = self.create_gradient(stops);
+
+                        CGContextSaveGState(context);
+                        self.load_path();
+                        CGContextClip(context);
+                        CGContextDrawLinearGradient(context, *gradient, CGPoint { x: *x1 as CGFloat, y: *y1 as CGFloat }, CGPoint { x: *x2 as CGFloat, y: *y2 as CGFloat }, gradient_fill);
+                        CGContextRestoreGState(context);
+                    }
+
+                    FillStyle::RadialGradient(stops, (x, y), radius)        => {
+                        let gradient    = self.create_gradient(stops);
+                        let center      = CGPoint { x: *x as CGFloat, y: *y as CGFloat };
+
+                        CGContextSaveGState(context);
+                        self.load_path();
+                        CGContextClip(context);
+                        CGContextDrawRadialGradient(context, *gradient, center, 0.0, center, *radius as CGFloat, gradient_fill);
+                        CGContextRestoreGState(context);
+                    }
+
+                    FillStyle::Texture(pattern, fg, bg, size)               => {
+                        // Textures are drawn by setting a pattern colour
+                        let texture         = self.create_texture(*pattern, *fg, *bg, *size);
+                        let pattern_space   = CFRef::from(CGColorSpaceCreatePattern(ptr::null_mut()));
+                        let alpha           = [1.0 as CGFloat];
+
+                        CGContextSaveGState(context);
+                        CGContextSetFillColorSpace(context, *pattern_space);
+                        CGContextSetFillPattern(context, *texture, alpha.as_ptr());
+                        self.load_path();
+                        CGContextFillPath(context);
+                        CGContextRestoreGState(context);
+                    }
+                }
+            }
+        }
+    }
+
     ///
     /// Sets the stroke color of this state
     ///
@@ -497,6 +625,65 @@ impl CanvasState {
     }
 }
 
+///
+/// Draws a single tile of a texture fill into a unit square (matching the definitions in TexturePattern::is_foreground)
+///
+unsafe extern "C" fn draw_texture_tile(info: *mut c_void, context: CGContextRef) {
+    let (pattern, foreground, background) = *(info as *const (TexturePattern, Color, Color));
+
+    // Fill with the background colour
+    let (r, g, b, a) = background.to_rgba_components();
+    CGContextSetRGBFillColor(context, r as CGFloat, g as CGFloat, b as CGFloat, a as CGFloat);
+    CGContextFillRect(context, CGRect { origin: CGPoint { x: 0.0, y: 0.0 }, size: CGSize { width: 1.0, height: 1.0 } });
+
+    // Draw the foreground shapes
+    let (r, g, b, a) = foreground.to_rgba_components();
+    CGContextSetRGBFillColor(context, r as CGFloat, g as CGFloat, b as CGFloat, a as CGFloat);
+    CGContextBeginPath(context);
+
+    match pattern {
+        TexturePattern::Stripes => {
+            CGContextMoveToPoint(context, 0.0, 0.0);
+            CGContextAddLineToPoint(context, 0.5, 0.0);
+            CGContextAddLineToPoint(context, 0.0, 0.5);
+            CGContextClosePath(context);
+
+            CGContextMoveToPoint(context, 1.0, 0.0);
+            CGContextAddLineToPoint(context, 1.0, 0.5);
+            CGContextAddLineToPoint(context, 0.5, 1.0);
+            CGContextAddLineToPoint(context, 0.0, 1.0);
+            CGContextClosePath(context);
+        }
+
+        TexturePattern::Checks  => {
+            CGContextMoveToPoint(context, 0.0, 0.0);
+            CGContextAddLineToPoint(context, 0.5, 0.0);
+            CGContextAddLineToPoint(context, 0.5, 0.5);
+            CGContextAddLineToPoint(context, 0.0, 0.5);
+            CGContextClosePath(context);
+
+            CGContextMoveToPoint(context, 0.5, 0.5);
+            CGContextAddLineToPoint(context, 1.0, 0.5);
+            CGContextAddLineToPoint(context, 1.0, 1.0);
+            CGContextAddLineToPoint(context, 0.5, 1.0);
+            CGContextClosePath(context);
+        }
+
+        TexturePattern::Dots    => {
+            CGContextAddEllipseInRect(context, CGRect { origin: CGPoint { x: 0.25, y: 0.25 }, size: CGSize { width: 0.5, height: 0.5 } });
+        }
+    }
+
+    CGContextFillPath(context);
+}
+
+///
+/// Frees the texture description passed to a CGPattern
+///
+unsafe extern "C" fn release_texture_tile(info: *mut c_void) {
+    drop(Box::from_raw(info as *mut (TexturePattern, Color, Color)));
+}
+
 impl From<BlendMode> for CGBlendMode {
     fn from(blendmode: BlendMode) -> CGBlendMode {
         match blendmode {
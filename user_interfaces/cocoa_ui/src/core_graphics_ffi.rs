@@ -1,6 +1,7 @@
 //! FFI for core graphics functions
 #[cfg(target_pointer_width = "32")] use std::os::raw::c_float;
 #[cfg(target_pointer_width = "64")] use std::os::raw::c_double;
+use std::os::raw::{c_void, c_uint};
 use std::ops::Deref;
 
 #[cfg(target_pointer_width = "64")] pub type CGFloat = c_double;
@@ -21,6 +22,16 @@ pub type CGColorRef = *mut CGColor;
 #[repr(C)] pub struct CGMutablePath { _private: [u8; 0] }
 pub type CGMutablePathRef = *mut CGMutablePath;
 
+#[repr(C)] pub struct CGGradient { _private: [u8; 0] }
+pub type CGGradientRef = *mut CGGradient;
+
+#[repr(C)] pub struct CGPattern { _private: [u8; 0] }
+pub type CGPatternRef = *mut CGPattern;
+
+pub type CGGradientDrawingOptions = u32;
+#[allow(non_upper_case_globals)] pub const kCGGradientDrawsBeforeStartLocation: CGGradientDrawingOptions = 1;
+#[allow(non_upper_case_globals)] pub const kCGGradientDrawsAfterEndLocation: CGGradientDrawingOptions    = 2;
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)] pub struct CGAffineTransform {
     pub a: CGFloat,
@@ -82,6 +93,19 @@ pub type CGMutablePathRef = *mut CGMutablePath;
     PlusLighter
 }
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)] pub enum CGPatternTiling {
+    NoDistortion,
+    ConstantSpacingMinimalDistortion,
+    ConstantSpacing
+}
+
+#[repr(C)] pub struct CGPatternCallbacks {
+    pub version:        c_uint,
+    pub draw_pattern:   Option<unsafe extern "C" fn(info: *mut c_void, ctxt: CGContextRef)>,
+    pub release_info:   Option<unsafe extern "C" fn(info: *mut c_void)>
+}
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)] pub enum CGLineJoin {
     Miter,
@@ -134,8 +158,17 @@ extern {
 
     pub fn CGColorRetain(color: CGColorRef);
     pub fn CGColorRelease(color: CGColorRef);
+    pub fn CGColorSpaceCreatePattern(base_space: CGColorSpaceRef) -> CGColorSpaceRef;
     pub fn CGColorCreate(colorspace: CGColorSpaceRef, components: *const CGFloat) -> CGColorRef;
 
+    pub fn CGGradientRetain(gradient: CGGradientRef);
+    pub fn CGGradientRelease(gradient: CGGradientRef);
+    pub fn CGGradientCreateWithColorComponents(colorspace: CGColorSpaceRef, components: *const CGFloat, locations: *const CGFloat, count: usize) -> CGGradientRef;
+
+    pub fn CGPatternRetain(pattern: CGPatternRef);
+    pub fn CGPatternRelease(pattern: CGPatternRef);
+    pub fn CGPatternCreate(info: *mut c_void, bounds: CGRect, matrix: CGAffineTransform, x_step: CGFloat, y_step: CGFloat, tiling: CGPatternTiling, is_colored: bool, callbacks: *const CGPatternCallbacks) -> CGPatternRef;
+
     pub fn CGAffineTransformTranslate(t: CGAffineTransform, tx: CGFloat, ty: CGFloat) -> CGAffineTransform;
     pub fn CGAffineTransformScale(t: CGAffineTransform, sx: CGFloat, sy: CGFloat) -> CGAffineTransform;
     pub fn CGAffineTransformRotate(t: CGAffineTransform, angle: CGFloat) -> CGAffineTransform;
@@ -168,6 +201,13 @@ extern {
     pub fn CGContextSetLineCap(ctxt: CGContextRef, cap: CGLineCap);
     pub fn CGContextSetFillColorWithColor(ctxt: CGContextRef, color: CGColorRef);
     pub fn CGContextSetStrokeColorWithColor(ctxt: CGContextRef, color: CGColorRef);
+    pub fn CGContextSetRGBFillColor(ctxt: CGContextRef, red: CGFloat, green: CGFloat, blue: CGFloat, alpha: CGFloat);
+    pub fn CGContextSetFillColorSpace(ctxt: CGContextRef, space: CGColorSpaceRef);
+    pub fn CGContextSetFillPattern(ctxt: CGContextRef, pattern: CGPatternRef, components: *const CGFloat);
+    pub fn CGContextFillRect(ctxt: CGContextRef, rect: CGRect);
+    pub fn CGContextAddEllipseInRect(ctxt: CGContextRef, rect: CGRect);
+    pub fn CGContextDrawLinearGradient(ctxt: CGContextRef, gradient: CGGradientRef, start_point: CGPoint, end_point: CGPoint, options: CGGradientDrawingOptions);
+    pub fn CGContextDrawRadialGradient(ctxt: CGContextRef, gradient: CGGradientRef, start_center: CGPoint, start_radius: CGFloat, end_center: CGPoint, end_radius: CGFloat, options: CGGradientDrawingOptions);
     pub fn CGContextConcatCTM(ctxt: CGContextRef, transform: CGAffineTransform);
     pub fn CGContextGetCTM(ctxt: CGContextRef) -> CGAffineTransform;
     pub fn CGContextSetBlendMode(ctxt: CGContextRef, blendMode: CGBlendMode);
@@ -225,6 +265,28 @@ impl CFReleasable for CGMutablePathRef {
     }
 }
 
+impl CFReleasable for CGGradientRef {
+    #[inline] fn retain(&self) -> Self {
+        unsafe { CGGradientRetain(*self); }
+        *self
+    }
+
+    #[inline] fn release(&self) {
+        unsafe { CGGradientRelease(*self); }
+    }
+}
+
+impl CFReleasable for CGPatternRef {
+    #[inline] fn retain(&self) -> Self {
+        unsafe { CGPatternRetain(*self); }
+        *self
+    }
+
+    #[inline] fn release(&self) {
+        unsafe { CGPatternRelease(*self); }
+    }
+}
+
 pub struct CFRef<T: CFReleasable>(T);
 
 impl<T: CFReleasable> Clone for CFRef<T> {
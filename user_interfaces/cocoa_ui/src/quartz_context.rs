@@ -130,7 +130,7 @@ impl QuartzContext {
                 Line(x, y)                                          => { self.state.path_line(*x as CGFloat, *y as CGFloat); }
                 BezierCurve((ex, ey), (c1x, c1y), (c2x, c2y))       => { self.state.path_bezier_curve((*c1x as CGFloat, *c1y as CGFloat), (*c2x as CGFloat, *c2y as CGFloat), (*ex as CGFloat, *ey as CGFloat)); }
                 ClosePath                                           => { self.state.path_close(); }
-                Fill                                                => { self.state.load_path(); CGContextFillPath(*self.context); }
+                Fill                                                => { self.state.fill(); }
                 Stroke                                              => { self.state.load_path(); CGContextStrokePath(*self.context); }
                 LineWidth(width)                                    => { self.state.set_line_width(*width as CGFloat); }
                 LineWidthPixels(width_pixels)                       => {
@@ -149,6 +149,11 @@ impl QuartzContext {
                 DashOffset(offset)                                  => { /* TODO */ }
                 FillColor(col)                                      => { self.state.set_fill_color(col); }
                 StrokeColor(col)                                    => { self.state.set_stroke_color(col); }
+                NewGradient                                         => { self.state.new_gradient(); }
+                GradientStop(pos, col)                              => { self.state.add_gradient_stop(*pos, col); }
+                FillLinearGradient(start, end)                      => { self.state.set_fill_linear_gradient(*start, *end); }
+                FillRadialGradient(center, radius)                  => { self.state.set_fill_radial_gradient(*center, *radius); }
+                FillTexture(pattern, fg, bg, size)                  => { self.state.set_fill_texture(*pattern, fg, bg, *size); }
                 BlendMode(blend)                                    => { self.state.set_blend_mode(blend); }
                 Unclip                                              => { self.state.unclip(); }
                 Clip                                                => { self.state.clip(); }
@@ -206,6 +211,7 @@ impl QuartzContext {
                 }
                 Layer(_layer_id)                                    => { /* Layers need to be implemented elsewhere */ }
                 LayerBlend(_layer_id, _blend)                       => { /* Layers need to be implemented elsewhere */ }
+                LayerAlpha(_layer_id, _alpha)                       => { /* Layers need to be implemented elsewhere */ }
                 Sprite(_sprite_id)                                  => { unimplemented!() }
                 SpriteTransform(_transform)                         => { unimplemented!() }
                 ClearSprite                                         => { unimplemented!() }
@@ -458,8 +458,12 @@ impl CocoaSession {
         let update_layer    = move |layer_id, layer_obj: StrongPtr| { unsafe { let _: () = msg_send!(*view, viewUpdateCache: *layer_obj fromLayerWithId: layer_id); } };
         let view            = view_src.clone();
         let restore_layer   = move |layer_id, layer_obj: StrongPtr| { unsafe { let _: () = msg_send!(*view, viewRestoreLayerTo: layer_id fromCopy: *layer_obj); } };
+        let view            = view_src.clone();
+        let layer_alpha     = move |layer_id: u32, alpha: f64| { unsafe { let _: () = msg_send!(*view, viewSetLayerAlpha: layer_id alpha: alpha); } };
+        let view            = view_src.clone();
+        let layer_blend     = move |layer_id: u32, blend_mode: CGBlendMode| { unsafe { let _: () = msg_send!(*view, viewSetLayerBlend: layer_id blendMode: blend_mode as i32); } };
 
-        ViewCanvas::new(clear_canvas, copy_layer, update_layer, restore_layer)
+        ViewCanvas::new(clear_canvas, copy_layer, update_layer, restore_layer, layer_alpha, layer_blend)
     }
 
     ///
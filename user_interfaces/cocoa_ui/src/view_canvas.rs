@@ -37,6 +37,12 @@ pub struct ViewCanvas {
     /// Callback function to restore the state of a layer from a copy created previously with copy_layer
     restore_layer: Box<dyn FnMut(u32, StrongPtr) -> ()>,
 
+    /// Callback function to set the alpha value used when compositing a layer
+    set_layer_alpha: Box<dyn FnMut(u32, f64) -> ()>,
+
+    /// Callback function to set the blend mode used when compositing a layer
+    set_layer_blend: Box<dyn FnMut(u32, CGBlendMode) -> ()>,
+
     /// Sprites defined for the canvas
     sprites: HashMap<SpriteId, Vec<Draw>>
 
@@ -46,21 +52,25 @@ impl ViewCanvas {
     ///
     /// Creates a new canvas for a view
     ///
-    pub fn new<ClearCanvasFn, CopyLayerFn, UpdateLayerFn, RestoreLayerFn>(clear_canvas: ClearCanvasFn, copy_layer: CopyLayerFn, update_layer: UpdateLayerFn, restore_layer: RestoreLayerFn) -> ViewCanvas
+    pub fn new<ClearCanvasFn, CopyLayerFn, UpdateLayerFn, RestoreLayerFn, LayerAlphaFn, LayerBlendFn>(clear_canvas: ClearCanvasFn, copy_layer: CopyLayerFn, update_layer: UpdateLayerFn, restore_layer: RestoreLayerFn, set_layer_alpha: LayerAlphaFn, set_layer_blend: LayerBlendFn) -> ViewCanvas
     where   ClearCanvasFn:  'static+FnMut() -> (),
             CopyLayerFn:    'static+FnMut(u32) -> StrongPtr,
             UpdateLayerFn:  'static+FnMut(u32, StrongPtr) -> (),
-            RestoreLayerFn: 'static+FnMut(u32, StrongPtr) -> () {
+            RestoreLayerFn: 'static+FnMut(u32, StrongPtr) -> (),
+            LayerAlphaFn:   'static+FnMut(u32, f64) -> (),
+            LayerBlendFn:   'static+FnMut(u32, CGBlendMode) -> () {
         ViewCanvas {
-            canvas:         Canvas::new(),
-            size:           CGSize { width: 1.0, height: 1.0 },
-            visible:        CGRect { origin: CGPoint { x: 0.0, y: 0.0 }, size: CGSize { width: 1.0, height: 1.0 } },
-            state:          None,
-            clear_canvas:   Box::new(clear_canvas),
-            copy_layer:     Box::new(copy_layer),
-            update_layer:   Box::new(update_layer),
-            restore_layer:  Box::new(restore_layer),
-            sprites:        HashMap::new()
+            canvas:             Canvas::new(),
+            size:               CGSize { width: 1.0, height: 1.0 },
+            visible:            CGRect { origin: CGPoint { x: 0.0, y: 0.0 }, size: CGSize { width: 1.0, height: 1.0 } },
+            state:              None,
+            clear_canvas:       Box::new(clear_canvas),
+            copy_layer:         Box::new(copy_layer),
+            update_layer:       Box::new(update_layer),
+            restore_layer:      Box::new(restore_layer),
+            set_layer_alpha:    Box::new(set_layer_alpha),
+            set_layer_blend:    Box::new(set_layer_blend),
+            sprites:            HashMap::new()
         }
     }
 
@@ -167,6 +177,16 @@ impl ViewCanvas {
                     context.draw(&Draw::Layer(new_layer_id));
                 },
 
+                LayerAlpha(layer_id, alpha) => {
+                    // Layers are composited by the view, which will apply the alpha value when it draws the layer
+                    (self.set_layer_alpha)(layer_id, (alpha as f64).max(0.0).min(1.0));
+                },
+
+                LayerBlend(layer_id, blend_mode) => {
+                    // Layers are composited by the view, which will apply the blend mode when it draws the layer
+                    (self.set_layer_blend)(layer_id, CGBlendMode::from(blend_mode));
+                },
+
                 Sprite(new_sprite_id) => {
                     self.sprites.entry(new_sprite_id).or_insert_with(|| vec![]);
                     context.get_state().set_sprite(Some(new_sprite_id));
@@ -6,6 +6,9 @@ use flo_canvas::*;
 use cairo;
 use cairo::*;
 
+/// The size in pixels of the surface used to render a single tile of a texture fill
+const TEXTURE_TILE_PIXELS: i32 = 32;
+
 ///
 /// The current source colour that's set
 ///
@@ -22,7 +25,8 @@ enum ColorTarget {
 struct SavedState {
     dash_pattern:   Vec<f64>,
     stroke_color:   Color,
-    fill_color:     Color
+    fill_style:     FillStyle,
+    gradient_stops: Vec<(f32, Color)>
 }
 
 impl SavedState {
@@ -33,7 +37,8 @@ impl SavedState {
         SavedState {
             dash_pattern:   drawing.dash_pattern.clone(),
             stroke_color:   drawing.stroke_color.clone(),
-            fill_color:     drawing.fill_color.clone()
+            fill_style:     drawing.fill_style.clone(),
+            gradient_stops: drawing.gradient_stops.clone()
         }
     }
 
@@ -43,7 +48,8 @@ impl SavedState {
     pub fn restore(self, drawing: &mut CairoDraw) {
         drawing.dash_pattern    = self.dash_pattern;
         drawing.stroke_color    = self.stroke_color;
-        drawing.fill_color      = self.fill_color;
+        drawing.fill_style      = self.fill_style;
+        drawing.gradient_stops  = self.gradient_stops;
         drawing.set_color       = ColorTarget::None;
     }
 }
@@ -56,7 +62,8 @@ pub struct CairoState {
     line_width:     f64,
     line_join:      cairo::LineJoin,
     line_cap:       cairo::LineCap,
-    fill_color:     Color,
+    fill_style:     FillStyle,
+    gradient_stops: Vec<(f32, Color)>,
     stroke_color:   Color,
     dash_pattern:   Vec<f64>
 }
@@ -80,8 +87,11 @@ pub struct CairoDraw {
     /// The current stroke colour
     stroke_color: Color,
 
-    /// The current fill colour
-    fill_color: Color,
+    /// How paths are currently filled
+    fill_style: FillStyle,
+
+    /// The colour stops for the next gradient fill
+    gradient_stops: Vec<(f32, Color)>,
 
     /// The colour that's currently set
     set_color: ColorTarget,
@@ -115,7 +125,8 @@ impl CairoDraw {
             saved_states:   vec![],
             dash_pattern:   vec![],
             stroke_color:   Color::Rgba(0.0, 0.0, 0.0, 1.0),
-            fill_color:     Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            fill_style:     FillStyle::Solid(Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            gradient_stops: vec![],
             set_color:      ColorTarget::None,
             initial_matrix: Matrix::from(&viewport),
             viewport:       viewport
@@ -149,13 +160,25 @@ impl CairoDraw {
     ///
     #[inline]
     fn set_color(&mut self, target: ColorTarget) {
+        // Gradients and textures are positioned relative to the transform in effect when the source is set, so are always set again
+        if target == ColorTarget::Fill {
+            match self.fill_style {
+                FillStyle::Solid(_) => { }
+                _                   => {
+                    self.set_fill_pattern();
+                    self.set_color = ColorTarget::None;
+                    return;
+                }
+            }
+        }
+
         // Only change the colour if it's not already set
         if self.set_color != target {
             // Get the RGBA components for this target
             let (r, g, b, a) = {
                 match target {
                     ColorTarget::None   => (0.0, 0.0, 0.0, 1.0),
-                    ColorTarget::Fill   => self.fill_color.to_rgba_components(),
+                    ColorTarget::Fill   => self.fill_style.color_at((0.0, 0.0)).to_rgba_components(),
                     ColorTarget::Stroke => self.stroke_color.to_rgba_components()
                 }
             };
@@ -168,10 +191,108 @@ impl CairoDraw {
         }
     }
 
+    ///
+    /// Adds the stops for a gradient fill to a Cairo gradient
+    ///
+    fn add_color_stops(gradient: &Gradient, stops: &Vec<(f32, Color)>) {
+        let mut stops = stops.clone();
+        stops.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        for (pos, color) in stops {
+            let (r, g, b, a) = color.to_rgba_components();
+            gradient.add_color_stop_rgba(pos as f64, r as f64, g as f64, b as f64, a as f64);
+        }
+    }
+
+    ///
+    /// Creates a surface containing a single tile of a texture pattern
+    ///
+    fn create_texture_tile(pattern: TexturePattern, foreground: Color, background: Color) -> Option<ImageSurface> {
+        let tile    = ImageSurface::create(Format::ARgb32, TEXTURE_TILE_PIXELS, TEXTURE_TILE_PIXELS).ok()?;
+        let ctxt    = Context::new(&tile);
+        let size    = TEXTURE_TILE_PIXELS as f64;
+        let half    = size / 2.0;
+
+        // Fill with the background colour
+        let (r, g, b, a) = background.to_rgba_components();
+        ctxt.set_source_rgba(r as f64, g as f64, b as f64, a as f64);
+        ctxt.paint();
+
+        // Draw the foreground shapes (matching the definitions in TexturePattern::is_foreground)
+        let (r, g, b, a) = foreground.to_rgba_components();
+        ctxt.set_source_rgba(r as f64, g as f64, b as f64, a as f64);
+
+        match pattern {
+            TexturePattern::Stripes => {
+                ctxt.move_to(0.0, 0.0);
+                ctxt.line_to(half, 0.0);
+                ctxt.line_to(0.0, half);
+                ctxt.close_path();
+
+                ctxt.move_to(size, 0.0);
+                ctxt.line_to(size, half);
+                ctxt.line_to(half, size);
+                ctxt.line_to(0.0, size);
+                ctxt.close_path();
+            }
+
+            TexturePattern::Checks  => {
+                ctxt.rectangle(0.0, 0.0, half, half);
+                ctxt.rectangle(half, half, half, half);
+            }
+
+            TexturePattern::Dots    => {
+                ctxt.arc(half, half, size / 4.0, 0.0, 2.0 * std::f64::consts::PI);
+            }
+        }
+
+        ctxt.fill();
+
+        Some(tile)
+    }
+
+    ///
+    /// Sets the source of the context to the current gradient or texture fill
+    ///
+    fn set_fill_pattern(&self) {
+        match &self.fill_style {
+            FillStyle::Solid(color)                                 => {
+                let (r, g, b, a) = color.to_rgba_components();
+                self.ctxt.set_source_rgba(r as f64, g as f64, b as f64, a as f64);
+            }
+
+            FillStyle::LinearGradient(stops, (x1, y1), (x2, y2))    => {
+                let gradient = LinearGradient::new(*x1 as f64, *y1 as f64, *x2 as f64, *y2 as f64);
+                Self::add_color_stops(&gradient, stops);
+                self.ctxt.set_source(&gradient);
+            }
+
+            FillStyle::RadialGradient(stops, (x, y), radius)        => {
+                let gradient = RadialGradient::new(*x as f64, *y as f64, 0.0, *x as f64, *y as f64, *radius as f64);
+                Self::add_color_stops(&gradient, stops);
+                self.ctxt.set_source(&gradient);
+            }
+
+            FillStyle::Texture(pattern, fg, bg, size)               => {
+                if let Some(tile) = Self::create_texture_tile(*pattern, *fg, *bg) {
+                    // The pattern matrix maps from user space to the tile's pixels
+                    let texture     = SurfacePattern::create(&tile);
+                    let scale       = (TEXTURE_TILE_PIXELS as f64) / (size.max(0.001) as f64);
+                    let mut matrix  = Matrix::identity();
+                    matrix.scale(scale, scale);
+
+                    texture.set_extend(Extend::Repeat);
+                    texture.set_matrix(matrix);
+                    self.ctxt.set_source(&texture);
+                }
+            }
+        }
+    }
+
     ///
     /// Converts a blend mode into an operator
     ///
-    fn get_operator(blend: flo::BlendMode) -> cairo::Operator {
+    pub fn get_operator(blend: flo::BlendMode) -> cairo::Operator {
         match blend {
             flo::BlendMode::SourceOver      => cairo::Operator::Over,
             flo::BlendMode::SourceIn        => cairo::Operator::In,
@@ -286,7 +407,8 @@ impl CairoDraw {
         let line_width      = self.ctxt.get_line_width();
         let line_join       = self.ctxt.get_line_join();
         let line_cap        = self.ctxt.get_line_cap();
-        let fill_color      = self.fill_color;
+        let fill_style      = self.fill_style.clone();
+        let gradient_stops  = self.gradient_stops.clone();
         let stroke_color    = self.stroke_color;
         let dash_pattern    = self.dash_pattern.clone();
 
@@ -295,7 +417,8 @@ impl CairoDraw {
             line_width,
             line_join,
             line_cap,
-            fill_color,
+            fill_style,
+            gradient_stops,
             stroke_color,
             dash_pattern
         }
@@ -309,7 +432,8 @@ impl CairoDraw {
         self.ctxt.set_line_width(state.line_width);
         self.ctxt.set_line_join(state.line_join);
         self.ctxt.set_line_cap(state.line_cap);
-        self.fill_color     = state.fill_color;
+        self.fill_style     = state.fill_style.clone();
+        self.gradient_stops = state.gradient_stops.clone();
         self.stroke_color   = state.stroke_color;
         self.dash_pattern   = state.dash_pattern.clone();
         self.set_color      = ColorTarget::None;
@@ -336,7 +460,12 @@ impl CairoDraw {
             NewDashPattern                              => { self.dash_pattern = vec![]; self.ctxt.set_dash(&[], 0.0); },
             DashLength(length)                          => { self.dash_pattern.push(length as f64); self.ctxt.set_dash(&self.dash_pattern, self.ctxt.get_dash_offset()); },
             DashOffset(offset)                          => { self.ctxt.set_dash(&self.dash_pattern, offset as f64); },
-            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_color = color; },
+            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_style = FillStyle::Solid(color); },
+            NewGradient                                 => { self.gradient_stops = vec![]; },
+            GradientStop(pos, color)                    => { self.gradient_stops.push((pos, color)); },
+            FillLinearGradient(start, end)              => { self.set_color = ColorTarget::None; self.fill_style = FillStyle::LinearGradient(self.gradient_stops.clone(), start, end); },
+            FillRadialGradient(center, radius)          => { self.set_color = ColorTarget::None; self.fill_style = FillStyle::RadialGradient(self.gradient_stops.clone(), center, radius); },
+            FillTexture(pattern, fg, bg, size)          => { self.set_color = ColorTarget::None; self.fill_style = FillStyle::Texture(pattern, fg, bg, size); },
             StrokeColor(color)                          => { self.set_color = ColorTarget::None; self.stroke_color = color; },
             BlendMode(blend)                            => { self.ctxt.set_operator(Self::get_operator(blend)); },
             IdentityTransform                           => { self.ctxt.set_matrix(self.initial_matrix); },
@@ -350,6 +479,7 @@ impl CairoDraw {
             PopState                                    => { self.ctxt.restore(); self.saved_states.pop().map(|state| state.restore(self)); },
             Layer(_layer_id)                            => { /* Layers require external support */ },
             LayerBlend(_layer_id, _mode)                => { /* Layers require external support */ },
+            LayerAlpha(_layer_id, _alpha)               => { /* Layers require external support */ },
 
             CanvasHeight(height)                        => {
                 let transform   = self.initial_matrix.clone();
@@ -375,7 +505,8 @@ impl CairoDraw {
                 self.ctxt.paint();
 
                 // Reset state
-                self.fill_color     = Color::Rgba(0.0, 0.0, 0.0, 1.0);
+                self.fill_style     = FillStyle::Solid(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+                self.gradient_stops = vec![];
                 self.stroke_color   = Color::Rgba(0.0, 0.0, 0.0, 1.0);
                 self.set_color      = ColorTarget::None;
                 self.dash_pattern   = vec![];
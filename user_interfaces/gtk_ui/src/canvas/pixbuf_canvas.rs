@@ -15,6 +15,12 @@ struct Layer {
     /// Stored variation of this surface
     stored: Option<cairo::ImageSurface>,
 
+    /// How this layer is blended with the layers underneath it
+    blend_mode: BlendMode,
+
+    /// The alpha value to apply when this layer is blended with the layers underneath it
+    alpha: f64,
+
     /// Context that this surface will be drawn upon
     context: CairoDraw
 }
@@ -165,6 +171,23 @@ impl PixBufCanvas {
                 self.current_layer = new_layer_id;
             },
 
+            Draw::LayerBlend(layer_id, blend_mode) => {
+                // Layers are composited when the canvas is rendered, so just store the blend mode for later
+                let viewport        = &self.viewport;
+                let pixel_scale     = self.pixel_scale;
+                let layer           = self.layers.entry(layer_id).or_insert_with(|| Self::create_layer(viewport, pixel_scale));
+
+                layer.blend_mode    = blend_mode;
+            },
+
+            Draw::LayerAlpha(layer_id, alpha) => {
+                let viewport        = &self.viewport;
+                let pixel_scale     = self.pixel_scale;
+                let layer           = self.layers.entry(layer_id).or_insert_with(|| Self::create_layer(viewport, pixel_scale));
+
+                layer.alpha         = (alpha as f64).max(0.0).min(1.0);
+            },
+
             Draw::Store             => { let current_layer = self.current_layer; self.save_layer(current_layer); },
             Draw::Restore           => { let current_layer = self.current_layer; self.restore_layer(current_layer); },
             Draw::FreeStoredBuffer  => { let current_layer = self.current_layer; self.clear_storage(current_layer); },
@@ -217,9 +240,9 @@ impl PixBufCanvas {
             let layer_pattern = cairo::SurfacePattern::create(&layer.surface);
             layer_pattern.set_filter(cairo::Filter::Nearest);
 
-            drawable.set_operator(cairo::Operator::Over);
+            drawable.set_operator(CairoDraw::get_operator(layer.blend_mode));
             drawable.set_source(&layer_pattern);
-            drawable.paint();
+            drawable.paint_with_alpha(layer.alpha);
         }
 
         drawable.restore();
@@ -260,7 +283,9 @@ impl PixBufCanvas {
         let new_layer = Layer {
             surface:    surface,
             context:    draw,
-            stored:     None
+            stored:     None,
+            blend_mode: BlendMode::SourceOver,
+            alpha:      1.0
         };
 
         new_layer
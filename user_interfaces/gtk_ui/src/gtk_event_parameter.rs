@@ -57,7 +57,8 @@ pub struct GtkPainting {
     pub position:   (f64, f64),
     pub pressure:   f64,
     pub xtilt:      f64,
-    pub ytilt:      f64
+    pub ytilt:      f64,
+    pub modifiers:  PaintModifiers
 }
 
 impl From<GtkEventParameter> for ActionParameter {
@@ -113,6 +114,17 @@ impl GtkPainting {
         }
     }
 
+    ///
+    /// Reads the modifier keys that are held down from the state of an event
+    ///
+    fn modifiers_from_state(state: gdk::ModifierType) -> PaintModifiers {
+        PaintModifiers {
+            shift:      state.contains(gdk::ModifierType::SHIFT_MASK),
+            control:    state.contains(gdk::ModifierType::CONTROL_MASK),
+            alt:        state.contains(gdk::ModifierType::MOD1_MASK)
+        }
+    }
+
     ///
     /// Transforms this painting event using a matrix
     ///
@@ -129,7 +141,8 @@ impl GtkPainting {
             position: button.get_position(),
             pressure: 1.0,
             xtilt: 0.0,
-            ytilt: 0.0
+            ytilt: 0.0,
+            modifiers: Self::modifiers_from_state(button.get_state())
         };
 
         // Update from the axes available from this device
@@ -151,7 +164,8 @@ impl GtkPainting {
             position: motion.get_position(),
             pressure: 1.0,
             xtilt: 0.0,
-            ytilt: 0.0
+            ytilt: 0.0,
+            modifiers: Self::modifiers_from_state(motion.get_state())
         };
 
         // Update from the axes available from this device
@@ -184,7 +198,8 @@ impl GtkPainting {
             location:   (x as f32, y as f32),
             pressure:   self.pressure as f32,
             tilt_x:     self.xtilt as f32,
-            tilt_y:     self.ytilt as f32
+            tilt_y:     self.ytilt as f32,
+            modifiers:  self.modifiers
         }
     }
 }
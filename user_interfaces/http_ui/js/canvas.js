@@ -152,12 +152,14 @@ let flo_canvas = (function() {
         let inverse_transform           = null;
         let dash_pattern                = [];
         let set_dash_pattern            = true;
+        let gradient_stops              = [];
         let stored_pixels               = document.createElement('canvas');
         let generate_buffer_on_store    = false;
         let have_stored_image           = false;
         let last_store_pos              = null;
         let layer_canvases              = null;
         let blend_for_layer             = {};
+        let alpha_for_layer             = {};
         let current_layer_id            = 0;
         let current_sprite              = [ ];
         let sprites                     = { };
@@ -262,6 +264,65 @@ let flo_canvas = (function() {
             }
         }
 
+        ///
+        /// Converts colour components to a CSS colour
+        ///
+        function css_color(r, g, b, a) {
+            r = Math.floor(r*255.0);
+            g = Math.floor(g*255.0);
+            b = Math.floor(b*255.0);
+
+            return 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')';
+        }
+
+        ///
+        /// Adds the current gradient stops to a canvas gradient
+        ///
+        function add_gradient_stops(gradient) {
+            gradient_stops.forEach(([pos, r, g, b, a]) => {
+                gradient.addColorStop(Math.min(1, Math.max(0, pos)), css_color(r, g, b, a));
+            });
+        }
+
+        ///
+        /// Creates a canvas containing a single tile of a texture pattern (the shapes match TexturePattern::is_foreground)
+        ///
+        function create_texture_tile(pattern, fg, bg) {
+            let size            = 32;
+            let half            = size/2;
+            let tile            = document.createElement('canvas');
+            tile.width          = size;
+            tile.height         = size;
+
+            let tile_context    = tile.getContext('2d');
+
+            tile_context.fillStyle = css_color(bg[0], bg[1], bg[2], bg[3]);
+            tile_context.fillRect(0, 0, size, size);
+
+            tile_context.fillStyle = css_color(fg[0], fg[1], fg[2], fg[3]);
+            tile_context.beginPath();
+
+            switch (pattern) {
+            case 'stripes':
+                tile_context.moveTo(0, 0); tile_context.lineTo(half, 0); tile_context.lineTo(0, half); tile_context.closePath();
+                tile_context.moveTo(size, 0); tile_context.lineTo(size, half); tile_context.lineTo(half, size); tile_context.lineTo(0, size); tile_context.closePath();
+                break;
+
+            case 'checks':
+                tile_context.rect(0, 0, half, half);
+                tile_context.rect(half, half, half, half);
+                break;
+
+            case 'dots':
+                tile_context.arc(half, half, size/4, 0, 2*Math.PI);
+                break;
+            }
+
+            tile_context.fill();
+
+            return tile;
+        }
+
         ///
         /// Creates a new layer
         ///
@@ -303,6 +364,39 @@ let flo_canvas = (function() {
                 context.fillStyle = 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')';
             },
 
+            new_gradient: () => {
+                gradient_stops = [];
+            },
+
+            gradient_stop: (pos, r, g, b, a) => {
+                gradient_stops.push([pos, r, g, b, a]);
+            },
+
+            fill_linear_gradient: (x1, y1, x2, y2) => {
+                // Gradients are positioned using the transform that's in effect when the path is filled
+                let gradient = context.createLinearGradient(x1, y1, x2, y2);
+                add_gradient_stops(gradient);
+                context.fillStyle = gradient;
+            },
+
+            fill_radial_gradient: (x, y, radius) => {
+                let gradient = context.createRadialGradient(x, y, 0, x, y, Math.max(0, radius));
+                add_gradient_stops(gradient);
+                context.fillStyle = gradient;
+            },
+
+            fill_texture: (pattern, fg, bg, size) => {
+                let tile    = create_texture_tile(pattern, fg, bg);
+                let texture = context.createPattern(tile, 'repeat');
+                let scale   = size / tile.width;
+
+                if (texture.setTransform) {
+                    texture.setTransform(new DOMMatrix([scale, 0, 0, scale, 0, 0]));
+                }
+
+                context.fillStyle = texture;
+            },
+
             stroke_color: (r, g, b, a) => {
                 r = Math.floor(r*255.0);
                 g = Math.floor(g*255.0);
@@ -595,6 +689,7 @@ let flo_canvas = (function() {
 
                 // Reset the blend mode
                 blend_for_layer[current_layer_id] = 'source-over';
+                alpha_for_layer[current_layer_id] = 1.0;
 
                 // Remove everything from the canvas that was on this layer
                 for (let index=1; index<replay.length; ++index) {
@@ -613,11 +708,16 @@ let flo_canvas = (function() {
                 blend_for_layer[layer_id] = blend_mode;
             },
 
+            layer_alpha: (layer_id, alpha) => {
+                alpha_for_layer[layer_id] = alpha;
+            },
+
             clear_canvas: () => {
                 // Clear layers
                 layer_canvases      = null;
                 context             = canvas.getContext('2d');
                 blend_for_layer     = {};
+                alpha_for_layer     = {};
                 current_layer_id    = 0;
                 render              = layer_renderer;
 
@@ -637,6 +737,7 @@ let flo_canvas = (function() {
                 fill_color(0,0,0,1);
                 stroke_color(0,0,0,1);
                 line_width(1.0);
+                gradient_stops = [];
             },
 
             sprite: (sprite_id) => {
@@ -763,9 +864,14 @@ let flo_canvas = (function() {
 
                 // Draw each of the layers
                 Object.keys(layer_canvases).forEach(layer_id => {
-                    layer_context.globalCompositeOperation = blend_for_layer[layer_id] || 'source-over';
+                    let alpha = alpha_for_layer[layer_id];
+
+                    layer_context.globalCompositeOperation  = blend_for_layer[layer_id] || 'source-over';
+                    layer_context.globalAlpha               = alpha === undefined ? 1.0 : alpha;
                     layer_context.drawImage(layer_canvases[layer_id], 0,0, width,height);
                 });
+
+                layer_context.globalAlpha = 1.0;
             }
         }
 
@@ -787,6 +893,11 @@ let flo_canvas = (function() {
             dash_offset:                    (offset)                    => { current_sprite.push([dash_offset, [offset]]); },
             fill_color:                     (r, g, b, a)                => { current_sprite.push([fill_color, [r, g, b, a]]); },
             stroke_color:                   (r, g, b, a)                => { current_sprite.push([stroke_color, [r, g, b, a]]); },
+            new_gradient:                   ()                          => { current_sprite.push([new_gradient, []]); },
+            gradient_stop:                  (pos, r, g, b, a)           => { current_sprite.push([gradient_stop, [pos, r, g, b, a]]); },
+            fill_linear_gradient:           (x1, y1, x2, y2)            => { current_sprite.push([fill_linear_gradient, [x1, y1, x2, y2]]); },
+            fill_radial_gradient:           (x, y, radius)              => { current_sprite.push([fill_radial_gradient, [x, y, radius]]); },
+            fill_texture:                   (pattern, fg, bg, size)     => { current_sprite.push([fill_texture, [pattern, fg, bg, size]]); },
             blend_mode:                     (mode)                      => { current_sprite.push([blend_mode, [mode]]); },
             identity_transform:             ()                          => { current_sprite.push([identity_transform, []]); },
             canvas_height:                  (height)                    => { current_sprite.push([canvas_height, [height]]); },
@@ -800,6 +911,7 @@ let flo_canvas = (function() {
             push_state:                     ()                          => { current_sprite.push([push_state, []]); },
             pop_state:                      ()                          => { current_sprite.push([pop_state, []]); },
             layer_blend:                    (blend_mode)                => { current_sprite.push([layer_blend, [blend_mode]]); },
+            layer_alpha:                    (layer_id, alpha)           => { current_sprite.push([layer_alpha, [layer_id, alpha]]); },
             clear_layer:                    ()                          => { current_sprite.push([clear_layer, []]); },
             clear_canvas:                   ()                          => { current_sprite.push([clear_canvas, []]); },
             draw_sprite:                    (sprite_id)                 => { current_sprite.push([draw_sprite, [sprite_id]]); },
@@ -834,6 +946,11 @@ let flo_canvas = (function() {
         function dash_offset(offset)                    { render.dash_offset(offset); }
         function fill_color(r, g, b, a)                 { render.fill_color(r, g, b, a); }
         function stroke_color(r, g, b, a)               { render.stroke_color(r, g, b, a); }
+        function new_gradient()                         { render.new_gradient(); }
+        function gradient_stop(pos, r, g, b, a)         { render.gradient_stop(pos, r, g, b, a); }
+        function fill_linear_gradient(x1, y1, x2, y2)   { render.fill_linear_gradient(x1, y1, x2, y2); }
+        function fill_radial_gradient(x, y, radius)     { render.fill_radial_gradient(x, y, radius); }
+        function fill_texture(pattern, fg, bg, size)    { render.fill_texture(pattern, fg, bg, size); }
         function blend_mode(mode)                       { render.blend_mode(mode); }
         function identity_transform()                   { render.identity_transform(); }
         function canvas_height(height)                  { render.canvas_height(height); }
@@ -848,6 +965,7 @@ let flo_canvas = (function() {
         function pop_state()                            { render.pop_state(); }
         function layer(layer_id)                        { render.layer(layer_id); }
         function layer_blend(blend_mode)                { render.layer_blend(blend_mode); }
+        function layer_alpha(layer_id, alpha)           { render.layer_alpha(layer_id, alpha); }
         function clear_layer()                          { render.clear_layer(); }
         function clear_canvas()                         { render.clear_canvas(); }
         function sprite(sprite_id)                      { render.sprite(sprite_id); }
@@ -879,6 +997,11 @@ let flo_canvas = (function() {
             dash_offset:        (offset)        => { replay.push([dash_offset, [offset], current_layer_id]);                render.dash_length(offset);            },
             fill_color:         (r, g, b, a)    => { replay.push([fill_color, [r, g, b, a], current_layer_id]);             render.fill_color(r, g, b, a);         },
             stroke_color:       (r, g, b, a)    => { replay.push([stroke_color, [r, g, b, a], current_layer_id]);           render.stroke_color(r, g, b, a);       },
+            new_gradient:       ()              => { replay.push([new_gradient, [], current_layer_id]);                     render.new_gradient();                 },
+            gradient_stop:      (pos, r, g, b, a) => { replay.push([gradient_stop, [pos, r, g, b, a], current_layer_id]);   render.gradient_stop(pos, r, g, b, a); },
+            fill_linear_gradient: (x1, y1, x2, y2) => { replay.push([fill_linear_gradient, [x1, y1, x2, y2], current_layer_id]); render.fill_linear_gradient(x1, y1, x2, y2); },
+            fill_radial_gradient: (x, y, radius) => { replay.push([fill_radial_gradient, [x, y, radius], current_layer_id]); render.fill_radial_gradient(x, y, radius); },
+            fill_texture:       (pattern, fg, bg, size) => { replay.push([fill_texture, [pattern, fg, bg, size], current_layer_id]); render.fill_texture(pattern, fg, bg, size); },
             blend_mode:         (mode)          => { replay.push([blend_mode, [mode], current_layer_id]);                   render.blend_mode(mode);               },
             identity_transform: ()              => { replay.push([identity_transform, [], current_layer_id]);               render.identity_transform();           },
             canvas_height:      (height)        => { replay.push([canvas_height, [height], current_layer_id]);              render.canvas_height(height);          },
@@ -893,6 +1016,7 @@ let flo_canvas = (function() {
             pop_state:          ()              => { replay.push([pop_state, [], current_layer_id]);                        render.pop_state();                    },
             layer:              (layer_id)      => { replay.push([layer, [layer_id], layer]);                               render.layer(layer_id);                },
             layer_blend:        (layer_id, blend_mode) => { replay.push([layer_blend, [layer_id, blend_mode], -1]);         render.layer_blend(layer_id, blend_mode); },
+            layer_alpha:        (layer_id, alpha)   => { replay.push([layer_alpha, [layer_id, alpha], -1]);                 render.layer_alpha(layer_id, alpha);   },
             clear_layer:        ()              => { replay.push([clear_layer, [], current_layer_id]);                      render.clear_layer();                  },
             clear_canvas:       ()              => { replay = [ [clear_canvas, [], current_layer_id] ];                     render.clear_canvas();                 },
             sprite:             (sprite_id)     => { replay = [ [sprite, [sprite_id], current_layer_id] ];                  render.sprite(sprite_id);              },
@@ -1059,6 +1183,7 @@ let flo_canvas = (function() {
                 case 'A':   draw.clear_canvas();    break;
                 case 'l':   draw.layer(read_u32()); break;
                 case 'b':   draw.layer_blend(read_u32(), decode_blend_mode()); break;
+                case 't':   draw.layer_alpha(read_u32(), read_float()); break;
                 case 'C':   draw.clear_layer();     break;
                 case 's':   draw.sprite(read_sprite_id()); break;
                 }
@@ -1069,15 +1194,49 @@ let flo_canvas = (function() {
             ///
             let decode_color = () => {
                 let color_target    = read_char();
-                let color           = read_rgba();
+                let color           = null;
 
                 switch (color_target) {
-                case 's':   draw.stroke_color(color[0], color[1], color[2], color[3]);  break;
-                case 'f':   draw.fill_color(color[0], color[1], color[2], color[3]);    break;
+                case 's':   color = read_rgba(); draw.stroke_color(color[0], color[1], color[2], color[3]);  break;
+                case 'f':   color = read_rgba(); draw.fill_color(color[0], color[1], color[2], color[3]);    break;
+                case 'l':   draw.fill_linear_gradient(read_float(), read_float(), read_float(), read_float()); break;
+                case 'r':   draw.fill_radial_gradient(read_float(), read_float(), read_float()); break;
+                case 't':
+                    {
+                        let pattern = null;
+                        switch (read_char()) {
+                        case 'S':   pattern = 'stripes';    break;
+                        case 'C':   pattern = 'checks';     break;
+                        case 'D':   pattern = 'dots';       break;
+                        }
+
+                        let fg      = read_rgba();
+                        let bg      = read_rgba();
+                        let size    = read_float();
+
+                        draw.fill_texture(pattern, fg, bg, size);
+                    }
+                    break;
                 default:    throw 'Unknown color target: \'' + color_target + '\'';
                 }
             };
 
+            ///
+            /// Decodes a gradient operation
+            ///
+            let decode_gradient = () => {
+                switch (read_char()) {
+                case 'n':   draw.new_gradient(); break;
+                case 's':
+                    {
+                        let pos     = read_float();
+                        let color   = read_rgba();
+                        draw.gradient_stop(pos, color[0], color[1], color[2], color[3]);
+                    }
+                    break;
+                }
+            };
+
             ///
             /// Decodes a line properties command
             ///
@@ -1202,6 +1361,7 @@ let flo_canvas = (function() {
                 case 'L':   decode_line();                              break;
                 case 'D':   decode_dash();                              break;
                 case 'C':   decode_color();                             break;
+                case 'G':   decode_gradient();                          break;
                 case 'M':   decode_blend_mode();                        break;
                 case 'T':   decode_transform();                         break;
                 case 'Z':   decode_clip();                              break;
@@ -27,8 +27,8 @@ function flowbetween(root_node) {
     // Promise that will resolve once the next update has completed
     let next_update_promise = Promise.resolve();
 
-    // URL where the flowbetween session resides
-    let target_url = '/flowbetween/session';
+    // URL where the flowbetween session resides (the query string is passed on, so '?mode=viewer' starts a read-only session)
+    let target_url = '/flowbetween/session' + window.location.search;
 
     // UTF encoder
     let utf8 = new TextEncoder('utf-8');
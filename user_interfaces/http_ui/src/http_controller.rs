@@ -1,5 +1,7 @@
 use ui::*;
 
+use std::collections::HashMap;
+
 ///
 /// Trait implemented by controllers types that can be used as the target of a HTTP
 /// session
@@ -9,4 +11,27 @@ pub trait HttpController : Controller {
     /// Creates a new instance of this controller
     ///
     fn start_new() -> Self;
+
+    ///
+    /// Creates a new instance of this controller for a user who has logged in
+    ///
+    /// By default, every user gets the same controller as an anonymous session
+    ///
+    fn start_for_user(_user: &str) -> Self
+    where Self: Sized {
+        Self::start_new()
+    }
+
+    ///
+    /// Creates a new instance of this controller using the query parameters from the URL that started the session
+    ///
+    /// By default, the parameters are ignored
+    ///
+    fn start_with_parameters(user: Option<&str>, _parameters: &HashMap<String, String>) -> Self
+    where Self: Sized {
+        match user {
+            Some(user)  => Self::start_for_user(user),
+            None        => Self::start_new()
+        }
+    }
 }
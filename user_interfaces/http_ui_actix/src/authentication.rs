@@ -0,0 +1,170 @@
+use super::authenticator::*;
+
+use actix_web::*;
+use uuid::*;
+
+use std::sync::*;
+use std::time::{Duration, Instant};
+use std::collections::HashMap;
+
+/// The name of the cookie used to store the login token
+pub const LOGIN_COOKIE: &str = "flo_login";
+
+/// How long a login token lasts after it was last used
+pub const DEFAULT_LOGIN_EXPIRY: Duration = Duration::from_secs(12 * 60 * 60);
+
+///
+/// A user that has logged in
+///
+struct Login {
+    /// The user that the login token belongs to
+    user: String,
+
+    /// When the login token was last used
+    last_used: Instant
+}
+
+///
+/// The users that are logged in and the sessions that they own
+///
+struct AuthenticationCore {
+    /// The user for each login token
+    logins: HashMap<String, Login>,
+
+    /// How long a login token can go unused before it expires
+    login_expiry: Duration,
+
+    /// The user that created each session
+    session_owners: HashMap<String, String>
+}
+
+///
+/// Tracks which users are logged in to the server and which sessions they're allowed to use
+///
+/// Authentication is enabled by adding an `Arc<Authentication>` to the actix app data: if this is not present, every
+/// request is allowed to create and use sessions.
+///
+pub struct Authentication {
+    /// Checks the credentials of users logging in
+    authenticator: Box<dyn Authenticator>,
+
+    /// The logins and sessions
+    core: Mutex<AuthenticationCore>
+}
+
+impl Authentication {
+    ///
+    /// Creates a new authentication object that uses the specified authenticator
+    ///
+    pub fn new<Auth: 'static+Authenticator>(authenticator: Auth) -> Authentication {
+        Authentication {
+            authenticator:  Box::new(authenticator),
+            core:           Mutex::new(AuthenticationCore {
+                logins:         HashMap::new(),
+                login_expiry:   DEFAULT_LOGIN_EXPIRY,
+                session_owners: HashMap::new()
+            })
+        }
+    }
+
+    ///
+    /// Changes how long a login token can go unused before the user has to log in again
+    ///
+    pub fn with_login_expiry(self, login_expiry: Duration) -> Authentication {
+        self.core.lock().unwrap().login_expiry = login_expiry;
+        self
+    }
+
+    ///
+    /// Logs in using a set of credentials, returning the login token if they're valid
+    ///
+    pub fn log_in(&self, credentials: &Credentials) -> Option<String> {
+        let user = self.authenticator.authenticate(credentials)?;
+
+        // Forget any logins that have expired
+        let mut core        = self.core.lock().unwrap();
+        let login_expiry    = core.login_expiry;
+        core.logins.retain(|_, login| login.last_used.elapsed() < login_expiry);
+
+        // Generate a new login token for this user
+        let login_token = Uuid::new_v4().to_simple().to_string();
+        core.logins.insert(login_token.clone(), Login { user: user, last_used: Instant::now() });
+
+        Some(login_token)
+    }
+
+    ///
+    /// Removes a login token
+    ///
+    pub fn log_out(&self, login_token: &str) {
+        self.core.lock().unwrap().logins.remove(login_token);
+    }
+
+    ///
+    /// Returns the user that a login token belongs to (None if the token is unknown or has expired)
+    ///
+    /// Using a token resets the time before it expires.
+    ///
+    pub fn user_for_token(&self, login_token: &str) -> Option<String> {
+        let mut core        = self.core.lock().unwrap();
+        let login_expiry    = core.login_expiry;
+
+        let user = core.logins.get_mut(login_token)
+            .and_then(|login| {
+                if login.last_used.elapsed() < login_expiry {
+                    login.last_used = Instant::now();
+                    Some(login.user.clone())
+                } else {
+                    None
+                }
+            });
+
+        // Expired tokens can't be used again
+        if user.is_none() {
+            core.logins.remove(login_token);
+        }
+
+        user
+    }
+
+    ///
+    /// Returns the user that made a request, if they've logged in
+    ///
+    pub fn user_for_request(&self, req: &HttpRequest) -> Option<String> {
+        req.cookie(LOGIN_COOKIE)
+            .and_then(|cookie| self.user_for_token(cookie.value()))
+    }
+
+    ///
+    /// Records the user that created a session
+    ///
+    pub fn set_session_owner(&self, session_id: &str, user: &str) {
+        self.core.lock().unwrap().session_owners.insert(session_id.to_string(), user.to_string());
+    }
+
+    ///
+    /// Forgets the owner of a session (once the session has finished)
+    ///
+    pub fn remove_session(&self, session_id: &str) {
+        self.core.lock().unwrap().session_owners.remove(session_id);
+    }
+
+    ///
+    /// Returns true if a user is allowed to use a particular session
+    ///
+    pub fn can_access_session(&self, user: &str, session_id: &str) -> bool {
+        self.core.lock().unwrap().session_owners.get(session_id).map(|owner| owner == user).unwrap_or(false)
+    }
+}
+
+///
+/// Returns true if a request is allowed to use the session with the specified ID
+///
+pub (crate) fn request_can_access_session(req: &HttpRequest, session_id: &str) -> bool {
+    match req.app_data::<Arc<Authentication>>() {
+        None                    => true,
+        Some(authentication)    => authentication.user_for_request(req)
+            .map(|user| authentication.can_access_session(&user, session_id))
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,206 @@
+use std::io;
+use std::fs;
+use std::path::{Path};
+use std::collections::HashMap;
+
+///
+/// The credentials supplied by someone trying to log in
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum Credentials {
+    /// A user name and password
+    Password(String, String),
+
+    /// An access token
+    Token(String)
+}
+
+///
+/// Trait implemented by objects that can decide whether or not a set of credentials are valid
+///
+pub trait Authenticator : Send+Sync {
+    ///
+    /// Checks a set of credentials, returning the name of the user they belong to if they're valid
+    ///
+    fn authenticate(&self, credentials: &Credentials) -> Option<String>;
+}
+
+///
+/// Returns true if a string is valid as a user name
+///
+/// User names are used to pick where the files for each user are stored, so they're restricted to letters,
+/// numbers, '-' and '_'.
+///
+pub fn is_valid_user_name(name: &str) -> bool {
+    name.len() > 0 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+///
+/// Compares two strings in a way that takes the same time wherever they differ
+///
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+
+    if a.len() != b.len() {
+        false
+    } else {
+        a.iter().zip(b.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+}
+
+///
+/// Authenticates users against the contents of a password file
+///
+/// Each line of the file is of the form `user:password`. Blank lines and lines starting with `#` are ignored.
+/// The passwords are stored as-is, so the file should only be readable by the user running the server.
+///
+pub struct PasswordFile {
+    /// The password for each user
+    passwords: HashMap<String, String>
+}
+
+impl PasswordFile {
+    ///
+    /// Reads a password file from disk
+    ///
+    pub fn open(path: &Path) -> io::Result<PasswordFile> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    ///
+    /// Parses the contents of a password file
+    ///
+    pub fn parse(contents: &str) -> io::Result<PasswordFile> {
+        let mut passwords = HashMap::new();
+
+        for (line_num, line) in contents.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+
+            // Skip blank lines and comments
+            if line.trim().len() == 0 || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            // Split into user and password
+            let separator = line.find(':')
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("Line {}: expected 'user:password'", line_num+1)))?;
+
+            let user        = line[0..separator].trim();
+            let password    = &line[(separator+1)..];
+
+            if !is_valid_user_name(user) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Line {}: user names can only contain letters, numbers, '-' and '_'", line_num+1)));
+            }
+
+            if password.len() == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Line {}: the password for '{}' is empty", line_num+1, user)));
+            }
+
+            passwords.insert(user.to_string(), password.to_string());
+        }
+
+        Ok(PasswordFile {
+            passwords: passwords
+        })
+    }
+}
+
+impl Authenticator for PasswordFile {
+    fn authenticate(&self, credentials: &Credentials) -> Option<String> {
+        match credentials {
+            Credentials::Password(user, password)   => {
+                self.passwords.get(user)
+                    .and_then(|expected| if constant_time_eq(expected, password) { Some(user.clone()) } else { None })
+            }
+
+            Credentials::Token(_)                   => None
+        }
+    }
+}
+
+///
+/// Authenticates a single user using an access token
+///
+pub struct AccessToken {
+    /// The user that the token logs in as
+    user: String,
+
+    /// The token
+    token: String
+}
+
+impl AccessToken {
+    ///
+    /// Creates an authenticator where a particular token logs in as the specified user
+    ///
+    /// The user name must be valid and the token can't be empty (as an empty token would let anyone log in)
+    ///
+    pub fn new(user: &str, token: &str) -> io::Result<AccessToken> {
+        if !is_valid_user_name(user) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("'{}' is not a valid user name", user)));
+        }
+
+        if token.len() == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "The access token is empty"));
+        }
+
+        Ok(AccessToken {
+            user:   user.to_string(),
+            token:  token.to_string()
+        })
+    }
+}
+
+impl Authenticator for AccessToken {
+    fn authenticate(&self, credentials: &Credentials) -> Option<String> {
+        match credentials {
+            Credentials::Token(token)   => if constant_time_eq(&self.token, token) { Some(self.user.clone()) } else { None },
+            Credentials::Password(_, _) => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn password_file_accepts_correct_password() {
+        let passwords = PasswordFile::parse("# Users\nalice:secret\n\nbob:pass:word\n").unwrap();
+
+        assert!(passwords.authenticate(&Credentials::Password("alice".to_string(), "secret".to_string())) == Some("alice".to_string()));
+        assert!(passwords.authenticate(&Credentials::Password("bob".to_string(), "pass:word".to_string())) == Some("bob".to_string()));
+    }
+
+    #[test]
+    fn password_file_rejects_incorrect_password() {
+        let passwords = PasswordFile::parse("alice:secret\n").unwrap();
+
+        assert!(passwords.authenticate(&Credentials::Password("alice".to_string(), "secreT".to_string())) == None);
+        assert!(passwords.authenticate(&Credentials::Password("bob".to_string(), "secret".to_string())) == None);
+        assert!(passwords.authenticate(&Credentials::Token("secret".to_string())) == None);
+    }
+
+    #[test]
+    fn password_file_rejects_invalid_user_names() {
+        assert!(PasswordFile::parse("../alice:secret\n").is_err());
+        assert!(PasswordFile::parse(":secret\n").is_err());
+        assert!(PasswordFile::parse("alice\n").is_err());
+    }
+
+    #[test]
+    fn access_token_accepts_token() {
+        let token = AccessToken::new("default", "abc123").unwrap();
+
+        assert!(token.authenticate(&Credentials::Token("abc123".to_string())) == Some("default".to_string()));
+        assert!(token.authenticate(&Credentials::Token("abc124".to_string())) == None);
+        assert!(token.authenticate(&Credentials::Password("default".to_string(), "abc123".to_string())) == None);
+    }
+
+    #[test]
+    fn access_token_must_not_be_empty() {
+        assert!(AccessToken::new("default", "").is_err());
+        assert!(AccessToken::new("../default", "abc123").is_err());
+    }
+}
@@ -14,15 +14,22 @@ extern crate bytes;
 #[macro_use] extern crate lazy_static;
 extern crate serde_json;
 extern crate percent_encoding;
+extern crate uuid;
 
 mod actix_session;
 mod session_handler;
 mod session_websocket_handler;
 mod session_resource_handler;
 mod static_file_handler;
+mod authenticator;
+mod authentication;
+mod login_handler;
 
 pub use self::actix_session::*;
 pub use self::session_handler::*;
 pub use self::session_websocket_handler::*;
 pub use self::session_resource_handler::*;
 pub use self::static_file_handler::*;
+pub use self::authenticator::*;
+pub use self::authentication::*;
+pub use self::login_handler::*;
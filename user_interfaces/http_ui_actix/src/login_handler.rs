@@ -0,0 +1,154 @@
+use super::authenticator::*;
+use super::authentication::*;
+
+use actix_web::*;
+use actix_web::web::Form;
+use actix_web::cookie::{Cookie, SameSite};
+use futures::future;
+use futures::future::{LocalBoxFuture};
+
+use std::sync::*;
+use std::collections::HashMap;
+
+/// Where the browser is sent after logging in or out
+const HOME_PATH: &str = "/";
+
+///
+/// Generates the login page, with a message to display above the login forms
+///
+fn login_page(message: &str) -> String {
+    format!(r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <title>FlowBetween</title>
+</head>
+<body>
+    <p>{}</p>
+    <form method="post">
+        <p><label>User <input type="text" name="user" autofocus></label></p>
+        <p><label>Password <input type="password" name="password"></label></p>
+        <p><input type="submit" value="Log in"></p>
+    </form>
+    <form method="post">
+        <p><label>Access token <input type="password" name="token"></label></p>
+        <p><input type="submit" value="Log in with token"></p>
+    </form>
+</body>
+</html>
+"#, message)
+}
+
+///
+/// Reads the credentials from a login form
+///
+fn credentials_from_form(form: &HashMap<String, String>) -> Option<Credentials> {
+    if let Some(token) = form.get("token").filter(|token| token.len() > 0) {
+        Some(Credentials::Token(token.clone()))
+    } else if let (Some(user), Some(password)) = (form.get("user"), form.get("password")) {
+        Some(Credentials::Password(user.clone(), password.clone()))
+    } else {
+        None
+    }
+}
+
+///
+/// Get request handler for the login page
+///
+pub fn login_get_handler(req: HttpRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
+    if req.app_data::<Arc<Authentication>>().is_none() {
+        // Nothing to log in to if authentication is turned off
+        return Box::pin(future::ok(HttpResponse::NotFound().body("Not found")));
+    }
+
+    let page = HttpResponse::Ok()
+        .header(http::header::CONTENT_TYPE, "text/html; charset=utf-8")
+        .body(login_page("Please log in to FlowBetween"));
+
+    Box::pin(future::ok(page))
+}
+
+///
+/// Post request handler for the login page
+///
+/// The form should contain either `user` and `password` fields or a `token` field. A successful login sets the login
+/// cookie and redirects to the main page.
+///
+pub fn login_post_handler(req: HttpRequest, form: Form<HashMap<String, String>>) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
+    let authentication = match req.app_data::<Arc<Authentication>>() {
+        Some(authentication)    => Arc::clone(authentication),
+        None                    => { return Box::pin(future::ok(HttpResponse::NotFound().body("Not found"))); }
+    };
+
+    // Try to log in
+    let login_token = credentials_from_form(&*form)
+        .and_then(|credentials| authentication.log_in(&credentials));
+
+    let response = if let Some(login_token) = login_token {
+        // Store the login token in a cookie and go to the main page
+        let cookie = Cookie::build(LOGIN_COOKIE, login_token)
+            .path("/")
+            .http_only(true)
+            .same_site(SameSite::Strict)
+            .finish();
+
+        HttpResponse::SeeOther()
+            .header(http::header::LOCATION, HOME_PATH)
+            .cookie(cookie)
+            .finish()
+    } else {
+        // Show the login page again
+        HttpResponse::Unauthorized()
+            .header(http::header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(login_page("The login details were not recognised"))
+    };
+
+    Box::pin(future::ok(response))
+}
+
+///
+/// Post request handler that logs out the current user
+///
+pub fn logout_handler(req: HttpRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
+    let mut response = HttpResponse::SeeOther();
+    response.header(http::header::LOCATION, HOME_PATH);
+
+    if let (Some(authentication), Some(cookie)) = (req.app_data::<Arc<Authentication>>(), req.cookie(LOGIN_COOKIE)) {
+        // Forget the login
+        authentication.log_out(cookie.value());
+
+        // Remove the cookie from the browser
+        let mut removed_cookie = Cookie::named(LOGIN_COOKIE);
+        removed_cookie.set_path("/");
+        response.del_cookie(&removed_cookie);
+    }
+
+    Box::pin(future::ok(response.finish()))
+}
+
+///
+/// Wraps a request handler so that users that have not logged in are redirected to the login page
+///
+/// Requests are passed straight through to the handler if authentication is not enabled
+///
+pub fn login_required<Handler>(login_path: &str, handler: Handler) -> impl Fn(HttpRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>>
+where Handler: Fn(HttpRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
+    let login_path = login_path.to_string();
+
+    move |req: HttpRequest| {
+        let logged_in = match req.app_data::<Arc<Authentication>>() {
+            None                    => true,
+            Some(authentication)    => authentication.user_for_request(&req).is_some()
+        };
+
+        if logged_in {
+            handler(req)
+        } else {
+            let redirect = HttpResponse::SeeOther()
+                .header(http::header::LOCATION, login_path.as_str())
+                .finish();
+
+            Box::pin(future::ok(redirect))
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::actix_session::*;
+use super::authentication::*;
 use super::session_resource_handler::*;
 
 use flo_http_ui::*;
@@ -7,11 +8,13 @@ use actix_web::*;
 use actix_web::web::Json;
 use actix_web::Error;
 use futures::*;
+use futures::future;
 use futures::stream;
 use futures::future::{BoxFuture, LocalBoxFuture};
 use futures::executor::{ThreadPool};
 
 use std::sync::*;
+use std::collections::HashMap;
 
 lazy_static! {
     /// The thread pool where the sessions will run
@@ -54,7 +57,7 @@ fn handle_with_session<Session: ActixSession>(session: &mut HttpSession<Session:
 ///
 /// (This creates a new session for this user)
 ///
-fn handle_no_session<Session: ActixSession>(session: Arc<Session>, base_url: String, ui_request: &UiHandlerRequest) -> impl Future<Output=Result<UiHandlerResponse, Error>> {
+fn handle_no_session<Session: ActixSession>(session: Arc<Session>, base_url: String, authentication: Option<(Arc<Authentication>, String)>, parameters: HashMap<String, String>, ui_request: &UiHandlerRequest) -> impl Future<Output=Result<UiHandlerResponse, Error>> {
     // Convert the events into an iterator
     let ui_request  = ui_request.clone();
     let events      = stream::iter::<_>(ui_request.events.into_iter());
@@ -67,9 +70,21 @@ fn handle_no_session<Session: ActixSession>(session: Arc<Session>, base_url: Str
                     let mut updates = vec![];
 
                     // Start a new session
-                    let session_controller      = Session::Controller::start_new();
+                    let user                    = authentication.as_ref().map(|(_, user)| user.as_str());
+                    let session_controller      = Session::Controller::start_with_parameters(user, &parameters);
                     let (session_id, run_loop)  = session.new_session(session_controller, &base_url);
 
+                    // Only the user that created the session can use it (until the session finishes)
+                    let run_loop                = if let Some((authentication, user)) = &authentication {
+                        authentication.set_session_owner(&session_id, user);
+
+                        let authentication  = Arc::clone(authentication);
+                        let session_id      = session_id.clone();
+                        run_loop.map(move |_| authentication.remove_session(&session_id)).boxed()
+                    } else {
+                        run_loop.boxed()
+                    };
+
                     // Spawn the session run loop
                     THREAD_POOL.spawn_ok(run_loop);
 
@@ -103,38 +118,56 @@ fn handle_no_session<Session: ActixSession>(session: Arc<Session>, base_url: Str
 ///
 /// Handles a JSON UI request
 ///
-fn handle_ui_request<Session: ActixSession+Sync+Send+'static>(req: HttpRequest, ui_request: &UiHandlerRequest) -> impl Future<Output=Result<HttpResponse, Error>> {
+fn handle_ui_request<Session: ActixSession+Sync+Send+'static>(req: HttpRequest, ui_request: &UiHandlerRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
     let session_state = req.app_data::<Arc<Session>>().expect("Flowbetween session state");
 
     let session_state = Arc::clone(&session_state);
 
+    // If authentication is turned on, the user must be logged in
+    let authentication = match req.app_data::<Arc<Authentication>>() {
+        None                    => None,
+        Some(authentication)    => match authentication.user_for_request(&req) {
+            Some(user)  => Some((Arc::clone(authentication), user)),
+            None        => { return Box::pin(future::ok(HttpResponse::Unauthorized().body("Not logged in"))); }
+        }
+    };
+
     // Fetch the session ID from the request
     let session_id  = ui_request.session_id.clone();
 
+    // The query parameters are passed to any new session that's created
+    let parameters  = web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .map(|parameters| parameters.into_inner())
+        .unwrap_or_else(|_| HashMap::new());
+
     // Generate the response
     let response: BoxFuture<'static, Result<UiHandlerResponse, Error>> = match session_id {
-        None                => Box::pin(handle_no_session(session_state, base_url(&req), ui_request)),
+        None                => Box::pin(handle_no_session(session_state, base_url(&req), authentication, parameters, ui_request)),
         Some(session_id)    => {
-            // Try to fetch the session corresponding to this ID
-            let session = session_state.get_session(&session_id);
+            // Try to fetch the session corresponding to this ID (sessions that belong to other users are treated as missing)
+            let session = if request_can_access_session(&req, &session_id) {
+                session_state.get_session(&session_id)
+            } else {
+                None
+            };
 
             // Send the events to the appropriate session if we find one
             match session {
                 Some(session)   => Box::pin(handle_with_session::<Session>(&mut *session.lock().unwrap(), ui_request)),
-                None            => Box::pin(handle_no_session(session_state, base_url(&req), ui_request))
+                None            => Box::pin(handle_no_session(session_state, base_url(&req), authentication, parameters, ui_request))
             }
         }
     };
 
     // Turn the UI response into a JSON response
-    response
+    Box::pin(response
         .map(move |response| {
             response.map(|response| {
                 HttpResponse::Ok()
                     .header(http::header::CONTENT_TYPE, "application/json; charset=utf-8")
                     .json(response)
                 })
-        })
+        }))
 }
 
 ///
@@ -142,7 +175,7 @@ fn handle_ui_request<Session: ActixSession+Sync+Send+'static>(req: HttpRequest,
 ///
 pub fn session_post_handler<Session: 'static+Send+Sync+ActixSession>(req: HttpRequest, ui_request: Json<UiHandlerRequest>) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
     // Process this UI request
-    Box::pin(handle_ui_request::<Session>(req, &*ui_request))
+    handle_ui_request::<Session>(req, &*ui_request)
 }
 
 ///
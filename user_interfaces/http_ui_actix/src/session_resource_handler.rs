@@ -1,4 +1,5 @@
 use super::actix_session::*;
+use super::authentication::*;
 
 use flo_ui::*;
 use flo_ui::session::*;
@@ -233,8 +234,12 @@ pub fn session_resource_handler<Session: 'static+ActixSession>(req: HttpRequest)
         let resource = decode_url(&path);
 
         if let Some(resource) = resource {
-            // Got a valid resource
-            let session = state.get_session(&resource.session_id);
+            // Got a valid resource (sessions that belong to other users are treated as missing)
+            let session = if request_can_access_session(&req, &resource.session_id) {
+                state.get_session(&resource.session_id)
+            } else {
+                None
+            };
 
             if let Some(session) = session {
                 // URL is in a valid format and the session could be found
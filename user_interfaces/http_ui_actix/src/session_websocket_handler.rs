@@ -1,4 +1,5 @@
 use super::actix_session::*;
+use super::authentication::*;
 
 use flo_http_ui::*;
 use flo_stream::*;
@@ -114,9 +115,13 @@ pub fn session_websocket_handler<Session: 'static+ActixSession>(req: HttpRequest
             tail.to_string()
         };
 
-        // Look up the session
+        // Look up the session (sessions that belong to other users are treated as missing)
         let session_state   = req.app_data::<Arc<Session>>().cloned().expect("Flowbetween session state");
-        let session         = session_state.get_session(&session_id).clone();
+        let session         = if request_can_access_session(&req, &session_id) {
+            session_state.get_session(&session_id).clone()
+        } else {
+            None
+        };
 
         Box::pin(async move {
             if let Some(session) = session {
@@ -0,0 +1,206 @@
+use flo_http_ui::*;
+use flo_http_ui_actix::*;
+
+use actix_web::*;
+use actix_web::test::{TestServer};
+use actix_web::cookie::{Cookie};
+use actix_web::http::{StatusCode};
+
+use std::sync::*;
+use std::thread;
+use std::time::{Duration};
+
+///
+/// Starts a test server with the login and session handlers (and authentication turned on if an authenticator is supplied)
+///
+fn start_server(authentication: Option<Arc<Authentication>>) -> TestServer {
+    let sessions: Arc<WebSessions<NullSession>> = Arc::new(WebSessions::new());
+
+    test::start(move || {
+        let app = App::new()
+            .app_data(sessions.clone());
+        let app = match &authentication {
+            Some(authentication)    => app.app_data(authentication.clone()),
+            None                    => app
+        };
+
+        app
+            .service(web::resource("/login")
+                .route(web::get().to(login_get_handler))
+                .route(web::post().to(login_post_handler)))
+            .service(web::resource("/logout").route(web::post().to(logout_handler)))
+            .service(web::resource("/session")
+                .route(web::get().to(session_get_handler::<WebSessions<NullSession>>))
+                .route(web::post().to(session_post_handler::<WebSessions<NullSession>>)))
+            .service(web::resource("/session/{tail:.*}")
+                .route(web::get().to(session_get_handler::<WebSessions<NullSession>>))
+                .route(web::post().to(session_post_handler::<WebSessions<NullSession>>)))
+    })
+}
+
+///
+/// Creates the authentication object used for the tests (two users: alice and bob)
+///
+fn test_authentication() -> Option<Arc<Authentication>> {
+    let passwords = PasswordFile::parse("alice:alice-password\nbob:bob-password\n").unwrap();
+    Some(Arc::new(Authentication::new(passwords)))
+}
+
+///
+/// Logs in to the test server, returning the login cookie
+///
+async fn log_in(server: &TestServer, user: &str, password: &str) -> Cookie<'static> {
+    let response = server.post("/login")
+        .send_form(&[("user", user), ("password", password)])
+        .await
+        .unwrap();
+
+    assert!(response.status() == StatusCode::SEE_OTHER);
+
+    response.cookie(LOGIN_COOKIE).expect("Login cookie")
+}
+
+///
+/// Sends a UI request to the test server
+///
+async fn send_ui_request(server: &TestServer, login: Option<&Cookie<'static>>, session_id: Option<String>, events: Vec<Event>) -> (StatusCode, Option<UiHandlerResponse>) {
+    let request     = UiHandlerRequest { session_id: session_id, events: events };
+    let mut post    = server.post("/session");
+
+    if let Some(login) = login {
+        post = post.cookie(login.clone());
+    }
+
+    let mut response    = post.send_json(&request).await.unwrap();
+    let status          = response.status();
+
+    if status == StatusCode::OK {
+        (status, Some(response.json::<UiHandlerResponse>().await.unwrap()))
+    } else {
+        (status, None)
+    }
+}
+
+///
+/// Creates a new session, returning the session ID
+///
+async fn new_session(server: &TestServer, login: Option<&Cookie<'static>>) -> String {
+    let (status, response) = send_ui_request(server, login, None, vec![Event::NewSession]).await;
+
+    assert!(status == StatusCode::OK);
+
+    response.unwrap().updates.into_iter()
+        .filter_map(|update| match update {
+            Update::NewSession(session_id)  => Some(session_id),
+            _                               => None
+        })
+        .nth(0)
+        .expect("New session ID")
+}
+
+#[actix_rt::test]
+async fn can_create_session_without_authentication() {
+    let server = start_server(None);
+
+    new_session(&server, None).await;
+}
+
+#[actix_rt::test]
+async fn login_page_is_missing_without_authentication() {
+    let server      = start_server(None);
+    let response    = server.get("/login").send().await.unwrap();
+
+    assert!(response.status() == StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn cannot_create_session_without_logging_in() {
+    let server      = start_server(test_authentication());
+    let (status, _) = send_ui_request(&server, None, None, vec![Event::NewSession]).await;
+
+    assert!(status == StatusCode::UNAUTHORIZED);
+}
+
+#[actix_rt::test]
+async fn incorrect_password_is_rejected() {
+    let server      = start_server(test_authentication());
+    let response    = server.post("/login")
+        .send_form(&[("user", "alice"), ("password", "bob-password")])
+        .await
+        .unwrap();
+
+    assert!(response.status() == StatusCode::UNAUTHORIZED);
+    assert!(response.cookie(LOGIN_COOKIE).is_none());
+}
+
+#[actix_rt::test]
+async fn can_create_session_after_logging_in() {
+    let server  = start_server(test_authentication());
+    let login   = log_in(&server, "alice", "alice-password").await;
+
+    new_session(&server, Some(&login)).await;
+}
+
+#[actix_rt::test]
+async fn can_log_in_with_token() {
+    let server      = start_server(Some(Arc::new(Authentication::new(AccessToken::new("default", "some-token").unwrap()))));
+    let response    = server.post("/login")
+        .send_form(&[("token", "some-token")])
+        .await
+        .unwrap();
+
+    assert!(response.status() == StatusCode::SEE_OTHER);
+
+    let login = response.cookie(LOGIN_COOKIE).expect("Login cookie");
+    new_session(&server, Some(&login)).await;
+}
+
+#[actix_rt::test]
+async fn cannot_use_another_users_session() {
+    let server      = start_server(test_authentication());
+    let alice       = log_in(&server, "alice", "alice-password").await;
+    let bob         = log_in(&server, "bob", "bob-password").await;
+
+    let session_id  = new_session(&server, Some(&alice)).await;
+
+    // Bob should see alice's session as missing
+    let (status, response) = send_ui_request(&server, Some(&bob), Some(session_id.clone()), vec![Event::Tick]).await;
+
+    assert!(status == StatusCode::OK);
+    assert!(response.unwrap().updates == vec![Update::MissingSession]);
+
+    // Alice's resources should not be available to bob either
+    let resource = server.get(&format!("/session/{}/i/flo", session_id))
+        .cookie(bob.clone())
+        .send()
+        .await
+        .unwrap();
+
+    assert!(resource.status() == StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn logging_out_forgets_login() {
+    let server      = start_server(test_authentication());
+    let alice       = log_in(&server, "alice", "alice-password").await;
+
+    let response    = server.post("/logout").cookie(alice.clone()).send().await.unwrap();
+    assert!(response.status() == StatusCode::SEE_OTHER);
+
+    let (status, _) = send_ui_request(&server, Some(&alice), None, vec![Event::NewSession]).await;
+    assert!(status == StatusCode::UNAUTHORIZED);
+}
+
+#[actix_rt::test]
+async fn login_expires_when_unused() {
+    let passwords   = PasswordFile::parse("alice:alice-password\n").unwrap();
+    let server      = start_server(Some(Arc::new(Authentication::new(passwords).with_login_expiry(Duration::from_millis(200)))));
+    let alice       = log_in(&server, "alice", "alice-password").await;
+
+    new_session(&server, Some(&alice)).await;
+
+    thread::sleep(Duration::from_millis(400));
+
+    let (status, _) = send_ui_request(&server, Some(&alice), None, vec![Event::NewSession]).await;
+    assert!(status == StatusCode::UNAUTHORIZED);
+}
@@ -34,6 +34,7 @@ struct AppPainting {
     double pressure;
     double tilt_x;
     double tilt_y;
+    uint64_t modifier_flags;
 };
 
 typedef struct AppPainting AppPainting;
@@ -139,6 +140,8 @@ typedef struct AppPainting AppPainting;
 - (void) viewFinishedDrawing;
 - (void) viewSetTransform: (CGAffineTransform) transform;
 - (void) viewClearCanvas;
+- (void) viewSetLayerAlpha: (uint32_t) layer_id alpha: (double) alpha;
+- (void) viewSetLayerBlend: (uint32_t) layer_id blendMode: (int32_t) blendMode;
 
 @end
 
@@ -800,6 +800,22 @@ public class FloView : NSObject, FloViewDelegate {
         _drawingLayer?.clearBackingLayers()
     }
 
+    ///
+    /// Sets the alpha value used when compositing a layer in the canvas
+    ///
+    @objc public func viewSetLayerAlpha(_ layerId: UInt32, alpha: Double) {
+        _drawingLayer?.setLayerAlpha(id: layerId, alpha: CGFloat(alpha))
+    }
+
+    ///
+    /// Sets the blend mode used when compositing a layer in the canvas
+    ///
+    @objc public func viewSetLayerBlend(_ layerId: UInt32, blendMode: Int32) {
+        if let blendMode = CGBlendMode(rawValue: blendMode) {
+            _drawingLayer?.setLayerBlendMode(id: layerId, blendMode: blendMode)
+        }
+    }
+
     ///
     /// Sets the property that describes whether or not the popup for this view is open
     ///
@@ -21,6 +21,12 @@ class FloCanvasLayer : CALayer {
     /// Layers that we stopped using during the last clear command
     fileprivate var _unusedLayers: [CGLayer]
 
+    /// The alpha value to use when drawing each layer (layers not in this list are drawn opaque)
+    fileprivate var _layerAlpha: [UInt32: CGFloat]
+
+    /// The blend mode to use when drawing each layer (layers not in this list use the normal blend mode)
+    fileprivate var _layerBlendMode: [UInt32: CGBlendMode]
+
     /// Function called to trigger a redraw
     fileprivate var _triggerRedraw: ((NSSize, NSRect) -> ())?
 
@@ -40,6 +46,8 @@ class FloCanvasLayer : CALayer {
         _visibleRect    = NSRect(x: 0, y: 0, width: 1, height: 1)
         _backing        = [UInt32: CGLayer]()
         _unusedLayers   = []
+        _layerAlpha     = [UInt32: CGFloat]()
+        _layerBlendMode = [UInt32: CGBlendMode]()
 
         super.init()
     }
@@ -49,11 +57,15 @@ class FloCanvasLayer : CALayer {
         _visibleRect    = NSRect(x: 0, y: 0, width: 1, height: 1)
         _backing        = [UInt32: CGLayer]()
         _unusedLayers   = []
+        _layerAlpha     = [UInt32: CGFloat]()
+        _layerBlendMode = [UInt32: CGBlendMode]()
 
         super.init()
 
         if let layer = layer as? FloCanvasLayer {
             _backing            = layer._backing
+            _layerAlpha         = layer._layerAlpha
+            _layerBlendMode     = layer._layerBlendMode
             _canvasSize         = layer._canvasSize
             _visibleRect        = layer._visibleRect
             _resolution         = layer._resolution
@@ -65,6 +77,8 @@ class FloCanvasLayer : CALayer {
         _visibleRect    = NSRect(x: 0, y: 0, width: 1, height: 1)
         _backing        = [UInt32: CGLayer]()
         _unusedLayers   = []
+        _layerAlpha     = [UInt32: CGFloat]()
+        _layerBlendMode = [UInt32: CGBlendMode]()
 
         super.init(coder: aDecoder)
     }
@@ -110,7 +124,14 @@ class FloCanvasLayer : CALayer {
         }
 
         for layer_id in layer_ids {
+            // Composite each layer using its alpha value and blend mode
+            ctx.saveGState()
+            ctx.setAlpha(_layerAlpha[layer_id] ?? 1.0)
+            ctx.setBlendMode(_layerBlendMode[layer_id] ?? .normal)
+
             ctx.draw(_backing[layer_id]!, at: bounds.origin)
+
+            ctx.restoreGState()
         }
 
         ctx.restoreGState()
@@ -164,6 +185,24 @@ class FloCanvasLayer : CALayer {
 
         // Clear the bottom layer
         _backing[0]?.context?.clear(CGRect(size: self.bounds.size))
+
+        // Layers are composited normally until their properties are set again
+        _layerAlpha.removeAll()
+        _layerBlendMode.removeAll()
+    }
+
+    ///
+    /// Sets the alpha value to use when compositing a layer
+    ///
+    func setLayerAlpha(id: UInt32, alpha: CGFloat) {
+        _layerAlpha[id] = alpha
+    }
+
+    ///
+    /// Sets the blend mode to use when compositing a layer
+    ///
+    func setLayerBlendMode(id: UInt32, blendMode: CGBlendMode) {
+        _layerBlendMode[id] = blendMode
     }
 
     ///
@@ -217,6 +256,8 @@ class FloCanvasLayer : CALayer {
         // Both the backing and the unused layers become invalidated so we can't re-use them
         _backing        = [UInt32: CGLayer]()
         _unusedLayers   = []
+        _layerAlpha     = [UInt32: CGFloat]()
+        _layerBlendMode = [UInt32: CGBlendMode]()
         _clearCount     += 1
     }
 
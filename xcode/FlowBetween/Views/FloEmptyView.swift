@@ -305,7 +305,8 @@ class FloEmptyView : NSView, FloContainerView {
             position_y: Double(locationInCanvas.y),
             pressure:   Double(event.pressure),
             tilt_x:     0.0,
-            tilt_y:     0.0
+            tilt_y:     0.0,
+            modifier_flags: UInt64(event.modifierFlags.rawValue)
         )
     }
 